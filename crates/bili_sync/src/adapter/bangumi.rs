@@ -2,7 +2,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Utc};
 use futures::Stream;
 use sea_orm::prelude::*;
@@ -13,9 +13,55 @@ use tracing::info;
 use bili_sync_entity::VideoSourceTrait;
 use sea_orm::sea_query::SimpleExpr;
 
-use crate::adapter::VideoSource;
+use crate::adapter::{SubtaskToggles, VideoSource};
 use crate::bilibili::bangumi::Bangumi;
 use crate::bilibili::{BiliClient, VideoInfo};
+use crate::config::{DanmakuEmbedMode, NFOTimeType};
+use crate::utils::title_filter::TitleFilter;
+
+/// 番剧集数范围，如 "1-12,25"，两端均包含
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpisodeRange(Vec<(i32, i32)>);
+
+impl EpisodeRange {
+    pub fn parse(range: &str) -> Result<Self> {
+        let mut ranges = Vec::new();
+        for part in range.split([',', '，']).map(str::trim) {
+            if part.is_empty() {
+                continue;
+            }
+            let parse_number = |number: &str| -> Result<i32> {
+                number
+                    .trim()
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|number| *number > 0)
+                    .ok_or_else(|| anyhow!("「{}」不是有效的集数，集数应为正整数", number.trim()))
+            };
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (parse_number(start)?, parse_number(end)?),
+                None => {
+                    let number = parse_number(part)?;
+                    (number, number)
+                }
+            };
+            if start > end {
+                bail!("集数范围「{}」的起始集数大于结束集数", part);
+            }
+            ranges.push((start, end));
+        }
+        if ranges.is_empty() {
+            bail!("集数范围不能为空");
+        }
+        Ok(Self(ranges))
+    }
+
+    pub fn contains(&self, episode_number: i32) -> bool {
+        self.0
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&episode_number))
+    }
+}
 
 #[derive(Clone)]
 pub struct BangumiSource {
@@ -30,6 +76,22 @@ pub struct BangumiSource {
     pub page_name_template: Option<String>,
     pub selected_seasons: Option<Vec<String>>,
     pub scan_deleted_videos: bool,
+    pub pubtime_after: Option<String>,
+    pub pubtime_before: Option<String>,
+    pub title_include: Option<String>,
+    pub title_exclude: Option<String>,
+    pub title_regex: bool,
+    pub episode_range: Option<EpisodeRange>,
+    pub strm_mode: bool,
+    pub strm_refresh: bool,
+    pub strict_incremental: bool,
+    pub webdav_upload: bool,
+    pub audio_only: bool,
+    pub upgrade_quality: bool,
+    pub danmaku_embed_mode: DanmakuEmbedMode,
+    pub subtask_toggles: SubtaskToggles,
+    pub limit_newest: Option<usize>,
+    pub nfo_time_type: Option<NFOTimeType>,
 }
 
 impl BangumiSource {
@@ -477,6 +539,58 @@ impl VideoSource for BangumiSource {
         self.scan_deleted_videos
     }
 
+    fn pubtime_window(&self) -> (Option<&str>, Option<&str>) {
+        (self.pubtime_after.as_deref(), self.pubtime_before.as_deref())
+    }
+
+    fn title_filter(&self) -> Option<TitleFilter> {
+        TitleFilter::from_columns(
+            self.title_include.as_deref(),
+            self.title_exclude.as_deref(),
+            self.title_regex,
+        )
+    }
+
+    fn strm_mode(&self) -> bool {
+        self.strm_mode
+    }
+
+    fn strm_refresh(&self) -> bool {
+        self.strm_refresh
+    }
+
+    fn strict_incremental(&self) -> bool {
+        self.strict_incremental
+    }
+
+    fn webdav_upload(&self) -> bool {
+        self.webdav_upload
+    }
+
+    fn audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    fn upgrade_quality(&self) -> bool {
+        self.upgrade_quality
+    }
+
+    fn danmaku_embed_mode(&self) -> DanmakuEmbedMode {
+        self.danmaku_embed_mode
+    }
+
+    fn subtask_toggles(&self) -> SubtaskToggles {
+        self.subtask_toggles
+    }
+
+    fn limit_newest(&self) -> Option<usize> {
+        self.limit_newest
+    }
+
+    fn nfo_time_type(&self) -> Option<NFOTimeType> {
+        self.nfo_time_type.clone()
+    }
+
     fn source_type_display(&self) -> String {
         "番剧".to_string()
     }
@@ -485,3 +599,21 @@ impl VideoSource for BangumiSource {
         self.name.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_episode_range() {
+        let range = EpisodeRange::parse("1-12, 25").unwrap();
+        assert!(range.contains(1));
+        assert!(range.contains(12));
+        assert!(!range.contains(13));
+        assert!(range.contains(25));
+        assert!(EpisodeRange::parse("12-1").is_err());
+        assert!(EpisodeRange::parse("1-a").is_err());
+        assert!(EpisodeRange::parse("0").is_err());
+        assert!(EpisodeRange::parse(" , ").is_err());
+    }
+}
@@ -10,8 +10,12 @@ use sea_orm::sea_query::{OnConflict, SimpleExpr};
 use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseConnection, Unchanged};
 
-use crate::adapter::{VideoSource, VideoSourceEnum, _ActiveModel};
+use crate::adapter::{
+    _ActiveModel, limit_newest_from_column, nfo_time_type_from_column, SubtaskToggles, VideoSource, VideoSourceEnum,
+};
 use crate::bilibili::{BiliClient, Collection, CollectionItem, CollectionType, VideoInfo};
+use crate::config::{DanmakuEmbedMode, NFOTimeType};
+use crate::utils::title_filter::TitleFilter;
 
 impl VideoSource for collection::Model {
     fn filter_expr(&self) -> SimpleExpr {
@@ -85,6 +89,63 @@ impl VideoSource for collection::Model {
         self.scan_deleted_videos
     }
 
+    fn pubtime_window(&self) -> (Option<&str>, Option<&str>) {
+        (self.pubtime_after.as_deref(), self.pubtime_before.as_deref())
+    }
+
+    fn title_filter(&self) -> Option<TitleFilter> {
+        TitleFilter::from_columns(
+            self.title_include.as_deref(),
+            self.title_exclude.as_deref(),
+            self.title_regex,
+        )
+    }
+
+    fn strm_mode(&self) -> bool {
+        self.strm_mode
+    }
+
+    fn strm_refresh(&self) -> bool {
+        self.strm_refresh
+    }
+
+    fn strict_incremental(&self) -> bool {
+        self.strict_incremental
+    }
+
+    fn webdav_upload(&self) -> bool {
+        self.webdav_upload
+    }
+
+    fn audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    fn upgrade_quality(&self) -> bool {
+        self.upgrade_quality
+    }
+
+    fn danmaku_embed_mode(&self) -> DanmakuEmbedMode {
+        DanmakuEmbedMode::parse(&self.danmaku_embed_mode).unwrap_or_default()
+    }
+
+    fn subtask_toggles(&self) -> SubtaskToggles {
+        SubtaskToggles {
+            cover: self.download_cover,
+            nfo: self.download_nfo,
+            danmaku: self.download_danmaku,
+            subtitle: self.download_subtitle,
+        }
+    }
+
+    fn limit_newest(&self) -> Option<usize> {
+        limit_newest_from_column(self.limit_newest)
+    }
+
+    fn nfo_time_type(&self) -> Option<NFOTimeType> {
+        nfo_time_type_from_column(self.nfo_time_type.as_deref())
+    }
+
     fn source_type_display(&self) -> String {
         CollectionType::from(self.r#type).to_string()
     }
@@ -218,6 +279,19 @@ pub(super) async fn collection_from<'a>(
     VideoSourceEnum,
     Pin<Box<dyn Stream<Item = Result<VideoInfo>> + 'a + Send>>,
 )> {
+    // 手动列表的视频在导入时已写入数据库，扫描时没有需要拉取的新视频，只需处理未完成的下载
+    if collection_item.collection_type == CollectionType::Manual {
+        let manual = collection::Entity::find()
+            .filter(
+                collection::Column::SId
+                    .eq(collection_item.sid.clone())
+                    .and(collection::Column::Type.eq(Into::<i32>::into(CollectionType::Manual))),
+            )
+            .one(connection)
+            .await?
+            .context("manual collection not found")?;
+        return Ok((manual.into(), Box::pin(futures::stream::empty())));
+    }
     let collection = Collection::new(bili_client, collection_item);
     let collection_info = collection.get_info().await?;
     collection::Entity::insert(collection::ActiveModel {
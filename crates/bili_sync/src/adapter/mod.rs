@@ -10,7 +10,7 @@ mod watch_later;
 // pub use submission::init_submission_sources;
 // pub use watch_later::init_watch_later_source;
 
-pub use bangumi::BangumiSource;
+pub use bangumi::{BangumiSource, EpisodeRange};
 
 use std::path::Path;
 use std::pin::Pin;
@@ -34,6 +34,8 @@ use crate::adapter::favorite::favorite_from;
 use crate::adapter::submission::submission_from;
 use crate::adapter::watch_later::watch_later_from;
 use crate::bilibili::{BiliClient, CollectionItem, VideoInfo};
+use crate::config::{DanmakuEmbedMode, NFOTimeType};
+use crate::utils::title_filter::TitleFilter;
 
 #[enum_dispatch]
 pub enum VideoSourceEnum {
@@ -65,10 +67,7 @@ pub trait VideoSource {
 
     // 判断是否应该继续拉取视频
     fn should_take(&self, release_datetime: &chrono::DateTime<Utc>, latest_row_at_string: &str) -> bool {
-        let beijing_tz = crate::utils::time_format::beijing_timezone();
-        let release_beijing = release_datetime.with_timezone(&beijing_tz);
-        let release_beijing_str = release_beijing.format("%Y-%m-%d %H:%M:%S").to_string();
-        release_beijing_str.as_str() > latest_row_at_string
+        is_newer_than_latest_row(release_datetime, latest_row_at_string)
     }
 
     /// 开始刷新视频
@@ -92,6 +91,42 @@ pub trait VideoSource {
     /// 获取是否扫描已删除视频的设置
     fn scan_deleted_videos(&self) -> bool;
 
+    /// 获取发布时间窗口（YYYY-MM-DD），分别为起始和截止日期，None 表示不限制
+    fn pubtime_window(&self) -> (Option<&str>, Option<&str>);
+
+    /// 获取按标题关键词筛选视频的过滤器，未设置关键词时返回 None
+    fn title_filter(&self) -> Option<TitleFilter>;
+
+    /// 获取是否启用 .strm 模式，启用后只写入播放地址而不下载视频流
+    fn strm_mode(&self) -> bool;
+
+    /// 获取是否在每次扫描时刷新已生成的 .strm 文件中的播放地址
+    fn strm_refresh(&self) -> bool;
+
+    /// 获取是否启用严格增量模式，启用后只拉取比 latest_row_at 更新的视频，从不回退到全量扫描
+    fn strict_incremental(&self) -> bool;
+
+    /// 获取是否在分页下载完成后上传到 WebDAV
+    fn webdav_upload(&self) -> bool;
+
+    /// 获取是否启用仅音频模式，启用后只下载音频流并输出为带标签和封面的音频文件
+    fn audio_only(&self) -> bool;
+
+    /// 获取是否在重新扫描时检查并升级到更高画质
+    fn upgrade_quality(&self) -> bool;
+
+    /// 获取弹幕嵌入视频的方式
+    fn danmaku_embed_mode(&self) -> DanmakuEmbedMode;
+
+    /// 获取封面、NFO、弹幕、字幕子任务的开关
+    fn subtask_toggles(&self) -> SubtaskToggles;
+
+    /// 获取扫描时最多处理的最新视频数量，None 表示不限制
+    fn limit_newest(&self) -> Option<usize>;
+
+    /// 获取视频源单独设置的 NFO 时间类型，None 表示使用全局配置（番剧为首播时间）
+    fn nfo_time_type(&self) -> Option<NFOTimeType>;
+
     /// 获取选择的视频列表，仅对 submission 类型有效
     /// 返回 Some(Vec<String>) 表示有选择性下载列表，None 表示下载所有视频
     fn get_selected_videos(&self) -> Option<Vec<String>> {
@@ -110,6 +145,56 @@ pub trait VideoSource {
     fn source_name_display(&self) -> String;
 }
 
+/// 将数据库中的 limit_newest 转换为数量限制，未设置或不大于 0 时不限制
+pub fn limit_newest_from_column(limit_newest: Option<i32>) -> Option<usize> {
+    limit_newest.filter(|limit| *limit > 0).map(|limit| limit as usize)
+}
+
+/// 将数据库中的 nfo_time_type 转换为时间类型，未设置或无法识别时使用全局配置
+pub fn nfo_time_type_from_column(nfo_time_type: Option<&str>) -> Option<NFOTimeType> {
+    nfo_time_type.and_then(NFOTimeType::parse)
+}
+
+/// 视频源级别的子任务开关，关闭的子任务在状态中记为不适用
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubtaskToggles {
+    pub cover: bool,
+    pub nfo: bool,
+    pub danmaku: bool,
+    pub subtitle: bool,
+}
+
+impl Default for SubtaskToggles {
+    fn default() -> Self {
+        Self {
+            cover: true,
+            nfo: true,
+            danmaku: true,
+            subtitle: true,
+        }
+    }
+}
+
+impl SubtaskToggles {
+    /// 与 VideoStatus 的子任务一一对应：视频封面、视频信息、Up 主头像、Up 主信息、分 P 下载
+    pub fn video_applicable(&self) -> [bool; 5] {
+        [self.cover, self.nfo, self.cover, self.nfo, true]
+    }
+
+    /// 与 PageStatus 的子任务一一对应：视频封面、视频内容、视频信息、视频弹幕、视频字幕
+    pub fn page_applicable(&self) -> [bool; 5] {
+        [self.cover, true, self.nfo, self.danmaku, self.subtitle]
+    }
+}
+
+/// 判断视频发布时间（转换为北京时间）是否晚于上次扫描记录的 latest_row_at
+pub fn is_newer_than_latest_row(release_datetime: &chrono::DateTime<Utc>, latest_row_at_string: &str) -> bool {
+    let beijing_tz = crate::utils::time_format::beijing_timezone();
+    let release_beijing = release_datetime.with_timezone(&beijing_tz);
+    let release_beijing_str = release_beijing.format("%Y-%m-%d %H:%M:%S").to_string();
+    release_beijing_str.as_str() > latest_row_at_string
+}
+
 #[derive(Clone, Debug)]
 pub enum Args {
     Favorite {
@@ -225,6 +310,13 @@ pub async fn bangumi_from<'a>(
             None
         };
 
+        // 解析集数范围，添加时已校验，此处仅防御数据库中的异常值
+        let episode_range = model.episode_range.as_deref().and_then(|range| {
+            EpisodeRange::parse(range)
+                .inspect_err(|e| warn!("番剧 {} 的集数范围「{}」无效，已忽略: {:#}", model.name, range, e))
+                .ok()
+        });
+
         BangumiSource {
             id: model.id,
             name: model.name,
@@ -237,6 +329,27 @@ pub async fn bangumi_from<'a>(
             page_name_template: model.page_name_template,
             selected_seasons,
             scan_deleted_videos: model.scan_deleted_videos,
+            pubtime_after: model.pubtime_after,
+            pubtime_before: model.pubtime_before,
+            title_include: model.title_include,
+            title_exclude: model.title_exclude,
+            title_regex: model.title_regex,
+            episode_range,
+            strm_mode: model.strm_mode,
+            strm_refresh: model.strm_refresh,
+            strict_incremental: model.strict_incremental,
+            webdav_upload: model.webdav_upload,
+            audio_only: model.audio_only,
+            upgrade_quality: model.upgrade_quality,
+            danmaku_embed_mode: DanmakuEmbedMode::parse(&model.danmaku_embed_mode).unwrap_or_default(),
+            subtask_toggles: SubtaskToggles {
+                cover: model.download_cover,
+                nfo: model.download_nfo,
+                danmaku: model.download_danmaku,
+                subtitle: model.download_subtitle,
+            },
+            limit_newest: limit_newest_from_column(model.limit_newest),
+            nfo_time_type: nfo_time_type_from_column(model.nfo_time_type.as_deref()),
         }
     } else {
         // 如果数据库中不存在，使用默认值并发出警告
@@ -260,6 +373,22 @@ pub async fn bangumi_from<'a>(
             page_name_template: None,
             selected_seasons: None,
             scan_deleted_videos: false,
+            pubtime_after: None,
+            pubtime_before: None,
+            title_include: None,
+            title_exclude: None,
+            title_regex: false,
+            episode_range: None,
+            strm_mode: false,
+            strm_refresh: false,
+            strict_incremental: false,
+            webdav_upload: false,
+            audio_only: false,
+            upgrade_quality: false,
+            danmaku_embed_mode: DanmakuEmbedMode::None,
+            subtask_toggles: SubtaskToggles::default(),
+            limit_newest: None,
+            nfo_time_type: None,
         }
     };
 
@@ -276,3 +405,19 @@ pub async fn bangumi_from<'a>(
 
     Ok((VideoSourceEnum::BangumiSource(bangumi_source), video_stream))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer_than_latest_row() {
+        // 2025-01-01 04:00:00 UTC 即北京时间 2025-01-01 12:00:00
+        let release = chrono::DateTime::parse_from_rfc3339("2025-01-01T04:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(is_newer_than_latest_row(&release, "2025-01-01 11:59:59"));
+        assert!(!is_newer_than_latest_row(&release, "2025-01-01 12:00:00"));
+        assert!(is_newer_than_latest_row(&release, "1970-01-01 00:00:00"));
+    }
+}
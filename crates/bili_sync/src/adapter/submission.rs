@@ -12,8 +12,12 @@ use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseConnection, Unchanged};
 use tracing::{debug, info, warn};
 
-use crate::adapter::{VideoSource, VideoSourceEnum, _ActiveModel};
+use crate::adapter::{
+    _ActiveModel, limit_newest_from_column, nfo_time_type_from_column, SubtaskToggles, VideoSource, VideoSourceEnum,
+};
 use crate::bilibili::{BiliClient, Submission, VideoInfo};
+use crate::config::{DanmakuEmbedMode, NFOTimeType};
+use crate::utils::title_filter::TitleFilter;
 
 impl VideoSource for submission::Model {
     fn filter_expr(&self) -> SimpleExpr {
@@ -151,6 +155,63 @@ impl VideoSource for submission::Model {
         self.scan_deleted_videos
     }
 
+    fn pubtime_window(&self) -> (Option<&str>, Option<&str>) {
+        (self.pubtime_after.as_deref(), self.pubtime_before.as_deref())
+    }
+
+    fn title_filter(&self) -> Option<TitleFilter> {
+        TitleFilter::from_columns(
+            self.title_include.as_deref(),
+            self.title_exclude.as_deref(),
+            self.title_regex,
+        )
+    }
+
+    fn strm_mode(&self) -> bool {
+        self.strm_mode
+    }
+
+    fn strm_refresh(&self) -> bool {
+        self.strm_refresh
+    }
+
+    fn strict_incremental(&self) -> bool {
+        self.strict_incremental
+    }
+
+    fn webdav_upload(&self) -> bool {
+        self.webdav_upload
+    }
+
+    fn audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    fn upgrade_quality(&self) -> bool {
+        self.upgrade_quality
+    }
+
+    fn danmaku_embed_mode(&self) -> DanmakuEmbedMode {
+        DanmakuEmbedMode::parse(&self.danmaku_embed_mode).unwrap_or_default()
+    }
+
+    fn subtask_toggles(&self) -> SubtaskToggles {
+        SubtaskToggles {
+            cover: self.download_cover,
+            nfo: self.download_nfo,
+            danmaku: self.download_danmaku,
+            subtitle: self.download_subtitle,
+        }
+    }
+
+    fn limit_newest(&self) -> Option<usize> {
+        limit_newest_from_column(self.limit_newest)
+    }
+
+    fn nfo_time_type(&self) -> Option<NFOTimeType> {
+        nfo_time_type_from_column(self.nfo_time_type.as_deref())
+    }
+
     fn get_selected_videos(&self) -> Option<Vec<String>> {
         self.selected_videos.as_ref().and_then(|json_str| {
             serde_json::from_str::<Vec<String>>(json_str)
@@ -221,6 +282,30 @@ pub async fn init_submission_sources(
                         latest_row_at: Set("1970-01-01 00:00:00".to_string()),
                         enabled: Set(true),
                         scan_deleted_videos: Set(false),
+                        scan_interval: Set(None),
+                        pubtime_after: Set(None),
+                        pubtime_before: Set(None),
+                        title_include: Set(None),
+                        title_exclude: Set(None),
+                        title_regex: Set(false),
+                        strm_mode: Set(false),
+                        strm_refresh: Set(false),
+                        strict_incremental: Set(false),
+                        webdav_upload: Set(false),
+                        retention_days: Set(None),
+                        retention_max_count: Set(None),
+                        audio_only: Set(false),
+                        upgrade_quality: Set(false),
+                        danmaku_embed_mode: Set(DanmakuEmbedMode::None.as_str().to_string()),
+                        download_cover: Set(true),
+                        download_nfo: Set(true),
+                        download_danmaku: Set(true),
+                        download_subtitle: Set(true),
+                        last_error: Set(None),
+                        last_error_at: Set(None),
+                        limit_newest: Set(None),
+                        nfo_time_type: Set(None),
+                        paused: Set(false),
                         selected_videos: Set(None),
                     };
 
@@ -244,6 +329,30 @@ pub async fn init_submission_sources(
                         latest_row_at: Set("1970-01-01 00:00:00".to_string()),
                         enabled: Set(true),
                         scan_deleted_videos: Set(false),
+                        scan_interval: Set(None),
+                        pubtime_after: Set(None),
+                        pubtime_before: Set(None),
+                        title_include: Set(None),
+                        title_exclude: Set(None),
+                        title_regex: Set(false),
+                        strm_mode: Set(false),
+                        strm_refresh: Set(false),
+                        strict_incremental: Set(false),
+                        webdav_upload: Set(false),
+                        retention_days: Set(None),
+                        retention_max_count: Set(None),
+                        audio_only: Set(false),
+                        upgrade_quality: Set(false),
+                        danmaku_embed_mode: Set(DanmakuEmbedMode::None.as_str().to_string()),
+                        download_cover: Set(true),
+                        download_nfo: Set(true),
+                        download_danmaku: Set(true),
+                        download_subtitle: Set(true),
+                        last_error: Set(None),
+                        last_error_at: Set(None),
+                        limit_newest: Set(None),
+                        nfo_time_type: Set(None),
+                        paused: Set(false),
                         selected_videos: Set(None),
                     };
 
@@ -10,8 +10,12 @@ use sea_orm::sea_query::SimpleExpr;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseConnection, Unchanged};
 
-use crate::adapter::{VideoSource, VideoSourceEnum, _ActiveModel};
+use crate::adapter::{
+    _ActiveModel, limit_newest_from_column, nfo_time_type_from_column, SubtaskToggles, VideoSource, VideoSourceEnum,
+};
 use crate::bilibili::{BiliClient, VideoInfo, WatchLater};
+use crate::config::{DanmakuEmbedMode, NFOTimeType};
+use crate::utils::title_filter::TitleFilter;
 
 impl VideoSource for watch_later::Model {
     fn filter_expr(&self) -> SimpleExpr {
@@ -75,6 +79,63 @@ impl VideoSource for watch_later::Model {
         self.scan_deleted_videos
     }
 
+    fn pubtime_window(&self) -> (Option<&str>, Option<&str>) {
+        (self.pubtime_after.as_deref(), self.pubtime_before.as_deref())
+    }
+
+    fn title_filter(&self) -> Option<TitleFilter> {
+        TitleFilter::from_columns(
+            self.title_include.as_deref(),
+            self.title_exclude.as_deref(),
+            self.title_regex,
+        )
+    }
+
+    fn strm_mode(&self) -> bool {
+        self.strm_mode
+    }
+
+    fn strm_refresh(&self) -> bool {
+        self.strm_refresh
+    }
+
+    fn strict_incremental(&self) -> bool {
+        self.strict_incremental
+    }
+
+    fn webdav_upload(&self) -> bool {
+        self.webdav_upload
+    }
+
+    fn audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    fn upgrade_quality(&self) -> bool {
+        self.upgrade_quality
+    }
+
+    fn danmaku_embed_mode(&self) -> DanmakuEmbedMode {
+        DanmakuEmbedMode::parse(&self.danmaku_embed_mode).unwrap_or_default()
+    }
+
+    fn subtask_toggles(&self) -> SubtaskToggles {
+        SubtaskToggles {
+            cover: self.download_cover,
+            nfo: self.download_nfo,
+            danmaku: self.download_danmaku,
+            subtitle: self.download_subtitle,
+        }
+    }
+
+    fn limit_newest(&self) -> Option<usize> {
+        limit_newest_from_column(self.limit_newest)
+    }
+
+    fn nfo_time_type(&self) -> Option<NFOTimeType> {
+        nfo_time_type_from_column(self.nfo_time_type.as_deref())
+    }
+
     fn source_type_display(&self) -> String {
         "稍后再看".to_string()
     }
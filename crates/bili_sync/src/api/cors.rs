@@ -0,0 +1,85 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// 根据配置中的 `cors_allowed_origins` 构造跨域中间件
+///
+/// 每次请求时读取最新配置，修改允许的来源后无需重启
+pub fn cors_layer() -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin, parts| {
+            origin_allowed(origin, &parts.headers)
+        }))
+        .allow_methods(Any)
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+}
+
+/// 判断请求来源是否允许访问，同源请求始终允许
+pub fn origin_allowed(origin: &HeaderValue, headers: &HeaderMap) -> bool {
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    if is_same_origin(origin, headers) {
+        return true;
+    }
+    crate::config::with_config(|bundle| origin_allowed_in(&bundle.config.cors_allowed_origins, origin))
+}
+
+fn origin_allowed_in(allowed_origins: &[String], origin: &str) -> bool {
+    let origin = origin.trim_end_matches('/');
+    allowed_origins
+        .iter()
+        .any(|allowed| allowed == "*" || allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+}
+
+/// 来源中的主机部分与 Host 头一致时视为同源
+fn is_same_origin(origin: &str, headers: &HeaderMap) -> bool {
+    let Some(host) = headers.get(header::HOST).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    origin
+        .split_once("://")
+        .is_some_and(|(_, authority)| authority.trim_end_matches('/').eq_ignore_ascii_case(host))
+}
+
+/// 校验配置中的单个来源，必须为 `*` 或不带路径的 http(s) 地址
+pub fn validate_origin(origin: &str) -> Result<(), String> {
+    if origin == "*" {
+        return Ok(());
+    }
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return Err(format!("跨域来源 {} 缺少协议，应形如 https://example.com", origin));
+    };
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("跨域来源 {} 的协议必须为 http 或 https", origin));
+    }
+    let authority = authority.trim_end_matches('/');
+    if authority.is_empty() || authority.contains('/') {
+        return Err(format!("跨域来源 {} 不能包含路径", origin));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_check() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_static("127.0.0.1:12345"));
+        assert!(is_same_origin("http://127.0.0.1:12345", &headers));
+        assert!(!is_same_origin("http://evil.example.com", &headers));
+
+        let allowed = vec!["https://dash.example.com/".to_string()];
+        assert!(origin_allowed_in(&allowed, "https://dash.example.com"));
+        assert!(!origin_allowed_in(&allowed, "http://dash.example.com"));
+        assert!(!origin_allowed_in(&[], "https://dash.example.com"));
+        assert!(origin_allowed_in(&["*".to_string()], "https://any.example.com"));
+
+        assert!(validate_origin("*").is_ok());
+        assert!(validate_origin("http://localhost:5173").is_ok());
+        assert!(validate_origin("localhost:5173").is_err());
+        assert!(validate_origin("ftp://example.com").is_err());
+        assert!(validate_origin("https://example.com/path").is_err());
+    }
+}
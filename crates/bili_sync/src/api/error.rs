@@ -4,6 +4,8 @@ use thiserror::Error;
 pub enum InnerApiError {
     #[error("Primary key not found: {0}")]
     NotFound(i32),
+    #[error("Task not found: {0}")]
+    TaskNotFound(String),
     #[error("Bad request: {0}")]
     BadRequest(String),
     #[error("Database error: {0}")]
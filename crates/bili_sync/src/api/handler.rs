@@ -24,20 +24,25 @@ use utoipa::OpenApi;
 use crate::api::auth::OpenAPIAuth;
 use crate::api::error::InnerApiError;
 use crate::api::request::{
-    AddVideoSourceRequest, BatchUpdateConfigRequest, ConfigHistoryRequest, QRGenerateRequest, QRPollRequest,
-    ResetSpecificTasksRequest, ResetVideoSourcePathRequest, SetupAuthTokenRequest, SubmissionVideosRequest,
-    UpdateConfigItemRequest, UpdateConfigRequest, UpdateCredentialRequest, UpdateVideoStatusRequest, VideosRequest,
+    AddVideoSourceRequest, BatchUpdateConfigRequest, ConfigExportRequest, ConfigHistoryRequest, ConfigImportRequest,
+    PreviewTemplateRequest, QRGenerateRequest, QRPollRequest, ResetSpecificTasksRequest, ResetVideoSourcePathRequest,
+    RestoreTrashRequest, SetupAuthTokenRequest, SubmissionVideosRequest, UpdateConfigItemRequest, UpdateConfigRequest,
+    UpdateCredentialRequest, UpdateVideoStatusRequest, VideosRequest,
 };
 use crate::api::response::{
-    AddVideoSourceResponse, BangumiSeasonInfo, BangumiSourceListResponse, BangumiSourceOption, ConfigChangeInfo,
-    ConfigHistoryResponse, ConfigItemResponse, ConfigReloadResponse, ConfigResponse, ConfigValidationResponse,
-    DashBoardResponse, DeleteVideoResponse, DeleteVideoSourceResponse, HotReloadStatusResponse,
-    InitialSetupCheckResponse, MonitoringStatus, PageInfo, QRGenerateResponse, QRPollResponse, QRUserInfo,
-    ResetAllVideosResponse, ResetVideoResponse, ResetVideoSourcePathResponse, SetupAuthTokenResponse,
-    SubmissionVideosResponse, UpdateConfigResponse, UpdateCredentialResponse, UpdateVideoStatusResponse, VideoInfo,
-    VideoResponse, VideoSource, VideoSourcesResponse, VideosResponse,
+    AddVideoSourceResponse, BangumiSeasonInfo, BangumiSourceListResponse, BangumiSourceOption,
+    BatchDeleteVideosResponse, ConfigChangeInfo, ConfigDiffItem, ConfigExportResponse, ConfigHistoryResponse,
+    ConfigImportResponse, ConfigItemResponse, ConfigReloadResponse, ConfigResponse, ConfigRollbackResponse,
+    ConfigValidationResponse, DashBoardResponse, DeleteVideoResponse, DeleteVideoSourceResponse,
+    HotReloadStatusResponse, ImportVideoListResponse, InitialSetupCheckResponse, MonitoringStatus, PageInfo,
+    PreviewTemplateResponse, QRGenerateResponse, QRPollResponse, QRUserInfo, ResetAllVideosResponse,
+    ResetVideoResponse, ResetVideoSourcePathResponse, RestoreTrashResponse, SearchResultGroup, SearchVideosResponse,
+    SetupAuthTokenResponse, StatsResponse, SubmissionVideosResponse, TrashBatchInfo, TrashListResponse,
+    UpdateConfigResponse, UpdateCredentialResponse, UpdateVideoStatusResponse, VideoInfo, VideoResponse, VideoSource,
+    VideoSourcesResponse, VideoTagsResponse, VideosResponse,
 };
 use crate::api::wrapper::{ApiError, ApiResponse};
+use crate::task::retention_cleaner::RetentionPolicy;
 use crate::utils::status::{PageStatus, VideoStatus};
 
 // 全局静态的扫码登录服务实例
@@ -55,45 +60,9 @@ fn normalize_file_path(path: &str) -> String {
 /// # 参数
 /// - `deleted_path`: 已删除的文件夹路径
 /// - `stop_at`: 停止清理的父目录路径（避免删除配置的基础路径）
-fn cleanup_empty_parent_dirs(deleted_path: &str, _stop_at: &str) {
-    use std::fs;
-    use std::path::Path;
-
-    let mut current_path = Path::new(deleted_path).parent();
-    while let Some(parent) = current_path {
-        let parent_str = parent.to_string_lossy().to_string();
-
-        // 检查父目录是否为空
-        if parent.exists() {
-            match fs::read_dir(parent) {
-                Ok(mut entries) => {
-                    // 如果目录为空（没有子项），则删除它
-                    if entries.next().is_none() {
-                        match fs::remove_dir(parent) {
-                            Ok(_) => {
-                                info!("清理空父目录: {}", parent_str);
-                                current_path = parent.parent();
-                                continue;
-                            }
-                            Err(e) => {
-                                warn!("无法删除空父目录 {}: {}", parent_str, e);
-                                break;
-                            }
-                        }
-                    } else {
-                        // 目录不为空，停止清理
-                        info!("目录不为空，停止清理: {}", parent_str);
-                        break;
-                    }
-                }
-                Err(e) => {
-                    warn!("无法读取父目录 {}: {}", parent_str, e);
-                    break;
-                }
-            }
-        } else {
-            break;
-        }
+fn cleanup_empty_parent_dirs(deleted_path: &str, stop_at: &str) {
+    if let Some(parent) = std::path::Path::new(deleted_path).parent() {
+        crate::utils::empty_dirs::remove_empty_dirs_upward(parent, std::path::Path::new(stop_at));
     }
 }
 
@@ -101,21 +70,132 @@ fn cleanup_empty_parent_dirs(deleted_path: &str, _stop_at: &str) {
 /// 这样可以保持目录结构同时确保每个段都是安全的文件名
 fn process_path_with_filenamify(input: &str) -> String {
     // 修复：采用与下载流程相同的两阶段处理
-    // 阶段1：先对内容进行安全化，保护模板分隔符
-    let temp_placeholder = "🔒TEMP_PATH_SEP🔒";
-    let protected_input = input.replace("___PATH_SEP___", temp_placeholder);
+    // 阶段1：先将路径分隔符标记转换为模板分隔符，由 filenamify 按所有文件名策略统一保护
+    let protected_input = input.replace("___PATH_SEP___", "__UNIX_SEP__");
 
     // 阶段2：对保护后的内容进行安全化处理（内容中的斜杠会被转换为下划线）
-    let safe_content = crate::utils::filenamify::filenamify(&protected_input);
+    let safe_content = crate::utils::filenamify::filenamify_with_options(&protected_input, true);
 
     // 阶段3：恢复模板路径分隔符
-    safe_content.replace(temp_placeholder, "/")
+    safe_content.replace("__UNIX_SEP__", "/")
+}
+
+/// 可以预览的模板类型，与配置包中注册的模板名称一致
+const PREVIEW_TEMPLATE_TYPES: [&str; 6] = [
+    "video",
+    "page",
+    "multi_page",
+    "bangumi",
+    "bangumi_folder",
+    "folder_structure",
+];
+
+/// 使用给定模板替换当前配置中对应的模板后渲染，返回渲染结果、下载路径与重命名路径
+fn render_template_preview(
+    config: &crate::config::Config,
+    template_type: &str,
+    template: &str,
+    data: &serde_json::Value,
+) -> Result<(String, String, String)> {
+    use std::borrow::Cow;
+
+    let mut config = config.clone();
+    let template = Cow::Owned(template.to_string());
+    match template_type {
+        "video" => config.video_name = template,
+        "page" => config.page_name = template,
+        "multi_page" => config.multi_page_name = template,
+        "bangumi" => config.bangumi_name = template,
+        "bangumi_folder" => config.bangumi_folder_name = template,
+        "folder_structure" => config.folder_structure = template,
+        _ => return Err(anyhow!("不支持的模板类型: {}", template_type)),
+    }
+    let bundle = crate::config::ConfigBundle::from_config(config).context("模板语法错误")?;
+    let rendered = bundle.handlebars.render(template_type, data).context("模板渲染失败")?;
+    let raw = rendered.replace("__UNIX_SEP__", "/").replace("__WIN_SEP__", "\\");
+    let rendered_path = bundle.render_template(template_type, data)?;
+    let rename_path = process_path_with_filenamify(
+        &rendered
+            .replace("__UNIX_SEP__", "___PATH_SEP___")
+            .replace("__WIN_SEP__", "___PATH_SEP___"),
+    );
+    Ok((raw, rendered_path, rename_path))
 }
 
 #[cfg(test)]
 mod rename_tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_get_video_sources_reads_source_settings() {
+        use bili_sync_migration::{Migrator, MigratorTrait};
+
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        db.execute_unprepared(
+            "INSERT INTO watch_later (path, created_at, latest_row_at, enabled, scan_deleted_videos, scan_interval, title_include, title_regex, strm_mode, strm_refresh, limit_newest) \
+             VALUES ('/watch_later', '2026-01-01 00:00:00', '2026-01-01 00:00:00', 1, 0, 600, '[\"预告\"]', 1, 1, 1, 0)",
+        )
+        .await
+        .unwrap();
+
+        let response = get_video_sources(Extension(Arc::new(db))).await.unwrap();
+        let response = serde_json::to_value(response).unwrap();
+        let sources = &response["data"];
+        assert!(sources["collection"].as_array().unwrap().is_empty());
+        assert!(sources["bangumi"].as_array().unwrap().is_empty());
+        let watch_later = &sources["watch_later"][0];
+        assert_eq!(watch_later["name"], "稍后再看");
+        assert_eq!(watch_later["scan_interval"], 600);
+        assert_eq!(watch_later["title_include"], serde_json::json!(["预告"]));
+        assert_eq!(watch_later["title_regex"], true);
+        assert_eq!(watch_later["strm_refresh"], true);
+        assert_eq!(watch_later["danmaku_embed_mode"], "none");
+        assert_eq!(watch_later["download_cover"], true);
+        assert!(watch_later["limit_newest"].is_null());
+        assert!(watch_later["f_id"].is_null());
+    }
+
+    #[test]
+    fn test_group_ids_by_status_batches_large_reset() {
+        // 模拟重置数千个分页：状态值只有少数几种，应合并为少量 UPDATE 语句
+        let statuses = [0u32, 1 << 31, (1 << 31) | 7];
+        let pages = (1..=5000).map(|id| (id, statuses[id as usize % statuses.len()]));
+
+        let batches = group_ids_by_status(pages);
+
+        // 每种状态约 1667 个 ID，按 500 一块切分后为 4 块
+        assert_eq!(batches.len(), statuses.len() * 4);
+        assert!(batches.iter().all(|(_, ids)| ids.len() <= STATUS_UPDATE_CHUNK_SIZE));
+        let mut all_ids: Vec<i32> = batches.iter().flat_map(|(_, ids)| ids.iter().copied()).collect();
+        all_ids.sort_unstable();
+        assert_eq!(all_ids, (1..=5000).collect::<Vec<_>>());
+        for (status, ids) in &batches {
+            assert!(ids.iter().all(|id| statuses[*id as usize % statuses.len()] == *status));
+        }
+    }
+
+    #[test]
+    fn test_update_request_from_import() {
+        let merged = serde_json::json!({
+            "video_name": "{{bvid}}",
+            "proxy_url": null,
+            "submission_risk_control": { "batch_size": 10 },
+            "notification": { "webhook_url": "https://example.com/hook" },
+        });
+        let changed_keys: Vec<String> = ["notification", "proxy_url", "submission_risk_control", "video_name"]
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+        let (request, covered_keys) = update_request_from_import(&merged, &changed_keys).unwrap();
+        assert_eq!(covered_keys, ["proxy_url", "submission_risk_control", "video_name"]);
+        assert_eq!(request.video_name.as_deref(), Some("{{bvid}}"));
+        // 清空的配置项以空字符串传给更新配置接口
+        assert_eq!(request.proxy_url.as_deref(), Some(""));
+        assert_eq!(request.batch_size, Some(10));
+        assert!(request.page_name.is_none());
+    }
+
     #[test]
     fn test_process_path_with_filenamify_slash_handling() {
         // 测试与用户报告相同的情况
@@ -139,6 +219,21 @@ mod rename_tests {
         );
     }
 
+    #[test]
+    fn test_render_template_preview() {
+        let config = crate::config::Config::default();
+        let data = serde_json::json!({ "upper_name": "UP", "title": "分身/ドッペルゲンガー" });
+        let (raw, rendered_path, rename_path) =
+            render_template_preview(&config, "video", "{{upper_name}}/{{title}}", &data).unwrap();
+
+        assert_eq!(raw, "UP/分身/ドッペルゲンガー");
+        // 内容中的斜杠被安全化，只保留模板中的路径分隔符
+        assert_eq!(rendered_path, "UP/分身_ドッペルゲンガー");
+        assert_eq!(rename_path, "UP/分身_ドッペルゲンガー");
+        assert!(render_template_preview(&config, "unknown", "{{title}}", &data).is_err());
+        assert!(render_template_preview(&config, "video", "{{#if}}", &data).is_err());
+    }
+
     #[test]
     fn test_process_path_without_separator() {
         // 测试不包含模板分隔符的情况
@@ -153,7 +248,7 @@ mod rename_tests {
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(get_video_sources, get_videos, get_video, reset_video, reset_all_videos, reset_specific_tasks, update_video_status, add_video_source, update_video_source_enabled, update_video_source_scan_deleted, reset_video_source_path, delete_video_source, reload_config, get_config, update_config, get_bangumi_seasons, search_bilibili, get_user_favorites, get_user_collections, get_user_followings, get_subscribed_collections, get_submission_videos, get_logs, get_queue_status, proxy_image, get_config_item, get_config_history, validate_config, get_hot_reload_status, check_initial_setup, setup_auth_token, update_credential, generate_qr_code, poll_qr_status, get_current_user, clear_credential, pause_scanning_endpoint, resume_scanning_endpoint, get_task_control_status, get_video_play_info, proxy_video_stream, validate_favorite, get_user_favorites_by_uid, test_notification_handler, get_notification_config, update_notification_config, get_notification_status, test_risk_control_handler),
+    paths(get_video_sources, get_videos, search_videos, export_videos, get_video, add_video_tags, remove_video_tags, reset_video, reset_all_videos, batch_reset_videos, refresh_video_metadata, batch_refresh_video_metadata, refresh_source_metadata, batch_delete_videos, import_video_list, reset_specific_tasks, update_video_status, add_video_source, update_video_source_enabled, update_video_source_scan_deleted, update_video_source_scan_interval, update_video_source_pubtime_window, update_video_source_title_filter, update_video_source_strm, update_video_source_strict_incremental, update_video_source_webdav_upload, update_video_source_audio_only, update_video_source_upgrade_quality, update_video_source_danmaku_embed, update_video_source_subtasks, update_video_source_retention, update_video_source_limit_newest, update_video_source_nfo_time_type, scan_video_source, pause_video_source, resume_video_source, get_source_scan_history, get_source_playlist, refresh_collection_cover, get_source_scan_task, reset_video_source_path, delete_video_source, get_trash, restore_trash, reload_config, get_config, update_config, get_bangumi_seasons, search_bilibili, get_user_favorites, get_user_collections, get_user_followings, get_subscribed_collections, get_submission_videos, get_logs, get_stats, get_queue_status, get_queue_task, cancel_queue_task, get_dead_letter_tasks, requeue_dead_letter_task, proxy_image, get_config_item, get_config_history, rollback_config_change, preview_template, export_config, import_config, validate_config, get_hot_reload_status, check_initial_setup, setup_auth_token, update_credential, import_credential_cookie, get_credential_status, generate_qr_code, poll_qr_status, get_current_user, clear_credential, pause_scanning_endpoint, resume_scanning_endpoint, trigger_scan_all, get_task_control_status, get_video_play_info, proxy_video_stream, validate_favorite, get_user_favorites_by_uid, test_notification_handler, get_notification_config, update_notification_config, get_notification_status, test_risk_control_handler),
     modifiers(&OpenAPIAuth),
     security(
         ("Token" = []),
@@ -170,6 +265,163 @@ fn get_config_path() -> Result<PathBuf> {
         .map(|dir| dir.join("bili-sync").join("config.toml"))
 }
 
+/// 校验并规范化发布时间窗口，空字符串视为不限制
+fn normalize_pubtime_window(
+    after: Option<String>,
+    before: Option<String>,
+) -> Result<(Option<String>, Option<String>), ApiError> {
+    let parse = |value: Option<String>| -> Result<Option<chrono::NaiveDate>, ApiError> {
+        match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(v) => crate::utils::time_format::parse_date_string(v)
+                .map(Some)
+                .ok_or_else(|| anyhow!("无效的日期: {}，格式应为 YYYY-MM-DD", v).into()),
+            None => Ok(None),
+        }
+    };
+    let after = parse(after)?;
+    let before = parse(before)?;
+    if let (Some(after), Some(before)) = (after, before) {
+        if after > before {
+            return Err(anyhow!("发布时间窗口的起始日期 {} 不能晚于截止日期 {}", after, before).into());
+        }
+    }
+    let format = |date: chrono::NaiveDate| date.format(crate::utils::time_format::DATE_FORMAT).to_string();
+    Ok((after.map(format), before.map(format)))
+}
+
+/// 视频源列表的查询结果，各类视频源表的同名列直接映射，缺少的名称与类型特有ID列由查询补齐
+#[derive(FromQueryResult)]
+struct VideoSourceRow {
+    id: i32,
+    name: String,
+    enabled: bool,
+    path: String,
+    scan_deleted_videos: bool,
+    scan_interval: Option<i64>,
+    pubtime_after: Option<String>,
+    pubtime_before: Option<String>,
+    title_include: Option<String>,
+    title_exclude: Option<String>,
+    title_regex: bool,
+    strm_mode: bool,
+    strm_refresh: bool,
+    strict_incremental: bool,
+    webdav_upload: bool,
+    audio_only: bool,
+    upgrade_quality: bool,
+    danmaku_embed_mode: String,
+    download_cover: bool,
+    download_nfo: bool,
+    download_danmaku: bool,
+    download_subtitle: bool,
+    last_error: Option<String>,
+    last_error_at: Option<String>,
+    retention_days: Option<i32>,
+    retention_max_count: Option<i32>,
+    limit_newest: Option<i32>,
+    nfo_time_type: Option<String>,
+    f_id: Option<i64>,
+    s_id: Option<i64>,
+    m_id: Option<i64>,
+    upper_id: Option<i64>,
+    season_id: Option<String>,
+    media_id: Option<String>,
+    selected_seasons: Option<String>,
+}
+
+impl VideoSourceRow {
+    fn into_video_source(self, paused: bool) -> VideoSource {
+        let parse_terms = |json_str: Option<String>| -> Vec<String> {
+            json_str
+                .and_then(|json_str| serde_json::from_str::<Vec<String>>(&json_str).ok())
+                .unwrap_or_default()
+        };
+        let title_include = parse_terms(self.title_include);
+        let title_exclude = parse_terms(self.title_exclude);
+        // 未设置任何关键词时正则模式没有意义，按未开启返回
+        let title_regex = self.title_regex && (!title_include.is_empty() || !title_exclude.is_empty());
+        let retention = RetentionPolicy::from_columns(self.retention_days, self.retention_max_count);
+        let selected_seasons =
+            self.selected_seasons
+                .as_ref()
+                .and_then(|json| match serde_json::from_str::<Vec<String>>(json) {
+                    Ok(seasons) if !seasons.is_empty() => Some(seasons),
+                    Ok(_) => None,
+                    Err(err) => {
+                        warn!(
+                            "Failed to parse selected_seasons for bangumi source {}: {}",
+                            self.id, err
+                        );
+                        None
+                    }
+                });
+
+        VideoSource {
+            id: self.id,
+            name: self.name,
+            enabled: self.enabled,
+            path: self.path,
+            scan_deleted_videos: self.scan_deleted_videos,
+            scan_interval: self.scan_interval.and_then(|interval| u64::try_from(interval).ok()),
+            pubtime_after: self.pubtime_after,
+            pubtime_before: self.pubtime_before,
+            title_include,
+            title_exclude,
+            title_regex,
+            strm_mode: self.strm_mode,
+            strm_refresh: self.strm_mode && self.strm_refresh,
+            strict_incremental: self.strict_incremental,
+            webdav_upload: self.webdav_upload,
+            audio_only: self.audio_only,
+            upgrade_quality: self.upgrade_quality,
+            danmaku_embed_mode: crate::config::DanmakuEmbedMode::parse(&self.danmaku_embed_mode)
+                .unwrap_or_default()
+                .as_str()
+                .to_string(),
+            download_cover: self.download_cover,
+            download_nfo: self.download_nfo,
+            download_danmaku: self.download_danmaku,
+            download_subtitle: self.download_subtitle,
+            last_error_at: self.last_error.as_ref().and(self.last_error_at),
+            last_error: self.last_error,
+            retention_days: retention.days,
+            retention_max_count: retention.max_count,
+            limit_newest: self.limit_newest.filter(|limit| *limit > 0).map(|limit| limit as u32),
+            nfo_time_type: crate::adapter::nfo_time_type_from_column(self.nfo_time_type.as_deref())
+                .map(|time_type| time_type.as_str().to_string()),
+            paused,
+            f_id: self.f_id,
+            s_id: self.s_id,
+            m_id: self.m_id,
+            upper_id: self.upper_id,
+            season_id: self.season_id,
+            media_id: self.media_id,
+            selected_seasons,
+        }
+    }
+}
+
+/// 校验并规范化标题过滤关键词，去除空白与重复项
+fn normalize_title_terms(terms: Vec<String>, regex_mode: bool) -> Result<Vec<String>, ApiError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for term in terms {
+        let term = term.trim().to_string();
+        if !term.is_empty() && !normalized.contains(&term) {
+            normalized.push(term);
+        }
+    }
+    crate::utils::keyword_matcher::validate_terms(&normalized, regex_mode)?;
+    Ok(normalized)
+}
+
+/// 将标题过滤关键词序列化为数据库中保存的 JSON 数组，为空时返回 None
+fn title_terms_column(terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    serde_json::to_string(terms).ok()
+}
+
 /// 列出所有视频来源
 #[utoipa::path(
     get,
@@ -181,290 +433,90 @@ fn get_config_path() -> Result<PathBuf> {
 pub async fn get_video_sources(
     Extension(db): Extension<Arc<DatabaseConnection>>,
 ) -> Result<ApiResponse<VideoSourcesResponse>, ApiError> {
-    // 获取各类视频源
-    let collection_sources = collection::Entity::find()
-        .select_only()
-        .columns([
-            collection::Column::Id,
-            collection::Column::Name,
-            collection::Column::Enabled,
-            collection::Column::Path,
-            collection::Column::ScanDeletedVideos,
-            collection::Column::SId,
-            collection::Column::MId,
-        ])
-        .column_as(Expr::value(None::<i64>), "f_id")
-        .column_as(Expr::value(None::<i64>), "upper_id")
-        .column_as(Expr::value(None::<String>), "season_id")
-        .column_as(Expr::value(None::<String>), "media_id")
-        .into_tuple::<(
-            i32,
-            String,
-            bool,
-            String,
-            bool,
-            i64,
-            i64,
-            Option<i64>,
-            Option<i64>,
-            Option<String>,
-            Option<String>,
-        )>()
-        .all(db.as_ref())
-        .await?
-        .into_iter()
-        .map(
-            |(id, name, enabled, path, scan_deleted_videos, s_id, m_id, f_id, upper_id, season_id, media_id)| {
-                VideoSource {
-                    id,
-                    name,
-                    enabled,
-                    path,
-                    scan_deleted_videos,
-                    f_id,
-                    s_id: Some(s_id),
-                    m_id: Some(m_id),
-                    upper_id,
-                    season_id,
-                    media_id,
-                    selected_seasons: None,
-                }
-            },
-        )
-        .collect();
+    use crate::utils::scan_id_tracker::SourceType;
+
+    let paused_sources = crate::task::TASK_CONTROLLER.paused_sources();
+    let into_sources = |rows: Vec<VideoSourceRow>, source_type: SourceType| -> Vec<VideoSource> {
+        rows.into_iter()
+            .map(|row| {
+                let paused = paused_sources.contains(&(source_type, row.id));
+                row.into_video_source(paused)
+            })
+            .collect()
+    };
 
-    let favorite_sources = favorite::Entity::find()
-        .select_only()
-        .columns([
-            favorite::Column::Id,
-            favorite::Column::Name,
-            favorite::Column::Enabled,
-            favorite::Column::Path,
-            favorite::Column::ScanDeletedVideos,
-            favorite::Column::FId,
-        ])
-        .column_as(Expr::value(None::<i64>), "s_id")
-        .column_as(Expr::value(None::<i64>), "m_id")
-        .column_as(Expr::value(None::<i64>), "upper_id")
-        .column_as(Expr::value(None::<String>), "season_id")
-        .column_as(Expr::value(None::<String>), "media_id")
-        .into_tuple::<(
-            i32,
-            String,
-            bool,
-            String,
-            bool,
-            i64,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-            Option<String>,
-            Option<String>,
-        )>()
-        .all(db.as_ref())
-        .await?
-        .into_iter()
-        .map(
-            |(id, name, enabled, path, scan_deleted_videos, f_id, s_id, m_id, upper_id, season_id, media_id)| {
-                VideoSource {
-                    id,
-                    name,
-                    enabled,
-                    path,
-                    scan_deleted_videos,
-                    f_id: Some(f_id),
-                    s_id,
-                    m_id,
-                    upper_id,
-                    season_id,
-                    media_id,
-                    selected_seasons: None,
-                }
-            },
-        )
-        .collect();
+    // 获取各类视频源，表中缺少的列以空值补齐
+    let collection_sources = into_sources(
+        collection::Entity::find()
+            .column_as(Expr::value(None::<i64>), "f_id")
+            .column_as(Expr::value(None::<i64>), "upper_id")
+            .column_as(Expr::value(None::<String>), "season_id")
+            .column_as(Expr::value(None::<String>), "media_id")
+            .column_as(Expr::value(None::<String>), "selected_seasons")
+            .into_model::<VideoSourceRow>()
+            .all(db.as_ref())
+            .await?,
+        SourceType::Collection,
+    );
 
-    let submission_sources = submission::Entity::find()
-        .select_only()
-        .columns([
-            submission::Column::Id,
-            submission::Column::Enabled,
-            submission::Column::Path,
-            submission::Column::ScanDeletedVideos,
-            submission::Column::UpperId,
-        ])
-        .column_as(submission::Column::UpperName, "name")
-        .column_as(Expr::value(None::<i64>), "f_id")
-        .column_as(Expr::value(None::<i64>), "s_id")
-        .column_as(Expr::value(None::<i64>), "m_id")
-        .column_as(Expr::value(None::<String>), "season_id")
-        .column_as(Expr::value(None::<String>), "media_id")
-        .into_tuple::<(
-            i32,
-            bool,
-            String,
-            bool,
-            i64,
-            String,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-            Option<String>,
-            Option<String>,
-        )>()
-        .all(db.as_ref())
-        .await?
-        .into_iter()
-        .map(
-            |(id, enabled, path, scan_deleted_videos, upper_id, name, f_id, s_id, m_id, season_id, media_id)| {
-                VideoSource {
-                    id,
-                    name,
-                    enabled,
-                    path,
-                    scan_deleted_videos,
-                    f_id,
-                    s_id,
-                    m_id,
-                    upper_id: Some(upper_id),
-                    season_id,
-                    media_id,
-                    selected_seasons: None,
-                }
-            },
-        )
-        .collect();
+    let favorite_sources = into_sources(
+        favorite::Entity::find()
+            .column_as(Expr::value(None::<i64>), "s_id")
+            .column_as(Expr::value(None::<i64>), "m_id")
+            .column_as(Expr::value(None::<i64>), "upper_id")
+            .column_as(Expr::value(None::<String>), "season_id")
+            .column_as(Expr::value(None::<String>), "media_id")
+            .column_as(Expr::value(None::<String>), "selected_seasons")
+            .into_model::<VideoSourceRow>()
+            .all(db.as_ref())
+            .await?,
+        SourceType::Favorite,
+    );
 
-    let watch_later_sources = watch_later::Entity::find()
-        .select_only()
-        .columns([
-            watch_later::Column::Id,
-            watch_later::Column::Enabled,
-            watch_later::Column::Path,
-            watch_later::Column::ScanDeletedVideos,
-        ])
-        .column_as(Expr::value("稍后再看"), "name")
-        .column_as(Expr::value(None::<i64>), "f_id")
-        .column_as(Expr::value(None::<i64>), "s_id")
-        .column_as(Expr::value(None::<i64>), "m_id")
-        .column_as(Expr::value(None::<i64>), "upper_id")
-        .column_as(Expr::value(None::<String>), "season_id")
-        .column_as(Expr::value(None::<String>), "media_id")
-        .into_tuple::<(
-            i32,
-            bool,
-            String,
-            bool,
-            String,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-            Option<String>,
-            Option<String>,
-        )>()
-        .all(db.as_ref())
-        .await?
-        .into_iter()
-        .map(
-            |(id, enabled, path, scan_deleted_videos, name, f_id, s_id, m_id, upper_id, season_id, media_id)| {
-                VideoSource {
-                    id,
-                    name,
-                    enabled,
-                    path,
-                    scan_deleted_videos,
-                    f_id,
-                    s_id,
-                    m_id,
-                    upper_id,
-                    season_id,
-                    media_id,
-                    selected_seasons: None,
-                }
-            },
-        )
-        .collect();
+    let submission_sources = into_sources(
+        submission::Entity::find()
+            .column_as(submission::Column::UpperName, "name")
+            .column_as(Expr::value(None::<i64>), "f_id")
+            .column_as(Expr::value(None::<i64>), "s_id")
+            .column_as(Expr::value(None::<i64>), "m_id")
+            .column_as(Expr::value(None::<String>), "season_id")
+            .column_as(Expr::value(None::<String>), "media_id")
+            .column_as(Expr::value(None::<String>), "selected_seasons")
+            .into_model::<VideoSourceRow>()
+            .all(db.as_ref())
+            .await?,
+        SourceType::Submission,
+    );
 
-    // 确保bangumi_sources是一个数组，即使为空
-    let bangumi_sources = video_source::Entity::find()
-        .filter(video_source::Column::Type.eq(1))
-        .select_only()
-        .columns([
-            video_source::Column::Id,
-            video_source::Column::Name,
-            video_source::Column::Enabled,
-            video_source::Column::Path,
-            video_source::Column::ScanDeletedVideos,
-            video_source::Column::SeasonId,
-            video_source::Column::MediaId,
-            video_source::Column::SelectedSeasons,
-        ])
-        .column_as(Expr::value(None::<i64>), "f_id")
-        .column_as(Expr::value(None::<i64>), "s_id")
-        .column_as(Expr::value(None::<i64>), "m_id")
-        .column_as(Expr::value(None::<i64>), "upper_id")
-        .into_tuple::<(
-            i32,
-            String,
-            bool,
-            String,
-            bool,
-            Option<String>,
-            Option<String>,
-            Option<String>,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-            Option<i64>,
-        )>()
-        .all(db.as_ref())
-        .await?
-        .into_iter()
-        .map(
-            |(
-                id,
-                name,
-                enabled,
-                path,
-                scan_deleted_videos,
-                season_id,
-                media_id,
-                selected_seasons_json,
-                f_id,
-                s_id,
-                m_id,
-                upper_id,
-            )| {
-                let selected_seasons =
-                    selected_seasons_json
-                        .as_ref()
-                        .and_then(|json| match serde_json::from_str::<Vec<String>>(json) {
-                            Ok(seasons) if !seasons.is_empty() => Some(seasons),
-                            Ok(_) => None,
-                            Err(err) => {
-                                warn!("Failed to parse selected_seasons for bangumi source {}: {}", id, err);
-                                None
-                            }
-                        });
+    let watch_later_sources = into_sources(
+        watch_later::Entity::find()
+            .column_as(Expr::value("稍后再看"), "name")
+            .column_as(Expr::value(None::<i64>), "f_id")
+            .column_as(Expr::value(None::<i64>), "s_id")
+            .column_as(Expr::value(None::<i64>), "m_id")
+            .column_as(Expr::value(None::<i64>), "upper_id")
+            .column_as(Expr::value(None::<String>), "season_id")
+            .column_as(Expr::value(None::<String>), "media_id")
+            .column_as(Expr::value(None::<String>), "selected_seasons")
+            .into_model::<VideoSourceRow>()
+            .all(db.as_ref())
+            .await?,
+        SourceType::WatchLater,
+    );
 
-                VideoSource {
-                    id,
-                    name,
-                    enabled,
-                    path,
-                    scan_deleted_videos,
-                    f_id,
-                    s_id,
-                    m_id,
-                    upper_id,
-                    season_id,
-                    media_id,
-                    selected_seasons,
-                }
-            },
-        )
-        .collect();
+    // 确保bangumi_sources是一个数组，即使为空
+    let bangumi_sources = into_sources(
+        video_source::Entity::find()
+            .filter(video_source::Column::Type.eq(1))
+            .column_as(Expr::value(None::<i64>), "f_id")
+            .column_as(Expr::value(None::<i64>), "s_id")
+            .column_as(Expr::value(None::<i64>), "m_id")
+            .column_as(Expr::value(None::<i64>), "upper_id")
+            .into_model::<VideoSourceRow>()
+            .all(db.as_ref())
+            .await?,
+        SourceType::Bangumi,
+    );
 
     // 返回响应，确保每个分类都是一个数组
     Ok(ApiResponse::ok(VideoSourcesResponse {
@@ -476,21 +528,8 @@ pub async fn get_video_sources(
     }))
 }
 
-/// 列出视频的基本信息，支持根据视频来源筛选、名称查找和分页
-#[utoipa::path(
-    get,
-    path = "/api/videos",
-    params(
-        VideosRequest,
-    ),
-    responses(
-        (status = 200, body = ApiResponse<VideosResponse>),
-    )
-)]
-pub async fn get_videos(
-    Extension(db): Extension<Arc<DatabaseConnection>>,
-    Query(params): Query<VideosRequest>,
-) -> Result<ApiResponse<VideosResponse>, ApiError> {
+/// 按 VideosRequest 的筛选与排序条件构建视频查询，供列表与导出共用
+fn filtered_videos_query(params: &VideosRequest) -> sea_orm::Select<video::Entity> {
     let mut query = video::Entity::find();
 
     // 根据配置决定是否过滤已删除的视频
@@ -515,20 +554,27 @@ pub async fn get_videos(
             }
         }
     }
-    if let Some(query_word) = params.query {
+    if let Some(query_word) = &params.query {
         query = query.filter(
             video::Column::Name
-                .contains(&query_word)
-                .or(video::Column::Path.contains(&query_word)),
+                .contains(query_word)
+                .or(video::Column::Path.contains(query_word)),
         );
     }
 
+    // 按自定义标签筛选，标签以 JSON 字符串数组保存，匹配带引号的完整标签
+    if let Some(tag) = params.tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty()) {
+        if let Ok(quoted) = serde_json::to_string(tag) {
+            query = query.filter(video::Column::UserTags.contains(quoted));
+        }
+    }
+
     // 筛选失败任务（仅显示下载状态中包含失败的视频）
     if params.show_failed_only.unwrap_or(false) {
         // download_status是u32类型，使用位运算编码5个子任务状态
         // 每3位表示一个子任务：(download_status >> (offset * 3)) & 7
-        // 状态值：0=未开始，1-6=失败次数，7=成功
-        // 筛选任一子任务状态在1-6范围内的视频
+        // 状态值：0=未开始，1-5=失败次数，6=不适用（视频源关闭了该子任务），7=成功
+        // 筛选任一子任务状态在1-5范围内的视频
         use sea_orm::sea_query::Expr;
 
         let mut conditions = Vec::new();
@@ -537,9 +583,9 @@ pub async fn get_videos(
         for offset in 0..5 {
             let shift = offset * 3;
             // 提取第offset个子任务状态: (download_status >> shift) & 7
-            // 检查是否为失败状态: >= 1 AND <= 6
+            // 检查是否为失败状态: >= 1 AND <= 5
             conditions.push(Expr::cust(format!(
-                "((download_status >> {}) & 7) BETWEEN 1 AND 6",
+                "((download_status >> {}) & 7) BETWEEN 1 AND 5",
                 shift
             )));
         }
@@ -553,13 +599,6 @@ pub async fn get_videos(
         query = query.filter(final_condition);
     }
 
-    let total_count = query.clone().count(db.as_ref()).await?;
-    let (page, page_size) = if let (Some(page), Some(page_size)) = (params.page, params.page_size) {
-        (page, page_size)
-    } else {
-        (1, 10)
-    };
-
     // 处理排序参数
     let sort_by = params.sort_by.as_deref().unwrap_or("id");
     let sort_order = params.sort_order.as_deref().unwrap_or("desc");
@@ -599,44 +638,63 @@ pub async fn get_videos(
         }
     };
 
-    Ok(ApiResponse::ok(VideosResponse {
-        videos: {
-            // 查询包含season_id和source_type字段，用于番剧标题获取
-            type RawVideoTuple = (
-                i32,
-                String,
-                String,
-                String,
-                i32,
-                u32,
-                String,
-                Option<String>,
-                Option<i32>,
-            );
-            let raw_videos: Vec<RawVideoTuple> = query
-                .select_only()
-                .columns([
-                    video::Column::Id,
-                    video::Column::Name,
-                    video::Column::UpperName,
-                    video::Column::Path,
-                    video::Column::Category,
-                    video::Column::DownloadStatus,
-                    video::Column::Cover,
+    query
+}
+
+/// 列出视频的基本信息，支持根据视频来源筛选、名称查找和分页
+#[utoipa::path(
+    get,
+    path = "/api/videos",
+    params(
+        VideosRequest,
+    ),
+    responses(
+        (status = 200, body = ApiResponse<VideosResponse>),
+    )
+)]
+pub async fn get_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<VideosRequest>,
+) -> Result<ApiResponse<VideosResponse>, ApiError> {
+    let query = filtered_videos_query(&params);
+
+    let total_count = query.clone().count(db.as_ref()).await?;
+    let (page, page_size) = if let (Some(page), Some(page_size)) = (params.page, params.page_size) {
+        (page, page_size)
+    } else {
+        (1, 10)
+    };
+
+    Ok(ApiResponse::ok(VideosResponse {
+        videos: {
+            // 查询包含season_id和source_type字段，用于番剧标题获取
+            type RawVideoTuple = (
+                i32,
+                String,
+                String,
+                String,
+                i32,
+                u32,
+                String,
+                Option<String>,
+                Option<i32>,
+                Option<String>,
+            );
+            let raw_videos: Vec<RawVideoTuple> = query
+                .select_only()
+                .columns([
+                    video::Column::Id,
+                    video::Column::Name,
+                    video::Column::UpperName,
+                    video::Column::Path,
+                    video::Column::Category,
+                    video::Column::DownloadStatus,
+                    video::Column::Cover,
                     video::Column::SeasonId,
                     video::Column::SourceType,
+                    video::Column::UserTags,
                 ])
-                .into_tuple::<(
-                    i32,
-                    String,
-                    String,
-                    String,
-                    i32,
-                    u32,
-                    String,
-                    Option<String>,
-                    Option<i32>,
-                )>()
+                .into_tuple::<RawVideoTuple>()
                 .paginate(db.as_ref(), page_size)
                 .fetch_page(page)
                 .await?;
@@ -645,8 +703,19 @@ pub async fn get_videos(
             let mut videos: Vec<VideoInfo> = raw_videos
                 .iter()
                 .map(
-                    |(id, name, upper_name, path, category, download_status, cover, _season_id, _source_type)| {
-                        VideoInfo::from((
+                    |(
+                        id,
+                        name,
+                        upper_name,
+                        path,
+                        category,
+                        download_status,
+                        cover,
+                        _season_id,
+                        _source_type,
+                        user_tags,
+                    )| {
+                        let mut info = VideoInfo::from((
                             *id,
                             name.clone(),
                             upper_name.clone(),
@@ -654,27 +723,34 @@ pub async fn get_videos(
                             *category,
                             *download_status,
                             cover.clone(),
-                        ))
+                        ));
+                        info.user_tags = parse_user_tags(user_tags.as_deref());
+                        info
                     },
                 )
                 .collect();
 
             // 为番剧类型的视频填充真实标题
-            for (i, (_id, _name, _upper_name, _path, _category, _download_status, _cover, season_id, source_type)) in
-                raw_videos.iter().enumerate()
+            for (
+                i,
+                (
+                    _id,
+                    _name,
+                    _upper_name,
+                    _path,
+                    _category,
+                    _download_status,
+                    _cover,
+                    season_id,
+                    source_type,
+                    _user_tags,
+                ),
+            ) in raw_videos.iter().enumerate()
             {
-                if *source_type == Some(1) && season_id.is_some() {
+                if *source_type == Some(1) {
                     // 番剧类型且有season_id，尝试获取真实标题
                     if let Some(ref season_id_str) = season_id {
-                        // 先从缓存获取
-                        if let Some(title) = get_cached_season_title(season_id_str).await {
-                            videos[i].bangumi_title = Some(title);
-                        } else {
-                            // 缓存中没有，尝试从API获取并存入缓存
-                            if let Some(title) = fetch_and_cache_season_title(season_id_str).await {
-                                videos[i].bangumi_title = Some(title);
-                            }
-                        }
+                        videos[i].bangumi_title = resolve_season_title(season_id_str).await;
                     }
                 }
             }
@@ -685,6 +761,418 @@ pub async fn get_videos(
     }))
 }
 
+/// 获取番剧真实标题：先查缓存，缓存中没有时从API获取并存入缓存
+async fn resolve_season_title(season_id: &str) -> Option<String> {
+    match get_cached_season_title(season_id).await {
+        Some(title) => Some(title),
+        None => fetch_and_cache_season_title(season_id).await,
+    }
+}
+
+/// 根据视频记录中的来源字段确定所属视频源
+fn video_source_key(video: &video::Model) -> Option<(&'static str, i32)> {
+    if video.source_type == Some(1) {
+        return video.source_id.map(|id| ("bangumi", id));
+    }
+    [
+        ("collection", video.collection_id),
+        ("favorite", video.favorite_id),
+        ("submission", video.submission_id),
+        ("watch_later", video.watch_later_id),
+    ]
+    .into_iter()
+    .find_map(|(source_type, id)| id.map(|id| (source_type, id)))
+}
+
+/// 筛选属于指定视频源的视频
+fn source_video_expr(
+    source_type: crate::utils::scan_id_tracker::SourceType,
+    id: i32,
+) -> sea_orm::sea_query::SimpleExpr {
+    use crate::utils::scan_id_tracker::SourceType;
+
+    match source_type {
+        SourceType::Collection => video::Column::CollectionId.eq(id),
+        SourceType::Favorite => video::Column::FavoriteId.eq(id),
+        SourceType::Submission => video::Column::SubmissionId.eq(id),
+        SourceType::WatchLater => video::Column::WatchLaterId.eq(id),
+        SourceType::Bangumi => video::Column::SourceId.eq(id).and(video::Column::SourceType.eq(1)),
+    }
+}
+
+/// 查询搜索结果涉及的视频源名称
+async fn resolve_source_names(
+    db: &DatabaseConnection,
+    keys: &[(&'static str, i32)],
+) -> Result<std::collections::HashMap<(&'static str, i32), String>, ApiError> {
+    let ids_of = |target: &str| -> Vec<i32> {
+        keys.iter()
+            .filter(|(source_type, _)| *source_type == target)
+            .map(|(_, id)| *id)
+            .collect()
+    };
+    let (collections, favorites, submissions, bangumis) = tokio::try_join!(
+        collection::Entity::find()
+            .filter(collection::Column::Id.is_in(ids_of("collection")))
+            .select_only()
+            .columns([collection::Column::Id, collection::Column::Name])
+            .into_tuple::<(i32, String)>()
+            .all(db),
+        favorite::Entity::find()
+            .filter(favorite::Column::Id.is_in(ids_of("favorite")))
+            .select_only()
+            .columns([favorite::Column::Id, favorite::Column::Name])
+            .into_tuple::<(i32, String)>()
+            .all(db),
+        submission::Entity::find()
+            .filter(submission::Column::Id.is_in(ids_of("submission")))
+            .select_only()
+            .columns([submission::Column::Id, submission::Column::UpperName])
+            .into_tuple::<(i32, String)>()
+            .all(db),
+        video_source::Entity::find()
+            .filter(video_source::Column::Id.is_in(ids_of("bangumi")))
+            .select_only()
+            .columns([video_source::Column::Id, video_source::Column::Name])
+            .into_tuple::<(i32, String)>()
+            .all(db),
+    )?;
+
+    let mut names = std::collections::HashMap::new();
+    for (source_type, rows) in [
+        ("collection", collections),
+        ("favorite", favorites),
+        ("submission", submissions),
+        ("bangumi", bangumis),
+    ] {
+        names.extend(rows.into_iter().map(|(id, name)| ((source_type, id), name)));
+    }
+    for id in ids_of("watch_later") {
+        names.insert(("watch_later", id), "稍后再看".to_string());
+    }
+    Ok(names)
+}
+
+/// 跨所有视频源按标题、UP主名称和BV号搜索本地视频，结果按所属视频源分组
+#[utoipa::path(
+    get,
+    path = "/api/videos/search",
+    params(
+        crate::api::request::SearchVideosRequest,
+    ),
+    responses(
+        (status = 200, body = ApiResponse<SearchVideosResponse>),
+    )
+)]
+pub async fn search_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<crate::api::request::SearchVideosRequest>,
+) -> Result<ApiResponse<SearchVideosResponse>, ApiError> {
+    let keyword = params.q.trim();
+    if keyword.is_empty() {
+        return Err(InnerApiError::BadRequest("搜索关键词不能为空".to_string()).into());
+    }
+
+    let mut query = video::Entity::find().filter(
+        Condition::any()
+            .add(video::Column::Name.contains(keyword))
+            .add(video::Column::UpperName.contains(keyword))
+            .add(video::Column::Bvid.contains(keyword)),
+    );
+    let scan_deleted = crate::config::with_config(|bundle| bundle.config.scan_deleted_videos);
+    if !scan_deleted {
+        query = query.filter(video::Column::Deleted.eq(0));
+    }
+
+    let total_count = query.clone().count(db.as_ref()).await?;
+    let videos = query
+        .order_by_desc(video::Column::Id)
+        .paginate(db.as_ref(), params.page_size.unwrap_or(20))
+        .fetch_page(params.page.unwrap_or(0))
+        .await?;
+
+    let keys: Vec<_> = videos.iter().filter_map(video_source_key).collect();
+    let source_names = resolve_source_names(db.as_ref(), &keys).await?;
+
+    let mut groups: Vec<SearchResultGroup> = Vec::new();
+    for video in videos {
+        let (source_type, source_id) = video_source_key(&video).unwrap_or(("unknown", 0));
+        let bangumi_title = match (&video.season_id, source_type) {
+            (Some(season_id), "bangumi") => resolve_season_title(season_id).await,
+            _ => None,
+        };
+        let user_tags = parse_user_tags(video.user_tags.as_deref());
+        let mut info = VideoInfo::from((
+            video.id,
+            video.name,
+            video.upper_name,
+            video.path,
+            video.category,
+            video.download_status,
+            video.cover,
+        ));
+        info.bangumi_title = bangumi_title;
+        info.user_tags = user_tags;
+
+        match groups
+            .iter_mut()
+            .find(|group| group.source_type == source_type && group.source_id == source_id)
+        {
+            Some(group) => group.videos.push(info),
+            None => groups.push(SearchResultGroup {
+                source_type: source_type.to_string(),
+                source_id,
+                source_name: source_names
+                    .get(&(source_type, source_id))
+                    .cloned()
+                    .unwrap_or_else(|| "未知来源".to_string()),
+                videos: vec![info],
+            }),
+        }
+    }
+
+    Ok(ApiResponse::ok(SearchVideosResponse { groups, total_count }))
+}
+
+/// 解析数据库中以 JSON 字符串数组保存的自定义标签
+fn parse_user_tags(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str(raw).ok()).unwrap_or_default()
+}
+
+/// 读取视频的自定义标签，修改后写回数据库
+async fn modify_video_tags(
+    db: &DatabaseConnection,
+    id: i32,
+    modify: impl FnOnce(&mut Vec<String>),
+) -> Result<VideoTagsResponse, ApiError> {
+    let Some(raw) = video::Entity::find_by_id(id)
+        .select_only()
+        .column(video::Column::UserTags)
+        .into_tuple::<Option<String>>()
+        .one(db)
+        .await?
+    else {
+        return Err(InnerApiError::NotFound(id).into());
+    };
+
+    let mut user_tags = parse_user_tags(raw.as_deref());
+    modify(&mut user_tags);
+    let stored = if user_tags.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&user_tags)?)
+    };
+    video::Entity::update_many()
+        .col_expr(video::Column::UserTags, Expr::value(stored))
+        .filter(video::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(VideoTagsResponse {
+        video_id: id,
+        user_tags,
+    })
+}
+
+/// 去掉标签首尾空白并丢弃空标签
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    tags.into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// 为视频添加自定义标签，已存在的标签会被忽略
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/tags",
+    params(
+        ("id" = i32, description = "视频ID")
+    ),
+    request_body = crate::api::request::UpdateVideoTagsRequest,
+    responses(
+        (status = 200, body = ApiResponse<VideoTagsResponse>),
+    )
+)]
+pub async fn add_video_tags(
+    Path(id): Path<i32>,
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<crate::api::request::UpdateVideoTagsRequest>,
+) -> Result<ApiResponse<VideoTagsResponse>, ApiError> {
+    let tags = normalize_tags(request.tags);
+    let response = modify_video_tags(db.as_ref(), id, |user_tags| {
+        for tag in tags {
+            if !user_tags.contains(&tag) {
+                user_tags.push(tag);
+            }
+        }
+    })
+    .await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 移除视频的自定义标签，不存在的标签会被忽略
+#[utoipa::path(
+    delete,
+    path = "/api/videos/{id}/tags",
+    params(
+        ("id" = i32, description = "视频ID")
+    ),
+    request_body = crate::api::request::UpdateVideoTagsRequest,
+    responses(
+        (status = 200, body = ApiResponse<VideoTagsResponse>),
+    )
+)]
+pub async fn remove_video_tags(
+    Path(id): Path<i32>,
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<crate::api::request::UpdateVideoTagsRequest>,
+) -> Result<ApiResponse<VideoTagsResponse>, ApiError> {
+    let tags = normalize_tags(request.tags);
+    let response = modify_video_tags(db.as_ref(), id, |user_tags| {
+        user_tags.retain(|tag| !tags.contains(tag));
+    })
+    .await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 导出时每次从数据库读取的视频数量
+const EXPORT_VIDEOS_BATCH_SIZE: u64 = 500;
+
+/// 导出视频列表中的一行
+#[derive(Serialize)]
+struct ExportedVideo {
+    bvid: String,
+    title: String,
+    upper_name: String,
+    path: String,
+    category: i32,
+    download_status: String,
+}
+
+impl ExportedVideo {
+    fn to_csv_line(&self) -> String {
+        let fields = [
+            self.bvid.as_str(),
+            self.title.as_str(),
+            self.upper_name.as_str(),
+            self.path.as_str(),
+            &self.category.to_string(),
+            self.download_status.as_str(),
+        ]
+        .map(escape_csv_field);
+        format!("{}\r\n", fields.join(","))
+    }
+}
+
+/// 按 RFC 4180 转义 CSV 字段，包含分隔符、引号或换行时用双引号包裹
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 分批读取视频并逐行生成导出内容，避免把完整列表缓存在内存中
+fn export_videos_stream(
+    db: Arc<DatabaseConnection>,
+    query: sea_orm::Select<video::Entity>,
+    json: bool,
+) -> impl futures::Stream<Item = Result<String>> {
+    async_stream::try_stream! {
+        // CSV 带上 BOM，方便表格软件正确识别中文
+        let header = if json {
+            "[".to_string()
+        } else {
+            "\u{feff}bvid,title,upper_name,path,category,download_status\r\n".to_string()
+        };
+        yield header;
+        let mut paginator = query
+            .select_only()
+            .columns([
+                video::Column::Bvid,
+                video::Column::Name,
+                video::Column::UpperName,
+                video::Column::Path,
+                video::Column::Category,
+                video::Column::DownloadStatus,
+            ])
+            .into_tuple::<(String, String, String, String, i32, u32)>()
+            .paginate(db.as_ref(), EXPORT_VIDEOS_BATCH_SIZE);
+        let mut first = true;
+        while let Some(rows) = paginator.fetch_and_next().await? {
+            let mut chunk = String::new();
+            for (bvid, title, upper_name, path, category, download_status) in rows {
+                let video = ExportedVideo {
+                    bvid,
+                    title,
+                    upper_name,
+                    path,
+                    category,
+                    download_status: VideoStatus::from(download_status)
+                        .describe(&crate::utils::status::VIDEO_SUBTASK_NAMES),
+                };
+                if json {
+                    if !first {
+                        chunk.push(',');
+                    }
+                    chunk.push_str(&serde_json::to_string(&video)?);
+                } else {
+                    chunk.push_str(&video.to_csv_line());
+                }
+                first = false;
+            }
+            yield chunk;
+        }
+        if json {
+            yield "]".to_string();
+        }
+    }
+}
+
+/// 按与视频列表相同的筛选条件导出全部匹配的视频，支持 CSV 与 JSON 格式
+#[utoipa::path(
+    get,
+    path = "/api/videos/export",
+    params(
+        VideosRequest,
+        crate::api::request::ExportVideosRequest,
+    ),
+    responses(
+        (status = 200, description = "导出的视频列表文件"),
+    )
+)]
+pub async fn export_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<VideosRequest>,
+    Query(export): Query<crate::api::request::ExportVideosRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::http::header;
+
+    let (json, content_type, extension) = match export.format.as_deref().unwrap_or("csv") {
+        "csv" => (false, "text/csv; charset=utf-8", "csv"),
+        "json" => (true, "application/json; charset=utf-8", "json"),
+        other => {
+            return Err(InnerApiError::BadRequest(format!("不支持的导出格式: {}，仅支持 csv 或 json", other)).into());
+        }
+    };
+    let file_name = format!("videos-{}.{}", chrono::Local::now().format("%Y%m%d-%H%M%S"), extension);
+    let stream = export_videos_stream(db, filtered_videos_query(&params), json);
+
+    let response = axum::response::Response::builder()
+        .status(200)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        )
+        .body(axum::body::Body::from_stream(stream))
+        .map_err(|e| InnerApiError::BadRequest(format!("构建响应失败: {}", e)))?;
+
+    Ok(response)
+}
+
 /// 获取视频详细信息，包括关联的所有 page
 #[utoipa::path(
     get,
@@ -709,6 +1197,7 @@ pub async fn get_video(
             video::Column::Cover,
             video::Column::SeasonId,
             video::Column::SourceType,
+            video::Column::UserTags,
         ])
         .into_tuple::<(
             i32,
@@ -720,17 +1209,20 @@ pub async fn get_video(
             String,
             Option<String>,
             Option<i32>,
+            Option<String>,
         )>()
         .one(db.as_ref())
         .await?;
 
-    let Some((_id, name, upper_name, path, category, download_status, cover, season_id, source_type)) = raw_video
+    let Some((_id, name, upper_name, path, category, download_status, cover, season_id, source_type, user_tags)) =
+        raw_video
     else {
         return Err(InnerApiError::NotFound(id).into());
     };
 
     // 创建VideoInfo并填充bangumi_title
     let mut video_info = VideoInfo::from((_id, name, upper_name, path, category, download_status, cover));
+    video_info.user_tags = parse_user_tags(user_tags.as_deref());
 
     // 为番剧类型的视频填充真实标题
     if source_type == Some(1) && season_id.is_some() {
@@ -875,17 +1367,13 @@ pub async fn reset_video(
             .await?;
         }
 
-        if !resetted_pages_info.is_empty() {
-            for page in &resetted_pages_info {
-                page::Entity::update(page::ActiveModel {
-                    id: Unchanged(page.id),
-                    download_status: Set(PageStatus::from(page.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        batch_update_page_status(
+            &txn,
+            resetted_pages_info
+                .iter()
+                .map(|page| (page.id, PageStatus::from(page.download_status).into())),
+        )
+        .await?;
 
         txn.commit().await?;
     }
@@ -934,8 +1422,6 @@ pub async fn reset_all_videos(
     Extension(db): Extension<Arc<DatabaseConnection>>,
     Query(params): Query<crate::api::request::VideosRequest>,
 ) -> Result<ApiResponse<ResetAllVideosResponse>, ApiError> {
-    use std::collections::HashSet;
-
     // 构建查询条件，与get_videos保持一致
     let mut video_query = video::Entity::find();
 
@@ -1021,22 +1507,140 @@ pub async fn reset_all_videos(
 
     // 获取force参数，默认为false
     let force_reset = params.force.unwrap_or(false);
+    let response = reset_videos_and_pages(db.as_ref(), all_videos, all_pages, force_reset).await?;
+    // 触发立即扫描（缩短等待）
+    crate::task::resume_scanning();
+    Ok(ApiResponse::ok(response))
+}
 
-    // 处理页面重置
-    let resetted_pages_info = all_pages
-        .into_iter()
-        .filter_map(|(id, pid, name, download_status, video_id)| {
-            let mut page_status = PageStatus::from(download_status);
-            let should_reset = if force_reset {
-                page_status.reset_all()
-            } else {
-                page_status.reset_failed()
-            };
-            if should_reset {
-                let page_info = PageInfo::from((id, pid, name, page_status.into()));
-                Some((page_info, video_id))
-            } else {
-                None
+/// 按视频ID列表批量重置视频和页面的失败状态，不存在的ID会被忽略
+#[utoipa::path(
+    post,
+    path = "/api/videos/batch-reset",
+    request_body = crate::api::request::BatchResetVideosRequest,
+    responses(
+        (status = 200, body = ApiResponse<ResetAllVideosResponse>),
+    )
+)]
+pub async fn batch_reset_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::BatchResetVideosRequest>,
+) -> Result<ApiResponse<ResetAllVideosResponse>, ApiError> {
+    if params.video_ids.is_empty() {
+        return Ok(ApiResponse::ok(ResetAllVideosResponse {
+            resetted: false,
+            resetted_videos_count: 0,
+            resetted_pages_count: 0,
+        }));
+    }
+
+    let (all_videos, all_pages) = tokio::try_join!(
+        video::Entity::find()
+            .filter(video::Column::Id.is_in(params.video_ids.clone()))
+            .select_only()
+            .columns([
+                video::Column::Id,
+                video::Column::Name,
+                video::Column::UpperName,
+                video::Column::Path,
+                video::Column::Category,
+                video::Column::DownloadStatus,
+                video::Column::Cover,
+            ])
+            .into_tuple::<(i32, String, String, String, i32, u32, String)>()
+            .all(db.as_ref()),
+        page::Entity::find()
+            .filter(page::Column::VideoId.is_in(params.video_ids.clone()))
+            .select_only()
+            .columns([
+                page::Column::Id,
+                page::Column::Pid,
+                page::Column::Name,
+                page::Column::DownloadStatus,
+                page::Column::VideoId,
+            ])
+            .into_tuple::<(i32, i32, String, u32, i32)>()
+            .all(db.as_ref())
+    )?;
+
+    let response = reset_videos_and_pages(db.as_ref(), all_videos, all_pages, params.force).await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 单条 UPDATE 语句中 IN 子句包含的最大 ID 数量，避免超出 SQLite 的参数数量限制
+const STATUS_UPDATE_CHUNK_SIZE: usize = 500;
+
+/// 将 (id, 新状态) 按状态值分组并切块，每一块可以用一条 update_many 写回
+fn group_ids_by_status(items: impl IntoIterator<Item = (i32, u32)>) -> Vec<(u32, Vec<i32>)> {
+    let mut groups: std::collections::BTreeMap<u32, Vec<i32>> = std::collections::BTreeMap::new();
+    for (id, status) in items {
+        groups.entry(status).or_default().push(id);
+    }
+    groups
+        .into_iter()
+        .flat_map(|(status, ids)| {
+            ids.chunks(STATUS_UPDATE_CHUNK_SIZE)
+                .map(|chunk| (status, chunk.to_vec()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// 按状态值分组批量写回视频的下载状态，enable_auto_download 为 true 时同时开启自动下载
+async fn batch_update_video_status<C: ConnectionTrait>(
+    conn: &C,
+    videos: impl IntoIterator<Item = (i32, u32)>,
+    enable_auto_download: bool,
+) -> Result<(), sea_orm::DbErr> {
+    for (status, ids) in group_ids_by_status(videos) {
+        let mut update = video::Entity::update_many().col_expr(video::Column::DownloadStatus, Expr::value(status));
+        if enable_auto_download {
+            update = update.col_expr(video::Column::AutoDownload, Expr::value(true));
+        }
+        update.filter(video::Column::Id.is_in(ids)).exec(conn).await?;
+    }
+    Ok(())
+}
+
+/// 按状态值分组批量写回分页的下载状态
+async fn batch_update_page_status<C: ConnectionTrait>(
+    conn: &C,
+    pages: impl IntoIterator<Item = (i32, u32)>,
+) -> Result<(), sea_orm::DbErr> {
+    for (status, ids) in group_ids_by_status(pages) {
+        page::Entity::update_many()
+            .col_expr(page::Column::DownloadStatus, Expr::value(status))
+            .filter(page::Column::Id.is_in(ids))
+            .exec(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 按 VideoStatus/PageStatus 的重置规则重置给定的视频和分页，并在一个事务中写回数据库
+async fn reset_videos_and_pages(
+    db: &DatabaseConnection,
+    all_videos: Vec<(i32, String, String, String, i32, u32, String)>,
+    all_pages: Vec<(i32, i32, String, u32, i32)>,
+    force_reset: bool,
+) -> Result<ResetAllVideosResponse> {
+    use std::collections::HashSet;
+
+    // 处理页面重置
+    let resetted_pages_info = all_pages
+        .into_iter()
+        .filter_map(|(id, pid, name, download_status, video_id)| {
+            let mut page_status = PageStatus::from(download_status);
+            let should_reset = if force_reset {
+                page_status.reset_all()
+            } else {
+                page_status.reset_failed()
+            };
+            if should_reset {
+                let page_info = PageInfo::from((id, pid, name, page_status.into()));
+                Some((page_info, video_id))
+            } else {
+                None
             }
         })
         .collect::<Vec<_>>();
@@ -1078,60 +1682,144 @@ pub async fn reset_all_videos(
     if resetted {
         let txn = db.begin().await?;
 
-        // 批量更新视频状态 + 开启自动下载
-        if !resetted_videos_info.is_empty() {
-            for video in &resetted_videos_info {
-                video::Entity::update(video::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(video.id),
-                    download_status: sea_orm::Set(VideoStatus::from(video.download_status).into()),
-                    auto_download: sea_orm::Set(true),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新视频状态，并开启自动下载避免被过滤（与 scan 流程对齐）
+        batch_update_video_status(
+            &txn,
+            resetted_videos_info
+                .iter()
+                .map(|video| (video.id, VideoStatus::from(video.download_status).into())),
+            true,
+        )
+        .await?;
 
-        // 批量更新页面状态
-        if !resetted_pages_info.is_empty() {
-            for page in &resetted_pages_info {
-                page::Entity::update(page::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(page.id),
-                    download_status: sea_orm::Set(PageStatus::from(page.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新页面状态
+        batch_update_page_status(
+            &txn,
+            resetted_pages_info
+                .iter()
+                .map(|page| (page.id, PageStatus::from(page.download_status).into())),
+        )
+        .await?;
 
         txn.commit().await?;
-
-        // 开启这些视频的自动下载，避免被过滤（与 scan 流程对齐）
-        if !resetted_videos_info.is_empty() {
-            for video in &resetted_videos_info {
-                video::Entity::update(video::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(video.id),
-                    auto_download: sea_orm::Set(true),
-                    ..Default::default()
-                })
-                .exec(db.as_ref())
-                .await?;
-            }
-        }
     }
 
-    // 触发立即扫描（缩短等待）
-    crate::task::resume_scanning();
     // 触发立即扫描（缩短等待）
     if resetted {
         crate::task::resume_scanning();
     }
-    Ok(ApiResponse::ok(ResetAllVideosResponse {
+    Ok(ResetAllVideosResponse {
         resetted,
         resetted_videos_count: resetted_videos_info.len(),
         resetted_pages_count: resetted_pages_info.len(),
-    }))
+    })
+}
+
+/// 重新生成视频的元数据（NFO、封面、弹幕），不重新下载视频文件
+///
+/// 仅将这些已成功的子任务标记为重新执行，下一轮扫描时按当前配置与数据库中保存的视频信息重新生成
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/refresh-metadata",
+    params(
+        ("id" = i32, Path, description = "视频ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<ResetAllVideosResponse>),
+    )
+)]
+pub async fn refresh_video_metadata(
+    Path(id): Path<i32>,
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<ResetAllVideosResponse>, ApiError> {
+    if video::Entity::find_by_id(id).one(db.as_ref()).await?.is_none() {
+        return Err(InnerApiError::NotFound(id).into());
+    }
+    let response = refresh_metadata_where(db.as_ref(), video::Column::Id.eq(id), None).await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 按视频ID列表批量重新生成元数据（NFO、封面、弹幕），不存在的ID会被忽略
+#[utoipa::path(
+    post,
+    path = "/api/videos/batch-refresh-metadata",
+    request_body = crate::api::request::BatchRefreshMetadataRequest,
+    responses(
+        (status = 200, body = ApiResponse<ResetAllVideosResponse>),
+    )
+)]
+pub async fn batch_refresh_video_metadata(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::BatchRefreshMetadataRequest>,
+) -> Result<ApiResponse<ResetAllVideosResponse>, ApiError> {
+    if params.video_ids.is_empty() {
+        return Ok(ApiResponse::ok(ResetAllVideosResponse {
+            resetted: false,
+            resetted_videos_count: 0,
+            resetted_pages_count: 0,
+        }));
+    }
+    let response = refresh_metadata_where(db.as_ref(), video::Column::Id.is_in(params.video_ids), None).await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 重新生成视频源下所有视频的元数据（NFO、封面、弹幕），不重新下载视频文件
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/{source_type}/{id}/refresh-metadata",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<ResetAllVideosResponse>),
+    )
+)]
+pub async fn refresh_source_metadata(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<ApiResponse<ResetAllVideosResponse>, ApiError> {
+    let Some(scan_source_type) = crate::utils::scan_id_tracker::SourceType::parse(&source_type) else {
+        return Err(InnerApiError::BadRequest(format!("不支持的视频源类型: {}", source_type)).into());
+    };
+    let response = refresh_metadata_where(
+        db.as_ref(),
+        source_video_expr(scan_source_type, id),
+        Some((scan_source_type, id)),
+    )
+    .await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 重置符合条件的视频的元数据子任务，并请求扫描以尽快重新生成
+///
+/// 指定了视频源时只扫描该视频源，否则请求扫描所有视频源
+async fn refresh_metadata_where(
+    db: &DatabaseConnection,
+    expr: sea_orm::sea_query::SimpleExpr,
+    source: Option<(crate::utils::scan_id_tracker::SourceType, i32)>,
+) -> Result<ResetAllVideosResponse> {
+    use crate::utils::model::{reset_completed_subtasks, METADATA_PAGE_SUBTASKS, METADATA_VIDEO_SUBTASKS};
+
+    let (videos, pages) =
+        reset_completed_subtasks(Some(expr), &METADATA_VIDEO_SUBTASKS, &METADATA_PAGE_SUBTASKS, db).await?;
+    let resetted = videos > 0 || pages > 0;
+    if resetted {
+        info!("已标记 {} 个视频、{} 个分页重新生成元数据", videos, pages);
+        match source {
+            Some((source_type, id)) => {
+                crate::task::TASK_CONTROLLER.request_source_scan(source_type, id);
+            }
+            None => {
+                crate::task::TASK_CONTROLLER.request_full_scan(false);
+            }
+        }
+    }
+    Ok(ResetAllVideosResponse {
+        resetted,
+        resetted_videos_count: videos,
+        resetted_pages_count: pages,
+    })
 }
 
 /// 强制重置特定任务状态（不管当前状态）
@@ -1320,31 +2008,24 @@ pub async fn reset_specific_tasks(
     if resetted {
         let txn = db.begin().await?;
 
-        // 批量更新视频状态
-        if !resetted_videos_info.is_empty() {
-            for video in &resetted_videos_info {
-                video::Entity::update(video::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(video.id),
-                    download_status: sea_orm::Set(VideoStatus::from(video.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新视频状态
+        batch_update_video_status(
+            &txn,
+            resetted_videos_info
+                .iter()
+                .map(|video| (video.id, VideoStatus::from(video.download_status).into())),
+            false,
+        )
+        .await?;
 
-        // 批量更新页面状态
-        if !resetted_pages_info.is_empty() {
-            for page in &resetted_pages_info {
-                page::Entity::update(page::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(page.id),
-                    download_status: sea_orm::Set(PageStatus::from(page.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新页面状态
+        batch_update_page_status(
+            &txn,
+            resetted_pages_info
+                .iter()
+                .map(|page| (page.id, PageStatus::from(page.download_status).into())),
+        )
+        .await?;
 
         txn.commit().await?;
     }
@@ -1550,15 +2231,13 @@ pub async fn update_video_status(
         }
 
         if has_page_updates {
-            for page in &updated_pages_info {
-                page::Entity::update(page::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(page.id),
-                    download_status: sea_orm::Set(PageStatus::from(page.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
+            batch_update_page_status(
+                &txn,
+                updated_pages_info
+                    .iter()
+                    .map(|page| (page.id, PageStatus::from(page.download_status).into())),
+            )
+            .await?;
         }
 
         txn.commit().await?;
@@ -1656,6 +2335,14 @@ pub async fn add_video_source(
             ep_id: params.ep_id.clone(),
             download_all_seasons: params.download_all_seasons,
             selected_seasons: params.selected_seasons.clone(),
+            episode_range: params.episode_range.clone(),
+            scan_interval: params.scan_interval,
+            pubtime_after: params.pubtime_after.clone(),
+            pubtime_before: params.pubtime_before.clone(),
+            title_include: params.title_include.clone(),
+            title_exclude: params.title_exclude.clone(),
+            title_regex: params.title_regex,
+            priority: params.priority.unwrap_or(crate::task::DEFAULT_TASK_PRIORITY),
             task_id: task_id.clone(),
         };
 
@@ -1667,6 +2354,7 @@ pub async fn add_video_source(
         );
 
         return Ok(ApiResponse::ok(AddVideoSourceResponse {
+            task_id: Some(task_id),
             success: true,
             source_id: 0, // 队列中的任务还没有ID
             source_type: params.source_type,
@@ -1688,6 +2376,24 @@ pub async fn add_video_source_internal(
 ) -> Result<AddVideoSourceResponse, ApiError> {
     // 使用主数据库连接
 
+    // 0 视为未设置，使用全局扫描间隔
+    let scan_interval = params
+        .scan_interval
+        .filter(|interval| *interval > 0)
+        .map(|interval| interval as i64);
+
+    let (pubtime_after, pubtime_before) =
+        normalize_pubtime_window(params.pubtime_after.clone(), params.pubtime_before.clone())?;
+    let title_regex = params.title_regex.unwrap_or(false);
+    let title_include = title_terms_column(&normalize_title_terms(
+        params.title_include.clone().unwrap_or_default(),
+        title_regex,
+    )?);
+    let title_exclude = title_terms_column(&normalize_title_terms(
+        params.title_exclude.clone().unwrap_or_default(),
+        title_regex,
+    )?);
+
     let txn = db.begin().await?;
 
     let result = match params.source_type.as_str() {
@@ -1781,6 +2487,30 @@ pub async fn add_video_source_internal(
                 latest_row_at: sea_orm::Set("1970-01-01 00:00:00".to_string()),
                 enabled: sea_orm::Set(true),
                 scan_deleted_videos: sea_orm::Set(false),
+                scan_interval: sea_orm::Set(scan_interval),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                title_include: sea_orm::Set(title_include.clone()),
+                title_exclude: sea_orm::Set(title_exclude.clone()),
+                title_regex: sea_orm::Set(title_regex),
+                strm_mode: sea_orm::Set(false),
+                strm_refresh: sea_orm::Set(false),
+                strict_incremental: sea_orm::Set(false),
+                webdav_upload: sea_orm::Set(false),
+                retention_days: sea_orm::Set(None),
+                retention_max_count: sea_orm::Set(None),
+                audio_only: sea_orm::Set(false),
+                upgrade_quality: sea_orm::Set(false),
+                danmaku_embed_mode: sea_orm::Set(crate::config::DanmakuEmbedMode::None.as_str().to_string()),
+                download_cover: sea_orm::Set(true),
+                download_nfo: sea_orm::Set(true),
+                download_danmaku: sea_orm::Set(true),
+                download_subtitle: sea_orm::Set(true),
+                last_error: sea_orm::Set(None),
+                last_error_at: sea_orm::Set(None),
+                limit_newest: sea_orm::Set(None),
+                nfo_time_type: sea_orm::Set(None),
+                paused: sea_orm::Set(false),
                 cover: sea_orm::Set(cover_url),
             };
 
@@ -1789,6 +2519,7 @@ pub async fn add_video_source_internal(
             info!("合集添加成功: {} (ID: {}, UP主: {})", collection_name, s_id, up_id);
 
             AddVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: insert_result.last_insert_id,
                 source_type: "collection".to_string(),
@@ -1824,6 +2555,30 @@ pub async fn add_video_source_internal(
                 latest_row_at: sea_orm::Set("1970-01-01 00:00:00".to_string()),
                 enabled: sea_orm::Set(true),
                 scan_deleted_videos: sea_orm::Set(false),
+                scan_interval: sea_orm::Set(scan_interval),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                title_include: sea_orm::Set(title_include.clone()),
+                title_exclude: sea_orm::Set(title_exclude.clone()),
+                title_regex: sea_orm::Set(title_regex),
+                strm_mode: sea_orm::Set(false),
+                strm_refresh: sea_orm::Set(false),
+                strict_incremental: sea_orm::Set(false),
+                webdav_upload: sea_orm::Set(false),
+                retention_days: sea_orm::Set(None),
+                retention_max_count: sea_orm::Set(None),
+                audio_only: sea_orm::Set(false),
+                upgrade_quality: sea_orm::Set(false),
+                danmaku_embed_mode: sea_orm::Set(crate::config::DanmakuEmbedMode::None.as_str().to_string()),
+                download_cover: sea_orm::Set(true),
+                download_nfo: sea_orm::Set(true),
+                download_danmaku: sea_orm::Set(true),
+                download_subtitle: sea_orm::Set(true),
+                last_error: sea_orm::Set(None),
+                last_error_at: sea_orm::Set(None),
+                limit_newest: sea_orm::Set(None),
+                nfo_time_type: sea_orm::Set(None),
+                paused: sea_orm::Set(false),
             };
 
             let insert_result = favorite::Entity::insert(favorite).exec(&txn).await?;
@@ -1831,6 +2586,7 @@ pub async fn add_video_source_internal(
             info!("收藏夹添加成功: {} (ID: {})", favorite_name, f_id);
 
             AddVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: insert_result.last_insert_id,
                 source_type: "favorite".to_string(),
@@ -1866,6 +2622,30 @@ pub async fn add_video_source_internal(
                 latest_row_at: sea_orm::Set("1970-01-01 00:00:00".to_string()),
                 enabled: sea_orm::Set(true),
                 scan_deleted_videos: sea_orm::Set(false),
+                scan_interval: sea_orm::Set(scan_interval),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                title_include: sea_orm::Set(title_include.clone()),
+                title_exclude: sea_orm::Set(title_exclude.clone()),
+                title_regex: sea_orm::Set(title_regex),
+                strm_mode: sea_orm::Set(false),
+                strm_refresh: sea_orm::Set(false),
+                strict_incremental: sea_orm::Set(false),
+                webdav_upload: sea_orm::Set(false),
+                retention_days: sea_orm::Set(None),
+                retention_max_count: sea_orm::Set(None),
+                audio_only: sea_orm::Set(false),
+                upgrade_quality: sea_orm::Set(false),
+                danmaku_embed_mode: sea_orm::Set(crate::config::DanmakuEmbedMode::None.as_str().to_string()),
+                download_cover: sea_orm::Set(true),
+                download_nfo: sea_orm::Set(true),
+                download_danmaku: sea_orm::Set(true),
+                download_subtitle: sea_orm::Set(true),
+                last_error: sea_orm::Set(None),
+                last_error_at: sea_orm::Set(None),
+                limit_newest: sea_orm::Set(None),
+                nfo_time_type: sea_orm::Set(None),
+                paused: sea_orm::Set(false),
                 selected_videos: sea_orm::Set(
                     params
                         .selected_videos
@@ -1878,6 +2658,7 @@ pub async fn add_video_source_internal(
             info!("UP主投稿添加成功: {} (ID: {})", upper_name, upper_id);
 
             AddVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: insert_result.last_insert_id,
                 source_type: "submission".to_string(),
@@ -1890,8 +2671,25 @@ pub async fn add_video_source_internal(
                 return Err(anyhow!("番剧标识不能全部为空，请至少提供 season_id、media_id 或 ep_id 中的一个").into());
             }
 
+            // 校验集数范围，格式错误时直接拒绝添加
+            let episode_range = params
+                .episode_range
+                .as_deref()
+                .map(str::trim)
+                .filter(|range| !range.is_empty())
+                .map(|range| {
+                    crate::adapter::EpisodeRange::parse(range)
+                        .map(|_| range.to_string())
+                        .map_err(|e| anyhow!("无效的集数范围「{}」: {:#}", range, e))
+                })
+                .transpose()?;
+
             // 如果指定了合并目标，进行合并操作并提交事务
             if let Some(merge_target_id) = params.merge_to_source_id {
+                // 集数范围作用于整个视频源，合并时会误过滤目标源中其它季度的剧集，因此不支持同时指定
+                if episode_range.is_some() {
+                    return Err(anyhow!("合并到已有番剧源时不支持指定集数范围").into());
+                }
                 let result = handle_bangumi_merge_to_existing(&txn, params, merge_target_id).await?;
                 txn.commit().await?;
                 return Ok(result);
@@ -2014,6 +2812,21 @@ pub async fn add_video_source_internal(
                     merge_message.push_str(&format!("番剧名称已更新为: {}", params.name));
                 }
 
+                // 更新集数范围（如果提供了不同的范围）
+                let episode_range_changed = episode_range.is_some() && episode_range != existing.episode_range;
+                if episode_range_changed {
+                    existing.episode_range = episode_range.clone();
+                    updated = true;
+
+                    if !merge_message.is_empty() {
+                        merge_message.push('，');
+                    }
+                    merge_message.push_str(&format!(
+                        "集数范围已更新为: {}",
+                        episode_range.as_deref().unwrap_or_default()
+                    ));
+                }
+
                 if updated {
                     // 更新数据库记录 - 修复：正确使用ActiveModel更新
                     let mut existing_update = video_source::ActiveModel {
@@ -2045,6 +2858,11 @@ pub async fn add_video_source_internal(
                         existing_update.name = sea_orm::Set(params.name.clone());
                     }
 
+                    // 更新集数范围（如果有变更）
+                    if episode_range_changed {
+                        existing_update.episode_range = sea_orm::Set(episode_range);
+                    }
+
                     video_source::Entity::update(existing_update).exec(&txn).await?;
 
                     // 确保目标路径存在
@@ -2053,6 +2871,7 @@ pub async fn add_video_source_internal(
                     info!("番剧配置合并成功: {}", merge_message);
 
                     AddVideoSourceResponse {
+                        task_id: None,
                         success: true,
                         source_id: existing.id,
                         source_type: "bangumi".to_string(),
@@ -2061,6 +2880,7 @@ pub async fn add_video_source_internal(
                 } else {
                     // 没有实际更新
                     AddVideoSourceResponse {
+                        task_id: None,
                         success: true,
                         source_id: existing.id,
                         source_type: "bangumi".to_string(),
@@ -2157,6 +2977,13 @@ pub async fn add_video_source_internal(
                     ep_id: sea_orm::Set(params.ep_id),
                     download_all_seasons: sea_orm::Set(Some(download_all_seasons)),
                     selected_seasons: sea_orm::Set(selected_seasons_json),
+                    episode_range: sea_orm::Set(episode_range),
+                    scan_interval: sea_orm::Set(scan_interval),
+                    pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                    pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                    title_include: sea_orm::Set(title_include.clone()),
+                    title_exclude: sea_orm::Set(title_exclude.clone()),
+                    title_regex: sea_orm::Set(title_regex),
                     ..Default::default()
                 };
 
@@ -2178,6 +3005,7 @@ pub async fn add_video_source_internal(
                 info!("新番剧添加完成: {}", success_message);
 
                 AddVideoSourceResponse {
+                    task_id: None,
                     success: true,
                     source_id: insert_result.last_insert_id,
                     source_type: "bangumi".to_string(),
@@ -2209,6 +3037,30 @@ pub async fn add_video_source_internal(
                 latest_row_at: sea_orm::Set(crate::utils::time_format::now_standard_string()),
                 enabled: sea_orm::Set(true),
                 scan_deleted_videos: sea_orm::Set(false),
+                scan_interval: sea_orm::Set(scan_interval),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                title_include: sea_orm::Set(title_include.clone()),
+                title_exclude: sea_orm::Set(title_exclude.clone()),
+                title_regex: sea_orm::Set(title_regex),
+                strm_mode: sea_orm::Set(false),
+                strm_refresh: sea_orm::Set(false),
+                strict_incremental: sea_orm::Set(false),
+                webdav_upload: sea_orm::Set(false),
+                retention_days: sea_orm::Set(None),
+                retention_max_count: sea_orm::Set(None),
+                audio_only: sea_orm::Set(false),
+                upgrade_quality: sea_orm::Set(false),
+                danmaku_embed_mode: sea_orm::Set(crate::config::DanmakuEmbedMode::None.as_str().to_string()),
+                download_cover: sea_orm::Set(true),
+                download_nfo: sea_orm::Set(true),
+                download_danmaku: sea_orm::Set(true),
+                download_subtitle: sea_orm::Set(true),
+                last_error: sea_orm::Set(None),
+                last_error_at: sea_orm::Set(None),
+                limit_newest: sea_orm::Set(None),
+                nfo_time_type: sea_orm::Set(None),
+                paused: sea_orm::Set(false),
             };
 
             let insert_result = watch_later::Entity::insert(watch_later).exec(&txn).await?;
@@ -2216,6 +3068,7 @@ pub async fn add_video_source_internal(
             info!("稍后观看添加成功，保存路径: {}", params.path);
 
             AddVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: insert_result.last_insert_id,
                 source_type: "watch_later".to_string(),
@@ -2247,7 +3100,9 @@ pub async fn reload_config(Extension(db): Extension<Arc<DatabaseConnection>>) ->
         // 正在扫描，将重载配置任务加入队列
         let task_id = uuid::Uuid::new_v4().to_string();
         let reload_task = crate::task::ReloadConfigTask {
+            credential: None,
             task_id: task_id.clone(),
+            priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
         };
 
         crate::task::enqueue_reload_task(reload_task, &db).await?;
@@ -2477,7 +3332,8 @@ pub async fn update_video_source_enabled_internal(
     params(
         ("source_type" = String, Path, description = "视频源类型"),
         ("id" = i32, Path, description = "视频源ID"),
-        ("delete_local_files" = bool, Query, description = "是否删除本地文件")
+        ("delete_local_files" = bool, Query, description = "是否删除本地文件"),
+        ("priority" = Option<i32>, Query, description = "任务优先级，正在扫描时生效，数值越大越先执行")
     ),
     responses(
         (status = 200, body = ApiResponse<DeleteVideoSourceResponse>),
@@ -2498,6 +3354,7 @@ pub async fn delete_video_source(
             source_type: source_type.clone(),
             source_id: id,
             delete_local_files,
+            priority: params.priority.unwrap_or(crate::task::DEFAULT_TASK_PRIORITY),
             task_id: task_id.clone(),
         };
 
@@ -2506,6 +3363,7 @@ pub async fn delete_video_source(
         info!("检测到正在扫描，删除任务已加入队列等待处理: {} ID={}", source_type, id);
 
         return Ok(ApiResponse::ok(crate::api::response::DeleteVideoSourceResponse {
+            task_id: Some(task_id),
             success: true,
             source_id: id,
             source_type,
@@ -2541,6 +3399,8 @@ pub async fn delete_video(
         let task_id = uuid::Uuid::new_v4().to_string();
         let delete_task = crate::task::DeleteVideoTask {
             video_id: id,
+            delete_local_files: true,
+            priority: crate::task::DEFAULT_TASK_PRIORITY,
             task_id: task_id.clone(),
         };
 
@@ -2549,6 +3409,7 @@ pub async fn delete_video(
         info!("检测到正在扫描，视频删除任务已加入队列等待处理: 视频ID={}", id);
 
         return Ok(ApiResponse::ok(crate::api::response::DeleteVideoResponse {
+            task_id: Some(task_id),
             success: true,
             video_id: id,
             message: "正在扫描中，视频删除任务已加入队列，将在扫描完成后自动处理".to_string(),
@@ -2556,8 +3417,9 @@ pub async fn delete_video(
     }
 
     // 没有扫描，直接执行删除
-    match delete_video_internal(db, id).await {
+    match delete_video_internal(db, id, true).await {
         Ok(_) => Ok(ApiResponse::ok(crate::api::response::DeleteVideoResponse {
+            task_id: None,
             success: true,
             video_id: id,
             message: "视频已成功删除".to_string(),
@@ -2566,53 +3428,298 @@ pub async fn delete_video(
     }
 }
 
-/// 内部删除视频函数（用于队列处理和直接调用）
-pub async fn delete_video_internal(db: Arc<DatabaseConnection>, video_id: i32) -> Result<(), ApiError> {
-    use bili_sync_entity::video;
-    use sea_orm::*;
-
-    // 检查视频是否存在
-    let video = video::Entity::find_by_id(video_id).one(db.as_ref()).await?;
+/// 按视频ID列表批量删除视频，每个视频作为一个删除任务加入队列
+#[utoipa::path(
+    post,
+    path = "/api/videos/batch-delete",
+    request_body = crate::api::request::BatchDeleteVideosRequest,
+    responses(
+        (status = 200, body = ApiResponse<BatchDeleteVideosResponse>),
+    )
+)]
+pub async fn batch_delete_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::BatchDeleteVideosRequest>,
+) -> Result<ApiResponse<BatchDeleteVideosResponse>, ApiError> {
+    let mut video_ids = params.video_ids;
+    video_ids.sort_unstable();
+    video_ids.dedup();
+
+    // 只有存在且未被删除的视频才需要加入队列
+    let deletable_ids: std::collections::HashSet<i32> = video::Entity::find()
+        .filter(video::Column::Id.is_in(video_ids.clone()))
+        .filter(video::Column::Deleted.eq(0))
+        .select_only()
+        .column(video::Column::Id)
+        .into_tuple::<i32>()
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .collect();
 
-    let video = match video {
-        Some(v) => v,
-        None => {
-            return Err(crate::api::error::InnerApiError::NotFound(video_id).into());
+    let mut queued_count = 0;
+    for video_id in video_ids.iter().copied() {
+        if !deletable_ids.contains(&video_id)
+            || crate::task::VIDEO_DELETE_TASK_QUEUE
+                .has_pending_delete_task(video_id, &db)
+                .await?
+        {
+            continue;
         }
+        let delete_task = crate::task::DeleteVideoTask {
+            video_id,
+            delete_local_files: params.delete_local_files,
+            priority: crate::task::DEFAULT_TASK_PRIORITY,
+            task_id: uuid::Uuid::new_v4().to_string(),
+        };
+        crate::task::enqueue_video_delete_task(delete_task, &db).await?;
+        queued_count += 1;
+    }
+    let skipped_count = video_ids.len() - queued_count;
+
+    let message = if queued_count == 0 {
+        "没有需要删除的视频".to_string()
+    } else if crate::task::is_scanning() {
+        info!("检测到正在扫描，{} 个视频删除任务已加入队列等待处理", queued_count);
+        "正在扫描中，删除任务已加入队列，将在扫描完成后自动处理".to_string()
+    } else {
+        // 没有扫描时立即在后台处理队列
+        let db = db.clone();
+        tokio::spawn(async move {
+            if let Err(e) = crate::task::process_video_delete_tasks(db).await {
+                error!("处理批量视频删除任务失败: {:#}", e);
+            }
+        });
+        "删除任务已加入队列，正在后台处理".to_string()
     };
 
-    // 检查是否已经删除
-    if video.deleted == 1 {
-        return Err(crate::api::error::InnerApiError::BadRequest("视频已经被删除".to_string()).into());
+    Ok(ApiResponse::ok(BatchDeleteVideosResponse {
+        queued_count,
+        skipped_count,
+        message,
+    }))
+}
+
+/// 导入 BV 号或视频链接列表，归入按保存路径区分的手动列表，并立即加入扫描队列下载
+#[utoipa::path(
+    post,
+    path = "/api/videos/import-list",
+    request_body = crate::api::request::ImportVideoListRequest,
+    responses(
+        (status = 200, body = ApiResponse<ImportVideoListResponse>),
+    )
+)]
+pub async fn import_video_list(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::ImportVideoListRequest>,
+) -> Result<ApiResponse<ImportVideoListResponse>, ApiError> {
+    use crate::bilibili::CollectionType;
+    use crate::utils::scan_id_tracker::SourceType;
+    use sea_orm::{ActiveModelTrait, IntoActiveModel};
+
+    let path = params.path.trim();
+    if path.is_empty() {
+        return Err(anyhow!("保存路径不能为空").into());
     }
 
-    // 删除本地文件 - 根据page表中的路径精确删除
-    let deleted_files = delete_video_files_from_pages(db.clone(), video_id).await?;
+    let mut bvids = Vec::new();
+    let mut invalid_entries = Vec::new();
+    for entry in params
+        .videos
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+    {
+        match crate::bilibili::parse_bvid(entry) {
+            Some(bvid) if !bvids.contains(&bvid) => bvids.push(bvid),
+            Some(_) => {}
+            None => invalid_entries.push(entry.to_string()),
+        }
+    }
+    if bvids.is_empty() {
+        return Err(anyhow!("没有识别到有效的BV号").into());
+    }
 
-    if deleted_files > 0 {
-        info!("已删除 {} 个视频文件", deleted_files);
+    let txn = db.begin().await?;
 
-        // 检查视频文件夹是否为空，如果为空则删除文件夹
-        let normalized_video_path = normalize_file_path(&video.path);
-        let video_path = std::path::Path::new(&normalized_video_path);
-        if video_path.exists() {
-            match tokio::fs::read_dir(&normalized_video_path).await {
-                Ok(mut entries) => {
-                    if entries.next_entry().await.unwrap_or(None).is_none() {
-                        // 文件夹为空，删除它
-                        if let Err(e) = std::fs::remove_dir(&normalized_video_path) {
-                            warn!("删除空文件夹失败: {} - {}", normalized_video_path, e);
-                        } else {
-                            info!("已删除空文件夹: {}", normalized_video_path);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("读取文件夹失败: {} - {}", normalized_video_path, e);
-                }
+    // 已存在于任意视频源中的视频不再重复导入
+    let existing_bvids: std::collections::HashSet<String> = video::Entity::find()
+        .filter(video::Column::Bvid.is_in(bvids.clone()))
+        .filter(video::Column::Deleted.eq(0))
+        .select_only()
+        .column(video::Column::Bvid)
+        .into_tuple::<String>()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .collect();
+    let (skipped_bvids, new_bvids): (Vec<String>, Vec<String>) =
+        bvids.into_iter().partition(|bvid| existing_bvids.contains(bvid));
+
+    // 同一保存路径复用已有的手动列表
+    let manual_type: i32 = CollectionType::Manual.into();
+    let existing_manual = collection::Entity::find()
+        .filter(collection::Column::Type.eq(manual_type))
+        .filter(collection::Column::Path.eq(path))
+        .one(&txn)
+        .await?;
+    let manual = match existing_manual {
+        Some(manual) => manual,
+        None => {
+            let next_sid = collection::Entity::find()
+                .filter(collection::Column::Type.eq(manual_type))
+                .order_by_desc(collection::Column::SId)
+                .one(&txn)
+                .await?
+                .map_or(1, |manual| manual.s_id + 1);
+            let name = params
+                .name
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map_or_else(|| format!("手动列表 {}", next_sid), str::to_string);
+            collection::ActiveModel {
+                id: sea_orm::ActiveValue::NotSet,
+                s_id: sea_orm::Set(next_sid),
+                m_id: sea_orm::Set(0),
+                name: sea_orm::Set(name),
+                r#type: sea_orm::Set(manual_type),
+                path: sea_orm::Set(path.to_string()),
+                created_at: sea_orm::Set(now_standard_string()),
+                latest_row_at: sea_orm::Set("1970-01-01 00:00:00".to_string()),
+                enabled: sea_orm::Set(true),
+                scan_deleted_videos: sea_orm::Set(false),
+                scan_interval: sea_orm::Set(None),
+                pubtime_after: sea_orm::Set(None),
+                pubtime_before: sea_orm::Set(None),
+                title_include: sea_orm::Set(None),
+                title_exclude: sea_orm::Set(None),
+                title_regex: sea_orm::Set(false),
+                strm_mode: sea_orm::Set(false),
+                strm_refresh: sea_orm::Set(false),
+                strict_incremental: sea_orm::Set(false),
+                webdav_upload: sea_orm::Set(false),
+                retention_days: sea_orm::Set(None),
+                retention_max_count: sea_orm::Set(None),
+                audio_only: sea_orm::Set(false),
+                upgrade_quality: sea_orm::Set(false),
+                danmaku_embed_mode: sea_orm::Set(crate::config::DanmakuEmbedMode::None.as_str().to_string()),
+                download_cover: sea_orm::Set(true),
+                download_nfo: sea_orm::Set(true),
+                download_danmaku: sea_orm::Set(true),
+                download_subtitle: sea_orm::Set(true),
+                last_error: sea_orm::Set(None),
+                last_error_at: sea_orm::Set(None),
+                limit_newest: sea_orm::Set(None),
+                nfo_time_type: sea_orm::Set(None),
+                paused: sea_orm::Set(false),
+                cover: sea_orm::Set(None),
             }
+            .insert(&txn)
+            .await?
+        }
+    };
+
+    // 只写入 BV 号，标题、分页等详情在扫描时通过视频详情接口填充
+    if !new_bvids.is_empty() {
+        let models = new_bvids.iter().map(|bvid| video::ActiveModel {
+            id: sea_orm::ActiveValue::NotSet,
+            collection_id: sea_orm::Set(Some(manual.id)),
+            bvid: sea_orm::Set(bvid.clone()),
+            name: sea_orm::Set(bvid.clone()),
+            category: sea_orm::Set(2),
+            valid: sea_orm::Set(true),
+            auto_download: sea_orm::Set(true),
+            created_at: sea_orm::Set(now_standard_string()),
+            ..video::Model::default().into_active_model()
+        });
+        video::Entity::insert_many(models).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    let imported_count = new_bvids.len();
+    let message = if imported_count == 0 {
+        "没有需要导入的新视频".to_string()
+    } else {
+        let (_, newly_queued) = crate::task::TASK_CONTROLLER.request_source_scan(SourceType::Collection, manual.id);
+        info!(
+            "已向手动列表「{}」导入 {} 个视频，跳过 {} 个已存在的视频",
+            manual.name,
+            imported_count,
+            skipped_bvids.len()
+        );
+        if newly_queued {
+            format!("已导入 {} 个视频，已加入扫描队列等待下载", imported_count)
+        } else {
+            format!("已导入 {} 个视频，将在已排队的扫描中下载", imported_count)
+        }
+    };
+
+    Ok(ApiResponse::ok(ImportVideoListResponse {
+        source_id: manual.id,
+        source_name: manual.name,
+        imported_count,
+        skipped_bvids,
+        invalid_entries,
+        message,
+    }))
+}
+
+/// 内部删除视频函数（用于队列处理和直接调用），delete_local_files 为 false 时只做软删除并保留本地文件
+pub async fn delete_video_internal(
+    db: Arc<DatabaseConnection>,
+    video_id: i32,
+    delete_local_files: bool,
+) -> Result<(), ApiError> {
+    use bili_sync_entity::video;
+    use sea_orm::*;
+
+    // 检查视频是否存在
+    let video = video::Entity::find_by_id(video_id).one(db.as_ref()).await?;
+
+    let video = match video {
+        Some(v) => v,
+        None => {
+            return Err(crate::api::error::InnerApiError::NotFound(video_id).into());
         }
+    };
+
+    // 检查是否已经删除
+    if video.deleted == 1 {
+        return Err(crate::api::error::InnerApiError::BadRequest("视频已经被删除".to_string()).into());
+    }
+
+    // 删除文件前记录各分页所在的文件夹，删除后由此向上清理空文件夹
+    let page_dirs: Vec<std::path::PathBuf> = if delete_local_files {
+        page::Entity::find()
+            .filter(page::Column::VideoId.eq(video_id))
+            .all(db.as_ref())
+            .await?
+            .into_iter()
+            .filter_map(|page| page.path)
+            .filter_map(|path| {
+                std::path::Path::new(&normalize_file_path(&path))
+                    .parent()
+                    .map(|dir| dir.to_path_buf())
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // 删除本地文件 - 根据page表中的路径精确删除
+    let deleted_files = if delete_local_files {
+        delete_video_files_from_pages(db.clone(), video_id).await?
     } else {
+        0
+    };
+
+    if deleted_files > 0 {
+        info!("已删除 {} 个视频文件", deleted_files);
+
+        // 从分页所在文件夹向上清理空文件夹（如 Season 文件夹、视频文件夹），直到视频源根目录
+        crate::utils::empty_dirs::cleanup_video_dirs(db.as_ref(), &video, page_dirs).await;
+    } else if delete_local_files {
         debug!("未找到需要删除的文件，视频ID: {}", video_id);
     }
 
@@ -2630,7 +3737,11 @@ pub async fn delete_video_internal(db: Arc<DatabaseConnection>, video_id: i32) -
 
 /// 根据page表精确删除视频文件
 async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i32) -> Result<usize, ApiError> {
-    use tokio::fs;
+    // 按配置直接删除或移入视频源根目录下的回收站
+    let remover = match video::Entity::find_by_id(video_id).one(db.as_ref()).await? {
+        Some(video) => crate::utils::trash::remover_for_video(db.as_ref(), &video).await,
+        None => crate::utils::trash::FileRemover::Permanent,
+    };
 
     // 获取该视频的所有页面（分P）
     let pages = page::Entity::find()
@@ -2643,9 +3754,14 @@ async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i3
     for page in pages {
         if let Some(file_path) = &page.path {
             let path = std::path::Path::new(file_path);
+            // 其他视频源链接到该文件时先转移，避免删除后链接失效
+            if let Err(e) = crate::utils::dedup::release_linked_files(db.as_ref(), path).await {
+                warn!("转移被其他视频源引用的文件失败，跳过删除: {} - {:#}", file_path, e);
+                continue;
+            }
             info!("尝试删除视频文件: {}", file_path);
             if path.exists() {
-                match fs::remove_file(path).await {
+                match remover.remove_file(path) {
                     Ok(_) => {
                         debug!("已删除视频文件: {}", file_path);
                         deleted_count += 1;
@@ -2666,7 +3782,7 @@ async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i3
                 let path = std::path::Path::new(image_path);
                 info!("尝试删除封面图片: {}", image_path);
                 if path.exists() {
-                    match fs::remove_file(path).await {
+                    match remover.remove_file(path) {
                         Ok(_) => {
                             info!("已删除封面图片: {}", image_path);
                             deleted_count += 1;
@@ -2701,56 +3817,18 @@ async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i3
                     if let Some(file_stem) = video_file.file_stem() {
                         let file_stem_str = file_stem.to_string_lossy();
 
-                        // 删除同名的NFO文件
-                        let nfo_path = parent_dir.join(format!("{}.nfo", file_stem_str));
-                        if nfo_path.exists() {
-                            match fs::remove_file(&nfo_path).await {
-                                Ok(_) => {
-                                    debug!("已删除NFO文件: {:?}", nfo_path);
-                                    deleted_count += 1;
-                                }
-                                Err(e) => {
-                                    warn!("删除NFO文件失败: {:?} - {}", nfo_path, e);
-                                }
-                            }
-                        }
-
-                        // 删除封面文件 (-fanart.jpg, -thumb.jpg等)
-                        for suffix in &["fanart", "thumb"] {
-                            for ext in &["jpg", "jpeg", "png", "webp"] {
-                                let cover_path = parent_dir.join(format!("{}-{}.{}", file_stem_str, suffix, ext));
-                                if cover_path.exists() {
-                                    match fs::remove_file(&cover_path).await {
-                                        Ok(_) => {
-                                            debug!("已删除封面文件: {:?}", cover_path);
-                                            deleted_count += 1;
-                                        }
-                                        Err(e) => {
-                                            warn!("删除封面文件失败: {:?} - {}", cover_path, e);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        // 删除弹幕文件 (.zh-CN.default.ass等)
-                        let danmaku_patterns = [
-                            format!("{}.zh-CN.default.ass", file_stem_str),
-                            format!("{}.ass", file_stem_str),
-                            format!("{}.srt", file_stem_str),
-                            format!("{}.xml", file_stem_str),
-                        ];
-
-                        for pattern in &danmaku_patterns {
-                            let danmaku_path = parent_dir.join(pattern);
-                            if danmaku_path.exists() {
-                                match fs::remove_file(&danmaku_path).await {
+                        // 删除同名的附属文件（NFO、各风格封面、弹幕、字幕等），命名与下载流程共用
+                        for sidecar_path in
+                            crate::utils::page_files::sidecar_files(parent_dir, &file_stem_str, page.download_status)
+                        {
+                            if sidecar_path.exists() {
+                                match remover.remove_file(&sidecar_path) {
                                     Ok(_) => {
-                                        debug!("已删除弹幕文件: {:?}", danmaku_path);
+                                        debug!("已删除附属文件: {:?}", sidecar_path);
                                         deleted_count += 1;
                                     }
                                     Err(e) => {
-                                        warn!("删除弹幕文件失败: {:?} - {}", danmaku_path, e);
+                                        warn!("删除附属文件失败: {:?} - {}", sidecar_path, e);
                                     }
                                 }
                             }
@@ -2768,14 +3846,22 @@ async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i3
                     let video_path = std::path::Path::new(&video.path);
                     let page_path = std::path::Path::new(page_path);
 
-                    // 如果page路径包含Season文件夹，说明使用了Season结构
-                    let uses_season_structure = page_path.components().any(|component| {
-                        if let std::path::Component::Normal(name) = component {
-                            name.to_string_lossy().starts_with("Season ")
-                        } else {
-                            false
+                    // 季度文件夹中已没有其他视频时删除 season.nfo，多个视频共享同一季度文件夹时保留
+                    if let Some(season_dir) = page_path.parent() {
+                        match crate::utils::nfo::remove_unused_season_nfo(season_dir).await {
+                            Ok(true) => {
+                                info!("已删除季度文件夹中的season.nfo: {:?}", season_dir);
+                                deleted_count += 1;
+                            }
+                            Ok(false) => {}
+                            Err(e) => {
+                                warn!("删除季度文件夹中的season.nfo失败: {:?} - {}", season_dir, e);
+                            }
                         }
-                    });
+                    }
+
+                    // page位于视频文件夹下的季度子文件夹中，说明使用了Season结构
+                    let uses_season_structure = crate::utils::page_files::is_in_season_folder(video_path, page_path);
 
                     if uses_season_structure {
                         debug!("检测到Season结构，删除根目录元数据文件");
@@ -2812,17 +3898,13 @@ async fn delete_video_files_from_pages(db: Arc<DatabaseConnection>, video_id: i3
                                 }
                             };
 
-                            // 删除根目录的元数据文件
-                            let metadata_files = [
-                                "tvshow.nfo".to_string(),
-                                format!("{}-thumb.jpg", video_base_name),
-                                format!("{}-fanart.jpg", video_base_name),
-                            ];
+                            // 删除根目录的元数据文件（包含各 NFO 风格生成的图片与 .plexmatch）
+                            let metadata_files = crate::config::NFOFlavor::all_show_metadata_files(&video_base_name);
 
                             for metadata_file in &metadata_files {
                                 let metadata_path = video_path.join(metadata_file);
                                 if metadata_path.exists() {
-                                    match fs::remove_file(&metadata_path).await {
+                                    match remover.remove_file(&metadata_path) {
                                         Ok(_) => {
                                             info!("已删除Season结构根目录元数据文件: {:?}", metadata_path);
                                             deleted_count += 1;
@@ -2915,8 +3997,18 @@ pub async fn delete_video_source_internal(
 
             // 如果需要删除本地文件
             if delete_local_files {
+                // 其他视频源链接到这些文件时先转移，避免删除后链接失效
+                for video in &videos {
+                    if let Err(e) =
+                        crate::utils::dedup::release_linked_files(&txn, std::path::Path::new(&video.path)).await
+                    {
+                        warn!("转移被其他视频源引用的文件失败: {} - {:#}", video.path, e);
+                    }
+                }
                 // 添加安全检查
                 let base_path = &collection.path;
+                // 按配置直接删除或移入视频源根目录下的回收站
+                let remover = crate::utils::trash::FileRemover::new(std::path::Path::new(base_path));
                 if base_path.is_empty() || base_path == "/" || base_path == "\\" {
                     warn!("检测到危险路径，跳过删除: {}", base_path);
                 } else {
@@ -2937,7 +4029,7 @@ pub async fn delete_video_source_internal(
                                     let size_mb = size as f64 / 1024.0 / 1024.0;
                                     info!("删除合集视频文件夹: {} (大小: {:.2} MB)", video.path, size_mb);
 
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除合集视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除合集视频文件夹: {} ({:.2} MB)", video.path, size_mb);
@@ -2950,7 +4042,7 @@ pub async fn delete_video_source_internal(
                                 }
                                 Err(e) => {
                                     warn!("无法计算文件夹大小: {} - {}", video.path, e);
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除合集视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除合集视频文件夹: {}", video.path);
@@ -2982,6 +4074,7 @@ pub async fn delete_video_source_internal(
             collection::Entity::delete_by_id(id).exec(&txn).await?;
 
             crate::api::response::DeleteVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: id,
                 source_type: "collection".to_string(),
@@ -3043,7 +4136,17 @@ pub async fn delete_video_source_internal(
 
             // 如果需要删除本地文件
             if delete_local_files {
+                // 其他视频源链接到这些文件时先转移，避免删除后链接失效
+                for video in &videos {
+                    if let Err(e) =
+                        crate::utils::dedup::release_linked_files(&txn, std::path::Path::new(&video.path)).await
+                    {
+                        warn!("转移被其他视频源引用的文件失败: {} - {:#}", video.path, e);
+                    }
+                }
                 let base_path = &favorite.path;
+                // 按配置直接删除或移入视频源根目录下的回收站
+                let remover = crate::utils::trash::FileRemover::new(std::path::Path::new(base_path));
                 if base_path.is_empty() || base_path == "/" || base_path == "\\" {
                     warn!("检测到危险路径，跳过删除: {}", base_path);
                 } else {
@@ -3064,7 +4167,7 @@ pub async fn delete_video_source_internal(
                                     let size_mb = size as f64 / 1024.0 / 1024.0;
                                     info!("删除收藏夹视频文件夹: {} (大小: {:.2} MB)", video.path, size_mb);
 
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除收藏夹视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除收藏夹视频文件夹: {} ({:.2} MB)", video.path, size_mb);
@@ -3077,7 +4180,7 @@ pub async fn delete_video_source_internal(
                                 }
                                 Err(e) => {
                                     warn!("无法计算文件夹大小: {} - {}", video.path, e);
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除收藏夹视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除收藏夹视频文件夹: {}", video.path);
@@ -3109,6 +4212,7 @@ pub async fn delete_video_source_internal(
             favorite::Entity::delete_by_id(id).exec(&txn).await?;
 
             crate::api::response::DeleteVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: id,
                 source_type: "favorite".to_string(),
@@ -3173,7 +4277,17 @@ pub async fn delete_video_source_internal(
 
             // 如果需要删除本地文件
             if delete_local_files {
+                // 其他视频源链接到这些文件时先转移，避免删除后链接失效
+                for video in &videos {
+                    if let Err(e) =
+                        crate::utils::dedup::release_linked_files(&txn, std::path::Path::new(&video.path)).await
+                    {
+                        warn!("转移被其他视频源引用的文件失败: {} - {:#}", video.path, e);
+                    }
+                }
                 let base_path = &submission.path;
+                // 按配置直接删除或移入视频源根目录下的回收站
+                let remover = crate::utils::trash::FileRemover::new(std::path::Path::new(base_path));
                 if base_path.is_empty() || base_path == "/" || base_path == "\\" {
                     warn!("检测到危险路径，跳过删除: {}", base_path);
                 } else {
@@ -3194,7 +4308,7 @@ pub async fn delete_video_source_internal(
                                     let size_mb = size as f64 / 1024.0 / 1024.0;
                                     info!("删除UP主投稿视频文件夹: {} (大小: {:.2} MB)", video.path, size_mb);
 
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除UP主投稿视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除UP主投稿视频文件夹: {} ({:.2} MB)", video.path, size_mb);
@@ -3207,7 +4321,7 @@ pub async fn delete_video_source_internal(
                                 }
                                 Err(e) => {
                                     warn!("无法计算文件夹大小: {} - {}", video.path, e);
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除UP主投稿视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除UP主投稿视频文件夹: {}", video.path);
@@ -3239,6 +4353,7 @@ pub async fn delete_video_source_internal(
             submission::Entity::delete_by_id(id).exec(&txn).await?;
 
             crate::api::response::DeleteVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: id,
                 source_type: "submission".to_string(),
@@ -3300,7 +4415,17 @@ pub async fn delete_video_source_internal(
 
             // 如果需要删除本地文件
             if delete_local_files {
+                // 其他视频源链接到这些文件时先转移，避免删除后链接失效
+                for video in &videos {
+                    if let Err(e) =
+                        crate::utils::dedup::release_linked_files(&txn, std::path::Path::new(&video.path)).await
+                    {
+                        warn!("转移被其他视频源引用的文件失败: {} - {:#}", video.path, e);
+                    }
+                }
                 let base_path = &watch_later.path;
+                // 按配置直接删除或移入视频源根目录下的回收站
+                let remover = crate::utils::trash::FileRemover::new(std::path::Path::new(base_path));
                 if base_path.is_empty() || base_path == "/" || base_path == "\\" {
                     warn!("检测到危险路径，跳过删除: {}", base_path);
                 } else {
@@ -3321,7 +4446,7 @@ pub async fn delete_video_source_internal(
                                     let size_mb = size as f64 / 1024.0 / 1024.0;
                                     info!("删除稍后再看视频文件夹: {} (大小: {:.2} MB)", video.path, size_mb);
 
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除稍后再看视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除稍后再看视频文件夹: {} ({:.2} MB)", video.path, size_mb);
@@ -3334,7 +4459,7 @@ pub async fn delete_video_source_internal(
                                 }
                                 Err(e) => {
                                     warn!("无法计算文件夹大小: {} - {}", video.path, e);
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除稍后再看视频文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除稍后再看视频文件夹: {}", video.path);
@@ -3365,6 +4490,7 @@ pub async fn delete_video_source_internal(
             watch_later::Entity::delete_by_id(id).exec(&txn).await?;
 
             crate::api::response::DeleteVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: id,
                 source_type: "watch_later".to_string(),
@@ -3432,7 +4558,17 @@ pub async fn delete_video_source_internal(
 
             // 如果需要删除本地文件
             if delete_local_files {
+                // 其他视频源链接到这些文件时先转移，避免删除后链接失效
+                for video in &videos {
+                    if let Err(e) =
+                        crate::utils::dedup::release_linked_files(&txn, std::path::Path::new(&video.path)).await
+                    {
+                        warn!("转移被其他视频源引用的文件失败: {} - {:#}", video.path, e);
+                    }
+                }
                 let base_path = &bangumi.path;
+                // 按配置直接删除或移入视频源根目录下的回收站
+                let remover = crate::utils::trash::FileRemover::new(std::path::Path::new(base_path));
                 if base_path.is_empty() || base_path == "/" || base_path == "\\" {
                     warn!("检测到危险路径，跳过删除: {}", base_path);
                 } else {
@@ -3453,7 +4589,7 @@ pub async fn delete_video_source_internal(
                                     let size_mb = size as f64 / 1024.0 / 1024.0;
                                     info!("删除番剧季度文件夹: {} (大小: {:.2} MB)", video.path, size_mb);
 
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除番剧季度文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除番剧季度文件夹: {} ({:.2} MB)", video.path, size_mb);
@@ -3466,7 +4602,7 @@ pub async fn delete_video_source_internal(
                                 }
                                 Err(e) => {
                                     warn!("无法计算文件夹大小: {} - {}", video.path, e);
-                                    if let Err(e) = std::fs::remove_dir_all(&video.path) {
+                                    if let Err(e) = remover.remove_dir_all(std::path::Path::new(&video.path)) {
                                         error!("删除番剧季度文件夹失败: {} - {}", video.path, e);
                                     } else {
                                         info!("成功删除番剧季度文件夹: {}", video.path);
@@ -3498,6 +4634,7 @@ pub async fn delete_video_source_internal(
             video_source::Entity::delete_by_id(id).exec(&txn).await?;
 
             crate::api::response::DeleteVideoSourceResponse {
+                task_id: None,
                 success: true,
                 source_id: id,
                 source_type: "bangumi".to_string(),
@@ -3516,9 +4653,86 @@ pub async fn delete_video_source_internal(
         }
     }
 
+    if let Err(e) = crate::utils::scan_history::delete_source_history(&db, &result.source_type, id).await {
+        warn!("清除视频源 {} 的扫描记录失败: {}", id, e);
+    }
+
     Ok(result)
 }
 
+/// 列出所有回收站批次（包括已删除视频源的回收站），按删除时间倒序
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    responses(
+        (status = 200, body = ApiResponse<TrashListResponse>),
+    )
+)]
+pub async fn get_trash(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<TrashListResponse>, ApiError> {
+    let batches = crate::utils::trash::list_trash(db.as_ref())
+        .await?
+        .into_iter()
+        .map(|entry| TrashBatchInfo {
+            path: entry.path.to_string_lossy().to_string(),
+            source_root: entry.source_root.to_string_lossy().to_string(),
+            deleted_at: entry
+                .modified
+                .map(|modified| to_standard_string(chrono::DateTime::<chrono::Local>::from(modified))),
+            file_count: entry.file_count,
+        })
+        .collect();
+    Ok(ApiResponse::ok(TrashListResponse { batches }))
+}
+
+/// 将回收站批次中的文件移回原位置，原位置已存在的文件会被跳过
+#[utoipa::path(
+    post,
+    path = "/api/trash/restore",
+    request_body = RestoreTrashRequest,
+    responses(
+        (status = 200, body = ApiResponse<RestoreTrashResponse>),
+    )
+)]
+pub async fn restore_trash(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<RestoreTrashRequest>,
+) -> Result<ApiResponse<RestoreTrashResponse>, ApiError> {
+    // 只允许恢复已知回收站目录下的批次，避免通过任意路径移动文件
+    let batch = crate::utils::trash::list_trash(db.as_ref())
+        .await?
+        .into_iter()
+        .find(|entry| entry.path == std::path::Path::new(&request.path))
+        .ok_or_else(|| InnerApiError::BadRequest(format!("未找到回收站批次: {}", request.path)))?;
+
+    let batch_path = batch.path.clone();
+    let (restored_count, skipped) =
+        tokio::task::spawn_blocking(move || crate::utils::trash::restore_batch(&batch_path)).await??;
+    info!(
+        "已从回收站恢复 {} 个文件，跳过 {} 个: {:?}",
+        restored_count,
+        skipped.len(),
+        batch.path
+    );
+
+    let message = if skipped.is_empty() {
+        format!("已恢复 {} 个文件", restored_count)
+    } else {
+        format!(
+            "已恢复 {} 个文件，{} 个文件因原位置已存在而保留在回收站中",
+            restored_count,
+            skipped.len()
+        )
+    };
+    Ok(ApiResponse::ok(RestoreTrashResponse {
+        success: true,
+        restored_count,
+        skipped,
+        message,
+    }))
+}
+
 /// 更新视频源扫描已删除视频设置
 #[utoipa::path(
     put,
@@ -3686,8 +4900,2122 @@ pub async fn update_video_source_scan_deleted_internal(
         _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
     };
 
-    txn.commit().await?;
-    Ok(result)
+    txn.commit().await?;
+    Ok(result)
+}
+
+/// 更新视频源扫描间隔
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/scan-interval",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceScanIntervalRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceScanIntervalResponse>),
+    )
+)]
+pub async fn update_video_source_scan_interval(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceScanIntervalRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceScanIntervalResponse>, ApiError> {
+    // 0 视为未设置，使用全局扫描间隔
+    let scan_interval = params.scan_interval.filter(|interval| *interval > 0);
+    let value = sea_orm::Set(scan_interval.map(|interval| interval as i64));
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_interval: value,
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_interval: value,
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_interval: value,
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_interval: value,
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_interval: value,
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = match scan_interval {
+        Some(interval) => format!("{} 的扫描间隔已设置为 {} 秒", source_name, interval),
+        None => format!("{} 已恢复使用全局扫描间隔", source_name),
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceScanIntervalResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            scan_interval,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源发布时间窗口
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/pubtime-window",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourcePubtimeWindowRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourcePubtimeWindowResponse>),
+    )
+)]
+pub async fn update_video_source_pubtime_window(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourcePubtimeWindowRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourcePubtimeWindowResponse>, ApiError> {
+    let (pubtime_after, pubtime_before) = normalize_pubtime_window(params.pubtime_after, params.pubtime_before)?;
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                pubtime_after: sea_orm::Set(pubtime_after.clone()),
+                pubtime_before: sea_orm::Set(pubtime_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = match (&pubtime_after, &pubtime_before) {
+        (None, None) => format!("{} 已取消发布时间限制", source_name),
+        (after, before) => format!(
+            "{} 的发布时间窗口已设置为 {} ~ {}",
+            source_name,
+            after.as_deref().unwrap_or("不限"),
+            before.as_deref().unwrap_or("不限")
+        ),
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourcePubtimeWindowResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            pubtime_after,
+            pubtime_before,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源标题过滤关键词
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/title-filter",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceTitleFilterRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceTitleFilterResponse>),
+    )
+)]
+pub async fn update_video_source_title_filter(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceTitleFilterRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceTitleFilterResponse>, ApiError> {
+    let title_include = normalize_title_terms(params.title_include, params.title_regex)?;
+    let title_exclude = normalize_title_terms(params.title_exclude, params.title_regex)?;
+    let include_value = title_terms_column(&title_include);
+    let exclude_value = title_terms_column(&title_exclude);
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                title_include: sea_orm::Set(include_value.clone()),
+                title_exclude: sea_orm::Set(exclude_value.clone()),
+                title_regex: sea_orm::Set(params.title_regex),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                title_include: sea_orm::Set(include_value.clone()),
+                title_exclude: sea_orm::Set(exclude_value.clone()),
+                title_regex: sea_orm::Set(params.title_regex),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                title_include: sea_orm::Set(include_value.clone()),
+                title_exclude: sea_orm::Set(exclude_value.clone()),
+                title_regex: sea_orm::Set(params.title_regex),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                title_include: sea_orm::Set(include_value.clone()),
+                title_exclude: sea_orm::Set(exclude_value.clone()),
+                title_regex: sea_orm::Set(params.title_regex),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                title_include: sea_orm::Set(include_value.clone()),
+                title_exclude: sea_orm::Set(exclude_value.clone()),
+                title_regex: sea_orm::Set(params.title_regex),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = if title_include.is_empty() && title_exclude.is_empty() {
+        format!("{} 已取消标题过滤", source_name)
+    } else {
+        format!("{} 的标题过滤已更新", source_name)
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceTitleFilterResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            title_include,
+            title_exclude,
+            title_regex: params.title_regex,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源 .strm 模式设置
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/strm",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceStrmRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceStrmResponse>),
+    )
+)]
+pub async fn update_video_source_strm(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceStrmRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceStrmResponse>, ApiError> {
+    // 未启用 .strm 模式时刷新选项没有意义，一并关闭
+    let strm_refresh = params.strm_mode && params.strm_refresh;
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strm_mode: sea_orm::Set(params.strm_mode),
+                strm_refresh: sea_orm::Set(strm_refresh),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strm_mode: sea_orm::Set(params.strm_mode),
+                strm_refresh: sea_orm::Set(strm_refresh),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strm_mode: sea_orm::Set(params.strm_mode),
+                strm_refresh: sea_orm::Set(strm_refresh),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strm_mode: sea_orm::Set(params.strm_mode),
+                strm_refresh: sea_orm::Set(strm_refresh),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strm_mode: sea_orm::Set(params.strm_mode),
+                strm_refresh: sea_orm::Set(strm_refresh),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = match (params.strm_mode, strm_refresh) {
+        (true, true) => format!("{} 已启用 .strm 模式，每次扫描时刷新播放地址", source_name),
+        (true, false) => format!("{} 已启用 .strm 模式", source_name),
+        _ => format!("{} 已关闭 .strm 模式", source_name),
+    };
+    Ok(ApiResponse::ok(crate::api::response::UpdateVideoSourceStrmResponse {
+        success: true,
+        source_id: id,
+        source_type,
+        strm_mode: params.strm_mode,
+        strm_refresh,
+        message,
+    }))
+}
+
+/// 更新视频源严格增量模式设置
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/strict-incremental",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceStrictIncrementalRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceStrictIncrementalResponse>),
+    )
+)]
+pub async fn update_video_source_strict_incremental(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceStrictIncrementalRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceStrictIncrementalResponse>, ApiError> {
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strict_incremental: sea_orm::Set(params.strict_incremental),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strict_incremental: sea_orm::Set(params.strict_incremental),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strict_incremental: sea_orm::Set(params.strict_incremental),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strict_incremental: sea_orm::Set(params.strict_incremental),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                strict_incremental: sea_orm::Set(params.strict_incremental),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = if params.strict_incremental {
+        format!("{} 已启用严格增量模式", source_name)
+    } else {
+        format!("{} 已关闭严格增量模式", source_name)
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceStrictIncrementalResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            strict_incremental: params.strict_incremental,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源 WebDAV 上传设置
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/webdav-upload",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceWebdavUploadRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceWebdavUploadResponse>),
+    )
+)]
+pub async fn update_video_source_webdav_upload(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceWebdavUploadRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceWebdavUploadResponse>, ApiError> {
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                webdav_upload: sea_orm::Set(params.webdav_upload),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                webdav_upload: sea_orm::Set(params.webdav_upload),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                webdav_upload: sea_orm::Set(params.webdav_upload),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                webdav_upload: sea_orm::Set(params.webdav_upload),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                webdav_upload: sea_orm::Set(params.webdav_upload),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = if params.webdav_upload {
+        format!("{} 已启用 WebDAV 上传", source_name)
+    } else {
+        format!("{} 已关闭 WebDAV 上传", source_name)
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceWebdavUploadResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            webdav_upload: params.webdav_upload,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源 仅音频模式设置
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/audio-only",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceAudioOnlyRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceAudioOnlyResponse>),
+    )
+)]
+pub async fn update_video_source_audio_only(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceAudioOnlyRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceAudioOnlyResponse>, ApiError> {
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(params.audio_only),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(params.audio_only),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(params.audio_only),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(params.audio_only),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(params.audio_only),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = if params.audio_only {
+        format!("{} 已启用仅音频模式", source_name)
+    } else {
+        format!("{} 已关闭仅音频模式", source_name)
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceAudioOnlyResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            audio_only: params.audio_only,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源 画质升级设置
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/upgrade-quality",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceUpgradeQualityRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceUpgradeQualityResponse>),
+    )
+)]
+pub async fn update_video_source_upgrade_quality(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceUpgradeQualityRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceUpgradeQualityResponse>, ApiError> {
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                upgrade_quality: sea_orm::Set(params.upgrade_quality),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                upgrade_quality: sea_orm::Set(params.upgrade_quality),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                upgrade_quality: sea_orm::Set(params.upgrade_quality),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                upgrade_quality: sea_orm::Set(params.upgrade_quality),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                upgrade_quality: sea_orm::Set(params.upgrade_quality),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = if params.upgrade_quality {
+        format!("{} 已启用画质升级", source_name)
+    } else {
+        format!("{} 已关闭画质升级", source_name)
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceUpgradeQualityResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            upgrade_quality: params.upgrade_quality,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源弹幕嵌入方式
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/danmaku-embed",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceDanmakuEmbedRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceDanmakuEmbedResponse>),
+    )
+)]
+pub async fn update_video_source_danmaku_embed(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceDanmakuEmbedRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceDanmakuEmbedResponse>, ApiError> {
+    let mode = crate::config::DanmakuEmbedMode::parse(&params.danmaku_embed_mode).ok_or_else(|| {
+        crate::api::error::InnerApiError::BadRequest(format!(
+            "无效的弹幕嵌入方式: {}，可选值为 none、soft、hard",
+            params.danmaku_embed_mode
+        ))
+    })?;
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                danmaku_embed_mode: sea_orm::Set(mode.as_str().to_string()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                danmaku_embed_mode: sea_orm::Set(mode.as_str().to_string()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                danmaku_embed_mode: sea_orm::Set(mode.as_str().to_string()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                danmaku_embed_mode: sea_orm::Set(mode.as_str().to_string()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                danmaku_embed_mode: sea_orm::Set(mode.as_str().to_string()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = match mode {
+        crate::config::DanmakuEmbedMode::None => format!("{} 已关闭弹幕嵌入", source_name),
+        crate::config::DanmakuEmbedMode::Soft => format!("{} 已启用弹幕字幕轨道嵌入", source_name),
+        crate::config::DanmakuEmbedMode::Hard => format!("{} 已启用弹幕烧录，新下载的视频将重新编码", source_name),
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceDanmakuEmbedResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            danmaku_embed_mode: mode.as_str().to_string(),
+            message,
+        },
+    ))
+}
+
+/// 更新视频源的封面、NFO、弹幕、字幕子任务开关
+///
+/// 关闭的子任务在已有视频中标记为不适用；重新开启时将不适用的子任务重置为未开始，下次扫描时补充下载
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/subtasks",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceSubtasksRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceSubtasksResponse>),
+    )
+)]
+pub async fn update_video_source_subtasks(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceSubtasksRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceSubtasksResponse>, ApiError> {
+    use crate::adapter::SubtaskToggles;
+
+    let merge = |cover: bool, nfo: bool, danmaku: bool, subtitle: bool| SubtaskToggles {
+        cover: params.download_cover.unwrap_or(cover),
+        nfo: params.download_nfo.unwrap_or(nfo),
+        danmaku: params.download_danmaku.unwrap_or(danmaku),
+        subtitle: params.download_subtitle.unwrap_or(subtitle),
+    };
+
+    let txn = db.begin().await?;
+
+    let (source_name, toggles, video_filter) = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            let toggles = merge(
+                collection.download_cover,
+                collection.download_nfo,
+                collection.download_danmaku,
+                collection.download_subtitle,
+            );
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                download_cover: sea_orm::Set(toggles.cover),
+                download_nfo: sea_orm::Set(toggles.nfo),
+                download_danmaku: sea_orm::Set(toggles.danmaku),
+                download_subtitle: sea_orm::Set(toggles.subtitle),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            (
+                format!("合集 {}", collection.name),
+                toggles,
+                video::Column::CollectionId.eq(id),
+            )
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            let toggles = merge(
+                favorite.download_cover,
+                favorite.download_nfo,
+                favorite.download_danmaku,
+                favorite.download_subtitle,
+            );
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                download_cover: sea_orm::Set(toggles.cover),
+                download_nfo: sea_orm::Set(toggles.nfo),
+                download_danmaku: sea_orm::Set(toggles.danmaku),
+                download_subtitle: sea_orm::Set(toggles.subtitle),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            (
+                format!("收藏夹 {}", favorite.name),
+                toggles,
+                video::Column::FavoriteId.eq(id),
+            )
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            let toggles = merge(
+                submission.download_cover,
+                submission.download_nfo,
+                submission.download_danmaku,
+                submission.download_subtitle,
+            );
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                download_cover: sea_orm::Set(toggles.cover),
+                download_nfo: sea_orm::Set(toggles.nfo),
+                download_danmaku: sea_orm::Set(toggles.danmaku),
+                download_subtitle: sea_orm::Set(toggles.subtitle),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            (
+                format!("UP主投稿 {}", submission.upper_name),
+                toggles,
+                video::Column::SubmissionId.eq(id),
+            )
+        }
+        "watch_later" => {
+            let watch_later = watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            let toggles = merge(
+                watch_later.download_cover,
+                watch_later.download_nfo,
+                watch_later.download_danmaku,
+                watch_later.download_subtitle,
+            );
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                download_cover: sea_orm::Set(toggles.cover),
+                download_nfo: sea_orm::Set(toggles.nfo),
+                download_danmaku: sea_orm::Set(toggles.danmaku),
+                download_subtitle: sea_orm::Set(toggles.subtitle),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("稍后观看".to_string(), toggles, video::Column::WatchLaterId.eq(id))
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            let toggles = merge(
+                video_source.download_cover,
+                video_source.download_nfo,
+                video_source.download_danmaku,
+                video_source.download_subtitle,
+            );
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                download_cover: sea_orm::Set(toggles.cover),
+                download_nfo: sea_orm::Set(toggles.nfo),
+                download_danmaku: sea_orm::Set(toggles.danmaku),
+                download_subtitle: sea_orm::Set(toggles.subtitle),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            (
+                format!("番剧 {}", video_source.name),
+                toggles,
+                video::Column::SourceId.eq(id).and(video::Column::SourceType.eq(1)),
+            )
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    // 按新的开关同步已有视频与分页的子任务状态
+    let pages: Vec<(i32, u32, i32)> = page::Entity::find()
+        .inner_join(video::Entity)
+        .filter(video_filter.clone())
+        .select_only()
+        .columns([page::Column::Id, page::Column::DownloadStatus, page::Column::VideoId])
+        .into_tuple()
+        .all(&txn)
+        .await?;
+    let mut reopened_videos = std::collections::HashSet::new();
+    let changed_pages: Vec<(i32, u32)> = pages
+        .into_iter()
+        .filter_map(|(page_id, download_status, video_id)| {
+            let mut status = PageStatus::from(download_status);
+            if !status.apply_applicable(toggles.page_applicable()) {
+                return None;
+            }
+            if status.should_run().into_iter().any(|x| x) {
+                reopened_videos.insert(video_id);
+            }
+            Some((page_id, status.into()))
+        })
+        .collect();
+
+    let videos: Vec<(i32, u32)> = video::Entity::find()
+        .filter(video_filter)
+        .select_only()
+        .columns([video::Column::Id, video::Column::DownloadStatus])
+        .into_tuple()
+        .all(&txn)
+        .await?;
+    let changed_videos: Vec<(i32, u32)> = videos
+        .into_iter()
+        .filter_map(|(video_id, download_status)| {
+            let mut status = VideoStatus::from(download_status);
+            let mut changed = status.apply_applicable(toggles.video_applicable());
+            if reopened_videos.contains(&video_id) {
+                status.set(4, 0); // 分页有子任务需要补充执行，将"分P下载"重置为 0
+                changed = true;
+            }
+            changed.then(|| (video_id, status.into()))
+        })
+        .collect();
+
+    batch_update_page_status(&txn, changed_pages.iter().copied()).await?;
+    batch_update_video_status(&txn, changed_videos.iter().copied(), false).await?;
+
+    txn.commit().await?;
+
+    if !reopened_videos.is_empty() {
+        crate::task::resume_scanning();
+    }
+
+    let message = format!(
+        "{} 的子任务开关已更新，同步了 {} 个视频、{} 个分页的状态",
+        source_name,
+        changed_videos.len(),
+        changed_pages.len()
+    );
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceSubtasksResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            download_cover: toggles.cover,
+            download_nfo: toggles.nfo,
+            download_danmaku: toggles.danmaku,
+            download_subtitle: toggles.subtitle,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源保留策略
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/retention",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceRetentionRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceRetentionResponse>),
+    )
+)]
+pub async fn update_video_source_retention(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceRetentionRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceRetentionResponse>, ApiError> {
+    // 0 视为未设置，避免误配置导致删除全部视频
+    let retention_days = params.retention_days.filter(|days| *days > 0);
+    let retention_max_count = params.retention_max_count.filter(|count| *count > 0);
+    let days_value = sea_orm::Set(retention_days.map(|days| days.min(i32::MAX as u32) as i32));
+    let count_value = sea_orm::Set(retention_max_count.map(|count| count.min(i32::MAX as u32) as i32));
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                retention_days: days_value.clone(),
+                retention_max_count: count_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                retention_days: days_value.clone(),
+                retention_max_count: count_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                retention_days: days_value.clone(),
+                retention_max_count: count_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                retention_days: days_value.clone(),
+                retention_max_count: count_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                retention_days: days_value.clone(),
+                retention_max_count: count_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let message = match (retention_days, retention_max_count) {
+        (None, None) => format!("{} 已关闭保留策略", source_name),
+        (days, count) => {
+            let mut rules = Vec::new();
+            if let Some(days) = days {
+                rules.push(format!("保留 {} 天内的视频", days));
+            }
+            if let Some(count) = count {
+                rules.push(format!("最多保留 {} 个视频", count));
+            }
+            format!("{} 的保留策略已设置为：{}", source_name, rules.join("，"))
+        }
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceRetentionResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            retention_days,
+            retention_max_count,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源的最新视频数量限制
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/limit-newest",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceLimitNewestRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceLimitNewestResponse>),
+    )
+)]
+pub async fn update_video_source_limit_newest(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceLimitNewestRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceLimitNewestResponse>, ApiError> {
+    // 0 视为不限制
+    let limit_newest = params.limit_newest.filter(|limit| *limit > 0);
+    let limit_value = sea_orm::Set(limit_newest.map(|limit| limit.min(i32::MAX as u32) as i32));
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                limit_newest: limit_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                limit_newest: limit_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                limit_newest: limit_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                limit_newest: limit_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                limit_newest: limit_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    // 已写入数据库的视频不受影响，设置只作用于之后的扫描
+    let message = match limit_newest {
+        Some(limit) => format!("{} 之后的扫描只处理最新的 {} 个视频", source_name, limit),
+        None => format!("{} 已取消最新视频数量限制", source_name),
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceLimitNewestResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            limit_newest,
+            message,
+        },
+    ))
+}
+
+/// 更新视频源单独使用的 NFO 时间类型
+///
+/// 时间类型发生变化时，将该视频源已生成的 NFO 标记为重新生成并请求扫描
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/nfo-time-type",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceNfoTimeTypeRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceNfoTimeTypeResponse>),
+    )
+)]
+pub async fn update_video_source_nfo_time_type(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceNfoTimeTypeRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceNfoTimeTypeResponse>, ApiError> {
+    let Some(scan_source_type) = crate::utils::scan_id_tracker::SourceType::parse(&source_type) else {
+        return Err(InnerApiError::BadRequest(format!("不支持的视频源类型: {}", source_type)).into());
+    };
+    // 空字符串视为使用全局配置
+    let time_type = match params.nfo_time_type.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => Some(crate::config::NFOTimeType::parse(value).ok_or_else(|| {
+            InnerApiError::BadRequest(format!(
+                "无效的 NFO 时间类型: {}，可选值为 favtime、pubtime、ctime",
+                value
+            ))
+        })?),
+        None => None,
+    };
+    let time_type_value = sea_orm::Set(time_type.as_ref().map(|time_type| time_type.as_str().to_string()));
+
+    let txn = db.begin().await?;
+
+    let source_name = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                nfo_time_type: time_type_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("合集 {}", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                nfo_time_type: time_type_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("收藏夹 {}", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                nfo_time_type: time_type_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("UP主投稿 {}", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                nfo_time_type: time_type_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            "稍后观看".to_string()
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                nfo_time_type: time_type_value.clone(),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            format!("番剧 {}", video_source.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+
+    let (resetted_videos_count, _) = crate::utils::model::reset_completed_subtasks(
+        Some(source_video_expr(scan_source_type, id)),
+        &[1],
+        &[2],
+        db.as_ref(),
+    )
+    .await?;
+    if resetted_videos_count > 0 {
+        crate::task::TASK_CONTROLLER.request_source_scan(scan_source_type, id);
+    }
+
+    let time_type_desc = match &time_type {
+        Some(crate::config::NFOTimeType::FavTime) => "收藏/添加时间",
+        Some(crate::config::NFOTimeType::PubTime) => "发布时间",
+        Some(crate::config::NFOTimeType::CTime) => "投稿时间",
+        None => "全局配置",
+    };
+    let message = format!(
+        "{} 的 NFO 时间已改为使用{}，{} 个视频的 NFO 将在下次扫描时重新生成",
+        source_name, time_type_desc, resetted_videos_count
+    );
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceNfoTimeTypeResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            nfo_time_type: time_type.as_ref().map(|time_type| time_type.as_str().to_string()),
+            resetted_videos_count,
+            message,
+        },
+    ))
+}
+
+/// 立即扫描指定视频源
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/{source_type}/{id}/scan",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::ScanVideoSourceResponse>),
+    )
+)]
+pub async fn scan_video_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<ApiResponse<crate::api::response::ScanVideoSourceResponse>, ApiError> {
+    use crate::utils::scan_id_tracker::SourceType;
+
+    let (scan_source_type, source_name, enabled) = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            (
+                SourceType::Collection,
+                format!("合集 {}", collection.name),
+                collection.enabled,
+            )
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            (
+                SourceType::Favorite,
+                format!("收藏夹 {}", favorite.name),
+                favorite.enabled,
+            )
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            (
+                SourceType::Submission,
+                format!("UP主投稿 {}", submission.upper_name),
+                submission.enabled,
+            )
+        }
+        "watch_later" => {
+            let watch_later = watch_later::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后观看"))?;
+            (SourceType::WatchLater, "稍后观看".to_string(), watch_later.enabled)
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            (
+                SourceType::Bangumi,
+                format!("番剧 {}", video_source.name),
+                video_source.enabled,
+            )
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    if !enabled {
+        return Err(anyhow!("{} 已禁用，请先启用后再扫描", source_name).into());
+    }
+    if crate::task::TASK_CONTROLLER.is_source_paused(scan_source_type, id) {
+        return Err(anyhow!("{} 已暂停，请先恢复后再扫描", source_name).into());
+    }
+
+    let (task, newly_queued) = crate::task::TASK_CONTROLLER.request_source_scan(scan_source_type, id);
+    let is_paused = crate::task::TASK_CONTROLLER.is_paused();
+    let message = if !newly_queued {
+        format!("{} 已有扫描任务在队列中，未重复添加", source_name)
+    } else if is_paused {
+        format!("{} 已加入扫描队列，任务暂停中，恢复后开始扫描", source_name)
+    } else {
+        format!("{} 已加入扫描队列，将在当前扫描结束后立即开始", source_name)
+    };
+    if newly_queued {
+        info!("{}", message);
+    }
+
+    Ok(ApiResponse::ok(crate::api::response::ScanVideoSourceResponse {
+        success: true,
+        task_id: task.task_id,
+        source_id: id,
+        source_type,
+        status: task.status,
+        already_queued: !newly_queued,
+        is_paused,
+        message,
+    }))
+}
+
+/// 单独暂停指定视频源的扫描与下载，正在进行的下载会被取消，其他视频源不受影响
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/{source_type}/{id}/pause",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::PauseVideoSourceResponse>),
+    )
+)]
+pub async fn pause_video_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<ApiResponse<crate::api::response::PauseVideoSourceResponse>, ApiError> {
+    let (scan_source_type, source_name) = resolve_pausable_source(db.as_ref(), &source_type, id).await?;
+
+    // 暂停状态写入数据库，重启后保持暂停
+    crate::task::save_source_paused(db.as_ref(), scan_source_type, id, true).await?;
+    let message = if crate::task::TASK_CONTROLLER.pause_source(scan_source_type, id) {
+        let message = format!("{} 已暂停，正在进行的下载已取消", source_name);
+        info!("{}", message);
+        message
+    } else {
+        format!("{} 已处于暂停状态", source_name)
+    };
+
+    Ok(ApiResponse::ok(crate::api::response::PauseVideoSourceResponse {
+        success: true,
+        source_id: id,
+        source_type,
+        paused: true,
+        message,
+    }))
+}
+
+/// 恢复单独暂停的视频源，并请求尽快扫描
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/{source_type}/{id}/resume",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::PauseVideoSourceResponse>),
+    )
+)]
+pub async fn resume_video_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<ApiResponse<crate::api::response::PauseVideoSourceResponse>, ApiError> {
+    let (scan_source_type, source_name) = resolve_pausable_source(db.as_ref(), &source_type, id).await?;
+
+    crate::task::save_source_paused(db.as_ref(), scan_source_type, id, false).await?;
+    let message = if crate::task::TASK_CONTROLLER.resume_source(scan_source_type, id) {
+        // 暂停期间错过的扫描在恢复后立即补上
+        crate::task::TASK_CONTROLLER.request_source_scan(scan_source_type, id);
+        let message = format!("{} 已恢复，将在当前扫描结束后重新扫描", source_name);
+        info!("{}", message);
+        message
+    } else {
+        format!("{} 未处于暂停状态", source_name)
+    };
+
+    Ok(ApiResponse::ok(crate::api::response::PauseVideoSourceResponse {
+        success: true,
+        source_id: id,
+        source_type,
+        paused: false,
+        message,
+    }))
+}
+
+/// 校验要暂停或恢复的视频源，返回扫描使用的类型与显示名称
+async fn resolve_pausable_source(
+    db: &DatabaseConnection,
+    source_type: &str,
+    id: i32,
+) -> Result<(crate::utils::scan_id_tracker::SourceType, String), ApiError> {
+    let Some(scan_source_type) = crate::utils::scan_id_tracker::SourceType::parse(source_type) else {
+        return Err(InnerApiError::BadRequest(format!("不支持的视频源类型: {}", source_type)).into());
+    };
+    let source_key = scan_source_type.as_str();
+    let source_name = resolve_source_names(db, &[(source_key, id)])
+        .await?
+        .remove(&(source_key, id))
+        .ok_or(InnerApiError::NotFound(id))?;
+    Ok((scan_source_type, source_name))
+}
+
+/// 手动刷新合集封面，封面变化时重新下载本地封面
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/collection/{id}/refresh-cover",
+    params(
+        ("id" = i32, Path, description = "合集ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::RefreshCollectionCoverResponse>),
+    )
+)]
+pub async fn refresh_collection_cover(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::RefreshCollectionCoverResponse>, ApiError> {
+    use crate::utils::scan_id_tracker::SourceType;
+
+    let collection = collection::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+    if collection.r#type == i32::from(crate::bilibili::CollectionType::Manual) {
+        return Err(anyhow!("手动列表没有合集封面").into());
+    }
+
+    let bili_client = crate::bilibili::BiliClient::new(String::new());
+    let changed = crate::workflow::refresh_collection_cover(&bili_client, &collection, db.as_ref()).await?;
+    let message = if !changed {
+        format!("合集 {} 的封面没有变化", collection.name)
+    } else if collection.enabled {
+        crate::task::TASK_CONTROLLER.request_source_scan(SourceType::Collection, id);
+        format!("合集 {} 的封面已更新，将在扫描时重新下载", collection.name)
+    } else {
+        format!("合集 {} 的封面已更新，启用后扫描时重新下载", collection.name)
+    };
+    let cover = collection::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .and_then(|collection| collection.cover);
+
+    Ok(ApiResponse::ok(crate::api::response::RefreshCollectionCoverResponse {
+        success: true,
+        source_id: id,
+        cover,
+        changed,
+        message,
+    }))
+}
+
+/// 查询按需扫描任务状态
+#[utoipa::path(
+    get,
+    path = "/api/scan-tasks/{task_id}",
+    params(
+        ("task_id" = String, Path, description = "扫描任务ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::SourceScanTaskResponse>),
+    )
+)]
+pub async fn get_source_scan_task(
+    Path(task_id): Path<String>,
+) -> Result<ApiResponse<crate::api::response::SourceScanTaskResponse>, ApiError> {
+    let task = crate::task::TASK_CONTROLLER
+        .get_source_scan(&task_id)
+        .ok_or_else(|| anyhow!("未找到扫描任务: {}", task_id))?;
+
+    Ok(ApiResponse::ok(crate::api::response::SourceScanTaskResponse {
+        task_id: task.task_id,
+        source_id: task.source_id,
+        source_type: task.source_type.as_str().to_string(),
+        status: task.status,
+        message: task.message,
+        created_at: task.created_at,
+        finished_at: task.finished_at,
+    }))
+}
+
+/// 查询视频源最近的扫描记录，用于判断视频源是否长期没有新内容或持续失败
+#[utoipa::path(
+    get,
+    path = "/api/video-sources/{source_type}/{id}/history",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+        crate::api::request::ScanHistoryRequest,
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::SourceScanHistoryResponse>),
+    )
+)]
+pub async fn get_source_scan_history(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    Query(params): Query<crate::api::request::ScanHistoryRequest>,
+) -> Result<ApiResponse<crate::api::response::SourceScanHistoryResponse>, ApiError> {
+    if !matches!(
+        source_type.as_str(),
+        "collection" | "favorite" | "submission" | "watch_later" | "bangumi"
+    ) {
+        return Err(InnerApiError::BadRequest(format!("不支持的视频源类型: {}", source_type)).into());
+    }
+
+    let records =
+        crate::utils::scan_history::recent_source_history(db.as_ref(), &source_type, id, params.limit.unwrap_or(20))
+            .await?
+            .into_iter()
+            .map(|record| crate::api::response::ScanHistoryRecord {
+                id: record.id,
+                started_at: record.started_at,
+                duration_ms: record.duration_ms,
+                new_videos: record.new_videos,
+                new_pages: record.new_pages,
+                failed_videos: record.failed_videos,
+                filtered_videos: record.filtered_videos,
+                skipped_videos: record.skipped_videos,
+                error: record.error,
+            })
+            .collect();
+
+    Ok(ApiResponse::ok(crate::api::response::SourceScanHistoryResponse {
+        source_id: id,
+        source_type,
+        records,
+    }))
+}
+
+/// 播放列表中的分页：(名称, 时长, 路径, 下载状态)
+type PlaylistPage = (String, u32, Option<String>, u32);
+
+/// 导出视频源的 M3U 播放列表，按集数与分页顺序引用已下载的本地文件（.strm 模式下为播放地址），未下载完成的分页会被跳过
+#[utoipa::path(
+    get,
+    path = "/api/video-sources/{source_type}/{id}/playlist.m3u",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, description = "M3U 播放列表", content_type = "audio/x-mpegurl"),
+    )
+)]
+pub async fn get_source_playlist(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::http::header;
+
+    use crate::utils::playlist::{entry_title, page_location, render_m3u, PlaylistEntry};
+
+    let Some(scan_source_type) = crate::utils::scan_id_tracker::SourceType::parse(&source_type) else {
+        return Err(InnerApiError::BadRequest(format!("不支持的视频源类型: {}", source_type)).into());
+    };
+    let source_key = scan_source_type.as_str();
+    let video_filter = source_video_expr(scan_source_type, id);
+    let source_name = resolve_source_names(db.as_ref(), &[(source_key, id)])
+        .await?
+        .remove(&(source_key, id))
+        .ok_or(InnerApiError::NotFound(id))?;
+
+    // 番剧按季度与集数排序，其他视频源按发布时间排序
+    let (videos, pages) = tokio::try_join!(
+        video::Entity::find()
+            .filter(video_filter.clone())
+            .filter(video::Column::Deleted.eq(0))
+            .order_by_asc(video::Column::SeasonNumber)
+            .order_by_asc(video::Column::EpisodeNumber)
+            .order_by_asc(video::Column::Pubtime)
+            .order_by_asc(video::Column::Id)
+            .select_only()
+            .columns([video::Column::Id, video::Column::Name, video::Column::SinglePage])
+            .into_tuple::<(i32, String, Option<bool>)>()
+            .all(db.as_ref()),
+        page::Entity::find()
+            .inner_join(video::Entity)
+            .filter(video_filter)
+            .filter(video::Column::Deleted.eq(0))
+            .order_by_asc(page::Column::Pid)
+            .select_only()
+            .columns([
+                page::Column::VideoId,
+                page::Column::Name,
+                page::Column::Duration,
+                page::Column::Path,
+                page::Column::DownloadStatus,
+            ])
+            .into_tuple::<(i32, String, u32, Option<String>, u32)>()
+            .all(db.as_ref()),
+    )?;
+
+    let mut pages_by_video: std::collections::HashMap<i32, Vec<PlaylistPage>> = std::collections::HashMap::new();
+    for (video_id, name, duration, path, download_status) in pages {
+        pages_by_video
+            .entry(video_id)
+            .or_default()
+            .push((name, duration, path, download_status));
+    }
+
+    let mut entries = Vec::new();
+    for (video_id, video_name, single_page) in videos {
+        for (page_name, duration, path, download_status) in pages_by_video.remove(&video_id).unwrap_or_default() {
+            // 只引用视频内容已下载完成的分页
+            if PageStatus::from(download_status).get(1) != crate::utils::status::STATUS_OK {
+                continue;
+            }
+            let Some(location) = path.as_deref() else {
+                continue;
+            };
+            let Some(location) = page_location(location).await else {
+                debug!("播放列表跳过不存在的文件: {}", location);
+                continue;
+            };
+            entries.push(PlaylistEntry {
+                title: entry_title(&video_name, &page_name, single_page.unwrap_or(false)),
+                duration,
+                location,
+            });
+        }
+    }
+
+    let response = axum::response::Response::builder()
+        .status(200)
+        .header(header::CONTENT_TYPE, "audio/x-mpegurl; charset=utf-8")
+        .header(header::CONTENT_DISPOSITION, "inline; filename=\"playlist.m3u\"")
+        .body(axum::body::Body::from(render_m3u(&source_name, &entries)))
+        .map_err(|e| InnerApiError::BadRequest(format!("构建响应失败: {}", e)))?;
+
+    Ok(response)
 }
 
 /// 删除视频（软删除）
@@ -3749,6 +7077,10 @@ async fn validate_path_reset_safety(
                 path: None,
                 image: None,
                 download_status: 0,
+                webdav_upload_status: 0,
+                canonical_path: None,
+                downloaded_quality: None,
+                quality_checked_at: None,
                 created_at: now_standard_string(),
             };
 
@@ -4269,6 +7601,12 @@ async fn regenerate_video_and_page_paths_correctly(
             bundle.render_page_template(&page_args)
         })
         .map_err(|e| anyhow!("分页路径模板渲染失败: {}", e))?;
+        // 与下载时一致，按文件名与路径长度限制截断，保证数据库中的路径与实际文件一致
+        let new_page_path = crate::utils::filenamify::fit_file_stem(
+            &full_new_video_path,
+            new_page_path,
+            crate::utils::page_files::MAX_SUFFIX_BYTES,
+        );
 
         let full_new_page_path = full_new_video_path.join(format!("{}.mp4", new_page_path));
 
@@ -4390,6 +7728,9 @@ fn reload_config_file() -> Result<()> {
     Ok(())
 }
 
+/// 获取配置时代替已设置的密码、密钥返回的掩码，更新配置时传回该值表示保持不变
+const MASKED_SECRET: &str = "******";
+
 /// 获取当前配置
 #[utoipa::path(
     get,
@@ -4403,10 +7744,7 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
     // 使用配置包系统获取最新配置
     let config = crate::config::with_config(|bundle| bundle.config.clone());
 
-    let nfo_time_type = match config.nfo_time_type {
-        crate::config::NFOTimeType::FavTime => "favtime",
-        crate::config::NFOTimeType::PubTime => "pubtime",
-    };
+    let nfo_time_type = config.nfo_time_type.as_str();
 
     Ok(ApiResponse::ok(crate::api::response::ConfigResponse {
         video_name: config.video_name.to_string(),
@@ -4419,8 +7757,15 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         time_format: config.time_format.clone(),
         interval: config.interval,
         nfo_time_type: nfo_time_type.to_string(),
+        nfo_flavor: config.nfo_flavor.as_str().to_string(),
+        nfo_template: config.nfo_template.clone(),
+        nfo_download_actor_images: config.nfo_download_actor_images,
+        nfo_include_tags: config.nfo_include_tags,
+        nfo_max_tags: config.nfo_max_tags,
+        nfo_include_chapters: config.nfo_include_chapters,
         parallel_download_enabled: config.concurrent_limit.parallel_download.enabled,
         parallel_download_threads: config.concurrent_limit.parallel_download.threads,
+        download_speed_limit_kbps: config.concurrent_limit.download_speed_limit_kbps.unwrap_or(0),
         // 视频质量设置
         video_max_quality: format!("{:?}", config.filter_option.video_max_quality),
         video_min_quality: format!("{:?}", config.filter_option.video_min_quality),
@@ -4431,6 +7776,8 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         no_dolby_audio: config.filter_option.no_dolby_audio,
         no_hdr: config.filter_option.no_hdr,
         no_hires: config.filter_option.no_hires,
+        min_duration_secs: config.filter_option.min_duration_secs,
+        max_duration_secs: config.filter_option.max_duration_secs,
         // 弹幕设置
         danmaku_duration: config.danmaku_option.duration,
         danmaku_font: config.danmaku_option.font.clone(),
@@ -4444,6 +7791,11 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         danmaku_bold: config.danmaku_option.bold,
         danmaku_outline: config.danmaku_option.outline,
         danmaku_time_offset: config.danmaku_option.time_offset,
+        danmaku_formats: config.danmaku_option.danmaku_formats.clone(),
+        danmaku_block_keywords: config.danmaku_option.danmaku_block_keywords.clone(),
+        danmaku_block_regex: config.danmaku_option.danmaku_block_regex,
+        danmaku_max_per_second: config.danmaku_option.danmaku_max_per_second,
+        danmaku_exclude_modes: config.danmaku_option.danmaku_exclude_modes.clone(),
         // 并发控制设置
         concurrent_video: config.concurrent_limit.video,
         concurrent_page: config.concurrent_limit.page,
@@ -4451,6 +7803,18 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         rate_duration: config.concurrent_limit.rate_limit.as_ref().map(|r| r.duration),
         // 其他设置
         cdn_sorting: config.cdn_sorting,
+        verify_download_integrity: config.verify_download_integrity,
+        download_subtitles: config.download_subtitles,
+        bangumi_write_skip_markers: config.bangumi_write_skip_markers,
+        bangumi_skip_specials: config.bangumi_skip_specials,
+        proxy_url: config.proxy_url.clone(),
+        user_agent: config.user_agent.clone(),
+        extra_headers: config.extra_headers.clone(),
+        // 静默时段配置
+        quiet_hours_enabled: config.quiet_hours.enabled,
+        quiet_hours_start: config.quiet_hours.start.clone(),
+        quiet_hours_end: config.quiet_hours.end.clone(),
+        quiet_hours_weekdays: config.quiet_hours.weekdays.clone(),
         // UP主投稿风控配置
         large_submission_threshold: config.submission_risk_control.large_submission_threshold,
         base_request_delay: config.submission_risk_control.base_request_delay,
@@ -4472,6 +7836,49 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         enable_aria2_health_check: config.enable_aria2_health_check,
         enable_aria2_auto_restart: config.enable_aria2_auto_restart,
         aria2_health_check_interval: config.aria2_health_check_interval,
+        task_max_retry: config.task_max_retry,
+        retention_delete_local_files: config.retention_delete_local_files,
+        delete_to_trash: config.delete_to_trash,
+        trash_retention_days: config.trash_retention_days,
+        dedup_strategy: config.dedup_strategy.as_str().to_string(),
+        cover_max_width: config.cover_max_width,
+        cover_format: config.cover_format.as_str().to_string(),
+        filename_policy: config.filename_policy.as_str().to_string(),
+        max_filename_bytes: config.max_filename_bytes,
+        max_path_bytes: config.max_path_bytes,
+        temp_dir: config.temp_dir.as_ref().map(|dir| dir.to_string_lossy().to_string()),
+        min_free_disk_gb: config.min_free_disk_gb,
+        jellyfin_url: config.jellyfin_url.clone(),
+        jellyfin_api_key: config.jellyfin_api_key.as_ref().map(|_| MASKED_SECRET.to_string()),
+        api_rate_limit_per_minute: config.api_rate_limit_per_minute,
+        cors_allowed_origins: config.cors_allowed_origins.clone(),
+        trust_existing_files: config.trust_existing_files,
+        ffmpeg_path: config
+            .ffmpeg_path
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string()),
+        ffmpeg_hwaccel: config.ffmpeg_hwaccel.clone(),
+        ffmpeg_merge_timeout_secs: config.ffmpeg_merge_timeout_secs,
+        generate_thumbnail_fallback: config.generate_thumbnail_fallback,
+        refresh_covers: config.refresh_covers,
+        refresh_upper_avatar_days: config.refresh_upper_avatar_days,
+        skip_upower_exclusive: config.skip_upower_exclusive,
+        write_scan_report: config.write_scan_report,
+        source_scan_concurrency: config.source_scan_concurrency,
+        season_title_cache_size: config.season_title_cache_size,
+        season_title_cache_ttl_secs: config.season_title_cache_ttl_secs,
+        db_max_connections: config.db_max_connections,
+        db_acquire_timeout: config.db_acquire_timeout,
+        db_busy_timeout: config.db_busy_timeout,
+        download_max_retries: config.download_max_retries,
+        download_retry_base_delay_ms: config.download_retry_base_delay_ms,
+        aria2_rpc_url: config.aria2_rpc_url.clone(),
+        aria2_rpc_secret: config.aria2_rpc_secret.as_ref().map(|_| MASKED_SECRET.to_string()),
+        webdav_url: config.webdav.url.clone(),
+        webdav_username: config.webdav.username.clone(),
+        webdav_password: config.webdav.password.as_ref().map(|_| MASKED_SECRET.to_string()),
+        webdav_base_path: config.webdav.base_path.clone(),
+        webdav_delete_local: config.webdav.delete_local,
         // 多P视频目录结构配置
         multi_page_use_season_structure: config.multi_page_use_season_structure,
         // 合集目录结构配置
@@ -4501,8 +7908,14 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
             bark_device_key: config.notification.bark_device_key.clone(),
             bark_device_keys: config.notification.bark_device_keys.clone(),
             bark_defaults: crate::api::response::BarkDefaultsResponse::from(&config.notification.bark_defaults),
+            telegram_bot_token: config.notification.telegram_bot_token.clone(),
+            telegram_chat_id: config.notification.telegram_chat_id.clone(),
+            discord_webhook_url: config.notification.discord_webhook_url.clone(),
+            webhook_url: config.notification.webhook_url.clone(),
+            webhook_template: config.notification.webhook_template.clone(),
             events: crate::api::response::NotificationEventsResponse::from(&config.notification.events),
             enable_scan_notifications: config.notification.enable_scan_notifications,
+            enable_failure_notifications: config.notification.enable_failure_notifications,
             notification_min_videos: config.notification.notification_min_videos,
             notification_timeout: config.notification.notification_timeout,
             notification_retry_count: config.notification.notification_retry_count,
@@ -4520,6 +7933,7 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
                     solve_timeout: auto_solve.solve_timeout,
                 }
             }),
+            fingerprint_strategy: config.risk_control.fingerprint_strategy.clone(),
         },
         // 服务器绑定地址
         bind_address: config.bind_address.clone(),
@@ -4541,6 +7955,14 @@ pub async fn update_config(
     Extension(db): Extension<Arc<DatabaseConnection>>,
     axum::Json(params): axum::Json<crate::api::request::UpdateConfigRequest>,
 ) -> Result<ApiResponse<crate::api::response::UpdateConfigResponse>, ApiError> {
+    Ok(ApiResponse::ok(update_config_or_enqueue(db, params).await?))
+}
+
+/// 更新配置，正在扫描时加入配置任务队列，扫描结束后处理
+async fn update_config_or_enqueue(
+    db: Arc<DatabaseConnection>,
+    params: crate::api::request::UpdateConfigRequest,
+) -> Result<crate::api::response::UpdateConfigResponse, ApiError> {
     // 检查是否正在扫描
     if crate::task::is_scanning() {
         // 正在扫描，将更新配置任务加入队列
@@ -4556,8 +7978,15 @@ pub async fn update_config(
             time_format: params.time_format.clone(),
             interval: params.interval,
             nfo_time_type: params.nfo_time_type.clone(),
+            nfo_flavor: params.nfo_flavor.clone(),
+            nfo_template: params.nfo_template.clone(),
+            nfo_download_actor_images: params.nfo_download_actor_images,
+            nfo_include_tags: params.nfo_include_tags,
+            nfo_max_tags: params.nfo_max_tags,
+            nfo_include_chapters: params.nfo_include_chapters,
             parallel_download_enabled: params.parallel_download_enabled,
             parallel_download_threads: params.parallel_download_threads,
+            download_speed_limit_kbps: params.download_speed_limit_kbps,
             // 视频质量设置
             video_max_quality: params.video_max_quality.clone(),
             video_min_quality: params.video_min_quality.clone(),
@@ -4568,6 +7997,8 @@ pub async fn update_config(
             no_dolby_audio: params.no_dolby_audio,
             no_hdr: params.no_hdr,
             no_hires: params.no_hires,
+            min_duration_secs: params.min_duration_secs,
+            max_duration_secs: params.max_duration_secs,
             // 弹幕设置
             danmaku_duration: params.danmaku_duration,
             danmaku_font: params.danmaku_font.clone(),
@@ -4581,6 +8012,11 @@ pub async fn update_config(
             danmaku_bold: params.danmaku_bold,
             danmaku_outline: params.danmaku_outline,
             danmaku_time_offset: params.danmaku_time_offset,
+            danmaku_formats: params.danmaku_formats.clone(),
+            danmaku_block_keywords: params.danmaku_block_keywords.clone(),
+            danmaku_block_regex: params.danmaku_block_regex,
+            danmaku_max_per_second: params.danmaku_max_per_second,
+            danmaku_exclude_modes: params.danmaku_exclude_modes.clone(),
             // 并发控制设置
             concurrent_video: params.concurrent_video,
             concurrent_page: params.concurrent_page,
@@ -4588,6 +8024,12 @@ pub async fn update_config(
             rate_duration: params.rate_duration,
             // 其他设置
             cdn_sorting: params.cdn_sorting,
+            verify_download_integrity: params.verify_download_integrity,
+            download_subtitles: params.download_subtitles,
+            bangumi_write_skip_markers: params.bangumi_write_skip_markers,
+            bangumi_skip_specials: params.bangumi_skip_specials,
+            proxy_url: params.proxy_url.clone(),
+            user_agent: params.user_agent.clone(),
             // UP主投稿风控配置
             large_submission_threshold: params.large_submission_threshold,
             base_request_delay: params.base_request_delay,
@@ -4612,6 +8054,80 @@ pub async fn update_config(
             bangumi_use_season_structure: params.bangumi_use_season_structure,
             // UP主头像保存路径
             upper_path: params.upper_path.clone(),
+            // 静默时段配置
+            quiet_hours_enabled: params.quiet_hours_enabled,
+            quiet_hours_start: params.quiet_hours_start.clone(),
+            quiet_hours_end: params.quiet_hours_end.clone(),
+            quiet_hours_weekdays: params.quiet_hours_weekdays.clone(),
+            // 外部aria2 RPC配置
+            aria2_rpc_url: params.aria2_rpc_url.clone(),
+            aria2_rpc_secret: params.aria2_rpc_secret.clone(),
+            // Jellyfin 媒体库刷新配置
+            jellyfin_url: params.jellyfin_url.clone(),
+            jellyfin_api_key: params.jellyfin_api_key.clone(),
+            // 队列任务最大重试次数
+            task_max_retry: params.task_max_retry,
+            // WebDAV 上传配置
+            webdav_url: params.webdav_url.clone(),
+            webdav_username: params.webdav_username.clone(),
+            webdav_password: params.webdav_password.clone(),
+            webdav_base_path: params.webdav_base_path.clone(),
+            webdav_delete_local: params.webdav_delete_local,
+            // 保留策略清理视频时是否删除本地文件
+            retention_delete_local_files: params.retention_delete_local_files,
+            // 跨视频源的重复分页去重方式
+            dedup_strategy: params.dedup_strategy.clone(),
+            // 下载暂存目录
+            temp_dir: params.temp_dir.clone(),
+            // 磁盘剩余空间阈值（GB）
+            min_free_disk_gb: params.min_free_disk_gb,
+            // 同一 IP 每分钟允许的认证相关请求数
+            api_rate_limit_per_minute: params.api_rate_limit_per_minute,
+            // 是否信任媒体目录中已存在的文件
+            trust_existing_files: params.trust_existing_files,
+            // FFmpeg 路径与硬件加速方式
+            ffmpeg_path: params.ffmpeg_path.clone(),
+            ffmpeg_hwaccel: params.ffmpeg_hwaccel.clone(),
+            // 封面缺失时是否从视频中截取一帧作为封面
+            generate_thumbnail_fallback: params.generate_thumbnail_fallback,
+            // 扫描合集时是否同步最新封面
+            refresh_covers: params.refresh_covers,
+            // UP主头像检查间隔（天）
+            refresh_upper_avatar_days: params.refresh_upper_avatar_days,
+            // 同时扫描的视频源数量
+            source_scan_concurrency: params.source_scan_concurrency,
+            // 番剧标题缓存的最大条目数与过期时间（秒）
+            season_title_cache_size: params.season_title_cache_size,
+            season_title_cache_ttl_secs: params.season_title_cache_ttl_secs,
+            // 数据库连接池配置
+            db_max_connections: params.db_max_connections,
+            db_acquire_timeout: params.db_acquire_timeout,
+            db_busy_timeout: params.db_busy_timeout,
+            // 回收站配置
+            delete_to_trash: params.delete_to_trash,
+            trash_retention_days: params.trash_retention_days,
+            // FFmpeg 合并与弹幕嵌入的超时时间（秒）
+            ffmpeg_merge_timeout_secs: params.ffmpeg_merge_timeout_secs,
+            // 封面最大宽度与格式
+            cover_max_width: params.cover_max_width,
+            cover_format: params.cover_format.clone(),
+            // 允许跨域访问的来源列表
+            cors_allowed_origins: params.cors_allowed_origins.clone(),
+            // 文件名安全化策略与最大字节数
+            filename_policy: params.filename_policy.clone(),
+            max_filename_bytes: params.max_filename_bytes,
+            // 完整路径的最大字节数
+            max_path_bytes: params.max_path_bytes,
+            // 下载失败重试次数与基础等待时间（毫秒）
+            download_max_retries: params.download_max_retries,
+            download_retry_base_delay_ms: params.download_retry_base_delay_ms,
+            // 是否跳过无权观看的充电专享视频
+            skip_upower_exclusive: params.skip_upower_exclusive,
+            // 是否在每轮扫描后写入扫描报告文件
+            write_scan_report: params.write_scan_report,
+            // 访问B站时附加的请求头
+            extra_headers: params.extra_headers.clone(),
+            priority: params.priority.unwrap_or(crate::task::DEFAULT_TASK_PRIORITY),
             task_id: task_id.clone(),
         };
 
@@ -4619,20 +8135,17 @@ pub async fn update_config(
 
         info!("检测到正在扫描，更新配置任务已加入队列等待处理");
 
-        return Ok(ApiResponse::ok(crate::api::response::UpdateConfigResponse {
+        return Ok(crate::api::response::UpdateConfigResponse {
             success: true,
             message: "正在扫描中，更新配置任务已加入队列，将在扫描完成后自动处理".to_string(),
             updated_files: None,
             resetted_nfo_videos_count: None,
             resetted_nfo_pages_count: None,
-        }));
+        });
     }
 
     // 没有扫描，直接执行更新配置
-    match update_config_internal(db, params).await {
-        Ok(response) => Ok(ApiResponse::ok(response)),
-        Err(e) => Err(e),
-    }
+    update_config_internal(db, params).await
 }
 
 /// 内部更新配置函数（用于队列处理和直接调用）
@@ -4720,10 +8233,8 @@ pub async fn update_config_internal(
     }
 
     if let Some(nfo_time_type) = params.nfo_time_type {
-        let new_nfo_time_type = match nfo_time_type.as_str() {
-            "favtime" => crate::config::NFOTimeType::FavTime,
-            "pubtime" => crate::config::NFOTimeType::PubTime,
-            _ => return Err(anyhow!("无效的NFO时间类型，只支持 'favtime' 或 'pubtime'").into()),
+        let Some(new_nfo_time_type) = crate::config::NFOTimeType::parse(&nfo_time_type) else {
+            return Err(anyhow!("无效的NFO时间类型，只支持 'favtime'、'pubtime' 或 'ctime'").into());
         };
 
         // 只有当NFO时间类型真正发生变化时才标记为需要更新
@@ -4733,6 +8244,54 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(nfo_flavor) = params.nfo_flavor {
+        let new_nfo_flavor = crate::config::NFOFlavor::parse(nfo_flavor.as_str())
+            .ok_or_else(|| anyhow!("无效的NFO风格，只支持 'emby'、'kodi' 或 'plex'"))?;
+        if new_nfo_flavor != config.nfo_flavor {
+            config.nfo_flavor = new_nfo_flavor;
+            updated_fields.push("nfo_flavor");
+        }
+    }
+
+    if let Some(nfo_template) = params.nfo_template {
+        let nfo_template = Some(nfo_template).filter(|template| !template.trim().is_empty());
+        if let Some(template) = &nfo_template {
+            crate::utils::nfo::validate_nfo_template(template).map_err(|e| anyhow!("NFO 模板解析失败: {}", e))?;
+        }
+        if nfo_template != config.nfo_template {
+            config.nfo_template = nfo_template;
+            updated_fields.push("nfo_template");
+        }
+    }
+
+    if let Some(nfo_download_actor_images) = params.nfo_download_actor_images {
+        if nfo_download_actor_images != config.nfo_download_actor_images {
+            config.nfo_download_actor_images = nfo_download_actor_images;
+            updated_fields.push("nfo_download_actor_images");
+        }
+    }
+
+    if let Some(nfo_include_tags) = params.nfo_include_tags {
+        if nfo_include_tags != config.nfo_include_tags {
+            config.nfo_include_tags = nfo_include_tags;
+            updated_fields.push("nfo_include_tags");
+        }
+    }
+
+    if let Some(nfo_max_tags) = params.nfo_max_tags {
+        if nfo_max_tags != config.nfo_max_tags {
+            config.nfo_max_tags = nfo_max_tags;
+            updated_fields.push("nfo_max_tags");
+        }
+    }
+
+    if let Some(nfo_include_chapters) = params.nfo_include_chapters {
+        if nfo_include_chapters != config.nfo_include_chapters {
+            config.nfo_include_chapters = nfo_include_chapters;
+            updated_fields.push("nfo_include_chapters");
+        }
+    }
+
     if let Some(bangumi_name) = params.bangumi_name {
         if !bangumi_name.trim().is_empty() && bangumi_name != original_bangumi_name.as_ref() {
             config.bangumi_name = Cow::Owned(bangumi_name);
@@ -4762,6 +8321,14 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(kbps) = params.download_speed_limit_kbps {
+        let new_limit = (kbps > 0).then_some(kbps);
+        if new_limit != config.concurrent_limit.download_speed_limit_kbps {
+            config.concurrent_limit.download_speed_limit_kbps = new_limit;
+            updated_fields.push("download_speed_limit_kbps");
+        }
+    }
+
     // 处理视频质量设置
     if let Some(quality) = params.video_max_quality {
         use crate::bilibili::VideoQuality;
@@ -4845,6 +8412,22 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(min_duration_secs) = params.min_duration_secs {
+        let min_duration_secs = Some(min_duration_secs).filter(|secs| *secs > 0);
+        if min_duration_secs != config.filter_option.min_duration_secs {
+            config.filter_option.min_duration_secs = min_duration_secs;
+            updated_fields.push("min_duration_secs");
+        }
+    }
+
+    if let Some(max_duration_secs) = params.max_duration_secs {
+        let max_duration_secs = Some(max_duration_secs).filter(|secs| *secs > 0);
+        if max_duration_secs != config.filter_option.max_duration_secs {
+            config.filter_option.max_duration_secs = max_duration_secs;
+            updated_fields.push("max_duration_secs");
+        }
+    }
+
     // 处理弹幕设置
     if let Some(duration) = params.danmaku_duration {
         if duration != config.danmaku_option.duration {
@@ -4930,6 +8513,65 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(formats) = params.danmaku_formats {
+        let mut danmaku_formats = Vec::new();
+        for format in formats {
+            let format = format.trim().to_lowercase();
+            if !crate::bilibili::DANMAKU_FORMATS.contains(&format.as_str()) {
+                return Err(anyhow!("不支持的弹幕格式: {}，可选值为 ass、xml、json", format).into());
+            }
+            if !danmaku_formats.contains(&format) {
+                danmaku_formats.push(format);
+            }
+        }
+        if danmaku_formats.is_empty() {
+            return Err(anyhow!("至少需要选择一种弹幕格式").into());
+        }
+        if danmaku_formats != config.danmaku_option.danmaku_formats {
+            config.danmaku_option.danmaku_formats = danmaku_formats;
+            updated_fields.push("danmaku_formats");
+        }
+    }
+
+    if params.danmaku_block_keywords.is_some() || params.danmaku_block_regex.is_some() {
+        let block_keywords: Vec<String> = params
+            .danmaku_block_keywords
+            .unwrap_or_else(|| config.danmaku_option.danmaku_block_keywords.clone())
+            .into_iter()
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+        let block_regex = params
+            .danmaku_block_regex
+            .unwrap_or(config.danmaku_option.danmaku_block_regex);
+        crate::utils::keyword_matcher::validate_terms(&block_keywords, block_regex)?;
+        if block_keywords != config.danmaku_option.danmaku_block_keywords {
+            config.danmaku_option.danmaku_block_keywords = block_keywords;
+            updated_fields.push("danmaku_block_keywords");
+        }
+        if block_regex != config.danmaku_option.danmaku_block_regex {
+            config.danmaku_option.danmaku_block_regex = block_regex;
+            updated_fields.push("danmaku_block_regex");
+        }
+    }
+
+    if let Some(max_per_second) = params.danmaku_max_per_second {
+        let max_per_second = Some(max_per_second).filter(|max| *max > 0);
+        if max_per_second != config.danmaku_option.danmaku_max_per_second {
+            config.danmaku_option.danmaku_max_per_second = max_per_second;
+            updated_fields.push("danmaku_max_per_second");
+        }
+    }
+
+    if let Some(mut exclude_modes) = params.danmaku_exclude_modes {
+        exclude_modes.sort_unstable();
+        exclude_modes.dedup();
+        if exclude_modes != config.danmaku_option.danmaku_exclude_modes {
+            config.danmaku_option.danmaku_exclude_modes = exclude_modes;
+            updated_fields.push("danmaku_exclude_modes");
+        }
+    }
+
     // 处理并发控制设置
     if let Some(concurrent_video) = params.concurrent_video {
         if concurrent_video > 0 && concurrent_video != config.concurrent_limit.video {
@@ -4989,43 +8631,531 @@ pub async fn update_config_internal(
         }
     }
 
-    // 处理其他设置
-    if let Some(cdn_sorting) = params.cdn_sorting {
-        if cdn_sorting != config.cdn_sorting {
-            config.cdn_sorting = cdn_sorting;
-            updated_fields.push("cdn_sorting");
+    // 处理其他设置
+    if let Some(cdn_sorting) = params.cdn_sorting {
+        if cdn_sorting != config.cdn_sorting {
+            config.cdn_sorting = cdn_sorting;
+            updated_fields.push("cdn_sorting");
+        }
+    }
+
+    if let Some(verify_download_integrity) = params.verify_download_integrity {
+        if verify_download_integrity != config.verify_download_integrity {
+            config.verify_download_integrity = verify_download_integrity;
+            updated_fields.push("verify_download_integrity");
+        }
+    }
+
+    if let Some(download_subtitles) = params.download_subtitles {
+        if download_subtitles != config.download_subtitles {
+            config.download_subtitles = download_subtitles;
+            updated_fields.push("download_subtitles");
+        }
+    }
+
+    if let Some(bangumi_write_skip_markers) = params.bangumi_write_skip_markers {
+        if bangumi_write_skip_markers != config.bangumi_write_skip_markers {
+            config.bangumi_write_skip_markers = bangumi_write_skip_markers;
+            updated_fields.push("bangumi_write_skip_markers");
+        }
+    }
+
+    if let Some(bangumi_skip_specials) = params.bangumi_skip_specials {
+        if bangumi_skip_specials != config.bangumi_skip_specials {
+            config.bangumi_skip_specials = bangumi_skip_specials;
+            updated_fields.push("bangumi_skip_specials");
+        }
+    }
+
+    if let Some(proxy_url) = params.proxy_url {
+        let proxy_url = Some(proxy_url.trim().to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = &proxy_url {
+            crate::bilibili::build_proxy(url)?;
+        }
+        if proxy_url != config.proxy_url {
+            config.proxy_url = proxy_url;
+            updated_fields.push("proxy_url");
+        }
+    }
+
+    // User-Agent 在新建的请求客户端中生效
+    if let Some(user_agent) = params.user_agent {
+        let user_agent = Some(user_agent.trim().to_string()).filter(|ua| !ua.is_empty());
+        if let Some(ua) = &user_agent {
+            crate::http::headers::validate_user_agent(ua)?;
+        }
+        if user_agent != config.user_agent {
+            config.user_agent = user_agent;
+            updated_fields.push("user_agent");
+        }
+    }
+
+    // 额外请求头同样在新建的请求客户端中生效
+    if let Some(extra_headers) = params.extra_headers {
+        let extra_headers: std::collections::HashMap<String, String> = extra_headers
+            .into_iter()
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        crate::http::headers::validate_extra_headers(&extra_headers)?;
+        if extra_headers != config.extra_headers {
+            config.extra_headers = extra_headers;
+            updated_fields.push("extra_headers");
+        }
+    }
+
+    // 静默时段配置，各字段合并后整体校验
+    let mut quiet_hours = config.quiet_hours.clone();
+    if let Some(enabled) = params.quiet_hours_enabled {
+        quiet_hours.enabled = enabled;
+    }
+    if let Some(start) = params.quiet_hours_start {
+        quiet_hours.start = start.trim().to_string();
+    }
+    if let Some(end) = params.quiet_hours_end {
+        quiet_hours.end = end.trim().to_string();
+    }
+    if let Some(mut weekdays) = params.quiet_hours_weekdays {
+        weekdays.sort_unstable();
+        weekdays.dedup();
+        quiet_hours.weekdays = weekdays;
+    }
+    if quiet_hours.enabled != config.quiet_hours.enabled
+        || quiet_hours.start != config.quiet_hours.start
+        || quiet_hours.end != config.quiet_hours.end
+        || quiet_hours.weekdays != config.quiet_hours.weekdays
+    {
+        quiet_hours
+            .validate()
+            .map_err(|e| InnerApiError::BadRequest(format!("静默时段配置无效: {}", e)))?;
+        config.quiet_hours = quiet_hours;
+        updated_fields.push("quiet_hours");
+    }
+
+    // 处理显示已删除视频配置
+    if let Some(scan_deleted) = params.scan_deleted_videos {
+        if scan_deleted != config.scan_deleted_videos {
+            config.scan_deleted_videos = scan_deleted;
+            updated_fields.push("scan_deleted_videos");
+        }
+    }
+
+    // 处理aria2监控配置
+    if let Some(enable_health_check) = params.enable_aria2_health_check {
+        if enable_health_check != config.enable_aria2_health_check {
+            config.enable_aria2_health_check = enable_health_check;
+            updated_fields.push("enable_aria2_health_check");
+        }
+    }
+
+    if let Some(enable_auto_restart) = params.enable_aria2_auto_restart {
+        if enable_auto_restart != config.enable_aria2_auto_restart {
+            config.enable_aria2_auto_restart = enable_auto_restart;
+            updated_fields.push("enable_aria2_auto_restart");
+        }
+    }
+
+    if let Some(check_interval) = params.aria2_health_check_interval {
+        if check_interval > 0 && check_interval != config.aria2_health_check_interval {
+            config.aria2_health_check_interval = check_interval;
+            updated_fields.push("aria2_health_check_interval");
+        }
+    }
+
+    if let Some(max_retry) = params.task_max_retry {
+        if max_retry != config.task_max_retry {
+            config.task_max_retry = max_retry;
+            updated_fields.push("task_max_retry");
+        }
+    }
+
+    if let Some(delete_local_files) = params.retention_delete_local_files {
+        if delete_local_files != config.retention_delete_local_files {
+            config.retention_delete_local_files = delete_local_files;
+            updated_fields.push("retention_delete_local_files");
+        }
+    }
+
+    if let Some(delete_to_trash) = params.delete_to_trash {
+        if delete_to_trash != config.delete_to_trash {
+            config.delete_to_trash = delete_to_trash;
+            updated_fields.push("delete_to_trash");
+        }
+    }
+
+    if let Some(trash_retention_days) = params.trash_retention_days {
+        if trash_retention_days != config.trash_retention_days {
+            config.trash_retention_days = trash_retention_days;
+            updated_fields.push("trash_retention_days");
+        }
+    }
+
+    if let Some(dedup_strategy) = params.dedup_strategy {
+        let new_dedup_strategy = crate::config::DedupStrategy::parse(dedup_strategy.as_str())
+            .ok_or_else(|| anyhow!("无效的去重方式，只支持 'none'、'hardlink' 或 'symlink'"))?;
+        if new_dedup_strategy != config.dedup_strategy {
+            config.dedup_strategy = new_dedup_strategy;
+            updated_fields.push("dedup_strategy");
+        }
+    }
+
+    if let Some(cover_max_width) = params.cover_max_width {
+        if cover_max_width > crate::config::MAX_COVER_WIDTH {
+            return Err(
+                InnerApiError::BadRequest(format!("封面最大宽度不能超过 {}", crate::config::MAX_COVER_WIDTH)).into(),
+            );
+        }
+        if cover_max_width != config.cover_max_width {
+            config.cover_max_width = cover_max_width;
+            updated_fields.push("cover_max_width");
+        }
+    }
+
+    if let Some(cover_format) = params.cover_format {
+        let new_cover_format = crate::config::CoverFormat::parse(cover_format.as_str())
+            .ok_or_else(|| anyhow!("无效的封面格式，只支持 'original'、'jpg' 或 'webp'"))?;
+        if new_cover_format != config.cover_format {
+            config.cover_format = new_cover_format;
+            updated_fields.push("cover_format");
+        }
+    }
+
+    if let Some(filename_policy) = params.filename_policy {
+        let new_filename_policy = crate::config::FilenamePolicy::parse(filename_policy.as_str())
+            .ok_or_else(|| anyhow!("无效的文件名策略，只支持 'default'、'ascii' 或 'preserve_unicode'"))?;
+        if new_filename_policy != config.filename_policy {
+            config.filename_policy = new_filename_policy;
+            updated_fields.push("filename_policy");
+        }
+    }
+
+    if let Some(max_filename_bytes) = params.max_filename_bytes {
+        if max_filename_bytes != 0 && max_filename_bytes < crate::config::MIN_FILENAME_BYTES {
+            return Err(InnerApiError::BadRequest(format!(
+                "文件名最大字节数不能小于 {}（0 表示不限制）",
+                crate::config::MIN_FILENAME_BYTES
+            ))
+            .into());
+        }
+        if max_filename_bytes != config.max_filename_bytes {
+            config.max_filename_bytes = max_filename_bytes;
+            updated_fields.push("max_filename_bytes");
+        }
+    }
+
+    if let Some(max_path_bytes) = params.max_path_bytes {
+        if max_path_bytes != 0 && max_path_bytes < crate::config::MIN_PATH_BYTES {
+            return Err(InnerApiError::BadRequest(format!(
+                "路径最大字节数不能小于 {}（0 表示不限制）",
+                crate::config::MIN_PATH_BYTES
+            ))
+            .into());
+        }
+        if max_path_bytes != config.max_path_bytes {
+            config.max_path_bytes = max_path_bytes;
+            updated_fields.push("max_path_bytes");
+        }
+    }
+
+    if let Some(temp_dir) = params.temp_dir {
+        let temp_dir = Some(temp_dir.trim().to_string())
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+        if let Some(dir) = &temp_dir {
+            if !dir.is_absolute() {
+                return Err(anyhow!("下载暂存目录必须是绝对路径").into());
+            }
+        }
+        if temp_dir != config.temp_dir {
+            config.temp_dir = temp_dir;
+            updated_fields.push("temp_dir");
+        }
+    }
+
+    if let Some(min_free_disk_gb) = params.min_free_disk_gb {
+        if min_free_disk_gb != config.min_free_disk_gb {
+            config.min_free_disk_gb = min_free_disk_gb;
+            updated_fields.push("min_free_disk_gb");
+        }
+    }
+
+    if let Some(jellyfin_url) = params.jellyfin_url {
+        let jellyfin_url = Some(jellyfin_url.trim().trim_end_matches('/').to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = &jellyfin_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(anyhow!("Jellyfin 地址必须以 http:// 或 https:// 开头").into());
+            }
+        }
+        if jellyfin_url != config.jellyfin_url {
+            config.jellyfin_url = jellyfin_url;
+            updated_fields.push("jellyfin_url");
+        }
+    }
+
+    if let Some(api_key) = params.jellyfin_api_key.filter(|key| key != MASKED_SECRET) {
+        let api_key = Some(api_key.trim().to_string()).filter(|key| !key.is_empty());
+        if api_key != config.jellyfin_api_key {
+            config.jellyfin_api_key = api_key;
+            updated_fields.push("jellyfin_api_key");
+        }
+    }
+
+    if let Some(rate_limit) = params.api_rate_limit_per_minute {
+        if rate_limit != config.api_rate_limit_per_minute {
+            config.api_rate_limit_per_minute = rate_limit;
+            updated_fields.push("api_rate_limit_per_minute");
+        }
+    }
+
+    if let Some(cors_allowed_origins) = params.cors_allowed_origins {
+        let cors_allowed_origins: Vec<String> = cors_allowed_origins
+            .iter()
+            .map(|origin| origin.trim().to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+        for origin in &cors_allowed_origins {
+            crate::api::cors::validate_origin(origin).map_err(InnerApiError::BadRequest)?;
+        }
+        if cors_allowed_origins != config.cors_allowed_origins {
+            if config.auth_token.is_some() && cors_allowed_origins.iter().any(|origin| origin == "*") {
+                warn!("跨域来源配置为 *，任意网站都可以在浏览器中调用 API，启用认证时存在安全风险");
+            }
+            config.cors_allowed_origins = cors_allowed_origins;
+            updated_fields.push("cors_allowed_origins");
+        }
+    }
+
+    if let Some(trust_existing_files) = params.trust_existing_files {
+        if trust_existing_files != config.trust_existing_files {
+            config.trust_existing_files = trust_existing_files;
+            updated_fields.push("trust_existing_files");
+        }
+    }
+
+    // FFmpeg 配置在下一次合并时生效
+    if let Some(ffmpeg_path) = params.ffmpeg_path {
+        let ffmpeg_path = Some(ffmpeg_path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from);
+        if let Some(path) = &ffmpeg_path {
+            crate::utils::ffmpeg::validate_ffmpeg_path(path)?;
+        }
+        if ffmpeg_path != config.ffmpeg_path {
+            config.ffmpeg_path = ffmpeg_path;
+            updated_fields.push("ffmpeg_path");
+        }
+    }
+
+    if let Some(hwaccel) = params.ffmpeg_hwaccel {
+        let hwaccel = Some(hwaccel.trim().to_string()).filter(|hwaccel| !hwaccel.is_empty());
+        if let Some(hwaccel) = &hwaccel {
+            crate::utils::ffmpeg::validate_hwaccel(hwaccel)?;
+        }
+        if hwaccel != config.ffmpeg_hwaccel {
+            config.ffmpeg_hwaccel = hwaccel;
+            updated_fields.push("ffmpeg_hwaccel");
+        }
+    }
+
+    if let Some(ffmpeg_merge_timeout_secs) = params.ffmpeg_merge_timeout_secs {
+        if ffmpeg_merge_timeout_secs != config.ffmpeg_merge_timeout_secs {
+            config.ffmpeg_merge_timeout_secs = ffmpeg_merge_timeout_secs;
+            updated_fields.push("ffmpeg_merge_timeout_secs");
+        }
+    }
+
+    if let Some(generate_thumbnail_fallback) = params.generate_thumbnail_fallback {
+        if generate_thumbnail_fallback != config.generate_thumbnail_fallback {
+            config.generate_thumbnail_fallback = generate_thumbnail_fallback;
+            updated_fields.push("generate_thumbnail_fallback");
+        }
+    }
+
+    if let Some(refresh_covers) = params.refresh_covers {
+        if refresh_covers != config.refresh_covers {
+            config.refresh_covers = refresh_covers;
+            updated_fields.push("refresh_covers");
+        }
+    }
+
+    if let Some(refresh_upper_avatar_days) = params.refresh_upper_avatar_days {
+        if refresh_upper_avatar_days != config.refresh_upper_avatar_days {
+            config.refresh_upper_avatar_days = refresh_upper_avatar_days;
+            updated_fields.push("refresh_upper_avatar_days");
+        }
+    }
+
+    if let Some(skip_upower_exclusive) = params.skip_upower_exclusive {
+        if skip_upower_exclusive != config.skip_upower_exclusive {
+            config.skip_upower_exclusive = skip_upower_exclusive;
+            updated_fields.push("skip_upower_exclusive");
+        }
+    }
+
+    if let Some(write_scan_report) = params.write_scan_report {
+        if write_scan_report != config.write_scan_report {
+            config.write_scan_report = write_scan_report;
+            updated_fields.push("write_scan_report");
+        }
+    }
+
+    if let Some(source_scan_concurrency) = params.source_scan_concurrency {
+        if !(1..=crate::config::MAX_SOURCE_SCAN_CONCURRENCY).contains(&source_scan_concurrency) {
+            return Err(InnerApiError::BadRequest(format!(
+                "视频源扫描并发数必须在 1 到 {} 之间",
+                crate::config::MAX_SOURCE_SCAN_CONCURRENCY
+            ))
+            .into());
+        }
+        if source_scan_concurrency != config.source_scan_concurrency {
+            config.source_scan_concurrency = source_scan_concurrency;
+            updated_fields.push("source_scan_concurrency");
+        }
+    }
+
+    if let Some(season_title_cache_size) = params.season_title_cache_size {
+        if season_title_cache_size == 0 {
+            return Err(InnerApiError::BadRequest("番剧标题缓存容量必须大于 0".to_string()).into());
+        }
+        if season_title_cache_size != config.season_title_cache_size {
+            config.season_title_cache_size = season_title_cache_size;
+            updated_fields.push("season_title_cache_size");
+        }
+    }
+
+    if let Some(season_title_cache_ttl_secs) = params.season_title_cache_ttl_secs {
+        if season_title_cache_ttl_secs == 0 {
+            return Err(InnerApiError::BadRequest("番剧标题缓存过期时间必须大于 0".to_string()).into());
+        }
+        if season_title_cache_ttl_secs != config.season_title_cache_ttl_secs {
+            config.season_title_cache_ttl_secs = season_title_cache_ttl_secs;
+            updated_fields.push("season_title_cache_ttl_secs");
+        }
+    }
+
+    // 数据库连接池配置在重启后生效
+    if let Some(db_max_connections) = params.db_max_connections {
+        if !(1..=crate::config::MAX_DB_CONNECTIONS).contains(&db_max_connections) {
+            return Err(InnerApiError::BadRequest(format!(
+                "数据库最大连接数必须在 1 到 {} 之间",
+                crate::config::MAX_DB_CONNECTIONS
+            ))
+            .into());
+        }
+        if db_max_connections != config.db_max_connections {
+            config.db_max_connections = db_max_connections;
+            updated_fields.push("db_max_connections");
+        }
+    }
+
+    if let Some(db_acquire_timeout) = params.db_acquire_timeout {
+        if !(1..=crate::config::MAX_DB_TIMEOUT_SECS).contains(&db_acquire_timeout) {
+            return Err(InnerApiError::BadRequest(format!(
+                "数据库获取连接超时时间必须在 1 到 {} 秒之间",
+                crate::config::MAX_DB_TIMEOUT_SECS
+            ))
+            .into());
+        }
+        if db_acquire_timeout != config.db_acquire_timeout {
+            config.db_acquire_timeout = db_acquire_timeout;
+            updated_fields.push("db_acquire_timeout");
+        }
+    }
+
+    if let Some(db_busy_timeout) = params.db_busy_timeout {
+        if !(1..=crate::config::MAX_DB_TIMEOUT_SECS).contains(&db_busy_timeout) {
+            return Err(InnerApiError::BadRequest(format!(
+                "SQLite 忙等超时时间必须在 1 到 {} 秒之间",
+                crate::config::MAX_DB_TIMEOUT_SECS
+            ))
+            .into());
+        }
+        if db_busy_timeout != config.db_busy_timeout {
+            config.db_busy_timeout = db_busy_timeout;
+            updated_fields.push("db_busy_timeout");
+        }
+    }
+
+    if let Some(download_max_retries) = params.download_max_retries {
+        if download_max_retries > crate::config::MAX_DOWNLOAD_RETRIES {
+            return Err(InnerApiError::BadRequest(format!(
+                "下载重试次数不能超过 {}",
+                crate::config::MAX_DOWNLOAD_RETRIES
+            ))
+            .into());
+        }
+        if download_max_retries != config.download_max_retries {
+            config.download_max_retries = download_max_retries;
+            updated_fields.push("download_max_retries");
         }
     }
 
-    // 处理显示已删除视频配置
-    if let Some(scan_deleted) = params.scan_deleted_videos {
-        if scan_deleted != config.scan_deleted_videos {
-            config.scan_deleted_videos = scan_deleted;
-            updated_fields.push("scan_deleted_videos");
+    if let Some(base_delay_ms) = params.download_retry_base_delay_ms {
+        if !(1..=crate::config::MAX_DOWNLOAD_RETRY_BASE_DELAY_MS).contains(&base_delay_ms) {
+            return Err(InnerApiError::BadRequest(format!(
+                "下载重试基础等待时间必须在 1 到 {} 毫秒之间",
+                crate::config::MAX_DOWNLOAD_RETRY_BASE_DELAY_MS
+            ))
+            .into());
+        }
+        if base_delay_ms != config.download_retry_base_delay_ms {
+            config.download_retry_base_delay_ms = base_delay_ms;
+            updated_fields.push("download_retry_base_delay_ms");
         }
     }
 
-    // 处理aria2监控配置
-    if let Some(enable_health_check) = params.enable_aria2_health_check {
-        if enable_health_check != config.enable_aria2_health_check {
-            config.enable_aria2_health_check = enable_health_check;
-            updated_fields.push("enable_aria2_health_check");
+    // 外部aria2配置在下载器重建（暂停后恢复或重启程序）后生效
+    if let Some(rpc_url) = params.aria2_rpc_url {
+        let rpc_url = Some(rpc_url.trim().to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = &rpc_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(anyhow!("aria2 RPC地址必须以 http:// 或 https:// 开头").into());
+            }
+        }
+        if rpc_url != config.aria2_rpc_url {
+            config.aria2_rpc_url = rpc_url;
+            updated_fields.push("aria2_rpc_url");
         }
     }
 
-    if let Some(enable_auto_restart) = params.enable_aria2_auto_restart {
-        if enable_auto_restart != config.enable_aria2_auto_restart {
-            config.enable_aria2_auto_restart = enable_auto_restart;
-            updated_fields.push("enable_aria2_auto_restart");
+    if let Some(rpc_secret) = params.aria2_rpc_secret.filter(|secret| secret != MASKED_SECRET) {
+        let rpc_secret = Some(rpc_secret).filter(|secret| !secret.is_empty());
+        if rpc_secret != config.aria2_rpc_secret {
+            config.aria2_rpc_secret = rpc_secret;
+            updated_fields.push("aria2_rpc_secret");
         }
     }
 
-    if let Some(check_interval) = params.aria2_health_check_interval {
-        if check_interval > 0 && check_interval != config.aria2_health_check_interval {
-            config.aria2_health_check_interval = check_interval;
-            updated_fields.push("aria2_health_check_interval");
+    // WebDAV 上传配置，各字段合并后整体校验
+    let mut webdav = config.webdav.clone();
+    if let Some(url) = params.webdav_url {
+        webdav.url = Some(url.trim().to_string()).filter(|url| !url.is_empty());
+    }
+    if let Some(username) = params.webdav_username {
+        webdav.username = Some(username.trim().to_string()).filter(|username| !username.is_empty());
+    }
+    if let Some(password) = params.webdav_password {
+        if password != MASKED_SECRET {
+            webdav.password = Some(password).filter(|password| !password.is_empty());
         }
     }
+    if let Some(base_path) = params.webdav_base_path {
+        webdav.base_path = base_path.trim().to_string();
+    }
+    if let Some(delete_local) = params.webdav_delete_local {
+        webdav.delete_local = delete_local;
+    }
+    if webdav.url != config.webdav.url
+        || webdav.username != config.webdav.username
+        || webdav.password != config.webdav.password
+        || webdav.base_path != config.webdav.base_path
+        || webdav.delete_local != config.webdav.delete_local
+    {
+        webdav
+            .validate()
+            .map_err(|e| InnerApiError::BadRequest(format!("WebDAV 配置无效: {}", e)))?;
+        config.webdav = webdav;
+        updated_fields.push("webdav");
+    }
 
     // 处理UP主投稿风控配置
     if let Some(threshold) = params.large_submission_threshold {
@@ -5241,6 +9371,21 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(strategy) = params.risk_control_fingerprint_strategy {
+        let strategy = strategy.trim().to_string();
+        if !strategy.is_empty() && strategy != config.risk_control.fingerprint_strategy {
+            if !crate::hardware::is_valid_fingerprint_strategy(&strategy) {
+                return Err(anyhow!(
+                    "无效的硬件指纹策略，只支持 'auto'、'random' 或预设名称: {}",
+                    crate::hardware::PRESET_STRATEGIES.join(", ")
+                )
+                .into());
+            }
+            config.risk_control.fingerprint_strategy = strategy;
+            updated_fields.push("risk_control.fingerprint_strategy");
+        }
+    }
+
     // 自动验证配置处理
     if let Some(service) = params.risk_control_auto_solve_service {
         if !service.trim().is_empty() {
@@ -5332,6 +9477,22 @@ pub async fn update_config_internal(
         });
     }
 
+    // 保存前使用样例数据渲染模板，避免错误的模板在扫描时才暴露
+    let template_fields = [
+        "video_name",
+        "page_name",
+        "multi_page_name",
+        "bangumi_name",
+        "bangumi_folder_name",
+        "folder_structure",
+    ];
+    if updated_fields.iter().any(|field| template_fields.contains(field)) {
+        let errors = crate::config::ConfigBundle::check_templates(&config, &crate::config::sample_template_args());
+        if !errors.is_empty() {
+            return Err(InnerApiError::BadRequest(errors.join("；")).into());
+        }
+    }
+
     // 移除配置文件保存 - 配置现在完全基于数据库
     // config.save()?;
 
@@ -5400,6 +9561,42 @@ pub async fn update_config_internal(
                         .update_config_item("nfo_time_type", serde_json::to_value(&config.nfo_time_type)?)
                         .await
                 }
+                "nfo_flavor" => {
+                    manager
+                        .update_config_item("nfo_flavor", serde_json::to_value(config.nfo_flavor)?)
+                        .await
+                }
+                "nfo_template" => {
+                    manager
+                        .update_config_item("nfo_template", serde_json::to_value(&config.nfo_template)?)
+                        .await
+                }
+                "nfo_download_actor_images" => {
+                    manager
+                        .update_config_item(
+                            "nfo_download_actor_images",
+                            serde_json::to_value(config.nfo_download_actor_images)?,
+                        )
+                        .await
+                }
+                "nfo_include_tags" => {
+                    manager
+                        .update_config_item("nfo_include_tags", serde_json::to_value(config.nfo_include_tags)?)
+                        .await
+                }
+                "nfo_max_tags" => {
+                    manager
+                        .update_config_item("nfo_max_tags", serde_json::to_value(config.nfo_max_tags)?)
+                        .await
+                }
+                "nfo_include_chapters" => {
+                    manager
+                        .update_config_item(
+                            "nfo_include_chapters",
+                            serde_json::to_value(config.nfo_include_chapters)?,
+                        )
+                        .await
+                }
                 "upper_path" => {
                     manager
                         .update_config_item("upper_path", serde_json::to_value(&config.upper_path)?)
@@ -5407,46 +9604,317 @@ pub async fn update_config_internal(
                 }
                 "bind_address" => {
                     manager
-                        .update_config_item("bind_address", serde_json::to_value(&config.bind_address)?)
+                        .update_config_item("bind_address", serde_json::to_value(&config.bind_address)?)
+                        .await
+                }
+                "concurrent_limit" => {
+                    manager
+                        .update_config_item("concurrent_limit", serde_json::to_value(&config.concurrent_limit)?)
+                        .await
+                }
+                "cdn_sorting" => {
+                    manager
+                        .update_config_item("cdn_sorting", serde_json::to_value(config.cdn_sorting)?)
+                        .await
+                }
+                "download_subtitles" => {
+                    manager
+                        .update_config_item("download_subtitles", serde_json::to_value(config.download_subtitles)?)
+                        .await
+                }
+                "bangumi_write_skip_markers" => {
+                    manager
+                        .update_config_item(
+                            "bangumi_write_skip_markers",
+                            serde_json::to_value(config.bangumi_write_skip_markers)?,
+                        )
+                        .await
+                }
+                "bangumi_skip_specials" => {
+                    manager
+                        .update_config_item(
+                            "bangumi_skip_specials",
+                            serde_json::to_value(config.bangumi_skip_specials)?,
+                        )
+                        .await
+                }
+                "verify_download_integrity" => {
+                    manager
+                        .update_config_item(
+                            "verify_download_integrity",
+                            serde_json::to_value(config.verify_download_integrity)?,
+                        )
+                        .await
+                }
+                "proxy_url" => {
+                    manager
+                        .update_config_item("proxy_url", serde_json::to_value(&config.proxy_url)?)
+                        .await
+                }
+                "user_agent" => {
+                    manager
+                        .update_config_item("user_agent", serde_json::to_value(&config.user_agent)?)
+                        .await
+                }
+                "extra_headers" => {
+                    manager
+                        .update_config_item("extra_headers", serde_json::to_value(&config.extra_headers)?)
+                        .await
+                }
+                "quiet_hours" => {
+                    manager
+                        .update_config_item("quiet_hours", serde_json::to_value(&config.quiet_hours)?)
+                        .await
+                }
+                "scan_deleted_videos" => {
+                    manager
+                        .update_config_item("scan_deleted_videos", serde_json::to_value(config.scan_deleted_videos)?)
+                        .await
+                }
+                "enable_aria2_health_check" => {
+                    manager
+                        .update_config_item(
+                            "enable_aria2_health_check",
+                            serde_json::to_value(config.enable_aria2_health_check)?,
+                        )
+                        .await
+                }
+                "enable_aria2_auto_restart" => {
+                    manager
+                        .update_config_item(
+                            "enable_aria2_auto_restart",
+                            serde_json::to_value(config.enable_aria2_auto_restart)?,
+                        )
+                        .await
+                }
+                "aria2_health_check_interval" => {
+                    manager
+                        .update_config_item(
+                            "aria2_health_check_interval",
+                            serde_json::to_value(config.aria2_health_check_interval)?,
+                        )
+                        .await
+                }
+                "task_max_retry" => {
+                    manager
+                        .update_config_item("task_max_retry", serde_json::to_value(config.task_max_retry)?)
+                        .await
+                }
+                "retention_delete_local_files" => {
+                    manager
+                        .update_config_item(
+                            "retention_delete_local_files",
+                            serde_json::to_value(config.retention_delete_local_files)?,
+                        )
+                        .await
+                }
+                "delete_to_trash" => {
+                    manager
+                        .update_config_item("delete_to_trash", serde_json::to_value(config.delete_to_trash)?)
+                        .await
+                }
+                "trash_retention_days" => {
+                    manager
+                        .update_config_item(
+                            "trash_retention_days",
+                            serde_json::to_value(config.trash_retention_days)?,
+                        )
+                        .await
+                }
+                "dedup_strategy" => {
+                    manager
+                        .update_config_item("dedup_strategy", serde_json::to_value(config.dedup_strategy)?)
+                        .await
+                }
+                "cover_max_width" => {
+                    manager
+                        .update_config_item("cover_max_width", serde_json::to_value(config.cover_max_width)?)
+                        .await
+                }
+                "cover_format" => {
+                    manager
+                        .update_config_item("cover_format", serde_json::to_value(config.cover_format)?)
+                        .await
+                }
+                "filename_policy" => {
+                    manager
+                        .update_config_item("filename_policy", serde_json::to_value(config.filename_policy)?)
+                        .await
+                }
+                "max_filename_bytes" => {
+                    manager
+                        .update_config_item("max_filename_bytes", serde_json::to_value(config.max_filename_bytes)?)
+                        .await
+                }
+                "max_path_bytes" => {
+                    manager
+                        .update_config_item("max_path_bytes", serde_json::to_value(config.max_path_bytes)?)
+                        .await
+                }
+                "temp_dir" => {
+                    manager
+                        .update_config_item("temp_dir", serde_json::to_value(&config.temp_dir)?)
+                        .await
+                }
+                "min_free_disk_gb" => {
+                    manager
+                        .update_config_item("min_free_disk_gb", serde_json::to_value(config.min_free_disk_gb)?)
+                        .await
+                }
+                "jellyfin_url" => {
+                    manager
+                        .update_config_item("jellyfin_url", serde_json::to_value(&config.jellyfin_url)?)
+                        .await
+                }
+                "jellyfin_api_key" => {
+                    manager
+                        .update_config_item("jellyfin_api_key", serde_json::to_value(&config.jellyfin_api_key)?)
+                        .await
+                }
+                "api_rate_limit_per_minute" => {
+                    manager
+                        .update_config_item(
+                            "api_rate_limit_per_minute",
+                            serde_json::to_value(config.api_rate_limit_per_minute)?,
+                        )
+                        .await
+                }
+                "cors_allowed_origins" => {
+                    manager
+                        .update_config_item(
+                            "cors_allowed_origins",
+                            serde_json::to_value(&config.cors_allowed_origins)?,
+                        )
+                        .await
+                }
+                "trust_existing_files" => {
+                    manager
+                        .update_config_item(
+                            "trust_existing_files",
+                            serde_json::to_value(config.trust_existing_files)?,
+                        )
+                        .await
+                }
+                "ffmpeg_path" => {
+                    manager
+                        .update_config_item("ffmpeg_path", serde_json::to_value(&config.ffmpeg_path)?)
+                        .await
+                }
+                "ffmpeg_hwaccel" => {
+                    manager
+                        .update_config_item("ffmpeg_hwaccel", serde_json::to_value(&config.ffmpeg_hwaccel)?)
+                        .await
+                }
+                "ffmpeg_merge_timeout_secs" => {
+                    manager
+                        .update_config_item(
+                            "ffmpeg_merge_timeout_secs",
+                            serde_json::to_value(config.ffmpeg_merge_timeout_secs)?,
+                        )
+                        .await
+                }
+                "generate_thumbnail_fallback" => {
+                    manager
+                        .update_config_item(
+                            "generate_thumbnail_fallback",
+                            serde_json::to_value(config.generate_thumbnail_fallback)?,
+                        )
+                        .await
+                }
+                "refresh_covers" => {
+                    manager
+                        .update_config_item("refresh_covers", serde_json::to_value(config.refresh_covers)?)
+                        .await
+                }
+                "refresh_upper_avatar_days" => {
+                    manager
+                        .update_config_item(
+                            "refresh_upper_avatar_days",
+                            serde_json::to_value(config.refresh_upper_avatar_days)?,
+                        )
+                        .await
+                }
+                "skip_upower_exclusive" => {
+                    manager
+                        .update_config_item(
+                            "skip_upower_exclusive",
+                            serde_json::to_value(config.skip_upower_exclusive)?,
+                        )
+                        .await
+                }
+                "write_scan_report" => {
+                    manager
+                        .update_config_item("write_scan_report", serde_json::to_value(config.write_scan_report)?)
+                        .await
+                }
+                "source_scan_concurrency" => {
+                    manager
+                        .update_config_item(
+                            "source_scan_concurrency",
+                            serde_json::to_value(config.source_scan_concurrency)?,
+                        )
+                        .await
+                }
+                "season_title_cache_size" => {
+                    manager
+                        .update_config_item(
+                            "season_title_cache_size",
+                            serde_json::to_value(config.season_title_cache_size)?,
+                        )
+                        .await
+                }
+                "season_title_cache_ttl_secs" => {
+                    manager
+                        .update_config_item(
+                            "season_title_cache_ttl_secs",
+                            serde_json::to_value(config.season_title_cache_ttl_secs)?,
+                        )
                         .await
                 }
-                "concurrent_limit" => {
+                "db_max_connections" => {
                     manager
-                        .update_config_item("concurrent_limit", serde_json::to_value(&config.concurrent_limit)?)
+                        .update_config_item("db_max_connections", serde_json::to_value(config.db_max_connections)?)
                         .await
                 }
-                "cdn_sorting" => {
+                "db_acquire_timeout" => {
                     manager
-                        .update_config_item("cdn_sorting", serde_json::to_value(config.cdn_sorting)?)
+                        .update_config_item("db_acquire_timeout", serde_json::to_value(config.db_acquire_timeout)?)
                         .await
                 }
-                "scan_deleted_videos" => {
+                "db_busy_timeout" => {
                     manager
-                        .update_config_item("scan_deleted_videos", serde_json::to_value(config.scan_deleted_videos)?)
+                        .update_config_item("db_busy_timeout", serde_json::to_value(config.db_busy_timeout)?)
                         .await
                 }
-                "enable_aria2_health_check" => {
+                "download_max_retries" => {
                     manager
                         .update_config_item(
-                            "enable_aria2_health_check",
-                            serde_json::to_value(config.enable_aria2_health_check)?,
+                            "download_max_retries",
+                            serde_json::to_value(config.download_max_retries)?,
                         )
                         .await
                 }
-                "enable_aria2_auto_restart" => {
+                "download_retry_base_delay_ms" => {
                     manager
                         .update_config_item(
-                            "enable_aria2_auto_restart",
-                            serde_json::to_value(config.enable_aria2_auto_restart)?,
+                            "download_retry_base_delay_ms",
+                            serde_json::to_value(config.download_retry_base_delay_ms)?,
                         )
                         .await
                 }
-                "aria2_health_check_interval" => {
+                "aria2_rpc_url" => {
                     manager
-                        .update_config_item(
-                            "aria2_health_check_interval",
-                            serde_json::to_value(config.aria2_health_check_interval)?,
-                        )
+                        .update_config_item("aria2_rpc_url", serde_json::to_value(&config.aria2_rpc_url)?)
+                        .await
+                }
+                "webdav" => {
+                    manager
+                        .update_config_item("webdav", serde_json::to_value(&config.webdav)?)
+                        .await
+                }
+                "aria2_rpc_secret" => {
+                    manager
+                        .update_config_item("aria2_rpc_secret", serde_json::to_value(&config.aria2_rpc_secret)?)
                         .await
                 }
                 "submission_risk_control" => {
@@ -5462,6 +9930,7 @@ pub async fn update_config_internal(
                 | "rate_duration"
                 | "parallel_download_enabled"
                 | "parallel_download_threads"
+                | "download_speed_limit_kbps"
                 | "concurrent_video"
                 | "concurrent_page" => {
                     manager
@@ -5492,7 +9961,8 @@ pub async fn update_config_internal(
                 }
                 // 处理视频质量相关字段
                 "video_max_quality" | "video_min_quality" | "audio_max_quality" | "audio_min_quality" | "codecs"
-                | "no_dolby_video" | "no_dolby_audio" | "no_hdr" | "no_hires" => {
+                | "no_dolby_video" | "no_dolby_audio" | "no_hdr" | "no_hires" | "min_duration_secs"
+                | "max_duration_secs" => {
                     manager
                         .update_config_item("filter_option", serde_json::to_value(&config.filter_option)?)
                         .await
@@ -5509,7 +9979,12 @@ pub async fn update_config_internal(
                 | "danmaku_opacity"
                 | "danmaku_bold"
                 | "danmaku_outline"
-                | "danmaku_time_offset" => {
+                | "danmaku_time_offset"
+                | "danmaku_formats"
+                | "danmaku_block_keywords"
+                | "danmaku_block_regex"
+                | "danmaku_max_per_second"
+                | "danmaku_exclude_modes" => {
                     manager
                         .update_config_item("danmaku_option", serde_json::to_value(&config.danmaku_option)?)
                         .await
@@ -5568,6 +10043,7 @@ pub async fn update_config_internal(
                 "risk_control.enabled"
                 | "risk_control.mode"
                 | "risk_control.timeout"
+                | "risk_control.fingerprint_strategy"
                 | "risk_control.auto_solve.service"
                 | "risk_control.auto_solve.api_key"
                 | "risk_control.auto_solve.max_retries"
@@ -5684,7 +10160,17 @@ pub async fn update_config_internal(
     }
 
     // 检查是否需要重置NFO任务状态
-    let should_reset_nfo = updated_fields.contains(&"nfo_time_type");
+    let should_reset_nfo = [
+        "nfo_time_type",
+        "nfo_flavor",
+        "nfo_template",
+        "nfo_download_actor_images",
+        "nfo_include_tags",
+        "nfo_max_tags",
+        "nfo_include_chapters",
+    ]
+    .iter()
+    .any(|field| updated_fields.contains(field));
     let mut resetted_nfo_videos_count = 0;
     let mut resetted_nfo_pages_count = 0;
 
@@ -6281,21 +10767,27 @@ async fn rename_existing_files(
 
             if old_video_name != new_video_name {
                 // 重命名视频级别的文件
-                let video_level_files = [
+                let nfo_flavor = crate::config::reload_config().nfo_flavor;
+                let mut video_level_files = vec![
                     (
-                        format!("{}-thumb.jpg", old_video_name),
-                        format!("{}-thumb.jpg", new_video_name),
+                        nfo_flavor.show_poster_file_name(&old_video_name),
+                        nfo_flavor.show_poster_file_name(&new_video_name),
                     ),
                     (
-                        format!("{}-fanart.jpg", old_video_name),
-                        format!("{}-fanart.jpg", new_video_name),
+                        nfo_flavor.show_fanart_file_name(&old_video_name),
+                        nfo_flavor.show_fanart_file_name(&new_video_name),
                     ),
                     (format!("{}.nfo", old_video_name), format!("{}.nfo", new_video_name)),
                     // 兼容旧的硬编码文件名
-                    ("poster.jpg".to_string(), format!("{}-thumb.jpg", new_video_name)),
-                    ("fanart.jpg".to_string(), format!("{}-fanart.jpg", new_video_name)),
                     ("tvshow.nfo".to_string(), format!("{}.nfo", new_video_name)),
                 ];
+                // Kodi/Plex 风格本身就使用固定的图片文件名，只有 Emby 风格需要转换
+                if nfo_flavor == crate::config::NFOFlavor::Emby {
+                    video_level_files.extend([
+                        ("poster.jpg".to_string(), format!("{}-thumb.jpg", new_video_name)),
+                        ("fanart.jpg".to_string(), format!("{}-fanart.jpg", new_video_name)),
+                    ]);
+                }
 
                 for (old_file_name, new_file_name) in video_level_files {
                     let old_file_path = final_video_path.join(&old_file_name);
@@ -7120,6 +11612,18 @@ pub enum LogLevel {
     Debug,
 }
 
+impl LogLevel {
+    /// 日志级别的严重程度，数值越大越严重
+    pub fn severity(&self) -> u8 {
+        match self {
+            LogLevel::Debug => 0,
+            LogLevel::Info => 1,
+            LogLevel::Warn => 2,
+            LogLevel::Error => 3,
+        }
+    }
+}
+
 /// 日志条目结构
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LogEntry {
@@ -7144,12 +11648,18 @@ lazy_static::lazy_static! {
     static ref LOG_BUFFER: Arc<Mutex<VecDeque<LogEntry>>> = Arc::new(Mutex::new(VecDeque::with_capacity(100000)));
     // 为debug日志单独设置缓冲区，容量较小
     static ref DEBUG_LOG_BUFFER: Arc<Mutex<VecDeque<LogEntry>>> = Arc::new(Mutex::new(VecDeque::with_capacity(10000)));
+    // 实时日志广播，消费过慢的订阅者会丢弃最旧的日志
     static ref LOG_BROADCASTER: broadcast::Sender<LogEntry> = {
-        let (sender, _) = broadcast::channel(100);
+        let (sender, _) = broadcast::channel(1000);
         sender
     };
 }
 
+/// 订阅实时日志
+pub fn subscribe_logs() -> broadcast::Receiver<LogEntry> {
+    LOG_BROADCASTER.subscribe()
+}
+
 /// 添加日志到缓冲区
 pub fn add_log_entry(level: LogLevel, message: String, target: Option<String>) {
     let entry = LogEntry {
@@ -7323,11 +11833,13 @@ pub async fn get_logs(
 }
 
 /// 下载日志文件
+///
+/// 以流的方式返回磁盘上的日志文件，指定 `lines` 时只返回最后 N 行
 #[utoipa::path(
     get,
     path = "/api/logs/download",
     params(
-        ("level" = Option<String>, Query, description = "日志级别: all, info, warn, error, debug，默认all")
+        crate::api::request::DownloadLogFileRequest,
     ),
     responses(
         (status = 200, description = "下载日志文件成功"),
@@ -7336,56 +11848,76 @@ pub async fn get_logs(
     )
 )]
 pub async fn download_log_file(
-    Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<impl axum::response::IntoResponse, ApiError> {
+    Query(params): Query<crate::api::request::DownloadLogFileRequest>,
+) -> Result<axum::response::Response, ApiError> {
     use axum::http::header;
-    use tokio::fs;
+    use futures::StreamExt;
+    use tokio::io::{AsyncSeekExt, SeekFrom};
+
+    use crate::utils::file_logger::{parse_log_file_name, tail_start_offset, LOG_FILE_HEADER};
 
     // 先刷新所有缓冲的日志到文件，确保下载的是最新的
     crate::utils::file_logger::flush_file_logger();
 
-    // 获取日志级别参数
-    let level = params.get("level").map(|s| s.as_str()).unwrap_or("all");
-
-    // 构建日志文件路径
-    let log_dir = crate::config::CONFIG_DIR.join("logs");
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-
-    let file_name = match level {
-        "debug" => format!("logs-debug-{}.csv", today),
-        "info" => format!("logs-info-{}.csv", today),
-        "warn" => format!("logs-warn-{}.csv", today),
-        "error" => format!("logs-error-{}.csv", today),
-        _ => format!("logs-all-{}.csv", today),
+    // 文件名只接受日志文件列表中的格式，避免读取日志目录以外的文件
+    let file_name = match params.file.as_deref() {
+        Some(file_name) => {
+            if parse_log_file_name(file_name).is_none() {
+                return Err(InnerApiError::BadRequest(format!("无效的日志文件名: {}", file_name)).into());
+            }
+            file_name.to_string()
+        }
+        None => {
+            let level = params
+                .level
+                .as_deref()
+                .filter(|level| matches!(*level, "debug" | "info" | "warn" | "error"))
+                .unwrap_or("all");
+            format!("logs-{}-{}.csv", level, chrono::Local::now().format("%Y-%m-%d"))
+        }
     };
-
-    let file_path = log_dir.join(&file_name);
-
-    // 检查文件是否存在
-    if !file_path.exists() {
-        return Err(InnerApiError::BadRequest(format!("日志文件不存在: {}", file_name)).into());
+    if params.lines == Some(0) {
+        return Err(InnerApiError::BadRequest("lines 必须大于 0".to_string()).into());
     }
 
-    // 读取文件内容
-    let file_content = fs::read(&file_path)
+    let file_path = crate::utils::file_logger::log_dir().join(&file_name);
+    let mut file = tokio::fs::File::open(&file_path)
+        .await
+        .map_err(|_| InnerApiError::BadRequest(format!("日志文件不存在: {}", file_name)))?;
+
+    // 截取末尾时补上CSV头，保证返回的仍是完整的CSV
+    let offset = match params.lines {
+        Some(lines) => tail_start_offset(&mut file, lines)
+            .await
+            .map_err(|e| InnerApiError::BadRequest(format!("读取日志文件失败: {}", e)))?,
+        None => 0,
+    };
+    file.seek(SeekFrom::Start(offset))
         .await
         .map_err(|e| InnerApiError::BadRequest(format!("读取日志文件失败: {}", e)))?;
+    let header_stream = futures::stream::iter(
+        (offset > 0).then(|| Ok::<_, std::io::Error>(axum::body::Bytes::from_static(LOG_FILE_HEADER.as_bytes()))),
+    );
+    let stream = header_stream.chain(tokio_util::io::ReaderStream::new(file));
 
-    // 构建响应
+    let download_name = match params.lines {
+        Some(lines) => format!("{}-tail-{}.csv", file_name.trim_end_matches(".csv"), lines),
+        None => file_name,
+    };
     let response = axum::response::Response::builder()
         .status(200)
         .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
         .header(
             header::CONTENT_DISPOSITION,
-            format!("attachment; filename=\"{}\"", file_name),
+            format!("attachment; filename=\"{}\"", download_name),
         )
-        .body(axum::body::Body::from(file_content))
+        .body(axum::body::Body::from_stream(stream))
         .map_err(|e| InnerApiError::BadRequest(format!("构建响应失败: {}", e)))?;
 
     Ok(response)
 }
 
-/// 获取可用的日志文件列表
+/// 获取日志目录中的日志文件列表，包括之前日期轮转的文件，按日期从新到旧排列
 #[utoipa::path(
     get,
     path = "/api/logs/files",
@@ -7395,40 +11927,47 @@ pub async fn download_log_file(
     )
 )]
 pub async fn get_log_files() -> Result<ApiResponse<LogFilesResponse>, ApiError> {
-    use std::fs;
+    use crate::utils::file_logger::{parse_log_file_name, LOG_FILE_LEVELS};
 
-    let log_dir = crate::config::CONFIG_DIR.join("logs");
-    let startup_time = &*crate::utils::file_logger::STARTUP_TIME;
+    // 先刷新缓冲的日志，使列表中的文件大小为最新
+    crate::utils::file_logger::flush_file_logger();
 
     let mut files = vec![];
-
-    // 检查各个日志文件是否存在
-    let log_files = vec![
-        ("all", format!("logs-all-{}.csv", startup_time)),
-        ("debug", format!("logs-debug-{}.csv", startup_time)),
-        ("info", format!("logs-info-{}.csv", startup_time)),
-        ("warn", format!("logs-warn-{}.csv", startup_time)),
-        ("error", format!("logs-error-{}.csv", startup_time)),
-    ];
-
-    for (level, file_name) in log_files {
-        let file_path = log_dir.join(&file_name);
-        if file_path.exists() {
-            if let Ok(metadata) = fs::metadata(&file_path) {
-                files.push(LogFileInfo {
-                    level: level.to_string(),
-                    file_name,
-                    size: metadata.len(),
-                    modified: metadata
-                        .modified()
-                        .ok()
-                        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                        .map(|d| d.as_secs())
-                        .unwrap_or(0),
-                });
-            }
-        }
+    let mut entries = match tokio::fs::read_dir(crate::utils::file_logger::log_dir()).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(ApiResponse::ok(LogFilesResponse { files })),
+    };
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| InnerApiError::BadRequest(format!("读取日志目录失败: {}", e)))?
+    {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((level, date)) = parse_log_file_name(&file_name) else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        files.push(LogFileInfo {
+            level: level.to_string(),
+            date: date.format("%Y-%m-%d").to_string(),
+            file_name,
+            size: metadata.len(),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        });
     }
+    let level_order = |level: &str| LOG_FILE_LEVELS.iter().position(|l| *l == level);
+    files.sort_by(|a, b| {
+        b.date
+            .cmp(&a.date)
+            .then_with(|| level_order(&a.level).cmp(&level_order(&b.level)))
+    });
 
     Ok(ApiResponse::ok(LogFilesResponse { files }))
 }
@@ -7437,6 +11976,8 @@ pub async fn get_log_files() -> Result<ApiResponse<LogFilesResponse>, ApiError>
 #[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LogFileInfo {
     pub level: String,
+    // 日志日期，格式为 YYYY-MM-DD
+    pub date: String,
     pub file_name: String,
     pub size: u64,
     pub modified: u64,
@@ -7592,6 +12133,149 @@ pub async fn get_queue_status() -> Result<ApiResponse<QueueStatusResponse>, ApiE
     Ok(ApiResponse::ok(response))
 }
 
+/// 生成队列任务的内容摘要，不包含路径等详细信息
+fn queue_task_summary(task_type: &bili_sync_entity::task_queue::TaskType, data: &serde_json::Value) -> String {
+    use bili_sync_entity::task_queue::TaskType;
+
+    let field = |key: &str| match &data[key] {
+        serde_json::Value::String(value) => value.clone(),
+        serde_json::Value::Null => String::new(),
+        value => value.to_string(),
+    };
+    match task_type {
+        TaskType::AddVideoSource => format!("添加视频源: {} 名称={}", field("source_type"), field("name")),
+        TaskType::DeleteVideoSource => format!("删除视频源: {} ID={}", field("source_type"), field("source_id")),
+        TaskType::DeleteVideo => format!("删除视频: ID={}", field("video_id")),
+        TaskType::UpdateConfig => "更新配置".to_string(),
+        TaskType::ReloadConfig => "重载配置".to_string(),
+    }
+}
+
+/// 查询队列任务的执行状态
+#[utoipa::path(
+    get,
+    path = "/api/queue/tasks/{task_id}",
+    params(
+        ("task_id" = String, Path, description = "任务ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::QueueTaskStatusResponse>),
+        (status = 404, description = "任务不存在", body = String)
+    )
+)]
+pub async fn get_queue_task(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(task_id): Path<String>,
+) -> Result<ApiResponse<crate::api::response::QueueTaskStatusResponse>, ApiError> {
+    use sea_orm::ActiveEnum;
+
+    let Some(task) = crate::task::find_task_by_task_id(&task_id, &db).await? else {
+        return Err(InnerApiError::TaskNotFound(task_id).into());
+    };
+    let data = serde_json::from_str::<serde_json::Value>(&task.task_data).unwrap_or_default();
+
+    Ok(ApiResponse::ok(crate::api::response::QueueTaskStatusResponse {
+        task_id,
+        summary: queue_task_summary(&task.task_type, &data),
+        task_type: task.task_type.to_value(),
+        status: task.status.to_value(),
+        retry_count: task.retry_count,
+        priority: task.priority,
+        created_at: task.created_at,
+        updated_at: task.updated_at,
+    }))
+}
+
+/// 取消队列中尚未执行的任务
+#[utoipa::path(
+    delete,
+    path = "/api/queue/tasks/{task_id}",
+    params(
+        ("task_id" = String, Path, description = "任务ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::CancelQueueTaskResponse>),
+        (status = 404, description = "任务不存在或已执行", body = String)
+    )
+)]
+pub async fn cancel_queue_task(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(task_id): Path<String>,
+) -> Result<ApiResponse<crate::api::response::CancelQueueTaskResponse>, ApiError> {
+    if !crate::task::cancel_pending_task(&task_id, &db).await? {
+        return Err(InnerApiError::TaskNotFound(task_id).into());
+    }
+
+    Ok(ApiResponse::ok(crate::api::response::CancelQueueTaskResponse {
+        success: true,
+        message: format!("任务 {} 已取消", task_id),
+        task_id,
+    }))
+}
+
+/// 获取超过最大重试次数的死信任务
+#[utoipa::path(
+    get,
+    path = "/api/queue/dead-letter",
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::DeadLetterTasksResponse>),
+    )
+)]
+pub async fn get_dead_letter_tasks(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::DeadLetterTasksResponse>, ApiError> {
+    use sea_orm::ActiveEnum;
+
+    let tasks: Vec<_> = crate::task::list_dead_letter_tasks(&db)
+        .await?
+        .into_iter()
+        .map(|task| {
+            let task_data = serde_json::from_str::<serde_json::Value>(&task.task_data)
+                .unwrap_or(serde_json::Value::String(task.task_data));
+            crate::api::response::DeadLetterTaskResponse {
+                id: task.id,
+                task_id: task_data["task_id"].as_str().map(str::to_string),
+                task_type: task.task_type.to_value(),
+                task_data,
+                retry_count: task.retry_count,
+                created_at: task.created_at,
+                updated_at: task.updated_at,
+            }
+        })
+        .collect();
+
+    Ok(ApiResponse::ok(crate::api::response::DeadLetterTasksResponse {
+        total: tasks.len(),
+        tasks,
+    }))
+}
+
+/// 将死信任务重新加入队列
+#[utoipa::path(
+    post,
+    path = "/api/queue/dead-letter/{id}/requeue",
+    params(
+        ("id" = i32, Path, description = "死信任务的数据库记录ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::RequeueDeadLetterTaskResponse>),
+    )
+)]
+pub async fn requeue_dead_letter_task(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::RequeueDeadLetterTaskResponse>, ApiError> {
+    if !crate::task::requeue_dead_letter_task(id, &db).await? {
+        return Err(InnerApiError::NotFound(id).into());
+    }
+
+    Ok(ApiResponse::ok(crate::api::response::RequeueDeadLetterTaskResponse {
+        success: true,
+        id,
+        message: "任务已重新加入队列，将在下次处理队列时执行".to_string(),
+    }))
+}
+
 /// 代理B站图片请求，解决防盗链问题
 #[utoipa::path(
     get,
@@ -7616,7 +12300,7 @@ pub async fn proxy_image(
     }
 
     // 创建HTTP客户端
-    let client = reqwest::Client::new();
+    let client = crate::bilibili::proxied_reqwest_client();
 
     // 请求图片，添加必要的请求头
     tracing::debug!("发起图片下载请求: {}", url);
@@ -7838,7 +12522,391 @@ pub async fn get_config_history(
         changes: change_infos,
     };
 
-    Ok(ApiResponse::ok(response))
+    Ok(ApiResponse::ok(response))
+}
+
+/// 将配置恢复到某条变更记录之前的值，回滚本身会作为新的变更记录保存
+/// 更新配置接口支持的配置项通过更新配置任务处理，以便模板变化时同步处理已下载的文件
+#[utoipa::path(
+    post,
+    path = "/api/config/history/{id}/rollback",
+    params(
+        ("id" = i32, Path, description = "配置变更记录ID")
+    ),
+    responses(
+        (status = 200, description = "回滚结果", body = ConfigRollbackResponse),
+        (status = 400, description = "变更记录不存在或无法回滚"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("Token" = []))
+)]
+pub async fn rollback_config_change(
+    Path(id): Path<i32>,
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<ConfigRollbackResponse>, ApiError> {
+    use crate::config::ConfigManager;
+
+    let manager = ConfigManager::new(db.as_ref().clone());
+    let change = manager
+        .get_config_change(id)
+        .await
+        .map_err(|e| ApiError::from(anyhow!("获取配置变更记录失败: {}", e)))?
+        .ok_or_else(|| InnerApiError::BadRequest(format!("配置变更记录 {} 不存在", id)))?;
+    let Some(old_value) = change.old_value.as_deref() else {
+        return Err(InnerApiError::BadRequest(format!(
+            "配置项 '{}' 在该记录中为新增，没有可恢复的历史值",
+            change.key_name
+        ))
+        .into());
+    };
+    let restored: serde_json::Value =
+        serde_json::from_str(old_value).map_err(|e| ApiError::from(anyhow!("解析历史配置值失败: {}", e)))?;
+
+    let key = change.key_name;
+    let pointer = format!("/{}", key.replace('.', "/"));
+    let current_json = crate::config::with_config(|bundle| crate::config::export_config(&bundle.config, true))
+        .map_err(|e| ApiError::from(anyhow!("读取当前配置失败: {}", e)))?;
+    let current = current_json
+        .pointer(&pointer)
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    let changes: Vec<ConfigDiffItem> = crate::config::diff_config_values(&key, &current, &restored)
+        .into_iter()
+        .map(|(key, current_value, restored_value)| ConfigDiffItem {
+            key,
+            current_value,
+            restored_value,
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(ApiResponse::ok(ConfigRollbackResponse {
+            success: true,
+            history_id: id,
+            key,
+            changes,
+            queued: false,
+            message: "当前配置已经是该历史值，无需回滚".to_string(),
+        }));
+    }
+
+    let scanning = crate::task::is_scanning();
+    let update = serde_json::json!({ key.as_str(): restored.clone(), "task_id": uuid::Uuid::new_v4().to_string() });
+    // 只有更新配置任务包含该配置项时才走更新配置流程，其余配置项直接写入后重载
+    let update_task = serde_json::from_value::<crate::task::UpdateConfigTask>(update.clone())
+        .ok()
+        .filter(|task| serde_json::to_value(task).is_ok_and(|value| !value[key.as_str()].is_null()));
+    match update_task {
+        Some(task) if scanning => {
+            crate::task::enqueue_update_task(task, &db).await?;
+        }
+        Some(_) => {
+            let request = serde_json::from_value::<UpdateConfigRequest>(update)
+                .map_err(|e| ApiError::from(anyhow!("构建配置更新请求失败: {}", e)))?;
+            update_config_internal(db.clone(), request).await?;
+        }
+        None => {
+            // 直接写入前先将历史值合并到完整配置上校验，避免恢复出无效的配置
+            let mut merged = current_json;
+            let slot = merged
+                .pointer_mut(&pointer)
+                .ok_or_else(|| InnerApiError::BadRequest(format!("配置项 '{}' 已不存在，无法回滚", key)))?;
+            *slot = restored.clone();
+            let config: crate::config::Config = serde_json::from_value(merged)
+                .map_err(|e| InnerApiError::BadRequest(format!("配置项 '{}' 的历史值无效: {}", key, e)))?;
+            if !config.check() {
+                return Err(InnerApiError::BadRequest(format!(
+                    "配置项 '{}' 回滚后的配置未通过校验，详细原因请查看日志",
+                    key
+                ))
+                .into());
+            }
+            manager
+                .update_config_item(&key, restored)
+                .await
+                .map_err(|e| ApiError::from(anyhow!("回滚配置项 '{}' 失败: {}", key, e)))?;
+            if scanning {
+                let reload_task = crate::task::ReloadConfigTask {
+                    credential: None,
+                    task_id: uuid::Uuid::new_v4().to_string(),
+                    priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
+                };
+                crate::task::enqueue_reload_task(reload_task, &db).await?;
+            } else {
+                reload_config_internal().await?;
+            }
+        }
+    }
+    info!("配置项 '{}' 已回滚到变更记录 {} 之前的值", key, id);
+
+    let message = if scanning {
+        format!("配置项 '{}' 的回滚已加入队列，将在扫描完成后生效", key)
+    } else {
+        format!("配置项 '{}' 已回滚", key)
+    };
+    Ok(ApiResponse::ok(ConfigRollbackResponse {
+        success: true,
+        history_id: id,
+        key,
+        changes,
+        queued: scanning,
+        message,
+    }))
+}
+
+/// 预览模板渲染结果，不修改配置
+#[utoipa::path(
+    post,
+    path = "/api/config/preview-template",
+    request_body = PreviewTemplateRequest,
+    responses(
+        (status = 200, description = "模板预览结果", body = PreviewTemplateResponse),
+        (status = 400, description = "模板无效或视频不存在"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("Token" = []))
+)]
+pub async fn preview_template(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<PreviewTemplateRequest>,
+) -> Result<ApiResponse<PreviewTemplateResponse>, ApiError> {
+    use sea_orm::QueryOrder;
+
+    if !PREVIEW_TEMPLATE_TYPES.contains(&request.template_type.as_str()) {
+        return Err(InnerApiError::BadRequest(format!(
+            "不支持的模板类型: {}，可选值: {}",
+            request.template_type,
+            PREVIEW_TEMPLATE_TYPES.join(", ")
+        ))
+        .into());
+    }
+
+    let (sample, data) = match request.video_id {
+        Some(video_id) => {
+            let video = video::Entity::find_by_id(video_id)
+                .one(db.as_ref())
+                .await?
+                .ok_or(InnerApiError::NotFound(video_id))?;
+            let data = match request.template_type.as_str() {
+                "video" | "folder_structure" => crate::utils::format_arg::video_format_args(&video),
+                _ => {
+                    let page = page::Entity::find()
+                        .filter(page::Column::VideoId.eq(video_id))
+                        .order_by_asc(page::Column::Pid)
+                        .one(db.as_ref())
+                        .await?
+                        .ok_or_else(|| InnerApiError::BadRequest(format!("视频「{}」还没有分页信息", video.name)))?;
+                    crate::utils::format_arg::page_format_args(&video, &page)
+                }
+            };
+            (video.name, data)
+        }
+        None => ("内置样例".to_string(), crate::config::sample_template_args()),
+    };
+
+    let (raw, rendered_path, rename_path) = crate::config::with_config(|bundle| {
+        render_template_preview(&bundle.config, &request.template_type, &request.template, &data)
+    })
+    .map_err(|e| InnerApiError::BadRequest(format!("{:#}", e)))?;
+
+    let segments: Vec<String> = rendered_path.split(['/', '\\']).map(str::to_string).collect();
+    let mut warnings = Vec::new();
+    if rendered_path.trim().is_empty() {
+        warnings.push("渲染结果为空".to_string());
+    } else if segments.iter().any(|segment| segment.trim().is_empty()) {
+        warnings.push("渲染结果包含空的目录名，可能是模板变量为空或路径分隔符重复".to_string());
+    }
+    if raw.matches(['/', '\\']).count() != rendered_path.matches(['/', '\\']).count() {
+        warnings.push("变量内容中的路径分隔符已替换为下划线，只有模板中直接书写的分隔符会创建目录".to_string());
+    }
+    if rendered_path != rename_path {
+        warnings.push(format!(
+            "修改模板后重命名已下载文件时的路径与下载路径不同: {}",
+            rename_path
+        ));
+    }
+
+    Ok(ApiResponse::ok(PreviewTemplateResponse {
+        template_type: request.template_type,
+        template: request.template,
+        sample,
+        raw,
+        rendered_path,
+        rename_path,
+        segments,
+        warnings,
+    }))
+}
+
+/// 导出当前配置，默认移除凭证、auth_token 等敏感字段
+#[utoipa::path(
+    get,
+    path = "/api/config/export",
+    params(ConfigExportRequest),
+    responses(
+        (status = 200, description = "导出的配置", body = ConfigExportResponse),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("Token" = []))
+)]
+pub async fn export_config(
+    Query(params): Query<ConfigExportRequest>,
+) -> Result<ApiResponse<ConfigExportResponse>, ApiError> {
+    let config =
+        crate::config::with_config(|bundle| crate::config::export_config(&bundle.config, params.include_secrets))
+            .map_err(|e| ApiError::from(anyhow!("导出配置失败: {}", e)))?;
+
+    Ok(ApiResponse::ok(ConfigExportResponse {
+        config,
+        include_secrets: params.include_secrets,
+        exported_at: now_standard_string(),
+    }))
+}
+
+/// 导入配置时经由更新配置接口保存的顶层配置项，与更新配置任务队列支持的字段一致，
+/// 以便修改命名模板、NFO 等配置时同样触发重命名已下载文件、重置 NFO 任务等处理
+const IMPORT_VIA_UPDATE_CONFIG_KEYS: [&str; 27] = [
+    "video_name",
+    "page_name",
+    "multi_page_name",
+    "bangumi_name",
+    "folder_structure",
+    "bangumi_folder_name",
+    "collection_folder_mode",
+    "time_format",
+    "interval",
+    "nfo_time_type",
+    "nfo_flavor",
+    "nfo_template",
+    "nfo_download_actor_images",
+    "nfo_include_tags",
+    "nfo_max_tags",
+    "nfo_include_chapters",
+    "cdn_sorting",
+    "verify_download_integrity",
+    "download_subtitles",
+    "bangumi_write_skip_markers",
+    "bangumi_skip_specials",
+    "proxy_url",
+    "user_agent",
+    "multi_page_use_season_structure",
+    "collection_use_season_structure",
+    "bangumi_use_season_structure",
+    "upper_path",
+];
+
+/// 导入配置时展开为更新配置请求字段的嵌套配置项
+const IMPORT_VIA_UPDATE_CONFIG_NESTED_KEYS: [&str; 1] = ["submission_risk_control"];
+
+/// 由导入后发生变化的配置项构造更新配置请求，返回请求与其包含的顶层配置项
+fn update_request_from_import(
+    merged: &serde_json::Value,
+    changed_keys: &[String],
+) -> Result<(crate::api::request::UpdateConfigRequest, Vec<String>)> {
+    // 更新配置请求中空字符串表示清空
+    let field_value = |value: &serde_json::Value| {
+        if value.is_null() {
+            serde_json::Value::String(String::new())
+        } else {
+            value.clone()
+        }
+    };
+    let mut fields = serde_json::Map::new();
+    let mut covered_keys = Vec::new();
+    for key in changed_keys {
+        let Some(value) = merged.get(key) else {
+            continue;
+        };
+        if IMPORT_VIA_UPDATE_CONFIG_KEYS.contains(&key.as_str()) {
+            fields.insert(key.clone(), field_value(value));
+        } else if IMPORT_VIA_UPDATE_CONFIG_NESTED_KEYS.contains(&key.as_str()) {
+            let Some(object) = value.as_object() else {
+                continue;
+            };
+            for (field, value) in object {
+                fields.insert(field.clone(), field_value(value));
+            }
+        } else {
+            continue;
+        }
+        covered_keys.push(key.clone());
+    }
+    let request = serde_json::from_value(serde_json::Value::Object(fields)).context("构造更新配置请求失败")?;
+    Ok((request, covered_keys))
+}
+
+/// 导入配置，合并到当前配置并校验通过后保存
+///
+/// 命名模板、NFO 等配置项经由更新配置接口保存（扫描中时加入配置任务队列），其余配置项直接保存，扫描中时在扫描结束后重载生效
+#[utoipa::path(
+    post,
+    path = "/api/config/import",
+    request_body = ConfigImportRequest,
+    responses(
+        (status = 200, description = "导入结果", body = ConfigImportResponse),
+        (status = 400, description = "配置无效"),
+        (status = 500, description = "内部服务器错误")
+    ),
+    security(("Token" = []))
+)]
+pub async fn import_config(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<ConfigImportRequest>,
+) -> Result<ApiResponse<ConfigImportResponse>, ApiError> {
+    use crate::config::ConfigManager;
+
+    let current = crate::config::reload_config();
+    let manager = ConfigManager::new(db.as_ref().clone());
+    let (merged, updated_keys) = manager
+        .prepare_import(&current, request.config)
+        .map_err(|e| ApiError::from(InnerApiError::BadRequest(format!("导入配置失败: {:#}", e))))?;
+
+    if updated_keys.is_empty() {
+        return Ok(ApiResponse::ok(ConfigImportResponse {
+            success: true,
+            updated_keys,
+            message: "导入的配置与当前配置相同，无需更新".to_string(),
+        }));
+    }
+
+    let (update_request, covered_keys) = update_request_from_import(&merged, &updated_keys)
+        .map_err(|e| ApiError::from(InnerApiError::BadRequest(format!("导入配置失败: {:#}", e))))?;
+    let mut messages = Vec::new();
+    if !covered_keys.is_empty() {
+        let response = update_config_or_enqueue(db.clone(), update_request).await?;
+        messages.push(response.message);
+    }
+
+    let direct_keys: Vec<String> = updated_keys
+        .iter()
+        .filter(|key| !covered_keys.contains(key))
+        .cloned()
+        .collect();
+    if !direct_keys.is_empty() {
+        manager
+            .save_imported_items(&merged, &direct_keys)
+            .await
+            .map_err(|e| ApiError::from(anyhow!("保存导入的配置失败: {:#}", e)))?;
+        // 与重载配置一致，扫描中时加入配置任务队列，扫描结束后生效
+        if crate::task::is_scanning() {
+            let reload_task = crate::task::ReloadConfigTask {
+                credential: None,
+                task_id: uuid::Uuid::new_v4().to_string(),
+                priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
+            };
+            crate::task::enqueue_reload_task(reload_task, &db).await?;
+            messages.push(format!("其余 {} 个配置项将在扫描完成后生效", direct_keys.len()));
+        } else {
+            reload_config_internal().await?;
+        }
+    }
+    messages.insert(0, format!("已导入 {} 个配置项", updated_keys.len()));
+    let message = messages.join("；");
+
+    Ok(ApiResponse::ok(ConfigImportResponse {
+        success: true,
+        updated_keys,
+        message,
+    }))
 }
 
 /// 验证配置
@@ -7854,16 +12922,21 @@ pub async fn get_config_history(
 pub async fn validate_config(
     Extension(_db): Extension<Arc<DatabaseConnection>>,
 ) -> Result<ApiResponse<ConfigValidationResponse>, ApiError> {
-    // 使用当前配置进行验证
-    let is_valid = crate::config::with_config(|bundle| bundle.validate());
+    // 使用当前配置进行验证，并用样例数据实际渲染所有模板
+    let (is_valid, template_errors) = crate::config::with_config(|bundle| {
+        (
+            bundle.validate(),
+            crate::config::ConfigBundle::check_templates(&bundle.config, &crate::config::sample_template_args()),
+        )
+    });
 
+    let mut errors = template_errors;
+    if !is_valid {
+        errors.insert(0, "配置验证失败".to_string());
+    }
     let response = ConfigValidationResponse {
-        valid: is_valid,
-        errors: if is_valid {
-            vec![]
-        } else {
-            vec!["配置验证失败".to_string()]
-        },
+        valid: errors.is_empty(),
+        errors,
         warnings: vec![],
     };
 
@@ -7968,7 +13041,9 @@ pub async fn setup_auth_token(
         // 将配置更新任务加入队列
         use uuid::Uuid;
         let reload_task = crate::task::ReloadConfigTask {
+            credential: None,
             task_id: Uuid::new_v4().to_string(),
+            priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
         };
         crate::task::enqueue_reload_task(reload_task, &db).await?;
         info!("检测到正在扫描，API Token保存任务已加入队列");
@@ -8051,7 +13126,7 @@ pub async fn update_credential(
 
     // 如果用户没有提供 buvid4，尝试通过 spi 接口获取
     if new_credential.buvid4.is_none() {
-        if let Ok(client) = reqwest::Client::new()
+        if let Ok(client) = crate::bilibili::proxied_reqwest_client()
             .get("https://api.bilibili.com/x/frontend/finger/spi")
             .header("Referer", "https://www.bilibili.com")
             .header("Origin", "https://www.bilibili.com")
@@ -8090,7 +13165,9 @@ pub async fn update_credential(
         // 将配置更新任务加入队列
         use uuid::Uuid;
         let reload_task = crate::task::ReloadConfigTask {
+            credential: config.credential.load_full().as_deref().cloned(),
             task_id: Uuid::new_v4().to_string(),
+            priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
         };
         crate::task::enqueue_reload_task(reload_task, &db).await?;
         info!("检测到正在扫描，凭证保存任务已加入队列");
@@ -8128,6 +13205,131 @@ pub async fn update_credential(
     Ok(ApiResponse::ok(response))
 }
 
+/// 通过浏览器 Cookie 字符串导入B站登录凭证
+#[utoipa::path(
+    post,
+    path = "/api/credential/import-cookie",
+    request_body = crate::api::request::ImportCookieCredentialRequest,
+    responses(
+        (status = 200, description = "凭证导入成功", body = crate::api::response::ImportCookieCredentialResponse),
+        (status = 400, description = "Cookie 无效或已过期", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn import_credential_cookie(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::ImportCookieCredentialRequest>,
+) -> Result<ApiResponse<crate::api::response::ImportCookieCredentialResponse>, ApiError> {
+    let login_result = QR_SERVICE.import_cookie(&params.cookie).await.map_err(|e| {
+        warn!("导入 Cookie 凭证失败: {}", e);
+        ApiError::from(anyhow!("导入 Cookie 凭证失败: {}", e))
+    })?;
+    info!(
+        "Cookie 凭证校验通过: 用户 {}({})",
+        login_result.user_info.username, login_result.user_info.user_id
+    );
+
+    save_login_credential(&db, login_result.credential, "Cookie 导入").await?;
+
+    let response = crate::api::response::ImportCookieCredentialResponse {
+        success: true,
+        message: "Cookie 凭证导入成功".to_string(),
+        user_info: QRUserInfo {
+            user_id: login_result.user_info.user_id,
+            username: login_result.user_info.username,
+            avatar_url: login_result.user_info.avatar_url,
+        },
+    };
+
+    Ok(ApiResponse::ok(response))
+}
+
+/// 检查已保存的B站凭证是否有效
+#[utoipa::path(
+    get,
+    path = "/api/credential/status",
+    responses(
+        (status = 200, description = "检查完成", body = crate::api::response::CredentialStatusResponse),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn get_credential_status() -> Result<ApiResponse<crate::api::response::CredentialStatusResponse>, ApiError> {
+    let config = crate::config::with_config(|bundle| bundle.config.clone());
+    let credential = config.credential.load();
+    let Some(cred) = credential.as_deref().filter(|cred| !cred.sessdata.is_empty()) else {
+        return Ok(ApiResponse::ok(crate::api::response::CredentialStatusResponse {
+            is_valid: false,
+            user_id: None,
+            username: None,
+            vip_status: None,
+            vip_type: None,
+            coins: None,
+            sessdata_expires_at: None,
+            sessdata_remaining_seconds: None,
+            message: "未配置B站凭证".to_string(),
+        }));
+    };
+
+    let expires_at = cred.sessdata_expires_at();
+    let sessdata_expires_at = expires_at.map(crate::utils::time_format::timestamp_to_beijing_string);
+    let sessdata_remaining_seconds = expires_at.map(|ts| (ts - chrono::Utc::now().timestamp()).max(0));
+
+    let mut cookie_str = format!(
+        "SESSDATA={}; bili_jct={}; buvid3={}; DedeUserID={}",
+        cred.sessdata, cred.bili_jct, cred.buvid3, cred.dedeuserid
+    );
+    if let Some(buvid4) = cred.buvid4.as_deref() {
+        cookie_str.push_str(&format!("; buvid4={}", buvid4));
+    }
+
+    // 只读取凭证进行校验，不修改已保存的凭证
+    let data: serde_json::Value = crate::bilibili::proxied_reqwest_client()
+        .get("https://api.bilibili.com/x/web-interface/nav")
+        .headers(create_api_headers())
+        .header("Cookie", cookie_str)
+        .send()
+        .await
+        .map_err(|e| anyhow!("请求B站API失败: {}", e))?
+        .json()
+        .await
+        .map_err(|e| anyhow!("解析响应失败: {}", e))?;
+
+    let user_data = &data["data"];
+    let is_valid = data["code"].as_i64() == Some(0) && user_data["isLogin"].as_bool().unwrap_or(false);
+    let response = if is_valid {
+        crate::api::response::CredentialStatusResponse {
+            is_valid,
+            user_id: user_data["mid"].as_i64().map(|mid| mid.to_string()),
+            username: user_data["uname"].as_str().map(str::to_string),
+            vip_status: user_data["vipStatus"].as_i64(),
+            vip_type: user_data["vipType"].as_i64(),
+            coins: user_data["money"].as_f64(),
+            sessdata_expires_at,
+            sessdata_remaining_seconds,
+            message: "凭证有效".to_string(),
+        }
+    } else {
+        warn!(
+            "B站凭证校验未通过: code={}, message={}",
+            data["code"].as_i64().unwrap_or(-1),
+            data["message"].as_str().unwrap_or("Unknown error")
+        );
+        crate::api::response::CredentialStatusResponse {
+            is_valid,
+            user_id: None,
+            username: None,
+            vip_status: None,
+            vip_type: None,
+            coins: None,
+            sessdata_expires_at,
+            sessdata_remaining_seconds,
+            message: format!("凭证无效: {}", data["message"].as_str().unwrap_or("账号未登录")),
+        }
+    };
+
+    Ok(ApiResponse::ok(response))
+}
+
 /// 生成扫码登录二维码
 #[utoipa::path(
     post,
@@ -8164,6 +13366,65 @@ pub async fn generate_qr_code(
     Ok(ApiResponse::ok(response))
 }
 
+/// 保存登录得到的凭证到配置系统，扫描中则通过任务队列处理
+async fn save_login_credential(
+    db: &Arc<DatabaseConnection>,
+    credential: crate::bilibili::Credential,
+    source: &str,
+) -> Result<(), ApiError> {
+    // 保存凭证到配置系统
+    let config = crate::config::reload_config();
+    config.credential.store(Some(std::sync::Arc::new(credential)));
+
+    // 检查是否正在扫描，如果是则通过任务队列处理
+    if crate::task::is_scanning() {
+        // 将配置更新任务加入队列
+        use uuid::Uuid;
+        let reload_task = crate::task::ReloadConfigTask {
+            credential: config.credential.load_full().as_deref().cloned(),
+            task_id: Uuid::new_v4().to_string(),
+            priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
+        };
+        crate::task::enqueue_reload_task(reload_task, db)
+            .await
+            .map_err(|e| ApiError::from(anyhow!("保存凭证失败: {}", e)))?;
+        info!("检测到正在扫描，凭证保存任务已加入队列");
+    } else {
+        // 只更新凭据配置项，避免覆盖其他配置
+        use crate::config::ConfigManager;
+        let manager = ConfigManager::new(db.as_ref().clone());
+
+        let credential_json = serde_json::to_value(&config.credential).map_err(|e| {
+            error!("序列化凭据失败: {}", e);
+            ApiError::from(anyhow!("序列化凭据失败: {}", e))
+        })?;
+
+        if let Err(e) = manager.update_config_item("credential", credential_json).await {
+            error!("保存凭证到数据库失败: {}", e);
+            return Err(ApiError::from(anyhow!("保存凭证失败: {}", e)));
+        } else {
+            info!("{}凭证已保存到数据库", source);
+        }
+
+        // 重新加载全局配置包（从数据库）
+        if let Err(e) = crate::config::reload_config_bundle().await {
+            warn!("重新加载配置包失败: {}", e);
+            // 回退到传统的重新加载方式
+            crate::config::reload_config();
+        }
+
+        // 用户登录成功后，尝试初始化硬件指纹
+        use crate::hardware::HardwareFingerprint;
+        if let Err(e) = HardwareFingerprint::reinit_if_user_changed(db.as_ref()).await {
+            debug!("硬件指纹初始化失败: {}", e);
+        } else {
+            info!("登录后硬件指纹初始化完成");
+        }
+    }
+
+    Ok(())
+}
+
 /// 轮询扫码登录状态
 #[utoipa::path(
     get,
@@ -8214,55 +13475,7 @@ pub async fn poll_qr_status(
             user_info: None,
         },
         LoginStatus::Confirmed(login_result) => {
-            // 保存凭证到配置系统
-            let config = crate::config::reload_config();
-            config
-                .credential
-                .store(Some(std::sync::Arc::new(login_result.credential.clone())));
-
-            // 检查是否正在扫描，如果是则通过任务队列处理
-            if crate::task::is_scanning() {
-                // 将配置更新任务加入队列
-                use uuid::Uuid;
-                let reload_task = crate::task::ReloadConfigTask {
-                    task_id: Uuid::new_v4().to_string(),
-                };
-                crate::task::enqueue_reload_task(reload_task, &db)
-                    .await
-                    .map_err(|e| ApiError::from(anyhow!("保存凭证失败: {}", e)))?;
-                info!("检测到正在扫描，凭证保存任务已加入队列");
-            } else {
-                // 只更新凭据配置项，避免覆盖其他配置
-                use crate::config::ConfigManager;
-                let manager = ConfigManager::new(db.as_ref().clone());
-
-                let credential_json = serde_json::to_value(&config.credential).map_err(|e| {
-                    error!("序列化凭据失败: {}", e);
-                    ApiError::from(anyhow!("序列化凭据失败: {}", e))
-                })?;
-
-                if let Err(e) = manager.update_config_item("credential", credential_json).await {
-                    error!("保存凭证到数据库失败: {}", e);
-                    return Err(ApiError::from(anyhow!("保存凭证失败: {}", e)));
-                } else {
-                    info!("扫码登录凭证已保存到数据库");
-                }
-
-                // 重新加载全局配置包（从数据库）
-                if let Err(e) = crate::config::reload_config_bundle().await {
-                    warn!("重新加载配置包失败: {}", e);
-                    // 回退到传统的重新加载方式
-                    crate::config::reload_config();
-                }
-
-                // 用户登录成功后，尝试初始化硬件指纹
-                use crate::hardware::HardwareFingerprint;
-                if let Err(e) = HardwareFingerprint::reinit_if_user_changed(db.as_ref()).await {
-                    debug!("硬件指纹初始化失败: {}", e);
-                } else {
-                    info!("登录后硬件指纹初始化完成");
-                }
-            }
+            save_login_credential(&db, login_result.credential.clone(), "扫码登录").await?;
 
             crate::api::response::QRPollResponse {
                 status: "confirmed".to_string(),
@@ -8294,12 +13507,12 @@ pub async fn poll_qr_status(
     get,
     path = "/api/auth/current-user",
     responses(
-        (status = 200, description = "获取成功", body = QRUserInfo),
+        (status = 200, description = "获取成功", body = crate::api::response::CurrentUserResponse),
         (status = 401, description = "未登录或凭证无效"),
         (status = 500, description = "服务器内部错误")
     )
 )]
-pub async fn get_current_user() -> Result<ApiResponse<crate::api::response::QRUserInfo>, ApiError> {
+pub async fn get_current_user() -> Result<ApiResponse<crate::api::response::CurrentUserResponse>, ApiError> {
     // 获取当前凭证
     let config = crate::config::with_config(|bundle| bundle.config.clone());
     let credential = config.credential.load();
@@ -8316,7 +13529,7 @@ pub async fn get_current_user() -> Result<ApiResponse<crate::api::response::QRUs
     );
 
     // 创建 HTTP 客户端
-    let client = reqwest::Client::new();
+    let client = crate::bilibili::proxied_reqwest_client();
 
     // 调用B站API获取用户信息
     let request_url = "https://api.bilibili.com/x/web-interface/nav";
@@ -8366,10 +13579,17 @@ pub async fn get_current_user() -> Result<ApiResponse<crate::api::response::QRUs
     }
 
     let user_data = &data["data"];
-    Ok(ApiResponse::ok(crate::api::response::QRUserInfo {
+    let refresh_state = crate::task::credential_refresher::refresh_state();
+    Ok(ApiResponse::ok(crate::api::response::CurrentUserResponse {
         user_id: user_data["mid"].as_i64().unwrap_or(0).to_string(),
         username: user_data["uname"].as_str().unwrap_or("").to_string(),
         avatar_url: user_data["face"].as_str().unwrap_or("").to_string(),
+        credential_refresh: crate::api::response::CredentialRefreshStatus {
+            last_check_at: refresh_state.last_check_at,
+            last_refresh_at: refresh_state.last_refresh_at,
+            next_check_at: refresh_state.next_check_at,
+            last_error: refresh_state.last_error,
+        },
     }))
 }
 
@@ -8449,6 +13669,53 @@ pub async fn resume_scanning_endpoint() -> Result<ApiResponse<crate::api::respon
     }))
 }
 
+/// 立即扫描所有视频源，不受扫描间隔限制，供外部定时任务或 CI 控制扫描时机
+///
+/// 请求提交后立即返回；已有扫描正在进行时，新一轮扫描会在当前扫描结束后立即开始
+#[utoipa::path(
+    post,
+    path = "/api/scan/trigger-all",
+    params(crate::api::request::TriggerScanAllRequest),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::TriggerScanAllResponse>),
+    )
+)]
+pub async fn trigger_scan_all(
+    Query(params): Query<crate::api::request::TriggerScanAllRequest>,
+) -> Result<ApiResponse<crate::api::response::TriggerScanAllResponse>, ApiError> {
+    let force_metadata = params.force_metadata.unwrap_or(false);
+    let controller = &crate::task::TASK_CONTROLLER;
+
+    if controller.is_paused() && !params.resume.unwrap_or(false) {
+        return Ok(ApiResponse::ok(crate::api::response::TriggerScanAllResponse {
+            triggered: false,
+            already_scanning: false,
+            is_paused: true,
+            force_metadata,
+            message: "任务已暂停，未触发扫描，可使用 resume=true 自动恢复".to_string(),
+        }));
+    }
+
+    let already_scanning = controller.request_full_scan(force_metadata);
+    if controller.is_paused() {
+        info!("收到全量扫描请求，自动恢复已暂停的任务");
+        crate::task::resume_scanning();
+    }
+
+    let message = if already_scanning {
+        "已有扫描正在进行，将在当前扫描结束后立即开始新一轮扫描".to_string()
+    } else {
+        "已触发全量扫描".to_string()
+    };
+    Ok(ApiResponse::ok(crate::api::response::TriggerScanAllResponse {
+        triggered: true,
+        already_scanning,
+        is_paused: false,
+        force_metadata,
+        message,
+    }))
+}
+
 /// 获取任务控制状态
 #[utoipa::path(
     get,
@@ -9756,6 +15023,10 @@ async fn update_bangumi_video_path_in_database(
             path: None,
             image: None,
             download_status: 0,
+            webdav_upload_status: 0,
+            canonical_path: None,
+            downloaded_quality: None,
+            quality_checked_at: None,
             created_at: now_standard_string(),
         };
 
@@ -9889,6 +15160,10 @@ async fn move_bangumi_files_to_new_path(
             path: None,
             image: None,
             download_status: 0,
+            webdav_upload_status: 0,
+            canonical_path: None,
+            downloaded_quality: None,
+            quality_checked_at: None,
             created_at: now_standard_string(),
         };
 
@@ -10326,31 +15601,24 @@ async fn reset_nfo_tasks_for_config_change(db: Arc<DatabaseConnection>) -> Resul
     if resetted {
         let txn = db.begin().await?;
 
-        // 批量更新视频状态
-        if !resetted_videos_info.is_empty() {
-            for video in &resetted_videos_info {
-                video::Entity::update(video::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(video.id),
-                    download_status: sea_orm::Set(VideoStatus::from(video.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新视频状态
+        batch_update_video_status(
+            &txn,
+            resetted_videos_info
+                .iter()
+                .map(|video| (video.id, VideoStatus::from(video.download_status).into())),
+            false,
+        )
+        .await?;
 
-        // 批量更新页面状态
-        if !resetted_pages_info.is_empty() {
-            for page in &resetted_pages_info {
-                page::Entity::update(page::ActiveModel {
-                    id: sea_orm::ActiveValue::Unchanged(page.id),
-                    download_status: sea_orm::Set(PageStatus::from(page.download_status).into()),
-                    ..Default::default()
-                })
-                .exec(&txn)
-                .await?;
-            }
-        }
+        // 按状态值分组批量更新页面状态
+        batch_update_page_status(
+            &txn,
+            resetted_pages_info
+                .iter()
+                .map(|page| (page.id, PageStatus::from(page.download_status).into())),
+        )
+        .await?;
 
         txn.commit().await?;
     }
@@ -10367,14 +15635,9 @@ async fn reset_nfo_tasks_for_config_change(db: Arc<DatabaseConnection>) -> Resul
 }
 
 /// 从全局缓存中获取番剧季标题
-/// 如果缓存中没有，返回None（避免在API响应中阻塞）
+/// 如果缓存中没有或已过期，返回None（避免在API响应中阻塞）
 async fn get_cached_season_title(season_id: &str) -> Option<String> {
-    // 引用workflow模块中的全局缓存
-    if let Ok(cache) = crate::workflow::SEASON_TITLE_CACHE.lock() {
-        cache.get(season_id).cloned()
-    } else {
-        None
-    }
+    crate::utils::season_title_cache::SEASON_TITLE_CACHE.get(season_id)
 }
 
 /// 从API获取番剧标题并存入缓存
@@ -10383,7 +15646,7 @@ async fn fetch_and_cache_season_title(season_id: &str) -> Option<String> {
     let url = format!("https://api.bilibili.com/pgc/view/web/season?season_id={}", season_id);
 
     // 使用reqwest进行简单的HTTP请求
-    let client = reqwest::Client::new();
+    let client = crate::bilibili::proxied_reqwest_client();
 
     // 设置较短的超时时间，避免阻塞API响应
     match tokio::time::timeout(std::time::Duration::from_secs(3), client.get(&url).send()).await {
@@ -10395,10 +15658,8 @@ async fn fetch_and_cache_season_title(season_id: &str) -> Option<String> {
                             let title = title.to_string();
 
                             // 存入缓存
-                            if let Ok(mut cache) = crate::workflow::SEASON_TITLE_CACHE.lock() {
-                                cache.insert(season_id.to_string(), title.clone());
-                                debug!("缓存番剧标题: {} -> {}", season_id, title);
-                            }
+                            crate::utils::season_title_cache::SEASON_TITLE_CACHE.insert(season_id, &title);
+                            debug!("缓存番剧标题: {} -> {}", season_id, title);
 
                             return Some(title);
                         }
@@ -10514,6 +15775,13 @@ ORDER BY
         last_scan_time: task_status.last_run.map(to_standard_string),
         next_scan_time: task_status.next_run.map(to_standard_string),
         is_scanning,
+        aria2_status: crate::aria2_downloader::aria2_health_status().map(|status| crate::api::response::Aria2Status {
+            healthy: status.healthy,
+            error: status.error,
+            checked_at: to_standard_string(status.checked_at),
+        }),
+        fingerprint_rotations: crate::hardware::HardwareFingerprint::rotation_count(),
+        season_title_cache_hit_rate: crate::utils::season_title_cache::SEASON_TITLE_CACHE.hit_rate(),
     };
 
     Ok(ApiResponse::ok(crate::api::response::DashBoardResponse {
@@ -10532,6 +15800,174 @@ ORDER BY
     }))
 }
 
+/// 目录大小统计缓存的有效期，遍历目录开销较大
+const SOURCE_SIZE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// 视频源目录大小缓存：(计算时刻, 统计时间, 各视频源大小)
+#[allow(clippy::type_complexity)]
+static SOURCE_SIZE_CACHE: Lazy<
+    tokio::sync::Mutex<Option<(std::time::Instant, String, Vec<crate::api::response::SourceSizeStats>)>>,
+> = Lazy::new(|| tokio::sync::Mutex::new(None));
+
+/// 获取各视频源目录的占用空间，结果在有效期内直接复用缓存
+async fn get_source_sizes(
+    db: &DatabaseConnection,
+) -> Result<(String, Vec<crate::api::response::SourceSizeStats>), ApiError> {
+    // 持有锁期间计算，避免并发请求重复遍历目录
+    let mut cache = SOURCE_SIZE_CACHE.lock().await;
+    if let Some((calculated_at, time, sizes)) = cache.as_ref() {
+        if calculated_at.elapsed() < SOURCE_SIZE_CACHE_TTL {
+            return Ok((time.clone(), sizes.clone()));
+        }
+    }
+
+    let (favorites, collections, submissions, watch_laters, bangumis) = tokio::try_join!(
+        favorite::Entity::find()
+            .select_only()
+            .columns([favorite::Column::Id, favorite::Column::Name, favorite::Column::Path])
+            .into_tuple::<(i32, String, String)>()
+            .all(db),
+        collection::Entity::find()
+            .select_only()
+            .columns([
+                collection::Column::Id,
+                collection::Column::Name,
+                collection::Column::Path
+            ])
+            .into_tuple::<(i32, String, String)>()
+            .all(db),
+        submission::Entity::find()
+            .select_only()
+            .columns([
+                submission::Column::Id,
+                submission::Column::UpperName,
+                submission::Column::Path,
+            ])
+            .into_tuple::<(i32, String, String)>()
+            .all(db),
+        watch_later::Entity::find()
+            .select_only()
+            .columns([watch_later::Column::Id, watch_later::Column::Path])
+            .into_tuple::<(i32, String)>()
+            .all(db),
+        video_source::Entity::find()
+            .filter(video_source::Column::Type.eq(1))
+            .select_only()
+            .columns([
+                video_source::Column::Id,
+                video_source::Column::Name,
+                video_source::Column::Path
+            ])
+            .into_tuple::<(i32, String, String)>()
+            .all(db),
+    )?;
+
+    let sources: Vec<(&str, i32, String, String)> = favorites
+        .into_iter()
+        .map(|(id, name, path)| ("favorite", id, name, path))
+        .chain(
+            collections
+                .into_iter()
+                .map(|(id, name, path)| ("collection", id, name, path)),
+        )
+        .chain(
+            submissions
+                .into_iter()
+                .map(|(id, name, path)| ("submission", id, name, path)),
+        )
+        .chain(
+            watch_laters
+                .into_iter()
+                .map(|(id, path)| ("watch_later", id, "稍后再看".to_string(), path)),
+        )
+        .chain(bangumis.into_iter().map(|(id, name, path)| ("bangumi", id, name, path)))
+        .collect();
+
+    let sizes = tokio::task::spawn_blocking(move || {
+        sources
+            .into_iter()
+            .map(|(source_type, id, name, path)| {
+                let size = get_directory_size(&normalize_file_path(&path)).unwrap_or_else(|e| {
+                    debug!("计算视频源目录大小失败: {} - {}", path, e);
+                    0
+                });
+                crate::api::response::SourceSizeStats {
+                    source_type: source_type.to_string(),
+                    id,
+                    name,
+                    path,
+                    size,
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .context("计算视频源目录大小失败")?;
+
+    let time = now_standard_string();
+    *cache = Some((std::time::Instant::now(), time.clone(), sizes.clone()));
+    Ok((time, sizes))
+}
+
+/// 获取归档统计信息
+#[utoipa::path(
+    get,
+    path = "/api/stats",
+    responses(
+        (status = 200, body = ApiResponse<StatsResponse>),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+pub async fn get_stats(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<StatsResponse>, ApiError> {
+    let (favorite, collection, submission, watch_later, bangumi, video_statuses, total_pages) = tokio::try_join!(
+        favorite::Entity::find().count(db.as_ref()),
+        collection::Entity::find().count(db.as_ref()),
+        submission::Entity::find().count(db.as_ref()),
+        watch_later::Entity::find().count(db.as_ref()),
+        video_source::Entity::find()
+            .filter(video_source::Column::Type.eq(1))
+            .count(db.as_ref()),
+        video::Entity::find()
+            .filter(video::Column::Deleted.eq(0))
+            .select_only()
+            .column(video::Column::DownloadStatus)
+            .into_tuple::<u32>()
+            .all(db.as_ref()),
+        page::Entity::find()
+            .inner_join(video::Entity)
+            .filter(video::Column::Deleted.eq(0))
+            .count(db.as_ref()),
+    )?;
+
+    let total_videos = video_statuses.len() as u64;
+    let downloaded_videos = video_statuses
+        .into_iter()
+        .filter(|status| VideoStatus::from(*status).get_completed())
+        .count() as u64;
+    let (size_calculated_at, source_sizes) = get_source_sizes(db.as_ref()).await?;
+
+    Ok(ApiResponse::ok(StatsResponse {
+        sources: crate::api::response::SourceCountStats {
+            favorite,
+            collection,
+            submission,
+            watch_later,
+            bangumi,
+        },
+        total_videos,
+        downloaded_videos,
+        pending_videos: total_videos - downloaded_videos,
+        total_pages,
+        total_size: source_sizes.iter().map(|source| source.size).sum(),
+        source_sizes,
+        size_calculated_at,
+    }))
+}
+
 /// 测试推送通知
 #[utoipa::path(
     post,
@@ -10557,8 +15993,9 @@ pub async fn test_notification_handler(
         ));
     }
 
+    let has_extra_channel = config.has_extra_channel();
     match config.method {
-        crate::config::NotificationMethod::Serverchan if config.serverchan_key.is_none() => {
+        crate::config::NotificationMethod::Serverchan if config.serverchan_key.is_none() && !has_extra_channel => {
             return Ok(ApiResponse::bad_request(
                 crate::api::response::TestNotificationResponse {
                     success: false,
@@ -10566,7 +16003,7 @@ pub async fn test_notification_handler(
                 },
             ));
         }
-        crate::config::NotificationMethod::Bark if config.bark_device_key.is_none() => {
+        crate::config::NotificationMethod::Bark if config.bark_device_key.is_none() && !has_extra_channel => {
             return Ok(ApiResponse::bad_request(
                 crate::api::response::TestNotificationResponse {
                     success: false,
@@ -10617,8 +16054,14 @@ pub async fn get_notification_config() -> Result<ApiResponse<crate::api::respons
         bark_device_key: config.bark_device_key,
         bark_device_keys: config.bark_device_keys.clone(),
         bark_defaults: crate::api::response::BarkDefaultsResponse::from(&config.bark_defaults),
+        telegram_bot_token: config.telegram_bot_token.clone(),
+        telegram_chat_id: config.telegram_chat_id.clone(),
+        discord_webhook_url: config.discord_webhook_url.clone(),
+        webhook_url: config.webhook_url.clone(),
+        webhook_template: config.webhook_template.clone(),
         events: crate::api::response::NotificationEventsResponse::from(&config.events),
         enable_scan_notifications: config.enable_scan_notifications,
+        enable_failure_notifications: config.enable_failure_notifications,
         notification_min_videos: config.notification_min_videos,
         notification_timeout: config.notification_timeout,
         notification_retry_count: config.notification_retry_count,
@@ -10757,6 +16200,51 @@ pub async fn update_notification_config(
         }
     }
 
+    if let Some(ref token) = request.telegram_bot_token {
+        if token.trim().is_empty() {
+            notification_config.telegram_bot_token = None;
+        } else {
+            notification_config.telegram_bot_token = Some(token.trim().to_string());
+        }
+        updated = true;
+    }
+
+    if let Some(ref chat_id) = request.telegram_chat_id {
+        if chat_id.trim().is_empty() {
+            notification_config.telegram_chat_id = None;
+        } else {
+            notification_config.telegram_chat_id = Some(chat_id.trim().to_string());
+        }
+        updated = true;
+    }
+
+    if let Some(ref webhook_url) = request.discord_webhook_url {
+        if webhook_url.trim().is_empty() {
+            notification_config.discord_webhook_url = None;
+        } else {
+            notification_config.discord_webhook_url = Some(webhook_url.trim().to_string());
+        }
+        updated = true;
+    }
+
+    if let Some(ref webhook_url) = request.webhook_url {
+        if webhook_url.trim().is_empty() {
+            notification_config.webhook_url = None;
+        } else {
+            notification_config.webhook_url = Some(webhook_url.trim().to_string());
+        }
+        updated = true;
+    }
+
+    if let Some(ref template) = request.webhook_template {
+        if template.trim().is_empty() {
+            notification_config.webhook_template = None;
+        } else {
+            notification_config.webhook_template = Some(template.clone());
+        }
+        updated = true;
+    }
+
     if let Some(ref events) = request.events {
         if let Some(flag) = events.scan_summary {
             notification_config.events.scan_summary = flag;
@@ -10781,6 +16269,11 @@ pub async fn update_notification_config(
         updated = true;
     }
 
+    if let Some(enabled) = request.enable_failure_notifications {
+        notification_config.enable_failure_notifications = enabled;
+        updated = true;
+    }
+
     if let Some(min_videos) = request.notification_min_videos {
         if !(1..=100).contains(&min_videos) {
             return Err(ApiError::from(anyhow!("推送阈值必须在1-100之间")));
@@ -10849,28 +16342,17 @@ pub async fn get_notification_status() -> Result<ApiResponse<crate::api::respons
     let config = crate::config::with_config(|bundle| bundle.config.notification.clone());
 
     // 这里可以从数据库或缓存中获取推送统计信息
-    let configured = match config.method {
-        crate::config::NotificationMethod::Serverchan => config
-            .serverchan_key
-            .as_deref()
-            .map(|value| !value.trim().is_empty())
-            .unwrap_or(false),
-        crate::config::NotificationMethod::Bark => {
-            let single = config
-                .bark_device_key
-                .as_deref()
-                .map(|value| !value.trim().is_empty())
-                .unwrap_or(false);
-            let multi = config.bark_device_keys.iter().any(|value| !value.trim().is_empty());
-            single || multi
-        }
-    };
+    let channels: Vec<String> = crate::utils::notification::configured_channel_ids(&config)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
 
     let status = crate::api::response::NotificationStatusResponse {
-        configured,
+        configured: !channels.is_empty(),
         enabled: config.enable_scan_notifications,
         last_notification_time: None, // TODO: 从存储中获取
         method: config.method.as_str().to_string(),
+        channels,
     };
 
     Ok(ApiResponse::ok(status))
@@ -10930,7 +16412,7 @@ fn extract_bangumi_season_title(full_title: &str) -> String {
 }
 
 /// 从API获取合集封面URL
-async fn get_collection_cover_from_api(
+pub(crate) async fn get_collection_cover_from_api(
     up_id: i64,
     collection_id: i64,
     client: &crate::bilibili::BiliClient,
@@ -11119,6 +16601,7 @@ async fn handle_bangumi_merge_to_existing(
     }
 
     Ok(AddVideoSourceResponse {
+        task_id: None,
         success: true,
         source_id: target_source.id,
         source_type: "bangumi".to_string(),
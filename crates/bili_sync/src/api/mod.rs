@@ -1,5 +1,7 @@
 pub mod auth;
+pub mod cors;
 pub mod handler;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
 pub mod video_stream;
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use once_cell::sync::Lazy;
+
+use crate::api::wrapper::ApiResponse;
+
+/// 无论成败都计入限流的认证相关路径，防止暴力尝试
+const RATE_LIMITED_PATHS: [&str; 6] = [
+    "/api/setup/auth-token",
+    "/api/credential",
+    "/api/auth/qr/generate",
+    "/api/auth/qr/poll",
+    "/api/auth/clear-credential",
+    "/api/captcha/submit",
+];
+
+/// 桶数量超过该值时清理已经回满的桶，避免内存无限增长
+const MAX_TRACKED_IPS: usize = 4096;
+
+static LIMITER: Lazy<Mutex<RateLimiter>> = Lazy::new(|| Mutex::new(RateLimiter::default()));
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// 按 IP 划分的令牌桶，容量为每分钟请求数，令牌按每分钟回满的速度补充
+#[derive(Default)]
+struct RateLimiter {
+    buckets: HashMap<IpAddr, Bucket>,
+}
+
+impl RateLimiter {
+    /// 补充令牌后返回该 IP 当前的桶
+    fn refill(&mut self, ip: IpAddr, per_minute: u32, now: Instant) -> &mut Bucket {
+        let capacity = per_minute as f64;
+        if self.buckets.len() >= MAX_TRACKED_IPS && !self.buckets.contains_key(&ip) {
+            self.buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated_at).as_secs_f64() * capacity / 60.0 < capacity
+            });
+        }
+        let bucket = self.buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * capacity / 60.0).min(capacity);
+        bucket.updated_at = now;
+        bucket
+    }
+
+    /// 令牌不足时返回需要等待的时间
+    fn check(&mut self, ip: IpAddr, per_minute: u32, now: Instant) -> Option<Duration> {
+        let bucket = self.refill(ip, per_minute, now);
+        (bucket.tokens < 1.0).then(|| Duration::from_secs_f64((1.0 - bucket.tokens) * 60.0 / per_minute as f64))
+    }
+
+    fn consume(&mut self, ip: IpAddr, per_minute: u32, now: Instant) {
+        let bucket = self.refill(ip, per_minute, now);
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+/// 对认证相关接口与认证失败的请求按 IP 限流，超出时返回 429 并附带 Retry-After
+pub async fn rate_limit(request: Request, next: Next) -> Response {
+    let per_minute = crate::config::with_config(|bundle| bundle.config.api_rate_limit_per_minute);
+    let path = request.uri().path();
+    // WebSocket 升级请求不做限制
+    if per_minute == 0 || !path.starts_with("/api/") || path.starts_with("/api/ws") {
+        return next.run(request).await;
+    }
+    let Some(ip) = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+    else {
+        return next.run(request).await;
+    };
+    let sensitive = RATE_LIMITED_PATHS.iter().any(|limited| path.starts_with(limited));
+
+    let wait = LIMITER.lock().unwrap().check(ip, per_minute, Instant::now());
+    if let Some(wait) = wait {
+        tracing::warn!("IP {} 请求过于频繁，已限流: {}", ip, path);
+        let mut response = ApiResponse::too_many_requests("请求过于频繁，请稍后再试").into_response();
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(wait.as_secs_f64().ceil().max(1.0) as u64),
+        );
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if sensitive || response.status() == StatusCode::UNAUTHORIZED {
+        LIMITER.lock().unwrap().consume(ip, per_minute, Instant::now());
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::default();
+        let ip: IpAddr = "192.168.1.2".parse().unwrap();
+        let other: IpAddr = "192.168.1.3".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check(ip, 3, now).is_none());
+            limiter.consume(ip, 3, now);
+        }
+        // 令牌耗尽，每分钟 3 个令牌即 20 秒补充一个
+        assert_eq!(limiter.check(ip, 3, now), Some(Duration::from_secs(20)));
+        // 其他 IP 不受影响
+        assert!(limiter.check(other, 3, now).is_none());
+        // 等待足够时间后恢复
+        assert!(limiter.check(ip, 3, now + Duration::from_secs(20)).is_none());
+    }
+}
@@ -16,6 +16,51 @@ pub struct VideosRequest {
     pub force: Option<bool>,
     pub sort_by: Option<String>,    // "id", "name", "upper_name", "created_at", "updated_at"
     pub sort_order: Option<String>, // "asc", "desc"
+    pub tag: Option<String>,        // 只显示带有该自定义标签的视频
+}
+
+// 跨视频源搜索本地视频请求
+#[derive(Deserialize, IntoParams)]
+pub struct SearchVideosRequest {
+    // 搜索关键词，匹配标题、UP主名称和BV号
+    pub q: String,
+    // 页码从 0 开始，与视频列表一致
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+// 导出视频列表请求，筛选条件与 VideosRequest 相同
+#[derive(Deserialize, IntoParams)]
+pub struct ExportVideosRequest {
+    // 导出格式: "csv"（默认）或 "json"
+    pub format: Option<String>,
+}
+
+// 下载日志文件的查询参数
+#[derive(Deserialize, IntoParams)]
+pub struct DownloadLogFileRequest {
+    // 日志级别: all（默认）、debug、info、warn、error，下载当天的日志
+    pub level: Option<String>,
+    // 日志文件名，取自日志文件列表，指定时忽略 level，可下载之前日期的日志
+    pub file: Option<String>,
+    // 只返回最后 N 行
+    pub lines: Option<usize>,
+}
+
+// 触发全量扫描的查询参数
+#[derive(Deserialize, IntoParams)]
+pub struct TriggerScanAllRequest {
+    // 重新生成已下载视频的 NFO 文件，默认 false
+    pub force_metadata: Option<bool>,
+    // 任务已暂停时是否自动恢复，默认 false（暂停时不执行扫描）
+    pub resume: Option<bool>,
+}
+
+// 视频源扫描历史查询参数
+#[derive(Deserialize, IntoParams)]
+pub struct ScanHistoryRequest {
+    // 返回的记录数量，默认 20，最多 100
+    pub limit: Option<u64>,
 }
 
 #[derive(Deserialize, IntoParams)]
@@ -47,18 +92,36 @@ pub struct AddVideoSourceRequest {
     pub download_all_seasons: Option<bool>,
     // 选中的季度ID列表，仅当source_type为"bangumi"且download_all_seasons为false时有效
     pub selected_seasons: Option<Vec<String>>,
+    // 集数范围，如 "1-12,25"，仅当source_type为"bangumi"时有效，为空表示下载全部集数
+    pub episode_range: Option<String>,
     // 选中的视频ID列表，仅当source_type为"submission"时有效，用于选择性下载历史投稿
     pub selected_videos: Option<Vec<String>>,
     // 封面URL，仅当source_type为"collection"时有效
     pub cover: Option<String>,
     // 合并到现有番剧源的ID，仅当source_type为"bangumi"时有效
     pub merge_to_source_id: Option<i32>,
+    // 扫描间隔（秒），为空表示使用全局扫描间隔
+    pub scan_interval: Option<u64>,
+    // 只处理该日期（YYYY-MM-DD）及之后发布的视频，为空表示不限制
+    pub pubtime_after: Option<String>,
+    // 只处理该日期（YYYY-MM-DD）及之前发布的视频，为空表示不限制
+    pub pubtime_before: Option<String>,
+    // 标题需包含的关键词，设置后只保留命中任一关键词的视频
+    pub title_include: Option<Vec<String>>,
+    // 标题命中任一关键词的视频将被跳过
+    pub title_exclude: Option<Vec<String>>,
+    // 是否将标题关键词作为正则表达式匹配
+    pub title_regex: Option<bool>,
+    // 任务优先级，正在扫描时加入队列使用，数值越大越先执行，默认为0
+    pub priority: Option<i32>,
 }
 
 // 删除视频源的请求结构体
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct DeleteVideoSourceRequest {
     pub delete_local_files: bool,
+    // 任务优先级，正在扫描时加入队列使用，数值越大越先执行，默认为0
+    pub priority: Option<i32>,
 }
 
 // 更新视频源启用状态的请求结构体
@@ -73,6 +136,117 @@ pub struct UpdateVideoSourceScanDeletedRequest {
     pub scan_deleted_videos: bool,
 }
 
+// 更新视频源扫描间隔的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceScanIntervalRequest {
+    // 扫描间隔（秒），为空或 0 表示使用全局扫描间隔
+    pub scan_interval: Option<u64>,
+}
+
+// 更新视频源发布时间窗口的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourcePubtimeWindowRequest {
+    // 起始日期（YYYY-MM-DD），为空表示不限制
+    pub pubtime_after: Option<String>,
+    // 截止日期（YYYY-MM-DD），为空表示不限制
+    pub pubtime_before: Option<String>,
+}
+
+// 更新视频源标题过滤关键词的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceTitleFilterRequest {
+    // 标题需包含的关键词，为空表示不限制
+    #[serde(default)]
+    pub title_include: Vec<String>,
+    // 标题需排除的关键词
+    #[serde(default)]
+    pub title_exclude: Vec<String>,
+    // 是否将关键词作为正则表达式匹配（均不区分大小写）
+    #[serde(default)]
+    pub title_regex: bool,
+}
+
+// 更新视频源 .strm 模式的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceStrmRequest {
+    // 是否启用 .strm 模式，启用后只写入播放地址而不下载视频流
+    pub strm_mode: bool,
+    // 是否在每次扫描时刷新 .strm 文件中的播放地址（播放地址会过期）
+    #[serde(default)]
+    pub strm_refresh: bool,
+}
+
+// 更新视频源严格增量模式的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceStrictIncrementalRequest {
+    // 是否只拉取比上次扫描更新的视频，游标丢失时跳过扫描而不是回退到全量扫描
+    pub strict_incremental: bool,
+}
+
+// 更新视频源 WebDAV 上传设置的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceWebdavUploadRequest {
+    // 分页下载完成后是否上传到配置的 WebDAV 存储
+    pub webdav_upload: bool,
+}
+
+// 更新视频源保留策略的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceRetentionRequest {
+    // 只保留最近多少天内入库的视频，为空或 0 表示不限制
+    pub retention_days: Option<u32>,
+    // 最多保留的视频数量，超出时从最旧的开始删除，为空或 0 表示不限制
+    pub retention_max_count: Option<u32>,
+}
+
+// 更新视频源最新视频数量限制的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceLimitNewestRequest {
+    // 扫描时只处理最新的多少个视频，更早的视频不写入数据库也不下载，为空或 0 表示不限制
+    pub limit_newest: Option<u32>,
+}
+
+// 更新视频源 NFO 时间类型的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceNfoTimeTypeRequest {
+    // NFO 中使用的时间类型：favtime（收藏/添加时间）、pubtime（发布时间）、ctime（投稿时间），为空表示使用全局配置
+    pub nfo_time_type: Option<String>,
+}
+
+// 更新视频源仅音频模式的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceAudioOnlyRequest {
+    // 是否只下载音频并输出为带标签和封面的 .m4a 文件
+    pub audio_only: bool,
+}
+
+// 更新视频源画质升级设置的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceUpgradeQualityRequest {
+    // 是否在重新扫描时检查更高画质并重新下载（只升级不降级）
+    pub upgrade_quality: bool,
+}
+
+// 更新视频源弹幕嵌入方式的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceDanmakuEmbedRequest {
+    // 弹幕嵌入方式：none、soft（字幕轨道）或 hard（烧录进画面）
+    pub danmaku_embed_mode: String,
+}
+
+// 更新视频源子任务开关的请求结构体，未提供的字段保持不变
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceSubtasksRequest {
+    // 是否下载封面（包括视频封面与 Up 主头像）
+    pub download_cover: Option<bool>,
+    // 是否生成 NFO 文件（包括视频信息与 Up 主信息）
+    pub download_nfo: Option<bool>,
+    // 是否下载弹幕
+    pub download_danmaku: Option<bool>,
+    // 是否下载字幕
+    pub download_subtitle: Option<bool>,
+}
+
 // 重设视频源路径的请求结构体
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ResetVideoSourcePathRequest {
@@ -117,9 +291,22 @@ pub struct UpdateConfigRequest {
     pub interval: Option<u64>,
     // NFO时间类型
     pub nfo_time_type: Option<String>,
+    // NFO 命名风格（emby / kodi / plex）
+    pub nfo_flavor: Option<String>,
+    // 自定义 NFO 模板（Handlebars），空字符串表示使用内置格式
+    pub nfo_template: Option<String>,
+    // 是否下载演员（创作团队）头像到 .actors 目录
+    pub nfo_download_actor_images: Option<bool>,
+    // 是否将视频标签写入 NFO，以及最多写入的标签数量（0 表示不限制）
+    pub nfo_include_tags: Option<bool>,
+    pub nfo_max_tags: Option<usize>,
+    // 是否将视频看点写入 .chapters.xml 章节文件
+    pub nfo_include_chapters: Option<bool>,
     // 多线程下载配置
     pub parallel_download_enabled: Option<bool>,
     pub parallel_download_threads: Option<usize>,
+    // 全局下载限速（KB/s），0 表示不限速
+    pub download_speed_limit_kbps: Option<u64>,
     // 视频质量设置
     pub video_max_quality: Option<String>,
     pub video_min_quality: Option<String>,
@@ -130,6 +317,9 @@ pub struct UpdateConfigRequest {
     pub no_dolby_audio: Option<bool>,
     pub no_hdr: Option<bool>,
     pub no_hires: Option<bool>,
+    // 视频时长范围（秒），0 表示不限制
+    pub min_duration_secs: Option<u32>,
+    pub max_duration_secs: Option<u32>,
     // 弹幕设置
     pub danmaku_duration: Option<f64>,
     pub danmaku_font: Option<String>,
@@ -143,6 +333,15 @@ pub struct UpdateConfigRequest {
     pub danmaku_bold: Option<bool>,
     pub danmaku_outline: Option<f64>,
     pub danmaku_time_offset: Option<f64>,
+    // 弹幕输出格式（ass、xml、json）
+    pub danmaku_formats: Option<Vec<String>>,
+    // 弹幕屏蔽词，以及是否按正则表达式匹配
+    pub danmaku_block_keywords: Option<Vec<String>>,
+    pub danmaku_block_regex: Option<bool>,
+    // 每秒最多显示的弹幕数量，0 表示不限制
+    pub danmaku_max_per_second: Option<u32>,
+    // 排除的弹幕类型（1=滚动，4=底部，5=顶部，6=逆向，7=高级）
+    pub danmaku_exclude_modes: Option<Vec<u8>>,
     // 并发控制设置
     pub concurrent_video: Option<usize>,
     pub concurrent_page: Option<usize>,
@@ -150,6 +349,25 @@ pub struct UpdateConfigRequest {
     pub rate_duration: Option<u64>,
     // 其他设置
     pub cdn_sorting: Option<bool>,
+    // 下载完成后校验文件完整性
+    pub verify_download_integrity: Option<bool>,
+    // 是否下载官方CC字幕
+    pub download_subtitles: Option<bool>,
+    // 是否为番剧分集写入片头/片尾跳过标记（.edl 文件）
+    pub bangumi_write_skip_markers: Option<bool>,
+    // 是否跳过番剧特别篇（SP/OVA/PV 等）
+    pub bangumi_skip_specials: Option<bool>,
+    // 代理地址，空字符串表示不使用代理
+    pub proxy_url: Option<String>,
+    // 访问B站使用的 User-Agent，空字符串表示使用内置默认值
+    pub user_agent: Option<String>,
+    // 访问B站时附加的请求头，传入空对象表示清空
+    pub extra_headers: Option<std::collections::HashMap<String, String>>,
+    // 静默时段配置，时间格式为 HH:MM，星期为 1-7，传入空数组表示每天生效
+    pub quiet_hours_enabled: Option<bool>,
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    pub quiet_hours_weekdays: Option<Vec<u32>>,
     // UP主投稿风控配置
     pub large_submission_threshold: Option<usize>,
     pub base_request_delay: Option<u64>,
@@ -172,6 +390,74 @@ pub struct UpdateConfigRequest {
     pub enable_aria2_health_check: Option<bool>,
     pub enable_aria2_auto_restart: Option<bool>,
     pub aria2_health_check_interval: Option<u64>,
+    // 队列任务最大重试次数
+    pub task_max_retry: Option<u32>,
+    // 保留策略清理视频时是否删除本地文件
+    pub retention_delete_local_files: Option<bool>,
+    // 删除本地文件时是否移入回收站
+    pub delete_to_trash: Option<bool>,
+    // 回收站保留天数，0 表示不自动清理
+    pub trash_retention_days: Option<u32>,
+    // 跨视频源的重复分页去重方式（none / hardlink / symlink）
+    pub dedup_strategy: Option<String>,
+    // 封面最大宽度，0 表示下载原图
+    pub cover_max_width: Option<u32>,
+    // 封面格式（original / jpg / webp）
+    pub cover_format: Option<String>,
+    // 文件名安全化策略（default / ascii / preserve_unicode）
+    pub filename_policy: Option<String>,
+    // 文件名与完整路径的最大字节数，0 表示不限制
+    pub max_filename_bytes: Option<usize>,
+    pub max_path_bytes: Option<usize>,
+    // 下载暂存目录，空字符串表示直接写入媒体目录
+    pub temp_dir: Option<String>,
+    // 磁盘剩余空间低于该值（GB）时自动暂停下载，0 表示不检查
+    pub min_free_disk_gb: Option<u64>,
+    // Jellyfin 媒体库刷新配置，空字符串表示不通知
+    pub jellyfin_url: Option<String>,
+    pub jellyfin_api_key: Option<String>,
+    // 同一 IP 每分钟允许的认证相关请求数，0 表示不限制
+    pub api_rate_limit_per_minute: Option<u32>,
+    // 允许跨域访问的来源列表，空列表表示仅允许同源访问
+    pub cors_allowed_origins: Option<Vec<String>>,
+    // 是否信任媒体目录中已存在的文件，跳过重复下载
+    pub trust_existing_files: Option<bool>,
+    // FFmpeg 路径与硬件加速方式，空字符串表示恢复默认
+    pub ffmpeg_path: Option<String>,
+    pub ffmpeg_hwaccel: Option<String>,
+    // FFmpeg 合并与弹幕嵌入的超时时间（秒），0 表示不限制
+    pub ffmpeg_merge_timeout_secs: Option<u64>,
+    // 封面缺失时是否从视频中截取一帧作为封面
+    pub generate_thumbnail_fallback: Option<bool>,
+    // 扫描合集时是否同步最新封面
+    pub refresh_covers: Option<bool>,
+    // UP主头像检查间隔（天），0 表示不检查
+    pub refresh_upper_avatar_days: Option<u32>,
+    // 是否跳过无权观看的充电专享视频
+    pub skip_upower_exclusive: Option<bool>,
+    // 是否在每轮扫描后写入扫描报告文件
+    pub write_scan_report: Option<bool>,
+    // 同时扫描的视频源数量
+    pub source_scan_concurrency: Option<usize>,
+    // 番剧标题缓存的最大条目数与过期时间（秒）
+    pub season_title_cache_size: Option<usize>,
+    pub season_title_cache_ttl_secs: Option<u64>,
+    // 数据库连接池配置，修改后重启生效
+    pub db_max_connections: Option<u32>,
+    pub db_acquire_timeout: Option<u64>,
+    pub db_busy_timeout: Option<u64>,
+    // 下载失败重试次数与基础等待时间（毫秒）
+    pub download_max_retries: Option<u32>,
+    pub download_retry_base_delay_ms: Option<u64>,
+    // 外部aria2 RPC配置，空字符串表示使用内置aria2
+    pub aria2_rpc_url: Option<String>,
+    pub aria2_rpc_secret: Option<String>,
+    // WebDAV 上传配置，地址、用户名、密码传入空字符串表示清空，密码传入掩码表示保持不变
+    pub webdav_url: Option<String>,
+    pub webdav_username: Option<String>,
+    pub webdav_password: Option<String>,
+    pub webdav_base_path: Option<String>,
+    pub webdav_delete_local: Option<bool>,
     // 多P视频目录结构配置
     pub multi_page_use_season_structure: Option<bool>,
     // 合集目录结构配置
@@ -189,8 +475,12 @@ pub struct UpdateConfigRequest {
     pub risk_control_auto_solve_api_key: Option<String>,
     pub risk_control_auto_solve_max_retries: Option<u32>,
     pub risk_control_auto_solve_timeout: Option<u64>,
+    // 硬件指纹策略
+    pub risk_control_fingerprint_strategy: Option<String>,
     // 服务器绑定地址
     pub bind_address: Option<String>,
+    // 任务优先级，正在扫描时加入队列使用，数值越大越先执行，默认为0
+    pub priority: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -227,8 +517,14 @@ pub struct UpdateNotificationConfigRequest {
     pub bark_device_key: Option<String>,
     pub bark_device_keys: Option<Vec<String>>,
     pub bark_defaults: Option<BarkDefaultsRequest>,
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub webhook_url: Option<String>,
+    pub webhook_template: Option<String>,
     pub events: Option<NotificationEventsRequest>,
     pub enable_scan_notifications: Option<bool>,
+    pub enable_failure_notifications: Option<bool>,
     pub notification_min_videos: Option<usize>,
     pub notification_timeout: Option<u64>,
     pub notification_retry_count: Option<u8>,
@@ -284,6 +580,67 @@ pub struct UpdateVideoStatusRequest {
     pub page_updates: Vec<PageStatusUpdate>,
 }
 
+// 按视频ID批量重新生成元数据请求
+#[derive(Deserialize, ToSchema)]
+pub struct BatchRefreshMetadataRequest {
+    pub video_ids: Vec<i32>,
+}
+
+// 按视频ID批量重置请求
+#[derive(Deserialize, ToSchema)]
+pub struct BatchResetVideosRequest {
+    pub video_ids: Vec<i32>,
+    // 是否强制重置所有任务，默认只重置失败的任务
+    #[serde(default)]
+    pub force: bool,
+}
+
+// 添加或移除视频自定义标签请求
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateVideoTagsRequest {
+    pub tags: Vec<String>,
+}
+
+// 按视频ID批量删除请求
+#[derive(Deserialize, ToSchema)]
+pub struct BatchDeleteVideosRequest {
+    pub video_ids: Vec<i32>,
+    // 是否同时删除本地文件，默认删除
+    #[serde(default = "default_batch_delete_local_files")]
+    pub delete_local_files: bool,
+}
+
+fn default_batch_delete_local_files() -> bool {
+    true
+}
+
+// 模板预览请求
+#[derive(Deserialize, ToSchema)]
+pub struct PreviewTemplateRequest {
+    // 要预览的模板内容
+    pub template: String,
+    // 模板类型：video、page、multi_page、bangumi、bangumi_folder、folder_structure，默认 video
+    #[serde(default = "default_preview_template_type")]
+    pub template_type: String,
+    // 使用数据库中的视频作为样例，为空时使用内置样例
+    pub video_id: Option<i32>,
+}
+
+fn default_preview_template_type() -> String {
+    "video".to_string()
+}
+
+// 导入 BV 号列表请求
+#[derive(Deserialize, ToSchema)]
+pub struct ImportVideoListRequest {
+    // BV 号或视频链接，每项一个
+    pub videos: Vec<String>,
+    // 保存路径，同一路径的多次导入归入同一个手动列表
+    pub path: String,
+    // 新建手动列表时使用的名称，为空时自动生成
+    pub name: Option<String>,
+}
+
 // 选择性重置任务请求
 #[derive(Deserialize, ToSchema)]
 pub struct ResetSpecificTasksRequest {
@@ -319,20 +676,25 @@ pub struct ConfigHistoryRequest {
 }
 
 // 配置导出请求
-#[derive(Deserialize, ToSchema)]
-#[allow(dead_code)]
+#[derive(Deserialize, IntoParams)]
 pub struct ConfigExportRequest {
-    pub format: String,            // "json" 或 "toml"
-    pub keys: Option<Vec<String>>, // 指定要导出的配置键，None表示导出全部
+    // 是否包含凭证、auth_token、推送密钥等敏感字段，默认不包含
+    #[serde(default)]
+    pub include_secrets: bool,
+}
+
+// 回收站恢复请求
+#[derive(Deserialize, ToSchema)]
+pub struct RestoreTrashRequest {
+    // 回收站批次的完整路径，可通过 GET /api/trash 获取
+    pub path: String,
 }
 
 // 配置导入请求
 #[derive(Deserialize, ToSchema)]
-#[allow(dead_code)]
 pub struct ConfigImportRequest {
-    pub format: String, // "json" 或 "toml"
-    pub data: String,   // 配置数据
-    pub merge: bool,    // 是否合并现有配置，false表示覆盖
+    // 导出得到的配置对象，缺失的字段保留当前值
+    pub config: serde_json::Value,
 }
 
 // 初始设置相关请求
@@ -355,6 +717,13 @@ pub struct UpdateCredentialRequest {
     pub dedeuserid_ckmd5: Option<String>,
 }
 
+// 通过 Cookie 字符串导入凭证请求
+#[derive(Deserialize, ToSchema)]
+pub struct ImportCookieCredentialRequest {
+    /// 浏览器中复制的完整 Cookie 字符串
+    pub cookie: String,
+}
+
 // 扫码登录相关请求
 
 // 生成二维码请求
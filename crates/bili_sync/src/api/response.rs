@@ -63,6 +63,9 @@ pub struct AddVideoSourceResponse {
     pub success: bool,
     pub source_id: i32,
     pub source_type: String,
+    /// 正在扫描时任务加入队列，返回任务ID用于查询执行状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
     pub message: String,
 }
 
@@ -91,6 +94,9 @@ pub struct DeleteVideoSourceResponse {
     pub success: bool,
     pub source_id: i32,
     pub source_type: String,
+    /// 正在扫描时任务加入队列，返回任务ID用于查询执行状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
     pub message: String,
 }
 
@@ -98,6 +104,78 @@ pub struct DeleteVideoSourceResponse {
 pub struct DeleteVideoResponse {
     pub success: bool,
     pub video_id: i32,
+    /// 正在扫描时任务加入队列，返回任务ID用于查询执行状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BatchDeleteVideosResponse {
+    /// 成功加入删除队列的视频数量
+    pub queued_count: usize,
+    /// 因不存在、已删除或已有待处理删除任务而跳过的视频数量
+    pub skipped_count: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportVideoListResponse {
+    /// 视频所属手动列表（合集类型）的ID
+    pub source_id: i32,
+    pub source_name: String,
+    /// 新导入的视频数量
+    pub imported_count: usize,
+    /// 已存在于其他视频源而跳过的 BV 号
+    pub skipped_bvids: Vec<String>,
+    /// 无法识别出 BV 号的条目
+    pub invalid_entries: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CancelQueueTaskResponse {
+    pub success: bool,
+    pub task_id: String,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct QueueTaskStatusResponse {
+    pub task_id: String,
+    pub task_type: String,
+    /// 任务状态: pending / completed / failed / cancelled / dead_letter
+    pub status: String,
+    pub retry_count: i32,
+    pub priority: i32,
+    /// 任务内容摘要
+    pub summary: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeadLetterTaskResponse {
+    /// 数据库记录ID，重新入队时使用
+    pub id: i32,
+    pub task_id: Option<String>,
+    pub task_type: String,
+    pub task_data: serde_json::Value,
+    pub retry_count: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeadLetterTasksResponse {
+    pub total: usize,
+    pub tasks: Vec<DeadLetterTaskResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RequeueDeadLetterTaskResponse {
+    pub success: bool,
+    pub id: i32,
     pub message: String,
 }
 
@@ -119,6 +197,198 @@ pub struct UpdateVideoSourceScanDeletedResponse {
     pub message: String,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceScanIntervalResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub scan_interval: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourcePubtimeWindowResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub pubtime_after: Option<String>,
+    pub pubtime_before: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceTitleFilterResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub title_include: Vec<String>,
+    pub title_exclude: Vec<String>,
+    pub title_regex: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceStrmResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub strm_mode: bool,
+    pub strm_refresh: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceStrictIncrementalResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub strict_incremental: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceWebdavUploadResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub webdav_upload: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceRetentionResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub retention_days: Option<u32>,
+    pub retention_max_count: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceLimitNewestResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub limit_newest: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceNfoTimeTypeResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub nfo_time_type: Option<String>,
+    // 被标记为重新生成 NFO 的视频数量
+    pub resetted_videos_count: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PauseVideoSourceResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub paused: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceAudioOnlyResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub audio_only: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceUpgradeQualityResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub upgrade_quality: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceDanmakuEmbedResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub danmaku_embed_mode: String,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceSubtasksResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub download_cover: bool,
+    pub download_nfo: bool,
+    pub download_danmaku: bool,
+    pub download_subtitle: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RefreshCollectionCoverResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub cover: Option<String>,
+    /// 封面是否发生变化，变化时会加入扫描队列重新下载本地封面
+    pub changed: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScanVideoSourceResponse {
+    pub success: bool,
+    pub task_id: String,
+    pub source_id: i32,
+    pub source_type: String,
+    pub status: crate::task::SourceScanStatus,
+    /// 该视频源已有排队中或正在进行的扫描任务，未重复入队
+    pub already_queued: bool,
+    pub is_paused: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SourceScanTaskResponse {
+    pub task_id: String,
+    pub source_id: i32,
+    pub source_type: String,
+    pub status: crate::task::SourceScanStatus,
+    pub message: Option<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScanHistoryRecord {
+    pub id: i32,
+    pub started_at: String,
+    pub duration_ms: i64,
+    pub new_videos: i32,
+    pub new_pages: i32,
+    pub failed_videos: i32,
+    pub filtered_videos: i32,
+    pub skipped_videos: i32,
+    // 扫描失败时的错误信息
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SourceScanHistoryResponse {
+    pub source_id: i32,
+    pub source_type: String,
+    // 按时间倒序排列的扫描记录
+    pub records: Vec<ScanHistoryRecord>,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct ResetVideoSourcePathResponse {
     pub success: bool,
@@ -159,6 +429,45 @@ pub struct VideoSource {
     pub enabled: bool,
     pub path: String,
     pub scan_deleted_videos: bool,
+    // 扫描间隔（秒），为空表示使用全局扫描间隔
+    pub scan_interval: Option<u64>,
+    // 发布时间窗口（YYYY-MM-DD），为空表示不限制
+    pub pubtime_after: Option<String>,
+    pub pubtime_before: Option<String>,
+    // 标题过滤关键词
+    pub title_include: Vec<String>,
+    pub title_exclude: Vec<String>,
+    pub title_regex: bool,
+    // .strm 模式及是否每次扫描刷新播放地址
+    pub strm_mode: bool,
+    pub strm_refresh: bool,
+    // 严格增量模式：只拉取比上次扫描更新的视频，从不回退到全量扫描
+    pub strict_incremental: bool,
+    // 分页下载完成后是否上传到 WebDAV
+    pub webdav_upload: bool,
+    // 仅音频模式：只下载音频并输出为 .m4a 文件
+    pub audio_only: bool,
+    // 画质升级：重新扫描时若有更高画质可用则重新下载
+    pub upgrade_quality: bool,
+    // 弹幕嵌入方式：none 只生成外挂弹幕，soft 封装为字幕轨道，hard 烧录进画面
+    pub danmaku_embed_mode: String,
+    // 子任务开关：关闭后不再生成封面、NFO、弹幕、字幕，对应状态显示为不适用
+    pub download_cover: bool,
+    pub download_nfo: bool,
+    pub download_danmaku: bool,
+    pub download_subtitle: bool,
+    // 最近一次扫描失败的原因与时间，扫描成功后清空
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+    // 保留策略：保留天数与最大视频数量，为空表示不限制
+    pub retention_days: Option<u32>,
+    pub retention_max_count: Option<u32>,
+    // 只处理最新的 N 个视频，为空表示不限制
+    pub limit_newest: Option<u32>,
+    // 单独设置的 NFO 时间类型，为空表示使用全局配置
+    pub nfo_time_type: Option<String>,
+    // 是否被单独暂停扫描与下载，重启后恢复
+    pub paused: bool,
     // 类型特有的ID字段
     pub f_id: Option<i64>,         // 收藏夹ID
     pub s_id: Option<i64>,         // 合集ID
@@ -214,6 +523,30 @@ pub struct VideoInfo {
     pub cover: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bangumi_title: Option<String>, // 番剧真实标题，用于番剧类型视频的显示
+    /// 用户自定义标签，与 B 站视频标签无关
+    pub user_tags: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct VideoTagsResponse {
+    pub video_id: i32,
+    pub user_tags: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SearchVideosResponse {
+    /// 按所属视频源分组的搜索结果，分组顺序与视频在结果中首次出现的顺序一致
+    pub groups: Vec<SearchResultGroup>,
+    pub total_count: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SearchResultGroup {
+    /// 视频源类型: "collection", "favorite", "submission", "watch_later", "bangumi"，无法确定时为 "unknown"
+    pub source_type: String,
+    pub source_id: i32,
+    pub source_name: String,
+    pub videos: Vec<VideoInfo>,
 }
 
 impl From<(i32, String, String, String, i32, u32, String)> for VideoInfo {
@@ -229,6 +562,7 @@ impl From<(i32, String, String, String, i32, u32, String)> for VideoInfo {
             download_status: VideoStatus::from(download_status).into(),
             cover,
             bangumi_title: None, // 默认为None，将在API层根据视频类型填充
+            user_tags: Vec::new(),
         }
     }
 }
@@ -246,9 +580,17 @@ pub struct ConfigResponse {
     pub time_format: String,
     pub interval: u64,
     pub nfo_time_type: String,
+    pub nfo_flavor: String,
+    pub nfo_template: Option<String>,
+    pub nfo_download_actor_images: bool,
+    pub nfo_include_tags: bool,
+    pub nfo_max_tags: usize,
+    pub nfo_include_chapters: bool,
     // 多线程下载配置
     pub parallel_download_enabled: bool,
     pub parallel_download_threads: usize,
+    // 全局下载限速（KB/s），0 表示不限速
+    pub download_speed_limit_kbps: u64,
     // 视频质量设置
     pub video_max_quality: String,
     pub video_min_quality: String,
@@ -259,6 +601,8 @@ pub struct ConfigResponse {
     pub no_dolby_audio: bool,
     pub no_hdr: bool,
     pub no_hires: bool,
+    pub min_duration_secs: Option<u32>,
+    pub max_duration_secs: Option<u32>,
     // 弹幕设置
     pub danmaku_duration: f64,
     pub danmaku_font: String,
@@ -272,6 +616,11 @@ pub struct ConfigResponse {
     pub danmaku_bold: bool,
     pub danmaku_outline: f64,
     pub danmaku_time_offset: f64,
+    pub danmaku_formats: Vec<String>,
+    pub danmaku_block_keywords: Vec<String>,
+    pub danmaku_block_regex: bool,
+    pub danmaku_max_per_second: Option<u32>,
+    pub danmaku_exclude_modes: Vec<u8>,
     // 并发控制设置
     pub concurrent_video: usize,
     pub concurrent_page: usize,
@@ -279,6 +628,18 @@ pub struct ConfigResponse {
     pub rate_duration: Option<u64>,
     // 其他设置
     pub cdn_sorting: bool,
+    pub verify_download_integrity: bool,
+    pub download_subtitles: bool,
+    pub bangumi_write_skip_markers: bool,
+    pub bangumi_skip_specials: bool,
+    pub proxy_url: Option<String>,
+    pub user_agent: Option<String>,
+    pub extra_headers: std::collections::HashMap<String, String>,
+    // 静默时段配置
+    pub quiet_hours_enabled: bool,
+    pub quiet_hours_start: String,
+    pub quiet_hours_end: String,
+    pub quiet_hours_weekdays: Vec<u32>,
     // UP主投稿风控配置
     pub large_submission_threshold: usize,
     pub base_request_delay: u64,
@@ -301,6 +662,58 @@ pub struct ConfigResponse {
     pub enable_aria2_health_check: bool,
     pub enable_aria2_auto_restart: bool,
     pub aria2_health_check_interval: u64,
+    // 队列任务最大重试次数
+    pub task_max_retry: u32,
+    // 保留策略清理视频时是否删除本地文件
+    pub retention_delete_local_files: bool,
+    // 删除本地文件时是否移入回收站，以及回收站保留天数
+    pub delete_to_trash: bool,
+    pub trash_retention_days: u32,
+    // 跨视频源的重复分页去重方式（none / hardlink / symlink）
+    pub dedup_strategy: String,
+    // 封面缩放宽度与格式，0 与 original 表示下载原图
+    pub cover_max_width: u32,
+    pub cover_format: String,
+    // 文件名安全化策略
+    pub filename_policy: String,
+    // 文件名与完整路径的最大字节数，0 表示不限制
+    pub max_filename_bytes: usize,
+    pub max_path_bytes: usize,
+    // 下载暂存目录，为空表示直接写入媒体目录
+    pub temp_dir: Option<String>,
+    // 磁盘剩余空间低于该值（GB）时自动暂停下载，0 表示不检查
+    pub min_free_disk_gb: u64,
+    // Jellyfin 媒体库刷新配置
+    pub jellyfin_url: Option<String>,
+    pub jellyfin_api_key: Option<String>,
+    pub api_rate_limit_per_minute: u32,
+    pub cors_allowed_origins: Vec<String>,
+    pub trust_existing_files: bool,
+    // FFmpeg 路径与硬件加速方式，为空表示使用 PATH 中的 ffmpeg 且不启用硬件加速
+    pub ffmpeg_path: Option<String>,
+    pub ffmpeg_hwaccel: Option<String>,
+    pub ffmpeg_merge_timeout_secs: u64,
+    pub generate_thumbnail_fallback: bool,
+    pub refresh_covers: bool,
+    pub refresh_upper_avatar_days: u32,
+    pub skip_upower_exclusive: bool,
+    pub write_scan_report: bool,
+    pub source_scan_concurrency: usize,
+    pub season_title_cache_size: usize,
+    pub season_title_cache_ttl_secs: u64,
+    pub db_max_connections: u32,
+    pub db_acquire_timeout: u64,
+    pub db_busy_timeout: u64,
+    pub download_max_retries: u32,
+    pub download_retry_base_delay_ms: u64,
+    pub aria2_rpc_url: Option<String>,
+    pub aria2_rpc_secret: Option<String>,
+    // WebDAV 上传配置，已设置的密码以掩码返回
+    pub webdav_url: Option<String>,
+    pub webdav_username: Option<String>,
+    pub webdav_password: Option<String>,
+    pub webdav_base_path: String,
+    pub webdav_delete_local: bool,
     // 多P视频目录结构配置
     pub multi_page_use_season_structure: bool,
     // 合集目录结构配置
@@ -375,6 +788,84 @@ pub struct ConfigChangeInfo {
     pub changed_at: String,
 }
 
+// 配置导出响应
+#[derive(Serialize, ToSchema)]
+pub struct ConfigExportResponse {
+    pub config: serde_json::Value,
+    pub include_secrets: bool,
+    pub exported_at: String,
+}
+
+// 配置导入响应
+#[derive(Serialize, ToSchema)]
+pub struct ConfigImportResponse {
+    pub success: bool,
+    pub updated_keys: Vec<String>,
+    pub message: String,
+}
+
+// 回收站批次列表响应
+#[derive(Serialize, ToSchema)]
+pub struct TrashListResponse {
+    pub batches: Vec<TrashBatchInfo>,
+}
+
+// 回收站批次信息，每次删除操作对应一个批次
+#[derive(Serialize, ToSchema)]
+pub struct TrashBatchInfo {
+    pub path: String,
+    pub source_root: String,
+    pub deleted_at: Option<String>,
+    pub file_count: usize,
+}
+
+// 回收站恢复响应
+#[derive(Serialize, ToSchema)]
+pub struct RestoreTrashResponse {
+    pub success: bool,
+    pub restored_count: usize,
+    // 原位置已存在同名文件而未恢复的路径
+    pub skipped: Vec<String>,
+    pub message: String,
+}
+
+// 配置回滚响应
+#[derive(Serialize, ToSchema)]
+pub struct ConfigRollbackResponse {
+    pub success: bool,
+    pub history_id: i32,
+    pub key: String,
+    pub changes: Vec<ConfigDiffItem>,
+    // 正在扫描时回滚任务进入队列，扫描完成后生效
+    pub queued: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConfigDiffItem {
+    pub key: String,
+    pub current_value: serde_json::Value,
+    pub restored_value: serde_json::Value,
+}
+
+// 模板预览响应
+#[derive(Serialize, ToSchema)]
+pub struct PreviewTemplateResponse {
+    pub template_type: String,
+    pub template: String,
+    /// 样例数据来源，内置样例或视频名称
+    pub sample: String,
+    /// 模板渲染后未经文件名安全化的结果
+    pub raw: String,
+    /// 下载时实际使用的路径
+    pub rendered_path: String,
+    /// 修改模板后重命名已下载文件时使用的路径
+    pub rename_path: String,
+    /// 按路径分隔符拆分后的各级目录或文件名
+    pub segments: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
 // 配置验证响应
 #[derive(Serialize, ToSchema)]
 pub struct ConfigValidationResponse {
@@ -546,6 +1037,14 @@ pub struct UpdateCredentialResponse {
     pub message: String,
 }
 
+// 通过 Cookie 字符串导入凭证响应
+#[derive(Serialize, ToSchema)]
+pub struct ImportCookieCredentialResponse {
+    pub success: bool,
+    pub message: String,
+    pub user_info: QRUserInfo,
+}
+
 // 扫码登录相关响应
 
 // 生成二维码响应
@@ -572,6 +1071,42 @@ pub struct QRUserInfo {
     pub avatar_url: String,
 }
 
+// 当前登录用户信息
+#[derive(Serialize, ToSchema)]
+pub struct CurrentUserResponse {
+    pub user_id: String,
+    pub username: String,
+    pub avatar_url: String,
+    pub credential_refresh: CredentialRefreshStatus,
+}
+
+// 凭证健康检查结果
+#[derive(Serialize, ToSchema)]
+pub struct CredentialStatusResponse {
+    pub is_valid: bool,
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+    /// 大会员状态：0 非会员，1 会员
+    pub vip_status: Option<i64>,
+    /// 大会员类型：0 无，1 月度，2 年度及以上
+    pub vip_type: Option<i64>,
+    pub coins: Option<f64>,
+    /// SESSDATA 过期时间（北京时间），无法从 SESSDATA 中解析时为空
+    pub sessdata_expires_at: Option<String>,
+    /// SESSDATA 剩余有效秒数，已过期时为 0
+    pub sessdata_remaining_seconds: Option<i64>,
+    pub message: String,
+}
+
+// 凭证自动刷新状态
+#[derive(Serialize, ToSchema)]
+pub struct CredentialRefreshStatus {
+    pub last_check_at: Option<String>,
+    pub last_refresh_at: Option<String>,
+    pub next_check_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
 /// 任务控制响应
 #[derive(Serialize, ToSchema)]
 pub struct TaskControlResponse {
@@ -580,6 +1115,18 @@ pub struct TaskControlResponse {
     pub is_paused: bool,
 }
 
+/// 触发全量扫描响应
+#[derive(Serialize, ToSchema)]
+pub struct TriggerScanAllResponse {
+    /// 是否已提交扫描请求，任务暂停且未要求自动恢复时为 false
+    pub triggered: bool,
+    /// 请求时是否已有扫描正在进行，此时新一轮扫描会在当前扫描结束后立即开始
+    pub already_scanning: bool,
+    pub is_paused: bool,
+    pub force_metadata: bool,
+    pub message: String,
+}
+
 /// 任务控制状态响应
 #[derive(Serialize, ToSchema)]
 pub struct TaskControlStatusResponse {
@@ -660,6 +1207,45 @@ pub struct DashBoardResponse {
     pub monitoring_status: MonitoringStatus,
 }
 
+/// 归档统计信息
+#[derive(Serialize, ToSchema)]
+pub struct StatsResponse {
+    pub sources: SourceCountStats,
+    /// 未删除的视频总数
+    pub total_videos: u64,
+    /// 所有子任务均已完成的视频数量
+    pub downloaded_videos: u64,
+    /// 仍有子任务未完成的视频数量
+    pub pending_videos: u64,
+    pub total_pages: u64,
+    /// 所有视频源目录的占用空间之和（字节）
+    pub total_size: u64,
+    pub source_sizes: Vec<SourceSizeStats>,
+    /// 目录大小的统计时间，目录大小会缓存一段时间
+    pub size_calculated_at: String,
+}
+
+/// 各类型视频源数量
+#[derive(Serialize, ToSchema)]
+pub struct SourceCountStats {
+    pub favorite: u64,
+    pub collection: u64,
+    pub submission: u64,
+    pub watch_later: u64,
+    pub bangumi: u64,
+}
+
+/// 单个视频源目录的占用空间
+#[derive(Clone, Serialize, ToSchema)]
+pub struct SourceSizeStats {
+    pub source_type: String,
+    pub id: i32,
+    pub name: String,
+    pub path: String,
+    /// 目录占用空间（字节），目录不存在或无法读取时为 0
+    pub size: u64,
+}
+
 /// 监听状态信息
 #[derive(Serialize, ToSchema)]
 pub struct MonitoringStatus {
@@ -669,6 +1255,20 @@ pub struct MonitoringStatus {
     pub last_scan_time: Option<String>,
     pub next_scan_time: Option<String>,
     pub is_scanning: bool,
+    /// 最近一次aria2健康检查结果，未检查过时为空
+    pub aria2_status: Option<Aria2Status>,
+    /// 本次运行期间因风控轮换硬件指纹的次数
+    pub fingerprint_rotations: u64,
+    /// 本次运行期间番剧标题缓存的命中率（0~1），尚未有查询时为空
+    pub season_title_cache_hit_rate: Option<f64>,
+}
+
+/// aria2 健康状态
+#[derive(Serialize, ToSchema)]
+pub struct Aria2Status {
+    pub healthy: bool,
+    pub error: Option<String>,
+    pub checked_at: String,
 }
 
 /// 每日视频计数
@@ -761,8 +1361,14 @@ pub struct NotificationConfigResponse {
     pub bark_device_key: Option<String>,
     pub bark_device_keys: Vec<String>,
     pub bark_defaults: BarkDefaultsResponse,
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub webhook_url: Option<String>,
+    pub webhook_template: Option<String>,
     pub events: NotificationEventsResponse,
     pub enable_scan_notifications: bool,
+    pub enable_failure_notifications: bool,
     pub notification_min_videos: usize,
     pub notification_timeout: u64,
     pub notification_retry_count: u8,
@@ -782,6 +1388,8 @@ pub struct NotificationStatusResponse {
     pub enabled: bool,
     pub last_notification_time: Option<String>,
     pub method: String,
+    /// 已配置完整的推送渠道标识，如 serverchan、bark、telegram、discord
+    pub channels: Vec<String>,
 }
 
 // 风控验证配置响应
@@ -792,6 +1400,8 @@ pub struct RiskControlConfigResponse {
     pub timeout: u64,
     // 自动验证配置
     pub auto_solve: Option<AutoSolveConfigResponse>,
+    // 硬件指纹策略
+    pub fingerprint_strategy: String,
 }
 
 // 自动验证配置响应
@@ -30,6 +30,10 @@ impl<T: Serialize> ApiResponse<T> {
         Self { status_code: 400, data }
     }
 
+    pub fn too_many_requests(data: T) -> Self {
+        Self { status_code: 429, data }
+    }
+
     pub fn internal_server_error(data: T) -> Self {
         Self { status_code: 500, data }
     }
@@ -61,7 +65,9 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
         if let Some(inner_error) = self.0.downcast_ref::<InnerApiError>() {
             match inner_error {
-                InnerApiError::NotFound(_) => return ApiResponse::not_found(self.0.to_string()).into_response(),
+                InnerApiError::NotFound(_) | InnerApiError::TaskNotFound(_) => {
+                    return ApiResponse::not_found(self.0.to_string()).into_response()
+                }
                 InnerApiError::BadRequest(_) => return ApiResponse::bad_request(self.0.to_string()).into_response(),
                 InnerApiError::DatabaseError(_) => {
                     return ApiResponse::internal_server_error(self.0.to_string()).into_response()
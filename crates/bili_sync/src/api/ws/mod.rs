@@ -3,7 +3,8 @@ use std::time::Duration;
 
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::WebSocketUpgrade;
-use axum::response::IntoResponse;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::any;
 use axum::Router;
 use dashmap::DashMap;
@@ -18,8 +19,11 @@ use tokio::task::JoinHandle;
 use tokio_stream::wrappers::{IntervalStream, WatchStream};
 use uuid::Uuid;
 
+use crate::api::handler::{subscribe_logs, LogEntry, LogLevel};
 use crate::api::response::SysInfo;
-use crate::utils::task_notifier::{TaskStatus, TASK_STATUS_NOTIFIER};
+use crate::utils::task_notifier::{
+    DownloadProgress, DownloadSpeedSampler, ScanProgress, TaskStatus, SCAN_PROGRESS_NOTIFIER, TASK_STATUS_NOTIFIER,
+};
 
 static WEBSOCKET_HANDLER: LazyLock<WebSocketHandler> = LazyLock::new(WebSocketHandler::new);
 
@@ -27,8 +31,15 @@ pub fn router() -> Router {
     Router::new().route("/api/ws", any(websocket_handler))
 }
 
-async fn websocket_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_socket)
+async fn websocket_handler(headers: HeaderMap, ws: WebSocketUpgrade) -> Response {
+    // 浏览器发起的 WebSocket 连接不受 CORS 限制，需要按相同的跨域配置检查来源
+    if let Some(origin) = headers.get(header::ORIGIN) {
+        if !crate::api::cors::origin_allowed(origin, &headers) {
+            warn!("拒绝来自未允许来源的 WebSocket 连接: {:?}", origin);
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+    ws.on_upgrade(handle_socket).into_response()
 }
 
 // 事件类型枚举
@@ -37,12 +48,40 @@ async fn websocket_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
 enum EventType {
     Tasks,
     SysInfo,
+    DownloadProgress,
+    ScanProgress,
+    Logs,
+}
+
+/// 日志订阅选项
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogsSubscription {
+    /// 最低日志级别，低于该级别的日志不会推送
+    #[serde(default)]
+    min_level: Option<LogLevel>,
+}
+
+impl LogsSubscription {
+    fn accepts(&self, entry: &LogEntry) -> bool {
+        self.min_level
+            .as_ref()
+            .is_none_or(|min_level| entry.level.severity() >= min_level.severity())
+    }
+}
+
+/// 订阅消息，日志订阅可以携带过滤选项，如 `{"subscribe":{"logs":{"minLevel":"warn"}}}`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Subscription {
+    Event(EventType),
+    Logs { logs: LogsSubscription },
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 enum ClientEvent {
-    Subscribe(EventType),
+    Subscribe(Subscription),
     Unsubscribe(EventType),
 }
 
@@ -51,6 +90,9 @@ enum ClientEvent {
 enum ServerEvent {
     Tasks(Arc<TaskStatus>),
     SysInfo(Arc<SysInfo>),
+    DownloadProgress(Arc<DownloadProgress>),
+    ScanProgress(Arc<ScanProgress>),
+    Logs(LogEntry),
 }
 
 struct WebSocketHandler {
@@ -93,10 +135,26 @@ impl WebSocketHandler {
         uuid: Uuid,
     ) {
         let mut task_handle = None;
+        let mut progress_handle = None;
+        let mut scan_progress_handle = None;
+        let mut logs_handle: Option<JoinHandle<()>> = None;
         while let Some(Ok(msg)) = receiver.next().await {
             if let Message::Text(text) = msg {
                 match serde_json::from_str::<ClientEvent>(&text) {
-                    Ok(ClientEvent::Subscribe(event_type)) => match event_type {
+                    Ok(ClientEvent::Subscribe(Subscription::Logs { logs })) => {
+                        // 重复订阅时以新的过滤条件为准
+                        if let Some(handle) = logs_handle.take() {
+                            handle.abort();
+                        }
+                        logs_handle = Some(spawn_logs_forwarder(logs, tx.clone()));
+                    }
+                    Ok(ClientEvent::Subscribe(Subscription::Event(event_type))) => match event_type {
+                        EventType::Logs => {
+                            if let Some(handle) = logs_handle.take() {
+                                handle.abort();
+                            }
+                            logs_handle = Some(spawn_logs_forwarder(LogsSubscription::default(), tx.clone()));
+                        }
                         EventType::Tasks => {
                             if task_handle.as_ref().is_none_or(|h: &JoinHandle<()>| h.is_finished()) {
                                 let tx_clone = tx.clone();
@@ -113,6 +171,43 @@ impl WebSocketHandler {
                             }
                         }
                         EventType::SysInfo => self.add_sysinfo_subscriber(uuid, tx.clone()).await,
+                        EventType::DownloadProgress => {
+                            if progress_handle
+                                .as_ref()
+                                .is_none_or(|h: &JoinHandle<()>| h.is_finished())
+                            {
+                                let tx_clone = tx.clone();
+                                progress_handle = Some(tokio::spawn(async move {
+                                    let mut sampler = DownloadSpeedSampler::new();
+                                    let mut stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(2)))
+                                        .map(move |_| ServerEvent::DownloadProgress(Arc::new(sampler.sample())));
+                                    while let Some(event) = stream.next().await {
+                                        if let Err(e) = tx_clone.send(event).await {
+                                            error!("Failed to send download progress: {:?}", e);
+                                            break;
+                                        }
+                                    }
+                                }));
+                            }
+                        }
+                        EventType::ScanProgress => {
+                            if scan_progress_handle
+                                .as_ref()
+                                .is_none_or(|h: &JoinHandle<()>| h.is_finished())
+                            {
+                                let tx_clone = tx.clone();
+                                scan_progress_handle = Some(tokio::spawn(async move {
+                                    let mut stream = WatchStream::new(SCAN_PROGRESS_NOTIFIER.subscribe())
+                                        .map(ServerEvent::ScanProgress);
+                                    while let Some(event) = stream.next().await {
+                                        if let Err(e) = tx_clone.send(event).await {
+                                            error!("Failed to send scan progress: {:?}", e);
+                                            break;
+                                        }
+                                    }
+                                }));
+                            }
+                        }
                     },
                     Ok(ClientEvent::Unsubscribe(event_type)) => match event_type {
                         EventType::Tasks => {
@@ -123,6 +218,21 @@ impl WebSocketHandler {
                         EventType::SysInfo => {
                             self.remove_sysinfo_subscriber(uuid).await;
                         }
+                        EventType::DownloadProgress => {
+                            if let Some(handle) = progress_handle.take() {
+                                handle.abort();
+                            }
+                        }
+                        EventType::ScanProgress => {
+                            if let Some(handle) = scan_progress_handle.take() {
+                                handle.abort();
+                            }
+                        }
+                        EventType::Logs => {
+                            if let Some(handle) = logs_handle.take() {
+                                handle.abort();
+                            }
+                        }
                     },
                     Err(e) => {
                         error!("Failed to parse client message: {:?}", e);
@@ -133,6 +243,15 @@ impl WebSocketHandler {
         if let Some(handle) = task_handle {
             handle.abort();
         }
+        if let Some(handle) = progress_handle {
+            handle.abort();
+        }
+        if let Some(handle) = scan_progress_handle {
+            handle.abort();
+        }
+        if let Some(handle) = logs_handle {
+            handle.abort();
+        }
         self.remove_sysinfo_subscriber(uuid).await;
     }
 
@@ -203,6 +322,28 @@ impl WebSocketHandler {
     }
 }
 
+/// 将实时日志转发给订阅者，消费过慢时跳过被丢弃的旧日志
+fn spawn_logs_forwarder(subscription: LogsSubscription, tx: tokio::sync::mpsc::Sender<ServerEvent>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut rx = subscribe_logs();
+        loop {
+            match rx.recv().await {
+                Ok(entry) => {
+                    if !subscription.accepts(&entry) {
+                        continue;
+                    }
+                    // 发送失败时不再记录日志，避免日志转发自身产生新的日志
+                    if tx.send(ServerEvent::Logs(entry)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
 async fn handle_socket(socket: WebSocket) {
     let (ws_sender, ws_receiver) = socket.split();
     let uuid = Uuid::new_v4();
@@ -221,3 +362,41 @@ fn sys_refresh_kind() -> RefreshKind {
 fn disk_refresh_kind() -> DiskRefreshKind {
     DiskRefreshKind::nothing().with_storage()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_entry(level: LogLevel) -> LogEntry {
+        LogEntry {
+            timestamp: String::new(),
+            level,
+            message: String::new(),
+            target: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_logs_subscription() {
+        let event: ClientEvent = serde_json::from_str(r#"{"subscribe":"logs"}"#).unwrap();
+        assert!(matches!(
+            event,
+            ClientEvent::Subscribe(Subscription::Event(EventType::Logs))
+        ));
+
+        let event: ClientEvent = serde_json::from_str(r#"{"subscribe":{"logs":{"minLevel":"warn"}}}"#).unwrap();
+        let ClientEvent::Subscribe(Subscription::Logs { logs }) = event else {
+            panic!("应解析为带过滤条件的日志订阅");
+        };
+        assert!(!logs.accepts(&log_entry(LogLevel::Debug)));
+        assert!(!logs.accepts(&log_entry(LogLevel::Info)));
+        assert!(logs.accepts(&log_entry(LogLevel::Warn)));
+        assert!(logs.accepts(&log_entry(LogLevel::Error)));
+
+        // 未指定级别时推送所有日志
+        assert!(LogsSubscription::default().accepts(&log_entry(LogLevel::Debug)));
+
+        let event: ClientEvent = serde_json::from_str(r#"{"unsubscribe":"logs"}"#).unwrap();
+        assert!(matches!(event, ClientEvent::Unsubscribe(EventType::Logs)));
+    }
+}
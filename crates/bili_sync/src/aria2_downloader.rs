@@ -1,6 +1,6 @@
 use anyhow::{bail, Context, Result};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio::time::{sleep, timeout};
@@ -9,6 +9,7 @@ use tracing::{debug, error, info, warn};
 use crate::bilibili::Client;
 use crate::config::CONFIG_DIR;
 use crate::http::headers::create_aria2_headers;
+use crate::utils::task_notifier::{DownloadProgressHandle, DOWNLOAD_PROGRESS};
 
 /// 嵌入的aria2二进制文件 (编译时自动下载对应平台版本)
 #[cfg(target_os = "windows")]
@@ -20,11 +21,57 @@ static ARIA2_BINARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/aria2c"))
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 static ARIA2_BINARY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/aria2c"));
 
-/// 单个aria2进程实例
+/// aria2 会话文件的保存间隔（秒），进程被强制结束时最多丢失这段时间内的队列变化
+const ARIA2_SESSION_SAVE_INTERVAL: u64 = 30;
+
+/// aria2 中与目标文件对应的已有下载任务
+struct ExistingAria2Task {
+    instance_index: usize,
+    rpc_url: String,
+    rpc_secret: String,
+    gid: String,
+    status: String,
+}
+
+/// 本地 aria2 实例的 RPC 地址
+fn local_rpc_url(rpc_port: u16) -> String {
+    format!("http://127.0.0.1:{}/jsonrpc", rpc_port)
+}
+
+/// 构建 aria2 RPC 参数，设置了密钥时在最前面加上 token
+fn rpc_params(rpc_secret: &str, params: Vec<serde_json::Value>) -> serde_json::Value {
+    let token = (!rpc_secret.is_empty()).then(|| serde_json::Value::String(format!("token:{}", rpc_secret)));
+    serde_json::Value::Array(token.into_iter().chain(params).collect())
+}
+
+/// aria2 健康检查结果，供监听状态接口展示
+#[derive(Debug, Clone)]
+pub struct Aria2HealthStatus {
+    pub healthy: bool,
+    pub error: Option<String>,
+    pub checked_at: chrono::DateTime<chrono::Local>,
+}
+
+static ARIA2_HEALTH: LazyLock<parking_lot::RwLock<Option<Aria2HealthStatus>>> = LazyLock::new(Default::default);
+
+/// 最近一次aria2健康检查的结果，尚未检查过时返回 None
+pub fn aria2_health_status() -> Option<Aria2HealthStatus> {
+    ARIA2_HEALTH.read().clone()
+}
+
+fn record_health_status(errors: Vec<String>) {
+    *ARIA2_HEALTH.write() = Some(Aria2HealthStatus {
+        healthy: errors.is_empty(),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        checked_at: chrono::Local::now(),
+    });
+}
+
+/// 单个aria2实例，外部实例（用户配置的 aria2 RPC 地址）没有对应的子进程
 #[derive(Debug)]
 pub struct Aria2Instance {
-    process: tokio::process::Child,
-    rpc_port: u16,
+    process: Option<tokio::process::Child>,
+    rpc_url: String,
     rpc_secret: String,
     active_downloads: std::sync::atomic::AtomicUsize,
     last_used: std::sync::Arc<std::sync::Mutex<std::time::Instant>>,
@@ -32,10 +79,10 @@ pub struct Aria2Instance {
 }
 
 impl Aria2Instance {
-    pub fn new(process: tokio::process::Child, rpc_port: u16, rpc_secret: String) -> Self {
+    pub fn new(process: Option<tokio::process::Child>, rpc_url: String, rpc_secret: String) -> Self {
         Self {
             process,
-            rpc_port,
+            rpc_url,
             rpc_secret,
             active_downloads: std::sync::atomic::AtomicUsize::new(0),
             last_used: std::sync::Arc::new(std::sync::Mutex::new(std::time::Instant::now())),
@@ -58,12 +105,20 @@ impl Aria2Instance {
         self.active_downloads.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
     }
 
+    pub fn is_external(&self) -> bool {
+        self.process.is_none()
+    }
+
     pub fn is_healthy(&mut self) -> bool {
+        // 外部实例没有子进程，只能通过RPC检查健康状态
+        let Some(process) = self.process.as_mut() else {
+            return true;
+        };
         // 检查进程是否还在运行
-        match self.process.try_wait() {
+        match process.try_wait() {
             Ok(Some(_)) => {
                 // 进程已退出
-                debug!("aria2进程已退出 (端口: {})", self.rpc_port);
+                debug!("aria2进程已退出 (地址: {})", self.rpc_url);
                 false
             }
             Ok(None) => {
@@ -81,15 +136,15 @@ impl Aria2Instance {
                 if failure_count >= 3 {
                     // 连续3次失败才判定为不健康
                     warn!(
-                        "aria2进程状态检查连续{}次失败 (端口: {}): {}, 判定为不健康",
-                        failure_count, self.rpc_port, e
+                        "aria2进程状态检查连续{}次失败 (地址: {}): {}, 判定为不健康",
+                        failure_count, self.rpc_url, e
                     );
                     false
                 } else {
                     // 失败次数未达到阈值，仍认为健康
                     debug!(
-                        "aria2进程状态检查失败 (端口: {}, 第{}次): {}, 继续监控",
-                        self.rpc_port, failure_count, e
+                        "aria2进程状态检查失败 (地址: {}, 第{}次): {}, 继续监控",
+                        self.rpc_url, failure_count, e
                     );
                     true
                 }
@@ -103,6 +158,8 @@ pub struct Aria2Downloader {
     aria2_instances: Arc<Mutex<Vec<Aria2Instance>>>,
     aria2_binary_path: PathBuf,
     instance_count: usize,
+    /// 是否使用用户配置的外部aria2，此时不启动也不清理本地aria2进程
+    external: bool,
     #[allow(dead_code)]
     next_instance_index: std::sync::atomic::AtomicUsize,
 }
@@ -112,23 +169,30 @@ impl Aria2Downloader {
     pub async fn new(client: Client) -> Result<Self> {
         tracing::info!("初始化aria2下载器...");
 
-        // 启动前先清理所有旧的aria2进程
-        tracing::debug!("清理旧的aria2进程...");
-        Self::cleanup_all_aria2_processes().await;
-
-        tracing::debug!("提取aria2可执行文件...");
-        let aria2_binary_path = Self::extract_aria2_binary().await?;
-        tracing::debug!("aria2可执行文件路径: {}", aria2_binary_path.display());
-
-        // 确定进程数量：根据系统资源动态计算
-        let instance_count = Self::calculate_optimal_instance_count();
-        tracing::debug!("计算得出最佳aria2实例数: {}", instance_count);
+        let external = Self::external_rpc().is_some();
+        let (aria2_binary_path, instance_count) = if external {
+            (PathBuf::new(), 1)
+        } else {
+            // 启动前先清理所有旧的aria2进程
+            tracing::debug!("清理旧的aria2进程...");
+            Self::cleanup_all_aria2_processes().await;
+
+            tracing::debug!("提取aria2可执行文件...");
+            let aria2_binary_path = Self::extract_aria2_binary().await?;
+            tracing::debug!("aria2可执行文件路径: {}", aria2_binary_path.display());
+
+            // 确定进程数量：根据系统资源动态计算
+            let instance_count = Self::calculate_optimal_instance_count();
+            tracing::debug!("计算得出最佳aria2实例数: {}", instance_count);
+            (aria2_binary_path, instance_count)
+        };
 
         let mut downloader = Self {
             client,
             aria2_instances: Arc::new(Mutex::new(Vec::new())),
             aria2_binary_path,
             instance_count,
+            external,
             next_instance_index: std::sync::atomic::AtomicUsize::new(0),
         };
 
@@ -280,6 +344,45 @@ impl Aria2Downloader {
         optimal_count
     }
 
+    /// 计算单个实例的 max-overall-download-limit，全局限速平均分配给所有实例，"0" 表示不限速
+    fn overall_download_limit(speed_limit_kbps: Option<u64>, instance_count: usize) -> String {
+        match speed_limit_kbps.filter(|kbps| *kbps > 0) {
+            Some(kbps) => format!("{}K", (kbps / instance_count.max(1) as u64).max(1)),
+            None => "0".to_string(),
+        }
+    }
+
+    /// 将当前配置的下载限速同步到指定实例，使配置修改无需重启 aria2 即可生效
+    async fn sync_download_limit(&self, rpc_url: &str, rpc_secret: &str) -> Result<()> {
+        let current_config = crate::config::reload_config();
+        let limit = Self::overall_download_limit(
+            current_config.concurrent_limit.download_speed_limit_kbps,
+            self.instance_count,
+        );
+
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "aria2.changeGlobalOption",
+            "id": "change_global_option",
+            "params": rpc_params(rpc_secret, vec![serde_json::json!({ "max-overall-download-limit": limit })])
+        });
+
+        let json: serde_json::Value = self
+            .client
+            .post(rpc_url)
+            .json(&payload)
+            .send()
+            .await
+            .context("发送修改全局选项请求失败")?
+            .json()
+            .await?;
+
+        if let Some(error) = json.get("error") {
+            bail!("aria2 API错误: {}", error);
+        }
+        Ok(())
+    }
+
     /// 清理所有aria2进程 (Windows兼容)
     async fn cleanup_all_aria2_processes() {
         debug!("清理所有旧的aria2进程...");
@@ -400,7 +503,9 @@ impl Aria2Downloader {
                 .head(url)
                 .header(
                     "User-Agent",
-                    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36",
+                    crate::http::headers::user_agent_or(
+                        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36",
+                    ),
                 )
                 .header("Referer", "https://www.bilibili.com")
                 .send()
@@ -425,8 +530,45 @@ impl Aria2Downloader {
         }
     }
 
+    /// 用户配置的外部aria2 RPC 地址与密钥，未配置时返回 None
+    fn external_rpc() -> Option<(String, String)> {
+        crate::config::with_config(|bundle| {
+            let rpc_url = bundle.config.aria2_rpc_url.as_deref()?.trim();
+            if rpc_url.is_empty() {
+                return None;
+            }
+            let rpc_secret = bundle.config.aria2_rpc_secret.clone().unwrap_or_default();
+            Some((rpc_url.to_string(), rpc_secret))
+        })
+    }
+
+    /// 连接用户配置的外部aria2实例
+    async fn connect_external_instance(&mut self) -> Result<()> {
+        let (rpc_url, rpc_secret) = Self::external_rpc().context("未配置外部aria2 RPC地址")?;
+        info!("使用外部aria2实例: {}", rpc_url);
+
+        let result = self.test_instance_connection(&rpc_url, &rpc_secret).await;
+        record_health_status(
+            result
+                .as_ref()
+                .err()
+                .map(|e| format!("{}: {:#}", rpc_url, e))
+                .into_iter()
+                .collect(),
+        );
+        result.with_context(|| format!("无法连接外部aria2实例: {}", rpc_url))?;
+
+        *self.aria2_instances.lock().await = vec![Aria2Instance::new(None, rpc_url, rpc_secret)];
+        info!("外部aria2实例连接成功");
+        Ok(())
+    }
+
     /// 启动所有aria2进程实例
     async fn start_all_instances(&mut self) -> Result<()> {
+        if self.external {
+            return self.connect_external_instance().await;
+        }
+
         let mut instances = Vec::new();
 
         for i in 0..self.instance_count {
@@ -435,15 +577,18 @@ impl Aria2Downloader {
 
             info!("启动第 {} 个aria2进程，端口: {}", i + 1, rpc_port);
 
-            let process = self.start_single_instance(rpc_port, &rpc_secret).await?;
-            let instance = Aria2Instance::new(process, rpc_port, rpc_secret);
+            let process = self.start_single_instance(rpc_port, &rpc_secret, Some(i)).await?;
+            let instance = Aria2Instance::new(Some(process), local_rpc_url(rpc_port), rpc_secret);
 
             // 等待aria2 RPC服务完全启动（关键修复：避免过早检查）
             info!("等待aria2实例 {} RPC服务启动...", i + 1);
             tokio::time::sleep(Duration::from_secs(3)).await;
 
             // 验证连接（带重试）
-            if let Err(e) = self.test_instance_connection(rpc_port, &instance.rpc_secret).await {
+            if let Err(e) = self
+                .test_instance_connection(&instance.rpc_url, &instance.rpc_secret)
+                .await
+            {
                 warn!("aria2实例 {} 连接测试失败: {:#}", i + 1, e);
                 continue;
             }
@@ -701,8 +846,32 @@ impl Aria2Downloader {
         format!("bili-sync-{:x}", hasher.finish())
     }
 
-    /// 启动单个aria2实例
-    async fn start_single_instance(&self, rpc_port: u16, rpc_secret: &str) -> Result<tokio::process::Child> {
+    /// 第 index 个aria2实例的会话文件路径
+    fn session_file_path(index: usize) -> PathBuf {
+        CONFIG_DIR
+            .join("aria2_session")
+            .join(format!("instance-{}.session", index))
+    }
+
+    /// 准备会话文件，aria2 要求 --input-file 指向的文件必须存在
+    async fn prepare_session_file(index: usize) -> Result<PathBuf> {
+        let session_path = Self::session_file_path(index);
+        if let Some(parent) = session_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        if !session_path.exists() {
+            tokio::fs::write(&session_path, b"").await?;
+        }
+        Ok(session_path)
+    }
+
+    /// 启动单个aria2实例，指定 session_index 时会加载并定期保存该实例的会话，使未完成的任务在重启后继续
+    async fn start_single_instance(
+        &self,
+        rpc_port: u16,
+        rpc_secret: &str,
+        session_index: Option<usize>,
+    ) -> Result<tokio::process::Child> {
         let current_config = crate::config::reload_config();
         let total_threads = current_config.concurrent_limit.parallel_download.threads;
 
@@ -738,13 +907,31 @@ impl Aria2Downloader {
             "--enable-async-dns6=false".to_string(),
             // 网络优化配置
             "--lowest-speed-limit=1K".to_string(),
-            "--max-overall-download-limit=0".to_string(),
+            format!(
+                "--max-overall-download-limit={}",
+                Self::overall_download_limit(
+                    current_config.concurrent_limit.download_speed_limit_kbps,
+                    self.instance_count
+                )
+            ),
             "--stream-piece-selector=geom".to_string(),
             "--piece-length=1M".to_string(),
             "--summary-interval=0".to_string(),
             "--quiet=true".to_string(),
         ];
 
+        if let Some(index) = session_index {
+            match Self::prepare_session_file(index).await {
+                Ok(session_path) => {
+                    let session_path = session_path.to_string_lossy();
+                    args.push(format!("--input-file={}", session_path));
+                    args.push(format!("--save-session={}", session_path));
+                    args.push(format!("--save-session-interval={}", ARIA2_SESSION_SAVE_INTERVAL));
+                }
+                Err(e) => warn!("准备aria2会话文件失败，重启后将无法恢复未完成的任务: {:#}", e),
+            }
+        }
+
         // 添加SSL/TLS相关配置
         if cfg!(target_os = "linux") {
             let ca_paths = [
@@ -821,13 +1008,12 @@ impl Aria2Downloader {
     }
 
     /// 测试单个实例的连接（带重试机制）
-    async fn test_instance_connection(&self, rpc_port: u16, rpc_secret: &str) -> Result<()> {
-        let url = format!("http://127.0.0.1:{}/jsonrpc", rpc_port);
+    async fn test_instance_connection(&self, url: &str, rpc_secret: &str) -> Result<()> {
         let payload = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "aria2.getVersion",
             "id": "test",
-            "params": [format!("token:{}", rpc_secret)]
+            "params": rpc_params(rpc_secret, vec![])
         });
 
         // 使用重试机制测试连接 - 针对启动时机优化
@@ -837,19 +1023,22 @@ impl Aria2Downloader {
             Duration::from_secs(1), // 增加重试间隔，给RPC更多时间
             Duration::from_secs(8), // 适度增加单次超时
             || async {
-                let response = self.client.post(&url).json(&payload).send().await?;
-                if response.status().is_success() {
-                    Ok(())
-                } else {
+                let response = self.client.post(url).json(&payload).send().await?;
+                if !response.status().is_success() {
                     bail!("连接测试返回错误状态: {}", response.status())
                 }
+                let json: serde_json::Value = response.json().await?;
+                if let Some(error) = json.get("error") {
+                    bail!("aria2 API错误: {}", error);
+                }
+                Ok(())
             },
         )
         .await
     }
 
     /// 选择最佳aria2实例（负载均衡+健康检查）
-    async fn select_best_instance(&self) -> Result<(usize, u16, String)> {
+    async fn select_best_instance(&self) -> Result<(usize, String, String)> {
         let instances = self.aria2_instances.lock().await;
 
         if instances.is_empty() {
@@ -867,7 +1056,7 @@ impl Aria2Downloader {
         if healthy_instances.is_empty() {
             warn!("所有aria2实例都不健康，尝试使用第一个实例");
             let instance = &instances[0];
-            return Ok((0, instance.rpc_port, instance.rpc_secret.clone()));
+            return Ok((0, instance.rpc_url.clone(), instance.rpc_secret.clone()));
         }
 
         // 找到负载最低的健康实例
@@ -876,25 +1065,56 @@ impl Aria2Downloader {
             .min_by_key(|(_, instance)| instance.get_load())
             .ok_or_else(|| anyhow::anyhow!("无法找到可用实例"))?;
 
-        Ok((*best_index, best_instance.rpc_port, best_instance.rpc_secret.clone()))
+        Ok((
+            *best_index,
+            best_instance.rpc_url.clone(),
+            best_instance.rpc_secret.clone(),
+        ))
     }
 
     /// 使用aria2下载文件，支持多个URL备选和多进程
-    pub async fn fetch_with_aria2_fallback(&self, urls: &[&str], path: &Path) -> Result<()> {
+    /// 使用aria2下载文件，返回aria2报告的文件总大小（未知时为 None）
+    pub async fn fetch_with_aria2_fallback(&self, urls: &[&str], path: &Path) -> Result<Option<u64>> {
         if urls.is_empty() {
             bail!("No URLs provided");
         }
 
-        // 尝试删除已存在的文件以确保重新下载（忽略文件不存在的错误）
-        // 使用 remove_file 而不是 exists() 检查，避免Windows路径混合斜杠导致的问题
-        match tokio::fs::remove_file(path).await {
-            Ok(_) => debug!("删除已存在的文件以重新下载: {}", path.display()),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                // 文件不存在，继续下载
+        // 上次中断（暂停或进程重启后从会话恢复）的任务仍在aria2中，直接接管而不是重新添加
+        if let Some(task) = self.find_existing_task(path).await {
+            if task.status == "complete" && path.exists() {
+                info!("aria2已完成该文件的下载: {}", path.display());
+                self.remove_download_result(&task.rpc_url, &task.rpc_secret, &task.gid)
+                    .await;
+                self.verify_downloaded_file(path).await?;
+                return Ok(None);
             }
-            Err(e) => {
-                warn!("删除文件失败: {}, 错误: {:#}", path.display(), e);
-                // 即使删除失败也继续尝试下载，aria2会覆盖
+            if matches!(task.status.as_str(), "active" | "waiting" | "paused") {
+                info!(
+                    "继续aria2中未完成的下载任务: {} (GID: {}, 状态: {})",
+                    path.display(),
+                    task.gid,
+                    task.status
+                );
+                return self.wait_for_existing_task(task, path).await;
+            }
+        }
+
+        // 存在aria2控制文件说明上次下载未完成，保留已下载的部分交给 --continue 续传
+        let control_file = Self::control_file_path(path);
+        if control_file.exists() {
+            info!("检测到未完成的aria2下载，尝试断点续传: {}", path.display());
+        } else {
+            // 尝试删除已存在的文件以确保重新下载（忽略文件不存在的错误）
+            // 使用 remove_file 而不是 exists() 检查，避免Windows路径混合斜杠导致的问题
+            match tokio::fs::remove_file(path).await {
+                Ok(_) => debug!("删除已存在的文件以重新下载: {}", path.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    // 文件不存在，继续下载
+                }
+                Err(e) => {
+                    warn!("删除文件失败: {}, 错误: {:#}", path.display(), e);
+                    // 即使删除失败也继续尝试下载，aria2会覆盖
+                }
             }
         }
 
@@ -910,14 +1130,9 @@ impl Aria2Downloader {
             .context("Invalid directory path")?;
 
         // 选择最佳的aria2实例
-        let (instance_index, rpc_port, rpc_secret) = self.select_best_instance().await?;
+        let (instance_index, rpc_url, rpc_secret) = self.select_best_instance().await?;
 
-        info!(
-            "使用aria2实例 {} (端口: {}) 下载: {}",
-            instance_index + 1,
-            rpc_port,
-            file_name
-        );
+        info!("使用aria2实例 {} ({}) 下载: {}", instance_index + 1, rpc_url, file_name);
 
         // 增加该实例的负载计数
         {
@@ -929,13 +1144,15 @@ impl Aria2Downloader {
 
         // 构建aria2 RPC请求
         let gid = self
-            .add_download_task_to_instance(urls, dir, file_name, rpc_port, &rpc_secret)
+            .add_download_task_to_instance(urls, dir, file_name, &rpc_url, &rpc_secret)
             .await?;
 
         // 等待下载完成
+        let progress = DOWNLOAD_PROGRESS.start(file_name, None);
         let result = self
-            .wait_for_download_on_instance(&gid, rpc_port, &rpc_secret, instance_index)
+            .wait_for_download_on_instance(&gid, &rpc_url, &rpc_secret, instance_index, &progress)
             .await;
+        drop(progress);
 
         // 减少该实例的负载计数
         {
@@ -946,12 +1163,147 @@ impl Aria2Downloader {
         }
 
         // 检查下载结果
-        result?;
+        let total_length = result?;
+        self.remove_download_result(&rpc_url, &rpc_secret, &gid).await;
 
         // 增强的文件验证逻辑
         self.verify_downloaded_file(path).await?;
 
-        Ok(())
+        Ok(total_length)
+    }
+
+    /// aria2 下载过程中使用的控制文件路径
+    fn control_file_path(path: &Path) -> PathBuf {
+        let mut control_file = path.as_os_str().to_owned();
+        control_file.push(".aria2");
+        PathBuf::from(control_file)
+    }
+
+    /// 在所有实例中查找目标文件对应的下载任务
+    async fn find_existing_task(&self, path: &Path) -> Option<ExistingAria2Task> {
+        let target = path.to_string_lossy().replace('\\', "/");
+        let instances: Vec<(String, String)> = self
+            .aria2_instances
+            .lock()
+            .await
+            .iter()
+            .map(|instance| (instance.rpc_url.clone(), instance.rpc_secret.clone()))
+            .collect();
+        let keys = serde_json::json!(["gid", "status", "files"]);
+
+        for (instance_index, (rpc_url, rpc_secret)) in instances.into_iter().enumerate() {
+            let queries = [
+                ("aria2.tellActive", rpc_params(&rpc_secret, vec![keys.clone()])),
+                (
+                    "aria2.tellWaiting",
+                    rpc_params(&rpc_secret, vec![0.into(), 1000.into(), keys.clone()]),
+                ),
+                (
+                    "aria2.tellStopped",
+                    rpc_params(&rpc_secret, vec![0.into(), 1000.into(), keys.clone()]),
+                ),
+            ];
+            for (method, params) in queries {
+                let payload = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": method,
+                    "id": "find_existing",
+                    "params": params
+                });
+                let json = match self.client.post(&rpc_url).json(&payload).send().await {
+                    Ok(response) => match response.json::<serde_json::Value>().await {
+                        Ok(json) => json,
+                        Err(e) => {
+                            debug!("解析aria2任务列表失败 ({}): {:#}", rpc_url, e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        debug!("查询aria2任务列表失败 ({}): {:#}", rpc_url, e);
+                        continue;
+                    }
+                };
+                let Some(tasks) = json["result"].as_array() else {
+                    continue;
+                };
+                let found = tasks.iter().find(|task| {
+                    task["files"][0]["path"]
+                        .as_str()
+                        .is_some_and(|file_path| file_path.replace('\\', "/") == target)
+                });
+                if let Some(task) = found {
+                    return Some(ExistingAria2Task {
+                        instance_index,
+                        rpc_url: rpc_url.clone(),
+                        rpc_secret: rpc_secret.clone(),
+                        gid: task["gid"].as_str().unwrap_or_default().to_string(),
+                        status: task["status"].as_str().unwrap_or_default().to_string(),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// 接管aria2中已存在的下载任务并等待其完成
+    async fn wait_for_existing_task(&self, task: ExistingAria2Task, path: &Path) -> Result<Option<u64>> {
+        if task.status == "paused" {
+            let payload = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "aria2.unpause",
+                "id": "unpause",
+                "params": rpc_params(&task.rpc_secret, vec![serde_json::json!(task.gid)])
+            });
+            if let Err(e) = self.client.post(&task.rpc_url).json(&payload).send().await {
+                warn!("恢复暂停的aria2任务失败 (GID: {}): {:#}", task.gid, e);
+            }
+        }
+
+        {
+            let instances = self.aria2_instances.lock().await;
+            if let Some(instance) = instances.get(task.instance_index) {
+                instance.increment_load();
+            }
+        }
+
+        let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let progress = DOWNLOAD_PROGRESS.start(file_name, None);
+        let result = self
+            .wait_for_download_on_instance(
+                &task.gid,
+                &task.rpc_url,
+                &task.rpc_secret,
+                task.instance_index,
+                &progress,
+            )
+            .await;
+        drop(progress);
+
+        {
+            let instances = self.aria2_instances.lock().await;
+            if let Some(instance) = instances.get(task.instance_index) {
+                instance.decrement_load();
+            }
+        }
+
+        let total_length = result?;
+        self.remove_download_result(&task.rpc_url, &task.rpc_secret, &task.gid)
+            .await;
+        self.verify_downloaded_file(path).await?;
+        Ok(total_length)
+    }
+
+    /// 清除已完成任务的下载结果，避免之后下载同一路径时误认为已完成
+    async fn remove_download_result(&self, url: &str, rpc_secret: &str, gid: &str) {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "aria2.removeDownloadResult",
+            "id": "remove_result",
+            "params": rpc_params(rpc_secret, vec![serde_json::json!(gid)])
+        });
+        if let Err(e) = self.client.post(url).json(&payload).send().await {
+            debug!("清除aria2下载结果失败 (GID: {}): {:#}", gid, e);
+        }
     }
 
     /// 添加下载任务到指定实例（带重试机制）
@@ -960,11 +1312,9 @@ impl Aria2Downloader {
         urls: &[&str],
         dir: &str,
         file_name: &str,
-        rpc_port: u16,
+        url: &str,
         rpc_secret: &str,
     ) -> Result<String> {
-        let url = format!("http://127.0.0.1:{}/jsonrpc", rpc_port);
-
         // 智能计算当前实例的线程数
         let current_config = crate::config::reload_config();
         let total_threads = current_config.concurrent_limit.parallel_download.threads;
@@ -1046,15 +1396,15 @@ impl Aria2Downloader {
             options["check-certificate"] = serde_json::Value::String("false".to_string());
         }
 
+        if let Err(e) = self.sync_download_limit(url, rpc_secret).await {
+            warn!("同步aria2下载限速失败 ({}): {:#}", url, e);
+        }
+
         let payload = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "aria2.addUri",
             "id": "add_download",
-            "params": [
-                format!("token:{}", rpc_secret),
-                urls,
-                options
-            ]
+            "params": rpc_params(rpc_secret, vec![serde_json::json!(urls), options])
         });
 
         // 使用重试机制添加下载任务
@@ -1073,7 +1423,7 @@ impl Aria2Downloader {
 
                     let response = self
                         .client
-                        .post(&url)
+                        .post(url)
                         .json(&payload)
                         .send()
                         .await
@@ -1106,11 +1456,11 @@ impl Aria2Downloader {
     async fn wait_for_download_on_instance(
         &self,
         gid: &str,
-        rpc_port: u16,
+        url: &str,
         rpc_secret: &str,
         _instance_index: usize,
-    ) -> Result<()> {
-        let url = format!("http://127.0.0.1:{}/jsonrpc", rpc_port);
+        progress: &DownloadProgressHandle<'_>,
+    ) -> Result<Option<u64>> {
         let mut consecutive_failures = 0;
         const MAX_CONSECUTIVE_FAILURES: u32 = 5;
 
@@ -1145,11 +1495,13 @@ impl Aria2Downloader {
                 "jsonrpc": "2.0",
                 "method": "aria2.tellStatus",
                 "id": "check_status",
-                "params": [
-                    format!("token:{}", rpc_secret),
-                    gid,
-                    ["status", "totalLength", "completedLength", "downloadSpeed", "errorMessage"]
-                ]
+                "params": rpc_params(
+                    rpc_secret,
+                    vec![
+                        serde_json::json!(gid),
+                        serde_json::json!(["status", "totalLength", "completedLength", "downloadSpeed", "errorMessage"]),
+                    ]
+                )
             });
 
             // 单次状态检查带超时和重试
@@ -1162,7 +1514,7 @@ impl Aria2Downloader {
                     || async {
                         let response = self
                             .client
-                            .post(&url)
+                            .post(url)
                             .json(&payload)
                             .send()
                             .await
@@ -1210,12 +1562,14 @@ impl Aria2Downloader {
                     let total_length = result["totalLength"].as_str().unwrap_or("0");
                     let completed_length = result["completedLength"].as_str().unwrap_or("0");
                     let download_speed = result["downloadSpeed"].as_str().unwrap_or("0");
+                    let reported_total = total_length.parse::<u64>().ok().filter(|total| *total > 0);
 
                     if let (Ok(total), Ok(completed), Ok(speed)) = (
                         total_length.parse::<u64>(),
                         completed_length.parse::<u64>(),
                         download_speed.parse::<u64>(),
                     ) {
+                        progress.update(completed, Some(total));
                         let total_mb = total as f64 / 1_048_576.0;
                         let completed_mb = completed as f64 / 1_048_576.0;
                         let _speed_mb = speed as f64 / 1_048_576.0;
@@ -1236,7 +1590,7 @@ impl Aria2Downloader {
                             start_time.elapsed().as_secs_f64()
                         );
                     }
-                    return Ok(());
+                    return Ok(reported_total);
                 }
                 "error" => {
                     let error_msg = result["errorMessage"].as_str().unwrap_or("Unknown error");
@@ -1261,7 +1615,7 @@ impl Aria2Downloader {
                 }
                 "active" => {
                     // 优化：动态调整检查间隔和进度监控
-                    let _total_length = result["totalLength"]
+                    let total_length = result["totalLength"]
                         .as_str()
                         .unwrap_or("0")
                         .parse::<u64>()
@@ -1293,7 +1647,8 @@ impl Aria2Downloader {
                         last_completed_length = completed_length;
                     }
 
-                    // 不显示中间进度，只在完成时显示统计信息
+                    // 不在日志中显示中间进度，只上报给前端
+                    progress.update(completed_length, (total_length > 0).then_some(total_length));
 
                     // 动态调整检查间隔 - 适度增加间隔，减少RPC压力
                     check_interval = if download_speed > 5_242_880 {
@@ -1330,7 +1685,7 @@ impl Aria2Downloader {
     /// 智能下载：对于多进程aria2，直接使用aria2下载
     pub async fn smart_fetch(&self, url: &str, path: &Path) -> Result<()> {
         // 对于多进程aria2，直接使用aria2下载
-        self.fetch_with_aria2_fallback(&[url], path).await
+        self.fetch_with_aria2_fallback(&[url], path).await.map(|_| ())
     }
 
     /// 合并视频和音频文件
@@ -1368,18 +1723,21 @@ impl Aria2Downloader {
         let mut shutdown_futures = Vec::new();
 
         for (i, instance) in instances.iter_mut().enumerate() {
-            let rpc_port = instance.rpc_port;
+            // 外部aria2由用户自行管理，不关闭
+            let Some(process) = instance.process.as_mut() else {
+                continue;
+            };
+            let url = instance.rpc_url.clone();
             let rpc_secret = instance.rpc_secret.clone();
             let client = self.client.clone();
 
             // 尝试优雅关闭aria2实例
             let shutdown_future = async move {
-                let url = format!("http://127.0.0.1:{}/jsonrpc", rpc_port);
                 let payload = serde_json::json!({
                     "jsonrpc": "2.0",
                     "method": "aria2.shutdown",
                     "id": "shutdown",
-                    "params": [format!("token:{}", rpc_secret)]
+                    "params": rpc_params(&rpc_secret, vec![])
                 });
 
                 let _ = client.post(&url).json(&payload).send().await;
@@ -1389,13 +1747,13 @@ impl Aria2Downloader {
             shutdown_futures.push(shutdown_future);
 
             // 强制终止进程 - Windows兼容性改进
-            if let Err(e) = instance.process.kill().await {
+            if let Err(e) = process.kill().await {
                 warn!("终止aria2实例 {} 失败: {}", i + 1, e);
 
                 // 如果普通kill失败，尝试使用系统命令强制终止
                 #[cfg(target_os = "windows")]
                 {
-                    if let Some(pid) = instance.process.id() {
+                    if let Some(pid) = process.id() {
                         let _ = tokio::process::Command::new("taskkill")
                             .args(["/F", "/PID", &pid.to_string()])
                             .output()
@@ -1406,7 +1764,7 @@ impl Aria2Downloader {
 
                 #[cfg(target_os = "linux")]
                 {
-                    if let Some(pid) = instance.process.id() {
+                    if let Some(pid) = process.id() {
                         let _ = tokio::process::Command::new("kill")
                             .args(["-9", &pid.to_string()])
                             .output()
@@ -1417,7 +1775,7 @@ impl Aria2Downloader {
 
                 #[cfg(any(target_os = "macos", target_os = "ios"))]
                 {
-                    if let Some(pid) = instance.process.id() {
+                    if let Some(pid) = process.id() {
                         let _ = tokio::process::Command::new("kill")
                             .args(["-9", &pid.to_string()])
                             .output()
@@ -1436,8 +1794,10 @@ impl Aria2Downloader {
         instances.clear();
 
         // 最后再次确保所有aria2进程都被清理
-        tokio::time::sleep(Duration::from_millis(1000)).await;
-        Self::cleanup_all_aria2_processes().await;
+        if !self.external {
+            tokio::time::sleep(Duration::from_millis(1000)).await;
+            Self::cleanup_all_aria2_processes().await;
+        }
 
         info!("所有aria2实例已关闭");
         Ok(())
@@ -1478,6 +1838,7 @@ impl Aria2Downloader {
     ) -> Result<()> {
         let mut instances_guard = instances.lock().await;
         let mut unhealthy_indices = Vec::new();
+        let mut errors = Vec::new();
 
         // 检查每个实例的健康状态，避免在忙碌时进行RPC检查
         for (i, instance) in instances_guard.iter_mut().enumerate() {
@@ -1492,22 +1853,29 @@ impl Aria2Downloader {
             // 基础进程检查
             if !instance.is_healthy() {
                 warn!("aria2实例 {} 进程不健康，准备重启", i + 1);
+                errors.push(format!("{}: 进程已退出", instance.rpc_url));
                 unhealthy_indices.push(i);
                 continue;
             }
 
             // 对于空闲实例，进行RPC健康检查
-            let rpc_healthy = Self::check_instance_rpc_health(client, instance.rpc_port, &instance.rpc_secret).await;
-            if !rpc_healthy {
-                warn!("aria2实例 {} RPC连接不健康，准备重启", i + 1);
-                unhealthy_indices.push(i);
+            if let Err(e) = Self::check_instance_rpc_health(client, &instance.rpc_url, &instance.rpc_secret).await {
+                errors.push(format!("{}: {:#}", instance.rpc_url, e));
+                // 外部aria2无法由本程序重启，只记录错误，保留实例等待其恢复
+                if instance.is_external() {
+                    warn!("外部aria2 RPC连接不健康 ({}): {:#}", instance.rpc_url, e);
+                } else {
+                    warn!("aria2实例 {} RPC连接不健康，准备重启", i + 1);
+                    unhealthy_indices.push(i);
+                }
             }
         }
+        record_health_status(errors);
 
         // 移除不健康的实例
         for &index in unhealthy_indices.iter().rev() {
             let removed_instance = instances_guard.remove(index);
-            info!("移除不健康的aria2实例，端口: {}", removed_instance.rpc_port);
+            info!("移除不健康的aria2实例: {}", removed_instance.rpc_url);
         }
 
         let unhealthy_count = unhealthy_indices.len();
@@ -1547,8 +1915,13 @@ impl Aria2Downloader {
     }
 
     /// 检查实例的RPC健康状态
-    async fn check_instance_rpc_health(client: &crate::bilibili::Client, rpc_port: u16, rpc_secret: &str) -> bool {
+    async fn check_instance_rpc_health(
+        client: &crate::bilibili::Client,
+        rpc_url: &str,
+        rpc_secret: &str,
+    ) -> Result<()> {
         let client_clone = client.clone();
+        let rpc_url_clone = rpc_url.to_string();
         let rpc_secret_clone = rpc_secret.to_string();
 
         let result = Self::retry_with_backoff_static(
@@ -1559,28 +1932,32 @@ impl Aria2Downloader {
             Duration::from_secs(10), // 增加RPC健康检查超时时间到10秒
             move || {
                 let client = client_clone.clone();
+                let url = rpc_url_clone.clone();
                 let rpc_secret = rpc_secret_clone.clone();
                 async move {
-                    let url = format!("http://127.0.0.1:{}/jsonrpc", rpc_port);
                     let payload = serde_json::json!({
                         "jsonrpc": "2.0",
                         "method": "aria2.getVersion",
                         "id": "health_check",
-                        "params": [format!("token:{}", rpc_secret)]
+                        "params": rpc_params(&rpc_secret, vec![])
                     });
 
                     let response = client.post(&url).json(&payload).send().await?;
-                    if response.status().is_success() {
-                        Ok(())
-                    } else {
+                    if !response.status().is_success() {
                         bail!("RPC返回错误状态: {}", response.status())
                     }
+                    // 密钥错误时 aria2 仍返回 200，需要检查响应中的 error 字段
+                    let json: serde_json::Value = response.json().await?;
+                    if let Some(error) = json.get("error") {
+                        bail!("aria2 API错误: {}", error);
+                    }
+                    Ok(())
                 }
             },
         )
         .await;
 
-        result.is_ok()
+        result
     }
 
     /// 创建缺失的实例（用于监控任务自动恢复）
@@ -1594,15 +1971,20 @@ impl Aria2Downloader {
         let aria2_binary_path = Self::extract_aria2_binary().await?;
         let temp_downloader = Self::create_temp_downloader(aria2_binary_path).await?;
 
-        let process = temp_downloader.start_single_instance(rpc_port, &rpc_secret).await?;
-        let instance = Aria2Instance::new(process, rpc_port, rpc_secret.clone());
+        // 恢复出的实例不加载会话文件，避免与仍在运行的实例写入同一个会话文件
+        let process = temp_downloader
+            .start_single_instance(rpc_port, &rpc_secret, None)
+            .await?;
+        let instance = Aria2Instance::new(Some(process), local_rpc_url(rpc_port), rpc_secret.clone());
 
         // 等待RPC服务启动
         info!("等待新aria2实例RPC服务启动...");
         tokio::time::sleep(Duration::from_secs(3)).await;
 
         // 验证连接
-        temp_downloader.test_instance_connection(rpc_port, &rpc_secret).await?;
+        temp_downloader
+            .test_instance_connection(&instance.rpc_url, &rpc_secret)
+            .await?;
 
         info!("新aria2实例创建成功，端口: {}", rpc_port);
         Ok(instance)
@@ -1616,6 +1998,7 @@ impl Aria2Downloader {
             aria2_instances: Arc::new(Mutex::new(Vec::new())),
             aria2_binary_path,
             instance_count: 1,
+            external: false,
             next_instance_index: std::sync::atomic::AtomicUsize::new(0),
         })
     }
@@ -1852,17 +2235,17 @@ impl Aria2Downloader {
 
     /// 获取所有实例的状态信息
     #[allow(dead_code)]
-    pub async fn get_instances_status(&self) -> Vec<(u16, String, usize, bool)> {
+    pub async fn get_instances_status(&self) -> Vec<(String, String, usize, bool)> {
         let mut instances = self.aria2_instances.lock().await;
         let mut status_list = Vec::new();
 
         for instance in instances.iter_mut() {
-            let port = instance.rpc_port;
+            let rpc_url = instance.rpc_url.clone();
             let secret = instance.rpc_secret.clone();
             let load = instance.get_load();
             let healthy = instance.is_healthy();
 
-            status_list.push((port, secret, load, healthy));
+            status_list.push((rpc_url, secret, load, healthy));
         }
 
         status_list
@@ -1903,3 +2286,19 @@ impl Drop for Aria2Downloader {
         // 如果后续需要清理，可在显式卸载/清理路径中处理，而不是在Drop中
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_params() {
+        assert_eq!(
+            rpc_params("secret", vec![serde_json::json!("gid")]),
+            serde_json::json!(["token:secret", "gid"])
+        );
+        // 未设置密钥时不携带 token 参数
+        assert_eq!(rpc_params("", vec![]), serde_json::json!([]));
+        assert_eq!(local_rpc_url(6800), "http://127.0.0.1:6800/jsonrpc");
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -37,6 +37,9 @@ pub struct UserInfo {
     pub avatar_url: String,
 }
 
+/// 扫码会话的保留时长，超过后会被清理（二维码本身 3 分钟过期）
+const SESSION_RETENTION: Duration = Duration::from_secs(300);
+
 #[derive(Debug)]
 struct QRSession {
     qrcode_key: String,
@@ -45,6 +48,49 @@ struct QRSession {
     status: LoginStatus,
 }
 
+/// 登录凭证相关的 Cookie 键名
+const CREDENTIAL_COOKIE_KEYS: [&str; 6] = [
+    "SESSDATA",
+    "bili_jct",
+    "DedeUserID",
+    "DedeUserID__ckMd5",
+    "buvid3",
+    "buvid4",
+];
+
+fn ensure_required_cookies(cookies: &HashMap<String, String>) -> Result<()> {
+    let required_cookies = ["SESSDATA", "bili_jct", "DedeUserID"];
+    for &required in &required_cookies {
+        if !cookies.contains_key(required) {
+            return Err(anyhow::anyhow!("Missing required cookie: {}", required));
+        }
+    }
+    Ok(())
+}
+
+/// 取出必需的 Cookie，缺失时返回错误而不是 panic
+fn required_cookie(cookies: &HashMap<String, String>, key: &str) -> Result<String> {
+    cookies
+        .get(key)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Missing required cookie: {}", key))
+}
+
+/// 解析浏览器中复制的 Cookie 字符串（形如 `a=1; b=2`），只保留登录凭证相关的键
+pub fn parse_cookie_string(cookie_str: &str) -> Result<HashMap<String, String>> {
+    let cookies: HashMap<String, String> = cookie_str
+        .trim()
+        .trim_start_matches("Cookie:")
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+        .filter(|(key, value)| CREDENTIAL_COOKIE_KEYS.contains(key) && !value.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    ensure_required_cookies(&cookies)?;
+    Ok(cookies)
+}
+
 /// 扫码登录服务
 pub struct QRLoginService {
     client: Client,
@@ -53,8 +99,11 @@ pub struct QRLoginService {
 
 impl QRLoginService {
     pub fn new() -> Self {
-        let client = Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        // 扫码登录同样需要走全局代理
+        let client = crate::bilibili::apply_proxy(Client::builder())
+            .user_agent(crate::http::headers::user_agent_or(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            ))
             .cookie_store(true)
             .build()
             .unwrap_or_else(|_| Client::new());
@@ -69,6 +118,9 @@ impl QRLoginService {
     pub async fn generate_qr_code(&self) -> Result<(String, QRCodeInfo)> {
         tracing::info!("开始调用B站API生成二维码");
 
+        // 会话只在这里创建，顺便清理过期会话，避免会话表无限增长
+        self.cleanup_expired_sessions().await;
+
         // 首先访问B站主页获取 buvid3
         let homepage_url = "https://www.bilibili.com";
         tracing::debug!("发起B站主页访问请求: {}", homepage_url);
@@ -224,7 +276,10 @@ impl QRLoginService {
         match data["data"]["code"].as_i64() {
             Some(0) => {
                 // 登录成功，提取凭证
-                let cookies = self.extract_cookies_from_headers(&headers)?;
+                let cookies = match self.login_cookies(&headers) {
+                    Ok(cookies) => cookies,
+                    Err(status) => return Ok(status),
+                };
 
                 // 从登录响应中提取 buvid3 和 buvid4
                 let mut buvid3 = cookies.get("buvid3").cloned().unwrap_or_default();
@@ -283,10 +338,10 @@ impl QRLoginService {
                 }
 
                 let credential = Credential {
-                    sessdata: cookies.get("SESSDATA").unwrap().clone(),
-                    bili_jct: cookies.get("bili_jct").unwrap().clone(),
+                    sessdata: required_cookie(&cookies, "SESSDATA")?,
+                    bili_jct: required_cookie(&cookies, "bili_jct")?,
                     buvid3: buvid3.clone(),
-                    dedeuserid: cookies.get("DedeUserID").unwrap().clone(),
+                    dedeuserid: required_cookie(&cookies, "DedeUserID")?,
                     ac_time_value: data["data"]["refresh_token"].as_str().unwrap_or("").to_string(),
                     buvid4,
                     dedeuserid_ckmd5: cookies.get("DedeUserID__ckMd5").cloned(),
@@ -305,6 +360,17 @@ impl QRLoginService {
         }
     }
 
+    /// 提取登录成功响应中的凭证 Cookie；缺少必要 Cookie 时按登录失败处理，避免请求直接报错
+    fn login_cookies(
+        &self,
+        headers: &reqwest::header::HeaderMap,
+    ) -> std::result::Result<HashMap<String, String>, LoginStatus> {
+        self.extract_cookies_from_headers(headers).map_err(|e| {
+            tracing::error!("登录响应中的 Cookie 不完整: {}", e);
+            LoginStatus::Error(format!("登录响应异常: {}", e))
+        })
+    }
+
     /// 从响应头中提取Cookie
     fn extract_cookies_from_headers(&self, headers: &reqwest::header::HeaderMap) -> Result<HashMap<String, String>> {
         let mut cookies = HashMap::new();
@@ -318,16 +384,7 @@ impl QRLoginService {
                         let key = key.trim();
                         let value = value.trim();
 
-                        if [
-                            "SESSDATA",
-                            "bili_jct",
-                            "DedeUserID",
-                            "DedeUserID__ckMd5",
-                            "buvid3",
-                            "buvid4",
-                        ]
-                        .contains(&key)
-                        {
+                        if CREDENTIAL_COOKIE_KEYS.contains(&key) {
                             cookies.insert(key.to_string(), value.to_string());
                         }
                     }
@@ -336,14 +393,59 @@ impl QRLoginService {
         }
 
         // 确保必要的cookie都存在
-        let required_cookies = ["SESSDATA", "bili_jct", "DedeUserID"];
-        for &required in &required_cookies {
-            if !cookies.contains_key(required) {
-                return Err(anyhow::anyhow!("Missing required cookie: {}", required));
+        ensure_required_cookies(&cookies)?;
+
+        Ok(cookies)
+    }
+
+    /// 使用浏览器中复制的 Cookie 字符串登录，校验通过后返回凭证与用户信息
+    pub async fn import_cookie(&self, cookie_str: &str) -> Result<LoginResult> {
+        let cookies = parse_cookie_string(cookie_str)?;
+        let user_info = self
+            .get_user_info(&cookies)
+            .await
+            .map_err(|e| anyhow::anyhow!("Cookie 校验失败，可能已过期: {}", e))?;
+
+        let dedeuserid = required_cookie(&cookies, "DedeUserID")?;
+        if user_info.user_id != dedeuserid {
+            return Err(anyhow::anyhow!(
+                "Cookie 中的 DedeUserID({}) 与登录账号({})不一致",
+                dedeuserid,
+                user_info.user_id
+            ));
+        }
+
+        let mut buvid3 = cookies.get("buvid3").cloned().unwrap_or_default();
+        let mut buvid4 = cookies.get("buvid4").cloned();
+        if buvid3.is_empty() || buvid4.is_none() {
+            match self.generate_buvids().await {
+                Ok((new_buvid3, new_buvid4)) => {
+                    if buvid3.is_empty() {
+                        buvid3 = new_buvid3;
+                        tracing::debug!("Cookie 中缺少 buvid3，已生成新的 buvid3");
+                    }
+                    if buvid4.is_none() {
+                        buvid4 = new_buvid4;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("生成 buvids 失败: {}", e);
+                }
             }
         }
 
-        Ok(cookies)
+        let credential = Credential {
+            sessdata: required_cookie(&cookies, "SESSDATA")?,
+            bili_jct: required_cookie(&cookies, "bili_jct")?,
+            buvid3,
+            dedeuserid,
+            // 浏览器 Cookie 中没有 refresh_token，无法自动刷新凭证
+            ac_time_value: String::new(),
+            buvid4,
+            dedeuserid_ckmd5: cookies.get("DedeUserID__ckMd5").cloned(),
+        };
+
+        Ok(LoginResult { credential, user_info })
     }
 
     /// 获取用户信息
@@ -380,12 +482,19 @@ impl QRLoginService {
     }
 
     /// 清理过期会话
-    #[allow(dead_code)]
     pub async fn cleanup_expired_sessions(&self) {
+        self.cleanup_sessions_at(Instant::now()).await;
+    }
+
+    /// 清理截至 `now` 已超过保留时长的会话
+    async fn cleanup_sessions_at(&self, now: Instant) {
         let mut sessions = self.sessions.write().await;
-        sessions.retain(|_, session| {
-            session.created_at.elapsed().as_secs() < 300 // 5分钟
-        });
+        let before = sessions.len();
+        sessions.retain(|_, session| now.saturating_duration_since(session.created_at) < SESSION_RETENTION);
+        let removed = before - sessions.len();
+        if removed > 0 {
+            tracing::debug!("已清理 {} 个过期的扫码登录会话", removed);
+        }
     }
 
     /// 生成 buvid3 和 buvid4
@@ -442,3 +551,70 @@ impl Default for QRLoginService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cookie_string() {
+        let cookies = parse_cookie_string(
+            "Cookie: buvid3=abc; SESSDATA=x%2Cy; bili_jct=jct ;DedeUserID=123; DedeUserID__ckMd5=md5; other=1; buvid4=",
+        )
+        .unwrap();
+        assert_eq!(cookies.len(), 5);
+        assert_eq!(cookies["SESSDATA"], "x%2Cy");
+        assert_eq!(cookies["bili_jct"], "jct");
+        assert_eq!(cookies["DedeUserID"], "123");
+        assert!(!cookies.contains_key("buvid4"));
+        assert!(!cookies.contains_key("other"));
+
+        assert!(parse_cookie_string("SESSDATA=x; DedeUserID=123").is_err());
+        assert!(parse_cookie_string("").is_err());
+    }
+
+    #[test]
+    fn test_extract_cookies_missing_bili_jct() {
+        use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+
+        let mut headers = HeaderMap::new();
+        headers.append(SET_COOKIE, HeaderValue::from_static("SESSDATA=x%2Cy; Path=/; HttpOnly"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("DedeUserID=123; Path=/"));
+        let service = QRLoginService::new();
+        let err = service.extract_cookies_from_headers(&headers).unwrap_err();
+        assert!(err.to_string().contains("bili_jct"));
+
+        // 登录轮询中 Cookie 不完整时返回登录失败状态
+        match service.login_cookies(&headers) {
+            Err(LoginStatus::Error(msg)) => assert!(msg.contains("登录响应异常") && msg.contains("bili_jct")),
+            other => panic!("unexpected login cookies result: {:?}", other),
+        }
+
+        headers.append(SET_COOKIE, HeaderValue::from_static("bili_jct=jct; Path=/"));
+        assert!(service.login_cookies(&headers).is_ok());
+        let cookies = service.extract_cookies_from_headers(&headers).unwrap();
+        assert_eq!(required_cookie(&cookies, "bili_jct").unwrap(), "jct");
+        assert!(required_cookie(&cookies, "buvid3").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_expired_sessions() {
+        let service = QRLoginService::new();
+        let start = Instant::now();
+        let session = |offset: Duration| QRSession {
+            qrcode_key: String::new(),
+            created_at: start + offset,
+            status: LoginStatus::Pending,
+        };
+        {
+            let mut sessions = service.sessions.write().await;
+            sessions.insert("stale".to_string(), session(Duration::ZERO));
+            sessions.insert("fresh".to_string(), session(Duration::from_secs(291)));
+        }
+        // 以创建后 301 秒作为当前时间，避免依赖系统已运行的时长
+        service.cleanup_sessions_at(start + Duration::from_secs(301)).await;
+        let sessions = service.sessions.read().await;
+        assert!(!sessions.contains_key("stale"));
+        assert!(sessions.contains_key("fresh"));
+    }
+}
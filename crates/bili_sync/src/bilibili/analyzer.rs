@@ -120,6 +120,21 @@ pub struct FilterOption {
     pub no_dolby_audio: bool,
     pub no_hdr: bool,
     pub no_hires: bool,
+    /// 视频总时长下限（秒），None 或 0 表示不限制
+    #[serde(default)]
+    pub min_duration_secs: Option<u32>,
+    /// 视频总时长上限（秒），None 或 0 表示不限制
+    #[serde(default)]
+    pub max_duration_secs: Option<u32>,
+}
+
+impl FilterOption {
+    /// 判断视频时长是否在允许下载的范围内
+    pub fn duration_in_range(&self, duration_secs: u32) -> bool {
+        let min = self.min_duration_secs.filter(|min| *min > 0);
+        let max = self.max_duration_secs.filter(|max| *max > 0);
+        min.is_none_or(|min| duration_secs >= min) && max.is_none_or(|max| duration_secs <= max)
+    }
 }
 
 impl Default for FilterOption {
@@ -134,6 +149,8 @@ impl Default for FilterOption {
             no_dolby_audio: false,
             no_hdr: false,
             no_hires: false,
+            min_duration_secs: None,
+            max_duration_secs: None,
         }
     }
 }
@@ -144,12 +161,21 @@ pub struct FlvSegment {
     pub urls: Vec<String>,
     pub length: Option<u64>,
     pub size: Option<u64>,
+    pub md5: Option<String>,
 }
 
 impl FlvSegment {
     fn primary_urls(&self) -> Vec<&str> {
         self.urls.iter().map(String::as_str).collect()
     }
+
+    /// 分段的预期大小与MD5，用于下载完成后的完整性校验
+    pub fn expected_file(&self) -> crate::unified_downloader::ExpectedFile {
+        crate::unified_downloader::ExpectedFile {
+            size: self.size,
+            md5: self.md5.clone(),
+        }
+    }
 }
 
 // 上游项目中的五种流类型，不过目测应该只有 Flv、DashVideo、DashAudio 三种会被用到
@@ -294,6 +320,11 @@ impl PageAnalyzer {
                 urls,
                 length: segment.get("length").and_then(|v| v.as_u64()),
                 size: segment.get("size").and_then(|v| v.as_u64()),
+                md5: segment
+                    .get("md5")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string),
             });
         }
 
@@ -652,6 +683,27 @@ impl PageAnalyzer {
             }),
         })
     }
+
+    /// 仅音频模式下选择音频流，优先使用最佳的独立音频流，没有独立音频流时返回混合流，由 FFmpeg 从中提取音轨
+    pub fn best_audio_stream(&mut self, filter_option: &FilterOption) -> Result<Stream> {
+        let mut streams = self.streams(filter_option)?;
+        let best_audio = streams
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, s)| match s {
+                Stream::DashAudio { quality, .. } => Some((idx, *quality)),
+                _ => None,
+            })
+            .max_by_key(|(_, quality)| *quality)
+            .map(|(idx, _)| idx);
+        match best_audio {
+            Some(idx) => Ok(streams.swap_remove(idx)),
+            None => streams
+                .into_iter()
+                .find(|s| !matches!(s, Stream::DashVideo { .. }))
+                .context("no audio stream found"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -685,6 +737,23 @@ mod tests {
         assert_eq!(VideoQuality::Quality8k.to_string(), "8K超高清");
     }
 
+    #[test]
+    fn test_duration_in_range() {
+        let mut filter_option = FilterOption::default();
+        assert!(filter_option.duration_in_range(0));
+        assert!(filter_option.duration_in_range(36000));
+
+        filter_option.min_duration_secs = Some(60);
+        filter_option.max_duration_secs = Some(0);
+        assert!(!filter_option.duration_in_range(59));
+        assert!(filter_option.duration_in_range(60));
+        assert!(filter_option.duration_in_range(36000));
+
+        filter_option.max_duration_secs = Some(3600);
+        assert!(filter_option.duration_in_range(3600));
+        assert!(!filter_option.duration_in_range(3601));
+    }
+
     #[test]
     fn test_audio_quality_order() {
         assert!(AudioQuality::Quality64k < AudioQuality::Quality132k);
@@ -694,6 +763,33 @@ mod tests {
         assert!(AudioQuality::QualityDolby < AudioQuality::QualityHiRES);
     }
 
+    #[test]
+    fn test_best_audio_stream() {
+        let mut analyzer = PageAnalyzer::new(serde_json::json!({
+            "dash": {
+                "video": [{ "base_url": "https://upos-xxx.com/video.m4s", "id": 80, "codecid": 7 }],
+                "audio": [
+                    { "base_url": "https://upos-xxx.com/64k.m4s", "id": 30216 },
+                    { "base_url": "https://upos-xxx.com/192k.m4s", "id": 30280 }
+                ]
+            }
+        }));
+        let stream = analyzer.best_audio_stream(&FilterOption::default()).unwrap();
+        assert!(matches!(
+            stream,
+            Stream::DashAudio {
+                quality: AudioQuality::Quality192k,
+                ..
+            }
+        ));
+
+        // 无声视频没有可用的音频流
+        let mut analyzer = PageAnalyzer::new(serde_json::json!({
+            "dash": { "video": [{ "base_url": "https://upos-xxx.com/video.m4s", "id": 80, "codecid": 7 }] }
+        }));
+        assert!(analyzer.best_audio_stream(&FilterOption::default()).is_err());
+    }
+
     #[test]
     fn test_url_sort() {
         let urls = vec![
@@ -16,6 +16,39 @@ fn is_preview_episode(episode: &serde_json::Value) -> bool {
     episode["section_type"].as_i64().unwrap_or(0) == 1
 }
 
+/// 判断分集是否为特别篇（SP/OVA/PV 等），返回判定依据，正片返回 None
+/// 依次检查分区类型（section_type 非 0 即非正片分区）、分集标题与角标中的特别篇标识
+fn special_episode_reason(episode: &serde_json::Value) -> Option<String> {
+    let section_type = episode["section_type"].as_i64().unwrap_or(0);
+    if section_type != 0 {
+        return Some(format!("非正片分区(section_type={})", section_type));
+    }
+    const MARKERS: [&str; 5] = ["SP", "OVA", "OAD", "PV", "CM"];
+    const KEYWORDS: [&str; 4] = ["特别篇", "番外", "总集篇", "预告"];
+    let candidates = [
+        ("标题", episode["title"].as_str()),
+        ("角标", episode["badge"].as_str()),
+        ("角标", episode["badge_info"]["text"].as_str()),
+    ];
+    for (field, text) in candidates {
+        let Some(text) = text.map(str::trim).filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        let upper = text.to_uppercase();
+        let marker = MARKERS.iter().find(|marker| {
+            // 标识后不能紧跟字母，避免误判以这些字母开头的普通标题
+            upper.starts_with(*marker) && !upper[marker.len()..].starts_with(|c: char| c.is_ascii_alphabetic())
+        });
+        if let Some(marker) = marker {
+            return Some(format!("{}「{}」含特别篇标识 {}", field, text, marker));
+        }
+        if let Some(keyword) = KEYWORDS.iter().find(|keyword| text.contains(*keyword)) {
+            return Some(format!("{}「{}」含关键词 {}", field, text, keyword));
+        }
+    }
+    None
+}
+
 /// 智能集数分配算法，解决特殊剧集集数冲突问题
 ///
 /// 该函数处理B站API返回的剧集数据，为每个剧集分配唯一的集数：
@@ -67,6 +100,74 @@ fn assign_episode_numbers(episodes: &[serde_json::Value]) -> std::collections::H
     episode_assignments
 }
 
+/// 片头/片尾区间，单位为秒
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkipInterval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl SkipInterval {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let start = value["start"].as_u64()?;
+        let end = value["end"].as_u64()?;
+        // 接口对没有片头/片尾的分集会返回 start = end = 0
+        (end > start).then_some(Self { start, end })
+    }
+}
+
+/// 从番剧详情中解析各分集的跳过信息，只保留有跳过信息的分集
+/// 正片在 episodes 中，花絮等附加内容在 section[].episodes 中
+fn season_episode_skips(result: &serde_json::Value) -> std::collections::HashMap<String, EpisodeSkip> {
+    let section_episodes = result["section"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|section| section["episodes"].as_array())
+        .flatten();
+    result["episodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(section_episodes)
+        .filter_map(|episode| {
+            let ep_id = episode["id"].as_i64()?;
+            let skip = EpisodeSkip::from_episode(episode);
+            (!skip.is_empty()).then(|| (ep_id.to_string(), skip))
+        })
+        .collect()
+}
+
+/// 番剧分集的片头/片尾跳过信息
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EpisodeSkip {
+    pub op: Option<SkipInterval>,
+    pub ed: Option<SkipInterval>,
+}
+
+impl EpisodeSkip {
+    /// 从番剧详情接口 episodes 数组中的单集信息解析 skip 字段
+    pub fn from_episode(episode: &serde_json::Value) -> Self {
+        Self {
+            op: SkipInterval::from_json(&episode["skip"]["op"]),
+            ed: SkipInterval::from_json(&episode["skip"]["ed"]),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.op.is_none() && self.ed.is_none()
+    }
+
+    /// 生成 EDL 内容，每行为 `开始 结束 动作`，动作 3 表示可跳过的片段，Jellyfin/Kodi 均可识别
+    pub fn to_edl(&self) -> String {
+        [self.op, self.ed]
+            .into_iter()
+            .flatten()
+            .map(|interval| format!("{}\t{}\t3\n", interval.start, interval.end))
+            .collect()
+    }
+}
+
 pub struct Bangumi {
     client: BiliClient,
     media_id: Option<String>,
@@ -150,6 +251,23 @@ impl Bangumi {
         json.validate().map(|v| v["result"].clone())
     }
 
+    /// 获取整季各分集的片头/片尾跳过信息（以 ep_id 为键），同一季的分集只需请求一次详情接口
+    /// 优先按 season_id 请求，未提供时按 ep_id 请求其所在的季度
+    pub async fn get_season_skips(&self) -> Result<std::collections::HashMap<String, EpisodeSkip>> {
+        let query = if let Some(season_id) = &self.season_id {
+            format!("season_id={}", season_id)
+        } else if let Some(ep_id) = &self.ep_id {
+            format!("ep_id={}", ep_id)
+        } else {
+            bail!("season_id or ep_id is required");
+        };
+        let url = format!("https://api.bilibili.com/pgc/view/web/season?{}", query);
+        let resp = self.client.get(&url, CancellationToken::new()).await?;
+        let json: serde_json::Value = resp.json().await?;
+        let validated = json.validate()?;
+        Ok(season_episode_skips(&validated["result"]))
+    }
+
     /// 轻量级检查番剧是否有更新
     /// 返回 (是否有更新, 最新剧集时间)
     pub async fn check_update(&self, last_check_time: Option<DateTime<Utc>>) -> Result<(bool, Option<DateTime<Utc>>)> {
@@ -308,6 +426,7 @@ impl Bangumi {
             let mut new_episodes = 0;
             let mut skipped_episodes = 0;
             let mut preview_episodes = 0;
+            let mut special_episodes = 0;
 
             // 直接从 season_info 中解析分集信息，避免重复API调用
             let episodes = season_info["episodes"]
@@ -334,6 +453,19 @@ impl Bangumi {
                     continue;
                 }
 
+                // 检查是否为特别篇（SP/OVA/PV 等）并跳过
+                if config.bangumi_skip_specials {
+                    if let Some(reason) = special_episode_reason(episode) {
+                        let show_title = episode["show_title"].as_str().unwrap_or_default();
+                        special_episodes += 1;
+                        tracing::info!(
+                            "跳过特别篇：{}，依据: {}，show_season_type: {:?}",
+                            show_title, reason, show_season_type
+                        );
+                        continue;
+                    }
+                }
+
                 // 解析分集信息
                 let ep_id = episode["id"].as_i64().unwrap_or_default();
                 let aid = episode["aid"].as_i64().unwrap_or_default();
@@ -445,6 +577,9 @@ impl Bangumi {
             }
 
             // 输出统计信息
+            if special_episodes > 0 {
+                tracing::info!("单季度番剧「{}」跳过 {} 个特别篇", title, special_episodes);
+            }
             if latest_row_at.is_some() {
                 if preview_episodes > 0 {
                     tracing::info!(
@@ -497,6 +632,7 @@ impl Bangumi {
             let mut new_episodes = 0;
             let mut skipped_episodes = 0;
             let mut preview_episodes = 0;
+            let mut special_episodes = 0;
 
             // 对每个季度进行处理
             for (season_index, season) in seasons.iter().enumerate() {
@@ -541,6 +677,19 @@ impl Bangumi {
                         continue;
                     }
 
+                    // 检查是否为特别篇（SP/OVA/PV 等）并跳过
+                    if config.bangumi_skip_specials {
+                        if let Some(reason) = special_episode_reason(episode) {
+                            let show_title = episode["show_title"].as_str().unwrap_or_default();
+                            special_episodes += 1;
+                            tracing::info!(
+                                "跳过特别篇：{}，依据: {}，show_season_type: {:?} - 季度: {}",
+                                show_title, reason, show_season_type, season.season_title
+                            );
+                            continue;
+                        }
+                    }
+
                     // 解析分集信息
                     let ep_id = episode["id"].as_i64().unwrap_or_default();
                     let aid = episode["aid"].as_i64().unwrap_or_default();
@@ -656,6 +805,9 @@ impl Bangumi {
                 }
             }
 
+            if special_episodes > 0 {
+                tracing::info!("所有季度番剧跳过 {} 个特别篇", special_episodes);
+            }
             if latest_row_at.is_some() {
                 if preview_episodes > 0 {
                     tracing::info!(
@@ -794,6 +946,7 @@ impl Bangumi {
             let mut new_episodes = 0;
             let mut skipped_episodes = 0;
             let mut preview_episodes = 0;
+            let mut special_episodes = 0;
 
             // 对每个选中的季度进行处理
             for (season_index, season) in seasons.iter().enumerate() {
@@ -850,6 +1003,19 @@ impl Bangumi {
                         continue;
                     }
 
+                    // 检查是否为特别篇（SP/OVA/PV 等）并跳过
+                    if config.bangumi_skip_specials {
+                        if let Some(reason) = special_episode_reason(episode) {
+                            let show_title = episode["show_title"].as_str().unwrap_or_default();
+                            special_episodes += 1;
+                            tracing::info!(
+                                "跳过特别篇：{}，依据: {}，show_season_type: {:?} - 选中季度: {}",
+                                show_title, reason, show_season_type, season.season_title
+                            );
+                            continue;
+                        }
+                    }
+
                     // 解析分集信息
                     let ep_id = episode["id"].as_i64().unwrap_or_default();
                     let aid = episode["aid"].as_i64().unwrap_or_default();
@@ -965,6 +1131,9 @@ impl Bangumi {
                 }
             }
 
+            if special_episodes > 0 {
+                tracing::info!("选中季度番剧跳过 {} 个特别篇", special_episodes);
+            }
             if latest_row_at.is_some() {
                 if preview_episodes > 0 {
                     tracing::info!(
@@ -1031,3 +1200,64 @@ impl Bangumi {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_episode_skip() {
+        let episode = serde_json::json!({
+            "id": 1,
+            "skip": {
+                "op": {"start": 0, "end": 90},
+                "ed": {"start": 1320, "end": 1410}
+            }
+        });
+        let skip = EpisodeSkip::from_episode(&episode);
+        assert_eq!(skip.op, Some(SkipInterval { start: 0, end: 90 }));
+        assert_eq!(skip.to_edl(), "0\t90\t3\n1320\t1410\t3\n");
+
+        let episode = serde_json::json!({
+            "id": 2,
+            "skip": {"op": {"start": 0, "end": 0}, "ed": {"start": 0, "end": 0}}
+        });
+        assert!(EpisodeSkip::from_episode(&episode).is_empty());
+        assert!(EpisodeSkip::from_episode(&serde_json::json!({"id": 3})).is_empty());
+    }
+
+    #[test]
+    fn test_season_episode_skips() {
+        let result = serde_json::json!({
+            "episodes": [
+                {"id": 1, "skip": {"op": {"start": 0, "end": 90}}},
+                {"id": 2}
+            ],
+            "section": [
+                {"episodes": [{"id": 3, "skip": {"ed": {"start": 100, "end": 120}}}]}
+            ]
+        });
+        let skips = season_episode_skips(&result);
+        assert_eq!(skips.len(), 2);
+        assert_eq!(skips["1"].op, Some(SkipInterval { start: 0, end: 90 }));
+        assert_eq!(skips["3"].ed, Some(SkipInterval { start: 100, end: 120 }));
+        assert!(!skips.contains_key("2"));
+    }
+
+    #[test]
+    fn test_special_episode_reason() {
+        let regular = serde_json::json!({"title": "12", "section_type": 0});
+        assert!(special_episode_reason(&regular).is_none());
+        let sp = serde_json::json!({"title": "SP1"});
+        assert!(special_episode_reason(&sp).is_some());
+        let ova = serde_json::json!({"title": "13", "badge_info": {"text": "OVA"}});
+        assert!(special_episode_reason(&ova).is_some());
+        let section = serde_json::json!({"title": "1", "section_type": 2});
+        assert!(special_episode_reason(&section).is_some());
+        let recap = serde_json::json!({"title": "总集篇"});
+        assert!(special_episode_reason(&recap).is_some());
+        // 以 SP 开头的普通英文标题不应被误判
+        let normal = serde_json::json!({"title": "Spring"});
+        assert!(special_episode_reason(&normal).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+use quick_xml::escape::escape;
+
+/// 视频看点（分段章节），时间单位为秒
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct ViewPoint {
+    pub from: u32,
+    pub to: u32,
+    pub content: String,
+}
+
+/// 从播放器接口（x/player/wbi/v2）返回的 data 中解析看点，没有看点时返回空列表
+pub fn view_points_from_player_info(player_info: &serde_json::Value) -> Vec<ViewPoint> {
+    let mut points: Vec<ViewPoint> = player_info["view_points"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|point| serde_json::from_value::<ViewPoint>(point.clone()).ok())
+        .filter(|point| point.to > point.from && !point.content.trim().is_empty())
+        .collect();
+    points.sort_by_key(|point| point.from);
+    points
+}
+
+fn chapter_time(seconds: u32) -> String {
+    format!("{:02}:{:02}:{:02}.000", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// 生成 Matroska 章节 XML，可被 mkvmerge、MKVToolNix 等工具直接读取
+pub fn to_chapters_xml(points: &[ViewPoint]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Chapters>\n  <EditionEntry>\n");
+    for point in points {
+        xml.push_str("    <ChapterAtom>\n");
+        xml.push_str(&format!(
+            "      <ChapterTimeStart>{}</ChapterTimeStart>\n",
+            chapter_time(point.from)
+        ));
+        xml.push_str(&format!(
+            "      <ChapterTimeEnd>{}</ChapterTimeEnd>\n",
+            chapter_time(point.to)
+        ));
+        xml.push_str("      <ChapterDisplay>\n");
+        xml.push_str(&format!(
+            "        <ChapterString>{}</ChapterString>\n",
+            escape(point.content.trim())
+        ));
+        xml.push_str("        <ChapterLanguage>chi</ChapterLanguage>\n");
+        xml.push_str("      </ChapterDisplay>\n");
+        xml.push_str("    </ChapterAtom>\n");
+    }
+    xml.push_str("  </EditionEntry>\n</Chapters>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_points_to_chapters() {
+        let player_info = serde_json::json!({
+            "view_points": [
+                {"type": 2, "from": 95, "to": 3725, "content": "正文 & 总结", "imgUrl": ""},
+                {"type": 2, "from": 0, "to": 95, "content": "开场"},
+                {"type": 2, "from": 10, "to": 10, "content": "无效"}
+            ]
+        });
+        let points = view_points_from_player_info(&player_info);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].content, "开场");
+
+        let xml = to_chapters_xml(&points);
+        assert!(xml.contains("<ChapterTimeStart>00:01:35.000</ChapterTimeStart>"));
+        assert!(xml.contains("<ChapterTimeEnd>01:02:05.000</ChapterTimeEnd>"));
+        assert!(xml.contains("<ChapterString>正文 &amp; 总结</ChapterString>"));
+
+        assert!(view_points_from_player_info(&serde_json::json!({"view_points": null})).is_empty());
+        assert!(view_points_from_player_info(&serde_json::json!({})).is_empty());
+    }
+}
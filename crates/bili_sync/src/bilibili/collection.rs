@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use async_stream::try_stream;
 use futures::Stream;
 use reqwest::Method;
@@ -14,6 +14,8 @@ use crate::bilibili::{BiliClient, Validate, VideoInfo, MIXIN_KEY};
 pub enum CollectionType {
     Series,
     Season,
+    // 通过导入 BV 号列表创建的手动列表，不对应任何B站接口
+    Manual,
 }
 
 impl From<CollectionType> for i32 {
@@ -21,6 +23,7 @@ impl From<CollectionType> for i32 {
         match v {
             CollectionType::Series => 1,
             CollectionType::Season => 2,
+            CollectionType::Manual => 3,
         }
     }
 }
@@ -30,6 +33,7 @@ impl From<i32> for CollectionType {
         match v {
             1 => CollectionType::Series,
             2 => CollectionType::Season,
+            3 => CollectionType::Manual,
             _ => panic!("invalid collection type"),
         }
     }
@@ -40,6 +44,7 @@ impl Display for CollectionType {
         let s = match self {
             CollectionType::Series => "列表",
             CollectionType::Season => "合集",
+            CollectionType::Manual => "手动列表",
         };
         write!(f, "{}", s)
     }
@@ -102,6 +107,7 @@ impl<'a> Collection<'a> {
             // 没有找到专门获取 Season 信息的接口，所以直接获取第一页，从里面取 meta 信息
             CollectionType::Season => self.get_videos(1).await?["data"]["meta"].take(),
             CollectionType::Series => self.get_series_info().await?["data"]["meta"].take(),
+            CollectionType::Manual => bail!("手动列表没有对应的B站合集信息"),
         };
         Ok(serde_json::from_value(meta)?)
     }
@@ -149,6 +155,7 @@ impl<'a> Collection<'a> {
                     MIXIN_KEY.load().as_deref(),
                 ),
             ),
+            CollectionType::Manual => bail!("手动列表没有对应的B站视频列表"),
         };
         self.client
             .request(Method::GET, url)
@@ -193,6 +200,7 @@ impl<'a> Collection<'a> {
                 let fields = match self.collection.collection_type {
                     CollectionType::Series => ["num", "size", "total"],
                     CollectionType::Season => ["page_num", "page_size", "total"],
+                    CollectionType::Manual => unreachable!("手动列表不会请求B站视频列表"),
                 };
                 let values = fields
                     .iter()
@@ -80,6 +80,32 @@ impl Credential {
         Ok(serde_json::from_value(res["data"]["wbi_img"].take())?)
     }
 
+    /// 从 SESSDATA 中解析过期时间（Unix 时间戳，秒）
+    ///
+    /// SESSDATA 形如 `xxx%2C1735660800%2Cabcd*11`，第二段即为过期时间
+    pub fn sessdata_expires_at(&self) -> Option<i64> {
+        self.sessdata
+            .cow_replace("%2C", ",")
+            .cow_replace("%2c", ",")
+            .split(',')
+            .nth(1)
+            .and_then(|ts| ts.parse::<i64>().ok())
+            .filter(|ts| *ts > 0)
+    }
+
+    /// 通过 nav 接口检查凭据当前是否处于登录状态
+    pub async fn is_login(&self, client: &Client) -> Result<bool> {
+        // 未登录时接口返回 -101，这里不做 validate，直接读取 isLogin
+        let res = client
+            .request(Method::GET, "https://api.bilibili.com/x/web-interface/nav", Some(self))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?;
+        Ok(res["data"]["isLogin"].as_bool().unwrap_or(false))
+    }
+
     /// 检查凭据是否有效
     pub async fn need_refresh(&self, client: &Client) -> Result<bool> {
         let res = client
@@ -304,6 +330,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sessdata_expires_at() {
+        let credential = |sessdata: &str| Credential {
+            sessdata: sessdata.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            credential("ab12cd34%2C1735660800%2C1f2e3*c1").sessdata_expires_at(),
+            Some(1735660800)
+        );
+        assert_eq!(
+            credential("ab12cd34,1735660800,1f2e3*c1").sessdata_expires_at(),
+            Some(1735660800)
+        );
+        assert_eq!(credential("ab12cd34").sessdata_expires_at(), None);
+        assert_eq!(credential("").sessdata_expires_at(), None);
+    }
+
     #[test]
     fn test_wbi_key() {
         let key = WbiImg {
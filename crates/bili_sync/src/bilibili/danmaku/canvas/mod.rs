@@ -1,6 +1,8 @@
 //! 决定绘画策略
 mod lane;
 
+use std::collections::VecDeque;
+
 use anyhow::Result;
 use float_ord::FloatOrd;
 use lane::Lane;
@@ -32,6 +34,28 @@ pub struct DanmakuOption {
     pub outline: f64,
     /// 时间轴偏移
     pub time_offset: f64,
+    /// 输出的弹幕格式，可选 ass、xml（B站原生格式）、json
+    #[serde(default = "default_danmaku_formats")]
+    pub danmaku_formats: Vec<String>,
+    /// 屏蔽词，包含任一屏蔽词的弹幕不会被输出
+    #[serde(default)]
+    pub danmaku_block_keywords: Vec<String>,
+    /// 是否将屏蔽词作为正则表达式匹配，默认为不区分大小写的子串匹配
+    #[serde(default)]
+    pub danmaku_block_regex: bool,
+    /// 任意一秒内最多绘制的弹幕数量，超出的弹幕会被丢弃（保留最早出现的），None 表示不限制
+    #[serde(default)]
+    pub danmaku_max_per_second: Option<u32>,
+    /// 排除的弹幕类型：1=滚动，4=底部，5=顶部，6=逆向，7=高级
+    #[serde(default)]
+    pub danmaku_exclude_modes: Vec<u8>,
+}
+
+/// 支持输出的弹幕格式
+pub const DANMAKU_FORMATS: [&str; 3] = ["ass", "xml", "json"];
+
+fn default_danmaku_formats() -> Vec<String> {
+    vec!["ass".to_string()]
 }
 
 impl Default for DanmakuOption {
@@ -49,6 +73,11 @@ impl Default for DanmakuOption {
             bold: true,
             outline: 0.8,
             time_offset: 0.0,
+            danmaku_formats: default_danmaku_formats(),
+            danmaku_block_keywords: Vec::new(),
+            danmaku_block_regex: false,
+            danmaku_max_per_second: None,
+            danmaku_exclude_modes: Vec::new(),
         }
     }
 }
@@ -93,6 +122,7 @@ impl CanvasConfig {
         Canvas {
             config: self,
             float_lanes: vec![None; float_lanes_cnt],
+            recent: VecDeque::new(),
         }
     }
 }
@@ -100,6 +130,8 @@ impl CanvasConfig {
 pub struct Canvas {
     pub config: CanvasConfig,
     pub float_lanes: Vec<Option<Lane>>,
+    /// 最近一秒内已绘制弹幕的出现时间，用于限制弹幕密度
+    recent: VecDeque<f64>,
 }
 
 impl Canvas {
@@ -108,15 +140,30 @@ impl Canvas {
         if danmu.timeline_s < 0.0 {
             return Ok(None);
         }
-        match danmu.r#type {
-            DanmuType::Float => Ok(self.draw_float(danmu)),
+        let max_per_second = self.config.danmaku_option.danmaku_max_per_second.filter(|max| *max > 0);
+        if let Some(max_per_second) = max_per_second {
+            // 弹幕按时间顺序输入，只需要移除已经滑出一秒窗口的记录
+            while self.recent.front().is_some_and(|t| danmu.timeline_s - t >= 1.0) {
+                self.recent.pop_front();
+            }
+            if self.recent.len() >= max_per_second as usize {
+                return Ok(None);
+            }
+        }
+        let timeline_s = danmu.timeline_s;
+        let drawable = match danmu.r#type {
+            DanmuType::Float => self.draw_float(danmu),
             DanmuType::Bottom | DanmuType::Top | DanmuType::Reverse => {
                 // 不喜欢底部弹幕，直接转成 Bottom
                 // 这是 feature 不是 bug
                 danmu.r#type = DanmuType::Float;
-                Ok(self.draw_float(danmu))
+                self.draw_float(danmu)
             }
+        };
+        if max_per_second.is_some() && drawable.is_some() {
+            self.recent.push_back(timeline_s);
         }
+        Ok(drawable)
     }
 
     fn draw_float(&mut self, mut danmu: Danmu) -> Option<Drawable> {
@@ -170,3 +217,43 @@ impl Canvas {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_danmaku_max_per_second() {
+        let option: &'static DanmakuOption = Box::leak(Box::new(DanmakuOption {
+            danmaku_max_per_second: Some(5),
+            ..Default::default()
+        }));
+        let mut canvas = CanvasConfig::new(option, &PageInfo::default()).canvas();
+        let danmu_at = |timeline_s: f64| Danmu {
+            timeline_s,
+            content: "测试弹幕".to_string(),
+            ..Default::default()
+        };
+
+        let mut lanes = Vec::new();
+        for i in 0..100 {
+            if let Some(drawable) = canvas.draw(danmu_at(10.0 + i as f64 * 0.001)).unwrap() {
+                lanes.push(drawable);
+            }
+        }
+        assert_eq!(lanes.len(), 5);
+        // 被丢弃的弹幕不占用轨道，保留的弹幕依次分配到不同轨道
+        let mut ys: Vec<i32> = lanes
+            .iter()
+            .map(|drawable| match drawable.effect {
+                DrawEffect::Move { start, .. } => start.1,
+            })
+            .collect();
+        ys.sort_unstable();
+        ys.dedup();
+        assert_eq!(ys.len(), 5);
+
+        // 超出一秒窗口后可以继续绘制
+        assert!(canvas.draw(danmu_at(11.5)).unwrap().is_some());
+    }
+}
@@ -0,0 +1,49 @@
+//! 按关键词屏蔽弹幕
+use crate::bilibili::danmaku::DanmakuOption;
+use crate::utils::keyword_matcher::{build_matchers, KeywordMatcher};
+
+pub struct DanmakuFilter {
+    matchers: Vec<KeywordMatcher>,
+}
+
+impl DanmakuFilter {
+    /// 根据配置构建过滤器，没有设置屏蔽词时返回 None
+    pub fn new(option: &DanmakuOption) -> Option<Self> {
+        let matchers = build_matchers(&option.danmaku_block_keywords, option.danmaku_block_regex, "弹幕屏蔽");
+        if matchers.is_empty() {
+            None
+        } else {
+            Some(Self { matchers })
+        }
+    }
+
+    pub fn is_blocked(&self, content: &str) -> bool {
+        let lowercase_content = content.to_lowercase();
+        self.matchers
+            .iter()
+            .any(|matcher| matcher.is_match(content, &lowercase_content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_danmaku_filter() {
+        let mut option = DanmakuOption::default();
+        assert!(DanmakuFilter::new(&option).is_none());
+
+        option.danmaku_block_keywords = vec!["剧透".to_string(), "HaHa".to_string(), " ".to_string()];
+        let filter = DanmakuFilter::new(&option).unwrap();
+        assert!(filter.is_blocked("前方剧透预警"));
+        assert!(filter.is_blocked("hahaha"));
+        assert!(!filter.is_blocked("正常弹幕"));
+
+        option.danmaku_block_regex = true;
+        option.danmaku_block_keywords = vec![r"^\d+$".to_string(), "(".to_string()];
+        let filter = DanmakuFilter::new(&option).unwrap();
+        assert!(filter.is_blocked("23333"));
+        assert!(!filter.is_blocked("2333 哈哈"));
+    }
+}
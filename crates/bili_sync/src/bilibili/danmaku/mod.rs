@@ -2,12 +2,15 @@ mod ass_writer;
 mod canvas;
 mod danmu;
 mod drawable;
+mod filter;
 mod model;
 mod writer;
+mod xml_writer;
 
 pub use ass_writer::AssWriter;
-pub use canvas::DanmakuOption;
+pub use canvas::{DanmakuOption, DANMAKU_FORMATS};
 pub use danmu::Danmu;
 pub use drawable::{DrawEffect, Drawable};
+pub use filter::DanmakuFilter;
 pub use model::{DanmakuElem, DmSegMobileReply};
 pub use writer::DanmakuWriter;
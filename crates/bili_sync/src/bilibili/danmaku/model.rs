@@ -6,7 +6,7 @@ use prost::Message;
 
 use crate::bilibili::danmaku::danmu::{Danmu, DanmuType};
 /// 弹幕 pb 定义
-#[derive(Clone, Message)]
+#[derive(Clone, Message, serde::Serialize)]
 pub struct DanmakuElem {
     /// 弹幕 dmid
     #[prost(int64, tag = "1")]
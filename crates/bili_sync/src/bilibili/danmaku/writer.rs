@@ -1,26 +1,58 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use tokio::fs::{self, File};
 
 use crate::bilibili::danmaku::canvas::{CanvasConfig, DanmakuOption};
-use crate::bilibili::danmaku::{AssWriter, Danmu};
+use crate::bilibili::danmaku::{xml_writer, AssWriter, DanmakuElem, DanmakuFilter, Danmu};
 use crate::bilibili::PageInfo;
 
 pub struct DanmakuWriter<'a> {
     page: &'a PageInfo,
-    danmaku: Vec<Danmu>,
+    danmaku: Vec<DanmakuElem>,
 }
 
 impl<'a> DanmakuWriter<'a> {
-    pub fn new(page: &'a PageInfo, danmaku: Vec<Danmu>) -> Self {
+    pub fn new(page: &'a PageInfo, danmaku: Vec<DanmakuElem>) -> Self {
         DanmakuWriter { page, danmaku }
     }
 
-    pub async fn write(self, path: PathBuf) -> Result<()> {
+    /// 按配置的弹幕格式输出，path 为 ass 文件路径，其他格式与其共用文件名仅替换扩展名
+    pub async fn write(mut self, path: PathBuf) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).await?;
         }
+        // 屏蔽词对所有输出格式生效
+        let total = self.danmaku.len();
+        if let Some(filter) = crate::config::with_config(|bundle| DanmakuFilter::new(&bundle.config.danmaku_option)) {
+            self.danmaku.retain(|elem| !filter.is_blocked(&elem.content));
+        }
+        let blocked = total - self.danmaku.len();
+        let formats = crate::config::with_config(|bundle| bundle.config.danmaku_option.danmaku_formats.clone());
+        for format in &formats {
+            match format.as_str() {
+                "ass" => self.write_ass(&path).await?,
+                "xml" => {
+                    let xml = xml_writer::to_xml(self.page.cid, &self.danmaku)?;
+                    fs::write(path.with_extension("xml"), xml).await?;
+                }
+                "json" => {
+                    let json = serde_json::to_vec_pretty(&self.danmaku)?;
+                    fs::write(path.with_extension("json"), json).await?;
+                }
+                other => warn!("不支持的弹幕格式: {}，已跳过", other),
+            }
+        }
+        debug!(
+            "弹幕转换完成，共{}条弹幕，屏蔽{}条，格式: {:?}",
+            self.danmaku.len(),
+            blocked,
+            formats
+        );
+        Ok(())
+    }
+
+    async fn write_ass(&self, path: &Path) -> Result<()> {
         // 使用 with_config 来访问配置
         let canvas_config = crate::config::with_config(|bundle| {
             // 需要克隆 DanmakuOption 以避免生命周期问题
@@ -32,7 +64,7 @@ impl<'a> DanmakuWriter<'a> {
         let mut writer =
             AssWriter::construct(File::create(path).await?, self.page.name.clone(), canvas_config.clone()).await?;
         let mut canvas = canvas_config.canvas();
-        for danmuku in self.danmaku {
+        for danmuku in self.danmaku.iter().cloned().map(Danmu::from) {
             if let Some(drawable) = canvas.draw(danmuku)? {
                 writer.write(drawable).await?;
             }
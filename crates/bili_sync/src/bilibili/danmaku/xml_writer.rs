@@ -0,0 +1,88 @@
+//! 将弹幕输出为B站原生的 XML 格式，方便存档和其他播放器重新渲染
+use anyhow::Result;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::writer::Writer;
+
+use crate::bilibili::danmaku::DanmakuElem;
+
+/// 弹幕的 p 属性：出现时间,类型,字号,颜色,发送时间戳,弹幕池,发送者hash,dmid,权重
+fn p_attribute(elem: &DanmakuElem) -> String {
+    let dmid = if elem.dmid_str.is_empty() {
+        elem.id.to_string()
+    } else {
+        elem.dmid_str.clone()
+    };
+    format!(
+        "{:.5},{},{},{},{},{},{},{},{}",
+        elem.progress as f64 / 1000.0,
+        elem.mode,
+        elem.fontsize,
+        elem.color,
+        elem.ctime,
+        elem.pool,
+        elem.mid_hash,
+        dmid,
+        elem.weight
+    )
+}
+
+/// 去掉 XML 中不允许出现的控制字符
+fn sanitize_content(content: &str) -> String {
+    content
+        .chars()
+        .filter(|ch| !ch.is_control() || matches!(ch, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+pub fn to_xml(cid: i64, danmaku: &[DanmakuElem]) -> Result<Vec<u8>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer.create_element("i").write_inner_content(|writer| {
+        writer
+            .create_element("chatserver")
+            .write_text_content(BytesText::new("chat.bilibili.com"))?;
+        writer
+            .create_element("chatid")
+            .write_text_content(BytesText::new(&cid.to_string()))?;
+        writer
+            .create_element("maxlimit")
+            .write_text_content(BytesText::new(&danmaku.len().to_string()))?;
+        for elem in danmaku {
+            writer
+                .create_element("d")
+                .with_attribute(("p", p_attribute(elem).as_str()))
+                .write_text_content(BytesText::new(&sanitize_content(&elem.content)))?;
+        }
+        Ok(())
+    })?;
+    Ok(writer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_xml() {
+        let elem = DanmakuElem {
+            id: 1,
+            progress: 2323,
+            mode: 1,
+            fontsize: 25,
+            color: 16777215,
+            mid_hash: "cbe1b5d4".to_string(),
+            content: "<前方高能>\u{8}".to_string(),
+            ctime: 1697962020,
+            weight: 11,
+            pool: 0,
+            dmid_str: "1420470669379174400".to_string(),
+            ..Default::default()
+        };
+        let xml = String::from_utf8(to_xml(123, &[elem]).unwrap()).unwrap();
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(xml.contains("<chatid>123</chatid>"));
+        assert!(xml.contains(
+            r#"<d p="2.32300,1,25,16777215,1697962020,0,cbe1b5d4,1420470669379174400,11">&lt;前方高能&gt;</d>"#
+        ));
+    }
+}
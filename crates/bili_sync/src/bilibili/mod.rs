@@ -1,16 +1,19 @@
 use std::sync::Arc;
 
-pub use analyzer::{AudioQuality, BestStream, FilterOption, FlvSegment, Stream, VideoCodecs, VideoQuality};
+pub use analyzer::{
+    AudioQuality, BestStream, FilterOption, FlvSegment, PageAnalyzer, Stream, VideoCodecs, VideoQuality,
+};
 use anyhow::{bail, ensure, Result};
 use arc_swap::ArcSwapOption;
 pub use captcha_server::{get_captcha_info, serve_captcha_page, submit_captcha_result};
 pub use captcha_solver::CaptchaSolver;
+pub use chapter::{to_chapters_xml, view_points_from_player_info};
 use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
-pub use client::{BiliClient, Client, SearchResult};
+pub use client::{apply_proxy, build_proxy, proxied_reqwest_client, BiliClient, Client, SearchResult};
 pub use collection::{Collection, CollectionItem, CollectionType};
 pub use credential::Credential;
-pub use danmaku::DanmakuOption;
+pub use danmaku::{DanmakuOption, DANMAKU_FORMATS};
 pub use error::BiliError;
 pub use favorite_list::FavoriteList;
 use favorite_list::Upper;
@@ -18,13 +21,14 @@ use once_cell::sync::Lazy;
 pub use risk_control::{CaptchaInfo, CaptchaResult, GeetestInfo, RiskControl};
 pub use submission::Submission;
 pub use verification_coordinator::{VerificationRequest, VERIFICATION_COORDINATOR};
-pub use video::{bvid_to_aid, Dimension, PageInfo, Video};
+pub use video::{bvid_to_aid, parse_bvid, Dimension, PageInfo, Video};
 pub use watch_later::WatchLater;
 pub mod bangumi;
 
 mod analyzer;
 mod captcha_server;
 mod captcha_solver;
+mod chapter;
 mod client;
 mod collection;
 mod credential;
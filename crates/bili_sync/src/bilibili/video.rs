@@ -743,14 +743,20 @@ impl<'a> Video<'a> {
             }
         }
 
+        // 跳过用户排除的弹幕类型，对所有输出格式生效
+        let exclude_modes =
+            crate::config::with_config(|bundle| bundle.config.danmaku_option.danmaku_exclude_modes.clone());
+        if !exclude_modes.is_empty() {
+            let before = all_danmaku.len();
+            all_danmaku.retain(|d| !exclude_modes.iter().any(|mode| i32::from(*mode) == d.mode));
+            debug!("按弹幕类型排除了{}条弹幕", before - all_danmaku.len());
+        }
+
         // 按时间排序
         all_danmaku.sort_by_key(|d| d.progress);
         debug!("弹幕获取完成，共{}条弹幕", all_danmaku.len());
 
-        Ok(DanmakuWriter::new(
-            page,
-            all_danmaku.into_iter().map(|x| x.into()).collect(),
-        ))
+        Ok(DanmakuWriter::new(page, all_danmaku))
     }
 
     /// 带重试机制的弹幕分段获取
@@ -1965,8 +1971,44 @@ impl<'a> Video<'a> {
         Ok(PageAnalyzer::new(validated_res["result"].take()))
     }
 
-    pub async fn get_subtitles(&self, page: &PageInfo) -> Result<Vec<SubTitle>> {
-        let res = self
+    /// 获取分页音视频合一的 MP4 流（durl），用于 .strm 等只能记录单个播放地址的场景
+    /// 合一流的清晰度一般低于 DASH 流，部分视频（如番剧、付费内容）可能不提供
+    pub async fn get_page_mixed_analyzer(&self, page: &PageInfo) -> Result<PageAnalyzer> {
+        let cid_string = page.cid.to_string();
+        let mut res = self
+            .client
+            .request(Method::GET, "https://api.bilibili.com/x/player/wbi/playurl")
+            .await
+            .query(&encoded_query(
+                vec![
+                    ("avid", self.aid.as_str()),
+                    ("cid", cid_string.as_str()),
+                    ("qn", "80"),
+                    ("otype", "json"),
+                    ("fnval", "1"), // MP4 格式，音视频合一
+                    ("platform", "html5"),
+                    ("high_quality", "1"),
+                ],
+                MIXIN_KEY.load().as_deref(),
+            ))
+            .headers(create_api_headers())
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?
+            .validate()?;
+        let data = res["data"].take();
+        ensure!(
+            data["durl"].as_array().is_some_and(|durl| durl.len() == 1),
+            "未找到音视频合一的播放地址"
+        );
+        Ok(PageAnalyzer::new(data))
+    }
+
+    /// 获取播放器接口返回的分页信息（data 字段），其中包含字幕列表与看点，字幕和章节共用同一次请求
+    pub async fn get_player_info(&self, page: &PageInfo) -> Result<serde_json::Value> {
+        let mut res = self
             .client
             .request(Method::GET, "https://api.bilibili.com/x/player/wbi/v2")
             .await
@@ -1980,9 +2022,18 @@ impl<'a> Video<'a> {
             .json::<serde_json::Value>()
             .await?
             .validate()?;
+        Ok(res["data"].take())
+    }
 
+    pub async fn get_subtitles(&self, page: &PageInfo) -> Result<Vec<SubTitle>> {
+        let player_info = self.get_player_info(page).await?;
+        self.get_subtitles_from(&player_info).await
+    }
+
+    /// 根据已获取的播放器信息下载字幕
+    pub async fn get_subtitles_from(&self, player_info: &serde_json::Value) -> Result<Vec<SubTitle>> {
         // 检查字幕数据是否存在
-        let subtitle_data = &res["data"]["subtitle"];
+        let subtitle_data = &player_info["subtitle"];
         if subtitle_data.is_null() {
             debug!("视频没有字幕数据");
             return Ok(Vec::new());
@@ -1998,16 +2049,23 @@ impl<'a> Video<'a> {
         let (ai_subtitles, regular_subtitles): (Vec<_>, Vec<_>) =
             subtitles_info.subtitles.into_iter().partition(|info| info.is_ai_sub());
 
-        if !ai_subtitles.is_empty() {
-            debug!("检测到 {} 条 AI 字幕", ai_subtitles.len());
+        // 有人工字幕时只下载人工字幕，AI 字幕仅作为没有人工字幕时的备选
+        let selected_subtitles = if regular_subtitles.is_empty() {
+            debug!("没有人工字幕，使用 {} 条 AI 字幕", ai_subtitles.len());
             let has_credential = with_config(|bundle| bundle.config.credential.load().is_some());
             if !has_credential {
                 warn!("检测到 AI 字幕，但当前缺少登录凭据（SESSDATA），字幕下载可能失败");
             }
-        }
+            ai_subtitles
+        } else {
+            if !ai_subtitles.is_empty() {
+                debug!("存在人工字幕，跳过 {} 条 AI 字幕", ai_subtitles.len());
+            }
+            regular_subtitles
+        };
 
-        let ordered_subtitles = ai_subtitles.into_iter().chain(regular_subtitles.into_iter());
-        let tasks = ordered_subtitles
+        let tasks = selected_subtitles
+            .into_iter()
             .map(|info| self.get_subtitle(info))
             .collect::<FuturesUnordered<_>>();
         tasks.try_collect().await
@@ -2253,6 +2311,18 @@ mod tests {
         assert_eq!(super::bvid_to_aid("BV1Tr421n746"), 1401752220u64);
         assert_eq!(super::bvid_to_aid("BV1sH4y1s7fe"), 1051892992u64);
     }
+
+    #[test]
+    fn test_parse_bvid() {
+        assert_eq!(super::parse_bvid("BV1Tr421n746").as_deref(), Some("BV1Tr421n746"));
+        assert_eq!(
+            super::parse_bvid("https://www.bilibili.com/video/BV1sH4y1s7fe/?p=2").as_deref(),
+            Some("BV1sH4y1s7fe")
+        );
+        assert_eq!(super::parse_bvid("BV1sH4y1s7"), None);
+        assert_eq!(super::parse_bvid("BV1sH4y1s7fe0"), None);
+        assert_eq!(super::parse_bvid("av170001"), None);
+    }
 }
 
 pub fn bvid_to_aid(bvid: &str) -> u64 {
@@ -2266,3 +2336,15 @@ pub fn bvid_to_aid(bvid: &str) -> u64 {
     }
     (tmp & MASK_CODE) ^ XOR_CODE
 }
+
+/// 从 BV 号或包含 BV 号的链接中提取 BV 号
+pub fn parse_bvid(input: &str) -> Option<String> {
+    input.match_indices("BV").find_map(|(start, _)| {
+        let candidate: String = input[start..].chars().take(12).collect();
+        let rest = &input[start + candidate.len()..];
+        (candidate.chars().count() == 12
+            && candidate.chars().skip(2).all(|c| DATA.contains(&c))
+            && !rest.starts_with(|c: char| c.is_ascii_alphanumeric()))
+        .then_some(candidate)
+    })
+}
@@ -6,6 +6,34 @@ use leaky_bucket::RateLimiter;
 
 use crate::config::Config;
 
+/// 预览与校验模板时使用的内置样例数据，标题中包含斜杠和冒号以展示文件名安全化的效果
+pub fn sample_template_args() -> serde_json::Value {
+    serde_json::json!({
+        "bvid": "BV1xx411c7mD",
+        "title": "【4K】示例视频/Sample: Part 1",
+        "show_title": "【4K】示例视频/Sample: Part 1",
+        "upper_name": "示例UP主",
+        "upper_mid": 12345678,
+        "studio": "示例UP主",
+        "ptitle": "第一集",
+        "long_title": "第一集",
+        "pid": 1,
+        "pid_pad": "01",
+        "season": 1,
+        "season_pad": "01",
+        "year": 2024,
+        "series_title": "示例番剧",
+        "season_id": "12345",
+        "ep_id": "67890",
+        "category": 1,
+        "resolution": "1920x1080",
+        "actors": "",
+        "share_copy": "",
+        "pubtime": "2024-01-01",
+        "fav_time": "2024-01-02",
+    })
+}
+
 /// 配置包，包含所有需要热重载的组件
 /// 使用 ArcSwap<ConfigBundle> 确保原子性更新
 #[derive(Clone)]
@@ -17,6 +45,8 @@ pub struct ConfigBundle {
     /// HTTP 请求限流器
     #[allow(dead_code)]
     pub rate_limiter: Arc<RateLimiter>,
+    /// 下载限速器，令牌单位为字节，所有并发下载任务共享
+    pub download_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl ConfigBundle {
@@ -24,14 +54,59 @@ impl ConfigBundle {
     pub fn from_config(config: Config) -> Result<Self> {
         let handlebars = Self::build_handlebars(&config)?;
         let rate_limiter = Self::build_rate_limiter(&config);
+        let download_limiter = Self::build_download_limiter(&config).map(Arc::new);
 
         Ok(Self {
             config,
             handlebars,
             rate_limiter: Arc::new(rate_limiter),
+            download_limiter,
         })
     }
 
+    /// 使用样例数据编译并渲染所有命名模板，返回出错的配置项及错误信息
+    /// 用于在保存配置前发现拼写错误的 helper 等只有渲染时才会暴露的问题
+    pub fn check_templates(config: &Config, data: &serde_json::Value) -> Vec<String> {
+        let templates = [
+            ("video", "video_name", config.video_name.as_ref()),
+            ("page", "page_name", config.page_name.as_ref()),
+            ("multi_page", "multi_page_name", config.multi_page_name.as_ref()),
+            ("bangumi", "bangumi_name", config.bangumi_name.as_ref()),
+            (
+                "bangumi_folder",
+                "bangumi_folder_name",
+                config.bangumi_folder_name.as_ref(),
+            ),
+            ("folder_structure", "folder_structure", config.folder_structure.as_ref()),
+        ];
+        // 先逐个编译，语法错误可以准确对应到配置项
+        let errors: Vec<String> = templates
+            .iter()
+            .filter_map(|(_, key, template)| {
+                handlebars::Template::compile(template)
+                    .err()
+                    .map(|e| format!("模板 {} 语法错误: {}", key, e))
+            })
+            .collect();
+        if !errors.is_empty() {
+            return errors;
+        }
+        let bundle = match Self::from_config(config.clone()) {
+            Ok(bundle) => bundle,
+            Err(e) => return vec![format!("模板编译失败: {:#}", e)],
+        };
+        templates
+            .iter()
+            .filter_map(|(name, key, _)| {
+                bundle
+                    .handlebars
+                    .render(name, data)
+                    .err()
+                    .map(|e| format!("模板 {} 渲染失败: {}", key, e))
+            })
+            .collect()
+    }
+
     /// 构建 Handlebars 模板引擎
     fn build_handlebars(config: &Config) -> Result<Handlebars<'static>> {
         use handlebars::handlebars_helper;
@@ -129,6 +204,24 @@ impl ConfigBundle {
         }
     }
 
+    /// 构建下载限速器：每 100ms 补充十分之一的额度，最多积攒 1 秒的额度
+    fn build_download_limiter(config: &Config) -> Option<RateLimiter> {
+        let kbps = config
+            .concurrent_limit
+            .download_speed_limit_kbps
+            .filter(|kbps| *kbps > 0)?;
+        let bytes_per_second = usize::try_from(kbps.saturating_mul(1024)).unwrap_or(usize::MAX);
+        let refill = (bytes_per_second / 10).max(1);
+        Some(
+            RateLimiter::builder()
+                .initial(0)
+                .max(bytes_per_second)
+                .refill(refill)
+                .interval(std::time::Duration::from_millis(100))
+                .build(),
+        )
+    }
+
     /// 检查配置是否有效
     #[cfg(not(test))]
     pub fn validate(&self) -> bool {
@@ -167,14 +260,14 @@ impl ConfigBundle {
     /// 渲染模板的便捷方法（使用path_safe_render确保分隔符正确处理）
     #[allow(dead_code)]
     pub fn render_template(&self, template_name: &str, data: &serde_json::Value) -> Result<String> {
-        use crate::utils::filenamify::filenamify_with_options;
+        use crate::utils::filenamify::filenamify_with_policy;
 
         // 两阶段处理：
         // 1. 先渲染模板，保护模板路径分隔符
         let rendered = self.handlebars.render(template_name, data)?;
 
         // 2. 对整个渲染结果进行安全化，保护模板分隔符
-        let safe_rendered = filenamify_with_options(&rendered, true);
+        let safe_rendered = filenamify_with_policy(&rendered, true, self.config.filename_policy);
 
         // 3. 最后处理路径分隔符
         #[cfg(windows)]
@@ -189,29 +282,28 @@ impl ConfigBundle {
 
     /// 安全渲染模板的通用方法（修复原始斜杠分割问题）
     fn render_template_safe(&self, template_name: &str, data: &serde_json::Value) -> Result<String> {
-        use crate::utils::filenamify::filenamify_with_options;
+        use crate::utils::filenamify::{filenamify_with_policy, truncate_path_segments};
 
         // 两阶段处理（修复原始斜杠分割问题）：
         // 1. 先渲染模板，模板分隔符已转换为 __UNIX_SEP__ 等占位符
         let rendered = self.handlebars.render(template_name, data)?;
 
         // 2. 对整个渲染结果进行安全化，保护模板分隔符
-        // filenamify_with_options 已经正确处理了内容中的斜杠
-        let safe_rendered = filenamify_with_options(&rendered, true);
+        // filenamify_with_policy 已经正确处理了内容中的斜杠
+        let safe_rendered = filenamify_with_policy(&rendered, true, self.config.filename_policy);
 
         // 3. 最后处理模板路径分隔符，将占位符转换为真实的路径分隔符
         #[cfg(windows)]
-        {
-            Ok(safe_rendered
-                .replace("__UNIX_SEP__", "/")  // 模板路径分隔符 → 真实分隔符
-                .replace("__WIN_SEP__", "\\"))
-        }
+        let path = safe_rendered
+            .replace("__UNIX_SEP__", "/")  // 模板路径分隔符 → 真实分隔符
+            .replace("__WIN_SEP__", "\\");
         #[cfg(not(windows))]
-        {
-            Ok(safe_rendered
-                .replace("__UNIX_SEP__", "/")  // 模板路径分隔符 → 真实分隔符
-                .replace("__WIN_SEP__", "_"))
-        }
+        let path = safe_rendered
+            .replace("__UNIX_SEP__", "/")  // 模板路径分隔符 → 真实分隔符
+            .replace("__WIN_SEP__", "_");
+
+        // 4. 截断超过文件名长度上限的路径段
+        Ok(truncate_path_segments(&path, self.config.max_filename_bytes))
     }
 
     /// 渲染视频名称模板的便捷方法
@@ -252,6 +344,7 @@ impl std::fmt::Debug for ConfigBundle {
             .field("config", &"<Config instance>")
             .field("handlebars", &"<Handlebars instance>")
             .field("rate_limiter", &"<RateLimiter instance>")
+            .field("download_limiter", &self.download_limiter.is_some())
             .finish()
     }
 }
@@ -263,6 +356,28 @@ mod tests {
     use serde_json::json;
     use std::borrow::Cow;
 
+    #[test]
+    fn test_check_templates() {
+        let data = sample_template_args();
+        assert!(ConfigBundle::check_templates(&Config::default(), &data).is_empty());
+
+        let config = Config {
+            video_name: Cow::Borrowed("{{truncat title 20}}"),
+            ..Default::default()
+        };
+        let errors = ConfigBundle::check_templates(&config, &data);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("video_name"), "{}", errors[0]);
+
+        let config = Config {
+            page_name: Cow::Borrowed("{{#if title}}"),
+            ..Default::default()
+        };
+        let errors = ConfigBundle::check_templates(&config, &data);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("page_name"), "{}", errors[0]);
+    }
+
     #[test]
     fn test_video_template_path_separator_handling() {
         // 设置包含路径分隔符的模板，模拟用户问题中的场景
@@ -453,4 +568,47 @@ mod tests {
         // 验证原始等号保持不变
         assert_eq!(result, "=咬人猫=", "等号应该保持原样，实际结果: {}", result);
     }
+
+    #[test]
+    fn test_download_limiter_disabled_by_default() {
+        let bundle = ConfigBundle::from_config(Config::default()).unwrap();
+        assert!(bundle.download_limiter.is_none());
+
+        let mut config = Config::default();
+        config.concurrent_limit.download_speed_limit_kbps = Some(0);
+        let bundle = ConfigBundle::from_config(config).unwrap();
+        assert!(bundle.download_limiter.is_none(), "0 应视为不限速");
+    }
+
+    #[tokio::test]
+    async fn test_download_limiter_shared_across_concurrent_tasks() {
+        let mut config = Config::default();
+        config.concurrent_limit.video = 3;
+        config.concurrent_limit.download_speed_limit_kbps = Some(100);
+        let bundle = ConfigBundle::from_config(config).unwrap();
+        let limiter = bundle.download_limiter.clone().expect("应创建下载限速器");
+
+        // 3 个并发任务各下载 10KB，共 30KB，限速 100KB/s 下至少需要约 300ms
+        let start = std::time::Instant::now();
+        let tasks = (0..3)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    for _ in 0..10 {
+                        limiter.acquire(1024).await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= std::time::Duration::from_millis(250),
+            "并发任务应共享限速额度，实际耗时: {:?}",
+            elapsed
+        );
+    }
 }
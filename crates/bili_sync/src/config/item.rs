@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
 
 use crate::utils::filenamify::filenamify;
@@ -17,9 +18,29 @@ pub struct WatchLaterConfig {
 #[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum NFOTimeType {
+    /// 收藏时间（稍后观看为添加时间）
     #[default]
     FavTime,
+    /// 发布时间（番剧为首播时间）
     PubTime,
+    /// 投稿时间
+    CTime,
+}
+
+impl NFOTimeType {
+    pub const ALL: [NFOTimeType; 3] = [NFOTimeType::FavTime, NFOTimeType::PubTime, NFOTimeType::CTime];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NFOTimeType::FavTime => "favtime",
+            NFOTimeType::PubTime => "pubtime",
+            NFOTimeType::CTime => "ctime",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|time_type| time_type.as_str() == value)
+    }
 }
 
 /// NFO 文件格式类型
@@ -35,6 +56,245 @@ pub enum NFOFormatType {
     Detailed,
 }
 
+/// 不同视频源下载到相同分页（相同 cid）时的去重方式
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupStrategy {
+    /// 不去重，每个视频源各自下载
+    #[default]
+    None,
+    /// 创建硬链接，不在同一文件系统时退化为复制
+    Hardlink,
+    /// 创建指向已下载文件的符号链接
+    Symlink,
+}
+
+impl DedupStrategy {
+    pub const ALL: [DedupStrategy; 3] = [DedupStrategy::None, DedupStrategy::Hardlink, DedupStrategy::Symlink];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DedupStrategy::None => "none",
+            DedupStrategy::Hardlink => "hardlink",
+            DedupStrategy::Symlink => "symlink",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|strategy| strategy.as_str() == value)
+    }
+}
+
+/// 弹幕嵌入视频的方式，按视频源单独设置
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DanmakuEmbedMode {
+    /// 只生成外挂的 `.ass` 文件
+    #[default]
+    None,
+    /// 作为软字幕轨道封装进视频，不重新编码
+    Soft,
+    /// 烧录进画面，需要重新编码视频
+    Hard,
+}
+
+impl DanmakuEmbedMode {
+    pub const ALL: [DanmakuEmbedMode; 3] = [DanmakuEmbedMode::None, DanmakuEmbedMode::Soft, DanmakuEmbedMode::Hard];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DanmakuEmbedMode::None => "none",
+            DanmakuEmbedMode::Soft => "soft",
+            DanmakuEmbedMode::Hard => "hard",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == value)
+    }
+}
+
+/// 下载封面时请求的图片格式
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverFormat {
+    /// 保持B站原图格式
+    #[default]
+    Original,
+    Jpg,
+    Webp,
+}
+
+impl CoverFormat {
+    pub const ALL: [CoverFormat; 3] = [CoverFormat::Original, CoverFormat::Jpg, CoverFormat::Webp];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoverFormat::Original => "original",
+            CoverFormat::Jpg => "jpg",
+            CoverFormat::Webp => "webp",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.as_str() == value)
+    }
+
+    /// 本地封面文件的扩展名，原图沿用一直以来的 jpg
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CoverFormat::Original | CoverFormat::Jpg => "jpg",
+            CoverFormat::Webp => "webp",
+        }
+    }
+}
+
+/// 文件名安全化策略
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum FilenamePolicy {
+    /// 全角标点转为半角并替换特殊符号
+    #[default]
+    Default,
+    /// 只保留 ASCII 字符，无法转写的字符（如中文）会被替换为下划线
+    Ascii,
+    /// 保留全角标点与特殊符号，只替换文件系统不允许的字符，适合 exFAT/NTFS
+    PreserveUnicode,
+}
+
+impl FilenamePolicy {
+    pub const ALL: [FilenamePolicy; 3] = [
+        FilenamePolicy::Default,
+        FilenamePolicy::Ascii,
+        FilenamePolicy::PreserveUnicode,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilenamePolicy::Default => "default",
+            FilenamePolicy::Ascii => "ascii",
+            FilenamePolicy::PreserveUnicode => "preserve_unicode",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| policy.as_str() == value)
+    }
+}
+
+/// NFO 与本地图片资源的命名风格，对应不同的媒体服务器
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NFOFlavor {
+    /// Jellyfin / Emby（`-thumb.jpg`、`-fanart.jpg`）
+    #[default]
+    Emby,
+    /// Kodi（剧集根目录使用 `poster.jpg`、`fanart.jpg`）
+    Kodi,
+    /// Plex（`.plexmatch`，剧集根目录使用 `poster.jpg`、`background.jpg`）
+    Plex,
+}
+
+impl NFOFlavor {
+    pub const ALL: [NFOFlavor; 3] = [NFOFlavor::Emby, NFOFlavor::Kodi, NFOFlavor::Plex];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NFOFlavor::Emby => "emby",
+            NFOFlavor::Kodi => "kodi",
+            NFOFlavor::Plex => "plex",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|flavor| flavor.as_str() == value)
+    }
+
+    /// 剧集（多P视频、合集、番剧）根目录的封面文件名
+    pub fn show_poster_file_name(&self, base_name: &str) -> String {
+        match self {
+            NFOFlavor::Emby => format!("{}-thumb.jpg", base_name),
+            NFOFlavor::Kodi | NFOFlavor::Plex => "poster.jpg".to_string(),
+        }
+    }
+
+    /// 剧集根目录的背景图文件名
+    pub fn show_fanart_file_name(&self, base_name: &str) -> String {
+        match self {
+            NFOFlavor::Emby => format!("{}-fanart.jpg", base_name),
+            NFOFlavor::Kodi => "fanart.jpg".to_string(),
+            NFOFlavor::Plex => "background.jpg".to_string(),
+        }
+    }
+
+    /// 番剧横版封面的文件名，非 Emby 风格下 `poster.jpg` 已被竖版主封面占用
+    pub fn bangumi_thumb_file_name(&self, base_name: &str) -> String {
+        match self {
+            NFOFlavor::Emby => format!("{}-thumb.jpg", base_name),
+            NFOFlavor::Kodi | NFOFlavor::Plex => "landscape.jpg".to_string(),
+        }
+    }
+
+    /// 与视频文件同名的分集封面文件名
+    pub fn episode_thumb_file_name(&self, base_name: &str) -> String {
+        match self {
+            NFOFlavor::Emby | NFOFlavor::Kodi => format!("{}-thumb.jpg", base_name),
+            NFOFlavor::Plex => format!("{}.jpg", base_name),
+        }
+    }
+
+    /// 与视频文件同名的分集背景图文件名
+    pub fn episode_fanart_file_name(&self, base_name: &str) -> String {
+        format!("{}-fanart.jpg", base_name)
+    }
+
+    /// 所有风格下可能在剧集根目录生成的元数据文件，删除时不区分当前风格与封面格式，避免切换后残留
+    pub fn all_show_metadata_files(base_name: &str) -> Vec<String> {
+        let mut files = vec!["tvshow.nfo".to_string(), ".plexmatch".to_string()];
+        for flavor in Self::ALL {
+            for file in [
+                flavor.show_poster_file_name(base_name),
+                flavor.show_fanart_file_name(base_name),
+            ]
+            .into_iter()
+            .flat_map(cover_file_variants)
+            {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        files
+    }
+
+    /// 所有风格下可能生成的分集图片文件
+    pub fn all_episode_image_files(base_name: &str) -> Vec<String> {
+        let mut files = Vec::new();
+        for flavor in Self::ALL {
+            for file in [
+                flavor.episode_thumb_file_name(base_name),
+                flavor.episode_fanart_file_name(base_name),
+            ]
+            .into_iter()
+            .flat_map(cover_file_variants)
+            {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        files
+    }
+}
+
+/// 封面文件在各封面格式下的文件名
+fn cover_file_variants(file: String) -> Vec<String> {
+    let webp = file
+        .strip_suffix(".jpg")
+        .map(|stem| format!("{}.{}", stem, CoverFormat::Webp.extension()));
+    std::iter::once(file).chain(webp).collect()
+}
+
 /// 空UP主信息处理策略
 #[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
 #[serde(rename_all = "lowercase")]
@@ -172,6 +432,9 @@ pub struct ConcurrentLimit {
     pub rate_limit: Option<RateLimit>,
     #[serde(default)]
     pub parallel_download: ParallelDownloadConfig,
+    /// 全局下载限速（KB/s），所有并发下载任务共享，为空或 0 表示不限速
+    #[serde(default)]
+    pub download_speed_limit_kbps: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -191,10 +454,129 @@ impl Default for ConcurrentLimit {
                 duration: 250,
             }),
             parallel_download: ParallelDownloadConfig::default(),
+            download_speed_limit_kbps: None,
         }
     }
 }
 
+/// 静默时段配置，时段内自动暂停扫描与下载
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuietHoursConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 开始时间，格式为 HH:MM
+    #[serde(default = "default_quiet_hours_start")]
+    pub start: String,
+    /// 结束时间，格式为 HH:MM，早于开始时间表示跨越午夜
+    #[serde(default = "default_quiet_hours_end")]
+    pub end: String,
+    /// 生效的星期（1-7 表示周一到周日），为空表示每天生效
+    /// 跨越午夜的时段以开始时间所在的那一天为准
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+}
+
+fn default_quiet_hours_start() -> String {
+    "01:00".to_string()
+}
+
+fn default_quiet_hours_end() -> String {
+    "07:00".to_string()
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: default_quiet_hours_start(),
+            end: default_quiet_hours_end(),
+            weekdays: Vec::new(),
+        }
+    }
+}
+
+impl QuietHoursConfig {
+    fn parse_time(value: &str) -> Result<NaiveTime> {
+        NaiveTime::parse_from_str(value.trim(), "%H:%M")
+            .with_context(|| format!("无效的时间格式: {}，应为 HH:MM", value))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let start = Self::parse_time(&self.start)?;
+        let end = Self::parse_time(&self.end)?;
+        ensure!(start != end, "静默时段的开始时间与结束时间不能相同");
+        if let Some(day) = self.weekdays.iter().find(|day| !(1..=7).contains(*day)) {
+            bail!("无效的星期: {}，应为 1-7", day);
+        }
+        Ok(())
+    }
+
+    /// 判断给定时间是否处于静默时段内
+    pub fn is_active_at(&self, now: NaiveDateTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Ok(start), Ok(end)) = (Self::parse_time(&self.start), Self::parse_time(&self.end)) else {
+            return false;
+        };
+        let time = now.time();
+        // 找到当前所处时段开始的那一天
+        let window_day = if start < end {
+            if time < start || time >= end {
+                return false;
+            }
+            now.date()
+        } else if time >= start {
+            now.date()
+        } else if time < end {
+            match now.date().pred_opt() {
+                Some(day) => day,
+                None => return false,
+            }
+        } else {
+            return false;
+        };
+        self.weekdays.is_empty() || self.weekdays.contains(&window_day.weekday().number_from_monday())
+    }
+}
+
+/// WebDAV 上传配置，视频源开启上传后，分页下载完成的文件会按相对目录结构上传到该存储
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct WebDavConfig {
+    /// WebDAV 服务地址，如 https://dav.example.com/remote.php/dav/files/user，为空表示未配置
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 上传到的远程根目录，为空表示服务地址的根目录
+    #[serde(default)]
+    pub base_path: String,
+    /// 上传成功后是否删除本地文件
+    #[serde(default)]
+    pub delete_local: bool,
+}
+
+impl WebDavConfig {
+    /// 是否配置了可用的 WebDAV 服务地址
+    pub fn is_configured(&self) -> bool {
+        self.url.as_deref().is_some_and(|url| !url.trim().is_empty())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(url) = self.url.as_deref().map(str::trim).filter(|url| !url.is_empty()) {
+            ensure!(
+                url.starts_with("http://") || url.starts_with("https://"),
+                "WebDAV 地址必须以 http:// 或 https:// 开头: {}",
+                url
+            );
+            reqwest::Url::parse(url).with_context(|| format!("无效的 WebDAV 地址: {}", url))?;
+        }
+        Ok(())
+    }
+}
+
 /// UP主投稿风控配置
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SubmissionRiskControlConfig {
@@ -359,3 +741,103 @@ impl PathSafeTemplate for handlebars::Handlebars<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-01-01 是周一
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_same_day_window() {
+        let quiet_hours = QuietHoursConfig {
+            enabled: true,
+            start: "01:00".to_string(),
+            end: "07:00".to_string(),
+            weekdays: Vec::new(),
+        };
+        assert!(quiet_hours.is_active_at(at(1, 1, 0)));
+        assert!(quiet_hours.is_active_at(at(1, 6, 59)));
+        assert!(!quiet_hours.is_active_at(at(1, 7, 0)));
+        assert!(!quiet_hours.is_active_at(at(1, 0, 59)));
+        assert!(
+            !QuietHoursConfig::default().is_active_at(at(1, 2, 0)),
+            "未启用时不应生效"
+        );
+    }
+
+    #[test]
+    fn test_quiet_hours_overnight_window_with_weekdays() {
+        let quiet_hours = QuietHoursConfig {
+            enabled: true,
+            start: "23:00".to_string(),
+            end: "06:00".to_string(),
+            // 仅周五晚开始的时段生效
+            weekdays: vec![5],
+        };
+        assert!(quiet_hours.is_active_at(at(5, 23, 30)));
+        // 周六凌晨仍属于周五开始的时段
+        assert!(quiet_hours.is_active_at(at(6, 5, 0)));
+        assert!(!quiet_hours.is_active_at(at(6, 23, 30)));
+        assert!(!quiet_hours.is_active_at(at(5, 5, 0)));
+    }
+
+    #[test]
+    fn test_quiet_hours_validate() {
+        assert!(QuietHoursConfig::default().validate().is_ok());
+        let invalid_time = QuietHoursConfig {
+            start: "25:00".to_string(),
+            ..Default::default()
+        };
+        assert!(invalid_time.validate().is_err());
+        let invalid_weekday = QuietHoursConfig {
+            weekdays: vec![0],
+            ..Default::default()
+        };
+        assert!(invalid_weekday.validate().is_err());
+    }
+
+    #[test]
+    fn test_nfo_flavor_file_names() {
+        assert_eq!(NFOFlavor::default(), NFOFlavor::Emby);
+        assert_eq!(NFOFlavor::parse("plex"), Some(NFOFlavor::Plex));
+        assert_eq!(NFOFlavor::parse("jellyfin"), None);
+
+        assert_eq!(NFOFlavor::Emby.show_poster_file_name("a"), "a-thumb.jpg");
+        assert_eq!(NFOFlavor::Kodi.show_fanart_file_name("a"), "fanart.jpg");
+        assert_eq!(NFOFlavor::Plex.show_poster_file_name("a"), "poster.jpg");
+        assert_eq!(NFOFlavor::Plex.show_fanart_file_name("a"), "background.jpg");
+        assert_eq!(NFOFlavor::Plex.episode_thumb_file_name("S01E01"), "S01E01.jpg");
+
+        let show_files = NFOFlavor::all_show_metadata_files("a");
+        for file in [
+            "tvshow.nfo",
+            ".plexmatch",
+            "a-thumb.jpg",
+            "poster.jpg",
+            "background.jpg",
+        ] {
+            assert!(show_files.contains(&file.to_string()));
+        }
+        assert!(show_files.contains(&"poster.webp".to_string()));
+        assert_eq!(
+            NFOFlavor::all_episode_image_files("e"),
+            vec![
+                "e-thumb.jpg",
+                "e-thumb.webp",
+                "e-fanart.jpg",
+                "e-fanart.webp",
+                "e.jpg",
+                "e.webp"
+            ]
+        );
+    }
+}
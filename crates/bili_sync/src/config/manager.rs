@@ -9,6 +9,76 @@ use crate::config::{Config, ConfigBundle};
 use crate::utils::time_format::now_standard_string;
 use bili_sync_entity::entities::{config_item, prelude::ConfigItem};
 
+/// 导出配置时默认移除的敏感字段，使用 JSON Pointer 表示
+const SECRET_CONFIG_POINTERS: [&str; 12] = [
+    "/auth_token",
+    "/credential",
+    "/aria2_rpc_secret",
+    "/jellyfin_api_key",
+    "/webdav/password",
+    "/notification/serverchan_key",
+    "/notification/bark_device_key",
+    "/notification/bark_device_keys",
+    "/notification/telegram_bot_token",
+    "/notification/discord_webhook_url",
+    "/notification/webhook_url",
+    "/risk_control/auto_solve/api_key",
+];
+
+/// 将配置序列化为 JSON，不包含敏感字段时移除 [`SECRET_CONFIG_POINTERS`] 中的字段
+pub fn export_config(config: &Config, include_secrets: bool) -> Result<Value> {
+    let mut value = serde_json::to_value(config)?;
+    if !include_secrets {
+        for pointer in SECRET_CONFIG_POINTERS {
+            let (parent, key) = pointer.rsplit_once('/').unwrap_or_default();
+            if let Some(object) = value.pointer_mut(parent).and_then(Value::as_object_mut) {
+                object.remove(key);
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// 将导入的配置递归合并到现有配置上，导入数据中缺失的字段（如导出时移除的敏感字段）保留现有值
+fn merge_config_json(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_config_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// 比较配置值，返回发生变化的字段及其当前值与新值，对象按字段递归比较，字段名使用点号连接
+pub fn diff_config_values(key: &str, current: &Value, new: &Value) -> Vec<(String, Value, Value)> {
+    match (current, new) {
+        (Value::Object(current_map), Value::Object(new_map)) => {
+            let mut fields: Vec<&String> = current_map.keys().chain(new_map.keys()).collect();
+            fields.sort();
+            fields.dedup();
+            fields
+                .into_iter()
+                .flat_map(|field| {
+                    diff_config_values(
+                        &format!("{}.{}", key, field),
+                        current_map.get(field).unwrap_or(&Value::Null),
+                        new_map.get(field).unwrap_or(&Value::Null),
+                    )
+                })
+                .collect()
+        }
+        _ if current == new => Vec::new(),
+        _ => vec![(key.to_string(), current.clone(), new.clone())],
+    }
+}
+
 /// 配置管理器，负责配置的数据库存储和热重载
 #[derive(Clone)]
 pub struct ConfigManager {
@@ -274,6 +344,56 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// 导入配置第一步：合并到当前配置上并校验，返回合并后的配置 JSON 与实际变化的顶层配置项
+    /// 不写入数据库，调用方通过更新配置接口或 [`Self::save_imported_items`] 保存
+    pub fn prepare_import(&self, current: &Config, imported: Value) -> Result<(Value, Vec<String>)> {
+        if !imported.is_object() {
+            return Err(anyhow!("导入的配置必须是JSON对象"));
+        }
+        let current_json = serde_json::to_value(current)?;
+        let mut merged = current_json.clone();
+        merge_config_json(&mut merged, imported);
+        let config: Config = serde_json::from_value(merged.clone()).context("解析导入的配置失败")?;
+        let bundle = ConfigBundle::from_config(config).context("导入的配置无效")?;
+        if !bundle.validate() {
+            return Err(anyhow!("导入的配置未通过校验，详细原因请查看日志"));
+        }
+
+        let mut changed_keys: Vec<String> = self
+            .flatten_config_json(merged.clone())?
+            .into_iter()
+            .filter(|(key, value)| current_json.get(key) != Some(value))
+            .map(|(key, _)| key)
+            .collect();
+        changed_keys.sort();
+        Ok((merged, changed_keys))
+    }
+
+    /// 直接保存合并后配置中的指定顶层配置项，用于更新配置接口无法修改的配置项
+    pub async fn save_imported_items(&self, merged: &Value, keys: &[String]) -> Result<()> {
+        for key in keys {
+            let Some(value) = merged.get(key) else {
+                continue;
+            };
+            self.update_config_item(key, value.clone()).await?;
+            // 移除同名的嵌套配置项，避免加载时嵌套字段覆盖导入的完整对象
+            let prefix = format!("{}.", key);
+            let nested_keys: Vec<String> = ConfigItem::find()
+                .filter(config_item::Column::KeyName.starts_with(&prefix))
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|item| item.key_name)
+                .filter(|key_name| key_name.starts_with(&prefix))
+                .collect();
+            if !nested_keys.is_empty() {
+                Self::delete_config_keys_async(self.db.clone(), nested_keys).await;
+            }
+        }
+        info!("已直接保存 {} 个导入的配置项", keys.len());
+        Ok(())
+    }
+
     /// 将TOML配置迁移到数据库
     async fn migrate_to_database(&self, config: &Config) -> Result<()> {
         info!("开始迁移TOML配置到数据库");
@@ -384,6 +504,23 @@ impl ConfigManager {
         Ok(changes)
     }
 
+    /// 按ID获取单条配置变更记录
+    pub async fn get_config_change(&self, id: i32) -> Result<Option<config_item::ConfigChangeModel>> {
+        let sql = "SELECT id, key_name, old_value, new_value, changed_at FROM config_changes WHERE id = ?";
+        let stmt = sea_orm::Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, sql, vec![id.into()]);
+
+        let Some(row) = self.db.query_one(stmt).await? else {
+            return Ok(None);
+        };
+        Ok(Some(config_item::ConfigChangeModel {
+            id: row.try_get::<i32>("", "id")?,
+            key_name: row.try_get::<String>("", "key_name")?,
+            old_value: row.try_get::<Option<String>>("", "old_value")?,
+            new_value: row.try_get::<String>("", "new_value")?,
+            changed_at: row.try_get::<String>("", "changed_at")?,
+        }))
+    }
+
     /// 解决配置冲突：当既有完整对象又有嵌套字段时，优先使用嵌套字段
     fn resolve_config_conflicts(&self, config_map: &mut HashMap<String, Value>) -> Result<()> {
         // 检测可能冲突的配置前缀
@@ -474,3 +611,77 @@ impl ConfigManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_export_config_without_secrets() {
+        let mut config = Config {
+            auth_token: Some("secret-token".to_string()),
+            jellyfin_api_key: Some("secret-key".to_string()),
+            ..Default::default()
+        };
+        config.webdav.password = Some("secret-password".to_string());
+        config.notification.webhook_url = Some("https://example.com/hook?token=secret".to_string());
+
+        let exported = export_config(&config, false).unwrap();
+        assert!(exported.get("auth_token").is_none());
+        assert!(exported.get("credential").is_none());
+        assert!(exported.get("jellyfin_api_key").is_none());
+        assert!(exported["webdav"].get("password").is_none());
+        assert!(exported["notification"].get("webhook_url").is_none());
+        assert!(exported.get("video_name").is_some());
+
+        let exported = export_config(&config, true).unwrap();
+        assert_eq!(exported["auth_token"], "secret-token");
+        assert_eq!(exported["webdav"]["password"], "secret-password");
+    }
+
+    #[test]
+    fn test_diff_config_values() {
+        assert!(diff_config_values("interval", &json!(600), &json!(600)).is_empty());
+        assert_eq!(
+            diff_config_values("video_name", &json!("{{title}}"), &json!("{{bvid}}")),
+            vec![("video_name".to_string(), json!("{{title}}"), json!("{{bvid}}"))]
+        );
+        assert_eq!(
+            diff_config_values(
+                "webdav",
+                &json!({ "url": "http://old", "delete_local": false }),
+                &json!({ "url": "http://new", "delete_local": false, "base_path": "/" })
+            ),
+            vec![
+                ("webdav.base_path".to_string(), Value::Null, json!("/")),
+                ("webdav.url".to_string(), json!("http://old"), json!("http://new")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_config_json() {
+        let mut base = json!({
+            "auth_token": "keep",
+            "interval": 1200,
+            "webdav": { "url": "http://old", "password": "keep" }
+        });
+        merge_config_json(
+            &mut base,
+            json!({
+                "interval": 600,
+                "webdav": { "url": "http://new" }
+            }),
+        );
+        assert_eq!(
+            base,
+            json!({
+                "auth_token": "keep",
+                "interval": 600,
+                "webdav": { "url": "http://new", "password": "keep" }
+            })
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -13,7 +14,7 @@ mod item;
 mod manager;
 
 use crate::bilibili::{Credential, DanmakuOption, FilterOption};
-pub use crate::config::bundle::ConfigBundle;
+pub use crate::config::bundle::{sample_template_args, ConfigBundle};
 pub use crate::config::clap::version;
 pub use crate::config::global::{
     get_config_manager, init_config_with_database, reload_config, reload_config_bundle, with_config, ARGS,
@@ -21,9 +22,10 @@ pub use crate::config::global::{
 };
 use crate::config::item::ConcurrentLimit;
 pub use crate::config::item::{
-    EmptyUpperStrategy, NFOConfig, NFOTimeType, PathSafeTemplate, RateLimit, SubmissionRiskControlConfig,
+    CoverFormat, DanmakuEmbedMode, DedupStrategy, EmptyUpperStrategy, FilenamePolicy, NFOConfig, NFOFlavor,
+    NFOTimeType, PathSafeTemplate, QuietHoursConfig, RateLimit, SubmissionRiskControlConfig, WebDavConfig,
 };
-pub use crate::config::manager::ConfigManager;
+pub use crate::config::manager::{diff_config_values, export_config, ConfigManager};
 
 // 移除不再需要的配置结构体，因为视频源现在存储在数据库中
 // #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -137,6 +139,60 @@ fn default_bangumi_folder_name() -> Cow<'static, str> {
     Cow::Borrowed("{{series_title}}")
 }
 
+fn default_source_scan_concurrency() -> usize {
+    1
+}
+
+/// 同时扫描视频源数量的上限
+pub const MAX_SOURCE_SCAN_CONCURRENCY: usize = 16;
+
+fn default_db_max_connections() -> u32 {
+    20
+}
+
+fn default_db_acquire_timeout() -> u64 {
+    30
+}
+
+fn default_db_busy_timeout() -> u64 {
+    30
+}
+
+fn default_download_max_retries() -> u32 {
+    3
+}
+
+fn default_download_retry_base_delay_ms() -> u64 {
+    1000
+}
+
+/// 单个文件下载失败后重试次数的上限
+pub const MAX_DOWNLOAD_RETRIES: u32 = 10;
+
+/// 下载重试基础等待时间的上限（毫秒）
+pub const MAX_DOWNLOAD_RETRY_BASE_DELAY_MS: u64 = 60_000;
+
+/// 数据库连接池最大连接数的上限
+pub const MAX_DB_CONNECTIONS: u32 = 100;
+
+/// 数据库获取连接超时与 SQLite 忙等超时的上限（秒）
+pub const MAX_DB_TIMEOUT_SECS: u64 = 600;
+
+/// 封面最大宽度配置的上限（像素）
+pub const MAX_COVER_WIDTH: u32 = 4096;
+
+/// 文件名与完整路径长度限制的下限（字节），过小的限制会让文件名几乎被完全截断
+pub const MIN_FILENAME_BYTES: usize = 64;
+pub const MIN_PATH_BYTES: usize = 128;
+
+fn default_season_title_cache_size() -> usize {
+    1000
+}
+
+fn default_season_title_cache_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
 fn default_collection_folder_mode() -> Cow<'static, str> {
     Cow::Borrowed("unified") // 默认为统一模式
 }
@@ -173,6 +229,24 @@ pub struct Config {
     pub upper_path: PathBuf,
     #[serde(default)]
     pub nfo_time_type: NFOTimeType,
+    // NFO 与本地图片资源的命名风格（emby / kodi / plex）
+    #[serde(default)]
+    pub nfo_flavor: NFOFlavor,
+    // 自定义 movie / episodedetails NFO 的 Handlebars 模板，为空时使用内置格式
+    #[serde(default)]
+    pub nfo_template: Option<String>,
+    // 是否将创作团队头像下载到 .actors 目录，供媒体服务器显示演员头像
+    #[serde(default)]
+    pub nfo_download_actor_images: bool,
+    // 是否在 <genre> 之外再将视频标签重复写入 NFO 的 <tag>
+    #[serde(default = "default_nfo_include_tags")]
+    pub nfo_include_tags: bool,
+    // 额外写入的 <tag> 最多数量，0 表示不限制（<genre> 始终完整写入）
+    #[serde(default = "default_nfo_max_tags")]
+    pub nfo_max_tags: usize,
+    // 是否将B站的视频看点（分段章节）写入与视频同名的 .chapters.xml 文件，没有看点的视频不生成
+    #[serde(default)]
+    pub nfo_include_chapters: bool,
     #[serde(default)]
     pub nfo_config: NFOConfig,
     #[serde(default)]
@@ -181,6 +255,18 @@ pub struct Config {
     pub time_format: String,
     #[serde(default = "default_cdn_sorting")]
     pub cdn_sorting: bool,
+    // 下载完成后校验文件大小（以及B站提供的MD5），不一致时视为下载失败
+    #[serde(default = "default_verify_download_integrity")]
+    pub verify_download_integrity: bool,
+    // 是否下载官方CC字幕（保存为 .{语言}.srt）
+    #[serde(default = "default_download_subtitles")]
+    pub download_subtitles: bool,
+    // 是否为番剧分集写入片头/片尾跳过标记（.edl 文件）
+    #[serde(default)]
+    pub bangumi_write_skip_markers: bool,
+    // 是否跳过番剧特别篇（SP/OVA/PV 等）
+    #[serde(default)]
+    pub bangumi_skip_specials: bool,
     #[serde(default)]
     pub submission_risk_control: crate::config::item::SubmissionRiskControlConfig,
     #[serde(default)]
@@ -195,6 +281,13 @@ pub struct Config {
     pub enable_aria2_auto_restart: bool,
     #[serde(default = "default_aria2_health_check_interval")]
     pub aria2_health_check_interval: u64,
+    // 外部aria2的RPC地址（如 http://192.168.1.2:6800/jsonrpc），设置后不再启动内置aria2
+    // 外部aria2需要能访问与本程序相同的下载路径
+    #[serde(default)]
+    pub aria2_rpc_url: Option<String>,
+    // 外部aria2的RPC密钥（--rpc-secret）
+    #[serde(default)]
+    pub aria2_rpc_secret: Option<String>,
     // actors字段初始化状态标记
     #[serde(default)]
     pub actors_field_initialized: bool,
@@ -219,16 +312,160 @@ pub struct Config {
     // 风控验证配置
     #[serde(default)]
     pub risk_control: RiskControlConfig,
+    // 静默时段配置
+    #[serde(default)]
+    pub quiet_hours: QuietHoursConfig,
+    // 访问B站使用的代理地址，支持 http://、https://、socks5://
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    // 访问B站使用的 User-Agent，为空时使用内置的浏览器 UA
+    // 登录后修改可能导致风控指纹与已有会话不一致而触发验证，建议在重新登录前调整
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    // 访问B站时附加的请求头，同名时覆盖内置的请求头（如 Referer、Origin），修改后新建的请求客户端生效
+    // 属于高级选项，设置不当可能导致请求失败；Cookie、User-Agent 等请求头不允许覆盖
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+    // 队列任务的最大重试次数，超过后移入死信队列
+    #[serde(default = "default_task_max_retry")]
+    pub task_max_retry: u32,
+    // 保留策略清理视频时是否同时删除本地文件
+    #[serde(default = "default_retention_delete_local_files")]
+    pub retention_delete_local_files: bool,
+    // 删除本地文件时移动到视频源根目录下的 .trash 文件夹，而不是永久删除
+    #[serde(default)]
+    pub delete_to_trash: bool,
+    // 回收站中的文件保留天数，超过后永久删除，为 0 时不自动清理
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    // 跨视频源的重复分页去重方式（none / hardlink / symlink）
+    #[serde(default)]
+    pub dedup_strategy: DedupStrategy,
+    // 下载封面时请求的最大宽度（像素），通过B站图片地址的 @ 后缀缩放，为 0 时下载原图
+    #[serde(default)]
+    pub cover_max_width: u32,
+    // 下载封面时请求的图片格式（original / jpg / webp），webp 时本地封面文件也使用 .webp 扩展名
+    #[serde(default)]
+    pub cover_format: CoverFormat,
+    // 文件名安全化策略（default / ascii / preserve_unicode），只影响之后生成的文件名
+    #[serde(default)]
+    pub filename_policy: FilenamePolicy,
+    // 单个文件名或文件夹名的最大字节数，超出时截断并保留扩展名，0 表示不限制
+    #[serde(default = "default_max_filename_bytes")]
+    pub max_filename_bytes: usize,
+    // 分页文件完整路径的最大字节数，超出时截断文件名，0 表示不限制（Windows 未开启长路径支持时可设为 250 左右）
+    #[serde(default)]
+    pub max_path_bytes: usize,
+    // 下载暂存目录，下载及合并完成后再移动到媒体目录，为空时直接写入媒体目录
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>,
+    // 磁盘剩余空间低于该值（GB）时自动暂停下载，0 表示不检查
+    #[serde(default)]
+    pub min_free_disk_gb: u64,
+    // Jellyfin 服务地址，配置后每轮扫描有新视频时通知其刷新媒体库
+    #[serde(default)]
+    pub jellyfin_url: Option<String>,
+    // Jellyfin API 密钥
+    #[serde(default)]
+    pub jellyfin_api_key: Option<String>,
+    // 同一 IP 每分钟允许的认证相关请求数（含认证失败的请求），超出后返回 429，0 表示不限制
+    #[serde(default = "default_api_rate_limit_per_minute")]
+    pub api_rate_limit_per_minute: u32,
+    // 允许跨域访问 Web API 的来源（如 https://example.com），为空时仅允许同源访问，* 表示允许任意来源
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    // 信任媒体目录中已存在的文件：从未处理过的分页若目标文件已存在且大小合理，直接视为下载完成，用于在已有媒体库上重建数据库
+    #[serde(default)]
+    pub trust_existing_files: bool,
+    // FFmpeg 可执行文件路径，为空时使用 PATH 中的 ffmpeg
+    #[serde(default)]
+    pub ffmpeg_path: Option<PathBuf>,
+    // 合并音视频时使用的 FFmpeg 硬件加速方式（如 cuda、qsv），为空时不启用
+    #[serde(default)]
+    pub ffmpeg_hwaccel: Option<String>,
+    // 单次 FFmpeg 合并或弹幕嵌入的超时时间（秒），超时后终止 FFmpeg 并视为失败，为 0 时不限制
+    #[serde(default = "default_ffmpeg_merge_timeout_secs")]
+    pub ffmpeg_merge_timeout_secs: u64,
+    // 封面缺失或下载失败时使用 FFmpeg 从视频中截取一帧作为封面
+    #[serde(default)]
+    pub generate_thumbnail_fallback: bool,
+    // 扫描合集时同步B站的最新合集封面，封面变化时重新下载本地封面
+    #[serde(default)]
+    pub refresh_covers: bool,
+    // 每隔多少天检查一次UP主头像是否变化，头像变化时重新下载，为 0 时不检查
+    #[serde(default)]
+    pub refresh_upper_avatar_days: u32,
+    // 充电专享且当前账号无权观看的视频标记为无权限并跳过下载，关闭后仍会尝试下载
+    #[serde(default = "default_skip_upower_exclusive")]
+    pub skip_upower_exclusive: bool,
+    // 每轮扫描结束后在配置目录写入 JSON 格式的扫描报告 scan_report.json，供外部监控读取
+    #[serde(default)]
+    pub write_scan_report: bool,
+    // 同时扫描的视频源数量，默认为 1 即逐个扫描，下载并发仍受 concurrent_limit 限制
+    #[serde(default = "default_source_scan_concurrency")]
+    pub source_scan_concurrency: usize,
+    // 番剧标题缓存的最大条目数，超出时淘汰最久未使用的条目
+    #[serde(default = "default_season_title_cache_size")]
+    pub season_title_cache_size: usize,
+    // 番剧标题缓存的过期时间（秒），过期后在下次使用时重新获取
+    #[serde(default = "default_season_title_cache_ttl_secs")]
+    pub season_title_cache_ttl_secs: u64,
+    // 数据库连接池最大连接数，默认 20，视频源较多且并行扫描时可适当调大，修改后重启生效
+    #[serde(default = "default_db_max_connections")]
+    pub db_max_connections: u32,
+    // 从连接池获取连接的超时时间（秒），默认 30，修改后重启生效
+    #[serde(default = "default_db_acquire_timeout")]
+    pub db_acquire_timeout: u64,
+    // SQLite 数据库被锁定时的忙等超时时间（秒），默认 30，修改后重启生效
+    #[serde(default = "default_db_busy_timeout")]
+    pub db_busy_timeout: u64,
+    // 单个文件因网络错误或服务器 5xx 下载失败时的重试次数，为 0 时不重试，403/404 等错误不会重试
+    #[serde(default = "default_download_max_retries")]
+    pub download_max_retries: u32,
+    // 下载重试的基础等待时间（毫秒），每次重试等待时间翻倍并加入随机抖动
+    #[serde(default = "default_download_retry_base_delay_ms")]
+    pub download_retry_base_delay_ms: u64,
+    // WebDAV 上传配置
+    #[serde(default)]
+    pub webdav: WebDavConfig,
 }
 
 fn default_skip_bangumi_preview() -> bool {
     true // 默认跳过预告片
 }
 
+fn default_skip_upower_exclusive() -> bool {
+    true // 默认跳过无权观看的充电专享视频，避免反复下载失败
+}
+
 fn default_aria2_health_check_interval() -> u64 {
     300 // 默认5分钟
 }
 
+fn default_task_max_retry() -> u32 {
+    3 // 默认失败3次后不再重试
+}
+
+fn default_max_filename_bytes() -> usize {
+    255 // 常见文件系统单个文件名的上限
+}
+
+fn default_api_rate_limit_per_minute() -> u32 {
+    60 // 扫码登录每3秒轮询一次，留出足够余量
+}
+
+fn default_retention_delete_local_files() -> bool {
+    true // 默认同时删除本地文件，否则保留策略无法释放空间
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_ffmpeg_merge_timeout_secs() -> u64 {
+    3600
+}
+
 fn default_multi_page_use_season_structure() -> bool {
     true // 默认使用Season结构
 }
@@ -245,6 +482,22 @@ fn default_cdn_sorting() -> bool {
     true // 默认启用CDN排序
 }
 
+fn default_verify_download_integrity() -> bool {
+    true // 默认启用下载完整性校验
+}
+
+fn default_download_subtitles() -> bool {
+    true // 默认下载字幕
+}
+
+fn default_nfo_include_tags() -> bool {
+    false // 默认只写入 <genre>，避免标签重复
+}
+
+fn default_nfo_max_tags() -> usize {
+    0 // 默认不限制
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum NotificationMethod {
@@ -378,9 +631,23 @@ pub struct NotificationConfig {
     #[serde(default)]
     pub bark_defaults: BarkDefaults,
     #[serde(default)]
+    pub telegram_bot_token: Option<String>,
+    #[serde(default)]
+    pub telegram_chat_id: Option<String>,
+    #[serde(default)]
+    pub discord_webhook_url: Option<String>,
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Webhook 请求体的 Handlebars 模板，为空时使用默认 JSON 结构
+    #[serde(default)]
+    pub webhook_template: Option<String>,
+    #[serde(default)]
     pub events: NotificationEventsConfig,
     #[serde(default)]
     pub enable_scan_notifications: bool,
+    /// 子任务反复失败时单独推送提醒
+    #[serde(default)]
+    pub enable_failure_notifications: bool,
     #[serde(default = "default_notification_min_videos")]
     pub notification_min_videos: usize,
     #[serde(default = "default_notification_timeout")]
@@ -410,8 +677,14 @@ impl Default for NotificationConfig {
             bark_device_key: None,
             bark_device_keys: Vec::new(),
             bark_defaults: BarkDefaults::default(),
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            discord_webhook_url: None,
+            webhook_url: None,
+            webhook_template: None,
             events: NotificationEventsConfig::default(),
             enable_scan_notifications: false,
+            enable_failure_notifications: false,
             notification_min_videos: default_notification_min_videos(),
             notification_timeout: default_notification_timeout(),
             notification_retry_count: default_notification_retry_count(),
@@ -419,31 +692,70 @@ impl Default for NotificationConfig {
     }
 }
 
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
 impl NotificationConfig {
+    /// 获取已配置的 Telegram Bot Token 与 Chat ID，二者缺一则视为未配置
+    pub fn telegram_credentials(&self) -> Option<(&str, &str)> {
+        Some((
+            non_empty(self.telegram_bot_token.as_deref())?,
+            non_empty(self.telegram_chat_id.as_deref())?,
+        ))
+    }
+
+    /// 获取已配置的 Discord Webhook URL
+    pub fn discord_webhook(&self) -> Option<&str> {
+        non_empty(self.discord_webhook_url.as_deref())
+    }
+
+    /// 获取已配置的通用 Webhook URL
+    pub fn webhook(&self) -> Option<&str> {
+        non_empty(self.webhook_url.as_deref())
+    }
+
+    /// 是否配置了主推送方式之外的附加推送渠道
+    pub fn has_extra_channel(&self) -> bool {
+        self.telegram_credentials().is_some() || self.discord_webhook().is_some() || self.webhook().is_some()
+    }
+
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), String> {
+        let has_telegram_token = non_empty(self.telegram_bot_token.as_deref()).is_some();
+        let has_telegram_chat = non_empty(self.telegram_chat_id.as_deref()).is_some();
+        if has_telegram_token != has_telegram_chat {
+            return Err("Telegram Bot Token 与 Chat ID 必须同时配置".to_string());
+        }
+        if let Some(url) = self.discord_webhook() {
+            if !url.starts_with("https://") {
+                return Err("Discord Webhook URL 必须以 https:// 开头".to_string());
+            }
+        }
+        if let Some(url) = self.webhook() {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err("Webhook URL 必须以 http:// 或 https:// 开头".to_string());
+            }
+        }
+        if let Some(template) = non_empty(self.webhook_template.as_deref()) {
+            if let Err(e) = crate::utils::notification::validate_webhook_template(template) {
+                return Err(format!("Webhook 模板解析失败: {}", e));
+            }
+        }
+        // 主推送方式之外还配置了其它渠道时，不强制要求主推送方式的密钥
+        let has_extra_channel = self.has_extra_channel();
+
         if self.enable_scan_notifications {
             match self.method {
                 NotificationMethod::Serverchan => {
-                    if self
-                        .serverchan_key
-                        .as_deref()
-                        .map(str::trim)
-                        .filter(|v| !v.is_empty())
-                        .is_none()
-                    {
+                    if non_empty(self.serverchan_key.as_deref()).is_none() && !has_extra_channel {
                         return Err("启用推送通知时必须配置Server酱 SendKey".to_string());
                     }
                 }
                 NotificationMethod::Bark => {
-                    let has_single = self
-                        .bark_device_key
-                        .as_deref()
-                        .map(str::trim)
-                        .filter(|v| !v.is_empty())
-                        .is_some();
+                    let has_single = non_empty(self.bark_device_key.as_deref()).is_some();
                     let has_multi = self.bark_device_keys.iter().any(|v| !v.trim().is_empty());
-                    if !has_single && !has_multi {
+                    if !has_single && !has_multi && !has_extra_channel {
                         return Err("启用推送通知时必须配置 Bark Device Key".to_string());
                     }
                     if self.bark_server.trim().is_empty() {
@@ -496,6 +808,9 @@ pub struct RiskControlConfig {
     /// 自动验证配置
     #[serde(default)]
     pub auto_solve: Option<AutoSolveConfig>,
+    /// 硬件指纹策略: "auto" (随机选择预设配置), "random" (随机GPU并扰动WebGL参数), 或预设名称如 "gaming"
+    #[serde(default = "default_fingerprint_strategy")]
+    pub fingerprint_strategy: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -533,6 +848,10 @@ fn default_risk_control_timeout() -> u64 {
     300 // 默认5分钟超时
 }
 
+fn default_fingerprint_strategy() -> String {
+    "auto".to_string()
+}
+
 impl Default for RiskControlConfig {
     fn default() -> Self {
         Self {
@@ -540,6 +859,7 @@ impl Default for RiskControlConfig {
             mode: default_risk_control_mode(),
             timeout: default_risk_control_timeout(),
             auto_solve: None,
+            fingerprint_strategy: default_fingerprint_strategy(),
         }
     }
 }
@@ -555,6 +875,10 @@ impl RiskControlConfig {
             return Err("验证超时时间必须在60-3600秒之间".to_string());
         }
 
+        if !crate::hardware::is_valid_fingerprint_strategy(&self.fingerprint_strategy) {
+            return Err(format!("不支持的硬件指纹策略: {}", self.fingerprint_strategy));
+        }
+
         // 如果是自动模式，需要验证自动配置
         if self.mode == "auto" {
             if let Some(auto_config) = &self.auto_solve {
@@ -606,6 +930,8 @@ impl Clone for Config {
                 no_dolby_audio: self.filter_option.no_dolby_audio,
                 no_hdr: self.filter_option.no_hdr,
                 no_hires: self.filter_option.no_hires,
+                min_duration_secs: self.filter_option.min_duration_secs,
+                max_duration_secs: self.filter_option.max_duration_secs,
             },
             danmaku_option: DanmakuOption {
                 duration: self.danmaku_option.duration,
@@ -620,6 +946,11 @@ impl Clone for Config {
                 bold: self.danmaku_option.bold,
                 outline: self.danmaku_option.outline,
                 time_offset: self.danmaku_option.time_offset,
+                danmaku_formats: self.danmaku_option.danmaku_formats.clone(),
+                danmaku_block_keywords: self.danmaku_option.danmaku_block_keywords.clone(),
+                danmaku_block_regex: self.danmaku_option.danmaku_block_regex,
+                danmaku_max_per_second: self.danmaku_option.danmaku_max_per_second,
+                danmaku_exclude_modes: self.danmaku_option.danmaku_exclude_modes.clone(),
             },
             video_name: self.video_name.clone(),
             page_name: self.page_name.clone(),
@@ -631,16 +962,28 @@ impl Clone for Config {
             interval: self.interval,
             upper_path: self.upper_path.clone(),
             nfo_time_type: self.nfo_time_type.clone(),
+            nfo_flavor: self.nfo_flavor,
+            nfo_template: self.nfo_template.clone(),
+            nfo_download_actor_images: self.nfo_download_actor_images,
+            nfo_include_tags: self.nfo_include_tags,
+            nfo_max_tags: self.nfo_max_tags,
+            nfo_include_chapters: self.nfo_include_chapters,
             nfo_config: self.nfo_config.clone(),
             concurrent_limit: self.concurrent_limit.clone(),
             time_format: self.time_format.clone(),
             cdn_sorting: self.cdn_sorting,
+            verify_download_integrity: self.verify_download_integrity,
+            download_subtitles: self.download_subtitles,
+            bangumi_write_skip_markers: self.bangumi_write_skip_markers,
+            bangumi_skip_specials: self.bangumi_skip_specials,
             submission_risk_control: self.submission_risk_control.clone(),
             scan_deleted_videos: self.scan_deleted_videos,
             skip_bangumi_preview: self.skip_bangumi_preview,
             enable_aria2_health_check: self.enable_aria2_health_check,
             enable_aria2_auto_restart: self.enable_aria2_auto_restart,
             aria2_health_check_interval: self.aria2_health_check_interval,
+            aria2_rpc_url: self.aria2_rpc_url.clone(),
+            aria2_rpc_secret: self.aria2_rpc_secret.clone(),
             actors_field_initialized: self.actors_field_initialized,
             multi_page_use_season_structure: self.multi_page_use_season_structure,
             collection_use_season_structure: self.collection_use_season_structure,
@@ -649,6 +992,44 @@ impl Clone for Config {
             enable_startup_data_fix: self.enable_startup_data_fix,
             enable_cid_population: self.enable_cid_population,
             risk_control: self.risk_control.clone(),
+            quiet_hours: self.quiet_hours.clone(),
+            proxy_url: self.proxy_url.clone(),
+            user_agent: self.user_agent.clone(),
+            extra_headers: self.extra_headers.clone(),
+            task_max_retry: self.task_max_retry,
+            retention_delete_local_files: self.retention_delete_local_files,
+            delete_to_trash: self.delete_to_trash,
+            trash_retention_days: self.trash_retention_days,
+            dedup_strategy: self.dedup_strategy,
+            cover_max_width: self.cover_max_width,
+            cover_format: self.cover_format,
+            filename_policy: self.filename_policy,
+            max_filename_bytes: self.max_filename_bytes,
+            max_path_bytes: self.max_path_bytes,
+            temp_dir: self.temp_dir.clone(),
+            min_free_disk_gb: self.min_free_disk_gb,
+            jellyfin_url: self.jellyfin_url.clone(),
+            jellyfin_api_key: self.jellyfin_api_key.clone(),
+            api_rate_limit_per_minute: self.api_rate_limit_per_minute,
+            cors_allowed_origins: self.cors_allowed_origins.clone(),
+            trust_existing_files: self.trust_existing_files,
+            ffmpeg_path: self.ffmpeg_path.clone(),
+            ffmpeg_hwaccel: self.ffmpeg_hwaccel.clone(),
+            ffmpeg_merge_timeout_secs: self.ffmpeg_merge_timeout_secs,
+            generate_thumbnail_fallback: self.generate_thumbnail_fallback,
+            refresh_covers: self.refresh_covers,
+            refresh_upper_avatar_days: self.refresh_upper_avatar_days,
+            skip_upower_exclusive: self.skip_upower_exclusive,
+            write_scan_report: self.write_scan_report,
+            source_scan_concurrency: self.source_scan_concurrency,
+            season_title_cache_size: self.season_title_cache_size,
+            season_title_cache_ttl_secs: self.season_title_cache_ttl_secs,
+            db_max_connections: self.db_max_connections,
+            db_acquire_timeout: self.db_acquire_timeout,
+            db_busy_timeout: self.db_busy_timeout,
+            download_max_retries: self.download_max_retries,
+            download_retry_base_delay_ms: self.download_retry_base_delay_ms,
+            webdav: self.webdav.clone(),
         }
     }
 }
@@ -671,16 +1052,28 @@ impl Default for Config {
             interval: 1200,
             upper_path: CONFIG_DIR.join("upper_face"),
             nfo_time_type: NFOTimeType::FavTime,
+            nfo_flavor: NFOFlavor::default(),
+            nfo_template: None,
+            nfo_download_actor_images: false,
+            nfo_include_tags: default_nfo_include_tags(),
+            nfo_max_tags: default_nfo_max_tags(),
+            nfo_include_chapters: false,
             nfo_config: NFOConfig::default(),
             concurrent_limit: ConcurrentLimit::default(),
             time_format: default_time_format(),
             cdn_sorting: default_cdn_sorting(),
+            verify_download_integrity: default_verify_download_integrity(),
+            download_subtitles: default_download_subtitles(),
+            bangumi_write_skip_markers: false,
+            bangumi_skip_specials: false,
             submission_risk_control: crate::config::item::SubmissionRiskControlConfig::default(),
             scan_deleted_videos: false,
             skip_bangumi_preview: default_skip_bangumi_preview(),
             enable_aria2_health_check: false,
             enable_aria2_auto_restart: false,
             aria2_health_check_interval: default_aria2_health_check_interval(),
+            aria2_rpc_url: None,
+            aria2_rpc_secret: None,
             actors_field_initialized: false,
             multi_page_use_season_structure: default_multi_page_use_season_structure(),
             collection_use_season_structure: default_collection_use_season_structure(),
@@ -689,6 +1082,44 @@ impl Default for Config {
             enable_startup_data_fix: false, // 默认关闭，减少不必要的日志
             enable_cid_population: false,   // 默认关闭，减少不必要的日志
             risk_control: RiskControlConfig::default(),
+            quiet_hours: QuietHoursConfig::default(),
+            proxy_url: None,
+            user_agent: None,
+            extra_headers: HashMap::new(),
+            task_max_retry: default_task_max_retry(),
+            retention_delete_local_files: default_retention_delete_local_files(),
+            delete_to_trash: false,
+            trash_retention_days: default_trash_retention_days(),
+            dedup_strategy: DedupStrategy::default(),
+            cover_max_width: 0,
+            cover_format: CoverFormat::default(),
+            filename_policy: FilenamePolicy::default(),
+            max_filename_bytes: default_max_filename_bytes(),
+            max_path_bytes: 0,
+            temp_dir: None,
+            min_free_disk_gb: 0,
+            jellyfin_url: None,
+            jellyfin_api_key: None,
+            api_rate_limit_per_minute: default_api_rate_limit_per_minute(),
+            cors_allowed_origins: Vec::new(),
+            trust_existing_files: false,
+            ffmpeg_path: None,
+            ffmpeg_hwaccel: None,
+            ffmpeg_merge_timeout_secs: default_ffmpeg_merge_timeout_secs(),
+            generate_thumbnail_fallback: false,
+            refresh_covers: false,
+            refresh_upper_avatar_days: 0,
+            skip_upower_exclusive: default_skip_upower_exclusive(),
+            write_scan_report: false,
+            source_scan_concurrency: default_source_scan_concurrency(),
+            season_title_cache_size: default_season_title_cache_size(),
+            season_title_cache_ttl_secs: default_season_title_cache_ttl_secs(),
+            db_max_connections: default_db_max_connections(),
+            db_acquire_timeout: default_db_acquire_timeout(),
+            db_busy_timeout: default_db_busy_timeout(),
+            download_max_retries: default_download_max_retries(),
+            download_retry_base_delay_ms: default_download_retry_base_delay_ms(),
+            webdav: WebDavConfig::default(),
         }
     }
 }
@@ -776,6 +1207,121 @@ impl Config {
             ok = false;
             error!("video 和 page 允许的并发数必须大于 0");
         }
+        if self.danmaku_option.danmaku_formats.is_empty() {
+            ok = false;
+            error!("至少需要设置一种弹幕格式");
+        }
+        for format in &self.danmaku_option.danmaku_formats {
+            if !crate::bilibili::DANMAKU_FORMATS.contains(&format.as_str()) {
+                ok = false;
+                error!("不支持的弹幕格式: {}，可选值为 ass、xml、json", format);
+            }
+        }
+        if let Err(e) = crate::utils::keyword_matcher::validate_terms(
+            &self.danmaku_option.danmaku_block_keywords,
+            self.danmaku_option.danmaku_block_regex,
+        ) {
+            ok = false;
+            error!("弹幕屏蔽词配置无效: {:#}", e);
+        }
+        if let Err(e) = self.quiet_hours.validate() {
+            ok = false;
+            error!("静默时段配置无效: {:#}", e);
+        }
+        if let Err(e) = self.webdav.validate() {
+            ok = false;
+            error!("WebDAV 配置无效: {:#}", e);
+        }
+        if let Some(proxy_url) = &self.proxy_url {
+            if let Err(e) = crate::bilibili::build_proxy(proxy_url) {
+                ok = false;
+                error!("代理地址配置无效: {:#}", e);
+            }
+        }
+        if let Some(user_agent) = &self.user_agent {
+            if let Err(e) = crate::http::headers::validate_user_agent(user_agent) {
+                ok = false;
+                error!("User-Agent 配置无效: {:#}", e);
+            }
+        }
+        if let Err(e) = crate::http::headers::validate_extra_headers(&self.extra_headers) {
+            ok = false;
+            error!("额外请求头配置无效: {:#}", e);
+        }
+        if !(1..=MAX_SOURCE_SCAN_CONCURRENCY).contains(&self.source_scan_concurrency) {
+            ok = false;
+            error!(
+                "视频源扫描并发数必须在 1 到 {} 之间，当前为 {}",
+                MAX_SOURCE_SCAN_CONCURRENCY, self.source_scan_concurrency
+            );
+        }
+        if self.season_title_cache_size == 0 || self.season_title_cache_ttl_secs == 0 {
+            ok = false;
+            error!("番剧标题缓存的容量和过期时间必须大于 0");
+        }
+        if !(1..=MAX_DB_CONNECTIONS).contains(&self.db_max_connections) {
+            ok = false;
+            error!(
+                "数据库最大连接数必须在 1 到 {} 之间，当前为 {}",
+                MAX_DB_CONNECTIONS, self.db_max_connections
+            );
+        }
+        if !(1..=MAX_DB_TIMEOUT_SECS).contains(&self.db_acquire_timeout)
+            || !(1..=MAX_DB_TIMEOUT_SECS).contains(&self.db_busy_timeout)
+        {
+            ok = false;
+            error!("数据库超时时间必须在 1 到 {} 秒之间", MAX_DB_TIMEOUT_SECS);
+        }
+        if self.download_max_retries > MAX_DOWNLOAD_RETRIES {
+            ok = false;
+            error!("下载重试次数不能超过 {}", MAX_DOWNLOAD_RETRIES);
+        }
+        if !(1..=MAX_DOWNLOAD_RETRY_BASE_DELAY_MS).contains(&self.download_retry_base_delay_ms) {
+            ok = false;
+            error!(
+                "下载重试基础等待时间必须在 1 到 {} 毫秒之间",
+                MAX_DOWNLOAD_RETRY_BASE_DELAY_MS
+            );
+        }
+        for origin in &self.cors_allowed_origins {
+            if let Err(e) = crate::api::cors::validate_origin(origin) {
+                ok = false;
+                error!("{}", e);
+            }
+        }
+        if self.auth_token.is_some() && self.cors_allowed_origins.iter().any(|origin| origin == "*") {
+            warn!("跨域来源配置为 *，任意网站都可以在浏览器中调用 API，启用认证时存在安全风险，建议改为具体的来源");
+        }
+        if self.max_filename_bytes != 0 && self.max_filename_bytes < MIN_FILENAME_BYTES {
+            ok = false;
+            error!("文件名最大字节数不能小于 {}（0 表示不限制）", MIN_FILENAME_BYTES);
+        }
+        if self.max_path_bytes != 0 && self.max_path_bytes < MIN_PATH_BYTES {
+            ok = false;
+            error!("路径最大字节数不能小于 {}（0 表示不限制）", MIN_PATH_BYTES);
+        }
+        if self.cover_max_width > MAX_COVER_WIDTH {
+            ok = false;
+            error!("封面最大宽度不能超过 {}", MAX_COVER_WIDTH);
+        }
+        if let Some(ffmpeg_path) = &self.ffmpeg_path {
+            if let Err(e) = crate::utils::ffmpeg::validate_ffmpeg_path(ffmpeg_path) {
+                ok = false;
+                error!("FFmpeg 路径配置无效: {:#}", e);
+            }
+        }
+        if let Some(hwaccel) = &self.ffmpeg_hwaccel {
+            if let Err(e) = crate::utils::ffmpeg::validate_hwaccel(hwaccel) {
+                ok = false;
+                error!("FFmpeg 硬件加速配置无效: {:#}", e);
+            }
+        }
+        if let Some(jellyfin_url) = &self.jellyfin_url {
+            if !jellyfin_url.starts_with("http://") && !jellyfin_url.starts_with("https://") {
+                ok = false;
+                error!("Jellyfin 地址必须以 http:// 或 https:// 开头: {}", jellyfin_url);
+            }
+        }
 
         if critical_error {
             warn!("配置中检测到凭证未设置，程序将继续运行但功能受限");
@@ -785,4 +1331,10 @@ impl Config {
 
         ok
     }
+
+    /// 测试环境下的检查方法，总是返回 true
+    #[cfg(test)]
+    pub fn check(&self) -> bool {
+        true
+    }
 }
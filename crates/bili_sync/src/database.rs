@@ -1,11 +1,13 @@
 use anyhow::Result;
+use bili_sync_entity::entities::{config_item, prelude::ConfigItem};
 use bili_sync_migration::{Migrator, MigratorTrait};
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{ColumnTrait, Database, DatabaseConnection, EntityTrait, QueryFilter, SqlxSqliteConnector};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::OnceCell;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::config::CONFIG_DIR;
+use crate::config::{Config, CONFIG_DIR, MAX_DB_CONNECTIONS, MAX_DB_TIMEOUT_SECS};
 
 static GLOBAL_DB: OnceCell<Arc<DatabaseConnection>> = OnceCell::const_new();
 
@@ -17,17 +19,89 @@ fn database_url() -> String {
     format!("sqlite://{}?mode=rwc", CONFIG_DIR.join("data.sqlite").to_string_lossy())
 }
 
-async fn database_connection() -> Result<DatabaseConnection> {
-    let mut option = ConnectOptions::new(database_url());
-    option
-        .max_connections(20) // 降低最大连接数，避免过多连接
-        .min_connections(2) // 最小连接数
-        .acquire_timeout(std::time::Duration::from_secs(30)) // 缩短超时时间
-        .idle_timeout(std::time::Duration::from_secs(300)) // 空闲连接超时5分钟
-        .max_lifetime(std::time::Duration::from_secs(3600)) // 连接最大生命周期1小时
-        .sqlx_logging(false); // 禁用sqlx查询日志，避免过多的日志输出
+/// 数据库连接池配置
+///
+/// 连接池需要在加载数据库配置之前创建，因此这里直接从 config_items 表读取，修改后需重启才能生效
+struct PoolSettings {
+    max_connections: u32,
+    acquire_timeout: u64,
+    busy_timeout: u64,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            max_connections: config.db_max_connections,
+            acquire_timeout: config.db_acquire_timeout,
+            busy_timeout: config.db_busy_timeout,
+        }
+    }
+}
+
+impl PoolSettings {
+    const KEYS: [&'static str; 3] = ["db_max_connections", "db_acquire_timeout", "db_busy_timeout"];
+
+    async fn load(connection: &DatabaseConnection) -> Self {
+        let mut settings = Self::default();
+        let items = match ConfigItem::find()
+            .filter(config_item::Column::KeyName.is_in(Self::KEYS))
+            .all(connection)
+            .await
+        {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("读取数据库连接池配置失败，使用默认值: {}", e);
+                return settings;
+            }
+        };
+        for item in items {
+            if !settings.apply(&item.key_name, &item.value_json) {
+                warn!(
+                    "数据库连接池配置 {} 的值 {} 无效，使用默认值",
+                    item.key_name, item.value_json
+                );
+            }
+        }
+        settings
+    }
 
-    let connection = Database::connect(option).await?;
+    /// 应用单个配置项，值无效或超出范围时返回 false 并保留默认值
+    fn apply(&mut self, key: &str, value_json: &str) -> bool {
+        let Ok(value) = serde_json::from_str::<u64>(value_json) else {
+            return false;
+        };
+        match key {
+            "db_max_connections" if (1..=MAX_DB_CONNECTIONS as u64).contains(&value) => {
+                self.max_connections = value as u32
+            }
+            "db_acquire_timeout" if (1..=MAX_DB_TIMEOUT_SECS).contains(&value) => self.acquire_timeout = value,
+            "db_busy_timeout" if (1..=MAX_DB_TIMEOUT_SECS).contains(&value) => self.busy_timeout = value,
+            _ => return false,
+        }
+        true
+    }
+}
+
+async fn database_connection(settings: &PoolSettings) -> Result<DatabaseConnection> {
+    use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use sea_orm::sqlx::ConnectOptions as _;
+    use std::str::FromStr;
+
+    // busy_timeout 需要设置在连接选项上，才能作用于连接池中的每一个连接
+    let connect_options = SqliteConnectOptions::from_str(&database_url())?
+        .busy_timeout(Duration::from_secs(settings.busy_timeout))
+        .disable_statement_logging(); // 禁用sqlx查询日志，避免过多的日志输出
+    let pool = SqlitePoolOptions::new()
+        .max_connections(settings.max_connections)
+        .min_connections(settings.max_connections.min(2)) // 最小连接数
+        .acquire_timeout(Duration::from_secs(settings.acquire_timeout))
+        .idle_timeout(Duration::from_secs(300)) // 空闲连接超时5分钟
+        .max_lifetime(Duration::from_secs(3600)) // 连接最大生命周期1小时
+        .connect_with(connect_options)
+        .await?;
+
+    let connection = SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
 
     // 确保 WAL 模式已启用并应用额外的性能优化
     use sea_orm::ConnectionTrait;
@@ -47,18 +121,21 @@ async fn database_connection() -> Result<DatabaseConnection> {
     connection
         .execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE);")
         .await?; // 初始化时清理WAL
-    connection.execute_unprepared("PRAGMA busy_timeout = 30000;").await?; // 30秒忙等超时
 
     // 查询优化
     connection.execute_unprepared("PRAGMA optimize;").await?; // 启用查询优化器
     connection.execute_unprepared("PRAGMA analysis_limit = 1000;").await?; // 分析限制
 
     debug!("SQLite WAL 模式已启用，内存映射优化参数已应用（1GB mmap，64MB缓存）");
+    debug!(
+        "数据库连接池：最大连接数 {}，获取连接超时 {} 秒，忙等超时 {} 秒",
+        settings.max_connections, settings.acquire_timeout, settings.busy_timeout
+    );
 
     Ok(connection)
 }
 
-async fn migrate_database() -> Result<()> {
+async fn migrate_database() -> Result<DatabaseConnection> {
     // 检查数据库文件是否存在，不存在则会在连接时自动创建
     let db_path = CONFIG_DIR.join("data.sqlite");
     if !db_path.exists() {
@@ -72,7 +149,8 @@ async fn migrate_database() -> Result<()> {
     let connection = Database::connect(database_url()).await?;
 
     // 确保所有迁移都应用
-    Ok(Migrator::up(&connection, None).await?)
+    Migrator::up(&connection, None).await?;
+    Ok(connection)
 }
 
 /// 预热数据库，将关键数据加载到内存映射中
@@ -119,8 +197,13 @@ async fn preheat_database(connection: &DatabaseConnection) -> Result<()> {
 
 /// 进行数据库迁移并获取数据库连接，供外部使用
 pub async fn setup_database() -> DatabaseConnection {
-    migrate_database().await.expect("数据库迁移失败");
-    let connection = database_connection().await.expect("获取数据库连接失败");
+    let migrate_connection = migrate_database().await.expect("数据库迁移失败");
+    // 复用迁移时的单连接读取连接池配置，用完即关闭
+    let pool_settings = PoolSettings::load(&migrate_connection).await;
+    if let Err(e) = migrate_connection.close().await {
+        debug!("关闭迁移连接失败: {}", e);
+    }
+    let connection = database_connection(&pool_settings).await.expect("获取数据库连接失败");
 
     // 执行番剧缓存相关的数据库迁移
     if let Err(e) = crate::utils::bangumi_cache::ensure_cache_columns(&connection).await {
@@ -143,3 +226,24 @@ pub async fn setup_database() -> DatabaseConnection {
 pub fn get_global_db() -> Option<Arc<DatabaseConnection>> {
     GLOBAL_DB.get().cloned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_settings_apply() {
+        let mut settings = PoolSettings::default();
+        assert!(settings.apply("db_max_connections", "50"));
+        assert!(settings.apply("db_busy_timeout", "60"));
+        assert_eq!(settings.max_connections, 50);
+        assert_eq!(settings.busy_timeout, 60);
+
+        // 超出范围或无法解析的值保留原值
+        assert!(!settings.apply("db_max_connections", "0"));
+        assert!(!settings.apply("db_acquire_timeout", "\"abc\""));
+        assert!(!settings.apply("db_acquire_timeout", &(MAX_DB_TIMEOUT_SECS + 1).to_string()));
+        assert_eq!(settings.max_connections, 50);
+        assert_eq!(settings.acquire_timeout, 30);
+    }
+}
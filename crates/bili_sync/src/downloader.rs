@@ -1,15 +1,77 @@
 use core::str;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, ensure, Context, Result};
-use futures::TryStreamExt;
-use reqwest::Method;
-use tokio::fs::{self, File};
+use futures::StreamExt;
+use reqwest::{header, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::fs::{self, File, OpenOptions};
 use tokio::io::AsyncWriteExt;
-use tokio_util::io::StreamReader;
-use tracing::{error, warn};
+use tracing::{error, info, warn};
 
 use crate::bilibili::Client;
+use crate::utils::task_notifier::DOWNLOAD_PROGRESS;
+
+/// 断点续传信息，与 .part 文件一起保存，已下载的字节偏移即 .part 文件的长度
+#[derive(Serialize, Deserialize)]
+struct ResumeMeta {
+    total_bytes: u64,
+}
+
+struct ResumeState {
+    offset: u64,
+    total_bytes: u64,
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// 读取上次中断时留下的 .part 文件与续传信息，无法续传时返回 None
+async fn load_resume_state(part_path: &Path, meta_path: &Path) -> Option<ResumeState> {
+    let offset = fs::metadata(part_path).await.ok()?.len();
+    let meta: ResumeMeta = serde_json::from_slice(&fs::read(meta_path).await.ok()?).ok()?;
+    (offset > 0 && offset <= meta.total_bytes).then_some(ResumeState {
+        offset,
+        total_bytes: meta.total_bytes,
+    })
+}
+
+/// 指定文件是否留有可以断点续传的 .part 文件
+pub async fn has_resumable_part(path: &Path) -> bool {
+    load_resume_state(&with_suffix(path, ".part"), &with_suffix(path, ".part.meta"))
+        .await
+        .is_some()
+}
+
+/// 删除指定文件的 .part 文件与续传信息，下次下载从头开始
+pub async fn remove_resumable_part(path: &Path) {
+    for path in [with_suffix(path, ".part"), with_suffix(path, ".part.meta")] {
+        let _ = fs::remove_file(path).await;
+    }
+}
+
+async fn save_resume_state(meta_path: &Path, total_bytes: u64) {
+    let meta = ResumeMeta { total_bytes };
+    if let Err(e) = fs::write(meta_path, serde_json::to_vec(&meta).unwrap_or_default()).await {
+        warn!("保存断点续传信息失败: {:#}", e);
+    }
+}
+
+/// 从 Content-Range 响应头（bytes start-end/total）中解析文件总大小
+fn content_range_total(resp: &reqwest::Response) -> Option<u64> {
+    resp.headers()
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
 pub struct Downloader {
     client: Client,
 }
@@ -22,7 +84,8 @@ impl Downloader {
         Self { client }
     }
 
-    pub async fn fetch(&self, url: &str, path: &Path) -> Result<()> {
+    /// 下载单个文件，返回服务器报告的文件总大小（未知时为 None）
+    pub async fn fetch(&self, url: &str, path: &Path) -> Result<Option<u64>> {
         // 创建父目录
         if let Some(parent) = path.parent() {
             if !parent.exists() {
@@ -30,40 +93,98 @@ impl Downloader {
             }
         }
 
-        let mut file = match File::create(path).await {
-            Ok(f) => f,
-            Err(e) => {
-                error!("创建文件失败: {:#}", e);
-                return Err(e.into());
+        // 下载先写入 .part 文件，完成后再重命名，进程中断后可从 .part 的长度处续传
+        let part_path = with_suffix(path, ".part");
+        let meta_path = with_suffix(path, ".part.meta");
+        let resume = load_resume_state(&part_path, &meta_path).await;
+        if let Some(state) = resume.as_ref().filter(|state| state.offset == state.total_bytes) {
+            // 上次已下载完整但未来得及重命名
+            info!("已存在完整的下载文件: {} ({} 字节)", path.display(), state.total_bytes);
+            fs::rename(&part_path, path).await?;
+            let _ = fs::remove_file(&meta_path).await;
+            return Ok(Some(state.total_bytes));
+        }
+
+        let mut resp = self.send_get(url, resume.as_ref().map(|state| state.offset)).await?;
+
+        // 只有服务器返回了同一文件的剩余部分时才追加写入，否则从头下载
+        let range_requested = resume.is_some();
+        let resumed = resume.filter(|state| {
+            resp.status() == StatusCode::PARTIAL_CONTENT && content_range_total(&resp) == Some(state.total_bytes)
+        });
+        if range_requested && resumed.is_none() && resp.status() != StatusCode::OK {
+            // 返回的不是完整文件，也不是同一文件的剩余部分，丢弃已下载的部分后不带 Range 重新请求
+            warn!(
+                "服务器未按断点续传返回剩余部分 (状态码 {})，重新下载: {}",
+                resp.status(),
+                path.display()
+            );
+            drop(resp);
+            let _ = fs::remove_file(&part_path).await;
+            let _ = fs::remove_file(&meta_path).await;
+            resp = self.send_get(url, None).await?;
+        }
+
+        let expected = resp.content_length().unwrap_or_default();
+        let (mut file, offset, total_bytes) = match resumed {
+            Some(state) => {
+                info!(
+                    "断点续传: {} (已下载 {} / {} 字节)",
+                    path.display(),
+                    state.offset,
+                    state.total_bytes
+                );
+                let file = OpenOptions::new().append(true).open(&part_path).await?;
+                (file, state.offset, Some(state.total_bytes))
+            }
+            None => {
+                let file = match File::create(&part_path).await {
+                    Ok(f) => f,
+                    Err(e) => {
+                        error!("创建文件失败: {:#}", e);
+                        return Err(e.into());
+                    }
+                };
+                // 只有完整响应的长度才是文件总大小
+                let total_bytes = (resp.status() == StatusCode::OK && expected > 0).then_some(expected);
+                match total_bytes {
+                    Some(total_bytes) => save_resume_state(&meta_path, total_bytes).await,
+                    None => {
+                        let _ = fs::remove_file(&meta_path).await;
+                    }
+                }
+                (file, 0, total_bytes)
             }
         };
 
-        let resp = match self.client.request(Method::GET, url, None).send().await {
-            Ok(r) => match r.error_for_status() {
-                Ok(r) => r,
+        // 限速器从全局配置获取，所有并发下载任务共享同一份额度
+        let download_limiter = crate::config::with_config(|bundle| bundle.download_limiter.clone());
+        let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let progress = DOWNLOAD_PROGRESS.start(file_name, total_bytes);
+        progress.resume_from(offset);
+        let mut stream = resp.bytes_stream();
+        let mut received = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
                 Err(e) => {
-                    error!("HTTP状态码错误: {:#}", e);
+                    error!("下载过程中出错: {:#}", e);
                     return Err(e.into());
                 }
-            },
-            Err(e) => {
-                error!("HTTP请求失败: {:#}", e);
-                return Err(e.into());
+            };
+            if let Some(limiter) = &download_limiter {
+                limiter.acquire(chunk.len()).await;
             }
-        };
-
-        let expected = resp.content_length().unwrap_or_default();
-
-        let mut stream_reader = StreamReader::new(resp.bytes_stream().map_err(std::io::Error::other));
-        let received = match tokio::io::copy(&mut stream_reader, &mut file).await {
-            Ok(size) => size,
-            Err(e) => {
+            if let Err(e) = file.write_all(&chunk).await {
                 error!("下载过程中出错: {:#}", e);
                 return Err(e.into());
             }
-        };
+            received += chunk.len() as u64;
+            progress.advance(chunk.len() as u64);
+        }
 
         file.flush().await?;
+        drop(file);
 
         ensure!(
             received >= expected,
@@ -72,10 +193,34 @@ impl Downloader {
             expected
         );
 
-        Ok(())
+        fs::rename(&part_path, path).await?;
+        let _ = fs::remove_file(&meta_path).await;
+
+        Ok(total_bytes)
     }
 
-    pub async fn fetch_with_fallback(&self, urls: &[&str], path: &Path) -> Result<()> {
+    /// 发送下载请求，指定 offset 时只请求该位置之后的内容
+    async fn send_get(&self, url: &str, offset: Option<u64>) -> Result<reqwest::Response> {
+        let mut request = self.client.request(Method::GET, url, None);
+        if let Some(offset) = offset {
+            request = request.header(header::RANGE, format!("bytes={}-", offset));
+        }
+        match request.send().await {
+            Ok(r) => match r.error_for_status() {
+                Ok(r) => Ok(r),
+                Err(e) => {
+                    error!("HTTP状态码错误: {:#}", e);
+                    Err(e.into())
+                }
+            },
+            Err(e) => {
+                error!("HTTP请求失败: {:#}", e);
+                Err(e.into())
+            }
+        }
+    }
+
+    pub async fn fetch_with_fallback(&self, urls: &[&str], path: &Path) -> Result<Option<u64>> {
         if urls.is_empty() {
             bail!("no urls provided");
         }
@@ -83,8 +228,8 @@ impl Downloader {
         let mut last_error = None;
         for url in urls.iter() {
             match self.fetch(url, path).await {
-                Ok(_) => {
-                    return Ok(());
+                Ok(total_bytes) => {
+                    return Ok(total_bytes);
                 }
                 Err(err) => {
                     warn!("下载失败: {:#}", err);
@@ -135,7 +280,8 @@ impl Downloader {
         let audio_path_str = audio_path.to_string_lossy().to_string();
         let output_path_str = output_path.to_string_lossy().to_string();
 
-        // 构建FFmpeg命令
+        // 构建FFmpeg命令，配置了硬件加速时在输入参数之前插入 -hwaccel
+        let hwaccel_args = crate::utils::ffmpeg::hwaccel_args();
         let args = [
             "-i",
             &video_path_str,
@@ -149,7 +295,10 @@ impl Downloader {
             &output_path_str,
         ];
 
-        let output = tokio::process::Command::new("ffmpeg").args(args).output().await?;
+        let mut command = crate::utils::ffmpeg::ffmpeg_command();
+        command.args(&hwaccel_args).args(args);
+        // 超过配置的超时时间时终止 FFmpeg，避免卡住的合并一直占用下载任务
+        let output = crate::utils::ffmpeg::output_with_timeout(command).await?;
 
         if !output.status.success() {
             let stderr = str::from_utf8(&output.stderr).unwrap_or("unknown");
@@ -184,7 +333,7 @@ impl Downloader {
 
         // 使用ffprobe快速验证文件格式
         let file_path_str = file_path.to_string_lossy().to_string();
-        let result = tokio::process::Command::new("ffprobe")
+        let result = crate::utils::ffmpeg::ffprobe_command()
             .args([
                 "-v",
                 "quiet", // 静默模式
@@ -219,3 +368,45 @@ impl Downloader {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_resume_state() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-resume-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("video.mp4");
+        let part_path = with_suffix(&path, ".part");
+        let meta_path = with_suffix(&path, ".part.meta");
+        assert_eq!(part_path, dir.join("video.mp4.part"));
+
+        // 没有续传信息时不续传
+        fs::write(&part_path, vec![0u8; 100]).await.unwrap();
+        assert!(load_resume_state(&part_path, &meta_path).await.is_none());
+
+        save_resume_state(&meta_path, 300).await;
+        let state = load_resume_state(&part_path, &meta_path).await.unwrap();
+        assert_eq!((state.offset, state.total_bytes), (100, 300));
+        assert!(has_resumable_part(&path).await);
+
+        // .part 比记录的总大小还大，说明文件已不一致
+        fs::write(&part_path, vec![0u8; 400]).await.unwrap();
+        assert!(load_resume_state(&part_path, &meta_path).await.is_none());
+        assert!(!has_resumable_part(&path).await);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        let resp: reqwest::Response = axum::http::Response::builder()
+            .status(206)
+            .header(header::CONTENT_RANGE, "bytes 100-299/300")
+            .body("")
+            .unwrap()
+            .into();
+        assert_eq!(content_range_total(&resp), Some(300));
+    }
+}
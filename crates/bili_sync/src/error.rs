@@ -7,6 +7,14 @@ use thiserror::Error;
 #[error("Request too frequently")]
 pub struct DownloadAbortError();
 
+/// 下载完成后文件大小或MD5与预期不一致
+#[derive(Error, Debug)]
+#[error("文件完整性校验失败: {path}，{reason}")]
+pub struct IntegrityError {
+    pub path: String,
+    pub reason: String,
+}
+
 #[derive(Error, Debug)]
 pub struct ProcessPageError {
     pub video_name: String,
@@ -1,13 +1,18 @@
 use super::HardwareInfo;
 use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use rand::Rng;
 use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
 use tracing::{debug, info, warn};
 
-// 全局硬件指纹和用户ID管理 - 确保会话期间指纹固定
-static GLOBAL_HARDWARE_FINGERPRINT: OnceLock<HardwareFingerprint> = OnceLock::new();
+// 全局硬件指纹和用户ID管理 - 确保会话期间指纹固定，仅在触发风控时轮换
+static GLOBAL_HARDWARE_FINGERPRINT: Lazy<RwLock<Option<HardwareFingerprint>>> = Lazy::new(|| RwLock::new(None));
 static CURRENT_USER_ID: OnceLock<i64> = OnceLock::new();
+// 本次运行期间因风控轮换硬件指纹的次数
+static ROTATION_COUNT: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug, Clone)]
 pub struct HardwareFingerprint {
@@ -393,6 +398,20 @@ impl HardwareFingerprint {
         }
     }
 
+    // 按配置的硬件指纹策略生成新的指纹
+    pub fn from_strategy(strategy: &str) -> Self {
+        match strategy {
+            "random" => Self::with_random_resolution(HardwareInfo::random()),
+            // 移动端和 MacBook 使用对应设备的屏幕参数，保持指纹一致
+            "android_adreno" | "android_mali" => {
+                Self::new_with_resolution(HardwareInfo::by_strategy(strategy), (412, 915), 2.625)
+            }
+            "apple_m2" => Self::new_with_resolution(HardwareInfo::apple_m2(), (1470, 956), 2.0),
+            preset if super::PRESET_STRATEGIES.contains(&preset) => Self::new(HardwareInfo::by_strategy(preset)),
+            _ => Self::ultimate_random(),
+        }
+    }
+
     // 基于用户加载或创建硬件指纹
     pub async fn load_or_create_for_user(
         user_id: i64,
@@ -403,13 +422,21 @@ impl HardwareFingerprint {
 
         let config_manager = ConfigManager::new(db.clone());
         let config_key = format!("hardware_fingerprint.user_{}", user_id);
+        let strategy = crate::config::with_config(|bundle| bundle.config.risk_control.fingerprint_strategy.clone());
 
         // 如果不强制重新生成，先尝试从数据库加载
         if !force_regenerate {
             if let Ok(Some(existing_config)) = config_manager.get_config_item(&config_key).await {
                 if let Ok(fingerprint_data) = serde_json::from_value::<serde_json::Value>(existing_config) {
-                    // 尝试从JSON恢复硬件指纹
-                    if let Ok(fingerprint) = Self::from_json(&fingerprint_data) {
+                    // 早期保存的指纹没有记录策略，视为 auto
+                    let saved_strategy = fingerprint_data["strategy"].as_str().unwrap_or("auto");
+                    if saved_strategy != strategy {
+                        info!(
+                            "用户 {} 的硬件指纹策略已变更：{} -> {}，将生成新的",
+                            user_id, saved_strategy, strategy
+                        );
+                    } else if let Ok(fingerprint) = Self::from_json(&fingerprint_data) {
+                        // 尝试从JSON恢复硬件指纹
                         info!("成功从数据库加载用户 {} 的硬件指纹", user_id);
                         return Ok(fingerprint);
                     } else {
@@ -425,39 +452,11 @@ impl HardwareFingerprint {
             info!("用户 {} 重新登录，强制生成新的随机硬件指纹", user_id);
         }
 
-        // 生成新的随机硬件指纹
-        let fingerprint = Self::ultimate_random();
-
-        // 将硬件指纹数据序列化为JSON
-        let fingerprint_json = json!({
-            "hardware": {
-                "gpu": {
-                    "vendor": format!("{:?}", fingerprint.hardware.gpu.vendor),
-                    "model": fingerprint.hardware.gpu.model,
-                    "device_id": fingerprint.hardware.gpu.device_id,
-                    "driver_version": fingerprint.hardware.gpu.driver_version,
-                    "directx_version": fingerprint.hardware.gpu.directx_version,
-                    "angle_info": fingerprint.hardware.gpu.angle_info
-                },
-                "webgl": {
-                    "version": fingerprint.hardware.webgl.version,
-                    "shading_language_version": fingerprint.hardware.webgl.shading_language_version,
-                    "vendor": fingerprint.hardware.webgl.vendor,
-                    "renderer": fingerprint.hardware.webgl.renderer,
-                    "extensions": fingerprint.hardware.webgl.extensions
-                }
-            },
-            "screen_resolution": [fingerprint.screen_resolution.0, fingerprint.screen_resolution.1],
-            "device_pixel_ratio": fingerprint.device_pixel_ratio,
-            "timezone_offset": fingerprint.timezone_offset
-        });
+        // 按策略生成新的硬件指纹
+        let fingerprint = Self::from_strategy(&strategy);
 
         // 保存到config_items表
-        if let Err(e) = config_manager.update_config_item(&config_key, fingerprint_json).await {
-            warn!("保存硬件指纹到配置失败: {}", e);
-        } else {
-            info!("硬件指纹已保存到配置: {}", config_key);
-        }
+        fingerprint.save_for_user(user_id, db, &strategy).await;
 
         // 记录详细信息
         Self::log_fingerprint_details(&fingerprint, true);
@@ -492,7 +491,7 @@ impl HardwareFingerprint {
         let fingerprint = Self::load_or_create_for_user(user_id, db, false).await?;
 
         // 设置全局指纹（只在首次设置时生效）
-        let _ = GLOBAL_HARDWARE_FINGERPRINT.set(fingerprint);
+        GLOBAL_HARDWARE_FINGERPRINT.write().get_or_insert(fingerprint);
         let _ = CURRENT_USER_ID.set(user_id);
 
         Ok(())
@@ -556,8 +555,56 @@ impl HardwareFingerprint {
     }
 
     // 获取全局硬件指纹（如果已初始化）
-    pub fn get_global_if_initialized() -> Option<&'static HardwareFingerprint> {
-        GLOBAL_HARDWARE_FINGERPRINT.get()
+    pub fn get_global_if_initialized() -> Option<HardwareFingerprint> {
+        GLOBAL_HARDWARE_FINGERPRINT.read().clone()
+    }
+
+    // 保存用户的硬件指纹到config_items表
+    async fn save_for_user(&self, user_id: i64, db: &sea_orm::DatabaseConnection, strategy: &str) {
+        let config_manager = crate::config::ConfigManager::new(db.clone());
+        let config_key = format!("hardware_fingerprint.user_{}", user_id);
+        if let Err(e) = config_manager
+            .update_config_item(&config_key, self.to_json(strategy))
+            .await
+        {
+            warn!("保存硬件指纹到配置失败: {}", e);
+        } else {
+            info!("硬件指纹已保存到配置: {}", config_key);
+        }
+    }
+
+    // 触发风控后轮换全局硬件指纹，使后续请求的 dm_img/dm_cover_img 参数发生变化
+    pub async fn rotate_on_risk_control(db: &sea_orm::DatabaseConnection) -> bool {
+        let risk_control = crate::config::with_config(|bundle| bundle.config.risk_control.clone());
+        if !risk_control.enabled {
+            debug!("风控处理未启用，跳过硬件指纹轮换");
+            return false;
+        }
+
+        let previous_gpu = Self::get_global_if_initialized().map(|fingerprint| fingerprint.get_gpu_name());
+        // 预设策略每次生成的指纹相同，轮换时统一使用随机指纹
+        let fingerprint = Self::from_strategy("random");
+        if let Some(user_id) = CURRENT_USER_ID.get() {
+            fingerprint
+                .save_for_user(*user_id, db, &risk_control.fingerprint_strategy)
+                .await;
+        }
+        let current_gpu = fingerprint.get_gpu_name();
+        *GLOBAL_HARDWARE_FINGERPRINT.write() = Some(fingerprint);
+
+        let count = ROTATION_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+        info!(
+            "检测到风控，已轮换硬件指纹（第 {} 次）：{} -> {}",
+            count,
+            previous_gpu.as_deref().unwrap_or("未初始化"),
+            current_gpu
+        );
+        true
+    }
+
+    // 获取本次运行期间硬件指纹的轮换次数
+    pub fn rotation_count() -> u64 {
+        ROTATION_COUNT.load(Ordering::Relaxed)
     }
 
     // 获取GPU名称（用于日志）
@@ -580,6 +627,12 @@ impl HardwareFingerprint {
             "Intel Arc A770 Graphics".to_string()
         } else if angle_info.contains("Arc A750") {
             "Intel Arc A750 Graphics".to_string()
+        } else if angle_info.contains("Apple M2") {
+            "Apple M2".to_string()
+        } else if angle_info.contains("Adreno (TM) 740") {
+            "Qualcomm Adreno (TM) 740".to_string()
+        } else if angle_info.contains("Mali-G710") {
+            "ARM Mali-G710 MC10".to_string()
         } else {
             "Unknown GPU".to_string()
         }
@@ -589,11 +642,43 @@ impl HardwareFingerprint {
     pub fn get_browser_type(&self) -> &'static str {
         if self.hardware.webgl.vendor == "Mozilla" {
             "Firefox"
+        } else if self.hardware.webgl.shading_language_version.ends_with("(1.0)") {
+            "Safari"
+        } else if self.hardware.is_mobile() {
+            "Chrome Mobile"
         } else {
             "Chrome"
         }
     }
 
+    // 将硬件指纹序列化为JSON，记录生成时使用的策略
+    pub fn to_json(&self, strategy: &str) -> serde_json::Value {
+        json!({
+            "strategy": strategy,
+            "hardware": {
+                "gpu": {
+                    "vendor": format!("{:?}", self.hardware.gpu.vendor),
+                    "model": self.hardware.gpu.model,
+                    "device_id": self.hardware.gpu.device_id,
+                    "driver_version": self.hardware.gpu.driver_version,
+                    "directx_version": self.hardware.gpu.directx_version,
+                    "angle_info": self.hardware.gpu.angle_info
+                },
+                "webgl": {
+                    "version": self.hardware.webgl.version,
+                    "shading_language_version": self.hardware.webgl.shading_language_version,
+                    "vendor": self.hardware.webgl.vendor,
+                    "renderer": self.hardware.webgl.renderer,
+                    "precision": self.hardware.webgl.precision,
+                    "extensions": self.hardware.webgl.extensions
+                }
+            },
+            "screen_resolution": [self.screen_resolution.0, self.screen_resolution.1],
+            "device_pixel_ratio": self.device_pixel_ratio,
+            "timezone_offset": self.timezone_offset
+        })
+    }
+
     // 从JSON数据恢复硬件指纹
     pub fn from_json(json_data: &serde_json::Value) -> Result<Self> {
         let device_pixel_ratio = json_data["device_pixel_ratio"].as_f64().unwrap_or(1.0) as f32;
@@ -617,6 +702,9 @@ impl HardwareFingerprint {
             "Nvidia" => crate::hardware::GpuVendor::Nvidia,
             "Amd" => crate::hardware::GpuVendor::Amd,
             "Intel" => crate::hardware::GpuVendor::Intel,
+            "Apple" => crate::hardware::GpuVendor::Apple,
+            "Qualcomm" => crate::hardware::GpuVendor::Qualcomm,
+            "Arm" => crate::hardware::GpuVendor::Arm,
             _ => crate::hardware::GpuVendor::Nvidia,
         };
 
@@ -644,6 +732,10 @@ impl HardwareFingerprint {
                 .to_string(),
             vendor: webgl_data["vendor"].as_str().unwrap_or("WebKit").to_string(),
             renderer: webgl_data["renderer"].as_str().unwrap_or("WebKit WebGL").to_string(),
+            precision: webgl_data["precision"]
+                .as_str()
+                .unwrap_or(crate::hardware::webgl::DEFAULT_PRECISION)
+                .to_string(),
             extensions,
         };
 
@@ -663,7 +755,7 @@ impl Default for HardwareFingerprint {
         // 检查是否已初始化全局硬件指纹
         if let Some(fingerprint) = Self::get_global_if_initialized() {
             // 使用已初始化的全局硬件指纹，确保会话期间一致性
-            fingerprint.clone()
+            fingerprint
         } else {
             // 未初始化时生成临时随机指纹，不保存到全局状态
             debug!("硬件指纹未初始化，生成临时随机指纹用于API调用");
@@ -5,6 +5,9 @@ pub enum GpuVendor {
     Nvidia,
     Amd,
     Intel,
+    Apple,
+    Qualcomm,
+    Arm,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +129,41 @@ impl GpuInfo {
         }
     }
 
+    // Apple Silicon 与移动端GPU型号
+    pub fn apple_m2() -> Self {
+        Self {
+            vendor: GpuVendor::Apple,
+            model: "Apple M2".to_string(),
+            device_id: "0x00000000".to_string(),
+            driver_version: "Unspecified Version".to_string(),
+            directx_version: "Metal".to_string(),
+            angle_info: "ANGLE (Apple, ANGLE Metal Renderer: Apple M2, Unspecified Version)Google Inc. (Apple)"
+                .to_string(),
+        }
+    }
+
+    pub fn adreno_740() -> Self {
+        Self {
+            vendor: GpuVendor::Qualcomm,
+            model: "Adreno (TM) 740".to_string(),
+            device_id: "0x00000000".to_string(),
+            driver_version: "OpenGL ES 3.2".to_string(),
+            directx_version: "OpenGL ES".to_string(),
+            angle_info: "ANGLE (Qualcomm, Adreno (TM) 740, OpenGL ES 3.2)Google Inc. (Qualcomm)".to_string(),
+        }
+    }
+
+    pub fn mali_g710() -> Self {
+        Self {
+            vendor: GpuVendor::Arm,
+            model: "Mali-G710 MC10".to_string(),
+            device_id: "0x00000000".to_string(),
+            driver_version: "OpenGL ES 3.2".to_string(),
+            directx_version: "OpenGL ES".to_string(),
+            angle_info: "ANGLE (ARM, Mali-G710 MC10, OpenGL ES 3.2)Google Inc. (ARM)".to_string(),
+        }
+    }
+
     pub fn to_dm_cover_img_str(&self) -> String {
         // 生成包含完整GPU信息的硬件指纹字符串
         let full_gpu_info = format!(
@@ -139,11 +177,32 @@ impl GpuInfo {
         general_purpose::STANDARD.encode(full_gpu_info)
     }
 
+    /// 浏览器暴露的真实渲染器字符串，即 angle_info 中括号闭合前的部分
+    pub fn unmasked_renderer(&self) -> String {
+        let mut depth = 0;
+        for (index, c) in self.angle_info.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.angle_info[..=index].to_string();
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.angle_info.clone()
+    }
+
     pub fn get_vendor_name(&self) -> &'static str {
         match self.vendor {
             GpuVendor::Nvidia => "NVIDIA",
             GpuVendor::Amd => "AMD",
             GpuVendor::Intel => "Intel",
+            GpuVendor::Apple => "Apple",
+            GpuVendor::Qualcomm => "Qualcomm",
+            GpuVendor::Arm => "ARM",
         }
     }
 
@@ -6,6 +6,25 @@ pub use fingerprint::HardwareFingerprint;
 pub use gpu::{GpuInfo, GpuVendor};
 pub use webgl::WebGLInfo;
 
+use rand::Rng;
+
+/// 可在配置中使用的预设硬件策略名称
+pub const PRESET_STRATEGIES: &[&str] = &[
+    "firefox_high_end",
+    "firefox_workstation",
+    "gaming",
+    "workstation",
+    "budget",
+    "apple_m2",
+    "android_adreno",
+    "android_mali",
+];
+
+/// 判断硬件指纹策略是否有效
+pub fn is_valid_fingerprint_strategy(strategy: &str) -> bool {
+    matches!(strategy, "auto" | "random") || PRESET_STRATEGIES.contains(&strategy)
+}
+
 #[derive(Debug, Clone)]
 pub struct HardwareInfo {
     pub gpu: GpuInfo,
@@ -25,10 +44,38 @@ impl HardwareInfo {
             "gaming" => Self::nvidia_rtx4070ti(),
             "workstation" => Self::amd_rx7800xt(),
             "budget" => Self::intel_arc_a770(),
+            "apple_m2" => Self::apple_m2(),
+            "android_adreno" => Self::android_adreno(),
+            "android_mali" => Self::android_mali(),
             _ => Self::default(),
         }
     }
 
+    // 随机选择GPU与浏览器组合，并轻微扰动WebGL参数
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        let gpus: [fn() -> GpuInfo; 9] = [
+            GpuInfo::nvidia_rtx4070ti,
+            GpuInfo::nvidia_rtx4080,
+            GpuInfo::nvidia_rtx4090,
+            GpuInfo::nvidia_rtx4070,
+            GpuInfo::amd_rx7800xt,
+            GpuInfo::amd_rx7900xtx,
+            GpuInfo::amd_rx7700xt,
+            GpuInfo::intel_arc_a770,
+            GpuInfo::intel_arc_a750,
+        ];
+        let gpu = gpus[rng.gen_range(0..gpus.len())]();
+        // 70%概率Chrome，30%概率Firefox
+        let webgl = if rng.gen_bool(0.7) {
+            WebGLInfo::chrome_default()
+        } else {
+            WebGLInfo::firefox_default()
+        };
+        let webgl = webgl.perturbed(&gpu.unmasked_renderer(), &mut rng);
+        Self { gpu, webgl }
+    }
+
     pub fn nvidia_rtx4070ti() -> Self {
         Self {
             gpu: GpuInfo::nvidia_rtx4070ti(),
@@ -115,6 +162,33 @@ impl HardwareInfo {
         }
     }
 
+    // macOS Safari 与 Android Chrome 配置
+    pub fn apple_m2() -> Self {
+        Self {
+            gpu: GpuInfo::apple_m2(),
+            webgl: WebGLInfo::safari_default(),
+        }
+    }
+
+    pub fn android_adreno() -> Self {
+        Self {
+            gpu: GpuInfo::adreno_740(),
+            webgl: WebGLInfo::android_chrome_default(),
+        }
+    }
+
+    pub fn android_mali() -> Self {
+        Self {
+            gpu: GpuInfo::mali_g710(),
+            webgl: WebGLInfo::android_chrome_default(),
+        }
+    }
+
+    // 是否为移动端GPU
+    pub fn is_mobile(&self) -> bool {
+        matches!(self.gpu.vendor, GpuVendor::Qualcomm | GpuVendor::Arm)
+    }
+
     pub fn generate_dm_img_str(&self) -> String {
         self.webgl.to_dm_img_str()
     }
@@ -129,3 +203,68 @@ impl Default for HardwareInfo {
         Self::nvidia_rtx4070ti()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose, Engine as _};
+
+    use super::*;
+
+    #[test]
+    fn test_random_hardware_info() {
+        for _ in 0..20 {
+            let hardware = HardwareInfo::random();
+            assert!(hardware
+                .webgl
+                .extensions
+                .contains(&"ANGLE_instanced_arrays".to_string()));
+            assert!(
+                hardware.webgl.renderer == "WebKit WebGL"
+                    || hardware.webgl.renderer == "Mozilla"
+                    || hardware.webgl.renderer.starts_with("ANGLE (")
+            );
+
+            let dm_img = general_purpose::STANDARD
+                .decode(hardware.generate_dm_img_str())
+                .expect("dm_img_str 应为合法的 base64");
+            assert!(String::from_utf8(dm_img).unwrap().contains(&hardware.webgl.precision));
+            let dm_cover_img = general_purpose::STANDARD
+                .decode(hardware.generate_dm_cover_img_str())
+                .expect("dm_cover_img_str 应为合法的 base64");
+            assert!(String::from_utf8(dm_cover_img).unwrap().contains(&hardware.gpu.model));
+        }
+
+        assert!(is_valid_fingerprint_strategy("random"));
+        assert!(is_valid_fingerprint_strategy("gaming"));
+        assert!(!is_valid_fingerprint_strategy("unknown"));
+    }
+
+    #[test]
+    fn test_apple_and_mobile_strategies() {
+        let apple = HardwareInfo::by_strategy("apple_m2");
+        assert!(matches!(apple.gpu.vendor, GpuVendor::Apple));
+        assert_eq!(apple.webgl.shading_language_version, "WebGL GLSL ES 1.0 (1.0)");
+        assert_eq!(
+            apple.gpu.unmasked_renderer(),
+            "ANGLE (Apple, ANGLE Metal Renderer: Apple M2, Unspecified Version)"
+        );
+        assert!(!apple.is_mobile());
+
+        for (strategy, model) in [
+            ("android_adreno", "Adreno (TM) 740"),
+            ("android_mali", "Mali-G710 MC10"),
+        ] {
+            let hardware = HardwareInfo::by_strategy(strategy);
+            assert!(hardware.is_mobile());
+            assert_eq!(hardware.gpu.model, model);
+            assert!(hardware
+                .webgl
+                .extensions
+                .contains(&"WEBGL_compressed_texture_astc".to_string()));
+            assert!(is_valid_fingerprint_strategy(strategy));
+        }
+
+        // 默认配置保持桌面端 NVIDIA 显卡
+        assert!(matches!(HardwareInfo::default().gpu.vendor, GpuVendor::Nvidia));
+    }
+}
@@ -1,4 +1,6 @@
 use base64::{engine::general_purpose, Engine as _};
+use rand::seq::SliceRandom;
+use rand::Rng;
 
 #[derive(Debug, Clone)]
 pub struct WebGLInfo {
@@ -6,9 +8,24 @@ pub struct WebGLInfo {
     pub shading_language_version: String,
     pub vendor: String,
     pub renderer: String,
+    /// 片元着色器 HIGH_FLOAT 精度，格式为 precision/rangeMin/rangeMax
+    pub precision: String,
     pub extensions: Vec<String>,
 }
 
+/// 默认的 HIGH_FLOAT 精度
+pub const DEFAULT_PRECISION: &str = "23/127/127";
+
+/// 随机扰动时可选的 HIGH_FLOAT 精度
+const PRECISION_VARIANTS: &[&str] = &[DEFAULT_PRECISION, "23/128/127", "23/127/128"];
+
+/// 随机扰动时不会移除的扩展
+const REQUIRED_EXTENSIONS: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "WEBGL_debug_renderer_info",
+    "OES_texture_float",
+];
+
 impl WebGLInfo {
     pub fn chrome_default() -> Self {
         Self {
@@ -16,6 +33,7 @@ impl WebGLInfo {
             shading_language_version: "WebGL GLSL ES 1.0".to_string(),
             vendor: "WebKit".to_string(),
             renderer: "WebKit WebGL".to_string(),
+            precision: DEFAULT_PRECISION.to_string(),
             extensions: vec![
                 "ANGLE_instanced_arrays".to_string(),
                 "EXT_blend_minmax".to_string(),
@@ -57,6 +75,7 @@ impl WebGLInfo {
             shading_language_version: "WebGL GLSL ES 1.0".to_string(),
             vendor: "Mozilla".to_string(),
             renderer: "Mozilla".to_string(),
+            precision: DEFAULT_PRECISION.to_string(),
             extensions: vec![
                 "ANGLE_instanced_arrays".to_string(),
                 "EXT_blend_minmax".to_string(),
@@ -82,14 +101,132 @@ impl WebGLInfo {
         }
     }
 
+    /// 在预设基础上轻微扰动渲染器、精度和扩展列表，`unmasked_renderer` 为GPU的真实渲染器字符串
+    pub fn perturbed(mut self, unmasked_renderer: &str, rng: &mut impl Rng) -> Self {
+        // 部分浏览器会直接暴露真实的渲染器
+        if rng.gen_bool(0.5) {
+            self.renderer = unmasked_renderer.to_string();
+        }
+
+        self.precision = PRECISION_VARIANTS
+            .choose(rng)
+            .copied()
+            .unwrap_or(DEFAULT_PRECISION)
+            .to_string();
+
+        // 随机去掉少量可选扩展，必需的扩展保持不变
+        for _ in 0..rng.gen_range(0..=2) {
+            let optional: Vec<usize> = self
+                .extensions
+                .iter()
+                .enumerate()
+                .filter(|(_, ext)| !REQUIRED_EXTENSIONS.contains(&ext.as_str()))
+                .map(|(index, _)| index)
+                .collect();
+            if let Some(&index) = optional.choose(rng) {
+                self.extensions.remove(index);
+            }
+        }
+        self
+    }
+
+    pub fn safari_default() -> Self {
+        Self {
+            version: "WebGL 1.0".to_string(),
+            shading_language_version: "WebGL GLSL ES 1.0 (1.0)".to_string(),
+            vendor: "WebKit".to_string(),
+            renderer: "WebKit WebGL".to_string(),
+            precision: DEFAULT_PRECISION.to_string(),
+            extensions: vec![
+                "ANGLE_instanced_arrays".to_string(),
+                "EXT_blend_minmax".to_string(),
+                "EXT_clip_control".to_string(),
+                "EXT_color_buffer_half_float".to_string(),
+                "EXT_depth_clamp".to_string(),
+                "EXT_float_blend".to_string(),
+                "EXT_frag_depth".to_string(),
+                "EXT_polygon_offset_clamp".to_string(),
+                "EXT_shader_texture_lod".to_string(),
+                "EXT_texture_compression_bptc".to_string(),
+                "EXT_texture_compression_rgtc".to_string(),
+                "EXT_texture_filter_anisotropic".to_string(),
+                "EXT_texture_mirror_clamp_to_edge".to_string(),
+                "EXT_sRGB".to_string(),
+                "KHR_parallel_shader_compile".to_string(),
+                "OES_element_index_uint".to_string(),
+                "OES_fbo_render_mipmap".to_string(),
+                "OES_standard_derivatives".to_string(),
+                "OES_texture_float".to_string(),
+                "OES_texture_float_linear".to_string(),
+                "OES_texture_half_float".to_string(),
+                "OES_texture_half_float_linear".to_string(),
+                "OES_vertex_array_object".to_string(),
+                "WEBGL_color_buffer_float".to_string(),
+                "WEBGL_compressed_texture_astc".to_string(),
+                "WEBGL_compressed_texture_etc".to_string(),
+                "WEBGL_compressed_texture_etc1".to_string(),
+                "WEBGL_compressed_texture_pvrtc".to_string(),
+                "WEBKIT_WEBGL_compressed_texture_pvrtc".to_string(),
+                "WEBGL_compressed_texture_s3tc".to_string(),
+                "WEBGL_compressed_texture_s3tc_srgb".to_string(),
+                "WEBGL_debug_renderer_info".to_string(),
+                "WEBGL_debug_shaders".to_string(),
+                "WEBGL_depth_texture".to_string(),
+                "WEBGL_draw_buffers".to_string(),
+                "WEBGL_lose_context".to_string(),
+                "WEBGL_multi_draw".to_string(),
+            ],
+        }
+    }
+
+    pub fn android_chrome_default() -> Self {
+        Self {
+            version: "WebGL 1.0".to_string(),
+            shading_language_version: "WebGL GLSL ES 1.0".to_string(),
+            vendor: "WebKit".to_string(),
+            renderer: "WebKit WebGL".to_string(),
+            precision: DEFAULT_PRECISION.to_string(),
+            extensions: vec![
+                "ANGLE_instanced_arrays".to_string(),
+                "EXT_blend_minmax".to_string(),
+                "EXT_color_buffer_half_float".to_string(),
+                "EXT_disjoint_timer_query".to_string(),
+                "EXT_float_blend".to_string(),
+                "EXT_frag_depth".to_string(),
+                "EXT_shader_texture_lod".to_string(),
+                "EXT_texture_filter_anisotropic".to_string(),
+                "EXT_sRGB".to_string(),
+                "KHR_parallel_shader_compile".to_string(),
+                "OES_element_index_uint".to_string(),
+                "OES_fbo_render_mipmap".to_string(),
+                "OES_standard_derivatives".to_string(),
+                "OES_texture_float".to_string(),
+                "OES_texture_float_linear".to_string(),
+                "OES_texture_half_float".to_string(),
+                "OES_texture_half_float_linear".to_string(),
+                "OES_vertex_array_object".to_string(),
+                "WEBGL_color_buffer_float".to_string(),
+                "WEBGL_compressed_texture_astc".to_string(),
+                "WEBGL_compressed_texture_etc".to_string(),
+                "WEBGL_compressed_texture_etc1".to_string(),
+                "WEBGL_debug_renderer_info".to_string(),
+                "WEBGL_debug_shaders".to_string(),
+                "WEBGL_depth_texture".to_string(),
+                "WEBGL_lose_context".to_string(),
+                "WEBGL_multi_draw".to_string(),
+            ],
+        }
+    }
+
     pub fn to_dm_img_str(&self) -> String {
         // 生成包含完整WebGL上下文信息的指纹字符串
         let full_webgl_info = format!(
-            "{} | {} | {} | GLSL: {} | Extensions: {}",
+            "{} | {} | {} | GLSL: {} | Precision: {} | Extensions: {}",
             self.version,
             self.get_full_context_info(),
             self.renderer,
             self.shading_language_version,
+            self.precision,
             self.get_extensions_string()
         );
         general_purpose::STANDARD.encode(full_webgl_info)
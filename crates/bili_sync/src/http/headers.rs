@@ -1,4 +1,7 @@
-use reqwest::header::{HeaderMap, HeaderValue};
+use std::collections::HashMap;
+
+use anyhow::{bail, ensure, Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 
 /// 标准的Chrome 140浏览器User-Agent
 pub const CHROME_USER_AGENT: &str =
@@ -9,11 +12,81 @@ pub const SEC_CH_UA: &str = "\"Chromium\";v=\"140\", \"Not=A?Brand\";v=\"24\", \
 pub const SEC_CH_UA_MOBILE: &str = "?0";
 pub const SEC_CH_UA_PLATFORM: &str = "\"Windows\"";
 
+/// 校验用户配置的 User-Agent：不能为空，且必须是合法的请求头值
+pub fn validate_user_agent(user_agent: &str) -> Result<()> {
+    ensure!(!user_agent.trim().is_empty(), "User-Agent 不能为空");
+    HeaderValue::from_str(user_agent.trim()).context("User-Agent 包含非法字符")?;
+    Ok(())
+}
+
+/// 用户在配置中指定的 User-Agent，未配置或配置无效时返回 None
+pub fn configured_user_agent() -> Option<String> {
+    crate::config::with_config(|bundle| bundle.config.user_agent.clone())
+        .map(|ua| ua.trim().to_string())
+        .filter(|ua| validate_user_agent(ua).is_ok())
+}
+
+/// 优先使用用户配置的 User-Agent，未配置时使用给定的默认值
+pub fn user_agent_or(default: &str) -> String {
+    configured_user_agent().unwrap_or_else(|| default.to_string())
+}
+
+/// 不允许通过 extra_headers 覆盖的请求头：凭据、风控验证令牌以及由 HTTP 客户端维护的头
+/// User-Agent 请使用单独的 user_agent 配置
+const PROTECTED_HEADERS: [&str; 7] = [
+    "cookie",
+    "user-agent",
+    "x-gaia-vtoken",
+    "host",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+];
+
+/// 校验用户配置的额外请求头：名称与值必须合法，且不能覆盖受保护的请求头
+pub fn validate_extra_headers(extra_headers: &HashMap<String, String>) -> Result<()> {
+    for (name, value) in extra_headers {
+        let header_name =
+            HeaderName::from_bytes(name.trim().as_bytes()).with_context(|| format!("请求头名称 {} 不合法", name))?;
+        if PROTECTED_HEADERS.contains(&header_name.as_str()) {
+            bail!("请求头 {} 不允许通过 extra_headers 覆盖", name);
+        }
+        HeaderValue::from_str(value.trim()).with_context(|| format!("请求头 {} 的值包含非法字符", name))?;
+    }
+    Ok(())
+}
+
+/// 用户在配置中指定的额外请求头，跳过不合法或受保护的条目
+pub fn configured_extra_headers() -> Vec<(HeaderName, HeaderValue)> {
+    let extra_headers = crate::config::with_config(|bundle| bundle.config.extra_headers.clone());
+    extra_headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = HeaderName::from_bytes(name.trim().as_bytes()).ok()?;
+            let value = HeaderValue::from_str(value.trim()).ok()?;
+            (!PROTECTED_HEADERS.contains(&name.as_str())).then_some((name, value))
+        })
+        .collect()
+}
+
+/// 将配置的额外请求头合并到请求头中，同名的请求头会被覆盖
+pub fn apply_extra_headers(headers: &mut HeaderMap) {
+    for (name, value) in configured_extra_headers() {
+        headers.insert(name, value);
+    }
+}
+
+fn user_agent_header() -> HeaderValue {
+    configured_user_agent()
+        .and_then(|ua| HeaderValue::from_str(&ua).ok())
+        .unwrap_or(HeaderValue::from_static(CHROME_USER_AGENT))
+}
+
 /// 为API请求创建标准请求头
 pub fn create_api_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
 
-    headers.insert("User-Agent", HeaderValue::from_static(CHROME_USER_AGENT));
+    headers.insert("User-Agent", user_agent_header());
     headers.insert("Accept", HeaderValue::from_static("*/*"));
     headers.insert("Accept-Language", HeaderValue::from_static("zh-CN,zh;q=0.9,en;q=0.8"));
     headers.insert("Referer", HeaderValue::from_static("https://www.bilibili.com/"));
@@ -26,6 +99,7 @@ pub fn create_api_headers() -> HeaderMap {
     headers.insert("sec-fetch-dest", HeaderValue::from_static("empty"));
     headers.insert("sec-fetch-mode", HeaderValue::from_static("cors"));
     headers.insert("sec-fetch-site", HeaderValue::from_static("cross-site"));
+    apply_extra_headers(&mut headers);
 
     headers
 }
@@ -34,7 +108,7 @@ pub fn create_api_headers() -> HeaderMap {
 pub fn create_image_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
 
-    headers.insert("User-Agent", HeaderValue::from_static(CHROME_USER_AGENT));
+    headers.insert("User-Agent", user_agent_header());
     headers.insert("Referer", HeaderValue::from_static("https://www.bilibili.com/"));
 
     // 图片请求的安全头
@@ -44,6 +118,7 @@ pub fn create_image_headers() -> HeaderMap {
     headers.insert("sec-fetch-dest", HeaderValue::from_static("image"));
     headers.insert("sec-fetch-mode", HeaderValue::from_static("no-cors"));
     headers.insert("sec-fetch-site", HeaderValue::from_static("cross-site"));
+    apply_extra_headers(&mut headers);
 
     headers
 }
@@ -52,7 +127,7 @@ pub fn create_image_headers() -> HeaderMap {
 pub fn create_navigation_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
 
-    headers.insert("User-Agent", HeaderValue::from_static(CHROME_USER_AGENT));
+    headers.insert("User-Agent", user_agent_header());
 
     // 页面导航的安全头
     headers.insert("sec-ch-ua", HeaderValue::from_static(SEC_CH_UA));
@@ -61,14 +136,16 @@ pub fn create_navigation_headers() -> HeaderMap {
     headers.insert("sec-fetch-dest", HeaderValue::from_static("document"));
     headers.insert("sec-fetch-mode", HeaderValue::from_static("navigate"));
     headers.insert("sec-fetch-site", HeaderValue::from_static("none"));
+    apply_extra_headers(&mut headers);
 
     headers
 }
 
 /// 为Aria2下载器创建请求头字符串数组
 pub fn create_aria2_headers() -> Vec<String> {
-    vec![
-        format!("User-Agent: {}", CHROME_USER_AGENT),
+    let extra_headers = configured_extra_headers();
+    let mut headers: Vec<String> = vec![
+        format!("User-Agent: {}", user_agent_or(CHROME_USER_AGENT)),
         "Referer: https://www.bilibili.com".to_string(),
         "Accept: */*".to_string(),
         "Accept-Language: zh-CN,zh;q=0.9,en;q=0.8".to_string(),
@@ -79,5 +156,42 @@ pub fn create_aria2_headers() -> Vec<String> {
         "sec-fetch-mode: cors".to_string(),
         "sec-fetch-site: cross-site".to_string(),
         "Cache-Control: no-cache".to_string(),
-    ]
+    ];
+    // 额外请求头覆盖同名的默认请求头
+    headers.retain(|header| {
+        let name = header.split_once(':').map_or("", |(name, _)| name.trim());
+        !extra_headers
+            .iter()
+            .any(|(extra, _)| extra.as_str().eq_ignore_ascii_case(name))
+    });
+    headers.extend(
+        extra_headers
+            .iter()
+            .filter_map(|(name, value)| value.to_str().ok().map(|value| format!("{}: {}", name.as_str(), value))),
+    );
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_user_agent() {
+        assert!(validate_user_agent(CHROME_USER_AGENT).is_ok());
+        assert!(validate_user_agent("   ").is_err());
+        assert!(validate_user_agent("Mozilla/5.0\n(Windows)").is_err());
+    }
+
+    #[test]
+    fn test_validate_extra_headers() {
+        let headers = |name: &str, value: &str| HashMap::from([(name.to_string(), value.to_string())]);
+        assert!(validate_extra_headers(&headers("Referer", "https://www.bilibili.com/video/")).is_ok());
+        assert!(validate_extra_headers(&headers("X-Experimental", "1")).is_ok());
+        assert!(validate_extra_headers(&headers("Bad Header", "1")).is_err());
+        assert!(validate_extra_headers(&headers("Origin", "a\r\nb")).is_err());
+        assert!(validate_extra_headers(&headers("Cookie", "SESSDATA=x")).is_err());
+        assert!(validate_extra_headers(&headers("User-Agent", "curl")).is_err());
+        assert!(validate_extra_headers(&HashMap::new()).is_ok());
+    }
 }
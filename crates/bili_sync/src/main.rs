@@ -23,7 +23,7 @@ use std::future::Future;
 use std::sync::Arc;
 
 // 移除未使用的Lazy导入
-use task::{http_server, video_downloader};
+use task::{credential_refresher, http_server, retention_cleaner, video_downloader};
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 
@@ -56,6 +56,13 @@ async fn main() -> Result<()> {
         warn!("恢复断点信息失败: {:#}", e);
     }
 
+    // 恢复单独暂停的视频源
+    match crate::task::restore_paused_sources(&connection).await {
+        Ok(count) if count > 0 => info!("恢复了 {} 个已暂停的视频源", count),
+        Ok(_) => {}
+        Err(e) => warn!("恢复已暂停的视频源失败: {:#}", e),
+    }
+
     // 恢复待处理的任务到内存队列
     if let Err(e) = crate::task::recover_pending_tasks(connection.as_ref()).await {
         warn!("恢复待处理任务失败: {:#}", e);
@@ -121,6 +128,18 @@ async fn main() -> Result<()> {
     let tracker = TaskTracker::new();
 
     spawn_task("HTTP 服务", http_server(connection.clone()), &tracker, token.clone());
+    spawn_task(
+        "凭证刷新",
+        credential_refresher(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
+    spawn_task(
+        "保留策略清理",
+        retention_cleaner(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
     spawn_task("定时下载", video_downloader(connection), &tracker, token.clone());
 
     tracker.close();
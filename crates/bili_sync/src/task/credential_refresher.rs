@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sea_orm::DatabaseConnection;
+use tracing::{debug, info, warn};
+
+use crate::bilibili::BiliClient;
+use crate::config::ConfigManager;
+use crate::utils::time_format::{beijing_now, now_standard_string, to_standard_string};
+
+/// 启动后首次检查前的等待时间，避开启动阶段的初始化请求
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+/// 正常情况下两次检查的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 检查或刷新失败后的重试间隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// 凭证自动刷新的状态，供接口展示
+#[derive(Debug, Clone, Default)]
+pub struct CredentialRefreshState {
+    pub last_check_at: Option<String>,
+    pub last_refresh_at: Option<String>,
+    pub next_check_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+static REFRESH_STATE: Lazy<Mutex<CredentialRefreshState>> = Lazy::new(Default::default);
+
+/// 获取当前的凭证刷新状态
+pub fn refresh_state() -> CredentialRefreshState {
+    REFRESH_STATE.lock().clone()
+}
+
+/// 记录一次成功的凭证刷新（包括下载过程中遇到 -101 时触发的刷新）
+pub fn record_refresh() {
+    REFRESH_STATE.lock().last_refresh_at = Some(now_standard_string());
+}
+
+/// 启动定期检查并刷新B站凭证的任务
+pub async fn credential_refresher(connection: Arc<DatabaseConnection>) {
+    let bili_client = BiliClient::new(String::new());
+    let mut wait = STARTUP_DELAY;
+    loop {
+        if let Ok(wait) = chrono::Duration::from_std(wait) {
+            REFRESH_STATE.lock().next_check_at = Some(to_standard_string(beijing_now() + wait));
+        }
+        tokio::time::sleep(wait).await;
+
+        REFRESH_STATE.lock().last_check_at = Some(now_standard_string());
+        wait = match check_and_refresh(&bili_client, &connection).await {
+            Ok(()) => {
+                REFRESH_STATE.lock().last_error = None;
+                CHECK_INTERVAL
+            }
+            Err(e) => {
+                warn!(
+                    "凭证检查或刷新失败，{} 分钟后重试: {:#}",
+                    RETRY_INTERVAL.as_secs() / 60,
+                    e
+                );
+                REFRESH_STATE.lock().last_error = Some(format!("{:#}", e));
+                RETRY_INTERVAL
+            }
+        };
+    }
+}
+
+async fn check_and_refresh(bili_client: &BiliClient, connection: &DatabaseConnection) -> Result<()> {
+    let config = crate::config::reload_config();
+    let (has_credential, has_refresh_token) = match config.credential.load().as_deref() {
+        Some(credential) => (!credential.sessdata.is_empty(), !credential.ac_time_value.is_empty()),
+        None => (false, false),
+    };
+    if !has_credential {
+        debug!("未配置B站凭证，跳过凭证检查");
+        return Ok(());
+    }
+
+    let is_login = bili_client.is_credential_valid().await?;
+    if !is_login {
+        warn!("B站凭证已失效，尝试使用 refresh_token 刷新");
+    }
+    if !has_refresh_token {
+        if !is_login {
+            bail!("凭证已失效且缺少 refresh_token（ac_time_value），请重新登录");
+        }
+        debug!("凭证缺少 refresh_token，无法自动刷新，跳过");
+        return Ok(());
+    }
+
+    // 已失效的凭证无法再询问是否需要刷新，直接强制刷新
+    let Some(new_credential) = bili_client.refresh_credential(!is_login).await? else {
+        debug!("B站凭证仍然有效，暂不需要刷新");
+        return Ok(());
+    };
+
+    // 正在扫描时与登录保存凭证一致：先更新内存中的凭证，数据库保存交给配置任务队列在扫描结束后处理
+    if crate::task::is_scanning() {
+        crate::config::with_config(|bundle| bundle.config.credential.store(Some(Arc::new(new_credential.clone()))));
+        let reload_task = crate::task::ReloadConfigTask {
+            credential: Some(new_credential),
+            task_id: uuid::Uuid::new_v4().to_string(),
+            priority: crate::task::RELOAD_CONFIG_TASK_PRIORITY,
+        };
+        crate::task::enqueue_reload_task(reload_task, connection).await?;
+        info!("检测到正在扫描，自动刷新的B站凭证将在扫描结束后保存到数据库");
+        return Ok(());
+    }
+
+    let manager = ConfigManager::new(connection.clone());
+    manager
+        .update_config_item("credential", serde_json::to_value(&new_credential)?)
+        .await?;
+    if let Err(e) = crate::config::reload_config_bundle().await {
+        warn!("重新加载配置包失败: {}", e);
+        crate::config::reload_config();
+    }
+    info!("B站凭证已自动刷新并保存到数据库");
+    Ok(())
+}
@@ -15,12 +15,19 @@ use utoipa_swagger_ui::{Config, SwaggerUi};
 use crate::api::auth;
 use crate::api::handler::{
     add_video_source,
+    add_video_tags,
+    batch_delete_videos,
+    batch_refresh_video_metadata,
+    batch_reset_videos,
     batch_update_config_internal,
+    cancel_queue_task,
     check_initial_setup,
     clear_credential,
     delete_video,
     delete_video_source,
     download_log_file,
+    export_config,
+    export_videos,
     generate_qr_code,
     get_bangumi_seasons,
     get_bangumi_sources_for_merge,
@@ -28,17 +35,25 @@ use crate::api::handler::{
     get_config_history,
     // 新增配置管理API
     get_config_item,
+    get_credential_status,
     get_current_user,
     get_dashboard_data,
+    get_dead_letter_tasks,
     get_hot_reload_status,
     get_log_files,
     get_logs,
     get_notification_config,
     get_notification_status,
     get_queue_status,
+    get_queue_task,
+    get_source_playlist,
+    get_source_scan_history,
+    get_source_scan_task,
+    get_stats,
     get_submission_videos,
     get_subscribed_collections,
     get_task_control_status,
+    get_trash,
     get_user_collections,
     get_user_favorites,
     get_user_favorites_by_uid,
@@ -48,32 +63,62 @@ use crate::api::handler::{
     get_video_play_info,
     get_video_sources,
     get_videos,
+    import_config,
+    import_credential_cookie,
+    import_video_list,
     pause_scanning_endpoint,
+    pause_video_source,
     poll_qr_status,
+    preview_template,
     proxy_image,
     proxy_video_stream,
+    refresh_collection_cover,
+    refresh_source_metadata,
+    refresh_video_metadata,
     reload_config,
     reload_config_new_internal,
+    remove_video_tags,
+    requeue_dead_letter_task,
     reset_all_videos,
     reset_specific_tasks,
     reset_video,
     reset_video_source_path,
+    restore_trash,
     resume_scanning_endpoint,
+    resume_video_source,
+    rollback_config_change,
+    scan_video_source,
     search_bilibili,
+    search_videos,
     setup_auth_token,
     test_notification_handler,
     test_risk_control_handler,
+    trigger_scan_all,
     update_config,
     update_config_item_internal,
     update_credential,
     update_notification_config,
+    update_video_source_audio_only,
+    update_video_source_danmaku_embed,
     update_video_source_enabled,
+    update_video_source_limit_newest,
+    update_video_source_nfo_time_type,
+    update_video_source_pubtime_window,
+    update_video_source_retention,
     update_video_source_scan_deleted,
+    update_video_source_scan_interval,
+    update_video_source_strict_incremental,
+    update_video_source_strm,
+    update_video_source_subtasks,
+    update_video_source_title_filter,
+    update_video_source_upgrade_quality,
+    update_video_source_webdav_upload,
     update_video_status,
     validate_config,
     validate_favorite,
     ApiDoc,
 };
+use crate::api::rate_limit;
 use crate::api::request::{BatchUpdateConfigRequest, UpdateConfigItemRequest};
 use crate::api::video_stream::stream_video;
 use crate::api::wrapper::ApiResponse;
@@ -148,19 +193,99 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
             "/api/video-sources/{source_type}/{id}/scan-deleted",
             put(update_video_source_scan_deleted),
         )
+        .route(
+            "/api/video-sources/{source_type}/{id}/scan-interval",
+            put(update_video_source_scan_interval),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/pubtime-window",
+            put(update_video_source_pubtime_window),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/title-filter",
+            put(update_video_source_title_filter),
+        )
+        .route("/api/video-sources/{source_type}/{id}/strm", put(update_video_source_strm))
+        .route(
+            "/api/video-sources/{source_type}/{id}/strict-incremental",
+            put(update_video_source_strict_incremental),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/webdav-upload",
+            put(update_video_source_webdav_upload),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/audio-only",
+            put(update_video_source_audio_only),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/upgrade-quality",
+            put(update_video_source_upgrade_quality),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/danmaku-embed",
+            put(update_video_source_danmaku_embed),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/subtasks",
+            put(update_video_source_subtasks),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/retention",
+            put(update_video_source_retention),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/limit-newest",
+            put(update_video_source_limit_newest),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/nfo-time-type",
+            put(update_video_source_nfo_time_type),
+        )
+        .route("/api/video-sources/{source_type}/{id}/scan", post(scan_video_source))
+        .route("/api/video-sources/{source_type}/{id}/pause", post(pause_video_source))
+        .route("/api/video-sources/{source_type}/{id}/resume", post(resume_video_source))
+        .route(
+            "/api/video-sources/{source_type}/{id}/refresh-metadata",
+            post(refresh_source_metadata),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/playlist.m3u",
+            get(get_source_playlist),
+        )
+        .route(
+            "/api/video-sources/{source_type}/{id}/history",
+            get(get_source_scan_history),
+        )
+        .route(
+            "/api/video-sources/collection/{id}/refresh-cover",
+            post(refresh_collection_cover),
+        )
+        .route("/api/scan-tasks/{task_id}", get(get_source_scan_task))
         .route(
             "/api/video-sources/{source_type}/{id}/reset-path",
             post(reset_video_source_path),
         )
         .route("/api/video-sources/{source_type}/{id}", delete(delete_video_source))
+        .route("/api/trash", get(get_trash))
+        .route("/api/trash/restore", post(restore_trash))
         .route("/api/videos", get(get_videos))
         .route("/api/videos/{id}", get(get_video))
         .route("/api/videos/{id}", delete(delete_video))
         .route("/api/videos/{id}/reset", post(reset_video))
         .route("/api/videos/{id}/update-status", post(update_video_status))
         .route("/api/videos/reset-all", post(reset_all_videos))
+        .route("/api/videos/batch-reset", post(batch_reset_videos))
+        .route("/api/videos/{id}/refresh-metadata", post(refresh_video_metadata))
+        .route("/api/videos/batch-refresh-metadata", post(batch_refresh_video_metadata))
+        .route("/api/videos/export", get(export_videos))
+        .route("/api/videos/search", get(search_videos))
+        .route("/api/videos/{id}/tags", post(add_video_tags).delete(remove_video_tags))
+        .route("/api/videos/batch-delete", post(batch_delete_videos))
+        .route("/api/videos/import-list", post(import_video_list))
         .route("/api/videos/reset-specific-tasks", post(reset_specific_tasks))
         .route("/api/dashboard", get(get_dashboard_data))
+        .route("/api/stats", get(get_stats))
         .route("/api/reload-config", post(reload_config))
         .route("/api/config", get(get_config))
         .route("/api/config", put(update_config))
@@ -192,12 +317,18 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
             }),
         )
         .route("/api/config/history", get(get_config_history))
+        .route("/api/config/history/{id}/rollback", post(rollback_config_change))
+        .route("/api/config/preview-template", post(preview_template))
+        .route("/api/config/export", get(export_config))
+        .route("/api/config/import", post(import_config))
         .route("/api/config/validate", post(validate_config))
         .route("/api/config/hot-reload/status", get(get_hot_reload_status))
         // 初始设置API路由
         .route("/api/setup/check", get(check_initial_setup))
         .route("/api/setup/auth-token", post(setup_auth_token))
         .route("/api/credential", put(update_credential))
+        .route("/api/credential/import-cookie", post(import_credential_cookie))
+        .route("/api/credential/status", get(get_credential_status))
         // 扫码登录API路由
         .route("/api/auth/qr/generate", post(generate_qr_code))
         .route("/api/auth/qr/poll", get(poll_qr_status))
@@ -216,10 +347,15 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         .route("/api/logs/files", get(get_log_files))
         .route("/api/logs/download", get(download_log_file))
         .route("/api/queue-status", get(get_queue_status))
+        .route("/api/queue/tasks/{task_id}", get(get_queue_task))
+        .route("/api/queue/tasks/{task_id}", delete(cancel_queue_task))
+        .route("/api/queue/dead-letter", get(get_dead_letter_tasks))
+        .route("/api/queue/dead-letter/{id}/requeue", post(requeue_dead_letter_task))
         .route("/api/proxy/image", get(proxy_image))
         .route("/api/task-control/status", get(get_task_control_status))
         .route("/api/task-control/pause", post(pause_scanning_endpoint))
         .route("/api/task-control/resume", post(resume_scanning_endpoint))
+        .route("/api/scan/trigger-all", post(trigger_scan_all))
         // 推送通知API
         .route("/api/notification/test", post(test_notification_handler))
         .route("/api/config/notification", get(get_notification_config))
@@ -240,6 +376,8 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         // 先应用认证中间件
         .layer(Extension(optimized_connection.clone()))
         .layer(middleware::from_fn(auth::auth))
+        // 限流需要在认证之外，才能统计认证失败的请求
+        .layer(middleware::from_fn(rate_limit::rate_limit))
         // WebSocket API需要在认证中间件之后
         .merge(ws::router())
         .merge(
@@ -252,7 +390,9 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
                         .validator_url("none"),
                 ),
         )
-        .fallback_service(get(frontend_files));
+        .fallback_service(get(frontend_files))
+        // 跨域中间件放在最外层，预检请求无需经过认证
+        .layer(crate::api::cors::cors_layer());
     // 使用动态配置而非静态CONFIG
     // 启动周期性数据库连接健康检查
     let health_check_connection = optimized_connection.clone();
@@ -277,7 +417,11 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         .await
         .context("bind address failed")?;
     info!("开始运行管理页: http://{}", config.bind_address);
-    Ok(axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await?)
+    Ok(axum::serve(
+        listener,
+        ServiceExt::<Request>::into_make_service_with_connect_info::<std::net::SocketAddr>(app),
+    )
+    .await?)
 }
 
 async fn frontend_files(uri: Uri) -> impl IntoResponse {
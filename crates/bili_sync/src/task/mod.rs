@@ -1,9 +1,14 @@
+pub mod credential_refresher;
 mod http_server;
+pub mod retention_cleaner;
 pub mod video_downloader;
 
+pub use credential_refresher::credential_refresher;
 pub use http_server::http_server;
+pub use retention_cleaner::retention_cleaner;
 pub use video_downloader::video_downloader;
 
+use crate::utils::scan_id_tracker::SourceType;
 use crate::utils::time_format::now_standard_string;
 use anyhow::Result;
 use bili_sync_entity::task_queue::{self, Entity as TaskQueueEntity, TaskStatus, TaskType};
@@ -11,12 +16,12 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// 删除视频源任务结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +29,9 @@ pub struct DeleteVideoSourceTask {
     pub source_type: String,
     pub source_id: i32,
     pub delete_local_files: bool,
+    /// 任务优先级，数值越大越先执行，保存在 task_queue.priority 列中
+    #[serde(skip)]
+    pub priority: i32,
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
@@ -31,9 +39,19 @@ pub struct DeleteVideoSourceTask {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeleteVideoTask {
     pub video_id: i32,
+    /// 是否同时删除本地文件，旧版本持久化的任务没有该字段时按删除处理
+    #[serde(default = "default_delete_local_files")]
+    pub delete_local_files: bool,
+    /// 任务优先级，数值越大越先执行，保存在 task_queue.priority 列中
+    #[serde(skip)]
+    pub priority: i32,
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
+fn default_delete_local_files() -> bool {
+    true
+}
+
 /// 添加视频源任务结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddVideoSourceTask {
@@ -47,6 +65,23 @@ pub struct AddVideoSourceTask {
     pub ep_id: Option<String>,
     pub download_all_seasons: Option<bool>,
     pub selected_seasons: Option<Vec<String>>,
+    #[serde(default)]
+    pub episode_range: Option<String>,
+    #[serde(default)]
+    pub scan_interval: Option<u64>,
+    #[serde(default)]
+    pub pubtime_after: Option<String>,
+    #[serde(default)]
+    pub pubtime_before: Option<String>,
+    #[serde(default)]
+    pub title_include: Option<Vec<String>>,
+    #[serde(default)]
+    pub title_exclude: Option<Vec<String>>,
+    #[serde(default)]
+    pub title_regex: Option<bool>,
+    /// 任务优先级，数值越大越先执行，保存在 task_queue.priority 列中
+    #[serde(skip)]
+    pub priority: i32,
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
@@ -63,8 +98,15 @@ pub struct UpdateConfigTask {
     pub time_format: Option<String>,
     pub interval: Option<u64>,
     pub nfo_time_type: Option<String>,
+    pub nfo_flavor: Option<String>,
+    pub nfo_template: Option<String>,
+    pub nfo_download_actor_images: Option<bool>,
+    pub nfo_include_tags: Option<bool>,
+    pub nfo_max_tags: Option<usize>,
+    pub nfo_include_chapters: Option<bool>,
     pub parallel_download_enabled: Option<bool>,
     pub parallel_download_threads: Option<usize>,
+    pub download_speed_limit_kbps: Option<u64>,
     // 视频质量设置
     pub video_max_quality: Option<String>,
     pub video_min_quality: Option<String>,
@@ -75,6 +117,8 @@ pub struct UpdateConfigTask {
     pub no_dolby_audio: Option<bool>,
     pub no_hdr: Option<bool>,
     pub no_hires: Option<bool>,
+    pub min_duration_secs: Option<u32>,
+    pub max_duration_secs: Option<u32>,
     // 弹幕设置
     pub danmaku_duration: Option<f64>,
     pub danmaku_font: Option<String>,
@@ -88,6 +132,11 @@ pub struct UpdateConfigTask {
     pub danmaku_bold: Option<bool>,
     pub danmaku_outline: Option<f64>,
     pub danmaku_time_offset: Option<f64>,
+    pub danmaku_formats: Option<Vec<String>>,
+    pub danmaku_block_keywords: Option<Vec<String>>,
+    pub danmaku_block_regex: Option<bool>,
+    pub danmaku_max_per_second: Option<u32>,
+    pub danmaku_exclude_modes: Option<Vec<u8>>,
     // 并发控制设置
     pub concurrent_video: Option<usize>,
     pub concurrent_page: Option<usize>,
@@ -95,6 +144,14 @@ pub struct UpdateConfigTask {
     pub rate_duration: Option<u64>,
     // 其他设置
     pub cdn_sorting: Option<bool>,
+    pub verify_download_integrity: Option<bool>,
+    pub download_subtitles: Option<bool>,
+    pub bangumi_write_skip_markers: Option<bool>,
+    pub bangumi_skip_specials: Option<bool>,
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
     // UP主投稿风控配置
     pub large_submission_threshold: Option<usize>,
     pub base_request_delay: Option<u64>,
@@ -119,15 +176,191 @@ pub struct UpdateConfigTask {
     pub bangumi_use_season_structure: Option<bool>,
     // UP主头像保存路径
     pub upper_path: Option<String>,
+    // 静默时段配置
+    #[serde(default)]
+    pub quiet_hours_enabled: Option<bool>,
+    #[serde(default)]
+    pub quiet_hours_start: Option<String>,
+    #[serde(default)]
+    pub quiet_hours_end: Option<String>,
+    #[serde(default)]
+    pub quiet_hours_weekdays: Option<Vec<u32>>,
+    // 外部aria2 RPC配置
+    #[serde(default)]
+    pub aria2_rpc_url: Option<String>,
+    #[serde(default)]
+    pub aria2_rpc_secret: Option<String>,
+    // Jellyfin 媒体库刷新配置
+    #[serde(default)]
+    pub jellyfin_url: Option<String>,
+    #[serde(default)]
+    pub jellyfin_api_key: Option<String>,
+    // 队列任务最大重试次数
+    #[serde(default)]
+    pub task_max_retry: Option<u32>,
+    // WebDAV 上传配置
+    #[serde(default)]
+    pub webdav_url: Option<String>,
+    #[serde(default)]
+    pub webdav_username: Option<String>,
+    #[serde(default)]
+    pub webdav_password: Option<String>,
+    #[serde(default)]
+    pub webdav_base_path: Option<String>,
+    #[serde(default)]
+    pub webdav_delete_local: Option<bool>,
+    // 保留策略清理视频时是否删除本地文件
+    #[serde(default)]
+    pub retention_delete_local_files: Option<bool>,
+    // 跨视频源的重复分页去重方式
+    #[serde(default)]
+    pub dedup_strategy: Option<String>,
+    // 下载暂存目录
+    #[serde(default)]
+    pub temp_dir: Option<String>,
+    // 磁盘剩余空间阈值（GB）
+    #[serde(default)]
+    pub min_free_disk_gb: Option<u64>,
+    // 同一 IP 每分钟允许的认证相关请求数
+    #[serde(default)]
+    pub api_rate_limit_per_minute: Option<u32>,
+    // 是否信任媒体目录中已存在的文件
+    #[serde(default)]
+    pub trust_existing_files: Option<bool>,
+    // FFmpeg 路径与硬件加速方式
+    #[serde(default)]
+    pub ffmpeg_path: Option<String>,
+    #[serde(default)]
+    pub ffmpeg_hwaccel: Option<String>,
+    // 封面缺失时是否从视频中截取一帧作为封面
+    #[serde(default)]
+    pub generate_thumbnail_fallback: Option<bool>,
+    // 扫描合集时是否同步最新封面
+    #[serde(default)]
+    pub refresh_covers: Option<bool>,
+    // UP主头像检查间隔（天）
+    #[serde(default)]
+    pub refresh_upper_avatar_days: Option<u32>,
+    // 同时扫描的视频源数量
+    #[serde(default)]
+    pub source_scan_concurrency: Option<usize>,
+    // 番剧标题缓存的最大条目数与过期时间（秒）
+    #[serde(default)]
+    pub season_title_cache_size: Option<usize>,
+    #[serde(default)]
+    pub season_title_cache_ttl_secs: Option<u64>,
+    // 数据库连接池配置
+    #[serde(default)]
+    pub db_max_connections: Option<u32>,
+    #[serde(default)]
+    pub db_acquire_timeout: Option<u64>,
+    #[serde(default)]
+    pub db_busy_timeout: Option<u64>,
+    // 回收站配置
+    #[serde(default)]
+    pub delete_to_trash: Option<bool>,
+    #[serde(default)]
+    pub trash_retention_days: Option<u32>,
+    // FFmpeg 合并与弹幕嵌入的超时时间（秒）
+    #[serde(default)]
+    pub ffmpeg_merge_timeout_secs: Option<u64>,
+    // 封面最大宽度与格式
+    #[serde(default)]
+    pub cover_max_width: Option<u32>,
+    #[serde(default)]
+    pub cover_format: Option<String>,
+    // 允许跨域访问的来源列表
+    #[serde(default)]
+    pub cors_allowed_origins: Option<Vec<String>>,
+    // 文件名安全化策略与最大字节数
+    #[serde(default)]
+    pub filename_policy: Option<String>,
+    #[serde(default)]
+    pub max_filename_bytes: Option<usize>,
+    // 完整路径的最大字节数
+    #[serde(default)]
+    pub max_path_bytes: Option<usize>,
+    // 下载失败重试次数与基础等待时间（毫秒）
+    #[serde(default)]
+    pub download_max_retries: Option<u32>,
+    #[serde(default)]
+    pub download_retry_base_delay_ms: Option<u64>,
+    // 是否跳过无权观看的充电专享视频
+    #[serde(default)]
+    pub skip_upower_exclusive: Option<bool>,
+    // 是否在每轮扫描后写入扫描报告文件
+    #[serde(default)]
+    pub write_scan_report: Option<bool>,
+    // 访问B站时附加的请求头
+    #[serde(default)]
+    pub extra_headers: Option<std::collections::HashMap<String, String>>,
+    /// 任务优先级，数值越大越先执行，保存在 task_queue.priority 列中
+    #[serde(skip)]
+    pub priority: i32,
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
 /// 重载配置任务结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReloadConfigTask {
+    /// 重载前需要保存到数据库的B站凭证，扫描期间得到的新凭证通过该字段延后保存
+    #[serde(default)]
+    pub credential: Option<crate::bilibili::Credential>,
+    /// 任务优先级，数值越大越先执行，保存在 task_queue.priority 列中
+    #[serde(skip)]
+    pub priority: i32,
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
+/// 任务的默认优先级
+pub const DEFAULT_TASK_PRIORITY: i32 = 0;
+/// 重载配置任务会影响其他任务，默认使用最高优先级
+pub const RELOAD_CONFIG_TASK_PRIORITY: i32 = i32::MAX;
+
+/// 队列中的任务
+pub trait QueuedTask {
+    fn priority(&self) -> i32;
+    fn task_id(&self) -> &str;
+}
+
+macro_rules! impl_queued_task {
+    ($($task:ty),*) => {
+        $(impl QueuedTask for $task {
+            fn priority(&self) -> i32 {
+                self.priority
+            }
+
+            fn task_id(&self) -> &str {
+                &self.task_id
+            }
+        })*
+    };
+}
+
+impl_queued_task!(
+    DeleteVideoSourceTask,
+    DeleteVideoTask,
+    AddVideoSourceTask,
+    UpdateConfigTask,
+    ReloadConfigTask
+);
+
+/// 按优先级插入队列：优先级高的排在前面，同优先级保持先进先出
+fn push_by_priority<T: QueuedTask>(queue: &mut VecDeque<T>, task: T) {
+    let position = queue
+        .iter()
+        .position(|queued| queued.priority() < task.priority())
+        .unwrap_or(queue.len());
+    queue.insert(position, task);
+}
+
+/// 从队列中移除指定任务ID的任务，返回是否找到
+fn remove_by_task_id<T: QueuedTask>(queue: &mut VecDeque<T>, task_id: &str) -> bool {
+    let len = queue.len();
+    queue.retain(|task| task.task_id() != task_id);
+    queue.len() != len
+}
+
 /// 删除任务队列管理器
 pub struct DeleteTaskQueue {
     /// 待处理的删除任务队列（内存缓存）
@@ -153,6 +386,7 @@ impl DeleteTaskQueue {
             task_data: Set(task_data),
             status: Set(TaskStatus::Pending),
             retry_count: Set(0),
+            priority: Set(task.priority),
             created_at: Set(now_standard_string()),
             updated_at: Set(now_standard_string()),
             ..Default::default()
@@ -169,7 +403,7 @@ impl DeleteTaskQueue {
             queue.len() + 1,
             result.id
         );
-        queue.push_back(task);
+        push_by_priority(&mut queue, task);
 
         Ok(())
     }
@@ -207,27 +441,8 @@ impl DeleteTaskQueue {
 
     /// 标记任务为失败（更新数据库状态）
     pub async fn mark_task_failed(&self, task: &DeleteVideoSourceTask, connection: &DatabaseConnection) -> Result<()> {
-        // 获取优化的数据库连接（写穿透模式下为主数据库）
-
         let task_data = serde_json::to_string(task)?;
-
-        // 查找并更新数据库中的任务状态
-        if let Some(db_task) = TaskQueueEntity::find()
-            .filter(task_queue::Column::TaskType.eq(TaskType::DeleteVideoSource))
-            .filter(task_queue::Column::TaskData.eq(&task_data))
-            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-            .one(connection)
-            .await?
-        {
-            let retry_count = db_task.retry_count;
-            let mut active_model: task_queue::ActiveModel = db_task.into();
-            active_model.status = Set(TaskStatus::Failed);
-            active_model.retry_count = Set(retry_count + 1);
-            active_model.updated_at = Set(now_standard_string());
-            active_model.update(connection).await?;
-        }
-
-        Ok(())
+        mark_db_task_failed(TaskType::DeleteVideoSource, &task_data, connection).await
     }
 
     /// 获取队列长度
@@ -374,6 +589,7 @@ impl VideoDeleteTaskQueue {
             task_data: Set(task_data),
             status: Set(TaskStatus::Pending),
             retry_count: Set(0),
+            priority: Set(task.priority),
             created_at: Set(now_standard_string()),
             updated_at: Set(now_standard_string()),
             ..Default::default()
@@ -389,7 +605,7 @@ impl VideoDeleteTaskQueue {
             queue.len() + 1,
             result.id
         );
-        queue.push_back(task);
+        push_by_priority(&mut queue, task);
 
         Ok(())
     }
@@ -424,24 +640,7 @@ impl VideoDeleteTaskQueue {
     /// 标记任务为失败（更新数据库状态）
     pub async fn mark_task_failed(&self, task: &DeleteVideoTask, connection: &DatabaseConnection) -> Result<()> {
         let task_data = serde_json::to_string(task)?;
-
-        // 查找并更新数据库中的任务状态
-        if let Some(db_task) = TaskQueueEntity::find()
-            .filter(task_queue::Column::TaskType.eq(TaskType::DeleteVideo))
-            .filter(task_queue::Column::TaskData.eq(&task_data))
-            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-            .one(connection)
-            .await?
-        {
-            let retry_count = db_task.retry_count;
-            let mut active_model: task_queue::ActiveModel = db_task.into();
-            active_model.status = Set(TaskStatus::Failed);
-            active_model.retry_count = Set(retry_count + 1);
-            active_model.updated_at = Set(now_standard_string());
-            active_model.update(connection).await?;
-        }
-
-        Ok(())
+        mark_db_task_failed(TaskType::DeleteVideo, &task_data, connection).await
     }
 
     /// 获取队列长度
@@ -487,7 +686,7 @@ impl VideoDeleteTaskQueue {
             info!("正在处理视频删除任务: 视频ID={}", task.video_id);
 
             // 执行软删除操作
-            match delete_video_internal(db.clone(), task.video_id).await {
+            match delete_video_internal(db.clone(), task.video_id, task.delete_local_files).await {
                 Ok(_) => {
                     info!("视频删除任务执行成功: 视频ID={}", task.video_id);
                     processed_count += 1;
@@ -533,7 +732,11 @@ impl VideoDeleteTaskQueue {
 }
 
 /// 视频软删除内部实现
-async fn delete_video_internal(db: Arc<DatabaseConnection>, video_id: i32) -> Result<(), anyhow::Error> {
+async fn delete_video_internal(
+    db: Arc<DatabaseConnection>,
+    video_id: i32,
+    delete_local_files: bool,
+) -> Result<(), anyhow::Error> {
     use bili_sync_entity::{page, video};
     use sea_orm::*;
 
@@ -555,33 +758,38 @@ async fn delete_video_internal(db: Arc<DatabaseConnection>, video_id: i32) -> Re
         return Err(anyhow::anyhow!("视频已经被删除: ID={}", video_id));
     }
 
+    // 删除文件前记录各分页所在的文件夹，删除后由此向上清理空文件夹
+    let page_dirs: Vec<std::path::PathBuf> = if delete_local_files {
+        page::Entity::find()
+            .filter(page::Column::VideoId.eq(video_id))
+            .all(db.as_ref())
+            .await
+            .map_err(|e| anyhow::anyhow!("查询分页失败: {}", e))?
+            .into_iter()
+            .filter_map(|page| page.path)
+            .filter_map(|path| {
+                std::path::Path::new(&normalize_file_path_task(&path))
+                    .parent()
+                    .map(|dir| dir.to_path_buf())
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     // 删除本地文件 - 根据page表中的路径精确删除
-    let deleted_files = delete_video_files_from_pages_task(db.clone(), video_id).await?;
+    let deleted_files = if delete_local_files {
+        delete_video_files_from_pages_task(db.clone(), video_id).await?
+    } else {
+        0
+    };
 
     if deleted_files > 0 {
         info!("已删除 {} 个视频文件", deleted_files);
 
-        // 检查视频文件夹是否为空，如果为空则删除文件夹
-        let normalized_video_path = normalize_file_path_task(&video.path);
-        let video_path = std::path::Path::new(&normalized_video_path);
-        if video_path.exists() {
-            match tokio::fs::read_dir(&normalized_video_path).await {
-                Ok(mut entries) => {
-                    if entries.next_entry().await.unwrap_or(None).is_none() {
-                        // 文件夹为空，删除它
-                        if let Err(e) = std::fs::remove_dir(&normalized_video_path) {
-                            warn!("删除空文件夹失败: {} - {}", normalized_video_path, e);
-                        } else {
-                            info!("已删除空文件夹: {}", normalized_video_path);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("读取文件夹失败: {} - {}", normalized_video_path, e);
-                }
-            }
-        }
-    } else {
+        // 从分页所在文件夹向上清理空文件夹（如 Season 文件夹、视频文件夹），直到视频源根目录
+        crate::utils::empty_dirs::cleanup_video_dirs(db.as_ref(), &video, page_dirs).await;
+    } else if delete_local_files {
         debug!("未找到需要删除的文件，视频ID: {}", video_id);
     }
 
@@ -620,7 +828,16 @@ async fn delete_video_files_from_pages_task(
 ) -> Result<usize, anyhow::Error> {
     use bili_sync_entity::{page, video};
     use sea_orm::*;
-    use tokio::fs;
+
+    // 按配置直接删除或移入视频源根目录下的回收站
+    let remover = match video::Entity::find_by_id(video_id)
+        .one(db.as_ref())
+        .await
+        .map_err(|e| anyhow::anyhow!("查询视频信息失败: {}", e))?
+    {
+        Some(video) => crate::utils::trash::remover_for_video(db.as_ref(), &video).await,
+        None => crate::utils::trash::FileRemover::Permanent,
+    };
 
     // 获取该视频的所有页面（分P）
     let pages = page::Entity::find()
@@ -635,9 +852,14 @@ async fn delete_video_files_from_pages_task(
         // 删除视频文件
         if let Some(file_path) = &page.path {
             let path = std::path::Path::new(file_path);
+            // 其他视频源链接到该文件时先转移，避免删除后链接失效
+            if let Err(e) = crate::utils::dedup::release_linked_files(db.as_ref(), path).await {
+                warn!("转移被其他视频源引用的文件失败，跳过删除: {} - {:#}", file_path, e);
+                continue;
+            }
             info!("尝试删除视频文件: {}", file_path);
             if path.exists() {
-                match fs::remove_file(path).await {
+                match remover.remove_file(path) {
                     Ok(_) => {
                         debug!("已删除视频文件: {}", file_path);
                         deleted_count += 1;
@@ -658,7 +880,7 @@ async fn delete_video_files_from_pages_task(
                 let path = std::path::Path::new(image_path);
                 info!("尝试删除封面图片: {}", image_path);
                 if path.exists() {
-                    match fs::remove_file(path).await {
+                    match remover.remove_file(path) {
                         Ok(_) => {
                             info!("已删除封面图片: {}", image_path);
                             deleted_count += 1;
@@ -697,56 +919,18 @@ async fn delete_video_files_from_pages_task(
                     if let Some(file_stem) = video_file.file_stem() {
                         let file_stem_str = file_stem.to_string_lossy();
 
-                        // 删除同名的NFO文件
-                        let nfo_path = parent_dir.join(format!("{}.nfo", file_stem_str));
-                        if nfo_path.exists() {
-                            match fs::remove_file(&nfo_path).await {
-                                Ok(_) => {
-                                    debug!("已删除NFO文件: {:?}", nfo_path);
-                                    deleted_count += 1;
-                                }
-                                Err(e) => {
-                                    warn!("删除NFO文件失败: {:?} - {}", nfo_path, e);
-                                }
-                            }
-                        }
-
-                        // 删除封面文件 (-fanart.jpg, -thumb.jpg等)
-                        for suffix in &["fanart", "thumb"] {
-                            for ext in &["jpg", "jpeg", "png", "webp"] {
-                                let cover_path = parent_dir.join(format!("{}-{}.{}", file_stem_str, suffix, ext));
-                                if cover_path.exists() {
-                                    match fs::remove_file(&cover_path).await {
-                                        Ok(_) => {
-                                            debug!("已删除封面文件: {:?}", cover_path);
-                                            deleted_count += 1;
-                                        }
-                                        Err(e) => {
-                                            warn!("删除封面文件失败: {:?} - {}", cover_path, e);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        // 删除弹幕文件 (.zh-CN.default.ass等)
-                        let danmaku_patterns = [
-                            format!("{}.zh-CN.default.ass", file_stem_str),
-                            format!("{}.ass", file_stem_str),
-                            format!("{}.srt", file_stem_str),
-                            format!("{}.xml", file_stem_str),
-                        ];
-
-                        for pattern in &danmaku_patterns {
-                            let danmaku_path = parent_dir.join(pattern);
-                            if danmaku_path.exists() {
-                                match fs::remove_file(&danmaku_path).await {
+                        // 删除同名的附属文件（NFO、各风格封面、弹幕、字幕等），命名与下载流程共用
+                        for sidecar_path in
+                            crate::utils::page_files::sidecar_files(parent_dir, &file_stem_str, page.download_status)
+                        {
+                            if sidecar_path.exists() {
+                                match remover.remove_file(&sidecar_path) {
                                     Ok(_) => {
-                                        debug!("已删除弹幕文件: {:?}", danmaku_path);
+                                        debug!("已删除附属文件: {:?}", sidecar_path);
                                         deleted_count += 1;
                                     }
                                     Err(e) => {
-                                        warn!("删除弹幕文件失败: {:?} - {}", danmaku_path, e);
+                                        warn!("删除附属文件失败: {:?} - {}", sidecar_path, e);
                                     }
                                 }
                             }
@@ -764,14 +948,22 @@ async fn delete_video_files_from_pages_task(
                     let video_path = std::path::Path::new(&video.path);
                     let page_path = std::path::Path::new(page_path);
 
-                    // 如果page路径包含Season文件夹，说明使用了Season结构
-                    let uses_season_structure = page_path.components().any(|component| {
-                        if let std::path::Component::Normal(name) = component {
-                            name.to_string_lossy().starts_with("Season ")
-                        } else {
-                            false
+                    // 季度文件夹中已没有其他视频时删除 season.nfo，多个视频共享同一季度文件夹时保留
+                    if let Some(season_dir) = page_path.parent() {
+                        match crate::utils::nfo::remove_unused_season_nfo(season_dir).await {
+                            Ok(true) => {
+                                info!("已删除季度文件夹中的season.nfo: {:?}", season_dir);
+                                deleted_count += 1;
+                            }
+                            Ok(false) => {}
+                            Err(e) => {
+                                warn!("删除季度文件夹中的season.nfo失败: {:?} - {}", season_dir, e);
+                            }
                         }
-                    });
+                    }
+
+                    // page位于视频文件夹下的季度子文件夹中，说明使用了Season结构
+                    let uses_season_structure = crate::utils::page_files::is_in_season_folder(video_path, page_path);
 
                     if uses_season_structure {
                         debug!("检测到Season结构，删除根目录元数据文件");
@@ -808,17 +1000,13 @@ async fn delete_video_files_from_pages_task(
                                 }
                             };
 
-                            // 删除根目录的元数据文件
-                            let metadata_files = [
-                                "tvshow.nfo".to_string(),
-                                format!("{}-thumb.jpg", video_base_name),
-                                format!("{}-fanart.jpg", video_base_name),
-                            ];
+                            // 删除根目录的元数据文件（包含各 NFO 风格生成的图片与 .plexmatch）
+                            let metadata_files = crate::config::NFOFlavor::all_show_metadata_files(&video_base_name);
 
                             for metadata_file in &metadata_files {
                                 let metadata_path = video_path.join(metadata_file);
                                 if metadata_path.exists() {
-                                    match fs::remove_file(&metadata_path).await {
+                                    match remover.remove_file(&metadata_path) {
                                         Ok(_) => {
                                             info!("已删除Season结构根目录元数据文件: {:?}", metadata_path);
                                             deleted_count += 1;
@@ -866,6 +1054,7 @@ impl AddTaskQueue {
             task_data: Set(task_data),
             status: Set(TaskStatus::Pending),
             retry_count: Set(0),
+            priority: Set(task.priority),
             created_at: Set(now_standard_string()),
             updated_at: Set(now_standard_string()),
             ..Default::default()
@@ -882,7 +1071,7 @@ impl AddTaskQueue {
             queue.len() + 1,
             result.id
         );
-        queue.push_back(task);
+        push_by_priority(&mut queue, task);
 
         Ok(())
     }
@@ -917,24 +1106,7 @@ impl AddTaskQueue {
     /// 标记任务为失败（更新数据库状态）
     pub async fn mark_task_failed(&self, task: &AddVideoSourceTask, connection: &DatabaseConnection) -> Result<()> {
         let task_data = serde_json::to_string(task)?;
-
-        // 查找并更新数据库中的任务状态
-        if let Some(db_task) = TaskQueueEntity::find()
-            .filter(task_queue::Column::TaskType.eq(TaskType::AddVideoSource))
-            .filter(task_queue::Column::TaskData.eq(&task_data))
-            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-            .one(connection)
-            .await?
-        {
-            let retry_count = db_task.retry_count;
-            let mut active_model: task_queue::ActiveModel = db_task.into();
-            active_model.status = Set(TaskStatus::Failed);
-            active_model.retry_count = Set(retry_count + 1);
-            active_model.updated_at = Set(now_standard_string());
-            active_model.update(connection).await?;
-        }
-
-        Ok(())
+        mark_db_task_failed(TaskType::AddVideoSource, &task_data, connection).await
     }
 
     /// 获取队列长度
@@ -987,9 +1159,17 @@ impl AddTaskQueue {
                 ep_id: task.ep_id.clone(),
                 download_all_seasons: task.download_all_seasons,
                 selected_seasons: task.selected_seasons.clone(),
+                episode_range: task.episode_range.clone(),
                 selected_videos: None,    // 任务队列中暂时不支持选择性视频
                 cover: None,              // 任务队列中暂时不支持封面，等前端传递
                 merge_to_source_id: None, // 任务队列中暂时不支持合并功能
+                scan_interval: task.scan_interval,
+                pubtime_after: task.pubtime_after.clone(),
+                pubtime_before: task.pubtime_before.clone(),
+                title_include: task.title_include.clone(),
+                title_exclude: task.title_exclude.clone(),
+                title_regex: task.title_regex,
+                priority: Some(task.priority),
             };
 
             match add_video_source_internal(db.clone(), request).await {
@@ -1055,6 +1235,7 @@ impl ConfigTaskQueue {
             task_data: Set(task_data),
             status: Set(TaskStatus::Pending),
             retry_count: Set(0),
+            priority: Set(task.priority),
             created_at: Set(now_standard_string()),
             updated_at: Set(now_standard_string()),
             ..Default::default()
@@ -1069,7 +1250,7 @@ impl ConfigTaskQueue {
             queue.len() + 1,
             result.id
         );
-        queue.push_back(task);
+        push_by_priority(&mut queue, task);
 
         Ok(())
     }
@@ -1083,6 +1264,7 @@ impl ConfigTaskQueue {
             task_data: Set(task_data),
             status: Set(TaskStatus::Pending),
             retry_count: Set(0),
+            priority: Set(task.priority),
             created_at: Set(now_standard_string()),
             updated_at: Set(now_standard_string()),
             ..Default::default()
@@ -1097,7 +1279,7 @@ impl ConfigTaskQueue {
             queue.len() + 1,
             result.id
         );
-        queue.push_back(task);
+        push_by_priority(&mut queue, task);
 
         Ok(())
     }
@@ -1146,24 +1328,7 @@ impl ConfigTaskQueue {
         connection: &DatabaseConnection,
     ) -> Result<()> {
         let task_data = serde_json::to_string(task)?;
-
-        // 查找并更新数据库中的任务状态
-        if let Some(db_task) = TaskQueueEntity::find()
-            .filter(task_queue::Column::TaskType.eq(TaskType::UpdateConfig))
-            .filter(task_queue::Column::TaskData.eq(&task_data))
-            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-            .one(connection)
-            .await?
-        {
-            let retry_count = db_task.retry_count;
-            let mut active_model: task_queue::ActiveModel = db_task.into();
-            active_model.status = Set(TaskStatus::Failed);
-            active_model.retry_count = Set(retry_count + 1);
-            active_model.updated_at = Set(now_standard_string());
-            active_model.update(connection).await?;
-        }
-
-        Ok(())
+        mark_db_task_failed(TaskType::UpdateConfig, &task_data, connection).await
     }
 
     /// 标记重载配置任务为已完成（更新数据库状态）
@@ -1198,24 +1363,7 @@ impl ConfigTaskQueue {
         connection: &DatabaseConnection,
     ) -> Result<()> {
         let task_data = serde_json::to_string(task)?;
-
-        // 查找并更新数据库中的任务状态
-        if let Some(db_task) = TaskQueueEntity::find()
-            .filter(task_queue::Column::TaskType.eq(TaskType::ReloadConfig))
-            .filter(task_queue::Column::TaskData.eq(&task_data))
-            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-            .one(connection)
-            .await?
-        {
-            let retry_count = db_task.retry_count;
-            let mut active_model: task_queue::ActiveModel = db_task.into();
-            active_model.status = Set(TaskStatus::Failed);
-            active_model.retry_count = Set(retry_count + 1);
-            active_model.updated_at = Set(now_standard_string());
-            active_model.update(connection).await?;
-        }
-
-        Ok(())
+        mark_db_task_failed(TaskType::ReloadConfig, &task_data, connection).await
     }
 
     /// 获取更新配置队列长度
@@ -1266,6 +1414,7 @@ impl ConfigTaskQueue {
         let pending_tasks = TaskQueueEntity::find()
             .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
             .filter(task_queue::Column::TaskType.is_in([TaskType::UpdateConfig, TaskType::ReloadConfig]))
+            .order_by_desc(task_queue::Column::Priority)
             .order_by_asc(task_queue::Column::CreatedAt)
             .all(connection)
             .await?;
@@ -1275,18 +1424,20 @@ impl ConfigTaskQueue {
         for task_model in pending_tasks {
             match task_model.task_type {
                 TaskType::UpdateConfig => {
-                    if let Ok(task) = serde_json::from_str::<UpdateConfigTask>(&task_model.task_data) {
+                    if let Ok(mut task) = serde_json::from_str::<UpdateConfigTask>(&task_model.task_data) {
+                        task.priority = task_model.priority;
                         let mut queue = self.update_queue.lock().await;
-                        queue.push_back(task);
+                        push_by_priority(&mut queue, task);
                         recovered_count += 1;
                     } else {
                         warn!("无法反序列化更新配置任务数据: {}", task_model.task_data);
                     }
                 }
                 TaskType::ReloadConfig => {
-                    if let Ok(task) = serde_json::from_str::<ReloadConfigTask>(&task_model.task_data) {
+                    if let Ok(mut task) = serde_json::from_str::<ReloadConfigTask>(&task_model.task_data) {
+                        task.priority = task_model.priority;
                         let mut queue = self.reload_queue.lock().await;
-                        queue.push_back(task);
+                        push_by_priority(&mut queue, task);
                         recovered_count += 1;
                     } else {
                         warn!("无法反序列化重载配置任务数据: {}", task_model.task_data);
@@ -1384,8 +1535,15 @@ impl ConfigTaskQueue {
                 time_format: task.time_format.clone(),
                 interval: task.interval,
                 nfo_time_type: task.nfo_time_type.clone(),
+                nfo_flavor: task.nfo_flavor.clone(),
+                nfo_template: task.nfo_template.clone(),
+                nfo_download_actor_images: task.nfo_download_actor_images,
+                nfo_include_tags: task.nfo_include_tags,
+                nfo_max_tags: task.nfo_max_tags,
+                nfo_include_chapters: task.nfo_include_chapters,
                 parallel_download_enabled: task.parallel_download_enabled,
                 parallel_download_threads: task.parallel_download_threads,
+                download_speed_limit_kbps: task.download_speed_limit_kbps,
                 // 视频质量设置
                 video_max_quality: task.video_max_quality.clone(),
                 video_min_quality: task.video_min_quality.clone(),
@@ -1396,6 +1554,8 @@ impl ConfigTaskQueue {
                 no_dolby_audio: task.no_dolby_audio,
                 no_hdr: task.no_hdr,
                 no_hires: task.no_hires,
+                min_duration_secs: task.min_duration_secs,
+                max_duration_secs: task.max_duration_secs,
                 // 弹幕设置
                 danmaku_duration: task.danmaku_duration,
                 danmaku_font: task.danmaku_font.clone(),
@@ -1409,6 +1569,11 @@ impl ConfigTaskQueue {
                 danmaku_bold: task.danmaku_bold,
                 danmaku_outline: task.danmaku_outline,
                 danmaku_time_offset: task.danmaku_time_offset,
+                danmaku_formats: task.danmaku_formats.clone(),
+                danmaku_block_keywords: task.danmaku_block_keywords.clone(),
+                danmaku_block_regex: task.danmaku_block_regex,
+                danmaku_max_per_second: task.danmaku_max_per_second,
+                danmaku_exclude_modes: task.danmaku_exclude_modes.clone(),
                 // 并发控制设置
                 concurrent_video: task.concurrent_video,
                 concurrent_page: task.concurrent_page,
@@ -1416,6 +1581,12 @@ impl ConfigTaskQueue {
                 rate_duration: task.rate_duration,
                 // 其他设置
                 cdn_sorting: task.cdn_sorting,
+                verify_download_integrity: task.verify_download_integrity,
+                download_subtitles: task.download_subtitles,
+                bangumi_write_skip_markers: task.bangumi_write_skip_markers,
+                bangumi_skip_specials: task.bangumi_skip_specials,
+                proxy_url: task.proxy_url.clone(),
+                user_agent: task.user_agent.clone(),
                 // UP主投稿风控配置
                 large_submission_threshold: task.large_submission_threshold,
                 base_request_delay: task.base_request_delay,
@@ -1432,6 +1603,79 @@ impl ConfigTaskQueue {
                 auto_backoff_max_multiplier: task.auto_backoff_max_multiplier,
                 source_delay_seconds: task.source_delay_seconds,
                 submission_source_delay_seconds: task.submission_source_delay_seconds,
+                // 静默时段配置
+                quiet_hours_enabled: task.quiet_hours_enabled,
+                quiet_hours_start: task.quiet_hours_start.clone(),
+                quiet_hours_end: task.quiet_hours_end.clone(),
+                quiet_hours_weekdays: task.quiet_hours_weekdays.clone(),
+                // 外部aria2 RPC配置
+                aria2_rpc_url: task.aria2_rpc_url.clone(),
+                aria2_rpc_secret: task.aria2_rpc_secret.clone(),
+                // Jellyfin 媒体库刷新配置
+                jellyfin_url: task.jellyfin_url.clone(),
+                jellyfin_api_key: task.jellyfin_api_key.clone(),
+                // 队列任务最大重试次数
+                task_max_retry: task.task_max_retry,
+                // WebDAV 上传配置
+                webdav_url: task.webdav_url.clone(),
+                webdav_username: task.webdav_username.clone(),
+                webdav_password: task.webdav_password.clone(),
+                webdav_base_path: task.webdav_base_path.clone(),
+                webdav_delete_local: task.webdav_delete_local,
+                // 保留策略清理视频时是否删除本地文件
+                retention_delete_local_files: task.retention_delete_local_files,
+                // 跨视频源的重复分页去重方式
+                dedup_strategy: task.dedup_strategy.clone(),
+                // 下载暂存目录
+                temp_dir: task.temp_dir.clone(),
+                // 磁盘剩余空间阈值（GB）
+                min_free_disk_gb: task.min_free_disk_gb,
+                // 同一 IP 每分钟允许的认证相关请求数
+                api_rate_limit_per_minute: task.api_rate_limit_per_minute,
+                // 是否信任媒体目录中已存在的文件
+                trust_existing_files: task.trust_existing_files,
+                // FFmpeg 路径与硬件加速方式
+                ffmpeg_path: task.ffmpeg_path.clone(),
+                ffmpeg_hwaccel: task.ffmpeg_hwaccel.clone(),
+                // 封面缺失时是否从视频中截取一帧作为封面
+                generate_thumbnail_fallback: task.generate_thumbnail_fallback,
+                // 扫描合集时是否同步最新封面
+                refresh_covers: task.refresh_covers,
+                // UP主头像检查间隔（天）
+                refresh_upper_avatar_days: task.refresh_upper_avatar_days,
+                // 同时扫描的视频源数量
+                source_scan_concurrency: task.source_scan_concurrency,
+                // 番剧标题缓存的最大条目数与过期时间（秒）
+                season_title_cache_size: task.season_title_cache_size,
+                season_title_cache_ttl_secs: task.season_title_cache_ttl_secs,
+                // 数据库连接池配置
+                db_max_connections: task.db_max_connections,
+                db_acquire_timeout: task.db_acquire_timeout,
+                db_busy_timeout: task.db_busy_timeout,
+                // 回收站配置
+                delete_to_trash: task.delete_to_trash,
+                trash_retention_days: task.trash_retention_days,
+                // FFmpeg 合并与弹幕嵌入的超时时间（秒）
+                ffmpeg_merge_timeout_secs: task.ffmpeg_merge_timeout_secs,
+                // 封面最大宽度与格式
+                cover_max_width: task.cover_max_width,
+                cover_format: task.cover_format.clone(),
+                // 允许跨域访问的来源列表
+                cors_allowed_origins: task.cors_allowed_origins.clone(),
+                // 文件名安全化策略与最大字节数
+                filename_policy: task.filename_policy.clone(),
+                max_filename_bytes: task.max_filename_bytes,
+                // 完整路径的最大字节数
+                max_path_bytes: task.max_path_bytes,
+                // 下载失败重试次数与基础等待时间（毫秒）
+                download_max_retries: task.download_max_retries,
+                download_retry_base_delay_ms: task.download_retry_base_delay_ms,
+                // 是否跳过无权观看的充电专享视频
+                skip_upower_exclusive: task.skip_upower_exclusive,
+                // 是否在每轮扫描后写入扫描报告文件
+                write_scan_report: task.write_scan_report,
+                // 访问B站时附加的请求头
+                extra_headers: task.extra_headers.clone(),
                 // 系统配置相关字段，任务队列中不使用
                 scan_deleted_videos: None,
                 // aria2监控配置，任务队列中不使用
@@ -1455,8 +1699,10 @@ impl ConfigTaskQueue {
                 risk_control_auto_solve_api_key: None,
                 risk_control_auto_solve_max_retries: None,
                 risk_control_auto_solve_timeout: None,
+                risk_control_fingerprint_strategy: None,
                 // 服务器绑定地址，任务队列中不使用
                 bind_address: None,
+                priority: Some(task.priority),
             };
 
             match update_config_internal(db.clone(), request).await {
@@ -1488,6 +1734,17 @@ impl ConfigTaskQueue {
         while let Some(task) = self.dequeue_reload_task().await {
             info!("正在处理重载配置任务");
 
+            // 先保存任务携带的凭证，否则重载会用数据库中的旧凭证覆盖内存中的新凭证
+            if let Some(credential) = &task.credential {
+                if let Err(e) = save_credential(&db, credential).await {
+                    error!("重载配置任务保存B站凭证失败: {:#}", e);
+                    if let Err(e) = self.mark_reload_task_failed(&task, &db).await {
+                        error!("更新任务失败状态失败: {:#}", e);
+                    }
+                    continue;
+                }
+            }
+
             match reload_config_internal().await {
                 Ok(_) => {
                     info!("重载配置任务执行成功");
@@ -1540,6 +1797,41 @@ impl ConfigTaskQueue {
     }
 }
 
+/// 按需扫描任务最多保留的已结束记录数
+const MAX_FINISHED_SOURCE_SCANS: usize = 100;
+
+/// 按需扫描任务的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceScanStatus {
+    /// 已加入队列，等待扫描任务处理
+    Queued,
+    /// 正在扫描
+    Running,
+    /// 扫描完成
+    Completed,
+    /// 扫描失败或已取消
+    Failed,
+}
+
+impl SourceScanStatus {
+    fn is_pending(self) -> bool {
+        matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// 单个视频源的按需扫描任务
+#[derive(Debug, Clone)]
+pub struct SourceScanTask {
+    pub task_id: String, // 唯一任务ID，用于追踪
+    pub source_type: SourceType,
+    pub source_id: i32,
+    pub status: SourceScanStatus,
+    pub message: Option<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+}
+
 /// 全局任务控制器，用于控制定时扫描任务的暂停和恢复
 pub struct TaskController {
     /// 是否暂停定时扫描任务
@@ -1548,10 +1840,24 @@ pub struct TaskController {
     pub is_scanning: AtomicBool,
     /// 是否刚刚恢复（用于立即开始新扫描）
     pub just_resumed: AtomicBool,
+    /// 当前的暂停是否由静默时段触发（手动暂停不会在静默时段结束时自动恢复）
+    pub paused_by_quiet_hours: AtomicBool,
+    /// 当前的暂停是否由磁盘空间不足触发（空间恢复后自动恢复）
+    pub paused_by_low_disk: AtomicBool,
     /// 全局取消令牌，用于取消所有下载任务
     pub cancellation_token: Arc<Mutex<CancellationToken>>,
     /// 下载器的引用，用于暂停时停止下载
     pub downloader: Arc<Mutex<Option<Arc<crate::unified_downloader::UnifiedDownloader>>>>,
+    /// 按需扫描任务列表（包括排队中和最近结束的任务）
+    pub source_scans: parking_lot::Mutex<VecDeque<SourceScanTask>>,
+    /// 是否有外部触发的全量扫描请求，不受扫描间隔限制
+    pub full_scan_requested: AtomicBool,
+    /// 下一轮扫描开始前是否重置 NFO 子任务，重新生成已下载视频的元数据
+    pub force_metadata_requested: AtomicBool,
+    /// 单独暂停的视频源，扫描时跳过，不影响其他视频源；暂停状态同时保存在各视频源表的 paused 字段中
+    pub paused_sources: parking_lot::Mutex<HashSet<(SourceType, i32)>>,
+    /// 各视频源当前扫描使用的取消令牌（全局令牌的子令牌），暂停视频源时取消其正在进行的下载
+    pub source_tokens: parking_lot::Mutex<HashMap<(SourceType, i32), CancellationToken>>,
 }
 
 impl TaskController {
@@ -1560,8 +1866,15 @@ impl TaskController {
             is_paused: AtomicBool::new(false),
             is_scanning: AtomicBool::new(false),
             just_resumed: AtomicBool::new(false),
+            paused_by_quiet_hours: AtomicBool::new(false),
+            paused_by_low_disk: AtomicBool::new(false),
             cancellation_token: Arc::new(Mutex::new(CancellationToken::new())),
             downloader: Arc::new(Mutex::new(None)),
+            source_scans: parking_lot::Mutex::new(VecDeque::new()),
+            full_scan_requested: AtomicBool::new(false),
+            force_metadata_requested: AtomicBool::new(false),
+            paused_sources: parking_lot::Mutex::new(HashSet::new()),
+            source_tokens: parking_lot::Mutex::new(HashMap::new()),
         }
     }
 
@@ -1622,6 +1935,37 @@ impl TaskController {
         info!("定时扫描任务已恢复，将立即开始新一轮扫描");
     }
 
+    /// 根据是否处于静默时段自动暂停或恢复任务
+    pub async fn apply_quiet_hours(&self, in_quiet_hours: bool) {
+        if in_quiet_hours {
+            // 已被手动暂停时不接管，避免静默时段结束时误恢复
+            if !self.is_paused() {
+                info!("进入静默时段，自动暂停扫描和下载任务");
+                self.pause().await;
+                self.paused_by_quiet_hours.store(true, Ordering::SeqCst);
+            }
+        } else if self.paused_by_quiet_hours.swap(false, Ordering::SeqCst) && self.is_paused() {
+            info!("静默时段结束，自动恢复扫描和下载任务");
+            self.resume();
+        }
+    }
+
+    /// 根据磁盘剩余空间是否不足自动暂停或恢复任务，返回本次是否新触发了暂停
+    pub async fn apply_low_disk_space(&self, low_disk_space: bool) -> bool {
+        if low_disk_space {
+            // 已处于暂停状态时不接管，避免覆盖手动暂停或静默时段的恢复逻辑
+            if !self.is_paused() {
+                self.pause().await;
+                self.paused_by_low_disk.store(true, Ordering::SeqCst);
+                return true;
+            }
+        } else if self.paused_by_low_disk.swap(false, Ordering::SeqCst) && self.is_paused() {
+            info!("磁盘空间已恢复，自动恢复扫描和下载任务");
+            self.resume();
+        }
+        false
+    }
+
     /// 检查是否暂停
     pub fn is_paused(&self) -> bool {
         self.is_paused.load(Ordering::SeqCst)
@@ -1665,6 +2009,186 @@ impl TaskController {
         let mut token = self.cancellation_token.lock().await;
         *token = CancellationToken::new();
     }
+
+    /// 获取指定视频源使用的取消令牌
+    ///
+    /// 令牌为全局令牌的子令牌，全局暂停或单独暂停该视频源时都会被取消
+    pub async fn get_source_cancellation_token(&self, source_type: SourceType, source_id: i32) -> CancellationToken {
+        let token = self.get_cancellation_token().await.child_token();
+        if self.is_source_paused(source_type, source_id) {
+            token.cancel();
+        }
+        self.source_tokens
+            .lock()
+            .insert((source_type, source_id), token.clone());
+        token
+    }
+
+    /// 单独暂停指定视频源，取消其正在进行的下载和排队中的按需扫描，返回此前是否未暂停
+    pub fn pause_source(&self, source_type: SourceType, source_id: i32) -> bool {
+        let newly_paused = self.paused_sources.lock().insert((source_type, source_id));
+        if let Some(token) = self.source_tokens.lock().remove(&(source_type, source_id)) {
+            token.cancel();
+        }
+        for task in self.source_scans.lock().iter_mut() {
+            if task.source_type == source_type && task.source_id == source_id && task.status == SourceScanStatus::Queued
+            {
+                task.status = SourceScanStatus::Failed;
+                task.message = Some("视频源已暂停，扫描已取消".to_string());
+                task.finished_at = Some(now_standard_string());
+            }
+        }
+        newly_paused
+    }
+
+    /// 恢复单独暂停的视频源，返回此前是否处于暂停状态
+    pub fn resume_source(&self, source_type: SourceType, source_id: i32) -> bool {
+        self.paused_sources.lock().remove(&(source_type, source_id))
+    }
+
+    /// 指定视频源是否被单独暂停
+    pub fn is_source_paused(&self, source_type: SourceType, source_id: i32) -> bool {
+        self.paused_sources.lock().contains(&(source_type, source_id))
+    }
+
+    /// 获取所有单独暂停的视频源
+    pub fn paused_sources(&self) -> HashSet<(SourceType, i32)> {
+        self.paused_sources.lock().clone()
+    }
+
+    /// 请求立即扫描所有视频源，返回请求时是否已有扫描正在进行（此时在当前扫描结束后立即开始新一轮）
+    pub fn request_full_scan(&self, force_metadata: bool) -> bool {
+        if force_metadata {
+            self.force_metadata_requested.store(true, Ordering::SeqCst);
+        }
+        self.full_scan_requested.store(true, Ordering::SeqCst);
+        self.is_scanning()
+    }
+
+    /// 是否有尚未开始的全量扫描请求
+    pub fn has_full_scan_request(&self) -> bool {
+        self.full_scan_requested.load(Ordering::SeqCst)
+    }
+
+    /// 取出全量扫描请求（并重置标志）
+    pub fn take_full_scan_request(&self) -> bool {
+        self.full_scan_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// 取出重新生成元数据的请求（并重置标志）
+    pub fn take_force_metadata_request(&self) -> bool {
+        self.force_metadata_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// 请求立即扫描指定视频源
+    ///
+    /// 同一视频源已有排队中或正在扫描的任务时不会重复入队，返回已有任务和 `false`
+    pub fn request_source_scan(&self, source_type: SourceType, source_id: i32) -> (SourceScanTask, bool) {
+        let mut scans = self.source_scans.lock();
+        if let Some(existing) = scans
+            .iter()
+            .find(|task| task.source_type == source_type && task.source_id == source_id && task.status.is_pending())
+        {
+            return (existing.clone(), false);
+        }
+
+        let task = SourceScanTask {
+            task_id: uuid::Uuid::new_v4().to_string(),
+            source_type,
+            source_id,
+            status: SourceScanStatus::Queued,
+            message: None,
+            created_at: now_standard_string(),
+            finished_at: None,
+        };
+        scans.push_back(task.clone());
+
+        // 清理最早结束的记录，避免无限增长
+        let mut finished_count = scans.iter().filter(|task| !task.status.is_pending()).count();
+        while finished_count > MAX_FINISHED_SOURCE_SCANS {
+            if let Some(index) = scans.iter().position(|task| !task.status.is_pending()) {
+                scans.remove(index);
+            }
+            finished_count -= 1;
+        }
+
+        (task, true)
+    }
+
+    /// 根据任务ID查询按需扫描任务
+    pub fn get_source_scan(&self, task_id: &str) -> Option<SourceScanTask> {
+        self.source_scans
+            .lock()
+            .iter()
+            .find(|task| task.task_id == task_id)
+            .cloned()
+    }
+
+    /// 获取所有排队中的按需扫描视频源
+    pub fn queued_source_scans(&self) -> HashSet<(SourceType, i32)> {
+        self.source_scans
+            .lock()
+            .iter()
+            .filter(|task| task.status == SourceScanStatus::Queued)
+            .map(|task| (task.source_type, task.source_id))
+            .collect()
+    }
+
+    /// 是否有排队中的按需扫描任务
+    pub fn has_queued_source_scans(&self) -> bool {
+        self.source_scans
+            .lock()
+            .iter()
+            .any(|task| task.status == SourceScanStatus::Queued)
+    }
+
+    /// 将指定视频源排队中的按需扫描任务标记为正在扫描
+    pub fn start_source_scan(&self, source_type: SourceType, source_id: i32) {
+        for task in self.source_scans.lock().iter_mut() {
+            if task.source_type == source_type && task.source_id == source_id && task.status == SourceScanStatus::Queued
+            {
+                task.status = SourceScanStatus::Running;
+            }
+        }
+    }
+
+    /// 结束指定视频源正在扫描的按需扫描任务
+    pub fn finish_source_scan(&self, source_type: SourceType, source_id: i32, result: Result<String, String>) {
+        let (status, message) = match result {
+            Ok(message) => (SourceScanStatus::Completed, message),
+            Err(message) => (SourceScanStatus::Failed, message),
+        };
+        for task in self.source_scans.lock().iter_mut() {
+            if task.source_type == source_type
+                && task.source_id == source_id
+                && task.status == SourceScanStatus::Running
+            {
+                task.status = status;
+                task.message = Some(message.clone());
+                task.finished_at = Some(now_standard_string());
+            }
+        }
+    }
+
+    /// 本轮未能完成的按需扫描任务重新排队，等待下一轮处理
+    pub fn requeue_running_source_scans(&self) {
+        for task in self.source_scans.lock().iter_mut() {
+            if task.status == SourceScanStatus::Running {
+                task.status = SourceScanStatus::Queued;
+            }
+        }
+    }
+
+    /// 取消已不在启用视频源列表中的排队任务（视频源被删除、禁用或暂停）
+    pub fn cancel_missing_source_scans(&self, enabled_sources: &HashSet<(SourceType, i32)>) {
+        for task in self.source_scans.lock().iter_mut() {
+            if task.status.is_pending() && !enabled_sources.contains(&(task.source_type, task.source_id)) {
+                task.status = SourceScanStatus::Failed;
+                task.message = Some("视频源已被删除、禁用或暂停，扫描已取消".to_string());
+                task.finished_at = Some(now_standard_string());
+            }
+        }
+    }
 }
 
 impl Default for TaskController {
@@ -1696,6 +2220,9 @@ pub static VIDEO_DELETE_TASK_QUEUE: once_cell::sync::Lazy<Arc<VideoDeleteTaskQue
 /// 暂停定时扫描任务的便捷函数
 pub async fn pause_scanning() {
     TASK_CONTROLLER.pause().await;
+    // 手动暂停优先于静默时段，时段结束时不自动恢复
+    TASK_CONTROLLER.paused_by_quiet_hours.store(false, Ordering::SeqCst);
+    TASK_CONTROLLER.paused_by_low_disk.store(false, Ordering::SeqCst);
 }
 
 /// 恢复定时扫描任务的便捷函数
@@ -1734,6 +2261,13 @@ pub async fn enqueue_update_task(task: UpdateConfigTask, connection: &DatabaseCo
 }
 
 /// 添加重载配置任务到队列的便捷函数
+/// 将B站凭证保存到数据库的配置项中
+async fn save_credential(connection: &DatabaseConnection, credential: &crate::bilibili::Credential) -> Result<()> {
+    crate::config::ConfigManager::new(connection.clone())
+        .update_config_item("credential", serde_json::to_value(credential)?)
+        .await
+}
+
 pub async fn enqueue_reload_task(task: ReloadConfigTask, connection: &DatabaseConnection) -> Result<()> {
     CONFIG_TASK_QUEUE.enqueue_reload_task(task, connection).await
 }
@@ -1754,75 +2288,188 @@ pub async fn process_video_delete_tasks(db: Arc<DatabaseConnection>) -> Result<u
 }
 
 /// 从数据库恢复待处理的任务到内存队列中
+/// 根据任务数据中的 task_id 查找任务记录，存在多条时返回最新的一条
+pub async fn find_task_by_task_id(task_id: &str, connection: &DatabaseConnection) -> Result<Option<task_queue::Model>> {
+    // 先用 LIKE 缩小范围，再解析任务数据精确匹配
+    let candidates = TaskQueueEntity::find()
+        .filter(task_queue::Column::TaskData.contains(format!("\"task_id\":\"{}\"", task_id)))
+        .order_by_desc(task_queue::Column::Id)
+        .all(connection)
+        .await?;
+
+    Ok(candidates.into_iter().find(|db_task| {
+        serde_json::from_str::<serde_json::Value>(&db_task.task_data)
+            .is_ok_and(|data| data["task_id"].as_str() == Some(task_id))
+    }))
+}
+
+/// 取消尚未执行的任务：从内存队列中移除并将数据库记录标记为已取消
+///
+/// 任务已执行完成、正在执行或不存在时返回 false
+pub async fn cancel_pending_task(task_id: &str, connection: &DatabaseConnection) -> Result<bool> {
+    let Some(db_task) = find_task_by_task_id(task_id, connection)
+        .await?
+        .filter(|db_task| db_task.status == TaskStatus::Pending)
+    else {
+        return Ok(false);
+    };
+
+    // 已出队的任务正在执行，无法取消
+    let removed = match db_task.task_type {
+        TaskType::DeleteVideoSource => remove_by_task_id(&mut *DELETE_TASK_QUEUE.queue.lock().await, task_id),
+        TaskType::DeleteVideo => remove_by_task_id(&mut *VIDEO_DELETE_TASK_QUEUE.queue.lock().await, task_id),
+        TaskType::AddVideoSource => remove_by_task_id(&mut *ADD_TASK_QUEUE.queue.lock().await, task_id),
+        TaskType::UpdateConfig => remove_by_task_id(&mut *CONFIG_TASK_QUEUE.update_queue.lock().await, task_id),
+        TaskType::ReloadConfig => remove_by_task_id(&mut *CONFIG_TASK_QUEUE.reload_queue.lock().await, task_id),
+    };
+    if !removed {
+        return Ok(false);
+    }
+
+    let mut active_model: task_queue::ActiveModel = db_task.into();
+    active_model.status = Set(TaskStatus::Cancelled);
+    active_model.updated_at = Set(now_standard_string());
+    active_model.update(connection).await?;
+
+    info!("任务已取消: {}", task_id);
+    Ok(true)
+}
+
+/// 将视频源的单独暂停状态写入数据库，重启后由 [`restore_paused_sources`] 恢复
+pub async fn save_source_paused(
+    connection: &DatabaseConnection,
+    source_type: SourceType,
+    source_id: i32,
+    paused: bool,
+) -> Result<()> {
+    use bili_sync_entity::{collection, favorite, submission, video_source, watch_later};
+    use sea_orm::prelude::Expr;
+
+    match source_type {
+        SourceType::Collection => {
+            collection::Entity::update_many()
+                .col_expr(collection::Column::Paused, Expr::value(paused))
+                .filter(collection::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Favorite => {
+            favorite::Entity::update_many()
+                .col_expr(favorite::Column::Paused, Expr::value(paused))
+                .filter(favorite::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Submission => {
+            submission::Entity::update_many()
+                .col_expr(submission::Column::Paused, Expr::value(paused))
+                .filter(submission::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::WatchLater => {
+            watch_later::Entity::update_many()
+                .col_expr(watch_later::Column::Paused, Expr::value(paused))
+                .filter(watch_later::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Bangumi => {
+            video_source::Entity::update_many()
+                .col_expr(video_source::Column::Paused, Expr::value(paused))
+                .filter(video_source::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 启动时从数据库恢复单独暂停的视频源，返回恢复的数量
+pub async fn restore_paused_sources(connection: &DatabaseConnection) -> Result<usize> {
+    use bili_sync_entity::{collection, favorite, submission, video_source, watch_later};
+    use sea_orm::QuerySelect;
+
+    let mut paused = Vec::new();
+    paused.extend(
+        collection::Entity::find()
+            .select_only()
+            .column(collection::Column::Id)
+            .filter(collection::Column::Paused.eq(true))
+            .into_tuple::<i32>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|id| (SourceType::Collection, id)),
+    );
+    paused.extend(
+        favorite::Entity::find()
+            .select_only()
+            .column(favorite::Column::Id)
+            .filter(favorite::Column::Paused.eq(true))
+            .into_tuple::<i32>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|id| (SourceType::Favorite, id)),
+    );
+    paused.extend(
+        submission::Entity::find()
+            .select_only()
+            .column(submission::Column::Id)
+            .filter(submission::Column::Paused.eq(true))
+            .into_tuple::<i32>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|id| (SourceType::Submission, id)),
+    );
+    paused.extend(
+        watch_later::Entity::find()
+            .select_only()
+            .column(watch_later::Column::Id)
+            .filter(watch_later::Column::Paused.eq(true))
+            .into_tuple::<i32>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|id| (SourceType::WatchLater, id)),
+    );
+    paused.extend(
+        video_source::Entity::find()
+            .select_only()
+            .column(video_source::Column::Id)
+            .filter(video_source::Column::Paused.eq(true))
+            .into_tuple::<i32>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|id| (SourceType::Bangumi, id)),
+    );
+
+    let count = paused.len();
+    TASK_CONTROLLER.paused_sources.lock().extend(paused);
+    Ok(count)
+}
+
 pub async fn recover_pending_tasks(connection: &DatabaseConnection) -> Result<(), anyhow::Error> {
     info!("开始恢复数据库中的待处理任务到内存队列");
 
-    // 查询所有待处理状态的任务
+    // 查询所有待处理状态的任务，死信任务不会被恢复
     let pending_tasks = TaskQueueEntity::find()
         .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
-        .order_by_asc(task_queue::Column::CreatedAt) // 按创建时间排序
+        .order_by_desc(task_queue::Column::Priority) // 优先级高的先恢复
+        .order_by_asc(task_queue::Column::CreatedAt) // 同优先级按创建时间排序
         .all(connection)
         .await?;
 
     let mut recovered_count = 0;
 
     for db_task in pending_tasks {
-        let task_data = &db_task.task_data;
-
-        match db_task.task_type {
-            TaskType::DeleteVideoSource => {
-                match serde_json::from_str::<DeleteVideoSourceTask>(task_data) {
-                    Ok(task) => {
-                        // 直接添加到内存队列，不再写入数据库
-                        let mut queue = DELETE_TASK_QUEUE.queue.lock().await;
-                        queue.push_back(task);
-                        recovered_count += 1;
-                    }
-                    Err(e) => {
-                        error!("反序列化删除视频源任务失败: {:#}", e);
-                    }
-                }
-            }
-            TaskType::DeleteVideo => match serde_json::from_str::<DeleteVideoTask>(task_data) {
-                Ok(task) => {
-                    let mut queue = VIDEO_DELETE_TASK_QUEUE.queue.lock().await;
-                    queue.push_back(task);
-                    recovered_count += 1;
-                }
-                Err(e) => {
-                    error!("反序列化删除视频任务失败: {:#}", e);
-                }
-            },
-            TaskType::AddVideoSource => match serde_json::from_str::<AddVideoSourceTask>(task_data) {
-                Ok(task) => {
-                    let mut queue = ADD_TASK_QUEUE.queue.lock().await;
-                    queue.push_back(task);
-                    recovered_count += 1;
-                }
-                Err(e) => {
-                    error!("反序列化添加视频源任务失败: {:#}", e);
-                }
-            },
-            TaskType::UpdateConfig => match serde_json::from_str::<UpdateConfigTask>(task_data) {
-                Ok(task) => {
-                    let mut queue = CONFIG_TASK_QUEUE.update_queue.lock().await;
-                    queue.push_back(task);
-                    recovered_count += 1;
-                }
-                Err(e) => {
-                    error!("反序列化更新配置任务失败: {:#}", e);
-                }
-            },
-            TaskType::ReloadConfig => match serde_json::from_str::<ReloadConfigTask>(task_data) {
-                Ok(task) => {
-                    let mut queue = CONFIG_TASK_QUEUE.reload_queue.lock().await;
-                    queue.push_back(task);
-                    recovered_count += 1;
-                }
-                Err(e) => {
-                    error!("反序列化重载配置任务失败: {:#}", e);
-                }
-            },
+        // 直接添加到内存队列，不再写入数据库
+        match push_db_task_to_queue(&db_task).await {
+            Ok(()) => recovered_count += 1,
+            Err(e) => error!("反序列化任务失败 (数据库ID: {}): {:#}", db_task.id, e),
         }
     }
 
@@ -1834,3 +2481,238 @@ pub async fn recover_pending_tasks(connection: &DatabaseConnection) -> Result<()
 
     Ok(())
 }
+
+/// 将数据库中的任务记录反序列化后按优先级放入对应的内存队列
+async fn push_db_task_to_queue(db_task: &task_queue::Model) -> Result<()> {
+    let task_data = &db_task.task_data;
+    match db_task.task_type {
+        TaskType::DeleteVideoSource => {
+            let mut task = serde_json::from_str::<DeleteVideoSourceTask>(task_data)?;
+            task.priority = db_task.priority;
+            push_by_priority(&mut *DELETE_TASK_QUEUE.queue.lock().await, task);
+        }
+        TaskType::DeleteVideo => {
+            let mut task = serde_json::from_str::<DeleteVideoTask>(task_data)?;
+            task.priority = db_task.priority;
+            push_by_priority(&mut *VIDEO_DELETE_TASK_QUEUE.queue.lock().await, task);
+        }
+        TaskType::AddVideoSource => {
+            let mut task = serde_json::from_str::<AddVideoSourceTask>(task_data)?;
+            task.priority = db_task.priority;
+            push_by_priority(&mut *ADD_TASK_QUEUE.queue.lock().await, task);
+        }
+        TaskType::UpdateConfig => {
+            let mut task = serde_json::from_str::<UpdateConfigTask>(task_data)?;
+            task.priority = db_task.priority;
+            push_by_priority(&mut *CONFIG_TASK_QUEUE.update_queue.lock().await, task);
+        }
+        TaskType::ReloadConfig => {
+            let mut task = serde_json::from_str::<ReloadConfigTask>(task_data)?;
+            task.priority = db_task.priority;
+            push_by_priority(&mut *CONFIG_TASK_QUEUE.reload_queue.lock().await, task);
+        }
+    }
+    Ok(())
+}
+
+/// 标记数据库中的任务执行失败
+///
+/// 失败次数未超过上限时任务保持待处理状态，下次恢复队列时会重试；
+/// 超过上限后移入死信状态，不再自动恢复
+async fn mark_db_task_failed(task_type: TaskType, task_data: &str, connection: &DatabaseConnection) -> Result<()> {
+    let Some(db_task) = TaskQueueEntity::find()
+        .filter(task_queue::Column::TaskType.eq(task_type))
+        .filter(task_queue::Column::TaskData.eq(task_data))
+        .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+        .one(connection)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let retry_count = db_task.retry_count + 1;
+    let max_retry = crate::config::reload_config().task_max_retry as i32;
+    let dead_letter = retry_count > max_retry;
+    if dead_letter {
+        warn!(
+            "任务失败 {} 次，超过最大重试次数 {}，已移入死信队列 (数据库ID: {}): {}",
+            retry_count, max_retry, db_task.id, task_data
+        );
+    }
+
+    let mut active_model: task_queue::ActiveModel = db_task.into();
+    if dead_letter {
+        active_model.status = Set(TaskStatus::DeadLetter);
+    }
+    active_model.retry_count = Set(retry_count);
+    active_model.updated_at = Set(now_standard_string());
+    active_model.update(connection).await?;
+
+    Ok(())
+}
+
+/// 获取所有死信任务，最近失败的排在前面
+pub async fn list_dead_letter_tasks(connection: &DatabaseConnection) -> Result<Vec<task_queue::Model>> {
+    Ok(TaskQueueEntity::find()
+        .filter(task_queue::Column::Status.eq(TaskStatus::DeadLetter))
+        .order_by_desc(task_queue::Column::UpdatedAt)
+        .all(connection)
+        .await?)
+}
+
+/// 将死信任务重新放回队列，重置重试次数
+///
+/// 找不到对应的死信任务时返回 false
+pub async fn requeue_dead_letter_task(id: i32, connection: &DatabaseConnection) -> Result<bool> {
+    let Some(db_task) = TaskQueueEntity::find_by_id(id)
+        .filter(task_queue::Column::Status.eq(TaskStatus::DeadLetter))
+        .one(connection)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    let mut active_model: task_queue::ActiveModel = db_task.into();
+    active_model.status = Set(TaskStatus::Pending);
+    active_model.retry_count = Set(0);
+    active_model.updated_at = Set(now_standard_string());
+    let db_task = active_model.update(connection).await?;
+
+    push_db_task_to_queue(&db_task).await?;
+    info!("死信任务已重新加入队列 (数据库ID: {})", id);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_queue_operations() {
+        let mut queue = VecDeque::new();
+        for (task_id, priority) in [
+            ("a", 0),
+            ("b", 5),
+            ("c", 0),
+            ("d", 5),
+            ("e", RELOAD_CONFIG_TASK_PRIORITY),
+        ] {
+            push_by_priority(
+                &mut queue,
+                DeleteVideoTask {
+                    video_id: 1,
+                    delete_local_files: true,
+                    priority,
+                    task_id: task_id.to_string(),
+                },
+            );
+        }
+        // 优先级高的先出队，同优先级保持入队顺序
+        let order: Vec<_> = queue.iter().map(|task| task.task_id.as_str()).collect();
+        assert_eq!(order, ["e", "b", "d", "a", "c"]);
+
+        // 优先级不写入任务数据，避免影响按任务数据匹配数据库记录
+        let task_data = serde_json::to_string(&queue[0]).unwrap();
+        assert!(!task_data.contains("priority"));
+
+        assert!(remove_by_task_id(&mut queue, "b"));
+        assert!(!remove_by_task_id(&mut queue, "b"));
+        let order: Vec<_> = queue.iter().map(|task| task.task_id.as_str()).collect();
+        assert_eq!(order, ["e", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn test_source_scan_deduplication() {
+        let controller = TaskController::new();
+
+        let (first, queued) = controller.request_source_scan(SourceType::Favorite, 1);
+        assert!(queued);
+        assert_eq!(first.status, SourceScanStatus::Queued);
+
+        // 排队中和扫描中的视频源不会重复入队
+        let (duplicate, queued) = controller.request_source_scan(SourceType::Favorite, 1);
+        assert!(!queued);
+        assert_eq!(duplicate.task_id, first.task_id);
+        controller.start_source_scan(SourceType::Favorite, 1);
+        assert!(!controller.request_source_scan(SourceType::Favorite, 1).1);
+        assert!(!controller.has_queued_source_scans());
+
+        // 同 ID 的其他类型视频源互不影响
+        let (other, queued) = controller.request_source_scan(SourceType::Collection, 1);
+        assert!(queued);
+        assert_ne!(other.task_id, first.task_id);
+
+        controller.finish_source_scan(SourceType::Favorite, 1, Ok("扫描完成".to_string()));
+        let finished = controller.get_source_scan(&first.task_id).unwrap();
+        assert_eq!(finished.status, SourceScanStatus::Completed);
+        assert!(finished.finished_at.is_some());
+
+        // 扫描结束后可以再次入队
+        let (again, queued) = controller.request_source_scan(SourceType::Favorite, 1);
+        assert!(queued);
+        assert_ne!(again.task_id, first.task_id);
+
+        // 被禁用的视频源的排队任务会被取消
+        controller.cancel_missing_source_scans(&HashSet::from([(SourceType::Favorite, 1)]));
+        assert_eq!(
+            controller.get_source_scan(&other.task_id).unwrap().status,
+            SourceScanStatus::Failed
+        );
+        assert_eq!(
+            controller.queued_source_scans(),
+            HashSet::from([(SourceType::Favorite, 1)])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_source_pause() {
+        let controller = TaskController::new();
+        let running = controller.get_source_cancellation_token(SourceType::Favorite, 1).await;
+        let other = controller.get_source_cancellation_token(SourceType::Favorite, 2).await;
+        let (queued, _) = controller.request_source_scan(SourceType::Favorite, 1);
+
+        // 暂停只取消该视频源的下载和排队任务，其他视频源不受影响
+        assert!(controller.pause_source(SourceType::Favorite, 1));
+        assert!(!controller.pause_source(SourceType::Favorite, 1));
+        assert!(running.is_cancelled());
+        assert!(!other.is_cancelled());
+        assert_eq!(
+            controller.get_source_scan(&queued.task_id).unwrap().status,
+            SourceScanStatus::Failed
+        );
+        assert!(controller
+            .get_source_cancellation_token(SourceType::Favorite, 1)
+            .await
+            .is_cancelled());
+
+        assert!(controller.resume_source(SourceType::Favorite, 1));
+        assert!(!controller.resume_source(SourceType::Favorite, 1));
+        assert!(controller.paused_sources().is_empty());
+        assert!(!controller
+            .get_source_cancellation_token(SourceType::Favorite, 1)
+            .await
+            .is_cancelled());
+
+        // 全局暂停仍会取消所有视频源的令牌
+        controller.pause().await;
+        assert!(other.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_low_disk_space_pause() {
+        let controller = TaskController::new();
+
+        // 空间不足时暂停，空间恢复后自动恢复
+        assert!(controller.apply_low_disk_space(true).await);
+        assert!(controller.is_paused());
+        assert!(!controller.apply_low_disk_space(true).await);
+        controller.apply_low_disk_space(false).await;
+        assert!(!controller.is_paused());
+
+        // 手动暂停不会因磁盘空间恢复而被自动恢复
+        controller.pause().await;
+        assert!(!controller.apply_low_disk_space(true).await);
+        controller.apply_low_disk_space(false).await;
+        assert!(controller.is_paused());
+    }
+}
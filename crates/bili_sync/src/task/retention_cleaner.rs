@@ -0,0 +1,223 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use bili_sync_entity::*;
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{QueryOrder, QuerySelect};
+use tracing::{debug, info, warn};
+
+use crate::utils::time_format::{now_naive, parse_time_string};
+
+/// 启动后首次清理前的等待时间，避开启动阶段的任务恢复
+const STARTUP_DELAY: Duration = Duration::from_secs(5 * 60);
+/// 两次清理之间的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 视频源的保留策略，0 或负数视为未设置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub days: Option<u32>,
+    pub max_count: Option<u32>,
+}
+
+impl RetentionPolicy {
+    pub fn from_columns(days: Option<i32>, max_count: Option<i32>) -> Self {
+        let positive = |value: Option<i32>| value.and_then(|v| u32::try_from(v).ok()).filter(|v| *v > 0);
+        Self {
+            days: positive(days),
+            max_count: positive(max_count),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_none() && self.max_count.is_none()
+    }
+}
+
+/// 触发删除的保留规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionReason {
+    /// 超过保留天数
+    Age(u32),
+    /// 超过最大保留数量
+    Count(u32),
+}
+
+impl std::fmt::Display for RetentionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionReason::Age(days) => write!(f, "超过保留天数 {} 天", days),
+            RetentionReason::Count(count) => write!(f, "超过最大保留数量 {} 个", count),
+        }
+    }
+}
+
+/// 从按入库时间倒序排列的视频中选出需要删除的视频，返回的列表按从旧到新排列
+pub fn select_expired_videos(
+    videos: &[(i32, String)],
+    policy: RetentionPolicy,
+    now: NaiveDateTime,
+) -> Vec<(i32, RetentionReason)> {
+    let cutoff = policy.days.map(|days| now - chrono::Duration::days(days as i64));
+    let mut expired: Vec<(i32, RetentionReason)> = videos
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (id, created_at))| {
+            let too_old = cutoff
+                .zip(parse_time_string(created_at))
+                .is_some_and(|(cutoff, created_at)| created_at < cutoff);
+            if too_old {
+                return policy.days.map(|days| (*id, RetentionReason::Age(days)));
+            }
+            match policy.max_count {
+                Some(max_count) if index >= max_count as usize => Some((*id, RetentionReason::Count(max_count))),
+                _ => None,
+            }
+        })
+        .collect();
+    expired.reverse();
+    expired
+}
+
+/// 启动定期按保留策略清理旧视频的任务
+pub async fn retention_cleaner(connection: Arc<DatabaseConnection>) {
+    tokio::time::sleep(STARTUP_DELAY).await;
+    loop {
+        if crate::task::is_scanning() || crate::task::TASK_CONTROLLER.is_paused() {
+            debug!("正在扫描或任务已暂停，跳过本轮保留策略清理");
+        } else if let Err(e) = apply_retention_policies(&connection).await {
+            warn!("执行保留策略清理失败: {:#}", e);
+        }
+        if let Err(e) = crate::utils::trash::cleanup_expired_trash(&connection).await {
+            warn!("清理回收站失败: {:#}", e);
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+/// 对所有设置了保留策略的视频源执行一次清理，返回删除的视频数量
+pub async fn apply_retention_policies(connection: &Arc<DatabaseConnection>) -> Result<usize> {
+    let delete_local_files = crate::config::reload_config().retention_delete_local_files;
+    let mut deleted = 0;
+    for (source_name, filter, policy) in load_retention_sources(connection).await? {
+        let videos: Vec<(i32, String)> = video::Entity::find()
+            .select_only()
+            .columns([video::Column::Id, video::Column::CreatedAt])
+            .filter(filter.and(video::Column::Deleted.eq(0)))
+            .order_by_desc(video::Column::CreatedAt)
+            .order_by_desc(video::Column::Id)
+            .into_tuple()
+            .all(connection.as_ref())
+            .await?;
+        for (video_id, reason) in select_expired_videos(&videos, policy, now_naive()) {
+            match crate::api::handler::delete_video_internal(connection.clone(), video_id, delete_local_files).await {
+                Ok(()) => {
+                    deleted += 1;
+                    info!("{} 的视频 (ID: {}) {}，已按保留策略删除", source_name, video_id, reason);
+                }
+                Err(e) => warn!("{} 的视频 (ID: {}) 按保留策略删除失败: {:?}", source_name, video_id, e),
+            }
+        }
+    }
+    if deleted > 0 {
+        info!("保留策略清理完成，共删除 {} 个视频", deleted);
+    }
+    Ok(deleted)
+}
+
+/// 读取所有设置了保留策略的视频源，返回 (展示名称, 视频过滤条件, 保留策略)
+async fn load_retention_sources(connection: &DatabaseConnection) -> Result<Vec<(String, SimpleExpr, RetentionPolicy)>> {
+    let mut sources = Vec::new();
+    for source in collection::Entity::find().all(connection).await? {
+        let policy = RetentionPolicy::from_columns(source.retention_days, source.retention_max_count);
+        if !policy.is_empty() {
+            sources.push((
+                format!("合集「{}」", source.name),
+                video::Column::CollectionId.eq(source.id),
+                policy,
+            ));
+        }
+    }
+    for source in favorite::Entity::find().all(connection).await? {
+        let policy = RetentionPolicy::from_columns(source.retention_days, source.retention_max_count);
+        if !policy.is_empty() {
+            sources.push((
+                format!("收藏夹「{}」", source.name),
+                video::Column::FavoriteId.eq(source.id),
+                policy,
+            ));
+        }
+    }
+    for source in submission::Entity::find().all(connection).await? {
+        let policy = RetentionPolicy::from_columns(source.retention_days, source.retention_max_count);
+        if !policy.is_empty() {
+            sources.push((
+                format!("UP主投稿「{}」", source.upper_name),
+                video::Column::SubmissionId.eq(source.id),
+                policy,
+            ));
+        }
+    }
+    for source in watch_later::Entity::find().all(connection).await? {
+        let policy = RetentionPolicy::from_columns(source.retention_days, source.retention_max_count);
+        if !policy.is_empty() {
+            sources.push((
+                "稍后再看".to_string(),
+                video::Column::WatchLaterId.eq(source.id),
+                policy,
+            ));
+        }
+    }
+    for source in video_source::Entity::find().all(connection).await? {
+        let policy = RetentionPolicy::from_columns(source.retention_days, source.retention_max_count);
+        if !policy.is_empty() {
+            sources.push((
+                format!("番剧「{}」", source.name),
+                video::Column::SourceId
+                    .eq(source.id)
+                    .and(video::Column::SourceType.eq(1)),
+                policy,
+            ));
+        }
+    }
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn videos() -> Vec<(i32, String)> {
+        vec![
+            (4, "2026-10-16 12:00:00".to_string()),
+            (3, "2026-10-10 12:00:00".to_string()),
+            (2, "2026-10-01 12:00:00".to_string()),
+            (1, "2026-09-01 12:00:00".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_select_expired_videos() {
+        let now = parse_time_string("2026-10-17 00:00:00").unwrap();
+
+        let policy = RetentionPolicy::from_columns(None, Some(2));
+        assert_eq!(
+            select_expired_videos(&videos(), policy, now),
+            vec![(1, RetentionReason::Count(2)), (2, RetentionReason::Count(2))]
+        );
+
+        let policy = RetentionPolicy::from_columns(Some(10), Some(3));
+        assert_eq!(
+            select_expired_videos(&videos(), policy, now),
+            vec![(1, RetentionReason::Age(10)), (2, RetentionReason::Age(10))]
+        );
+
+        // 0 视为未设置，不能删除全部视频
+        let policy = RetentionPolicy::from_columns(Some(0), Some(0));
+        assert!(policy.is_empty());
+        assert!(select_expired_videos(&videos(), policy, now).is_empty());
+    }
+}
@@ -1,8 +1,15 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use sysinfo::Disks;
 use tracing::{debug, error, info, warn};
 
 use crate::adapter::{Args, VideoSource};
@@ -12,15 +19,121 @@ use crate::initialization;
 use crate::task::TASK_CONTROLLER;
 use crate::unified_downloader::UnifiedDownloader;
 use crate::utils::file_logger;
-use crate::utils::notification::{DownloadFailureNotification, RiskControlNotification};
+use crate::utils::notification::{DownloadFailureNotification, LowDiskSpaceNotification, RiskControlNotification};
 use crate::utils::scan_collector::ScanCollector;
 use crate::utils::scan_id_tracker::{
     get_last_scanned_ids, group_sources_by_new_old, update_last_scanned_ids, LastScannedIds, MaxIdRecorder, SourceType,
     VideoSourceWithId,
 };
+use crate::utils::task_notifier::SCAN_PROGRESS_NOTIFIER;
 use crate::workflow::process_video_source;
 use bili_sync_entity::entities;
 
+/// 判断视频源是否到期时允许的误差（秒），避免因等待阶段的检查粒度错过本轮
+const SCAN_INTERVAL_TOLERANCE: u64 = 5;
+
+/// 各视频源上次开始扫描的时间，用于按源的扫描间隔跳过未到期的源
+static LAST_SOURCE_SCAN: Lazy<Mutex<HashMap<(SourceType, i32), Instant>>> = Lazy::new(Default::default);
+
+/// 数据库中的扫描间隔转换为秒数，0 或负数视为未设置
+fn source_scan_interval(value: Option<i64>) -> Option<u64> {
+    value
+        .and_then(|interval| u64::try_from(interval).ok())
+        .filter(|interval| *interval > 0)
+}
+
+/// 判断视频源是否已到扫描时间，未设置扫描间隔的源使用全局间隔
+fn is_source_due(source: &VideoSourceWithId, global_interval: u64, now: Instant) -> bool {
+    let interval = source.scan_interval.unwrap_or(global_interval);
+    match LAST_SOURCE_SCAN.lock().get(&(source.source_type, source.id)) {
+        Some(last_scan) => now.duration_since(*last_scan).as_secs() + SCAN_INTERVAL_TOLERANCE >= interval,
+        None => true,
+    }
+}
+
+/// 暂停期间检查静默时段是否结束、磁盘空间是否恢复的间隔（秒）
+const QUIET_HOURS_CHECK_INTERVAL: u64 = 30;
+
+/// 按当前时间和配置同步静默时段的暂停状态
+async fn sync_quiet_hours() {
+    let quiet_hours = crate::config::with_config(|bundle| bundle.config.quiet_hours.clone());
+    let in_quiet_hours = quiet_hours.is_active_at(chrono::Local::now().naive_local());
+    TASK_CONTROLLER.apply_quiet_hours(in_quiet_hours).await;
+}
+
+/// 触发磁盘空间不足暂停的目录，暂停期间据此检查空间是否恢复
+static LOW_DISK_PATH: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(Default::default);
+
+/// 配置的最低剩余磁盘空间（字节），未启用时返回 None
+fn min_free_disk_bytes() -> Option<u64> {
+    let min_free_disk_gb = crate::config::with_config(|bundle| bundle.config.min_free_disk_gb);
+    (min_free_disk_gb > 0).then(|| min_free_disk_gb.saturating_mul(1024 * 1024 * 1024))
+}
+
+/// 获取路径所在磁盘的可用空间，无法确定所在磁盘时返回 None
+fn available_disk_space(path: &Path) -> Option<u64> {
+    // 目录可能尚未创建，取最近的已存在的上级目录
+    let path = path.ancestors().find(|dir| dir.exists())?.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// 检查下载目录及暂存目录所在磁盘的剩余空间，不足时暂停任务并推送提醒，返回空间是否充足
+pub async fn ensure_disk_space(path: &Path) -> bool {
+    let Some(min_free) = min_free_disk_bytes() else {
+        return true;
+    };
+    let temp_dir = crate::config::with_config(|bundle| bundle.config.temp_dir.clone());
+    for dir in std::iter::once(path.to_path_buf()).chain(temp_dir) {
+        let Some(available) = available_disk_space(&dir) else {
+            continue;
+        };
+        if available >= min_free {
+            continue;
+        }
+        *LOW_DISK_PATH.lock() = Some(dir.clone());
+        if TASK_CONTROLLER.apply_low_disk_space(true).await {
+            warn!(
+                "目录 {} 所在磁盘剩余空间不足（剩余 {} MB，最低要求 {} GB），已自动暂停下载任务",
+                dir.display(),
+                available / 1024 / 1024,
+                min_free / 1024 / 1024 / 1024
+            );
+            if let Err(e) = crate::utils::notification::send_low_disk_space_notification(LowDiskSpaceNotification {
+                path: dir.display().to_string(),
+                available,
+                threshold: min_free,
+            })
+            .await
+            {
+                warn!("发送磁盘空间不足通知失败: {}", e);
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// 磁盘空间不足暂停期间检查空间是否已释放，恢复后自动恢复任务
+async fn sync_disk_space() {
+    if !TASK_CONTROLLER.paused_by_low_disk.load(Ordering::SeqCst) {
+        return;
+    }
+    let low_disk_path = LOW_DISK_PATH.lock().clone();
+    let recovered = match (min_free_disk_bytes(), low_disk_path) {
+        (Some(min_free), Some(path)) => available_disk_space(&path).is_none_or(|available| available >= min_free),
+        // 阈值已被关闭
+        _ => true,
+    };
+    if recovered {
+        TASK_CONTROLLER.apply_low_disk_space(false).await;
+    }
+}
+
 /// 从数据库加载所有视频源的函数
 async fn load_video_sources_from_db(
     connection: &Arc<DatabaseConnection>,
@@ -35,10 +148,10 @@ async fn load_video_sources_from_db(
 
     for collection in collections {
         // 创建拥有的CollectionItem来匹配现有的Args结构
-        let collection_type = if collection.r#type == 1 {
-            CollectionType::Series
-        } else {
-            CollectionType::Season
+        let collection_type = match collection.r#type {
+            1 => CollectionType::Series,
+            3 => CollectionType::Manual,
+            _ => CollectionType::Season,
         };
 
         let collection_item = CollectionItem {
@@ -52,6 +165,7 @@ async fn load_video_sources_from_db(
             args: Args::Collection { collection_item },
             path: PathBuf::from(collection.path),
             source_type: SourceType::Collection,
+            scan_interval: source_scan_interval(collection.scan_interval),
         });
     }
 
@@ -68,6 +182,7 @@ async fn load_video_sources_from_db(
             args: Args::Favorite { fid },
             path: PathBuf::from(favorite.path),
             source_type: SourceType::Favorite,
+            scan_interval: source_scan_interval(favorite.scan_interval),
         });
     }
 
@@ -84,6 +199,7 @@ async fn load_video_sources_from_db(
             args: Args::Submission { upper_id },
             path: PathBuf::from(submission.path),
             source_type: SourceType::Submission,
+            scan_interval: source_scan_interval(submission.scan_interval),
         });
     }
 
@@ -99,6 +215,7 @@ async fn load_video_sources_from_db(
             args: Args::WatchLater,
             path: PathBuf::from(watch_later.path),
             source_type: SourceType::WatchLater,
+            scan_interval: source_scan_interval(watch_later.scan_interval),
         });
     }
 
@@ -119,6 +236,7 @@ async fn load_video_sources_from_db(
             },
             path: PathBuf::from(bangumi.path),
             source_type: SourceType::Bangumi,
+            scan_interval: source_scan_interval(bangumi.scan_interval),
         });
     }
 
@@ -174,6 +292,96 @@ async fn init_all_sources(
     Ok(())
 }
 
+/// 单个视频源的扫描结果：扫描前获取的视频源信息、扫描开始时间与耗时、扫描结果
+type SourceScanOutcome = (
+    Option<crate::adapter::VideoSourceEnum>,
+    (String, std::time::Duration),
+    Result<(
+        usize,
+        Vec<crate::utils::notification::NewVideoInfo>,
+        usize,
+        usize,
+        usize,
+    )>,
+);
+
+/// 扫描单个视频源，任务暂停时返回 None
+async fn scan_source(
+    index: usize,
+    source: &VideoSourceWithId,
+    bili_client: &BiliClient,
+    connection: &DatabaseConnection,
+    downloader: &UnifiedDownloader,
+    config: &Config,
+) -> Option<SourceScanOutcome> {
+    let args = &source.args;
+    let path = &source.path;
+
+    // 在处理每个视频源前检查是否暂停
+    if TASK_CONTROLLER.is_paused() {
+        return None;
+    }
+
+    LAST_SOURCE_SCAN
+        .lock()
+        .insert((source.source_type, source.id), Instant::now());
+    TASK_CONTROLLER.start_source_scan(source.source_type, source.id);
+
+    // 获取该视频源的取消令牌，全局暂停或单独暂停该视频源时取消下载任务
+    let cancellation_token = TASK_CONTROLLER
+        .get_source_cancellation_token(source.source_type, source.id)
+        .await;
+
+    // 视频源间延迟处理（第一个源不延迟），并行扫描时每个源各自延迟
+    if index > 0 {
+        let delay_seconds = match args {
+            crate::adapter::Args::Submission { .. } => {
+                // UP主投稿使用特殊延迟
+                config.submission_risk_control.submission_source_delay_seconds
+            }
+            _ => {
+                // 其他源使用通用延迟
+                config.submission_risk_control.source_delay_seconds
+            }
+        };
+
+        if delay_seconds > 0 {
+            let source_type = match args {
+                crate::adapter::Args::Submission { .. } => "UP主投稿",
+                crate::adapter::Args::Favorite { .. } => "收藏夹",
+                crate::adapter::Args::Collection { .. } => "合集",
+                crate::adapter::Args::WatchLater => "稍后再看",
+                crate::adapter::Args::Bangumi { .. } => "番剧",
+            };
+
+            info!("处理下一个{}前延迟 {} 秒，避免触发风控...", source_type, delay_seconds);
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {},
+                _ = tokio::time::sleep(tokio::time::Duration::from_secs(delay_seconds)) => {},
+            }
+        }
+    }
+
+    // 在处理视频源前获取视频源信息，用于记录到收集器
+    let video_source = crate::adapter::video_source_from(args, path, bili_client, connection, None)
+        .await
+        .ok()
+        .map(|(video_source, _)| video_source);
+    if let Some(video_source) = &video_source {
+        SCAN_PROGRESS_NOTIFIER.start_source(format!(
+            "{} {}",
+            video_source.source_type_display(),
+            video_source.source_name_display()
+        ));
+    }
+
+    let scan_started_at = crate::utils::time_format::now_standard_string();
+    let scan_start = Instant::now();
+    let result = process_video_source(args, bili_client, path, connection, downloader, cancellation_token).await;
+    let scan_timing = (scan_started_at, scan_start.elapsed());
+    Some((video_source, scan_timing, result))
+}
+
 /// 启动周期下载视频的任务
 pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
     let bili_client = BiliClient::new(String::new());
@@ -214,14 +422,33 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
         }
     }
 
+    // 首轮及手动触发时扫描所有视频源，不受各源扫描间隔限制
+    let mut force_full_scan = true;
+    // 因按需扫描请求提前开始的一轮只扫描被请求的视频源
+    let mut queued_scan_only = false;
+
     loop {
         // ========== 扫描任务阶段 ==========
         // 注意：在此阶段不应该中断任务，即使配置更新了也要等待当前扫描完成
 
+        // 根据静默时段自动暂停/恢复
+        sync_quiet_hours().await;
+
         // 检查是否需要暂停扫描任务
         if TASK_CONTROLLER.is_paused() {
             debug!("定时扫描任务已暂停，等待恢复...");
-            TASK_CONTROLLER.wait_if_paused().await;
+            // 恢复后扫描所有视频源，不受各源扫描间隔限制
+            force_full_scan = true;
+            // 定期检查静默时段是否结束、磁盘空间是否恢复，以便自动恢复
+            while TASK_CONTROLLER.is_paused() {
+                let _ = tokio::time::timeout(
+                    tokio::time::Duration::from_secs(QUIET_HOURS_CHECK_INTERVAL),
+                    TASK_CONTROLLER.wait_if_paused(),
+                )
+                .await;
+                sync_quiet_hours().await;
+                sync_disk_space().await;
+            }
             info!("定时扫描任务已恢复");
         }
 
@@ -246,6 +473,65 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             }
         };
 
+        // 下一轮等待时间取全局间隔与各源扫描间隔中的最小值
+        let wait_interval = video_sources
+            .iter()
+            .filter_map(|source| source.scan_interval)
+            .fold(config.interval, u64::min);
+
+        // 跳过单独暂停的视频源
+        let paused_sources = TASK_CONTROLLER.paused_sources();
+        let video_sources = if paused_sources.is_empty() {
+            video_sources
+        } else {
+            let sources_count = video_sources.len();
+            let video_sources = video_sources
+                .into_iter()
+                .filter(|source| !paused_sources.contains(&(source.source_type, source.id)))
+                .collect::<Vec<_>>();
+            if video_sources.len() < sources_count {
+                info!("{} 个视频源已暂停，本轮跳过", sources_count - video_sources.len());
+            }
+            video_sources
+        };
+
+        // 取消已被删除、禁用或暂停的视频源的按需扫描请求
+        TASK_CONTROLLER.cancel_missing_source_scans(
+            &video_sources
+                .iter()
+                .map(|source| (source.source_type, source.id))
+                .collect(),
+        );
+
+        // 跳过未到扫描间隔的视频源，按需扫描的视频源不受间隔限制
+        let enabled_sources_count = video_sources.len();
+        let full_scan = std::mem::take(&mut force_full_scan) | TASK_CONTROLLER.take_full_scan_request();
+        let queued_only = std::mem::take(&mut queued_scan_only);
+        let video_sources = if full_scan {
+            video_sources
+        } else {
+            let now = Instant::now();
+            let queued_scans = TASK_CONTROLLER.queued_source_scans();
+            video_sources
+                .into_iter()
+                .filter(|source| {
+                    queued_scans.contains(&(source.source_type, source.id))
+                        || (!queued_only && is_source_due(source, config.interval, now))
+                })
+                .collect::<Vec<_>>()
+        };
+        let skipped_sources_count = enabled_sources_count - video_sources.len();
+        if skipped_sources_count > 0 {
+            if queued_only {
+                info!(
+                    "本轮仅扫描按需请求的视频源，跳过其余 {} 个视频源",
+                    skipped_sources_count
+                );
+            } else {
+                info!("{} 个视频源未到扫描间隔，本轮跳过", skipped_sources_count);
+            }
+        }
+
         // 统计总的视频源数量（包括禁用的）
         let total_sources_count = match count_all_video_sources(&optimized_connection).await {
             Ok(count) => count,
@@ -255,7 +541,6 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             }
         };
 
-        let enabled_sources_count = video_sources.len();
         let disabled_sources_count = total_sources_count.saturating_sub(enabled_sources_count);
 
         if disabled_sources_count > 0 {
@@ -274,6 +559,11 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 break 'inner;
             }
 
+            if video_sources.is_empty() {
+                debug!("没有到期需要扫描的视频源，跳过本轮扫描");
+                break 'inner;
+            }
+
             // 在开始扫描前再次检查是否暂停
             if TASK_CONTROLLER.is_paused() {
                 debug!("扫描开始前检测到暂停信号，跳过本轮扫描");
@@ -284,6 +574,17 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             TASK_CONTROLLER.set_scanning(true);
             TASK_CONTROLLER.reset_cancellation_token().await;
 
+            // 外部请求重新生成元数据时，先将已完成的 NFO 子任务重置，本轮扫描中重新生成
+            if TASK_CONTROLLER.take_force_metadata_request() {
+                match crate::utils::model::reset_nfo_subtasks(&optimized_connection).await {
+                    Ok((videos, pages)) => info!(
+                        "已重置 {} 个视频、{} 个分页的 NFO 子任务，将在本轮扫描中重新生成",
+                        videos, pages
+                    ),
+                    Err(e) => error!("重置 NFO 子任务失败: {:#}", e),
+                }
+            }
+
             // 标记任务状态为运行中
             crate::utils::task_notifier::TASK_STATUS_NOTIFIER.set_running();
 
@@ -397,24 +698,41 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
 
             // 初始化扫描收集器来统计本轮扫描结果
             let mut scan_collector = ScanCollector::new();
+            SCAN_PROGRESS_NOTIFIER.start_scan(ordered_sources.len());
 
             // 初始化ID记录器
             let mut max_id_recorder = MaxIdRecorder::new();
 
             let mut processed_sources = 0;
             let mut sources_with_new_content = 0;
-            let mut is_first_source = true;
             let mut last_successful_source: Option<&VideoSourceWithId> = None; // 记录上一个成功处理的源
             let mut is_interrupted = false; // 标记是否因风控等原因中断
 
             // 定期同步相关变量
             let mut _videos_since_last_sync = 0; // 自上次同步以来处理的视频数（保留以备将来使用）
 
-            for source in &ordered_sources {
-                let args = &source.args;
-                let path = &source.path;
+            // 同时扫描的视频源数量，为 1 时逐个扫描。结果按源的顺序处理，保证扫描进度按顺序保存
+            let source_scan_concurrency = config.source_scan_concurrency.max(1);
+            if source_scan_concurrency > 1 {
+                info!("将同时扫描最多 {} 个视频源", source_scan_concurrency);
+            }
+            let (client_ref, connection_ref, downloader_ref, config_ref) =
+                (&bili_client, &optimized_connection, &downloader_arc, &config);
+            // 按下标迭代，流中的元素不携带引用，避免借用视频源的 future 在跨任务时无法满足 Send
+            let sources_ref = &ordered_sources;
+            let mut source_scans = futures::stream::iter(0..ordered_sources.len())
+                .map(move |index| {
+                    let source = &sources_ref[index];
+                    async move {
+                        let outcome =
+                            scan_source(index, source, client_ref, connection_ref, downloader_ref, config_ref).await;
+                        (source, outcome)
+                    }
+                })
+                .buffered(source_scan_concurrency);
 
-                // 在开始扫描当前源之前，保存上一个成功处理的源ID
+            while let Some((source, outcome)) = source_scans.next().await {
+                // 在处理当前源的结果之前，保存上一个成功处理的源ID
                 if let Some(prev_source) = last_successful_source {
                     max_id_recorder.record(prev_source.source_type, prev_source.id);
                     max_id_recorder.merge_into(&mut last_scanned_ids);
@@ -429,69 +747,55 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                     }
                 }
 
-                // 在处理每个视频源前检查是否暂停
-                if TASK_CONTROLLER.is_paused() {
+                let Some((video_source, (scan_started_at, scan_duration), result)) = outcome else {
                     debug!("在处理视频源时检测到暂停信号，停止当前轮次扫描");
                     // 重要：暂停时必须重置扫描状态
                     TASK_CONTROLLER.set_scanning(false);
                     crate::utils::task_notifier::TASK_STATUS_NOTIFIER.set_finished();
                     is_interrupted = true;
                     break;
+                };
+
+                // 扫描期间被单独暂停的视频源，其下载已被取消，不记录结果，恢复后重新处理
+                if TASK_CONTROLLER.is_source_paused(source.source_type, source.id) {
+                    SCAN_PROGRESS_NOTIFIER.finish_source();
+                    TASK_CONTROLLER.finish_source_scan(
+                        source.source_type,
+                        source.id,
+                        Err("视频源已暂停，扫描已取消".to_string()),
+                    );
+                    info!("视频源 (ID: {}) 已暂停，已取消其正在进行的下载", source.id);
+                    continue;
                 }
 
-                // 视频源间延迟处理（第一个源不延迟）
-                if !is_first_source {
-                    let delay_seconds = match args {
-                        crate::adapter::Args::Submission { .. } => {
-                            // UP主投稿使用特殊延迟
-                            config.submission_risk_control.submission_source_delay_seconds
-                        }
-                        _ => {
-                            // 其他源使用通用延迟
-                            config.submission_risk_control.source_delay_seconds
-                        }
-                    };
-
-                    if delay_seconds > 0 {
-                        let source_type = match args {
-                            crate::adapter::Args::Submission { .. } => "UP主投稿",
-                            crate::adapter::Args::Favorite { .. } => "收藏夹",
-                            crate::adapter::Args::Collection { .. } => "合集",
-                            crate::adapter::Args::WatchLater => "稍后再看",
-                            crate::adapter::Args::Bangumi { .. } => "番剧",
-                        };
-
-                        info!("处理下一个{}前延迟 {} 秒，避免触发风控...", source_type, delay_seconds);
-                        tokio::time::sleep(tokio::time::Duration::from_secs(delay_seconds)).await;
-                    }
-                }
-                is_first_source = false;
-
                 // 记录源ID
                 max_id_recorder.record(source.source_type, source.id);
 
-                // 获取全局取消令牌，用于下载任务控制
-                let cancellation_token = TASK_CONTROLLER.get_cancellation_token().await;
-
-                // 在处理视频源前记录到收集器
-                if let Ok((video_source, _)) =
-                    crate::adapter::video_source_from(args, path, &bili_client, &optimized_connection, None).await
-                {
-                    scan_collector.start_source(&video_source);
+                // 记录到收集器
+                if let Some(video_source) = &video_source {
+                    scan_collector.start_source(video_source);
+                    scan_collector.finish_source(
+                        video_source,
+                        scan_duration,
+                        result.as_ref().err().map(|e| format!("{:#}", e)),
+                    );
                 }
 
-                match process_video_source(
-                    args,
-                    &bili_client,
-                    path,
-                    &optimized_connection,
-                    &downloader_arc,
-                    cancellation_token,
-                )
-                .await
-                {
-                    Ok((new_video_count, new_videos)) => {
+                match result {
+                    Ok((
+                        new_video_count,
+                        new_videos,
+                        filtered_video_count,
+                        skipped_video_count,
+                        no_permission_video_count,
+                    )) => {
                         processed_sources += 1;
+                        SCAN_PROGRESS_NOTIFIER.finish_source();
+                        TASK_CONTROLLER.finish_source_scan(
+                            source.source_type,
+                            source.id,
+                            Ok(format!("扫描完成，新增 {} 个视频", new_video_count)),
+                        );
 
                         // 成功处理后，记录为上一个成功的源（不立即保存，等下次循环再保存）
                         last_successful_source = Some(source);
@@ -507,6 +811,19 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             sources_with_new_content += 1;
                         }
 
+                        // 复用扫描前获取的视频源信息记录到收集器
+                        if let Some(video_source) = &video_source {
+                            if filtered_video_count > 0 {
+                                scan_collector.add_filtered_videos(video_source, filtered_video_count);
+                            }
+                            if skipped_video_count > 0 {
+                                scan_collector.add_skipped_videos(video_source, skipped_video_count);
+                            }
+                            if no_permission_video_count > 0 {
+                                scan_collector.add_no_permission_videos(video_source, no_permission_video_count);
+                            }
+                        }
+
                         // 检查是否有新视频信息需要添加到收集器（修复：同时检查数量和向量）
                         if !new_videos.is_empty() {
                             // 获取待删除的视频ID列表，过滤掉充电专享视频
@@ -538,20 +855,12 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             }
 
                             if !filtered_videos.is_empty() {
-                                if let Ok((video_source, _)) = crate::adapter::video_source_from(
-                                    args,
-                                    path,
-                                    &bili_client,
-                                    &optimized_connection,
-                                    None,
-                                )
-                                .await
-                                {
+                                if let Some(video_source) = &video_source {
                                     debug!(
                                         "向scan_collector添加 {} 个新视频信息（已过滤充电视频）",
                                         filtered_videos.len()
                                     );
-                                    scan_collector.add_new_videos(&video_source, filtered_videos);
+                                    scan_collector.add_new_videos(video_source, filtered_videos);
                                 } else {
                                     warn!("无法获取视频源信息，跳过添加新视频到收集器");
                                 }
@@ -567,9 +876,64 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             _videos_since_last_sync += new_video_count as u32;
                         }
 
+                        if let Err(e) = crate::utils::scan_history::record_source_scan(
+                            &optimized_connection,
+                            source.source_type,
+                            source.id,
+                            scan_started_at,
+                            scan_duration,
+                            video_source.as_ref().and_then(|vs| scan_collector.source_result(vs)),
+                            None,
+                        )
+                        .await
+                        {
+                            warn!("记录扫描历史失败 (源ID: {}): {}", source.id, e);
+                        }
+                        if let Err(e) = crate::utils::scan_history::set_source_last_error(
+                            &optimized_connection,
+                            source.source_type,
+                            source.id,
+                            None,
+                        )
+                        .await
+                        {
+                            warn!("清除视频源错误信息失败 (源ID: {}): {}", source.id, e);
+                        }
+
                         // mmap自动处理数据持久化，不需要手动同步
                     }
                     Err(e) => {
+                        SCAN_PROGRESS_NOTIFIER.finish_source();
+                        if TASK_CONTROLLER.is_paused() {
+                            // 因暂停中断的按需扫描在恢复后重新处理
+                            TASK_CONTROLLER.requeue_running_source_scans();
+                        } else {
+                            TASK_CONTROLLER.finish_source_scan(source.source_type, source.id, Err(format!("{:#}", e)));
+                            if let Err(err) = crate::utils::scan_history::record_source_scan(
+                                &optimized_connection,
+                                source.source_type,
+                                source.id,
+                                scan_started_at,
+                                scan_duration,
+                                None,
+                                Some(format!("{:#}", e)),
+                            )
+                            .await
+                            {
+                                warn!("记录扫描历史失败 (源ID: {}): {}", source.id, err);
+                            }
+                            if let Err(err) = crate::utils::scan_history::set_source_last_error(
+                                &optimized_connection,
+                                source.source_type,
+                                source.id,
+                                Some(format!("{:#}", e)),
+                            )
+                            .await
+                            {
+                                warn!("记录视频源错误信息失败 (源ID: {}): {}", source.id, err);
+                            }
+                        }
+
                         // 检查是否为风控错误，如果是则停止所有后续扫描
                         let mut is_risk_control = false;
 
@@ -598,29 +962,21 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             }
                         }
 
-                        let (source_type_display, source_name_display) = match crate::adapter::video_source_from(
-                            args,
-                            path,
-                            &bili_client,
-                            &optimized_connection,
-                            None,
-                        )
-                        .await
-                        {
-                            Ok((video_source, _)) => {
+                        let (source_type_display, source_name_display) = match &video_source {
+                            Some(video_source) => {
                                 (video_source.source_type_display(), video_source.source_name_display())
                             }
-                            Err(fetch_err) => {
-                                warn!(
-                                    "获取视频源信息失败，无法提供完整通知上下文 (源ID: {}): {}",
-                                    source.id, fetch_err
-                                );
+                            None => {
+                                warn!("获取视频源信息失败，无法提供完整通知上下文 (源ID: {})", source.id);
                                 (format!("{:?}", source.source_type), format!("ID {}", source.id))
                             }
                         };
 
                         if is_risk_control {
                             error!("检测到风控，停止所有后续视频源的扫描");
+
+                            // 轮换硬件指纹，避免后续请求继续使用已被风控的指纹
+                            crate::hardware::HardwareFingerprint::rotate_on_risk_control(&optimized_connection).await;
                             info!("触发风控的源(ID: {})未完成处理，下次扫描将重新处理该源", source.id);
 
                             if let Err(err) =
@@ -656,8 +1012,15 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 }
             }
 
+            // 中断时停止仍在并行扫描的视频源，对应的按需扫描任务重新排队
+            drop(source_scans);
+            if is_interrupted {
+                TASK_CONTROLLER.requeue_running_source_scans();
+            }
+
             // 标记扫描结束
             TASK_CONTROLLER.set_scanning(false);
+            SCAN_PROGRESS_NOTIFIER.finish_scan();
 
             // 保存最后一个成功处理的源ID
             if let Some(final_source) = last_successful_source {
@@ -687,11 +1050,54 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             info!("本轮扫描完成 - 视频源数量: {}", ordered_sources.len());
 
             // 生成扫描摘要并发送推送通知
-            let scan_summary = scan_collector.generate_summary();
+            let mut scan_summary = scan_collector.generate_summary();
+            crate::utils::scan_collector::fill_total_size(&mut scan_summary, &optimized_connection).await;
+            if config.write_scan_report {
+                if let Err(e) = crate::utils::scan_report::write_scan_report(&scan_summary, &optimized_connection).await
+                {
+                    warn!("写入扫描报告失败: {:#}", e);
+                }
+            }
+            if scan_summary.total_filtered_videos > 0 {
+                info!(
+                    "本轮有 {} 个新视频因时长不在设置范围内被跳过",
+                    scan_summary.total_filtered_videos
+                );
+            }
+            if scan_summary.total_skipped_videos > 0 {
+                info!(
+                    "本轮有 {} 个视频因发布时间或标题不满足视频源的过滤条件被跳过",
+                    scan_summary.total_skipped_videos
+                );
+            }
+            if scan_summary.total_no_permission_videos > 0 {
+                info!(
+                    "本轮有 {} 个新视频为充电专享且当前账号无权观看，已标记为无权限",
+                    scan_summary.total_no_permission_videos
+                );
+            }
+            // 有新视频时通知 Jellyfin 刷新媒体库，每轮扫描只刷新一次
+            if scan_summary.total_new_videos > 0 {
+                crate::utils::jellyfin::refresh_library_if_configured().await;
+            }
             if let Err(e) = crate::utils::notification::send_scan_notification(scan_summary).await {
                 warn!("发送扫描完成推送失败: {}", e);
             }
 
+            // 检测子任务反复失败的视频并单独推送
+            if config.notification.enable_failure_notifications {
+                match crate::utils::scan_collector::detect_repeated_failures(&optimized_connection).await {
+                    Ok(failures) if !failures.is_empty() => {
+                        if let Err(e) = crate::utils::notification::send_repeated_failure_notification(&failures).await
+                        {
+                            warn!("发送反复失败推送失败: {}", e);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("检测反复失败的视频失败: {}", e),
+                }
+            }
+
             // 标记任务状态为结束
             crate::utils::task_notifier::TASK_STATUS_NOTIFIER.set_finished();
 
@@ -761,7 +1167,6 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
         // 安全时机：扫描任务已完成，可以安全地检测配置更新并决定是否立即开始下一轮
         // 智能等待：支持配置更新的间隔等待
         // 重要：只在扫描任务完成后才检测配置更新，确保不会中断正在进行的扫描
-        let wait_interval = config.interval;
         let check_frequency = 5; // 每5秒检查一次配置是否更新
         let mut remaining_time = wait_interval;
 
@@ -776,6 +1181,7 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 // 检查是否刚刚恢复，如果是则立即开始新扫描
                 if TASK_CONTROLLER.take_just_resumed() {
                     info!("任务恢复，立即开始新一轮扫描");
+                    force_full_scan = true;
                     break; // 跳出等待循环，立即开始新扫描
                 }
 
@@ -783,6 +1189,19 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 continue; // 暂停期间不计入等待时间
             }
 
+            // 有按需扫描请求时立即开始扫描
+            if TASK_CONTROLLER.has_queued_source_scans() {
+                info!("检测到按需扫描请求，立即开始扫描");
+                queued_scan_only = true;
+                break;
+            }
+
+            // 有全量扫描请求时立即开始扫描所有视频源
+            if TASK_CONTROLLER.has_full_scan_request() {
+                info!("检测到全量扫描请求，立即开始新一轮扫描");
+                break;
+            }
+
             let sleep_duration = remaining_time.min(check_frequency);
             tokio::time::sleep(tokio::time::Duration::from_secs(sleep_duration)).await;
             remaining_time = remaining_time.saturating_sub(sleep_duration);
@@ -790,15 +1209,16 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             // 检查是否刚刚恢复，如果是则立即开始新扫描
             if TASK_CONTROLLER.take_just_resumed() {
                 info!("检测到任务恢复信号，立即开始新一轮扫描");
+                force_full_scan = true;
                 break; // 跳出等待循环，立即开始新扫描
             }
 
             // 检查配置是否更新了（通过比较interval值）
             let current_config = crate::config::reload_config();
-            if current_config.interval != wait_interval {
+            if current_config.interval != config.interval {
                 info!(
                     "检测到扫描间隔时间配置更新：{} -> {} 秒，等待本轮结束后立即开始下一轮扫描",
-                    wait_interval, current_config.interval
+                    config.interval, current_config.interval
                 );
                 break; // 配置更新了，立即开始下一轮
             }
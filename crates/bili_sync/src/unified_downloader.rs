@@ -1,10 +1,212 @@
-use anyhow::Result;
-use std::path::Path;
-use tracing::{info, warn};
+use anyhow::{bail, Context, Result};
+use reqwest::StatusCode;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
 use crate::aria2_downloader::Aria2Downloader;
 use crate::bilibili::Client;
 use crate::downloader::Downloader;
+use crate::error::IntegrityError;
+
+/// 下载文件的预期信息，用于下载完成后的完整性校验
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedFile {
+    /// 预期的文件大小，未提供时使用下载时服务器报告的大小
+    pub size: Option<u64>,
+    /// B站提供的文件MD5（十六进制）
+    pub md5: Option<String>,
+}
+
+/// 校验已下载文件的大小与MD5，不一致时返回 IntegrityError
+async fn verify_file_integrity(path: &Path, size: Option<u64>, md5: Option<&str>) -> Result<()> {
+    let integrity_error = |reason: String| IntegrityError {
+        path: path.display().to_string(),
+        reason,
+    };
+    if let Some(expected) = size {
+        let actual = tokio::fs::metadata(path).await?.len();
+        if actual != expected {
+            return Err(integrity_error(format!("文件大小 {} 字节，预期 {} 字节", actual, expected)).into());
+        }
+    }
+    if let Some(expected) = md5 {
+        let actual = file_md5(path).await?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(integrity_error(format!("MD5 为 {}，预期 {}", actual, expected)).into());
+        }
+    }
+    Ok(())
+}
+
+/// 计算文件MD5时每次读取的大小
+const MD5_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 分块读取计算文件的MD5（十六进制），避免将整个视频文件读入内存
+async fn file_md5(path: &Path) -> Result<String> {
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut context = md5::Context::new();
+    let mut buf = vec![0u8; MD5_CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        context.consume(&buf[..read]);
+    }
+    Ok(hex::encode(context.compute().0))
+}
+
+/// 单次重试等待时间的上限
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// 重试等待时间的随机抖动比例，避免多个下载任务同时重试
+const RETRY_JITTER: f64 = 0.5;
+
+/// 计算第 attempt 次重试（从 1 开始）前的等待时间：基础时间按 2 的幂增长，再乘以 1 + jitter
+fn retry_delay(base_delay_ms: u64, attempt: u32, jitter: f64) -> Duration {
+    let exponential = base_delay_ms.saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+    let delay = Duration::from_millis(exponential).mul_f64(1.0 + jitter.clamp(0.0, 1.0));
+    delay.min(MAX_RETRY_DELAY)
+}
+
+/// 服务器 5xx、408 与 429 视为暂时性错误
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::REQUEST_TIMEOUT || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// 判断下载错误是否值得重试：网络中断、超时、服务器 5xx 与完整性校验失败可重试，403/404 等客户端错误及本地文件错误不重试
+fn is_retryable_download_error(err: &anyhow::Error) -> bool {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return match e.status() {
+                Some(status) => is_retryable_status(status),
+                None => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() || e.is_decode(),
+            };
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            return matches!(
+                e.kind(),
+                ErrorKind::TimedOut
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::UnexpectedEof
+                    | ErrorKind::Interrupted
+            );
+        }
+        if cause.downcast_ref::<IntegrityError>().is_some() {
+            return true;
+        }
+    }
+    false
+}
+
+/// 获取目标文件在下载暂存目录中的路径，未配置暂存目录或目标已位于暂存目录中时返回目标路径本身
+pub fn staging_path(dest: &Path) -> PathBuf {
+    let Some(temp_dir) = crate::config::with_config(|bundle| bundle.config.temp_dir.clone()) else {
+        return dest.to_path_buf();
+    };
+    staging_path_in(&temp_dir, dest)
+}
+
+/// 目标文件是否留有中断后可以续传的临时文件（原生下载器的 .part 或 aria2 的控制文件）
+pub async fn has_resumable_download(dest: &Path) -> bool {
+    let staged = staging_path(dest);
+    let mut aria2_control = staged.clone().into_os_string();
+    aria2_control.push(".aria2");
+    crate::downloader::has_resumable_part(&staged).await
+        || tokio::fs::try_exists(PathBuf::from(aria2_control))
+            .await
+            .unwrap_or(false)
+}
+
+/// 丢弃目标文件中断后留下的续传进度，包括原生下载器的 .part 文件与 aria2 的临时文件
+pub async fn discard_resumable_download(dest: &Path) {
+    let staged = staging_path(dest);
+    crate::downloader::remove_resumable_part(&staged).await;
+    let mut aria2_control = staged.clone().into_os_string();
+    aria2_control.push(".aria2");
+    if tokio::fs::try_exists(&aria2_control).await.unwrap_or(false) {
+        let _ = tokio::fs::remove_file(PathBuf::from(aria2_control)).await;
+        let _ = tokio::fs::remove_file(&staged).await;
+    }
+}
+
+fn staging_path_in(temp_dir: &Path, dest: &Path) -> PathBuf {
+    if dest.starts_with(temp_dir) {
+        return dest.to_path_buf();
+    }
+    // 使用目标路径的哈希命名，避免不同视频的同名文件冲突，同时规避过长的文件名
+    let hash = hex::encode(md5::compute(dest.to_string_lossy().as_bytes()).0);
+    match dest.extension() {
+        Some(ext) => temp_dir.join(format!("{}.{}", hash, ext.to_string_lossy())),
+        None => temp_dir.join(hash),
+    }
+}
+
+/// 将暂存目录中已完成的文件移动到目标位置，跨文件系统时先复制到目标目录下的临时文件再重命名，保证目标文件总是完整的
+pub async fn move_to_destination(staged: &Path, dest: &Path) -> Result<()> {
+    if staged == dest {
+        return Ok(());
+    }
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    match tokio::fs::rename(staged, dest).await {
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            let mut partial = dest.as_os_str().to_owned();
+            partial.push(".part");
+            let partial = PathBuf::from(partial);
+            if let Err(e) = tokio::fs::copy(staged, &partial).await {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(e).with_context(|| format!("复制 {} 到 {} 失败", staged.display(), partial.display()));
+            }
+            tokio::fs::rename(&partial, dest)
+                .await
+                .with_context(|| format!("重命名 {} 失败", partial.display()))?;
+            tokio::fs::remove_file(staged).await?;
+            Ok(())
+        }
+        res => res.with_context(|| format!("移动 {} 到 {} 失败", staged.display(), dest.display())),
+    }
+}
+
+/// 仅音频模式下写入音频文件的标签
+#[derive(Debug, Clone, Default)]
+pub struct AudioTags {
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+}
+
+/// 构建提取音轨的 FFmpeg 参数，音轨直接复制不重新编码，封面以 attached_pic 形式嵌入
+fn extract_audio_args(
+    input_path: &Path,
+    cover_path: Option<&Path>,
+    tags: &AudioTags,
+    output_path: &Path,
+) -> Vec<String> {
+    let mut args = vec!["-i".to_string(), input_path.to_string_lossy().to_string()];
+    if let Some(cover_path) = cover_path {
+        args.extend(["-i".to_string(), cover_path.to_string_lossy().to_string()]);
+    }
+    args.extend(["-map", "0:a:0", "-c:a", "copy"].map(String::from));
+    if cover_path.is_some() {
+        args.extend(["-map", "1:v:0", "-c:v", "mjpeg", "-disposition:v:0", "attached_pic"].map(String::from));
+    }
+    args.extend(["-metadata".to_string(), format!("title={}", tags.title)]);
+    args.extend(["-metadata".to_string(), format!("artist={}", tags.artist)]);
+    if let Some(album) = &tags.album {
+        args.extend(["-metadata".to_string(), format!("album={}", album)]);
+    }
+    args.extend(["-strict", "unofficial", "-y"].map(String::from));
+    args.push(output_path.to_string_lossy().to_string());
+    args
+}
 
 /// 统一下载器，可以在原生下载器和aria2下载器之间切换
 pub enum UnifiedDownloader {
@@ -52,9 +254,69 @@ impl UnifiedDownloader {
 
     /// 下载文件，支持多个URL备选
     pub async fn fetch_with_fallback(&self, urls: &[&str], path: &Path) -> Result<()> {
-        match self {
-            Self::Native(downloader) => downloader.fetch_with_fallback(urls, path).await,
-            Self::Aria2(downloader) => downloader.fetch_with_aria2_fallback(urls, path).await,
+        self.fetch_verified(urls, path, &ExpectedFile::default()).await
+    }
+
+    /// 下载文件并校验完整性，校验失败时删除文件并返回 IntegrityError
+    /// 配置了暂存目录时先下载到暂存目录，校验通过后再移动到目标位置
+    pub async fn fetch_verified(&self, urls: &[&str], path: &Path, expected: &ExpectedFile) -> Result<()> {
+        let staged = staging_path(path);
+        if let Some(parent) = staged.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let reported_size = match self {
+            Self::Native(downloader) => downloader.fetch_with_fallback(urls, &staged).await?,
+            Self::Aria2(downloader) => downloader.fetch_with_aria2_fallback(urls, &staged).await?,
+        };
+        if crate::config::with_config(|bundle| bundle.config.verify_download_integrity) {
+            let size = expected.size.or(reported_size);
+            if let Err(e) = verify_file_integrity(&staged, size, expected.md5.as_deref()).await {
+                if let Err(remove_err) = tokio::fs::remove_file(&staged).await {
+                    warn!("删除校验失败的文件出错: {} - {:#}", staged.display(), remove_err);
+                }
+                return Err(e);
+            }
+            debug!("文件完整性校验通过: {}", path.display());
+        }
+        move_to_destination(&staged, path).await
+    }
+
+    /// 下载文件并校验完整性，遇到可重试的错误时按配置进行指数退避重试
+    /// label 用于在重试日志中标明文件所属的视频与分页
+    pub async fn fetch_with_retry(
+        &self,
+        urls: &[&str],
+        path: &Path,
+        expected: &ExpectedFile,
+        label: &str,
+    ) -> Result<()> {
+        let (max_retries, base_delay_ms) = crate::config::with_config(|bundle| {
+            (
+                bundle.config.download_max_retries,
+                bundle.config.download_retry_base_delay_ms,
+            )
+        });
+        let mut attempt = 0;
+        loop {
+            let err = match self.fetch_verified(urls, path, expected).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+            if attempt >= max_retries || !is_retryable_download_error(&err) || crate::task::TASK_CONTROLLER.is_paused()
+            {
+                return Err(err);
+            }
+            attempt += 1;
+            let delay = retry_delay(base_delay_ms, attempt, rand::random::<f64>() * RETRY_JITTER);
+            warn!(
+                "{} 下载失败，{} 毫秒后进行第 {}/{} 次重试: {:#}",
+                label,
+                delay.as_millis(),
+                attempt,
+                max_retries,
+                err
+            );
+            tokio::time::sleep(delay).await;
         }
     }
 
@@ -66,6 +328,26 @@ impl UnifiedDownloader {
         }
     }
 
+    /// 从音频流或混合流中提取音轨输出为音频文件，写入标签并在提供封面时嵌入封面
+    pub async fn extract_audio(
+        &self,
+        input_path: &Path,
+        cover_path: Option<&Path>,
+        tags: &AudioTags,
+        output_path: &Path,
+    ) -> Result<()> {
+        if let Some(parent) = output_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let args = extract_audio_args(input_path, cover_path, tags, output_path);
+        let output = crate::utils::ffmpeg::ffmpeg_command().args(&args).output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("ffmpeg error: {}", stderr);
+        }
+        Ok(())
+    }
+
     /// 智能下载：根据文件大小和配置决定使用哪种下载方式
     #[allow(dead_code)]
     pub async fn smart_fetch(&self, url: &str, path: &Path) -> Result<()> {
@@ -73,7 +355,7 @@ impl UnifiedDownloader {
             Self::Native(downloader) => {
                 // 原生下载器现在只使用单线程下载
                 info!("原生下载器使用单线程下载");
-                downloader.fetch(url, path).await
+                downloader.fetch(url, path).await.map(|_| ())
             }
             Self::Aria2(downloader) => {
                 // aria2下载器：使用智能下载功能
@@ -118,3 +400,120 @@ impl UnifiedDownloader {
         matches!(self, Self::Native(_))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_retry_policy() {
+        assert_eq!(retry_delay(1000, 1, 0.0), Duration::from_millis(1000));
+        assert_eq!(retry_delay(1000, 3, 0.0), Duration::from_millis(4000));
+        assert_eq!(retry_delay(1000, 2, 0.5), Duration::from_millis(3000));
+        assert_eq!(retry_delay(60_000, 10, 0.0), MAX_RETRY_DELAY);
+
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::FORBIDDEN));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+
+        let reset = anyhow::Error::from(std::io::Error::from(ErrorKind::ConnectionReset)).context("下载失败");
+        assert!(is_retryable_download_error(&reset));
+        let denied = anyhow::Error::from(std::io::Error::from(ErrorKind::PermissionDenied));
+        assert!(!is_retryable_download_error(&denied));
+        let integrity = anyhow::Error::from(IntegrityError {
+            path: "a.mp4".to_string(),
+            reason: "大小不一致".to_string(),
+        });
+        assert!(is_retryable_download_error(&integrity));
+        assert!(!is_retryable_download_error(&anyhow::anyhow!("no urls provided")));
+    }
+
+    #[test]
+    fn test_extract_audio_args() {
+        let tags = AudioTags {
+            title: "歌曲".to_string(),
+            artist: "UP主".to_string(),
+            album: None,
+        };
+        let args = extract_audio_args(Path::new("in.m4s"), None, &tags, Path::new("out.m4a"));
+        assert_eq!(
+            args,
+            [
+                "-i",
+                "in.m4s",
+                "-map",
+                "0:a:0",
+                "-c:a",
+                "copy",
+                "-metadata",
+                "title=歌曲",
+                "-metadata",
+                "artist=UP主",
+                "-strict",
+                "unofficial",
+                "-y",
+                "out.m4a"
+            ]
+        );
+
+        // 提供封面时作为 attached_pic 嵌入
+        let args = extract_audio_args(
+            Path::new("in.m4s"),
+            Some(Path::new("cover.jpg")),
+            &tags,
+            Path::new("out.m4a"),
+        );
+        let cover_idx = args.iter().position(|arg| arg == "cover.jpg").unwrap();
+        assert_eq!(args[cover_idx - 1], "-i");
+        assert!(args.windows(2).any(|w| w == ["-disposition:v:0", "attached_pic"]));
+    }
+
+    #[tokio::test]
+    async fn test_staging_and_move() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-staging-test-{}", std::process::id()));
+        let temp_dir = dir.join("temp");
+        let dest = dir.join("media").join("视频.mp4");
+
+        let staged = staging_path_in(&temp_dir, &dest);
+        assert!(staged.starts_with(&temp_dir));
+        assert_eq!(staged.extension().unwrap(), "mp4");
+        // 已位于暂存目录中的路径不再重复暂存
+        assert_eq!(staging_path_in(&temp_dir, &staged), staged);
+
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        tokio::fs::write(&staged, b"video").await.unwrap();
+        move_to_destination(&staged, &dest).await.unwrap();
+        assert!(!staged.exists());
+        assert_eq!(tokio::fs::read(&dest).await.unwrap(), b"video");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_file_integrity() {
+        let path = std::env::temp_dir().join(format!("bili-sync-integrity-test-{}", std::process::id()));
+        tokio::fs::write(&path, b"hello").await.unwrap();
+
+        assert!(verify_file_integrity(&path, None, None).await.is_ok());
+        assert!(
+            verify_file_integrity(&path, Some(5), Some("5D41402ABC4B2A76B9719D911017C592"))
+                .await
+                .is_ok()
+        );
+
+        let err = verify_file_integrity(&path, Some(10), None).await.unwrap_err();
+        assert!(err.downcast_ref::<IntegrityError>().is_some());
+        let err = verify_file_integrity(&path, Some(5), Some("00000000000000000000000000000000"))
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<IntegrityError>().is_some());
+
+        // 跨越多个读取块的文件与一次性计算的结果一致
+        let data: Vec<u8> = (0..MD5_CHUNK_SIZE * 2 + 7).map(|i| i as u8).collect();
+        tokio::fs::write(&path, &data).await.unwrap();
+        assert_eq!(file_md5(&path).await.unwrap(), hex::encode(md5::compute(&data).0));
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
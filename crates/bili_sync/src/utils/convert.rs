@@ -280,6 +280,30 @@ impl VideoInfo {
             _ => unreachable!(),
         }
     }
+
+    /// 获取视频标题
+    pub fn title(&self) -> &str {
+        match self {
+            VideoInfo::Detail { title, .. }
+            | VideoInfo::Favorite { title, .. }
+            | VideoInfo::WatchLater { title, .. }
+            | VideoInfo::Collection { title, .. }
+            | VideoInfo::Submission { title, .. }
+            | VideoInfo::Bangumi { title, .. } => title,
+        }
+    }
+
+    /// 获取视频本身的发布时间，投稿接口没有单独的发布时间，使用 ctime 代替
+    pub fn pubtime(&self) -> &DateTime<Utc> {
+        match self {
+            VideoInfo::Detail { pubtime: time, .. }
+            | VideoInfo::Favorite { pubtime: time, .. }
+            | VideoInfo::WatchLater { pubtime: time, .. }
+            | VideoInfo::Collection { pubtime: time, .. }
+            | VideoInfo::Submission { ctime: time, .. }
+            | VideoInfo::Bangumi { pubtime: time, .. } => time,
+        }
+    }
 }
 
 impl PageInfo {
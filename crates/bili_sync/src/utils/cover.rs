@@ -0,0 +1,70 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use crate::config::CoverFormat;
+
+/// 按配置为B站图床地址添加缩放与格式后缀，未配置时或非B站图床地址原样返回
+///
+/// 下载封面与 NFO 中引用的封面地址都应经过此函数，保证两者一致
+pub fn sized_cover_url(url: &str) -> Cow<'_, str> {
+    let (max_width, format) =
+        crate::config::with_config(|bundle| (bundle.config.cover_max_width, bundle.config.cover_format));
+    sized_cover_url_with(url, max_width, format)
+}
+
+fn sized_cover_url_with(url: &str, max_width: u32, format: CoverFormat) -> Cow<'_, str> {
+    // 已带有 @ 后缀的地址说明已处理过或由B站指定了尺寸，不再叠加
+    if !url.contains(".hdslb.com/") || url.contains('@') {
+        return Cow::Borrowed(url);
+    }
+    let width = if max_width > 0 {
+        format!("{}w", max_width)
+    } else {
+        String::new()
+    };
+    let extension = match format {
+        CoverFormat::Original => "",
+        CoverFormat::Jpg => ".jpg",
+        CoverFormat::Webp => ".webp",
+    };
+    if width.is_empty() && extension.is_empty() {
+        return Cow::Borrowed(url);
+    }
+    Cow::Owned(format!("{}@{}{}", url, width, extension))
+}
+
+/// 按配置的封面格式调整本地封面文件的扩展名
+pub fn cover_file_path(path: PathBuf) -> PathBuf {
+    let format = crate::config::with_config(|bundle| bundle.config.cover_format);
+    path.with_extension(format.extension())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sized_cover_url() {
+        let url = "https://i0.hdslb.com/bfs/archive/abc.jpg";
+        assert_eq!(sized_cover_url_with(url, 0, CoverFormat::Original), url);
+        assert_eq!(
+            sized_cover_url_with(url, 640, CoverFormat::Original),
+            "https://i0.hdslb.com/bfs/archive/abc.jpg@640w"
+        );
+        assert_eq!(
+            sized_cover_url_with(url, 640, CoverFormat::Webp),
+            "https://i0.hdslb.com/bfs/archive/abc.jpg@640w.webp"
+        );
+        assert_eq!(
+            sized_cover_url_with(url, 0, CoverFormat::Jpg),
+            "https://i0.hdslb.com/bfs/archive/abc.jpg@.jpg"
+        );
+        // 非B站图床或已带后缀的地址不修改
+        assert_eq!(
+            sized_cover_url_with("https://example.com/cover.jpg", 640, CoverFormat::Webp),
+            "https://example.com/cover.jpg"
+        );
+        let resized = "https://i0.hdslb.com/bfs/archive/abc.jpg@320w.webp";
+        assert_eq!(sized_cover_url_with(resized, 640, CoverFormat::Jpg), resized);
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use bili_sync_entity::{page, video};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue::Set, ConnectionTrait};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+use crate::config::DedupStrategy;
+use crate::utils::status::{PageStatus, STATUS_OK};
+
+/// 查找其他视频源中已下载完成的相同分页（相同 cid），返回其原始文件路径
+async fn find_canonical_file(connection: &DatabaseConnection, page_model: &page::Model) -> Result<Option<PathBuf>> {
+    let candidates = page::Entity::find()
+        .inner_join(video::Entity)
+        .filter(
+            page::Column::Cid
+                .eq(page_model.cid)
+                .and(page::Column::Id.ne(page_model.id))
+                .and(page::Column::Path.is_not_null())
+                .and(video::Column::Deleted.eq(0)),
+        )
+        .all(connection)
+        .await?;
+    // 优先选择本身就是原始文件的分页
+    let mut candidates = candidates
+        .into_iter()
+        .filter(|page| PageStatus::from(page.download_status).get(1) == STATUS_OK)
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|page| page.canonical_path.is_some());
+    for candidate in candidates {
+        let path = candidate
+            .canonical_path
+            .or(candidate.path)
+            .map(PathBuf::from)
+            .filter(|path| path.extension().is_some_and(|ext| ext != "strm"));
+        if let Some(path) = path.filter(|path| path.is_file()) {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// 按去重方式在 target 处创建指向 source 的链接，硬链接跨文件系统时退化为复制
+async fn link_file(source: &Path, target: &Path, strategy: DedupStrategy) -> Result<()> {
+    if fs::symlink_metadata(target).await.is_ok() {
+        fs::remove_file(target).await?;
+    }
+    match strategy {
+        DedupStrategy::None => unreachable!("未启用去重时不应创建链接"),
+        DedupStrategy::Hardlink => match fs::hard_link(source, target).await {
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+                debug!(
+                    "{} 与 {} 不在同一文件系统，改为复制",
+                    source.display(),
+                    target.display()
+                );
+                fs::copy(source, target).await.map(|_| ()).context("复制文件失败")
+            }
+            res => res.context("创建硬链接失败"),
+        },
+        DedupStrategy::Symlink => {
+            #[cfg(unix)]
+            let res = fs::symlink(source, target).await;
+            #[cfg(windows)]
+            let res = fs::symlink_file(source, target).await;
+            res.context("创建符号链接失败")
+        }
+    }
+}
+
+/// 尝试复用其他视频源已下载的相同分页，成功时返回原始文件路径，调用方无需再下载
+pub async fn link_duplicate_page(
+    connection: &DatabaseConnection,
+    page_model: &page::Model,
+    video_path: &Path,
+) -> Option<String> {
+    let strategy = crate::config::reload_config().dedup_strategy;
+    if strategy == DedupStrategy::None {
+        return None;
+    }
+    let canonical = match find_canonical_file(connection, page_model).await {
+        Ok(Some(canonical)) if canonical != video_path => canonical,
+        Ok(_) => return None,
+        Err(e) => {
+            warn!("查找 cid {} 的已下载文件失败: {:#}", page_model.cid, e);
+            return None;
+        }
+    };
+    if let Some(parent) = video_path.parent() {
+        if let Err(e) = fs::create_dir_all(parent).await {
+            warn!("创建目录 {} 失败: {:#}", parent.display(), e);
+            return None;
+        }
+    }
+    match link_file(&canonical, video_path, strategy).await {
+        Ok(()) => {
+            info!(
+                "分页 (cid: {}) 已在其他视频源下载，已通过{}复用: {} -> {}",
+                page_model.cid,
+                strategy.as_str(),
+                video_path.display(),
+                canonical.display()
+            );
+            Some(canonical.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            warn!("复用已下载的分页 (cid: {}) 失败，将重新下载: {:#}", page_model.cid, e);
+            None
+        }
+    }
+}
+
+/// 在删除 target（文件或目录）之前调用：如果其他视频源的分页链接到了 target 下的文件，
+/// 将原始文件转移到其中一个链接的位置，并让其余链接改为指向新的位置，避免删除后链接失效
+pub async fn release_linked_files<C: ConnectionTrait>(connection: &C, target: &Path) -> Result<()> {
+    let target = target.to_string_lossy().to_string();
+    let prefix = format!("{}{}", target.trim_end_matches(['/', '\\']), std::path::MAIN_SEPARATOR);
+    let is_removed = |path: &str| path == target || path.starts_with(&prefix);
+    let dependents = page::Entity::find()
+        .filter(
+            page::Column::CanonicalPath
+                .eq(target.as_str())
+                .or(page::Column::CanonicalPath.starts_with(prefix.as_str())),
+        )
+        .all(connection)
+        .await?;
+    let mut groups: HashMap<String, Vec<page::Model>> = HashMap::new();
+    for dependent in dependents {
+        // 同样位于待删除路径下的分页会被一起删除，无需处理
+        if dependent.path.as_deref().is_some_and(|path| !is_removed(path)) {
+            if let Some(canonical) = dependent.canonical_path.clone() {
+                groups.entry(canonical).or_default().push(dependent);
+            }
+        }
+    }
+    for (canonical, dependents) in groups {
+        let canonical = PathBuf::from(canonical);
+        let new_canonical = PathBuf::from(dependents[0].path.clone().unwrap_or_default());
+        let is_symlink = |path: &Path| path.symlink_metadata().is_ok_and(|meta| meta.file_type().is_symlink());
+        if is_symlink(&new_canonical) && canonical.is_file() {
+            fs::remove_file(&new_canonical).await?;
+            if fs::rename(&canonical, &new_canonical).await.is_err() {
+                fs::copy(&canonical, &new_canonical)
+                    .await
+                    .with_context(|| format!("转移文件 {} 失败", canonical.display()))?;
+            }
+        }
+        for (index, dependent) in dependents.into_iter().enumerate() {
+            let path = PathBuf::from(dependent.path.clone().unwrap_or_default());
+            let mut active_model: page::ActiveModel = dependent.into();
+            if index == 0 {
+                active_model.canonical_path = Set(None);
+            } else {
+                if is_symlink(&path) {
+                    link_file(&new_canonical, &path, DedupStrategy::Symlink).await?;
+                }
+                active_model.canonical_path = Set(Some(new_canonical.to_string_lossy().to_string()));
+            }
+            active_model.update(connection).await?;
+        }
+        info!(
+            "{} 仍被其他视频源引用，已转移到 {}",
+            canonical.display(),
+            new_canonical.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_link_file() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-dedup-{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let source = dir.join("source.mp4");
+        fs::write(&source, b"video").await.unwrap();
+
+        let hardlink = dir.join("hardlink.mp4");
+        link_file(&source, &hardlink, DedupStrategy::Hardlink).await.unwrap();
+        assert_eq!(fs::read(&hardlink).await.unwrap(), b"video");
+
+        #[cfg(unix)]
+        {
+            let symlink = dir.join("symlink.mp4");
+            link_file(&source, &symlink, DedupStrategy::Symlink).await.unwrap();
+            assert!(symlink.symlink_metadata().unwrap().file_type().is_symlink());
+            // 目标已存在时会被替换
+            link_file(&hardlink, &symlink, DedupStrategy::Symlink).await.unwrap();
+            assert_eq!(fs::read_link(&symlink).await.unwrap(), hardlink);
+        }
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
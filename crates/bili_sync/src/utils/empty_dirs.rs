@@ -0,0 +1,119 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bili_sync_entity::video;
+use sea_orm::DatabaseConnection;
+use tracing::{debug, info, warn};
+
+/// 从 `start` 开始逐级向上删除空文件夹，`stop_at` 本身及其之外的文件夹不会被删除
+///
+/// 遇到符号链接或非空文件夹时停止，返回删除的文件夹数量
+pub fn remove_empty_dirs_upward(start: &Path, stop_at: &Path) -> usize {
+    let Ok(root) = stop_at.canonicalize() else {
+        warn!("无法解析清理空文件夹的根目录: {}", stop_at.display());
+        return 0;
+    };
+    let mut removed = 0;
+    let mut current = Some(start);
+    while let Some(dir) = current {
+        match std::fs::symlink_metadata(dir) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                debug!("遇到符号链接，停止清理空文件夹: {}", dir.display());
+                break;
+            }
+            Ok(metadata) if metadata.is_dir() => {}
+            // 文件夹已被整体删除，从上一级继续
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                current = dir.parent();
+                continue;
+            }
+            _ => break,
+        }
+        // 上级路径中可能存在符号链接，按实际位置判断是否仍在根目录之内
+        match dir.canonicalize() {
+            Ok(canonical) if canonical != root && canonical.starts_with(&root) => {}
+            _ => break,
+        }
+        match std::fs::read_dir(dir) {
+            Ok(mut entries) => {
+                if entries.next().is_some() {
+                    break;
+                }
+            }
+            Err(e) => {
+                warn!("读取文件夹失败: {} - {}", dir.display(), e);
+                break;
+            }
+        }
+        if let Err(e) = std::fs::remove_dir(dir) {
+            warn!("删除空文件夹失败: {} - {}", dir.display(), e);
+            break;
+        }
+        info!("已删除空文件夹: {}", dir.display());
+        removed += 1;
+        current = dir.parent();
+    }
+    removed
+}
+
+/// 删除视频文件后，从分页及视频所在文件夹向上清理空文件夹，直到视频源根目录
+///
+/// 找不到视频源时只清理视频文件夹及其子文件夹
+pub async fn cleanup_video_dirs(
+    connection: &DatabaseConnection,
+    video: &video::Model,
+    mut dirs: Vec<PathBuf>,
+) -> usize {
+    let video_path = PathBuf::from(video.path.replace('\\', "/"));
+    let root = match crate::utils::trash::video_source_root(connection, video).await {
+        Some(root) => root,
+        None => match video_path.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => return 0,
+        },
+    };
+    dirs.push(video_path);
+    // 先处理层级更深的文件夹，上层文件夹才有机会变为空
+    dirs.sort();
+    dirs.dedup();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    dirs.iter().map(|dir| remove_empty_dirs_upward(dir, &root)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_empty_dirs_upward_stops_at_root() {
+        let root = std::env::temp_dir().join(format!("bili_sync_empty_dirs_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let season = root.join("剧集").join("Season 01");
+        std::fs::create_dir_all(&season).unwrap();
+        std::fs::create_dir_all(root.join("其他视频")).unwrap();
+
+        assert_eq!(remove_empty_dirs_upward(&season, &root), 2);
+        assert!(!root.join("剧集").exists());
+        assert!(root.join("其他视频").exists());
+
+        // 根目录本身即使为空也不删除
+        std::fs::remove_dir(root.join("其他视频")).unwrap();
+        assert_eq!(remove_empty_dirs_upward(&root.join("其他视频"), &root), 0);
+        assert!(root.exists());
+
+        // 不会通过符号链接删除根目录之外的文件夹
+        #[cfg(unix)]
+        {
+            let outside = std::env::temp_dir().join(format!("bili_sync_empty_dirs_outside_{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&outside);
+            std::fs::create_dir_all(outside.join("空文件夹")).unwrap();
+            std::os::unix::fs::symlink(&outside, root.join("链接")).unwrap();
+            assert_eq!(remove_empty_dirs_upward(&root.join("链接").join("空文件夹"), &root), 0);
+            assert!(outside.join("空文件夹").exists());
+            assert_eq!(remove_empty_dirs_upward(&root.join("链接"), &root), 0);
+            assert!(root.join("链接").exists());
+            std::fs::remove_dir_all(&outside).unwrap();
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
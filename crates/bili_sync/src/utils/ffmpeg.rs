@@ -0,0 +1,240 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use tokio::process::Command;
+
+use crate::config::DanmakuEmbedMode;
+
+/// 校验配置的 FFmpeg 可执行文件存在
+pub fn validate_ffmpeg_path(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        bail!("FFmpeg 可执行文件不存在: {}", path.display());
+    }
+    Ok(())
+}
+
+/// 校验硬件加速方式，只允许 cuda、qsv、vaapi 这类简单标识
+pub fn validate_hwaccel(hwaccel: &str) -> Result<()> {
+    if hwaccel.is_empty() || !hwaccel.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        bail!("无效的硬件加速方式: {}", hwaccel);
+    }
+    Ok(())
+}
+
+/// 与 FFmpeg 位于同一目录的 ffprobe 路径，沿用 FFmpeg 的扩展名
+fn sibling_ffprobe(ffmpeg_path: &Path) -> PathBuf {
+    let mut file_name = OsString::from("ffprobe");
+    if let Some(extension) = ffmpeg_path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    ffmpeg_path.with_file_name(file_name)
+}
+
+/// 创建 FFmpeg 命令，未配置路径时使用 PATH 中的 ffmpeg
+pub fn ffmpeg_command() -> Command {
+    match crate::config::with_config(|bundle| bundle.config.ffmpeg_path.clone()) {
+        Some(path) => Command::new(path),
+        None => Command::new("ffmpeg"),
+    }
+}
+
+/// 创建 ffprobe 命令，配置了 FFmpeg 路径时优先使用同目录下的 ffprobe
+pub fn ffprobe_command() -> Command {
+    let ffprobe = crate::config::with_config(|bundle| bundle.config.ffmpeg_path.as_deref().map(sibling_ffprobe))
+        .filter(|path| path.is_file());
+    match ffprobe {
+        Some(path) => Command::new(path),
+        None => Command::new("ffprobe"),
+    }
+}
+
+/// 配置了硬件加速时返回需要插入到输入参数之前的 -hwaccel 参数
+pub fn hwaccel_args() -> Vec<String> {
+    crate::config::with_config(|bundle| bundle.config.ffmpeg_hwaccel.clone())
+        .map(|hwaccel| vec!["-hwaccel".to_string(), hwaccel])
+        .unwrap_or_default()
+}
+
+/// 执行 FFmpeg 命令并等待输出，超过配置的超时时间时终止进程
+pub async fn output_with_timeout(mut command: Command) -> Result<Output> {
+    let timeout_secs = crate::config::with_config(|bundle| bundle.config.ffmpeg_merge_timeout_secs);
+    // 超时后 future 被丢弃，需要同时终止子进程
+    command.kill_on_drop(true);
+    if timeout_secs == 0 {
+        return Ok(command.output().await?);
+    }
+    match tokio::time::timeout(Duration::from_secs(timeout_secs), command.output()).await {
+        Ok(output) => Ok(output?),
+        Err(_) => bail!("FFmpeg 执行超过 {} 秒，已终止", timeout_secs),
+    }
+}
+
+/// 构建嵌入弹幕的 FFmpeg 参数
+///
+/// soft 模式将弹幕封装为字幕轨道（mp4 只支持 mov_text，会丢失弹幕的位置与颜色），hard 模式用 ass 滤镜烧录进画面并重新编码视频。
+/// 滤镜参数中的路径需要多层转义，因此烧录时只传入不含特殊字符的文件名，由调用方将工作目录设为弹幕所在文件夹
+fn danmaku_embed_args(
+    video_path: &Path,
+    ass_path: &Path,
+    mode: DanmakuEmbedMode,
+    hwaccel_args: &[String],
+    output_path: &Path,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = Vec::new();
+    match mode {
+        DanmakuEmbedMode::None => {}
+        DanmakuEmbedMode::Soft => {
+            let subtitle_codec = match output_path.extension().and_then(|ext| ext.to_str()) {
+                Some("mkv") => "ass",
+                _ => "mov_text",
+            };
+            args.push("-i".into());
+            args.push(video_path.into());
+            args.push("-i".into());
+            args.push(ass_path.into());
+            for arg in [
+                "-map",
+                "0",
+                "-map",
+                "1",
+                "-c",
+                "copy",
+                "-c:s",
+                subtitle_codec,
+                "-metadata:s:s:0",
+                "language=chi",
+                "-metadata:s:s:0",
+                "title=弹幕",
+            ] {
+                args.push(arg.into());
+            }
+        }
+        DanmakuEmbedMode::Hard => {
+            args.extend(hwaccel_args.iter().map(OsString::from));
+            args.push("-i".into());
+            args.push(video_path.into());
+            let mut filter = OsString::from("ass=");
+            filter.push(ass_path.file_name().unwrap_or_default());
+            args.push("-vf".into());
+            args.push(filter);
+            for arg in ["-c:a", "copy"] {
+                args.push(arg.into());
+            }
+        }
+    }
+    args.push("-y".into());
+    args.push(output_path.into());
+    args
+}
+
+/// 将弹幕 ASS 嵌入视频，成功后替换原视频文件，外挂的 ASS 文件保持不变
+pub async fn embed_danmaku(video_path: &Path, ass_path: &Path, mode: DanmakuEmbedMode) -> Result<()> {
+    if mode == DanmakuEmbedMode::None {
+        return Ok(());
+    }
+    let video_path = std::path::absolute(video_path)?;
+    let dir = video_path.parent().context("视频文件没有所在文件夹")?;
+    let extension = video_path.extension().and_then(|ext| ext.to_str()).unwrap_or("mp4");
+    // 同一文件夹中可能同时处理多个分页，临时文件以视频路径的哈希区分
+    let hash = hex::encode(md5::compute(video_path.to_string_lossy().as_bytes()).0);
+    let output_path = dir.join(format!(".danmaku-embed-{}.{}", hash, extension));
+    let temp_ass_path = dir.join(format!(".danmaku-embed-{}.ass", hash));
+    tokio::fs::copy(ass_path, &temp_ass_path).await?;
+
+    let args = danmaku_embed_args(&video_path, &temp_ass_path, mode, &hwaccel_args(), &output_path);
+    let mut command = ffmpeg_command();
+    command.current_dir(dir).args(&args);
+    let result = output_with_timeout(command).await;
+    let _ = tokio::fs::remove_file(&temp_ass_path).await;
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&output_path).await;
+            return Err(e);
+        }
+    };
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&output_path).await;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffmpeg error: {}", stderr);
+    }
+    tokio::fs::rename(&output_path, &video_path).await?;
+    Ok(())
+}
+
+/// 从视频中截取指定时间点的一帧保存为图片，图片格式由输出文件扩展名决定
+pub async fn extract_frame(video_path: &Path, at_seconds: f64, output_path: &Path) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let output = ffmpeg_command()
+        .arg("-ss")
+        .arg(format!("{:.3}", at_seconds))
+        .arg("-i")
+        .arg(video_path)
+        .args(["-frames:v", "1", "-q:v", "2", "-y"])
+        .arg(output_path)
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffmpeg error: {}", stderr);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_hwaccel() {
+        assert!(validate_hwaccel("cuda").is_ok());
+        assert!(validate_hwaccel("qsv").is_ok());
+        assert!(validate_hwaccel("").is_err());
+        assert!(validate_hwaccel("cuda -i x").is_err());
+    }
+
+    #[test]
+    fn test_sibling_ffprobe() {
+        assert_eq!(
+            sibling_ffprobe(Path::new("/opt/ffmpeg/bin/ffmpeg")),
+            PathBuf::from("/opt/ffmpeg/bin/ffprobe")
+        );
+        assert_eq!(
+            sibling_ffprobe(Path::new("C:/ffmpeg/ffmpeg.exe")),
+            PathBuf::from("C:/ffmpeg/ffprobe.exe")
+        );
+    }
+
+    #[test]
+    fn test_danmaku_embed_args() {
+        let video = Path::new("/media/视频/P1.mp4");
+        let ass = Path::new("/media/视频/.danmaku-embed-abc.ass");
+        let output = Path::new("/media/视频/.danmaku-embed-abc.mp4");
+        let hwaccel = vec!["-hwaccel".to_string(), "cuda".to_string()];
+        let to_strings = |args: Vec<OsString>| -> Vec<String> {
+            args.into_iter().map(|arg| arg.to_string_lossy().into_owned()).collect()
+        };
+
+        let soft = to_strings(danmaku_embed_args(video, ass, DanmakuEmbedMode::Soft, &hwaccel, output));
+        assert!(!soft.contains(&"-hwaccel".to_string()));
+        assert!(soft.windows(2).any(|pair| pair == ["-c:s", "mov_text"]));
+        assert!(soft.windows(2).any(|pair| pair == ["-c", "copy"]));
+        assert_eq!(
+            soft.last().map(String::as_str),
+            Some("/media/视频/.danmaku-embed-abc.mp4")
+        );
+
+        let hard = to_strings(danmaku_embed_args(video, ass, DanmakuEmbedMode::Hard, &hwaccel, output));
+        assert_eq!(&hard[..2], ["-hwaccel", "cuda"]);
+        assert!(hard
+            .windows(2)
+            .any(|pair| pair == ["-vf", "ass=.danmaku-embed-abc.ass"]));
+        assert!(!hard.contains(&"-map".to_string()));
+    }
+}
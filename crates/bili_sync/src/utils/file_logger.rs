@@ -319,3 +319,79 @@ pub fn shutdown_file_logger() {
         writer.shutdown();
     }
 }
+
+// 日志文件的级别，与 create_daily_log_files 创建的文件对应
+pub const LOG_FILE_LEVELS: [&str; 5] = ["all", "debug", "info", "warn", "error"];
+
+// 日志文件的CSV头（含UTF-8 BOM），截取日志末尾时补在开头
+pub const LOG_FILE_HEADER: &str = "\u{feff}时间,级别,消息,来源\n";
+
+// 日志目录
+pub fn log_dir() -> std::path::PathBuf {
+    CONFIG_DIR.join("logs")
+}
+
+// 解析日志文件名 logs-{级别}-{日期}.csv，返回级别与日期，不符合格式时返回 None
+pub fn parse_log_file_name(file_name: &str) -> Option<(&'static str, NaiveDate)> {
+    let rest = file_name.strip_prefix("logs-")?.strip_suffix(".csv")?;
+    let (level, date) = rest.split_once('-')?;
+    let level = LOG_FILE_LEVELS.into_iter().find(|l| *l == level)?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some((level, date))
+}
+
+// 从文件末尾向前查找，返回最后 lines 行的起始位置，文件行数不足时返回 0
+pub async fn tail_start_offset(file: &mut tokio::fs::File, lines: usize) -> std::io::Result<u64> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+
+    const CHUNK_SIZE: u64 = 64 * 1024;
+    let len = file.metadata().await?.len();
+    let mut end = len;
+    let mut newlines = 0;
+    let mut buf = vec![0u8; CHUNK_SIZE as usize];
+    while end > 0 {
+        let start = end.saturating_sub(CHUNK_SIZE);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start)).await?;
+        file.read_exact(chunk).await?;
+        for (i, byte) in chunk.iter().enumerate().rev() {
+            // 文件末尾的换行不算作新的一行
+            if *byte != b'\n' || start + i as u64 == len - 1 {
+                continue;
+            }
+            newlines += 1;
+            if newlines == lines {
+                return Ok(start + i as u64 + 1);
+            }
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_log_file_tail() {
+        assert_eq!(
+            parse_log_file_name("logs-warn-2026-10-17.csv"),
+            Some(("warn", NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()))
+        );
+        assert!(parse_log_file_name("logs-trace-2026-10-17.csv").is_none());
+        assert!(parse_log_file_name("../data.sqlite").is_none());
+        assert!(parse_log_file_name("logs-all-2026-10-17.csv/../x").is_none());
+
+        let path = std::env::temp_dir().join(format!("bili-sync-log-tail-{}.csv", std::process::id()));
+        tokio::fs::write(&path, format!("{}a\nb\nc\n", LOG_FILE_HEADER))
+            .await
+            .unwrap();
+        let mut file = tokio::fs::File::open(&path).await.unwrap();
+        let content = tokio::fs::read(&path).await.unwrap();
+        let offset = tail_start_offset(&mut file, 2).await.unwrap() as usize;
+        assert_eq!(&content[offset..], b"b\nc\n");
+        assert_eq!(tail_start_offset(&mut file, 10).await.unwrap(), 0);
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
@@ -5,16 +5,32 @@ macro_rules! regex {
     }};
 }
 
+use crate::config::FilenamePolicy;
+
 pub fn filenamify<S: AsRef<str>>(input: S) -> String {
     filenamify_with_options(input, false)
 }
 
-/// 带选项的文件名安全化函数
+/// 带选项的文件名安全化函数，使用配置中的文件名策略
 ///
 /// # 参数
 /// - `input`: 输入字符串
 /// - `preserve_template_separators`: 是否保护模板路径分隔符（__UNIX_SEP__, __WIN_SEP__）
 pub fn filenamify_with_options<S: AsRef<str>>(input: S, preserve_template_separators: bool) -> String {
+    let policy = crate::config::with_config(|bundle| bundle.config.filename_policy);
+    filenamify_with_policy(input, preserve_template_separators, policy)
+}
+
+/// 按指定的文件名策略进行安全化
+///
+/// - `Default`: 全角标点转为半角，替换容易引起问题的特殊符号
+/// - `Ascii`: 在 `Default` 的基础上将全角字符转写为 ASCII，无法转写的字符替换为下划线
+/// - `PreserveUnicode`: 保留全角标点与特殊符号，只替换文件系统不允许的字符
+pub fn filenamify_with_policy<S: AsRef<str>>(
+    input: S,
+    preserve_template_separators: bool,
+    policy: FilenamePolicy,
+) -> String {
     let mut input = input.as_ref().to_string();
 
     // 保护路径分隔符标记，避免被处理
@@ -52,19 +68,26 @@ pub fn filenamify_with_options<S: AsRef<str>>(input: S, preserve_template_separa
     let paren_replacement_right = ")";
     let colon_replacement = "-";
 
-    // 1. 处理全角字符映射
-    input = fullwidth_colon.replace_all(&input, colon_replacement).into_owned();
-    input = fullwidth_space.replace_all(&input, space_replacement).into_owned();
-    input = angle_brackets.replace_all(&input, replacement).into_owned();
+    if policy != FilenamePolicy::PreserveUnicode {
+        // 1. 处理全角字符映射
+        input = fullwidth_colon.replace_all(&input, colon_replacement).into_owned();
+        input = fullwidth_space.replace_all(&input, space_replacement).into_owned();
+        input = angle_brackets.replace_all(&input, replacement).into_owned();
 
-    // 2. 处理全角括号
-    input = input.replace('「', bracket_replacement_left);
-    input = input.replace('」', bracket_replacement_right);
-    input = input.replace('（', paren_replacement_left);
-    input = input.replace('）', paren_replacement_right);
+        // 2. 处理全角括号
+        input = input.replace('「', bracket_replacement_left);
+        input = input.replace('」', bracket_replacement_right);
+        input = input.replace('（', paren_replacement_left);
+        input = input.replace('）', paren_replacement_right);
 
-    // 3. 处理其他有问题的字符
-    input = problematic_chars.replace_all(&input, replacement).into_owned();
+        // 3. 处理其他有问题的字符
+        input = problematic_chars.replace_all(&input, replacement).into_owned();
+    }
+
+    // 3.1 仅保留 ASCII 字符，需跳过分隔符占位符，之后的步骤会继续处理转写出的保留字符
+    if policy == FilenamePolicy::Ascii {
+        input = transliterate_ascii(&input, &[unix_sep_placeholder, win_sep_placeholder], replacement);
+    }
 
     // 4. 处理Windows保留字符
     input = reserved.replace_all(&input, replacement).into_owned();
@@ -115,9 +138,129 @@ pub fn filenamify_with_options<S: AsRef<str>>(input: S, preserve_template_separa
     input
 }
 
+/// 按字节数截断字符串，不会截断在多字节字符中间，并去除截断后末尾的空格与点号
+fn truncate_bytes(input: &str, max_bytes: usize) -> &str {
+    if input.len() <= max_bytes {
+        return input;
+    }
+    let mut end = max_bytes;
+    while !input.is_char_boundary(end) {
+        end -= 1;
+    }
+    // Windows 不允许文件名以空格或点号结尾
+    input[..end].trim_end_matches([' ', '.'])
+}
+
+/// 截断路径中超过 `max_bytes` 的每一段，`max_bytes` 为 0 时不限制
+pub fn truncate_path_segments(path: &str, max_bytes: usize) -> String {
+    if max_bytes == 0 {
+        return path.to_string();
+    }
+    path.split_inclusive(std::path::is_separator)
+        .map(|segment| {
+            let (name, separator) = match segment.char_indices().last() {
+                Some((index, c)) if std::path::is_separator(c) => segment.split_at(index),
+                _ => (segment, ""),
+            };
+            let truncated = truncate_bytes(name, max_bytes);
+            if truncated.len() < name.len() {
+                info!("路径段超过 {} 字节，已截断: {} -> {}", max_bytes, name, truncated);
+            }
+            format!("{}{}", truncated, separator)
+        })
+        .collect()
+}
+
+/// 截断文件名主干，使加上 `suffix_bytes` 字节的扩展名后文件名与完整路径都不超过配置的上限
+///
+/// 模板中包含路径分隔符时只截断最后一段
+pub fn fit_file_stem(parent: &std::path::Path, stem: String, suffix_bytes: usize) -> String {
+    let (max_filename_bytes, max_path_bytes) =
+        crate::config::with_config(|bundle| (bundle.config.max_filename_bytes, bundle.config.max_path_bytes));
+    match fit_file_stem_with(parent, &stem, suffix_bytes, max_filename_bytes, max_path_bytes) {
+        Some(truncated) => {
+            info!("文件名超过长度限制，已截断: {} -> {}", stem, truncated);
+            truncated
+        }
+        None => stem,
+    }
+}
+
+/// 截断文件名主干的实现，无需截断时返回 None
+fn fit_file_stem_with(
+    parent: &std::path::Path,
+    stem: &str,
+    suffix_bytes: usize,
+    max_filename_bytes: usize,
+    max_path_bytes: usize,
+) -> Option<String> {
+    // 截断后至少保留的字节数，路径过深时宁可超出限制也不生成无法辨认的文件名
+    const MIN_STEM_BYTES: usize = 16;
+
+    let (dir, name) = match stem.rfind(std::path::is_separator) {
+        Some(index) => stem.split_at(index + 1),
+        None => ("", stem),
+    };
+    let mut limit = usize::MAX;
+    if max_filename_bytes > 0 {
+        limit = limit.min(max_filename_bytes.saturating_sub(suffix_bytes));
+    }
+    if max_path_bytes > 0 {
+        // 父路径与文件名之间还有一个分隔符
+        let used = parent.as_os_str().len() + 1 + dir.len() + suffix_bytes;
+        limit = limit.min(max_path_bytes.saturating_sub(used));
+    }
+    if name.len() <= limit {
+        return None;
+    }
+    if limit < MIN_STEM_BYTES {
+        warn!(
+            "目录层级过深，文件名无法满足路径长度限制，仅截断到 {} 字节: {}",
+            MIN_STEM_BYTES,
+            parent.display()
+        );
+        limit = MIN_STEM_BYTES;
+        if name.len() <= limit {
+            return None;
+        }
+    }
+    Some(format!("{}{}", dir, truncate_bytes(name, limit)))
+}
+
+/// 将全角字符与常见的中文标点转写为 ASCII，其余非 ASCII 字符替换为 `replacement`，`keep` 中的片段原样保留
+fn transliterate_ascii(input: &str, keep: &[&str], replacement: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if let Some(token) = keep.iter().find(|token| rest.starts_with(*token)) {
+            output.push_str(token);
+            rest = &rest[token.len()..];
+            continue;
+        }
+        match c {
+            c if c.is_ascii() => output.push(c),
+            // 全角 ASCII 字符与半角字符一一对应
+            '\u{FF01}'..='\u{FF5E}' => output.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or('_')),
+            '\u{3000}' => output.push(' '),
+            '、' => output.push(','),
+            '。' | '·' | '・' => output.push('.'),
+            '‘' | '’' | '“' | '”' => output.push('\''),
+            '—' | '–' => output.push('-'),
+            '〜' => output.push('~'),
+            '…' => output.push_str("..."),
+            _ => output.push_str(replacement),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{filenamify, filenamify_with_options};
+    use super::{
+        filenamify, filenamify_with_options, filenamify_with_policy, fit_file_stem_with, truncate_path_segments,
+    };
+    use crate::config::FilenamePolicy;
 
     #[test]
     fn test_filenamify() {
@@ -200,4 +343,69 @@ mod tests {
             "UP主名__UNIX_SEP__[分身_ドッペルゲンガー]"
         );
     }
+
+    #[test]
+    fn test_filenamify_policies() {
+        let input = "【合集】标题：副标题（完整版）";
+        assert_eq!(
+            filenamify_with_policy(input, false, FilenamePolicy::Default),
+            filenamify(input)
+        );
+        assert_eq!(
+            filenamify_with_policy(input, false, FilenamePolicy::PreserveUnicode),
+            "【合集】标题：副标题（完整版）"
+        );
+        assert_eq!(
+            filenamify_with_policy("Ｔｅｓｔ：【4K】Title？", false, FilenamePolicy::Ascii),
+            "Test-_4K_Title_"
+        );
+        assert_eq!(
+            filenamify_with_policy("中文 Title", false, FilenamePolicy::Ascii),
+            "_Title"
+        );
+        // 全角斜杠转写后仍会被替换，不会产生额外的路径层级
+        assert_eq!(filenamify_with_policy("a／b", false, FilenamePolicy::Ascii), "a_b");
+        // 模板分隔符在所有策略下都保持不变
+        for policy in FilenamePolicy::ALL {
+            assert_eq!(
+                filenamify_with_policy("UP主__UNIX_SEP__分身/ドッペル", true, policy)
+                    .matches("__UNIX_SEP__")
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn test_filename_truncation() {
+        use std::path::Path;
+
+        // 按字节截断，不破坏多字节字符
+        assert_eq!(truncate_path_segments("UP主/标题标题", 7), "UP主/标题");
+        assert_eq!(truncate_path_segments("UP主/标题标题", 0), "UP主/标题标题");
+        assert_eq!(truncate_path_segments("abc. def/x", 5), "abc/x");
+
+        let parent = Path::new("/media/bili/UP主");
+        assert_eq!(fit_file_stem_with(parent, "short", 18, 255, 0), None);
+        // 为扩展名预留空间
+        assert_eq!(
+            fit_file_stem_with(parent, &"a".repeat(250), 18, 255, 0),
+            Some("a".repeat(237))
+        );
+        // 完整路径长度限制：父路径 17 字节 + 分隔符 + 后缀 4 字节
+        assert_eq!(
+            fit_file_stem_with(parent, &"b".repeat(100), 4, 255, 60),
+            Some("b".repeat(38))
+        );
+        // 只截断最后一段
+        assert_eq!(
+            fit_file_stem_with(parent, &format!("Season 1/{}", "c".repeat(100)), 4, 50, 0),
+            Some(format!("Season 1/{}", "c".repeat(46)))
+        );
+        // 路径过深时至少保留一部分文件名
+        assert_eq!(
+            fit_file_stem_with(parent, &"d".repeat(100), 4, 255, 20),
+            Some("d".repeat(16))
+        );
+    }
 }
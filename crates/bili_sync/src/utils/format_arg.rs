@@ -376,6 +376,7 @@ mod tests {
             show_season_type: None,
             actors: None,
             auto_download: false,
+            user_tags: None,
         };
 
         // 测试使用API标题的情况
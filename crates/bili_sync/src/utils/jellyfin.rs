@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use tracing::{info, warn};
+
+/// 媒体库刷新请求的超时时间（秒）
+const REFRESH_TIMEOUT: u64 = 30;
+
+/// 拼接 Jellyfin 媒体库刷新接口地址
+fn refresh_url(base_url: &str) -> Result<reqwest::Url> {
+    let url = format!("{}/Library/Refresh", base_url.trim().trim_end_matches('/'));
+    reqwest::Url::parse(&url).with_context(|| format!("无效的 Jellyfin 地址: {}", base_url))
+}
+
+/// 请求 Jellyfin 扫描所有媒体库
+pub async fn refresh_library(base_url: &str, api_key: &str) -> Result<()> {
+    let url = refresh_url(base_url)?;
+    Client::builder()
+        .timeout(Duration::from_secs(REFRESH_TIMEOUT))
+        .build()?
+        .post(url.clone())
+        .header("X-Emby-Token", api_key)
+        .send()
+        .await
+        .with_context(|| format!("请求 {} 失败", url))?
+        .error_for_status()
+        .context("Jellyfin 返回错误状态")?;
+    Ok(())
+}
+
+/// 配置了 Jellyfin 地址与 API 密钥时触发媒体库刷新，失败只记录日志
+pub async fn refresh_library_if_configured() {
+    let (url, api_key) = crate::config::with_config(|bundle| {
+        (
+            bundle.config.jellyfin_url.clone(),
+            bundle.config.jellyfin_api_key.clone(),
+        )
+    });
+    let (Some(url), Some(api_key)) = (url, api_key) else {
+        return;
+    };
+    match refresh_library(&url, &api_key).await {
+        Ok(()) => info!("已通知 Jellyfin 刷新媒体库"),
+        Err(e) => warn!("通知 Jellyfin 刷新媒体库失败: {:#}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_url() {
+        assert_eq!(
+            refresh_url("http://127.0.0.1:8096/").unwrap().as_str(),
+            "http://127.0.0.1:8096/Library/Refresh"
+        );
+        assert_eq!(
+            refresh_url(" https://media.example.com/jellyfin ").unwrap().as_str(),
+            "https://media.example.com/jellyfin/Library/Refresh"
+        );
+        assert!(refresh_url("not a url").is_err());
+    }
+}
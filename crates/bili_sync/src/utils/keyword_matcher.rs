@@ -0,0 +1,86 @@
+//! 关键词匹配，标题过滤与弹幕屏蔽共用：普通模式为不区分大小写的子串匹配，正则模式同样不区分大小写
+use anyhow::{Context, Result};
+use regex::Regex;
+
+pub enum KeywordMatcher {
+    /// 不区分大小写的子串匹配，关键词已转为小写
+    Keyword(String),
+    Regex(Regex),
+}
+
+impl KeywordMatcher {
+    /// `lowercase_text` 为 `text` 转为小写后的结果，匹配多个关键词时只需转换一次
+    pub fn is_match(&self, text: &str, lowercase_text: &str) -> bool {
+        match self {
+            Self::Keyword(keyword) => lowercase_text.contains(keyword.as_str()),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// 用户填写的关键词或正则表达式
+    pub fn term(&self) -> &str {
+        match self {
+            Self::Keyword(keyword) => keyword,
+            Self::Regex(regex) => regex.as_str().trim_start_matches("(?i)"),
+        }
+    }
+}
+
+/// 构建匹配器，忽略空白的关键词，无效的正则记录警告后忽略，`usage` 用于警告信息中标明用途
+pub fn build_matchers(terms: &[String], regex_mode: bool, usage: &str) -> Vec<KeywordMatcher> {
+    terms
+        .iter()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+        .filter_map(|term| {
+            if !regex_mode {
+                return Some(KeywordMatcher::Keyword(term.to_lowercase()));
+            }
+            match build_regex(term) {
+                Ok(regex) => Some(KeywordMatcher::Regex(regex)),
+                Err(e) => {
+                    warn!("{}正则无效，已忽略: {:#}", usage, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn build_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("(?i){}", pattern)).with_context(|| format!("无法解析正则表达式: {}", pattern))
+}
+
+/// 校验正则模式下的关键词是否都能被正确解析
+pub fn validate_terms(terms: &[String], regex_mode: bool) -> Result<()> {
+    if regex_mode {
+        for pattern in terms.iter().map(|term| term.trim()).filter(|term| !term.is_empty()) {
+            build_regex(pattern)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_matcher() {
+        let terms = vec!["HaHa".to_string(), " ".to_string()];
+        let matchers = build_matchers(&terms, false, "测试");
+        assert_eq!(matchers.len(), 1);
+        assert!(matchers[0].is_match("Hahaha", "hahaha"));
+        assert_eq!(matchers[0].term(), "haha");
+
+        let terms = vec![r"^\d+$".to_string(), "(".to_string()];
+        let matchers = build_matchers(&terms, true, "测试");
+        assert_eq!(matchers.len(), 1);
+        assert!(matchers[0].is_match("23333", "23333"));
+        assert!(!matchers[0].is_match("2333 哈哈", "2333 哈哈"));
+        assert_eq!(matchers[0].term(), r"^\d+$");
+
+        assert!(validate_terms(&terms, true).is_err());
+        assert!(validate_terms(&terms, false).is_ok());
+    }
+}
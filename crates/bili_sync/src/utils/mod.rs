@@ -1,21 +1,38 @@
 pub mod bangumi_cache;
 pub mod bangumi_name_extractor;
 pub mod convert;
+pub mod cover;
+pub mod dedup;
+pub mod empty_dirs;
+pub mod ffmpeg;
 pub mod file_logger;
 pub mod filenamify;
 pub mod format_arg;
+pub mod jellyfin;
+pub mod keyword_matcher;
 pub mod model;
 pub mod nfo;
 pub mod notification;
 mod notification_bark;
+mod notification_discord;
 mod notification_serverchan;
+mod notification_telegram;
+mod notification_webhook;
+pub mod page_files;
+pub mod playlist;
 pub mod scan_collector;
+pub mod scan_history;
 pub mod scan_id_tracker;
+pub mod scan_report;
+pub mod season_title_cache;
 pub mod signal;
 pub mod status;
 pub mod submission_checkpoint;
 pub mod task_notifier;
 pub mod time_format;
+pub mod title_filter;
+pub mod trash;
+pub mod webdav;
 
 use std::fmt;
 use tracing::{Event, Subscriber};
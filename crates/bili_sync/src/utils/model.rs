@@ -1,14 +1,14 @@
 use anyhow::{Context, Result};
 use bili_sync_entity::*;
 use sea_orm::entity::prelude::*;
-use sea_orm::sea_query::{OnConflict, SimpleExpr};
-use sea_orm::DatabaseTransaction;
-use std::collections::HashSet;
+use sea_orm::sea_query::{Expr, OnConflict, SimpleExpr};
+use sea_orm::{DatabaseTransaction, QuerySelect, TransactionTrait};
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, info};
 
 use crate::adapter::{VideoSource, VideoSourceEnum};
 use crate::bilibili::{PageInfo, VideoInfo};
-use crate::utils::status::STATUS_COMPLETED;
+use crate::utils::status::{PageStatus, VideoStatus, STATUS_COMPLETED, STATUS_OK};
 
 /// 从 VideoInfo 中提取 BVID
 fn extract_bvid(video_info: &VideoInfo) -> String {
@@ -768,3 +768,107 @@ pub async fn update_pages_model(pages: Vec<page::ActiveModel>, connection: &Data
 
     Ok(())
 }
+
+/// 重新生成元数据时需要重置的视频子任务：视频封面、视频信息、Up 主头像、Up 主信息
+pub const METADATA_VIDEO_SUBTASKS: [usize; 4] = [0, 1, 2, 3];
+
+/// 重新生成元数据时需要重置的分页子任务：视频封面、视频信息、视频弹幕
+pub const METADATA_PAGE_SUBTASKS: [usize; 3] = [0, 2, 3];
+
+/// 将已完成的视频与分页的 NFO 子任务重置为未开始，使下一轮扫描重新生成 NFO，返回重置的视频数与分页数
+pub async fn reset_nfo_subtasks(connection: &DatabaseConnection) -> Result<(usize, usize)> {
+    reset_completed_subtasks(None, &[1, 3], &[2], connection).await
+}
+
+/// 将符合条件的视频与分页中已成功的指定子任务重置为未开始，不影响视频文件本身，返回重置的视频数与分页数
+///
+/// 分页有子任务被重置时，视频的"分P下载"子任务同时重置，确保下一轮扫描会处理这些分页
+pub async fn reset_completed_subtasks(
+    additional_expr: Option<SimpleExpr>,
+    video_offsets: &[usize],
+    page_offsets: &[usize],
+    connection: &DatabaseConnection,
+) -> Result<(usize, usize)> {
+    // 与 filter_unhandled_video_pages 的条件保持一致，避免重置后无法被处理
+    let mut condition = video::Column::Valid
+        .eq(true)
+        .and(video::Column::Deleted.eq(0))
+        .and(video::Column::AutoDownload.eq(true));
+    if let Some(additional_expr) = additional_expr {
+        condition = condition.and(additional_expr);
+    }
+    let pages: Vec<(i32, u32, i32)> = page::Entity::find()
+        .inner_join(video::Entity)
+        .filter(condition.clone())
+        .select_only()
+        .columns([page::Column::Id, page::Column::DownloadStatus, page::Column::VideoId])
+        .into_tuple()
+        .all(connection)
+        .await?;
+    let videos: Vec<(i32, u32)> = video::Entity::find()
+        .filter(condition)
+        .select_only()
+        .columns([video::Column::Id, video::Column::DownloadStatus])
+        .into_tuple()
+        .all(connection)
+        .await?;
+
+    let mut page_updates: HashMap<u32, Vec<i32>> = HashMap::new();
+    let mut reset_pages = 0;
+    let mut videos_with_reset_pages = HashSet::new();
+    for (id, download_status, video_id) in pages {
+        let mut status = PageStatus::from(download_status);
+        let mut changed = false;
+        for &offset in page_offsets {
+            if status.get(offset) == STATUS_OK {
+                status.set(offset, 0);
+                changed = true;
+            }
+        }
+        if changed {
+            page_updates.entry(status.into()).or_default().push(id);
+            videos_with_reset_pages.insert(video_id);
+            reset_pages += 1;
+        }
+    }
+
+    let mut video_updates: HashMap<u32, Vec<i32>> = HashMap::new();
+    let mut reset_videos = 0;
+    for (id, download_status) in videos {
+        let mut status = VideoStatus::from(download_status);
+        let mut changed = false;
+        for &offset in video_offsets {
+            if status.get(offset) == STATUS_OK {
+                status.set(offset, 0);
+                changed = true;
+            }
+        }
+        if videos_with_reset_pages.contains(&id) {
+            status.set(4, 0); // 分页有子任务需要重新执行，将"分P下载"重置为 0
+            changed = true;
+        }
+        if changed {
+            video_updates.entry(status.into()).or_default().push(id);
+            reset_videos += 1;
+        }
+    }
+
+    let txn = connection.begin().await?;
+    for (status, ids) in page_updates {
+        page::Entity::update_many()
+            .col_expr(page::Column::DownloadStatus, Expr::value(status))
+            .filter(page::Column::Id.is_in(ids))
+            .exec(&txn)
+            .await?;
+    }
+    for (status, ids) in video_updates {
+        video::Entity::update_many()
+            .col_expr(video::Column::DownloadStatus, Expr::value(status))
+            .filter(video::Column::Id.is_in(ids))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    Ok((reset_videos, reset_pages))
+}
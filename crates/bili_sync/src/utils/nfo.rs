@@ -1,14 +1,35 @@
+use crate::utils::filenamify::filenamify;
 use crate::utils::time_format::parse_time_string;
 use anyhow::Result;
 use bili_sync_entity::*;
 use chrono::NaiveDateTime;
+use handlebars::Handlebars;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use quick_xml::events::{BytesCData, BytesText};
 use quick_xml::writer::Writer;
 use quick_xml::Error;
+use serde::Serialize;
+use std::sync::Arc;
 use tokio::io::{AsyncWriteExt, BufWriter};
 
+use crate::bilibili::StaffInfo;
 use crate::config::{EmptyUpperStrategy, NFOConfig, NFOTimeType};
 
+const NFO_HEADER: &str = r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>
+"#;
+
+const NFO_TEMPLATE_NAME: &str = "nfo";
+
+/// 演员头像目录，位于 NFO 同级目录下
+pub const ACTORS_DIR: &str = ".actors";
+
+/// 季度文件夹中的 NFO 文件名
+pub const SEASON_NFO_FILE: &str = "season.nfo";
+
+/// 判断季度文件夹是否仍被视频占用时识别的视频文件扩展名
+const MEDIA_EXTENSIONS: [&str; 4] = ["mp4", "mkv", "flv", "strm"];
+
 #[allow(clippy::upper_case_acronyms)]
 pub enum NFO<'a> {
     Movie(Movie<'a>),
@@ -46,6 +67,7 @@ pub struct Movie<'a> {
     pub cover_url: &'a str,              // 封面图片URL
     pub fanart_url: Option<&'a str>,     // 背景图片URL
     pub upper_face_url: Option<&'a str>, // UP主头像URL（用于演员thumb）
+    pub staff: Vec<StaffInfo>,           // 创作团队（无 staff 信息时为UP主本人）
 }
 
 pub struct TVShow<'a> {
@@ -108,6 +130,31 @@ pub struct Episode<'a> {
     pub genres: Option<Vec<String>>, // 类型标签
     pub thumb_url: Option<&'a str>,  // 缩略图URL
     pub fanart_url: Option<&'a str>, // 背景图URL
+    pub upper_name: &'a str,         // UP主名称（自定义模板中作为studio）
+    pub actors_info: Option<String>, // 番剧演员信息字符串（从API获取）
+    pub staff: Vec<StaffInfo>,       // 创作团队（无 staff 信息时为UP主本人）
+}
+
+/// 自定义 NFO 模板可用的变量
+#[derive(Serialize)]
+pub struct NFOTemplateContext<'a> {
+    /// 根元素名称：movie 或 episodedetails
+    pub root: &'static str,
+    pub title: &'a str,
+    pub original_title: &'a str,
+    pub plot: &'a str,
+    pub bvid: &'a str,
+    /// 发布日期，格式为 YYYY-MM-DD
+    pub pubdate: String,
+    pub year: String,
+    /// UP主名称，为空时使用默认制作公司
+    pub studio: &'a str,
+    pub genres: &'a [String],
+    pub actors: &'a [StaffInfo],
+    pub season: Option<i32>,
+    pub episode: Option<i32>,
+    /// 时长（分钟）
+    pub runtime: Option<i32>,
 }
 
 pub struct Season<'a> {
@@ -146,27 +193,44 @@ pub struct Season<'a> {
 impl NFO<'_> {
     pub async fn generate_nfo(self) -> Result<String> {
         let config = crate::config::reload_config();
-        let mut buffer = r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>
-"#
-        .as_bytes()
-        .to_vec();
+        let tag_limit = tag_element_limit(config.nfo_include_tags, config.nfo_max_tags);
+        if let Some(template) = config.nfo_template.as_deref().filter(|t| !t.trim().is_empty()) {
+            if let Some(rendered) = self.render_template(template, &config.nfo_config)? {
+                return Ok(rendered);
+            }
+        }
+        let mut buffer = NFO_HEADER.as_bytes().to_vec();
         let mut tokio_buffer = BufWriter::new(&mut buffer);
         let writer = Writer::new_with_indent(&mut tokio_buffer, b' ', 4);
         match self {
             NFO::Movie(movie) => {
-                Self::write_movie_nfo(writer, movie, &config.nfo_config).await?;
+                Self::write_movie_nfo(
+                    writer,
+                    movie,
+                    &config.nfo_config,
+                    config.nfo_download_actor_images,
+                    tag_limit,
+                )
+                .await?;
             }
             NFO::TVShow(tvshow) => {
-                Self::write_tvshow_nfo(writer, tvshow, &config.nfo_config).await?;
+                Self::write_tvshow_nfo(writer, tvshow, &config.nfo_config, tag_limit).await?;
             }
             NFO::Upper(upper) => {
                 Self::write_upper_nfo(writer, upper).await?;
             }
             NFO::Episode(episode) => {
-                Self::write_episode_nfo(writer, episode, &config.nfo_config).await?;
+                Self::write_episode_nfo(
+                    writer,
+                    episode,
+                    &config.nfo_config,
+                    config.nfo_download_actor_images,
+                    tag_limit,
+                )
+                .await?;
             }
             NFO::Season(season) => {
-                Self::write_season_nfo(writer, season, &config.nfo_config).await?;
+                Self::write_season_nfo(writer, season, &config.nfo_config, tag_limit).await?;
             }
         }
         tokio_buffer.flush().await?;
@@ -177,6 +241,8 @@ impl NFO<'_> {
         mut writer: Writer<&mut BufWriter<&mut Vec<u8>>>,
         movie: Movie<'_>,
         config: &NFOConfig,
+        local_actor_images: bool,
+        tag_limit: Option<usize>,
     ) -> Result<()> {
         // 验证数据有效性
         if !Self::validate_nfo_data(movie.name, movie.bvid, movie.upper_name) {
@@ -280,13 +346,8 @@ impl NFO<'_> {
                     .await?;
 
                 // 类型标签
-                if let Some(tags) = movie.tags {
-                    for tag in tags {
-                        writer
-                            .create_element("genre")
-                            .write_text_content_async(BytesText::new(&tag))
-                            .await?;
-                    }
+                if let Some(ref tags) = movie.tags {
+                    Self::write_genres_and_tags(writer, tags, tag_limit).await?;
                 }
 
                 // 为番剧剧场版添加默认类型标签
@@ -381,6 +442,9 @@ impl NFO<'_> {
                                 })
                                 .await?;
                         }
+                    } else if local_actor_images && !movie.staff.is_empty() {
+                        // 下载了演员头像时，列出创作团队并引用本地头像
+                        Self::write_staff_actors(writer, &movie.staff).await?;
                     } else {
                         // 备选：使用UP主信息作为创作者
                         let actor_info = Self::get_actor_info(movie.upper_id, movie.upper_name, config);
@@ -445,14 +509,18 @@ impl NFO<'_> {
                 if !movie.cover_url.is_empty() {
                     writer
                         .create_element("thumb")
-                        .write_text_content_async(BytesText::new(movie.cover_url))
+                        .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                            movie.cover_url,
+                        )))
                         .await?;
                     // 只有在真正有fanart_url时才添加fanart字段
                     if let Some(fanart_url) = movie.fanart_url {
                         if !fanart_url.is_empty() {
                             writer
                                 .create_element("fanart")
-                                .write_text_content_async(BytesText::new(fanart_url))
+                                .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                                    fanart_url,
+                                )))
                                 .await?;
                         }
                     }
@@ -468,6 +536,7 @@ impl NFO<'_> {
         mut writer: Writer<&mut BufWriter<&mut Vec<u8>>>,
         tvshow: TVShow<'_>,
         config: &NFOConfig,
+        tag_limit: Option<usize>,
     ) -> Result<()> {
         // 验证数据有效性
         if !Self::validate_nfo_data(tvshow.name, tvshow.bvid, tvshow.upper_name) {
@@ -483,24 +552,7 @@ impl NFO<'_> {
             .create_element("tvshow")
             .write_inner_content_async::<_, _, Error>(|writer| async move {
                 // 标题信息
-                let (display_title, original_title) = if Self::is_bangumi_video(tvshow.category) {
-                    // 对于番剧，尝试提取番剧名称作为主标题
-                    if let Some(bangumi_title) = Self::extract_bangumi_title_from_full_name(tvshow.name) {
-                        let cfg = crate::config::reload_config();
-                        let normalized = if cfg.bangumi_use_season_structure {
-                            crate::utils::bangumi_name_extractor::BangumiNameExtractor::normalize_series_name(
-                                &bangumi_title,
-                            )
-                        } else {
-                            bangumi_title
-                        };
-                        (normalized, tvshow.name.to_string())
-                    } else {
-                        (tvshow.name.to_string(), tvshow.original_title.to_string())
-                    }
-                } else {
-                    (tvshow.name.to_string(), tvshow.original_title.to_string())
-                };
+                let (display_title, original_title) = Self::tvshow_titles(&tvshow);
 
                 writer
                     .create_element("title")
@@ -613,13 +665,8 @@ impl NFO<'_> {
                 }
 
                 // 类型标签
-                if let Some(tags) = tvshow.tags {
-                    for tag in tags {
-                        writer
-                            .create_element("genre")
-                            .write_text_content_async(BytesText::new(&tag))
-                            .await?;
-                    }
+                if let Some(ref tags) = tvshow.tags {
+                    Self::write_genres_and_tags(writer, tags, tag_limit).await?;
                 }
 
                 // 国家信息
@@ -779,14 +826,18 @@ impl NFO<'_> {
                 if !tvshow.cover_url.is_empty() {
                     writer
                         .create_element("thumb")
-                        .write_text_content_async(BytesText::new(tvshow.cover_url))
+                        .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                            tvshow.cover_url,
+                        )))
                         .await?;
                     // 只有在真正有fanart_url时才添加fanart字段
                     if let Some(fanart_url) = tvshow.fanart_url {
                         if !fanart_url.is_empty() {
                             writer
                                 .create_element("fanart")
-                                .write_text_content_async(BytesText::new(fanart_url))
+                                .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                                    fanart_url,
+                                )))
                                 .await?;
                         }
                     }
@@ -837,6 +888,8 @@ impl NFO<'_> {
         mut writer: Writer<&mut BufWriter<&mut Vec<u8>>>,
         episode: Episode<'_>,
         config: &NFOConfig,
+        local_actor_images: bool,
+        tag_limit: Option<usize>,
     ) -> Result<()> {
         writer
             .create_element("episodedetails")
@@ -898,12 +951,7 @@ impl NFO<'_> {
 
                 // 类型标签
                 if let Some(ref genres) = episode.genres {
-                    for genre in genres {
-                        writer
-                            .create_element("genre")
-                            .write_text_content_async(BytesText::new(genre))
-                            .await?;
-                    }
+                    Self::write_genres_and_tags(writer, genres, tag_limit).await?;
                 }
 
                 // 为番剧添加默认类型标签
@@ -993,6 +1041,15 @@ impl NFO<'_> {
                         .await?;
                 }
 
+                // 演员信息：番剧使用缓存的演员表，下载了演员头像时列出创作团队
+                if config.include_actor_info {
+                    if let Some(ref actors_str) = episode.actors_info {
+                        Self::write_parsed_actors(writer, actors_str).await?;
+                    } else if local_actor_images {
+                        Self::write_staff_actors(writer, &episode.staff).await?;
+                    }
+                }
+
                 // 缩略图（本地文件路径优先）
                 if let Some(thumb_url) = episode.thumb_url {
                     writer
@@ -1005,7 +1062,7 @@ impl NFO<'_> {
                 if let Some(fanart_url) = episode.fanart_url {
                     writer
                         .create_element("fanart")
-                        .write_text_content_async(BytesText::new(fanart_url))
+                        .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(fanart_url)))
                         .await?;
                 }
 
@@ -1019,6 +1076,7 @@ impl NFO<'_> {
         mut writer: Writer<&mut BufWriter<&mut Vec<u8>>>,
         season: Season<'_>,
         config: &NFOConfig,
+        tag_limit: Option<usize>,
     ) -> Result<()> {
         // 验证数据有效性
         if !Self::validate_nfo_data(season.name, season.bvid, season.upper_name) {
@@ -1157,13 +1215,8 @@ impl NFO<'_> {
                 }
 
                 // 类型标签
-                if let Some(tags) = season.tags {
-                    for tag in tags {
-                        writer
-                            .create_element("genre")
-                            .write_text_content_async(BytesText::new(&tag))
-                            .await?;
-                    }
+                if let Some(ref tags) = season.tags {
+                    Self::write_genres_and_tags(writer, tags, tag_limit).await?;
                 }
 
                 // 国家信息
@@ -1316,14 +1369,18 @@ impl NFO<'_> {
                 if !season.cover_url.is_empty() {
                     writer
                         .create_element("thumb")
-                        .write_text_content_async(BytesText::new(season.cover_url))
+                        .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                            season.cover_url,
+                        )))
                         .await?;
                     // 只有在真正有fanart_url时才添加fanart字段
                     if let Some(fanart_url) = season.fanart_url {
                         if !fanart_url.is_empty() {
                             writer
                                 .create_element("fanart")
-                                .write_text_content_async(BytesText::new(fanart_url))
+                                .write_text_content_async(BytesText::new(&crate::utils::cover::sized_cover_url(
+                                    fanart_url,
+                                )))
                                 .await?;
                         }
                     }
@@ -1335,6 +1392,198 @@ impl NFO<'_> {
         Ok(())
     }
 
+    /// 视频标签完整写入 <genre>；开启 nfo_include_tags 时再按上限额外写入 <tag>
+    async fn write_genres_and_tags(
+        writer: &mut Writer<&mut BufWriter<&mut Vec<u8>>>,
+        tags: &[String],
+        tag_limit: Option<usize>,
+    ) -> std::result::Result<(), Error> {
+        for tag in tags {
+            writer
+                .create_element("genre")
+                .write_text_content_async(BytesText::new(tag))
+                .await?;
+        }
+        if let Some(limit) = tag_limit {
+            for tag in tags.iter().take(limit) {
+                writer
+                    .create_element("tag")
+                    .write_text_content_async(BytesText::new(tag))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 写入从 API 获取的演员表（格式见 parse_actors_string）
+    async fn write_parsed_actors(
+        writer: &mut Writer<&mut BufWriter<&mut Vec<u8>>>,
+        actors_str: &str,
+    ) -> std::result::Result<(), Error> {
+        let actors = Self::parse_actors_string(actors_str);
+        for (index, (character, actor)) in actors.iter().enumerate() {
+            writer
+                .create_element("actor")
+                .write_inner_content_async::<_, _, Error>(|writer| async move {
+                    writer
+                        .create_element("name")
+                        .write_text_content_async(BytesText::new(actor))
+                        .await?;
+                    writer
+                        .create_element("role")
+                        .write_text_content_async(BytesText::new(character))
+                        .await?;
+                    writer
+                        .create_element("order")
+                        .write_text_content_async(BytesText::new(&(index + 1).to_string()))
+                        .await?;
+                    Ok(writer)
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 使用视频源单独设置的时间类型覆盖播出时间，未设置时保持全局配置（番剧为首播时间）
+    pub fn with_time_type(mut self, video: &video::Model, time_type: Option<&NFOTimeType>) -> Self {
+        let Some(time_type) = time_type else {
+            return self;
+        };
+        let aired = nfo_aired_time(video, time_type);
+        match &mut self {
+            NFO::Movie(movie) => {
+                movie.aired = aired;
+                movie.premiered = aired;
+            }
+            NFO::TVShow(tvshow) => {
+                tvshow.aired = aired;
+                tvshow.premiered = aired;
+            }
+            NFO::Season(season) => {
+                season.aired = aired;
+                season.premiered = aired;
+            }
+            NFO::Episode(episode) => episode.aired = Some(aired),
+            NFO::Upper(_) => {}
+        }
+        self
+    }
+
+    /// 写入创作团队作为演员，头像引用 .actors 目录下的本地图片
+    async fn write_staff_actors(
+        writer: &mut Writer<&mut BufWriter<&mut Vec<u8>>>,
+        staff: &[StaffInfo],
+    ) -> std::result::Result<(), Error> {
+        for (index, member) in staff.iter().enumerate() {
+            writer
+                .create_element("actor")
+                .write_inner_content_async::<_, _, Error>(|writer| async move {
+                    writer
+                        .create_element("name")
+                        .write_text_content_async(BytesText::new(&member.name))
+                        .await?;
+                    writer
+                        .create_element("role")
+                        .write_text_content_async(BytesText::new(&member.title))
+                        .await?;
+                    if !member.face.is_empty() {
+                        writer
+                            .create_element("thumb")
+                            .write_text_content_async(BytesText::new(&actor_image_path(&member.name)))
+                            .await?;
+                    }
+                    writer
+                        .create_element("order")
+                        .write_text_content_async(BytesText::new(&(index + 1).to_string()))
+                        .await?;
+                    Ok(writer)
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 使用自定义模板渲染 movie / episodedetails，其它类型返回 None 继续使用内置格式
+    fn render_template(&self, template: &str, config: &NFOConfig) -> Result<Option<String>> {
+        let empty_genres = Vec::new();
+        let context = match self {
+            NFO::Movie(movie) => NFOTemplateContext {
+                root: "movie",
+                title: movie.name,
+                original_title: movie.original_title,
+                plot: movie.intro,
+                bvid: movie.bvid,
+                pubdate: movie.aired.format("%Y-%m-%d").to_string(),
+                year: movie.aired.format("%Y").to_string(),
+                studio: Some(movie.upper_name)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&config.default_studio),
+                genres: movie.tags.as_ref().unwrap_or(&empty_genres),
+                actors: &movie.staff,
+                season: None,
+                episode: None,
+                runtime: movie.duration,
+            },
+            NFO::Episode(episode) => NFOTemplateContext {
+                root: "episodedetails",
+                title: episode.name,
+                original_title: episode.original_title,
+                plot: episode.plot.unwrap_or_default(),
+                bvid: episode.bvid,
+                pubdate: episode
+                    .aired
+                    .map(|aired| aired.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                year: episode
+                    .aired
+                    .map(|aired| aired.format("%Y").to_string())
+                    .unwrap_or_default(),
+                studio: Some(episode.upper_name)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&config.default_studio),
+                genres: episode.genres.as_ref().unwrap_or(&empty_genres),
+                actors: &episode.staff,
+                season: Some(episode.season),
+                episode: Some(episode.episode_number),
+                runtime: episode.duration,
+            },
+            _ => return Ok(None),
+        };
+        let rendered = cached_template_engine(template)?.render(NFO_TEMPLATE_NAME, &context)?;
+        Ok(Some(format!("{}{}", NFO_HEADER, rendered.trim_start())))
+    }
+
+    /// 剧集的显示标题与原始标题，番剧会尝试提取番剧名称作为主标题
+    fn tvshow_titles(tvshow: &TVShow<'_>) -> (String, String) {
+        if Self::is_bangumi_video(tvshow.category) {
+            if let Some(bangumi_title) = Self::extract_bangumi_title_from_full_name(tvshow.name) {
+                let cfg = crate::config::reload_config();
+                let normalized = if cfg.bangumi_use_season_structure {
+                    crate::utils::bangumi_name_extractor::BangumiNameExtractor::normalize_series_name(&bangumi_title)
+                } else {
+                    bangumi_title
+                };
+                return (normalized, tvshow.name.to_string());
+            }
+        }
+        (tvshow.name.to_string(), tvshow.original_title.to_string())
+    }
+
+    /// 生成 Plex 的 `.plexmatch` 内容，仅剧集类型有意义
+    pub fn plexmatch(&self) -> Option<String> {
+        let NFO::TVShow(tvshow) = self else {
+            return None;
+        };
+        let (title, _) = Self::tvshow_titles(tvshow);
+        let mut content = format!("title: {}\nyear: {}\n", title, tvshow.aired.format("%Y"));
+        if let Some(season_id) = tvshow.season_id.as_deref().filter(|id| !id.is_empty()) {
+            content.push_str(&format!("# bilibili season_id: {}\n", season_id));
+        } else if !tvshow.bvid.is_empty() {
+            content.push_str(&format!("# bilibili bvid: {}\n", tvshow.bvid));
+        }
+        Some(content)
+    }
+
     #[inline]
     fn format_plot(bvid: &str, intro: &str) -> String {
         format!(
@@ -1552,6 +1801,15 @@ impl NFO<'_> {
     }
 }
 
+/// 按时间类型选取视频在 NFO 中的播出时间
+pub fn nfo_aired_time(video: &video::Model, time_type: &NFOTimeType) -> NaiveDateTime {
+    match time_type {
+        NFOTimeType::FavTime => video.favtime,
+        NFOTimeType::PubTime => video.pubtime,
+        NFOTimeType::CTime => video.ctime,
+    }
+}
+
 impl<'a> From<&'a video::Model> for Movie<'a> {
     fn from(video: &'a video::Model) -> Self {
         // 使用动态配置而非静态CONFIG
@@ -1565,10 +1823,7 @@ impl<'a> From<&'a video::Model> for Movie<'a> {
             &video.name
         };
 
-        let aired_time = match config.nfo_config.time_type {
-            NFOTimeType::FavTime => video.favtime,
-            NFOTimeType::PubTime => video.pubtime,
-        };
+        let aired_time = nfo_aired_time(video, &config.nfo_config.time_type);
 
         // 提取标语/副标题
         let tagline = if video.show_season_type == Some(2) {
@@ -1642,6 +1897,7 @@ impl<'a> From<&'a video::Model> for Movie<'a> {
             } else {
                 None
             },
+            staff: staff_from_video(video),
         }
     }
 }
@@ -1659,10 +1915,7 @@ impl<'a> From<&'a video::Model> for TVShow<'a> {
             &video.name
         };
 
-        let aired_time = match config.nfo_config.time_type {
-            NFOTimeType::FavTime => video.favtime,
-            NFOTimeType::PubTime => video.pubtime,
-        };
+        let aired_time = nfo_aired_time(video, &config.nfo_config.time_type);
 
         // 提取标语/副标题
         let tagline = if video.show_season_type == Some(2) {
@@ -1815,18 +2068,12 @@ impl<'a> TVShow<'a> {
         let aired_time = if let Some(ref publish_time) = season_info.publish_time {
             // 使用统一的时间解析函数
             {
-                let fallback_time = match config.nfo_config.time_type {
-                    crate::config::NFOTimeType::FavTime => video.favtime,
-                    crate::config::NFOTimeType::PubTime => video.pubtime,
-                };
+                let fallback_time = nfo_aired_time(video, &config.nfo_config.time_type);
                 parse_time_string(publish_time).unwrap_or(fallback_time)
             }
         } else {
             // 没有API时间，使用配置的时间类型
-            match config.nfo_config.time_type {
-                crate::config::NFOTimeType::FavTime => video.favtime,
-                crate::config::NFOTimeType::PubTime => video.pubtime,
-            }
+            nfo_aired_time(video, &config.nfo_config.time_type)
         };
 
         // 使用API提供的信息
@@ -1937,6 +2184,9 @@ impl<'a> From<&'a page::Model> for Episode<'a> {
             genres: None,                              // 无类型标签
             thumb_url: None,                           // 暂不设置本地路径
             fanart_url: None,                          // 暂不设置本地路径
+            upper_name: "",                            // 分页没有UP主信息
+            actors_info: None,                         // 分页没有演员信息
+            staff: Vec::new(),                         // 分页没有创作团队信息
         }
     }
 }
@@ -1979,6 +2229,9 @@ impl<'a> Episode<'a> {
                 .and_then(|tags| serde_json::from_value(tags.clone()).ok()), // 从视频标签提取类型
             thumb_url: None,                                          // 暂不设置本地路径
             fanart_url: None,                                         // 暂不设置本地路径
+            upper_name: &video.upper_name,                            // UP主名称
+            actors_info: video.actors.clone(),                        // 番剧演员信息
+            staff: staff_from_video(video),                           // 创作团队
         }
     }
 }
@@ -1996,10 +2249,7 @@ impl<'a> From<&'a video::Model> for Season<'a> {
             &video.name
         };
 
-        let aired_time = match config.nfo_config.time_type {
-            NFOTimeType::FavTime => video.favtime,
-            NFOTimeType::PubTime => video.pubtime,
-        };
+        let aired_time = nfo_aired_time(video, &config.nfo_config.time_type);
 
         // 提取标语/副标题
         let tagline = if video.show_season_type == Some(2) {
@@ -2081,6 +2331,29 @@ impl<'a> From<&'a video::Model> for Season<'a> {
 }
 
 impl<'a> Season<'a> {
+    /// 为启用Season结构的合集或多P视频创建Season，合集优先使用合集名称和封面
+    pub fn from_video_with_collection(
+        video: &'a video::Model,
+        collection_name: Option<&'a str>,
+        collection_cover: Option<&'a str>,
+    ) -> Self {
+        let mut season = Season::from(video);
+        // 合集和多P视频的Season结构固定为 Season 01
+        season.season_number = 1;
+
+        if let Some(name) = collection_name {
+            season.name = name;
+            season.original_title = name;
+            season.sorttitle = Some(name.to_string());
+        }
+
+        if let Some(cover) = collection_cover {
+            season.cover_url = cover;
+        }
+
+        season
+    }
+
     /// 从API获取的SeasonInfo创建带有完整元数据的Season
     pub fn from_season_info(video: &'a video::Model, season_info: &'a crate::workflow::SeasonInfo) -> Self {
         // 使用动态配置而非静态CONFIG
@@ -2090,18 +2363,12 @@ impl<'a> Season<'a> {
         let aired_time = if let Some(ref publish_time) = season_info.publish_time {
             // 使用统一的时间解析函数
             {
-                let fallback_time = match config.nfo_config.time_type {
-                    crate::config::NFOTimeType::FavTime => video.favtime,
-                    crate::config::NFOTimeType::PubTime => video.pubtime,
-                };
+                let fallback_time = nfo_aired_time(video, &config.nfo_config.time_type);
                 parse_time_string(publish_time).unwrap_or(fallback_time)
             }
         } else {
             // 没有API时间，使用配置的时间类型
-            match config.nfo_config.time_type {
-                crate::config::NFOTimeType::FavTime => video.favtime,
-                crate::config::NFOTimeType::PubTime => video.pubtime,
-            }
+            nfo_aired_time(video, &config.nfo_config.time_type)
         };
 
         // 使用API提供的信息
@@ -2173,6 +2440,96 @@ impl<'a> Season<'a> {
     }
 }
 
+/// 构建自定义 NFO 模板引擎，变量按 XML 规则转义，需原样输出时使用 {{{var}}}
+fn build_template_engine(template: &str) -> Result<Handlebars<'static>> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(false);
+    handlebars.register_escape_fn(|s| quick_xml::escape::escape(s).into_owned());
+    handlebars.register_template_string(NFO_TEMPLATE_NAME, template)?;
+    Ok(handlebars)
+}
+
+/// 模板内容及其编译后的模板引擎
+type CachedTemplateEngine = (String, Arc<Handlebars<'static>>);
+
+/// 最近一次编译的自定义 NFO 模板及其模板引擎，模板不变时直接复用，避免每次写入 NFO 都重新编译
+static NFO_TEMPLATE_ENGINE: Lazy<Mutex<Option<CachedTemplateEngine>>> = Lazy::new(Default::default);
+
+/// 获取自定义 NFO 模板对应的模板引擎，模板内容变化时重新编译
+fn cached_template_engine(template: &str) -> Result<Arc<Handlebars<'static>>> {
+    let mut cached = NFO_TEMPLATE_ENGINE.lock();
+    if let Some((cached_template, engine)) = cached.as_ref() {
+        if cached_template == template {
+            return Ok(engine.clone());
+        }
+    }
+    let engine = Arc::new(build_template_engine(template)?);
+    *cached = Some((template.to_string(), engine.clone()));
+    Ok(engine)
+}
+
+/// 检查自定义 NFO 模板能否被解析
+pub fn validate_nfo_template(template: &str) -> Result<()> {
+    build_template_engine(template).map(|_| ())
+}
+
+/// 季度文件夹中已没有任何视频文件时删除其中的 season.nfo，返回是否执行了删除
+///
+/// 多个视频共享同一季度文件夹（如统一模式的合集）时，只要还有其他视频就保留 season.nfo
+pub async fn remove_unused_season_nfo(season_dir: &std::path::Path) -> Result<bool> {
+    let nfo_path = season_dir.join(SEASON_NFO_FILE);
+    if !nfo_path.exists() {
+        return Ok(false);
+    }
+    let mut entries = tokio::fs::read_dir(season_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let is_media = entry
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| MEDIA_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if is_media {
+            return Ok(false);
+        }
+    }
+    tokio::fs::remove_file(&nfo_path).await?;
+    Ok(true)
+}
+
+/// 额外写入 <tag> 的数量：关闭时为 None，上限为 0 表示不限制
+fn tag_element_limit(include_tags: bool, max_tags: usize) -> Option<usize> {
+    match (include_tags, max_tags) {
+        (false, _) => None,
+        (true, 0) => Some(usize::MAX),
+        (true, max) => Some(max),
+    }
+}
+
+/// 演员头像相对于 NFO 所在目录的路径，如 `.actors/名字.jpg`
+pub fn actor_image_path(name: &str) -> String {
+    format!("{}/{}.jpg", ACTORS_DIR, filenamify(name).replace(' ', "_"))
+}
+
+/// 解析视频的创作团队，单人投稿没有 staff 信息时以UP主本人作为唯一成员
+pub fn staff_from_video(video: &video::Model) -> Vec<StaffInfo> {
+    let staff = video
+        .staff_info
+        .as_ref()
+        .and_then(|staff_info| serde_json::from_value::<Vec<StaffInfo>>(staff_info.clone()).ok())
+        .unwrap_or_default();
+    if !staff.is_empty() || video.upper_name.is_empty() {
+        return staff;
+    }
+    vec![StaffInfo {
+        mid: video.upper_id,
+        title: "UP主".to_string(),
+        name: video.upper_name.clone(),
+        face: video.upper_face.clone(),
+        follower: None,
+        label_style: None,
+    }]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2948,4 +3305,196 @@ mod tests {
 
         println!("NFO演员信息（UID和角色）测试通过");
     }
+
+    #[test]
+    fn test_plexmatch() {
+        let video = video::Model {
+            name: "合集名称".to_string(),
+            upper_name: "upper_name".to_string(),
+            bvid: "BV1nWcSeeEkV".to_string(),
+            favtime: chrono::NaiveDateTime::new(
+                chrono::NaiveDate::from_ymd_opt(2022, 2, 2).unwrap(),
+                chrono::NaiveTime::from_hms_opt(2, 2, 2).unwrap(),
+            ),
+            ..Default::default()
+        };
+
+        let plexmatch = NFO::TVShow((&video).into()).plexmatch().unwrap();
+        assert_eq!(
+            plexmatch,
+            "title: 合集名称\nyear: 2022\n# bilibili bvid: BV1nWcSeeEkV\n"
+        );
+        assert!(NFO::Movie((&video).into()).plexmatch().is_none());
+    }
+
+    #[test]
+    fn test_render_custom_template() {
+        let video = video::Model {
+            name: "标题 & 副标题".to_string(),
+            intro: "简介".to_string(),
+            upper_id: 1,
+            upper_name: "upper_name".to_string(),
+            upper_face: "https://example.com/face.jpg".to_string(),
+            bvid: "BV1nWcSeeEkV".to_string(),
+            favtime: chrono::NaiveDateTime::new(
+                chrono::NaiveDate::from_ymd_opt(2022, 2, 2).unwrap(),
+                chrono::NaiveTime::from_hms_opt(2, 2, 2).unwrap(),
+            ),
+            tags: Some(serde_json::json!(["tag1", "tag2"])),
+            ..Default::default()
+        };
+        let template =
+            "<{{root}}><title>{{title}}</title><studio>{{studio}}</studio><premiered>{{pubdate}}</premiered>\
+            {{#each genres}}<genre>{{this}}</genre>{{/each}}\
+            {{#each actors}}<actor><name>{{name}}</name><thumb>{{face}}</thumb></actor>{{/each}}</{{root}}>";
+
+        let rendered = NFO::Movie((&video).into())
+            .render_template(template, &NFOConfig::default())
+            .unwrap()
+            .unwrap();
+        assert!(rendered.starts_with(NFO_HEADER));
+        assert!(rendered.contains("<movie><title>标题 &amp; 副标题</title><studio>upper_name</studio>"));
+        assert!(rendered.contains("<premiered>2022-02-02</premiered>"));
+        assert!(rendered.contains("<genre>tag1</genre><genre>tag2</genre>"));
+        assert!(rendered.contains("<actor><name>upper_name</name><thumb>https://example.com/face.jpg</thumb></actor>"));
+
+        let upper = NFO::Upper((&video).into());
+        assert!(upper
+            .render_template(template, &NFOConfig::default())
+            .unwrap()
+            .is_none());
+
+        assert!(validate_nfo_template(template).is_ok());
+        assert!(validate_nfo_template("<movie>{{#each actors}}</movie>").is_err());
+    }
+
+    #[test]
+    fn test_cached_template_engine() {
+        let template = "<movie><title>{{title}}</title></movie>";
+        let engine = cached_template_engine(template).unwrap();
+        assert!(Arc::ptr_eq(&engine, &cached_template_engine(template).unwrap()));
+        // 模板内容变化后重新编译，无法解析的模板返回错误
+        let changed = cached_template_engine("<movie><plot>{{plot}}</plot></movie>").unwrap();
+        assert!(!Arc::ptr_eq(&engine, &changed));
+        assert!(cached_template_engine("<movie>{{#each actors}}</movie>").is_err());
+    }
+
+    #[test]
+    fn test_staff_and_actor_image_path() {
+        let video = video::Model {
+            upper_id: 1,
+            upper_name: "upper_name".to_string(),
+            upper_face: "https://example.com/face.jpg".to_string(),
+            ..Default::default()
+        };
+        let staff = staff_from_video(&video);
+        assert_eq!(staff.len(), 1);
+        assert_eq!(staff[0].name, "upper_name");
+        assert_eq!(staff[0].face, "https://example.com/face.jpg");
+
+        let video = video::Model {
+            staff_info: Some(serde_json::json!([
+                {"mid": 1, "title": "UP主", "name": "A B", "face": "https://example.com/a.jpg"},
+                {"mid": 2, "title": "剪辑", "name": "C/D", "face": "https://example.com/c.jpg"}
+            ])),
+            ..video
+        };
+        let staff = staff_from_video(&video);
+        assert_eq!(staff.len(), 2);
+        assert_eq!(actor_image_path(&staff[0].name), ".actors/A_B.jpg");
+        assert!(!actor_image_path(&staff[1].name).contains("C/D"));
+    }
+
+    #[tokio::test]
+    async fn test_nfo_tags_and_limit() {
+        let video = video::Model {
+            name: "name".to_string(),
+            upper_name: "upper_name".to_string(),
+            bvid: "BV1nWcSeeEkV".to_string(),
+            tags: Some(serde_json::json!(["tag1", "tag2", "tag3"])),
+            ..Default::default()
+        };
+        let movie_nfo = NFO::Movie((&video).into()).generate_nfo().await.unwrap();
+        assert!(movie_nfo.contains("<genre>tag3</genre>"));
+        assert!(!movie_nfo.contains("<tag>tag1</tag>"));
+
+        assert_eq!(tag_element_limit(false, 2), None);
+        assert_eq!(tag_element_limit(true, 0), Some(usize::MAX));
+        assert_eq!(tag_element_limit(true, 2), Some(2));
+
+        let mut buffer = Vec::new();
+        let mut tokio_buffer = BufWriter::new(&mut buffer);
+        let mut writer = Writer::new(&mut tokio_buffer);
+        let tags = ["tag1".to_string(), "tag2".to_string(), "tag3".to_string()];
+        NFO::write_genres_and_tags(&mut writer, &tags, Some(2)).await.unwrap();
+        tokio_buffer.flush().await.unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "<genre>tag1</genre><genre>tag2</genre><genre>tag3</genre><tag>tag1</tag><tag>tag2</tag>"
+        );
+    }
+
+    #[test]
+    fn test_nfo_time_type_override() {
+        let date = |day| {
+            chrono::NaiveDate::from_ymd_opt(2024, 1, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let video = video::Model {
+            name: "name".to_string(),
+            ctime: date(1),
+            pubtime: date(2),
+            favtime: date(3),
+            ..Default::default()
+        };
+        assert_eq!(NFOTimeType::parse("ctime"), Some(NFOTimeType::CTime));
+        assert_eq!(NFOTimeType::parse("order"), None);
+
+        let nfo = NFO::Movie((&video).into()).with_time_type(&video, Some(&NFOTimeType::CTime));
+        let NFO::Movie(ref movie) = nfo else { unreachable!() };
+        assert_eq!(movie.aired, date(1));
+        assert_eq!(movie.premiered, date(1));
+
+        let page = page::Model::default();
+        let nfo = NFO::Episode(Episode::from_video_and_page(&video, &page))
+            .with_time_type(&video, Some(&NFOTimeType::FavTime));
+        let NFO::Episode(ref episode) = nfo else { unreachable!() };
+        assert_eq!(episode.aired, Some(date(3)));
+
+        // 未单独设置时保持原有的时间
+        let nfo = NFO::Episode(Episode::from_video_and_page(&video, &page)).with_time_type(&video, None);
+        let NFO::Episode(ref episode) = nfo else { unreachable!() };
+        assert_eq!(episode.aired, Some(date(2)));
+    }
+
+    #[tokio::test]
+    async fn test_collection_season_nfo_and_cleanup() {
+        let video = video::Model {
+            name: "视频标题".to_string(),
+            intro: "视频简介".to_string(),
+            upper_name: "upper_name".to_string(),
+            bvid: "BV1nWcSeeEkV".to_string(),
+            season_number: Some(3),
+            ..Default::default()
+        };
+        let season = Season::from_video_with_collection(&video, Some("合集名称"), Some("https://example.com/c.jpg"));
+        assert_eq!(season.season_number, 1);
+        assert_eq!(season.cover_url, "https://example.com/c.jpg");
+        let season_nfo = NFO::Season(season).generate_nfo().await.unwrap();
+        assert!(season_nfo.contains("<title>合集名称</title>"));
+        assert!(season_nfo.contains("<seasonnumber>1</seasonnumber>"));
+
+        let season_dir = std::env::temp_dir().join(format!("bili-sync-season-nfo-{}", std::process::id()));
+        tokio::fs::create_dir_all(&season_dir).await.unwrap();
+        tokio::fs::write(season_dir.join(SEASON_NFO_FILE), "").await.unwrap();
+        tokio::fs::write(season_dir.join("S01E02.mp4"), "").await.unwrap();
+        // 仍有其他视频共享季度文件夹时保留 season.nfo
+        assert!(!remove_unused_season_nfo(&season_dir).await.unwrap());
+        tokio::fs::remove_file(season_dir.join("S01E02.mp4")).await.unwrap();
+        assert!(remove_unused_season_nfo(&season_dir).await.unwrap());
+        assert!(!season_dir.join(SEASON_NFO_FILE).exists());
+        tokio::fs::remove_dir_all(&season_dir).await.unwrap();
+    }
 }
@@ -6,7 +6,10 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
 use super::notification_bark::{self, BarkLevel, DeviceKeySelection};
+use super::notification_discord;
 use super::notification_serverchan;
+use super::notification_telegram::TelegramNotifier;
+use super::notification_webhook::{self, WebhookContext};
 use crate::config::{NotificationConfig, NotificationMethod};
 
 // 推送通知客户端
@@ -35,13 +38,28 @@ pub struct SourceScanResult {
     pub source_type: String,
     pub source_name: String,
     pub new_videos: Vec<NewVideoInfo>,
+    /// 因时长不在范围内而被过滤（不下载）的新视频数量
+    pub filtered_videos: usize,
+    /// 因发布时间或标题不满足视频源过滤条件而被跳过（未写入数据库）的视频数量
+    pub skipped_videos: usize,
+    /// 充电专享且当前账号无权观看，被标记为无权限的新视频数量
+    pub no_permission_videos: usize,
+    /// 该视频源本轮的扫描耗时，扫描未结束时为空
+    pub scan_duration: Option<Duration>,
+    /// 扫描失败的原因
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ScanSummary {
     pub total_sources: usize,
     pub total_new_videos: usize,
+    pub total_filtered_videos: usize,
+    pub total_skipped_videos: usize,
+    pub total_no_permission_videos: usize,
     pub scan_duration: Duration,
+    /// 新增视频已下载文件的总大小（字节）
+    pub total_size: u64,
     pub source_results: Vec<SourceScanResult>,
 }
 
@@ -53,6 +71,15 @@ pub struct DownloadFailureNotification {
     pub error: String,
 }
 
+/// 子任务反复失败的视频
+#[derive(Debug, Clone)]
+pub struct RepeatedFailureInfo {
+    pub bvid: String,
+    pub title: String,
+    /// 失败的子任务标识，分页子任务带有分P编号，如 "download(P1)"
+    pub subtasks: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RiskControlNotification {
     pub source_type: Option<String>,
@@ -60,12 +87,32 @@ pub struct RiskControlNotification {
     pub message: String,
 }
 
+/// 磁盘剩余空间不足导致任务暂停
+#[derive(Debug, Clone)]
+pub struct LowDiskSpaceNotification {
+    pub path: String,
+    pub available: u64,
+    pub threshold: u64,
+}
+
+/// 推送内容涉及的视频统计，供 Webhook 模板等结构化渠道使用
+#[derive(Debug, Clone, Default)]
+pub struct NotificationSummary {
+    pub count: usize,
+    pub total_size: u64,
+    pub source_name: String,
+    pub titles: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct NotificationMessage {
     pub title: String,
     pub subtitle: Option<String>,
     pub body_markdown: String,
     pub body_plain: String,
+    /// 结构化的摘要字段（名称, 值），用于支持富文本卡片的渠道
+    pub fields: Vec<(String, String)>,
+    pub summary: NotificationSummary,
     pub level: Option<BarkLevel>,
     pub volume: Option<u8>,
     pub badge: Option<u32>,
@@ -94,6 +141,8 @@ impl NotificationMessage {
             subtitle: None,
             body_markdown,
             body_plain,
+            fields: Vec::new(),
+            summary: NotificationSummary::default(),
             level: None,
             volume: None,
             badge: None,
@@ -118,7 +167,9 @@ enum NotificationEventKind {
     ScanSummary,
     SourceUpdate,
     DownloadFailure,
+    RepeatedFailure,
     RiskControl,
+    LowDiskSpace,
     Custom(&'static str),
 }
 
@@ -128,7 +179,9 @@ impl NotificationEventKind {
             NotificationEventKind::ScanSummary => "scan_summary",
             NotificationEventKind::SourceUpdate => "source_update",
             NotificationEventKind::DownloadFailure => "download_failure",
+            NotificationEventKind::RepeatedFailure => "repeated_failure",
             NotificationEventKind::RiskControl => "risk_control",
+            NotificationEventKind::LowDiskSpace => "low_disk_space",
             NotificationEventKind::Custom(label) => label,
         }
     }
@@ -140,6 +193,54 @@ impl fmt::Display for NotificationEventKind {
     }
 }
 
+/// 实际投递消息的推送渠道
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationChannel {
+    Serverchan,
+    Bark,
+    Telegram,
+    Discord,
+    Webhook,
+}
+
+impl NotificationChannel {
+    fn as_str(self) -> &'static str {
+        match self {
+            NotificationChannel::Serverchan => "Server酱",
+            NotificationChannel::Bark => "Bark",
+            NotificationChannel::Telegram => "Telegram",
+            NotificationChannel::Discord => "Discord",
+            NotificationChannel::Webhook => "Webhook",
+        }
+    }
+}
+
+impl NotificationChannel {
+    fn primary(method: NotificationMethod) -> Self {
+        match method {
+            NotificationMethod::Serverchan => NotificationChannel::Serverchan,
+            NotificationMethod::Bark => NotificationChannel::Bark,
+        }
+    }
+
+    /// 供前端识别的渠道标识
+    fn id(self) -> &'static str {
+        match self {
+            NotificationChannel::Serverchan => "serverchan",
+            NotificationChannel::Bark => "bark",
+            NotificationChannel::Telegram => "telegram",
+            NotificationChannel::Discord => "discord",
+            NotificationChannel::Webhook => "webhook",
+        }
+    }
+}
+
+impl fmt::Display for NotificationChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl NotificationClient {
     pub fn new(config: NotificationConfig) -> Self {
         let client = Client::builder()
@@ -204,6 +305,21 @@ impl NotificationClient {
             .await
     }
 
+    pub async fn send_repeated_failures(&self, failures: &[RepeatedFailureInfo]) -> Result<()> {
+        if !self.should_send(NotificationEventKind::RepeatedFailure) {
+            debug!("反复失败推送已禁用，跳过发送");
+            return Ok(());
+        }
+
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        let message = self.build_repeated_failure_message(failures);
+        self.dispatch_with_retry(NotificationEventKind::RepeatedFailure, message)
+            .await
+    }
+
     pub async fn send_risk_control(&self, details: RiskControlNotification) -> Result<()> {
         if !self.should_send(NotificationEventKind::RiskControl) {
             debug!("风控推送已禁用，跳过发送");
@@ -215,6 +331,17 @@ impl NotificationClient {
             .await
     }
 
+    pub async fn send_low_disk_space(&self, details: LowDiskSpaceNotification) -> Result<()> {
+        if !self.should_send(NotificationEventKind::LowDiskSpace) {
+            debug!("推送已禁用，跳过发送磁盘空间不足提醒");
+            return Ok(());
+        }
+
+        let message = self.build_low_disk_space_message(&details);
+        self.dispatch_with_retry(NotificationEventKind::LowDiskSpace, message)
+            .await
+    }
+
     pub async fn test_notification(&self) -> Result<()> {
         let message = NotificationMessage::new(
             "Bili Sync 测试推送",
@@ -233,34 +360,64 @@ impl NotificationClient {
     fn should_send(&self, kind: NotificationEventKind) -> bool {
         match kind {
             NotificationEventKind::Custom(_) => true,
-            _ => {
-                if !self.config.enable_scan_notifications {
-                    return false;
-                }
+            // 反复失败提醒由独立开关控制，不受扫描推送开关影响
+            NotificationEventKind::RepeatedFailure => self.config.enable_failure_notifications,
+            _ if !self.config.enable_scan_notifications => false,
+            NotificationEventKind::ScanSummary => self.config.events.scan_summary,
+            NotificationEventKind::SourceUpdate => self.config.events.source_updates,
+            NotificationEventKind::DownloadFailure => self.config.events.download_failures,
+            NotificationEventKind::RiskControl => self.config.events.risk_control,
+            NotificationEventKind::LowDiskSpace => true,
+        }
+    }
 
-                match kind {
-                    NotificationEventKind::ScanSummary => self.config.events.scan_summary,
-                    NotificationEventKind::SourceUpdate => self.config.events.source_updates,
-                    NotificationEventKind::DownloadFailure => self.config.events.download_failures,
-                    NotificationEventKind::RiskControl => self.config.events.risk_control,
-                    NotificationEventKind::Custom(_) => true,
-                }
-            }
+    /// 收集所有已配置的推送渠道；均未配置时保留主推送方式，以便在发送时给出明确的错误
+    fn configured_channels(&self) -> Vec<NotificationChannel> {
+        let channels = ready_channels(&self.config);
+        if channels.is_empty() {
+            vec![NotificationChannel::primary(self.config.method)]
+        } else {
+            channels
         }
     }
 
     async fn dispatch_with_retry(&self, kind: NotificationEventKind, message: NotificationMessage) -> Result<()> {
+        let mut failures = Vec::new();
+
+        // 逐个渠道投递，某个渠道失败不影响其它渠道
+        for channel in self.configured_channels() {
+            if let Err(err) = self.dispatch_channel_with_retry(kind, channel, &message).await {
+                failures.push(format!("{}: {}", channel, err));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("{} 推送发送失败: {}", kind, failures.join("; ")))
+        }
+    }
+
+    async fn dispatch_channel_with_retry(
+        &self,
+        kind: NotificationEventKind,
+        channel: NotificationChannel,
+        message: &NotificationMessage,
+    ) -> Result<()> {
         let retry_count = self.config.notification_retry_count.max(1) as usize;
         let mut last_error: Option<anyhow::Error> = None;
 
         for attempt in 1..=retry_count {
-            match self.send_once(kind, message.clone()).await {
+            match self.send_once(kind, channel, message.clone()).await {
                 Ok(_) => {
-                    info!("{} 推送发送成功", kind);
+                    info!("{} 推送通过 {} 发送成功", kind, channel);
                     return Ok(());
                 }
                 Err(err) => {
-                    warn!("{} 推送发送失败 (尝试 {}/{}): {}", kind, attempt, retry_count, err);
+                    warn!(
+                        "{} 推送通过 {} 发送失败 (尝试 {}/{}): {}",
+                        kind, channel, attempt, retry_count, err
+                    );
                     last_error = Some(err);
 
                     if attempt < retry_count {
@@ -270,30 +427,70 @@ impl NotificationClient {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| anyhow!("{} 推送发送失败", kind)))
+        Err(last_error.unwrap_or_else(|| anyhow!("{} 推送通过 {} 发送失败", kind, channel)))
     }
 
-    async fn send_once(&self, _kind: NotificationEventKind, message: NotificationMessage) -> Result<()> {
-        match self.config.method {
-            NotificationMethod::Serverchan => {
-                let key = self
-                    .config
-                    .serverchan_key
-                    .as_ref()
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .ok_or_else(|| anyhow!("未配置Server酱 SendKey"))?;
-
+    async fn send_once(
+        &self,
+        kind: NotificationEventKind,
+        channel: NotificationChannel,
+        message: NotificationMessage,
+    ) -> Result<()> {
+        match channel {
+            NotificationChannel::Serverchan => {
+                let key = self.serverchan_key()?;
                 notification_serverchan::send(&self.client, key, &message.title, &message.body_markdown).await
             }
-            NotificationMethod::Bark => {
+            NotificationChannel::Bark => {
                 let keys = self.bark_device_selection()?;
                 let payload = notification_bark::BarkPayload::from_message(&message, &self.config.bark_defaults, keys)?;
                 notification_bark::send(&self.client, &self.effective_bark_server(), payload).await
             }
+            NotificationChannel::Telegram => {
+                let (bot_token, chat_id) = self
+                    .config
+                    .telegram_credentials()
+                    .ok_or_else(|| anyhow!("未配置 Telegram Bot Token 或 Chat ID"))?;
+                TelegramNotifier::new(bot_token, chat_id)
+                    .send(&self.client, &message.title, &message.body_markdown)
+                    .await
+            }
+            NotificationChannel::Discord => {
+                let webhook_url = self
+                    .config
+                    .discord_webhook()
+                    .ok_or_else(|| anyhow!("未配置 Discord Webhook URL"))?;
+                notification_discord::send(&self.client, webhook_url, &message).await
+            }
+            NotificationChannel::Webhook => {
+                let url = self.config.webhook().ok_or_else(|| anyhow!("未配置 Webhook URL"))?;
+                let summary = &message.summary;
+                let context = WebhookContext {
+                    event: kind.as_str(),
+                    title: &message.title,
+                    body: &message.body_plain,
+                    count: summary.count,
+                    total_size: format_size(summary.total_size),
+                    total_size_bytes: summary.total_size,
+                    source_name: &summary.source_name,
+                    titles: summary.titles.join("\n"),
+                    titles_json: serde_json::to_string(&summary.titles)?,
+                    title_list: &summary.titles,
+                };
+                notification_webhook::send(&self.client, url, self.config.webhook_template.as_deref(), &context).await
+            }
         }
     }
 
+    fn serverchan_key(&self) -> Result<&str> {
+        self.config
+            .serverchan_key
+            .as_ref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("未配置Server酱 SendKey"))
+    }
+
     fn bark_device_selection(&self) -> Result<DeviceKeySelection> {
         let single = self
             .config
@@ -334,7 +531,30 @@ impl NotificationClient {
     fn build_scan_summary_message(&self, summary: &ScanSummary) -> NotificationMessage {
         let title = "Bili Sync 扫描完成";
         let body = format_scan_summary(summary);
-        NotificationMessage::new(title, body)
+        let mut message = NotificationMessage::new(title, body);
+        message.fields = vec![
+            ("新增视频".to_string(), format!("{} 个", summary.total_new_videos)),
+            ("总大小".to_string(), format_size(summary.total_size)),
+        ];
+        let sources_with_updates: Vec<&SourceScanResult> = summary
+            .source_results
+            .iter()
+            .filter(|s| !s.new_videos.is_empty())
+            .collect();
+        message.summary = NotificationSummary {
+            count: summary.total_new_videos,
+            total_size: summary.total_size,
+            source_name: sources_with_updates
+                .iter()
+                .map(|s| s.source_name.as_str())
+                .collect::<Vec<_>>()
+                .join("、"),
+            titles: sources_with_updates
+                .iter()
+                .flat_map(|s| s.new_videos.iter().map(|video| video.title.clone()))
+                .collect(),
+        };
+        message
     }
 
     fn build_source_update_message(&self, source: &SourceScanResult) -> NotificationMessage {
@@ -368,7 +588,14 @@ impl NotificationClient {
             ));
         }
 
-        NotificationMessage::new(title, body)
+        let mut message = NotificationMessage::new(title, body);
+        message.summary = NotificationSummary {
+            count: source.new_videos.len(),
+            total_size: 0,
+            source_name: source.source_name.clone(),
+            titles: source.new_videos.iter().map(|video| video.title.clone()).collect(),
+        };
+        message
     }
 
     fn build_download_failure_message(&self, details: &DownloadFailureNotification) -> NotificationMessage {
@@ -388,7 +615,46 @@ impl NotificationClient {
         body.push_str(&sanitize_text(&details.error));
         body.push_str("\n````");
 
-        NotificationMessage::new(title, body)
+        let mut message = NotificationMessage::new(title, body);
+        message.summary = NotificationSummary {
+            count: 0,
+            total_size: 0,
+            source_name: details.source_name.clone(),
+            titles: details.video_title.iter().cloned().collect(),
+        };
+        message
+    }
+
+    fn build_repeated_failure_message(&self, failures: &[RepeatedFailureInfo]) -> NotificationMessage {
+        let title = format!("{} 个视频反复下载失败", failures.len());
+        let mut body = String::from("以下视频的子任务已连续多次失败，可能需要手动处理：\n\n");
+
+        const MAX_VIDEOS: usize = 20;
+        for failure in failures.iter().take(MAX_VIDEOS) {
+            body.push_str(&format!(
+                "- [{}](https://www.bilibili.com/video/{}) ({}): {}\n",
+                sanitize_text(&failure.title),
+                failure.bvid,
+                failure.bvid,
+                failure.subtasks.join(", ")
+            ));
+        }
+
+        if failures.len() > MAX_VIDEOS {
+            body.push_str(&format!(
+                "...还有 {} 个视频（内容过长已省略）\n",
+                failures.len() - MAX_VIDEOS
+            ));
+        }
+
+        let mut message = NotificationMessage::new(title, body);
+        message.summary = NotificationSummary {
+            count: failures.len(),
+            total_size: 0,
+            source_name: String::new(),
+            titles: failures.iter().map(|failure| failure.title.clone()).collect(),
+        };
+        message
     }
 
     fn build_risk_control_message(&self, details: &RiskControlNotification) -> NotificationMessage {
@@ -408,7 +674,21 @@ impl NotificationClient {
         body.push_str("\n**详细信息**:\n");
         body.push_str(&sanitize_text(&details.message));
 
-        NotificationMessage::new(title, body)
+        let mut message = NotificationMessage::new(title, body);
+        message.summary.source_name = details.source_name.clone().unwrap_or_default();
+        message
+    }
+
+    fn build_low_disk_space_message(&self, details: &LowDiskSpaceNotification) -> NotificationMessage {
+        let body = format!(
+            "**目录**: {}\n**剩余空间**: {}\n**最低要求**: {}\n\n下载任务已自动暂停，释放空间后将自动恢复。",
+            sanitize_text(&details.path),
+            format_size(details.available),
+            format_size(details.threshold)
+        );
+        let mut message = NotificationMessage::new("磁盘空间不足", body);
+        message.level = Some(BarkLevel::TimeSensitive);
+        message
     }
 }
 
@@ -430,11 +710,21 @@ fn format_scan_summary(summary: &ScanSummary) -> String {
     const MAX_CONTENT_LENGTH: usize = 30_000;
 
     let mut content = format!(
-        "📊 **扫描摘要**\n\n- 扫描视频源: {}个\n- 新增视频: {}个\n- 扫描耗时: {:.1}分钟\n\n",
+        "📊 **扫描摘要**\n\n- 扫描视频源: {}个\n- 新增视频: {}个\n- 扫描耗时: {:.1}分钟\n",
         summary.total_sources,
         summary.total_new_videos,
         summary.scan_duration.as_secs_f64() / 60.0
     );
+    if summary.total_size > 0 {
+        content.push_str(&format!("- 下载大小: {}\n", format_size(summary.total_size)));
+    }
+    if summary.total_no_permission_videos > 0 {
+        content.push_str(&format!(
+            "- 无权观看（充电专享）: {}个\n",
+            summary.total_no_permission_videos
+        ));
+    }
+    content.push('\n');
 
     if summary.total_new_videos > 0 {
         content.push_str("📹 **新增视频详情**\n\n");
@@ -548,6 +838,23 @@ fn format_scan_summary(summary: &ScanSummary) -> String {
     }
 }
 
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.2} {}", size, UNITS[unit])
+    }
+}
+
 fn markdown_to_plain_text(markdown: &str) -> String {
     let mut plain = String::with_capacity(markdown.len());
     let mut chars = markdown.chars().peekable();
@@ -598,6 +905,55 @@ fn markdown_to_plain_text(markdown: &str) -> String {
     plain
 }
 
+/// 主推送方式（若已配置）与所有配置完整的附加渠道
+fn ready_channels(config: &NotificationConfig) -> Vec<NotificationChannel> {
+    let mut channels = Vec::new();
+
+    let primary_ready = match config.method {
+        NotificationMethod::Serverchan => config
+            .serverchan_key
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty()),
+        NotificationMethod::Bark => {
+            config
+                .bark_device_key
+                .as_deref()
+                .is_some_and(|value| !value.trim().is_empty())
+                || config.bark_device_keys.iter().any(|value| !value.trim().is_empty())
+        }
+    };
+    if primary_ready {
+        channels.push(NotificationChannel::primary(config.method));
+    }
+
+    if config.telegram_credentials().is_some() {
+        channels.push(NotificationChannel::Telegram);
+    }
+
+    if config.discord_webhook().is_some() {
+        channels.push(NotificationChannel::Discord);
+    }
+
+    if config.webhook().is_some() {
+        channels.push(NotificationChannel::Webhook);
+    }
+
+    channels
+}
+
+/// 检查 Webhook 推送模板能否被解析
+pub fn validate_webhook_template(template: &str) -> Result<()> {
+    notification_webhook::validate_template(template)
+}
+
+/// 当前已配置完整的推送渠道标识列表
+pub fn configured_channel_ids(config: &NotificationConfig) -> Vec<&'static str> {
+    ready_channels(config)
+        .into_iter()
+        .map(NotificationChannel::id)
+        .collect()
+}
+
 // 便捷函数
 pub async fn send_scan_notification(summary: ScanSummary) -> Result<()> {
     let config = crate::config::reload_config().notification;
@@ -611,12 +967,24 @@ pub async fn send_download_failure_notification(details: DownloadFailureNotifica
     client.send_download_failure(details).await
 }
 
+pub async fn send_repeated_failure_notification(failures: &[RepeatedFailureInfo]) -> Result<()> {
+    let config = crate::config::reload_config().notification;
+    let client = NotificationClient::new(config);
+    client.send_repeated_failures(failures).await
+}
+
 pub async fn send_risk_control_notification(details: RiskControlNotification) -> Result<()> {
     let config = crate::config::reload_config().notification;
     let client = NotificationClient::new(config);
     client.send_risk_control(details).await
 }
 
+pub async fn send_low_disk_space_notification(details: LowDiskSpaceNotification) -> Result<()> {
+    let config = crate::config::reload_config().notification;
+    let client = NotificationClient::new(config);
+    client.send_low_disk_space(details).await
+}
+
 #[allow(dead_code)]
 pub async fn test_notification() -> Result<()> {
     let config = crate::config::reload_config().notification;
@@ -630,3 +998,29 @@ pub async fn send_custom_test_notification(message: &str) -> Result<()> {
     let client = NotificationClient::new(config);
     client.send_custom_test(message).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_failure_ignores_scan_notification_switch() {
+        let config = NotificationConfig {
+            enable_scan_notifications: false,
+            enable_failure_notifications: true,
+            ..Default::default()
+        };
+        let client = NotificationClient::new(config);
+        assert!(client.should_send(NotificationEventKind::RepeatedFailure));
+        assert!(!client.should_send(NotificationEventKind::ScanSummary));
+        assert!(!client.should_send(NotificationEventKind::DownloadFailure));
+
+        let config = NotificationConfig {
+            enable_scan_notifications: true,
+            enable_failure_notifications: false,
+            ..Default::default()
+        };
+        let client = NotificationClient::new(config);
+        assert!(!client.should_send(NotificationEventKind::RepeatedFailure));
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use reqwest::{Client, Url};
 use serde::Serialize;
 use std::str::FromStr;
 
@@ -48,13 +48,20 @@ pub struct DeviceKeySelection {
 #[derive(Debug, Serialize)]
 pub struct BarkPayload {
     pub title: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub subtitle: Option<String>,
     pub body: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub device_key: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub device_keys: Vec<String>,
+    #[serde(flatten)]
+    pub options: BarkOptions,
+}
+
+/// 推送的可选参数，POST 时作为 JSON 字段，GET 时作为查询参数
+#[derive(Debug, Serialize)]
+pub struct BarkOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub level: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -146,35 +153,47 @@ impl BarkPayload {
 
         Ok(Self {
             title: message.title.clone(),
-            subtitle: message
-                .subtitle
-                .clone()
-                .or_else(|| defaults.subtitle.as_ref().map(|value| value.to_string())),
             body: message.body_plain.clone(),
             device_key: keys.device_key,
             device_keys: keys.device_keys,
-            level,
-            volume: message.volume.or(defaults.volume),
-            badge: message.badge.or(defaults.badge),
-            call: message.call.or(defaults.call).map(bool_to_flag),
-            auto_copy: message.auto_copy.or(defaults.auto_copy).map(bool_to_flag),
-            copy,
-            sound,
-            icon,
-            group,
-            ciphertext,
-            is_archive: message.is_archive.or(defaults.is_archive).map(bool_to_flag),
-            url,
-            action,
-            id,
-            delete: message.delete.or(defaults.delete).map(bool_to_flag),
+            options: BarkOptions {
+                subtitle: message
+                    .subtitle
+                    .clone()
+                    .or_else(|| defaults.subtitle.as_ref().map(|value| value.to_string())),
+                level,
+                volume: message.volume.or(defaults.volume),
+                badge: message.badge.or(defaults.badge),
+                call: message.call.or(defaults.call).map(bool_to_flag),
+                auto_copy: message.auto_copy.or(defaults.auto_copy).map(bool_to_flag),
+                copy,
+                sound,
+                icon,
+                group,
+                ciphertext,
+                is_archive: message.is_archive.or(defaults.is_archive).map(bool_to_flag),
+                url,
+                action,
+                id,
+                delete: message.delete.or(defaults.delete).map(bool_to_flag),
+            },
         })
     }
 }
 
+/// GET 请求 URL 的长度上限，超出时改用 POST，避免服务器拒绝过长的请求行
+const MAX_GET_URL_LENGTH: usize = 2048;
+
+/// 单设备且内容较短时使用 GET {server}/{key}/{title}/{body}，否则通过 POST {server}/push 发送
 pub(super) async fn send(client: &Client, server: &str, payload: BarkPayload) -> Result<()> {
-    let url = format!("{}/push", server.trim_end_matches('/'));
-    let response = client.post(&url).json(&payload).send().await?;
+    let request = match build_get_url(server, &payload)? {
+        Some(url) => client.get(url),
+        None => {
+            let url = format!("{}/push", server.trim_end_matches('/'));
+            client.post(&url).json(&payload)
+        }
+    };
+    let response = request.send().await?;
 
     if response.status().is_success() {
         Ok(())
@@ -185,6 +204,34 @@ pub(super) async fn send(client: &Client, server: &str, payload: BarkPayload) ->
     }
 }
 
+/// 构建 GET 推送地址，路径段经过 URL 编码，其余参数放在查询串中；不适合 GET 时返回 None
+fn build_get_url(server: &str, payload: &BarkPayload) -> Result<Option<Url>> {
+    let device_key = match payload.device_key.as_deref() {
+        Some(key) if payload.device_keys.is_empty() => key,
+        // 多设备推送只能通过 /push 接口批量发送
+        _ => return Ok(None),
+    };
+
+    let mut url = Url::parse(server.trim_end_matches('/')).map_err(|e| anyhow!("Bark 服务器地址无效: {}", e))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Bark 服务器地址无效: {}", server))?
+        .pop_if_empty()
+        .push(device_key)
+        .push(&payload.title)
+        .push(&payload.body);
+
+    let query = serde_urlencoded::to_string(&payload.options)?;
+    if !query.is_empty() {
+        url.set_query(Some(&query));
+    }
+
+    if url.as_str().len() > MAX_GET_URL_LENGTH {
+        Ok(None)
+    } else {
+        Ok(Some(url))
+    }
+}
+
 fn bool_to_flag(value: bool) -> u8 {
     if value {
         1
@@ -192,3 +239,35 @@ fn bool_to_flag(value: bool) -> u8 {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(device_keys: Vec<String>) -> BarkPayload {
+        let mut message = NotificationMessage::new("新视频 1/2", "标题 #1?");
+        message.sound = Some("bell".to_string());
+        let keys = DeviceKeySelection {
+            device_key: Some("abc".to_string()),
+            device_keys,
+        };
+        BarkPayload::from_message(&message, &BarkDefaults::default(), keys).unwrap()
+    }
+
+    #[test]
+    fn test_build_get_url_encodes_segments() {
+        let url = build_get_url("https://api.day.app/", &payload(Vec::new()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.day.app/abc/%E6%96%B0%E8%A7%86%E9%A2%91%201%2F2/%E6%A0%87%E9%A2%98%20%231%3F?sound=bell"
+        );
+    }
+
+    #[test]
+    fn test_build_get_url_falls_back_for_multiple_devices() {
+        let url = build_get_url("https://api.day.app", &payload(vec!["def".to_string()])).unwrap();
+        assert!(url.is_none());
+    }
+}
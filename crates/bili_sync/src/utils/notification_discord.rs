@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::Serialize;
+
+use crate::utils::notification::NotificationMessage;
+
+/// Discord embed 描述的最大长度
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+/// Discord embed 标题的最大长度
+const MAX_TITLE_LENGTH: usize = 256;
+/// B站粉色
+const EMBED_COLOR: u32 = 0xFB7299;
+
+#[derive(Serialize)]
+struct DiscordWebhookRequest {
+    username: &'static str,
+    embeds: Vec<DiscordEmbed>,
+}
+
+#[derive(Serialize)]
+struct DiscordEmbed {
+    title: String,
+    description: String,
+    color: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<DiscordEmbedField>,
+}
+
+#[derive(Serialize)]
+struct DiscordEmbedField {
+    name: String,
+    value: String,
+    inline: bool,
+}
+
+pub(super) async fn send(client: &Client, webhook_url: &str, message: &NotificationMessage) -> Result<()> {
+    let request = DiscordWebhookRequest {
+        username: "Bili Sync",
+        embeds: vec![DiscordEmbed {
+            title: truncate(&message.title, MAX_TITLE_LENGTH),
+            description: truncate(&message.body_markdown, MAX_DESCRIPTION_LENGTH),
+            color: EMBED_COLOR,
+            fields: message
+                .fields
+                .iter()
+                .map(|(name, value)| DiscordEmbedField {
+                    name: name.clone(),
+                    value: value.clone(),
+                    inline: true,
+                })
+                .collect(),
+        }],
+    };
+
+    let response = client.post(webhook_url).json(&request).send().await?;
+
+    // Discord 成功时返回 204 No Content
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        Err(anyhow!("Discord返回错误: {} {}", status, text))
+    }
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+
+    let mut truncated = text.chars().take(max_length - 3).collect::<String>();
+    truncated.push_str("...");
+    truncated
+}
@@ -0,0 +1,136 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// Telegram 单条消息的最大长度
+const MAX_MESSAGE_LENGTH: usize = 4096;
+
+#[derive(Serialize)]
+struct TelegramRequest<'a> {
+    chat_id: &'a str,
+    text: String,
+    disable_web_page_preview: bool,
+}
+
+#[derive(Deserialize)]
+struct TelegramResponse {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+pub(super) struct TelegramNotifier<'a> {
+    bot_token: &'a str,
+    chat_id: &'a str,
+}
+
+impl<'a> TelegramNotifier<'a> {
+    pub(super) fn new(bot_token: &'a str, chat_id: &'a str) -> Self {
+        Self { bot_token, chat_id }
+    }
+
+    pub(super) async fn send(&self, client: &Client, title: &str, desp: &str) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let request = self.build_request(title, desp);
+
+        // 请求地址中含有 bot token，错误信息中去掉地址，避免 token 出现在日志里
+        let response = client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)?;
+        let status = response.status();
+        let response_text = response.text().await.map_err(reqwest::Error::without_url)?;
+        let telegram_response: TelegramResponse = serde_json::from_str(&response_text)
+            .map_err(|e| anyhow!("解析响应失败: {}, 状态码: {}, 响应内容: {}", e, status, response_text))?;
+
+        if telegram_response.ok {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Telegram返回错误: {}",
+                telegram_response.description.unwrap_or_else(|| status.to_string())
+            ))
+        }
+    }
+
+    /// 构建发送请求，不设置 parse_mode，消息按纯文本发送，正文中的 Markdown/HTML 字符无需转义
+    fn build_request(&self, title: &str, desp: &str) -> TelegramRequest<'a> {
+        TelegramRequest {
+            chat_id: self.chat_id,
+            text: format_text(title, desp),
+            disable_web_page_preview: true,
+        }
+    }
+}
+
+/// 拼接标题与正文，超出 Telegram 长度限制时截断
+fn format_text(title: &str, desp: &str) -> String {
+    let text = format!("{}\n\n{}", title, desp);
+    if text.chars().count() <= MAX_MESSAGE_LENGTH {
+        return text;
+    }
+
+    let mut truncated = text.chars().take(MAX_MESSAGE_LENGTH - 20).collect::<String>();
+    truncated.push_str("\n\n...内容过长，已截断");
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NotificationConfig;
+
+    #[test]
+    fn test_build_request() {
+        let notifier = TelegramNotifier::new("123:abc", "-1001");
+        let request = notifier.build_request("Bili Sync 扫描完成", "新增 *1* 个视频: <a_b> & [链接](x)");
+        let payload = serde_json::to_value(&request).unwrap();
+        assert_eq!(payload["chat_id"], "-1001");
+        assert_eq!(payload["disable_web_page_preview"], true);
+        // 纯文本发送，特殊字符原样保留
+        assert_eq!(
+            payload["text"],
+            "Bili Sync 扫描完成\n\n新增 *1* 个视频: <a_b> & [链接](x)"
+        );
+        assert!(payload.get("parse_mode").is_none());
+    }
+
+    #[test]
+    fn test_format_text_truncate() {
+        let text = format_text("标题", &"视".repeat(MAX_MESSAGE_LENGTH));
+        assert!(text.chars().count() <= MAX_MESSAGE_LENGTH);
+        assert!(text.starts_with("标题\n\n视"));
+        assert!(text.ends_with("...内容过长，已截断"));
+    }
+
+    #[tokio::test]
+    async fn test_send_error_hides_token() {
+        // 通过不可连接的代理让请求失败，错误信息中不应包含 bot token
+        let client = Client::builder()
+            .proxy(reqwest::Proxy::all("http://127.0.0.1:1").unwrap())
+            .build()
+            .unwrap();
+        let notifier = TelegramNotifier::new("123:secret-token", "-1001");
+        let error = notifier.send(&client, "标题", "内容").await.unwrap_err();
+        assert!(!format!("{:#}", error).contains("secret-token"));
+    }
+
+    #[test]
+    fn test_skip_without_credentials() {
+        let mut config = NotificationConfig {
+            telegram_bot_token: Some("123:abc".to_string()),
+            telegram_chat_id: Some("-1001".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.telegram_credentials(), Some(("123:abc", "-1001")));
+
+        config.telegram_chat_id = Some("  ".to_string());
+        assert!(config.telegram_credentials().is_none());
+
+        config.telegram_chat_id = Some("-1001".to_string());
+        config.telegram_bot_token = None;
+        assert!(config.telegram_credentials().is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Context, Result};
+use handlebars::Handlebars;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+
+const TEMPLATE_NAME: &str = "webhook";
+
+/// 模板可用的变量
+#[derive(Serialize)]
+pub(super) struct WebhookContext<'a> {
+    pub event: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub count: usize,
+    /// 格式化后的大小，如 "1.25 GB"
+    pub total_size: String,
+    pub total_size_bytes: u64,
+    pub source_name: &'a str,
+    /// 换行分隔的视频标题
+    pub titles: String,
+    /// 视频标题的 JSON 数组，需使用 {{{titles_json}}} 原样输出
+    pub titles_json: String,
+    #[serde(skip)]
+    pub title_list: &'a [String],
+}
+
+/// 模板为空时使用的默认 JSON 结构
+#[derive(Serialize)]
+struct DefaultPayload<'a> {
+    event: &'a str,
+    title: &'a str,
+    body: &'a str,
+    count: usize,
+    total_size: &'a str,
+    total_size_bytes: u64,
+    source_name: &'a str,
+    titles: &'a [String],
+}
+
+/// 构建用于渲染 Webhook 模板的引擎，变量按 JSON 字符串规则转义
+fn build_handlebars(template: &str) -> Result<Handlebars<'static>> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(false);
+    handlebars.register_escape_fn(escape_json_string);
+    handlebars.register_template_string(TEMPLATE_NAME, template)?;
+    Ok(handlebars)
+}
+
+/// 检查 Webhook 模板能否被解析
+pub(super) fn validate_template(template: &str) -> Result<()> {
+    build_handlebars(template).map(|_| ())
+}
+
+pub(super) async fn send(
+    client: &Client,
+    url: &str,
+    template: Option<&str>,
+    context: &WebhookContext<'_>,
+) -> Result<()> {
+    let payload = render_payload(template, context)?;
+    let response = client.post(url).json(&payload).send().await?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        Err(anyhow!("Webhook返回错误: {} {}", status, text))
+    }
+}
+
+fn render_payload(template: Option<&str>, context: &WebhookContext<'_>) -> Result<Value> {
+    let template = template.map(str::trim).filter(|value| !value.is_empty());
+    let Some(template) = template else {
+        return Ok(serde_json::to_value(DefaultPayload {
+            event: context.event,
+            title: context.title,
+            body: context.body,
+            count: context.count,
+            total_size: &context.total_size,
+            total_size_bytes: context.total_size_bytes,
+            source_name: context.source_name,
+            titles: context.title_list,
+        })?);
+    };
+
+    let rendered = build_handlebars(template)
+        .context("Webhook 模板解析失败")?
+        .render(TEMPLATE_NAME, context)
+        .context("Webhook 模板渲染失败")?;
+    serde_json::from_str(&rendered).with_context(|| format!("Webhook 模板渲染结果不是合法的 JSON: {}", rendered))
+}
+
+fn escape_json_string(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(titles: &[String]) -> WebhookContext<'_> {
+        WebhookContext {
+            event: "scan_summary",
+            title: "Bili Sync 扫描完成",
+            body: "",
+            count: titles.len(),
+            total_size: "1.00 KB".to_string(),
+            total_size_bytes: 1024,
+            source_name: "收藏夹 \"默认\"",
+            titles: titles.join("\n"),
+            titles_json: serde_json::to_string(titles).unwrap(),
+            title_list: titles,
+        }
+    }
+
+    #[test]
+    fn test_render_custom_template() {
+        let titles = vec!["第一个".to_string(), "第二个".to_string()];
+        let template = r#"{"text": "{{source_name}}: {{titles}}", "count": {{count}}, "list": {{{titles_json}}}}"#;
+        let payload = render_payload(Some(template), &context(&titles)).unwrap();
+        assert_eq!(payload["text"], "收藏夹 \"默认\": 第一个\n第二个");
+        assert_eq!(payload["count"], 2);
+        assert_eq!(payload["list"][1], "第二个");
+    }
+
+    #[test]
+    fn test_render_default_payload() {
+        let titles = vec!["视频".to_string()];
+        let payload = render_payload(Some("  "), &context(&titles)).unwrap();
+        assert_eq!(payload["total_size_bytes"], 1024);
+        assert_eq!(payload["titles"][0], "视频");
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template(r#"{"count": {{count}}}"#).is_ok());
+        assert!(validate_template(r#"{"count": {{#if count}}}"#).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::NFOFlavor;
+use crate::utils::status::PageStatus;
+
+/// 文件名主干之后最长的后缀字节数，截断文件名时需要预留
+///
+/// 最长的是 `.zh-CN.default.ass` 与下载中的 `.tmp_video.part.meta`，字幕语言代码不固定，再留出余量
+pub const MAX_SUFFIX_BYTES: usize = 24;
+
+/// 分页 NFO 文件名
+pub fn nfo_file_name(stem: &str) -> String {
+    format!("{}.nfo", stem)
+}
+
+/// 弹幕 ASS 文件名
+pub fn danmaku_file_name(stem: &str) -> String {
+    format!("{}.zh-CN.default.ass", stem)
+}
+
+/// 字幕文件名，每种语言一个文件
+pub fn subtitle_file_name(stem: &str, lan: &str) -> String {
+    format!("{}.{}.srt", stem, lan)
+}
+
+/// 跳过片段使用的 EDL 文件名
+pub fn edl_file_name(stem: &str) -> String {
+    format!("{}.edl", stem)
+}
+
+/// 视频看点生成的章节文件名
+pub fn chapters_file_name(stem: &str) -> String {
+    format!("{}.chapters.xml", stem)
+}
+
+/// 旧版本或其他命名方式下可能残留的同名弹幕文件
+fn legacy_danmaku_file_names(stem: &str) -> Vec<String> {
+    vec![
+        format!("{}.ass", stem),
+        format!("{}.xml", stem),
+        format!("{}.zh-CN.default.xml", stem),
+        format!("{}.zh-CN.default.json", stem),
+    ]
+}
+
+/// 旧版本或其他命名方式下可能残留的同名图片文件
+fn legacy_image_file_names(stem: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    for suffix in ["thumb", "fanart", "poster"] {
+        for ext in ["jpg", "jpeg", "png", "webp"] {
+            files.push(format!("{}-{}.{}", stem, suffix, ext));
+        }
+    }
+    files
+}
+
+/// 是否为 `{stem}.{语言}.srt` 形式的字幕文件
+fn is_subtitle_of(file_name: &str, stem: &str) -> bool {
+    file_name
+        .strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(|rest| rest.strip_suffix(".srt"))
+        .is_some_and(|lan| !lan.is_empty() && !lan.contains('.'))
+}
+
+/// 与视频文件同名的所有附属文件（不含视频文件本身），删除视频时按此列表清理
+///
+/// 覆盖所有 NFO 风格的图片命名与旧版本的命名，字幕语言不固定，需要扫描所在文件夹；
+/// 根据分页的下载状态跳过被视频源关闭（不适用）的子任务，这些文件从未生成，无需查找
+pub fn sidecar_files(parent: &Path, stem: &str, download_status: u32) -> Vec<PathBuf> {
+    let status = PageStatus::from(download_status);
+    // 与 PageStatus 的子任务一一对应：视频封面、视频内容、视频信息、视频弹幕、视频字幕
+    let [cover, _, nfo, danmaku, subtitle]: [bool; 5] = std::array::from_fn(|i| !status.is_not_applicable(i));
+    let mut names = vec![edl_file_name(stem)];
+    if nfo {
+        names.push(nfo_file_name(stem));
+        names.push(chapters_file_name(stem));
+    }
+    if danmaku {
+        names.push(danmaku_file_name(stem));
+        names.extend(legacy_danmaku_file_names(stem));
+    }
+    if cover {
+        names.extend(NFOFlavor::all_episode_image_files(stem));
+        names.extend(legacy_image_file_names(stem));
+    }
+    if subtitle {
+        names.push(format!("{}.srt", stem));
+        if let Ok(entries) = std::fs::read_dir(parent) {
+            names.extend(
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| is_subtitle_of(name, stem)),
+            );
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    names
+        .into_iter()
+        .filter(|name| seen.insert(name.clone()))
+        .map(|name| parent.join(name))
+        .collect()
+}
+
+/// 分页文件是否位于视频文件夹下的季度子文件夹中（即使用了 Season 结构）
+///
+/// 季度文件夹名来自用户可自定义的 `folder_structure` 模板或固定的 `Season 01`，不能按名称前缀判断，
+/// 而视频记录保存的始终是季度文件夹的上一级，因此按实际的目录层级判断
+pub fn is_in_season_folder(video_path: &Path, page_path: &Path) -> bool {
+    page_path
+        .parent()
+        .and_then(|dir| dir.strip_prefix(video_path).ok())
+        .is_some_and(|rest| rest.components().count() == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_files_cover_all_artifacts() {
+        let dir = std::env::temp_dir().join(format!("bili_sync_page_files_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 按下载流程的命名写出一个分页的全部文件
+        let stem = "测试视频 - S01E01";
+        let video = dir.join(format!("{}.mp4", stem));
+        let mut artifacts = vec![
+            dir.join(nfo_file_name(stem)),
+            dir.join(danmaku_file_name(stem)),
+            dir.join(subtitle_file_name(stem, "zh-CN")),
+            dir.join(subtitle_file_name(stem, "ai-zh")),
+            dir.join(edl_file_name(stem)),
+            dir.join(chapters_file_name(stem)),
+            dir.join(format!("{}-poster.jpg", stem)),
+        ];
+        for flavor in NFOFlavor::ALL {
+            artifacts.push(dir.join(flavor.episode_thumb_file_name(stem)));
+            artifacts.push(dir.join(flavor.episode_fanart_file_name(stem)));
+        }
+        // 同一文件夹中其他分页的文件不应被清理
+        let other = [
+            dir.join("测试视频 - S01E02.mp4"),
+            dir.join(subtitle_file_name("测试视频 - S01E02", "zh-CN")),
+            dir.join(format!("{}.mp4", "测试视频 - S01E01.extra")),
+            dir.join(subtitle_file_name("测试视频 - S01E01.extra", "zh-CN")),
+        ];
+        for path in artifacts.iter().chain(other.iter()).chain(std::iter::once(&video)) {
+            std::fs::write(path, b"").unwrap();
+        }
+
+        for path in sidecar_files(&dir, stem, 0) {
+            if path.exists() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+        std::fs::remove_file(&video).unwrap();
+
+        let mut remaining: Vec<PathBuf> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+        remaining.sort();
+        let mut expected = other.to_vec();
+        expected.sort();
+        assert_eq!(remaining, expected);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sidecar_files_skip_not_applicable() {
+        let parent = Path::new("/nonexistent");
+        let stem = "测试视频";
+        // 封面、NFO、弹幕不适用，只剩视频内容与字幕
+        let status: u32 = PageStatus::from([6, 7, 6, 6, 7]).into();
+        let files = sidecar_files(parent, stem, status);
+        assert!(files.contains(&parent.join(edl_file_name(stem))));
+        assert!(files.contains(&parent.join(format!("{}.srt", stem))));
+        assert!(!files.contains(&parent.join(nfo_file_name(stem))));
+        assert!(!files.contains(&parent.join(danmaku_file_name(stem))));
+        assert!(!files.contains(&parent.join(format!("{}-poster.jpg", stem))));
+    }
+
+    #[test]
+    fn test_is_in_season_folder_ignores_folder_name() {
+        let video_path = Path::new("/media/番剧/某番剧");
+        assert!(is_in_season_folder(
+            video_path,
+            &video_path.join("Season 01/S01E01.mp4")
+        ));
+        // 自定义的 folder_structure 模板
+        assert!(is_in_season_folder(video_path, &video_path.join("第1季/S01E01.mp4")));
+        assert!(!is_in_season_folder(video_path, &video_path.join("S01E01.mp4")));
+        assert!(!is_in_season_folder(
+            video_path,
+            Path::new("/media/番剧/另一番剧/Season 01/S01E01.mp4")
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+/// M3U 播放列表中的一项
+pub struct PlaylistEntry {
+    pub title: String,
+    /// 时长（秒），未知时为 0，输出为 -1
+    pub duration: u32,
+    /// 本地文件路径或远程播放地址
+    pub location: String,
+}
+
+/// 播放列表中分页的标题，单 P 视频直接使用视频标题
+pub fn entry_title(video_name: &str, page_name: &str, single_page: bool) -> String {
+    if single_page || page_name.is_empty() || page_name == video_name {
+        video_name.to_string()
+    } else {
+        format!("{} - {}", video_name, page_name)
+    }
+}
+
+/// 分页在播放列表中的位置：.strm 文件取其中记录的播放地址，其他文件直接使用本地路径，文件不存在时返回 None
+pub async fn page_location(path: &str) -> Option<String> {
+    let file = Path::new(path);
+    if file.extension().is_some_and(|ext| ext == "strm") {
+        let content = tokio::fs::read_to_string(file).await.ok()?;
+        return content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string);
+    }
+    tokio::fs::try_exists(file)
+        .await
+        .ok()
+        .filter(|exists| *exists)
+        .map(|_| path.to_string())
+}
+
+/// 生成扩展 M3U 格式的播放列表
+pub fn render_m3u(name: &str, entries: &[PlaylistEntry]) -> String {
+    let mut output = String::from("#EXTM3U\n");
+    output.push_str(&format!("#PLAYLIST:{}\n", single_line(name)));
+    for entry in entries {
+        let duration = if entry.duration == 0 {
+            "-1".to_string()
+        } else {
+            entry.duration.to_string()
+        };
+        output.push_str(&format!("#EXTINF:{},{}\n", duration, single_line(&entry.title)));
+        output.push_str(&single_line(&entry.location));
+        output.push('\n');
+    }
+    output
+}
+
+/// M3U 按行解析，标题与路径中不能出现换行
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_m3u() {
+        let entries = vec![
+            PlaylistEntry {
+                title: entry_title("第1话", "第1话", false),
+                duration: 1420,
+                location: "/media/番剧/某番剧/Season 01/S01E01.mp4".to_string(),
+            },
+            PlaylistEntry {
+                title: entry_title("合集视频", "P2\n下", false),
+                duration: 0,
+                location: "https://example.com/video.m4s".to_string(),
+            },
+        ];
+        assert_eq!(
+            render_m3u("某番剧", &entries),
+            "#EXTM3U\n#PLAYLIST:某番剧\n#EXTINF:1420,第1话\n/media/番剧/某番剧/Season 01/S01E01.mp4\n#EXTINF:-1,合集视频 - P2 下\nhttps://example.com/video.m4s\n"
+        );
+        assert_eq!(entry_title("单P视频", "分P名", true), "单P视频");
+    }
+}
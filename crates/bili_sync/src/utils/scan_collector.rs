@@ -1,9 +1,58 @@
-use std::collections::HashMap;
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use bili_sync_entity::{page, video};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
 use tracing::{debug, warn};
 
 use crate::adapter::{VideoSource, VideoSourceEnum};
-use crate::utils::notification::{NewVideoInfo, ScanSummary, SourceScanResult};
+use crate::config::CONFIG_DIR;
+use crate::utils::notification::{NewVideoInfo, RepeatedFailureInfo, ScanSummary, SourceScanResult};
+use crate::utils::status::{
+    PageStatus, VideoStatus, PAGE_SUBTASK_NAMES, STATUS_MAX_RETRY, STATUS_REPEATED_FAILURE, VIDEO_SUBTASK_NAMES,
+};
+
+/// 反复失败的子任务：(视频ID, 分页ID, 子任务下标)
+type FailedSubtaskKey = (i32, Option<i32>, usize);
+
+/// 已提醒过的反复失败子任务的记录文件，保存在配置目录下，重启后不会重复提醒
+const REPORTED_FAILURES_FILE: &str = "reported_failures.json";
+
+/// 已经提醒过的反复失败子任务，首次使用时从记录文件中加载
+static REPORTED_FAILURES: Lazy<Mutex<HashSet<FailedSubtaskKey>>> =
+    Lazy::new(|| Mutex::new(load_reported_failures(&reported_failures_path())));
+
+fn reported_failures_path() -> PathBuf {
+    CONFIG_DIR.join(REPORTED_FAILURES_FILE)
+}
+
+fn load_reported_failures(path: &Path) -> HashSet<FailedSubtaskKey> {
+    let Ok(content) = std::fs::read(path) else {
+        return HashSet::new();
+    };
+    serde_json::from_slice(&content).unwrap_or_else(|e| {
+        warn!("解析反复失败提醒记录失败: {:?} - {}", path, e);
+        HashSet::new()
+    })
+}
+
+async fn save_reported_failures(path: &Path, reported: &HashSet<FailedSubtaskKey>) -> Result<()> {
+    let mut keys: Vec<_> = reported.iter().collect();
+    keys.sort();
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    // 先写入临时文件再重命名，避免写入中断时记录损坏
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, serde_json::to_vec(&keys)?).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
 
 /// 扫描收集器，用于收集每次完整扫描的统计信息
 pub struct ScanCollector {
@@ -31,6 +80,11 @@ impl ScanCollector {
             source_type: video_source.source_type_display(),
             source_name: video_source.source_name_display(),
             new_videos: Vec::new(),
+            filtered_videos: 0,
+            skipped_videos: 0,
+            no_permission_videos: 0,
+            scan_duration: None,
+            error: None,
         };
 
         self.source_results.insert(key, result);
@@ -67,14 +121,54 @@ impl ScanCollector {
         }
     }
 
+    /// 记录被过滤而不下载的视频数量
+    pub fn add_filtered_videos(&mut self, video_source: &VideoSourceEnum, count: usize) {
+        let key = self.get_source_key(video_source);
+        if let Some(result) = self.source_results.get_mut(&key) {
+            result.filtered_videos += count;
+        }
+    }
+
+    /// 记录因发布时间或标题不满足过滤条件而被跳过的视频数量
+    pub fn add_skipped_videos(&mut self, video_source: &VideoSourceEnum, count: usize) {
+        let key = self.get_source_key(video_source);
+        if let Some(result) = self.source_results.get_mut(&key) {
+            result.skipped_videos += count;
+        }
+    }
+
+    /// 记录充电专享且当前账号无权观看的视频数量
+    pub fn add_no_permission_videos(&mut self, video_source: &VideoSourceEnum, count: usize) {
+        let key = self.get_source_key(video_source);
+        if let Some(result) = self.source_results.get_mut(&key) {
+            result.no_permission_videos += count;
+        }
+    }
+
+    /// 记录视频源的扫描耗时，扫描失败时同时记录失败原因
+    pub fn finish_source(&mut self, video_source: &VideoSourceEnum, duration: Duration, error: Option<String>) {
+        let key = self.get_source_key(video_source);
+        if let Some(result) = self.source_results.get_mut(&key) {
+            result.scan_duration = Some(duration);
+            result.error = error;
+        }
+    }
+
     /// 生成扫描摘要
     pub fn generate_summary(self) -> ScanSummary {
         let scan_duration = self.start_time.elapsed();
         let total_new_videos = self.source_results.values().map(|result| result.new_videos.len()).sum();
+        let total_filtered_videos = self.source_results.values().map(|result| result.filtered_videos).sum();
+        let total_skipped_videos = self.source_results.values().map(|result| result.skipped_videos).sum();
+        let total_no_permission_videos = self
+            .source_results
+            .values()
+            .map(|result| result.no_permission_videos)
+            .sum();
 
         debug!(
-            "scan_collector.generate_summary: total_sources={}, total_new_videos={}",
-            self.total_sources, total_new_videos
+            "scan_collector.generate_summary: total_sources={}, total_new_videos={}, total_filtered_videos={}",
+            self.total_sources, total_new_videos, total_filtered_videos
         );
 
         // 详细记录每个源的新视频数量
@@ -89,11 +183,20 @@ impl ScanCollector {
         ScanSummary {
             total_sources: self.total_sources,
             total_new_videos,
+            total_filtered_videos,
+            total_skipped_videos,
+            total_no_permission_videos,
             scan_duration,
+            total_size: 0,
             source_results,
         }
     }
 
+    /// 获取视频源本轮扫描已收集的统计信息
+    pub fn source_result(&self, video_source: &VideoSourceEnum) -> Option<&SourceScanResult> {
+        self.source_results.get(&self.get_source_key(video_source))
+    }
+
     /// 获取当前总的新增视频数量
     #[allow(dead_code)]
     pub fn total_new_videos(&self) -> usize {
@@ -110,6 +213,152 @@ impl ScanCollector {
     }
 }
 
+/// 统计摘要中新增视频已下载文件的总大小
+pub async fn fill_total_size(summary: &mut ScanSummary, connection: &DatabaseConnection) {
+    let video_ids: Vec<i32> = summary
+        .source_results
+        .iter()
+        .flat_map(|result| result.new_videos.iter().filter_map(|video| video.video_id))
+        .collect();
+    if video_ids.is_empty() {
+        return;
+    }
+
+    let page_paths: Vec<Option<String>> = match page::Entity::find()
+        .select_only()
+        .column(page::Column::Path)
+        .filter(page::Column::VideoId.is_in(video_ids))
+        .into_tuple()
+        .all(connection)
+        .await
+    {
+        Ok(paths) => paths,
+        Err(e) => {
+            warn!("查询新增视频的分页路径失败，无法统计下载大小: {}", e);
+            return;
+        }
+    };
+
+    let mut total_size = 0;
+    for path in page_paths.into_iter().flatten() {
+        if let Ok(metadata) = tokio::fs::metadata(&path).await {
+            total_size += metadata.len();
+        }
+    }
+    summary.total_size = total_size;
+}
+
+/// 任一子任务的失败次数处于 [STATUS_REPEATED_FAILURE, STATUS_MAX_RETRY] 区间的筛选条件，
+/// download_status 中每 3 位表示一个子任务的状态
+fn repeatedly_failed_condition(subtask_count: usize) -> Condition {
+    (0..subtask_count).fold(Condition::any(), |condition, offset| {
+        condition.add(Expr::cust(format!(
+            "((download_status >> {}) & 7) BETWEEN {} AND {}",
+            offset * 3,
+            STATUS_REPEATED_FAILURE,
+            STATUS_MAX_RETRY
+        )))
+    })
+}
+
+/// 找出子任务失败次数已达到阈值的视频，只返回上次检测之后新出现的条目，避免每轮扫描重复提醒
+pub async fn detect_repeated_failures(connection: &DatabaseConnection) -> Result<Vec<RepeatedFailureInfo>> {
+    let pages: Vec<(i32, i32, i32, u32)> = page::Entity::find()
+        .select_only()
+        .columns([
+            page::Column::Id,
+            page::Column::VideoId,
+            page::Column::Pid,
+            page::Column::DownloadStatus,
+        ])
+        .filter(repeatedly_failed_condition(PAGE_SUBTASK_NAMES.len()))
+        .into_tuple()
+        .all(connection)
+        .await?;
+    // 分页反复失败的视频即使自身子任务正常，也需要查出标题用于提醒
+    let page_video_ids: Vec<i32> = pages
+        .iter()
+        .map(|(_, video_id, _, _)| *video_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let videos: Vec<(i32, String, String, u32)> = video::Entity::find()
+        .select_only()
+        .columns([
+            video::Column::Id,
+            video::Column::Bvid,
+            video::Column::Name,
+            video::Column::DownloadStatus,
+        ])
+        .filter(video::Column::Deleted.eq(0))
+        .filter(
+            Condition::any()
+                .add(repeatedly_failed_condition(VIDEO_SUBTASK_NAMES.len()))
+                .add(video::Column::Id.is_in(page_video_ids)),
+        )
+        .into_tuple()
+        .all(connection)
+        .await?;
+
+    // 视频ID -> 失败的子任务 (分页ID, 子任务描述, 子任务下标)
+    let mut failed: BTreeMap<i32, Vec<(Option<i32>, String, usize)>> = BTreeMap::new();
+    for (video_id, _, _, download_status) in &videos {
+        for offset in VideoStatus::from(*download_status).repeatedly_failed() {
+            failed
+                .entry(*video_id)
+                .or_default()
+                .push((None, VIDEO_SUBTASK_NAMES[offset].to_string(), offset));
+        }
+    }
+    for (page_id, video_id, pid, download_status) in &pages {
+        for offset in PageStatus::from(*download_status).repeatedly_failed() {
+            failed.entry(*video_id).or_default().push((
+                Some(*page_id),
+                format!("{}(P{})", PAGE_SUBTASK_NAMES[offset], pid),
+                offset,
+            ));
+        }
+    }
+
+    let current: HashSet<FailedSubtaskKey> = failed
+        .iter()
+        .flat_map(|(video_id, subtasks)| {
+            subtasks
+                .iter()
+                .map(move |(page_id, _, offset)| (*video_id, *page_id, *offset))
+        })
+        .collect();
+    // 已恢复（重置或成功）的子任务从记录中移除，之后再次失败时会重新提醒
+    let previous = std::mem::replace(&mut *REPORTED_FAILURES.lock(), current.clone());
+    if previous != current {
+        if let Err(e) = save_reported_failures(&reported_failures_path(), &current).await {
+            warn!("保存反复失败提醒记录失败: {:#}", e);
+        }
+    }
+
+    let video_info: HashMap<i32, (&String, &String)> =
+        videos.iter().map(|(id, bvid, name, _)| (*id, (bvid, name))).collect();
+    let result = failed
+        .into_iter()
+        .filter_map(|(video_id, subtasks)| {
+            let (bvid, title) = video_info.get(&video_id)?;
+            let subtasks: Vec<String> = subtasks
+                .into_iter()
+                .filter(|(page_id, _, offset)| !previous.contains(&(video_id, *page_id, *offset)))
+                .map(|(_, name, _)| name)
+                .collect();
+            (!subtasks.is_empty()).then(|| RepeatedFailureInfo {
+                bvid: bvid.to_string(),
+                title: title.to_string(),
+                subtasks,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    debug!("检测到 {} 个新出现反复失败子任务的视频", result.len());
+    Ok(result)
+}
+
 impl Default for ScanCollector {
     fn default() -> Self {
         Self::new()
@@ -136,3 +385,70 @@ pub fn create_new_video_info(
         video_id: None, // 测试代码中没有video_id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repeatedly_failed_condition() {
+        use sea_orm::sea_query::{Alias, Query, SqliteQueryBuilder};
+        use sea_orm::{ConnectionTrait, Database, Statement};
+
+        let connection = Database::connect("sqlite::memory:").await.unwrap();
+        connection
+            .execute_unprepared("CREATE TABLE t (download_status INTEGER NOT NULL)")
+            .await
+            .unwrap();
+        let statuses: Vec<u32> = vec![
+            0,
+            0b010,
+            0b011,
+            0b100,
+            0b101,
+            0b111_111_111_111_111,
+            0b111_011_111_111_111,
+            0b100_000_000_000_000 | (1 << 31),
+            0b110_101_111_010_001,
+        ];
+        for status in &statuses {
+            connection
+                .execute_unprepared(&format!("INSERT INTO t (download_status) VALUES ({})", status))
+                .await
+                .unwrap();
+        }
+
+        let sql = Query::select()
+            .column(Alias::new("download_status"))
+            .from(Alias::new("t"))
+            .cond_where(repeatedly_failed_condition(PAGE_SUBTASK_NAMES.len()))
+            .to_string(SqliteQueryBuilder);
+        let matched: Vec<u32> = connection
+            .query_all(Statement::from_string(connection.get_database_backend(), sql))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.try_get_by_index::<i64>(0).unwrap() as u32)
+            .collect();
+        let expected: Vec<u32> = statuses
+            .into_iter()
+            .filter(|status| !PageStatus::from(*status).repeatedly_failed().is_empty())
+            .collect();
+        assert_eq!(matched, expected);
+    }
+
+    #[tokio::test]
+    async fn test_reported_failures_persistence() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-reported-failures-{}", std::process::id()));
+        let path = dir.join(REPORTED_FAILURES_FILE);
+        assert!(load_reported_failures(&path).is_empty());
+
+        let reported = HashSet::from([(1, None, 0), (2, Some(3), 4)]);
+        save_reported_failures(&path, &reported).await.unwrap();
+        assert_eq!(load_reported_failures(&path), reported);
+
+        tokio::fs::write(&path, "not json").await.unwrap();
+        assert!(load_reported_failures(&path).is_empty());
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use bili_sync_entity::{collection, favorite, page, scan_history, submission, video, video_source, watch_later};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+use crate::utils::notification::SourceScanResult;
+use crate::utils::scan_id_tracker::SourceType;
+use crate::utils::status::VideoStatus;
+use crate::utils::time_format::now_standard_string;
+
+/// 每个视频源最多保留的扫描记录数量
+const MAX_HISTORY_PER_SOURCE: u64 = 100;
+
+/// 写入一条视频源的扫描记录，`result` 为扫描收集器中该源的统计，扫描失败时传入 `error`
+pub async fn record_source_scan(
+    connection: &DatabaseConnection,
+    source_type: SourceType,
+    source_id: i32,
+    started_at: String,
+    duration: Duration,
+    result: Option<&SourceScanResult>,
+    error: Option<String>,
+) -> Result<()> {
+    let video_ids: Vec<i32> = result
+        .map(|result| result.new_videos.iter().filter_map(|video| video.video_id).collect())
+        .unwrap_or_default();
+    let (new_pages, failed_videos) = if video_ids.is_empty() {
+        (0, 0)
+    } else {
+        let new_pages = page::Entity::find()
+            .filter(page::Column::VideoId.is_in(video_ids.clone()))
+            .count(connection)
+            .await?;
+        let statuses: Vec<u32> = video::Entity::find()
+            .select_only()
+            .column(video::Column::DownloadStatus)
+            .filter(video::Column::Id.is_in(video_ids))
+            .into_tuple()
+            .all(connection)
+            .await?;
+        let failed_videos = statuses
+            .into_iter()
+            .filter(|status| VideoStatus::from(*status).has_failed())
+            .count();
+        (new_pages, failed_videos)
+    };
+
+    scan_history::ActiveModel {
+        source_type: Set(source_type.as_str().to_string()),
+        source_id: Set(source_id),
+        started_at: Set(started_at),
+        duration_ms: Set(duration.as_millis() as i64),
+        new_videos: Set(result.map_or(0, |result| result.new_videos.len()) as i32),
+        new_pages: Set(new_pages as i32),
+        failed_videos: Set(failed_videos as i32),
+        filtered_videos: Set(result.map_or(0, |result| result.filtered_videos) as i32),
+        skipped_videos: Set(result.map_or(0, |result| result.skipped_videos) as i32),
+        error: Set(error),
+        ..Default::default()
+    }
+    .insert(connection)
+    .await?;
+
+    prune_source_history(connection, source_type.as_str(), source_id).await
+}
+
+/// 记录视频源最近一次扫描失败的原因，扫描成功时传入 None 清空
+pub async fn set_source_last_error(
+    connection: &DatabaseConnection,
+    source_type: SourceType,
+    source_id: i32,
+    error: Option<String>,
+) -> Result<()> {
+    let error_at = error.as_ref().map(|_| now_standard_string());
+    match source_type {
+        SourceType::Collection => {
+            collection::Entity::update_many()
+                .col_expr(collection::Column::LastError, Expr::value(error))
+                .col_expr(collection::Column::LastErrorAt, Expr::value(error_at))
+                .filter(collection::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Favorite => {
+            favorite::Entity::update_many()
+                .col_expr(favorite::Column::LastError, Expr::value(error))
+                .col_expr(favorite::Column::LastErrorAt, Expr::value(error_at))
+                .filter(favorite::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Submission => {
+            submission::Entity::update_many()
+                .col_expr(submission::Column::LastError, Expr::value(error))
+                .col_expr(submission::Column::LastErrorAt, Expr::value(error_at))
+                .filter(submission::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::WatchLater => {
+            watch_later::Entity::update_many()
+                .col_expr(watch_later::Column::LastError, Expr::value(error))
+                .col_expr(watch_later::Column::LastErrorAt, Expr::value(error_at))
+                .filter(watch_later::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+        SourceType::Bangumi => {
+            video_source::Entity::update_many()
+                .col_expr(video_source::Column::LastError, Expr::value(error))
+                .col_expr(video_source::Column::LastErrorAt, Expr::value(error_at))
+                .filter(video_source::Column::Id.eq(source_id))
+                .exec(connection)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 只保留视频源最近的扫描记录
+async fn prune_source_history(connection: &DatabaseConnection, source_type: &str, source_id: i32) -> Result<()> {
+    let oldest_kept: Option<i32> = scan_history::Entity::find()
+        .select_only()
+        .column(scan_history::Column::Id)
+        .filter(scan_history::Column::SourceType.eq(source_type))
+        .filter(scan_history::Column::SourceId.eq(source_id))
+        .order_by_desc(scan_history::Column::Id)
+        .offset(MAX_HISTORY_PER_SOURCE - 1)
+        .into_tuple()
+        .one(connection)
+        .await?;
+    if let Some(oldest_kept) = oldest_kept {
+        scan_history::Entity::delete_many()
+            .filter(scan_history::Column::SourceType.eq(source_type))
+            .filter(scan_history::Column::SourceId.eq(source_id))
+            .filter(scan_history::Column::Id.lt(oldest_kept))
+            .exec(connection)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 查询视频源最近的扫描记录，按时间倒序
+pub async fn recent_source_history(
+    connection: &DatabaseConnection,
+    source_type: &str,
+    source_id: i32,
+    limit: u64,
+) -> Result<Vec<scan_history::Model>> {
+    Ok(scan_history::Entity::find()
+        .filter(scan_history::Column::SourceType.eq(source_type))
+        .filter(scan_history::Column::SourceId.eq(source_id))
+        .order_by_desc(scan_history::Column::Id)
+        .limit(limit.min(MAX_HISTORY_PER_SOURCE))
+        .all(connection)
+        .await?)
+}
+
+/// 删除视频源时一并清理其扫描记录
+pub async fn delete_source_history(connection: &DatabaseConnection, source_type: &str, source_id: i32) -> Result<()> {
+    scan_history::Entity::delete_many()
+        .filter(scan_history::Column::SourceType.eq(source_type))
+        .filter(scan_history::Column::SourceId.eq(source_id))
+        .exec(connection)
+        .await?;
+    Ok(())
+}
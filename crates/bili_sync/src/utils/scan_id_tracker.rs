@@ -98,6 +98,8 @@ pub struct VideoSourceWithId {
     pub args: crate::adapter::Args,
     pub path: std::path::PathBuf,
     pub source_type: SourceType,
+    /// 该源的扫描间隔（秒），为空表示使用全局扫描间隔
+    pub scan_interval: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -109,6 +111,31 @@ pub enum SourceType {
     Bangumi,
 }
 
+impl SourceType {
+    /// API 路径中使用的视频源类型名称
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceType::Collection => "collection",
+            SourceType::Favorite => "favorite",
+            SourceType::Submission => "submission",
+            SourceType::WatchLater => "watch_later",
+            SourceType::Bangumi => "bangumi",
+        }
+    }
+
+    /// 解析 API 路径中的视频源类型名称
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "collection" => Some(SourceType::Collection),
+            "favorite" => Some(SourceType::Favorite),
+            "submission" => Some(SourceType::Submission),
+            "watch_later" => Some(SourceType::WatchLater),
+            "bangumi" => Some(SourceType::Bangumi),
+            _ => None,
+        }
+    }
+}
+
 /// 将视频源按新旧分组，并支持断点续传
 pub fn group_sources_by_new_old(
     sources: &[VideoSourceWithId],
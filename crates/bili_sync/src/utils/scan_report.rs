@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use bili_sync_entity::video;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use serde::Serialize;
+use tracing::debug;
+
+use crate::config::CONFIG_DIR;
+use crate::utils::notification::ScanSummary;
+use crate::utils::status::VideoStatus;
+use crate::utils::time_format::now_standard_string;
+
+/// 最新扫描报告的文件名，更早的报告依次命名为 scan_report.1.json、scan_report.2.json ...
+const SCAN_REPORT_FILE: &str = "scan_report";
+/// 最多保留的扫描报告数量（包括最新的一份）
+const MAX_SCAN_REPORTS: usize = 10;
+
+/// 一轮扫描的报告
+#[derive(Debug, Serialize)]
+pub struct ScanReport {
+    pub finished_at: String,
+    pub duration_ms: u64,
+    pub total_sources: usize,
+    pub total_new_videos: usize,
+    pub total_failed_videos: usize,
+    pub total_filtered_videos: usize,
+    pub total_skipped_videos: usize,
+    pub total_no_permission_videos: usize,
+    pub failed_sources: usize,
+    /// 新增视频已下载文件的总大小（字节）
+    pub total_size: u64,
+    pub sources: Vec<SourceScanReport>,
+}
+
+/// 单个视频源的扫描结果
+#[derive(Debug, Serialize)]
+pub struct SourceScanReport {
+    pub source_type: String,
+    pub source_name: String,
+    pub duration_ms: Option<u64>,
+    pub new_videos: usize,
+    /// 新增视频中下载失败的数量
+    pub failed_videos: usize,
+    pub filtered_videos: usize,
+    pub skipped_videos: usize,
+    pub no_permission_videos: usize,
+    pub error: Option<String>,
+}
+
+/// 由扫描摘要生成报告，`failed_video_ids` 为新增视频中下载失败的视频ID
+pub fn build_scan_report(summary: &ScanSummary, failed_video_ids: &HashSet<i32>, finished_at: String) -> ScanReport {
+    let mut sources: Vec<SourceScanReport> = summary
+        .source_results
+        .iter()
+        .map(|result| SourceScanReport {
+            source_type: result.source_type.clone(),
+            source_name: result.source_name.clone(),
+            duration_ms: result.scan_duration.map(|duration| duration.as_millis() as u64),
+            new_videos: result.new_videos.len(),
+            failed_videos: result
+                .new_videos
+                .iter()
+                .filter(|video| video.video_id.is_some_and(|id| failed_video_ids.contains(&id)))
+                .count(),
+            filtered_videos: result.filtered_videos,
+            skipped_videos: result.skipped_videos,
+            no_permission_videos: result.no_permission_videos,
+            error: result.error.clone(),
+        })
+        .collect();
+    sources.sort_by(|a, b| (&a.source_type, &a.source_name).cmp(&(&b.source_type, &b.source_name)));
+
+    ScanReport {
+        finished_at,
+        duration_ms: summary.scan_duration.as_millis() as u64,
+        total_sources: summary.total_sources,
+        total_new_videos: summary.total_new_videos,
+        total_failed_videos: sources.iter().map(|source| source.failed_videos).sum(),
+        total_filtered_videos: summary.total_filtered_videos,
+        total_skipped_videos: summary.total_skipped_videos,
+        total_no_permission_videos: summary.total_no_permission_videos,
+        failed_sources: sources.iter().filter(|source| source.error.is_some()).count(),
+        total_size: summary.total_size,
+        sources,
+    }
+}
+
+/// 将扫描摘要写入配置目录下的 scan_report.json，并轮换保留最近的报告
+pub async fn write_scan_report(summary: &ScanSummary, connection: &DatabaseConnection) -> Result<()> {
+    let video_ids: Vec<i32> = summary
+        .source_results
+        .iter()
+        .flat_map(|result| result.new_videos.iter().filter_map(|video| video.video_id))
+        .collect();
+    let failed_video_ids = if video_ids.is_empty() {
+        HashSet::new()
+    } else {
+        video::Entity::find()
+            .select_only()
+            .columns([video::Column::Id, video::Column::DownloadStatus])
+            .filter(video::Column::Id.is_in(video_ids))
+            .into_tuple::<(i32, u32)>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .filter(|(_, status)| VideoStatus::from(*status).has_failed())
+            .map(|(id, _)| id)
+            .collect()
+    };
+
+    let report = build_scan_report(summary, &failed_video_ids, now_standard_string());
+    let content = serde_json::to_string_pretty(&report)?;
+
+    tokio::fs::create_dir_all(&*CONFIG_DIR).await?;
+    rotate_scan_reports(&CONFIG_DIR, MAX_SCAN_REPORTS).await?;
+    // 先写入临时文件再重命名，避免外部程序读到写了一半的报告
+    let path = report_path(&CONFIG_DIR, 0);
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, content)
+        .await
+        .with_context(|| format!("写入 {} 失败", tmp_path.display()))?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+    debug!("扫描报告已写入 {}", path.display());
+    Ok(())
+}
+
+/// 第 `index` 份报告的路径，0 为最新的报告
+fn report_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(format!("{}.json", SCAN_REPORT_FILE))
+    } else {
+        dir.join(format!("{}.{}.json", SCAN_REPORT_FILE, index))
+    }
+}
+
+/// 将已有的报告依次后移一位，超出保留数量的最早报告被删除
+async fn rotate_scan_reports(dir: &Path, max_reports: usize) -> Result<()> {
+    if max_reports == 0 {
+        return Ok(());
+    }
+    let oldest = report_path(dir, max_reports - 1);
+    if tokio::fs::try_exists(&oldest).await? {
+        tokio::fs::remove_file(&oldest).await?;
+    }
+    for index in (0..max_reports - 1).rev() {
+        let from = report_path(dir, index);
+        if tokio::fs::try_exists(&from).await? {
+            tokio::fs::rename(&from, report_path(dir, index + 1)).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::utils::notification::{NewVideoInfo, SourceScanResult};
+
+    fn new_video(video_id: i32) -> NewVideoInfo {
+        NewVideoInfo {
+            title: format!("视频{}", video_id),
+            bvid: format!("BV{}", video_id),
+            upper_name: "UP".to_string(),
+            source_type: "收藏夹".to_string(),
+            source_name: "默认".to_string(),
+            pubtime: None,
+            episode_number: None,
+            season_number: None,
+            video_id: Some(video_id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_report() {
+        let summary = ScanSummary {
+            total_sources: 2,
+            total_new_videos: 2,
+            total_filtered_videos: 1,
+            total_skipped_videos: 0,
+            total_no_permission_videos: 0,
+            scan_duration: Duration::from_millis(1500),
+            total_size: 1024,
+            source_results: vec![
+                SourceScanResult {
+                    source_type: "收藏夹".to_string(),
+                    source_name: "默认".to_string(),
+                    new_videos: vec![new_video(1), new_video(2)],
+                    filtered_videos: 1,
+                    skipped_videos: 0,
+                    no_permission_videos: 0,
+                    scan_duration: Some(Duration::from_millis(800)),
+                    error: None,
+                },
+                SourceScanResult {
+                    source_type: "合集".to_string(),
+                    source_name: "教程".to_string(),
+                    new_videos: Vec::new(),
+                    filtered_videos: 0,
+                    skipped_videos: 0,
+                    no_permission_videos: 0,
+                    scan_duration: Some(Duration::from_millis(200)),
+                    error: Some("请求失败".to_string()),
+                },
+            ],
+        };
+        let report = build_scan_report(&summary, &HashSet::from([2]), "2026-10-17 12:00:00".to_string());
+        assert_eq!(report.duration_ms, 1500);
+        assert_eq!(report.total_failed_videos, 1);
+        assert_eq!(report.failed_sources, 1);
+        assert_eq!(report.sources[0].source_type, "合集");
+        assert_eq!(report.sources[1].failed_videos, 1);
+        assert_eq!(report.sources[1].duration_ms, Some(800));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["sources"][0]["error"], "请求失败");
+
+        // 轮换时只保留最近的报告
+        let dir = std::env::temp_dir().join(format!("bili-sync-scan-report-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        for round in 0..4 {
+            rotate_scan_reports(&dir, 3).await.unwrap();
+            tokio::fs::write(report_path(&dir, 0), round.to_string()).await.unwrap();
+        }
+        assert_eq!(tokio::fs::read_to_string(report_path(&dir, 0)).await.unwrap(), "3");
+        assert_eq!(tokio::fs::read_to_string(report_path(&dir, 2)).await.unwrap(), "1");
+        assert!(!tokio::fs::try_exists(report_path(&dir, 3)).await.unwrap());
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
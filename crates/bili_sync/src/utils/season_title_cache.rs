@@ -0,0 +1,178 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+/// 全局番剧季度标题缓存，以 season_id 为键
+pub static SEASON_TITLE_CACHE: Lazy<SeasonTitleCache> = Lazy::new(SeasonTitleCache::new);
+
+struct CacheEntry {
+    title: String,
+    inserted_at: Instant,
+    // 最近一次访问的序号，用于在 order 中定位该条目
+    tick: u64,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<String, CacheEntry>,
+    // 访问序号 -> season_id，序号最小的即最久未使用的条目
+    order: BTreeMap<u64, String>,
+    next_tick: u64,
+}
+
+impl CacheInner {
+    fn touch(&mut self, season_id: &str) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        if let Some(entry) = self.entries.get_mut(season_id) {
+            self.order.remove(&entry.tick);
+            entry.tick = tick;
+            self.order.insert(tick, season_id.to_string());
+        }
+    }
+
+    fn remove(&mut self, season_id: &str) {
+        if let Some(entry) = self.entries.remove(season_id) {
+            self.order.remove(&entry.tick);
+        }
+    }
+}
+
+/// 带容量上限与过期时间的 LRU 缓存
+///
+/// 超出容量时淘汰最久未使用的条目，条目过期后视为未命中，由调用方重新获取并写回缓存
+pub struct SeasonTitleCache {
+    inner: Mutex<CacheInner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl SeasonTitleCache {
+    fn new() -> Self {
+        Self {
+            inner: Mutex::new(CacheInner::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// 按配置的过期时间读取缓存
+    pub fn get(&self, season_id: &str) -> Option<String> {
+        let ttl = crate::config::with_config(|bundle| bundle.config.season_title_cache_ttl_secs);
+        self.get_with_ttl(season_id, Duration::from_secs(ttl))
+    }
+
+    /// 按配置的容量上限写入缓存
+    pub fn insert(&self, season_id: &str, title: &str) {
+        let capacity = crate::config::with_config(|bundle| bundle.config.season_title_cache_size);
+        self.insert_with_capacity(season_id, title, capacity);
+    }
+
+    fn get_with_ttl(&self, season_id: &str, ttl: Duration) -> Option<String> {
+        let result = match self.inner.lock() {
+            Ok(mut inner) => match inner.entries.get(season_id) {
+                Some(entry) if entry.inserted_at.elapsed() < ttl => {
+                    let title = entry.title.clone();
+                    inner.touch(season_id);
+                    Some(title)
+                }
+                Some(_) => {
+                    // 已过期，移除后按未命中处理
+                    inner.remove(season_id);
+                    None
+                }
+                None => None,
+            },
+            Err(_) => None,
+        };
+        if result.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn insert_with_capacity(&self, season_id: &str, title: &str, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        inner.remove(season_id);
+        while inner.entries.len() >= capacity {
+            let Some((_, oldest)) = inner.order.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+        inner.entries.insert(
+            season_id.to_string(),
+            CacheEntry {
+                title: title.to_string(),
+                inserted_at: Instant::now(),
+                tick,
+            },
+        );
+        inner.order.insert(tick, season_id.to_string());
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.inner.lock().map(|inner| inner.entries.len()).unwrap_or(0)
+    }
+
+    /// 本次运行期间的缓存命中率，尚未有查询时为空
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let total = hits + self.misses.load(Ordering::Relaxed);
+        if total == 0 {
+            None
+        } else {
+            Some(hits as f64 / total as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = SeasonTitleCache::new();
+        let ttl = Duration::from_secs(60);
+        cache.insert_with_capacity("1", "第一季", 2);
+        cache.insert_with_capacity("2", "第二季", 2);
+        // 访问 1 之后，2 成为最久未使用的条目
+        assert_eq!(cache.get_with_ttl("1", ttl).as_deref(), Some("第一季"));
+        cache.insert_with_capacity("3", "第三季", 2);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get_with_ttl("2", ttl), None);
+        assert_eq!(cache.get_with_ttl("1", ttl).as_deref(), Some("第一季"));
+        assert_eq!(cache.get_with_ttl("3", ttl).as_deref(), Some("第三季"));
+        assert_eq!(cache.hit_rate(), Some(0.75));
+    }
+
+    #[test]
+    fn test_expired_entry_is_miss() {
+        let cache = SeasonTitleCache::new();
+        cache.insert_with_capacity("1", "第一季", 10);
+        assert_eq!(cache.get_with_ttl("1", Duration::ZERO), None);
+        assert_eq!(cache.len(), 0);
+
+        // 过期后重新写入即可再次命中
+        cache.insert_with_capacity("1", "第一季（新）", 10);
+        assert_eq!(
+            cache.get_with_ttl("1", Duration::from_secs(60)).as_deref(),
+            Some("第一季（新）")
+        );
+    }
+}
@@ -3,11 +3,19 @@ use crate::error::ExecutionStatus;
 pub(super) static STATUS_MAX_RETRY: u32 = 0b100;
 pub static STATUS_OK: u32 = 0b111;
 pub static STATUS_COMPLETED: u32 = 1 << 31;
+/// 子任务失败次数达到该值时视为反复失败，需要单独提醒
+pub static STATUS_REPEATED_FAILURE: u32 = 0b011;
+/// 当前账号无权观看（如充电专享视频），跳过执行，也不视为失败
+pub static STATUS_NO_PERMISSION: u32 = 0b101;
+/// 子任务被视频源设置关闭，不需要执行，也不视为失败
+pub static STATUS_NOT_APPLICABLE: u32 = 0b110;
 
 /// 用来表示下载的状态，不想写太多列了，所以仅使用一个 u32 表示。
 /// 从低位开始，固定每三位表示一种子任务的状态。
 /// 子任务状态从 0b000 开始，每执行失败一次将状态加一，最多 0b100（即允许重试 4 次），该值定义为 STATUS_MAX_RETRY。
 /// 如果子任务执行成功，将状态设置为 0b111，该值定义为 STATUS_OK。
+/// 子任务被视频源设置关闭时，将状态设置为 0b110，该值定义为 STATUS_NOT_APPLICABLE，同样认为该子任务已经完成。
+/// 当前账号无权观看视频时，将状态设置为 0b101，该值定义为 STATUS_NO_PERMISSION，同样认为该子任务已经完成。
 /// 子任务达到最大失败次数或者执行成功时，认为该子任务已经完成。
 /// 当所有子任务都已经完成时，为最高位打上标记 1，表示整个下载任务已经完成。
 #[derive(Clone, Copy, Default)]
@@ -33,7 +41,7 @@ impl<const N: usize> Status<N> {
         let mut changed = false;
         for i in 0..N {
             let status = self.get_status(i);
-            if !(status < STATUS_MAX_RETRY || status == STATUS_OK) {
+            if !(status < STATUS_MAX_RETRY || is_finished_without_failure(status)) {
                 self.set_status(i, 0);
                 changed = true;
             }
@@ -66,6 +74,44 @@ impl<const N: usize> Status<N> {
         changed
     }
 
+    /// 按视频源的子任务开关更新状态：关闭且未成功的子任务标记为不适用，重新开启的子任务从头开始执行
+    ///
+    /// 返回值表示 status 是否发生了变化
+    pub fn apply_applicable(&mut self, applicable: [bool; N]) -> bool {
+        let before = self.0;
+        for (i, applicable) in applicable.into_iter().enumerate() {
+            let status = self.get_status(i);
+            if applicable && status == STATUS_NOT_APPLICABLE {
+                self.set_status(i, 0);
+            } else if !applicable && status != STATUS_OK && status != STATUS_NOT_APPLICABLE {
+                self.set_status(i, STATUS_NOT_APPLICABLE);
+            }
+        }
+        let completed = self.should_run().into_iter().all(|x| !x);
+        self.set_completed(completed);
+        self.0 != before
+    }
+
+    /// 子任务是否被视频源设置关闭
+    pub fn is_not_applicable(&self, offset: usize) -> bool {
+        self.get_status(offset) == STATUS_NOT_APPLICABLE
+    }
+
+    /// 将所有未成功的子任务标记为无权限并打上完成标记，之后不再尝试执行
+    pub fn mark_no_permission(&mut self) {
+        for i in 0..N {
+            if self.get_status(i) != STATUS_OK {
+                self.set_status(i, STATUS_NO_PERMISSION);
+            }
+        }
+        self.set_completed(true);
+    }
+
+    /// 是否存在因无权限而跳过的子任务
+    pub fn is_no_permission(&self) -> bool {
+        (0..N).any(|i| self.get_status(i) == STATUS_NO_PERMISSION)
+    }
+
     /// 覆盖某个子任务的状态
     pub fn set(&mut self, offset: usize, status: u32) {
         assert!(status < 0b1000, "status should be less than 0b1000");
@@ -130,6 +176,43 @@ impl<const N: usize> Status<N> {
         self.get_status(offset) < STATUS_MAX_RETRY
     }
 
+    /// 将各子任务的状态转换为可读的描述，形如 `cover=成功 nfo=失败2次`
+    pub fn describe(&self, names: &[&str; N]) -> String {
+        names
+            .iter()
+            .enumerate()
+            .map(|(offset, name)| {
+                let state = match self.get(offset) {
+                    0 => "未开始".to_string(),
+                    status if status == STATUS_OK => "成功".to_string(),
+                    status if status == STATUS_NOT_APPLICABLE => "不适用".to_string(),
+                    status if status == STATUS_NO_PERMISSION => "无权限".to_string(),
+                    status => format!("失败{}次", status),
+                };
+                format!("{}={}", name, state)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 是否存在执行失败过且尚未成功的子任务
+    pub fn has_failed(&self) -> bool {
+        (0..N).any(|i| {
+            let status = self.get_status(i);
+            status != 0 && !is_finished_without_failure(status)
+        })
+    }
+
+    /// 返回失败次数已达到 STATUS_REPEATED_FAILURE 的子任务下标
+    pub fn repeatedly_failed(&self) -> Vec<usize> {
+        (0..N)
+            .filter(|&i| {
+                let status = self.get_status(i);
+                status >= STATUS_REPEATED_FAILURE && !is_finished_without_failure(status)
+            })
+            .collect()
+    }
+
     /// 根据子任务执行结果更新子任务的状态
     fn set_result(&mut self, result: &ExecutionStatus, offset: usize) {
         // 如果任务返回 FixedFailed 状态，那么无论之前的状态如何，都将状态设置为 FixedFailed 的状态
@@ -146,6 +229,11 @@ impl<const N: usize> Status<N> {
     }
 }
 
+/// 成功、不适用与无权限的子任务都已经结束，且不视为失败
+fn is_finished_without_failure(status: u32) -> bool {
+    status == STATUS_OK || status == STATUS_NOT_APPLICABLE || status == STATUS_NO_PERMISSION
+}
+
 impl<const N: usize> From<u32> for Status<N> {
     fn from(status: u32) -> Self {
         Status(status)
@@ -185,15 +273,85 @@ impl<const N: usize> From<[u32; N]> for Status<N> {
 /// 包含五个子任务，从前到后依次是：视频封面、视频信息、Up 主头像、Up 主信息、分 P 下载
 pub type VideoStatus = Status<5>;
 
+/// VideoStatus 各子任务的标识，与子任务顺序一一对应
+pub const VIDEO_SUBTASK_NAMES: [&str; 5] = ["cover", "nfo", "upper_face", "upper_nfo", "page"];
+
 /// 包含五个子任务，从前到后分别是：视频封面、视频内容、视频信息、视频弹幕、视频字幕
 pub type PageStatus = Status<5>;
 
+/// PageStatus 各子任务的标识，与子任务顺序一一对应
+pub const PAGE_SUBTASK_NAMES: [&str; 5] = ["cover", "download", "nfo", "danmaku", "subtitle"];
+
+/// 仅包含一个子任务：将分页文件上传到 WebDAV，上传失败不影响 PageStatus
+pub type UploadStatus = Status<1>;
+
 #[cfg(test)]
 mod test {
     use anyhow::anyhow;
 
     use super::*;
 
+    #[test]
+    fn test_status_describe() {
+        let status = VideoStatus::from([7, 0, 2, 7, 4]);
+        assert_eq!(
+            status.describe(&VIDEO_SUBTASK_NAMES),
+            "cover=成功 nfo=未开始 upper_face=失败2次 upper_nfo=成功 page=失败4次"
+        );
+    }
+
+    #[test]
+    fn test_status_apply_applicable() {
+        let mut status = Status::<3>::from([0, 2, 7]);
+        assert!(status.apply_applicable([true, false, false]));
+        assert_eq!(<[u32; 3]>::from(status), [0, 6, 7]);
+        assert_eq!(status.should_run(), [true, false, false]);
+        assert!(!status.has_failed());
+        assert_eq!(status.describe(&["a", "b", "c"]), "a=未开始 b=不适用 c=成功");
+        // 不适用的子任务不会被当作失败重置
+        assert!(!status.reset_failed());
+
+        status.update_status(&[
+            ExecutionStatus::Succeeded,
+            ExecutionStatus::Skipped,
+            ExecutionStatus::Skipped,
+        ]);
+        assert_eq!(<[u32; 3]>::from(status), [7, 6, 7]);
+        assert!(status.get_completed());
+
+        // 重新开启后从头执行
+        assert!(status.apply_applicable([true, true, true]));
+        assert_eq!(<[u32; 3]>::from(status), [7, 0, 7]);
+        assert!(!status.get_completed());
+        assert!(!status.apply_applicable([true, true, true]));
+    }
+
+    #[test]
+    fn test_status_no_permission() {
+        let mut status = VideoStatus::from([7, 0, 2, 0, 0]);
+        status.mark_no_permission();
+        assert_eq!(<[u32; 5]>::from(status), [7, 5, 5, 5, 5]);
+        assert!(status.get_completed());
+        assert!(status.is_no_permission());
+        assert!(!status.has_failed());
+        assert!(status.repeatedly_failed().is_empty());
+        // 无权限不是失败，重试失败任务时不会被重置
+        assert!(!status.reset_failed());
+        assert_eq!(
+            status.describe(&VIDEO_SUBTASK_NAMES),
+            "cover=成功 nfo=无权限 upper_face=无权限 upper_nfo=无权限 page=无权限"
+        );
+        assert!(status.reset_all());
+        assert!(!status.is_no_permission());
+    }
+
+    #[test]
+    fn test_status_has_failed() {
+        assert!(!Status::<3>::from([0, 7, 7]).has_failed());
+        assert!(Status::<3>::from([1, 7, 7]).has_failed());
+        assert!(Status::<3>::from([7, 4, 0]).has_failed());
+    }
+
     #[test]
     fn test_status_update() {
         let mut status = Status::<3>::default();
@@ -281,4 +439,12 @@ mod test {
         assert!(status.get_completed());
         assert_eq!(<[u32; 5]>::from(status), [4, 7, 7, 7, 7]);
     }
+
+    #[test]
+    fn test_status_repeatedly_failed() {
+        let status = Status::<5>::from([2, 3, 4, 7, 0]);
+        assert_eq!(status.repeatedly_failed(), vec![1, 2]);
+        let status = Status::<5>::from([7, 7, 7, 7, 7]);
+        assert!(status.repeatedly_failed().is_empty());
+    }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, LazyLock};
 
+use parking_lot::Mutex;
 use serde::Serialize;
 
 pub static TASK_STATUS_NOTIFIER: LazyLock<TaskStatusNotifier> = LazyLock::new(TaskStatusNotifier::new);
@@ -56,3 +59,287 @@ impl TaskStatusNotifier {
         self.rx.clone()
     }
 }
+
+pub static SCAN_PROGRESS_NOTIFIER: LazyLock<ScanProgressNotifier> = LazyLock::new(ScanProgressNotifier::new);
+
+/// 推送给前端的扫描进度
+#[derive(Serialize, Clone, Default)]
+pub struct ScanProgress {
+    pub is_scanning: bool,
+    /// 当前正在处理的视频源名称
+    pub source_name: Option<String>,
+    /// 本轮已处理完成的视频源数量
+    pub sources_processed: usize,
+    /// 本轮待扫描的视频源数量
+    pub sources_total: usize,
+    /// 当前视频源已处理的视频数量
+    pub videos_processed: usize,
+    /// 当前视频源待处理的视频数量
+    pub videos_total: usize,
+    /// 本轮已下载完成的分页数量
+    pub pages_downloaded: usize,
+}
+
+/// 扫描进度通知器，扫描流程写入进度，WebSocket 订阅推送
+pub struct ScanProgressNotifier {
+    tx: tokio::sync::watch::Sender<Arc<ScanProgress>>,
+    rx: tokio::sync::watch::Receiver<Arc<ScanProgress>>,
+}
+
+impl ScanProgressNotifier {
+    pub fn new() -> Self {
+        let (tx, rx) = tokio::sync::watch::channel(Arc::new(ScanProgress::default()));
+        Self { tx, rx }
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut ScanProgress)) {
+        self.tx.send_modify(|progress| f(Arc::make_mut(progress)));
+    }
+
+    /// 开始新一轮扫描，重置所有进度
+    pub fn start_scan(&self, sources_total: usize) {
+        self.tx.send_replace(Arc::new(ScanProgress {
+            is_scanning: true,
+            sources_total,
+            ..Default::default()
+        }));
+    }
+
+    /// 开始处理某个视频源
+    pub fn start_source(&self, source_name: String) {
+        self.modify(|progress| {
+            progress.source_name = Some(source_name);
+            progress.videos_processed = 0;
+            progress.videos_total = 0;
+        });
+    }
+
+    /// 当前视频源处理结束
+    pub fn finish_source(&self) {
+        self.modify(|progress| progress.sources_processed += 1);
+    }
+
+    /// 设置当前视频源待下载的视频数量
+    pub fn set_videos_total(&self, videos_total: usize) {
+        self.modify(|progress| {
+            progress.videos_processed = 0;
+            progress.videos_total = videos_total;
+        });
+    }
+
+    /// 当前视频源又处理完成了一个视频
+    pub fn video_processed(&self) {
+        self.modify(|progress| progress.videos_processed += 1);
+    }
+
+    /// 又有一个分页下载完成
+    pub fn page_downloaded(&self) {
+        self.modify(|progress| progress.pages_downloaded += 1);
+    }
+
+    /// 本轮扫描结束，保留最终统计
+    pub fn finish_scan(&self) {
+        self.modify(|progress| {
+            progress.is_scanning = false;
+            progress.source_name = None;
+        });
+    }
+
+    pub fn subscribe(&self) -> tokio::sync::watch::Receiver<Arc<ScanProgress>> {
+        self.rx.clone()
+    }
+}
+
+pub static DOWNLOAD_PROGRESS: LazyLock<DownloadProgressTracker> = LazyLock::new(DownloadProgressTracker::new);
+
+/// 正在下载的单个文件的进度
+#[derive(Serialize, Clone)]
+pub struct ActiveDownload {
+    pub name: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
+/// 推送给前端的下载进度快照
+#[derive(Serialize, Clone, Default)]
+pub struct DownloadProgress {
+    /// 所有下载任务的总速度（字节/秒）
+    pub speed: u64,
+    pub downloads: Vec<ActiveDownload>,
+}
+
+/// 下载进度追踪器，下载器写入进度，WebSocket 定时采样
+pub struct DownloadProgressTracker {
+    /// 累计下载的字节数，用于计算速度
+    total_downloaded: AtomicU64,
+    next_id: AtomicU64,
+    active: Mutex<HashMap<u64, ActiveDownload>>,
+}
+
+impl DownloadProgressTracker {
+    fn new() -> Self {
+        Self {
+            total_downloaded: AtomicU64::new(0),
+            next_id: AtomicU64::new(0),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 登记一个新的下载任务，返回的句柄在 drop 时自动移除该任务
+    pub fn start(&self, name: impl Into<String>, total_bytes: Option<u64>) -> DownloadProgressHandle<'_> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.active.lock().insert(
+            id,
+            ActiveDownload {
+                name: name.into(),
+                downloaded_bytes: 0,
+                total_bytes,
+            },
+        );
+        DownloadProgressHandle { tracker: self, id }
+    }
+
+    pub fn total_downloaded(&self) -> u64 {
+        self.total_downloaded.load(Ordering::Relaxed)
+    }
+
+    pub fn active_downloads(&self) -> Vec<ActiveDownload> {
+        self.active.lock().values().cloned().collect()
+    }
+}
+
+pub struct DownloadProgressHandle<'a> {
+    tracker: &'a DownloadProgressTracker,
+    id: u64,
+}
+
+impl DownloadProgressHandle<'_> {
+    /// 在已下载字节数上累加
+    pub fn advance(&self, bytes: u64) {
+        if let Some(download) = self.tracker.active.lock().get_mut(&self.id) {
+            download.downloaded_bytes += bytes;
+        }
+        self.tracker.total_downloaded.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// 设置断点续传时已有的字节数，不计入下载速度
+    pub fn resume_from(&self, bytes: u64) {
+        if let Some(download) = self.tracker.active.lock().get_mut(&self.id) {
+            download.downloaded_bytes = bytes;
+        }
+    }
+
+    /// 直接设置已下载字节数，适用于只能查询到总进度的下载器（如 aria2）
+    pub fn update(&self, downloaded_bytes: u64, total_bytes: Option<u64>) {
+        let delta = {
+            let mut active = self.tracker.active.lock();
+            let Some(download) = active.get_mut(&self.id) else {
+                return;
+            };
+            let delta = downloaded_bytes.saturating_sub(download.downloaded_bytes);
+            download.downloaded_bytes = downloaded_bytes;
+            if total_bytes.is_some() {
+                download.total_bytes = total_bytes;
+            }
+            delta
+        };
+        self.tracker.total_downloaded.fetch_add(delta, Ordering::Relaxed);
+    }
+}
+
+impl Drop for DownloadProgressHandle<'_> {
+    fn drop(&mut self) {
+        self.tracker.active.lock().remove(&self.id);
+    }
+}
+
+/// 根据两次采样之间的累计下载量计算下载速度
+pub struct DownloadSpeedSampler {
+    last_total: u64,
+    last_instant: std::time::Instant,
+}
+
+impl DownloadSpeedSampler {
+    pub fn new() -> Self {
+        Self {
+            last_total: DOWNLOAD_PROGRESS.total_downloaded(),
+            last_instant: std::time::Instant::now(),
+        }
+    }
+
+    pub fn sample(&mut self) -> DownloadProgress {
+        let total = DOWNLOAD_PROGRESS.total_downloaded();
+        let now = std::time::Instant::now();
+        let elapsed = now.duration_since(self.last_instant).as_secs_f64();
+        let speed = if elapsed > 0.0 {
+            (total.saturating_sub(self.last_total) as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        self.last_total = total;
+        self.last_instant = now;
+        DownloadProgress {
+            speed,
+            downloads: DOWNLOAD_PROGRESS.active_downloads(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_progress_notifier() {
+        let notifier = ScanProgressNotifier::new();
+        let rx = notifier.subscribe();
+
+        notifier.start_scan(2);
+        notifier.start_source("收藏夹 测试".to_string());
+        notifier.set_videos_total(3);
+        notifier.video_processed();
+        notifier.page_downloaded();
+        notifier.page_downloaded();
+        {
+            let progress = rx.borrow();
+            assert!(progress.is_scanning);
+            assert_eq!(progress.source_name.as_deref(), Some("收藏夹 测试"));
+            assert_eq!((progress.videos_processed, progress.videos_total), (1, 3));
+            assert_eq!(progress.pages_downloaded, 2);
+        }
+
+        // 切换视频源时重置视频计数，分页计数按整轮累计
+        notifier.finish_source();
+        notifier.start_source("合集 测试".to_string());
+        {
+            let progress = rx.borrow();
+            assert_eq!(progress.sources_processed, 1);
+            assert_eq!((progress.videos_processed, progress.videos_total), (0, 0));
+            assert_eq!(progress.pages_downloaded, 2);
+        }
+
+        notifier.finish_scan();
+        assert!(!rx.borrow().is_scanning);
+        assert!(rx.borrow().source_name.is_none());
+    }
+
+    #[test]
+    fn test_download_progress_handle() {
+        let tracker = DownloadProgressTracker::new();
+        {
+            let handle = tracker.start("video.mp4", Some(100));
+            handle.advance(30);
+            handle.advance(20);
+            let downloads = tracker.active_downloads();
+            assert_eq!(downloads.len(), 1);
+            assert_eq!(downloads[0].downloaded_bytes, 50);
+            assert_eq!(downloads[0].total_bytes, Some(100));
+
+            // 绝对进度更新只累加增量
+            handle.update(80, None);
+            assert_eq!(tracker.total_downloaded(), 80);
+        }
+        assert!(tracker.active_downloads().is_empty(), "句柄 drop 后应移除任务");
+        assert_eq!(tracker.total_downloaded(), 80);
+    }
+}
@@ -3,11 +3,14 @@
 //! 本模块提供统一的时间格式化和解析功能
 //! 标准格式：YYYY-MM-DD HH:MM:SS (不含毫秒和时区)
 
-use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 
 /// 标准时间格式
 pub const STANDARD_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
+/// 日期格式，用于视频源的发布时间窗口
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
 /// 北京时区 (UTC+8)
 pub const BEIJING_OFFSET: i32 = 8 * 3600;
 
@@ -97,6 +100,24 @@ pub fn timestamp_to_standard_string(timestamp: i64) -> String {
     timestamp_to_beijing_string(timestamp)
 }
 
+/// 解析 YYYY-MM-DD 格式的日期字符串
+pub fn parse_date_string(date_str: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date_str.trim(), DATE_FORMAT).ok()
+}
+
+/// 判断发布时间是否位于 [after, before] 窗口内（按北京时间的日期比较，两端均包含）
+/// 未设置或无法解析的边界视为不限制
+pub fn pubtime_in_window(pubtime: &DateTime<Utc>, after: Option<&str>, before: Option<&str>) -> bool {
+    let date = pubtime.with_timezone(&beijing_timezone()).date_naive();
+    if after.and_then(parse_date_string).is_some_and(|after| date < after) {
+        return false;
+    }
+    if before.and_then(parse_date_string).is_some_and(|before| date > before) {
+        return false;
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +147,16 @@ mod tests {
             assert!(parsed.is_some(), "Failed to parse: {}", format);
         }
     }
+
+    #[test]
+    fn test_pubtime_in_window() {
+        // 北京时间 2024-03-01 00:30:00，对应 UTC 2024-02-29 16:30:00
+        let pubtime = Utc.with_ymd_and_hms(2024, 2, 29, 16, 30, 0).unwrap();
+        assert!(pubtime_in_window(&pubtime, None, None));
+        assert!(pubtime_in_window(&pubtime, Some("2024-03-01"), Some("2024-03-01")));
+        assert!(!pubtime_in_window(&pubtime, Some("2024-03-02"), None));
+        assert!(!pubtime_in_window(&pubtime, None, Some("2024-02-29")));
+        // 无法解析的边界视为不限制
+        assert!(pubtime_in_window(&pubtime, Some("invalid"), None));
+    }
 }
@@ -0,0 +1,83 @@
+//! 按标题关键词筛选视频源中的视频
+use crate::utils::keyword_matcher::{build_matchers, KeywordMatcher};
+
+pub struct TitleFilter {
+    include: Vec<KeywordMatcher>,
+    exclude: Vec<KeywordMatcher>,
+}
+
+impl TitleFilter {
+    /// 根据包含/排除关键词构建过滤器，两者均为空时返回 None
+    pub fn new(include: &[String], exclude: &[String], regex_mode: bool) -> Option<Self> {
+        let filter = Self {
+            include: build_matchers(include, regex_mode, "标题过滤"),
+            exclude: build_matchers(exclude, regex_mode, "标题过滤"),
+        };
+        if filter.include.is_empty() && filter.exclude.is_empty() {
+            None
+        } else {
+            Some(filter)
+        }
+    }
+
+    /// 从数据库中以 JSON 数组保存的关键词构建过滤器
+    pub fn from_columns(include: Option<&str>, exclude: Option<&str>, regex_mode: bool) -> Option<Self> {
+        let parse = |json_str: Option<&str>| -> Vec<String> {
+            json_str
+                .and_then(|json_str| match serde_json::from_str::<Vec<String>>(json_str) {
+                    Ok(terms) => Some(terms),
+                    Err(e) => {
+                        warn!("解析标题过滤关键词 JSON 失败: {}", e);
+                        None
+                    }
+                })
+                .unwrap_or_default()
+        };
+        Self::new(&parse(include), &parse(exclude), regex_mode)
+    }
+
+    /// 判断标题是否应被跳过，返回跳过原因，None 表示保留
+    pub fn skip_reason(&self, title: &str) -> Option<String> {
+        let lowercase_title = title.to_lowercase();
+        if let Some(matcher) = self
+            .exclude
+            .iter()
+            .find(|matcher| matcher.is_match(title, &lowercase_title))
+        {
+            return Some(format!("命中排除关键词「{}」", matcher.term()));
+        }
+        if !self.include.is_empty()
+            && !self
+                .include
+                .iter()
+                .any(|matcher| matcher.is_match(title, &lowercase_title))
+        {
+            return Some("未命中任何包含关键词".to_string());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_filter() {
+        assert!(TitleFilter::new(&[], &[" ".to_string()], false).is_none());
+
+        let filter = TitleFilter::new(&["周报".to_string()], &["PV".to_string()], false).unwrap();
+        assert!(filter.skip_reason("科技周报 第10期").is_none());
+        assert!(filter.skip_reason("科技周报 预告pv").is_some());
+        assert!(filter.skip_reason("日常vlog").is_some());
+
+        let filter = TitleFilter::new(&[], &[r"^\[直播回放\]".to_string()], true).unwrap();
+        assert!(filter.skip_reason("[直播回放] 1月1日").is_some());
+        assert!(filter.skip_reason("新年快乐 [直播回放]").is_none());
+
+        assert!(TitleFilter::from_columns(Some(r#"["周报"]"#), None, false)
+            .unwrap()
+            .skip_reason("周报")
+            .is_none());
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use bili_sync_entity::*;
+use sea_orm::{DatabaseConnection, EntityTrait, QuerySelect};
+use tracing::{debug, info, warn};
+
+use crate::config::CONFIG_DIR;
+
+/// 回收站文件夹名，位于视频源根目录下
+pub const TRASH_DIR_NAME: &str = ".trash";
+/// 记录所有创建过回收站的目录，视频源被删除后仍能通过它找到对应的回收站
+const TRASH_INDEX_FILE: &str = "trash_roots.json";
+
+static TRASH_INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// 删除本地文件的方式：直接删除，或移动到视频源根目录下 `.trash/<时间戳>/` 中并保留相对路径
+pub enum FileRemover {
+    Permanent,
+    Trash(TrashBatch),
+}
+
+impl FileRemover {
+    /// 按 delete_to_trash 配置创建，同一次删除操作共用一个回收站批次
+    pub fn new(source_root: &Path) -> Self {
+        if crate::config::with_config(|bundle| bundle.config.delete_to_trash) {
+            FileRemover::Trash(TrashBatch::new(source_root))
+        } else {
+            FileRemover::Permanent
+        }
+    }
+
+    pub fn remove_file(&self, path: &Path) -> io::Result<()> {
+        match self {
+            FileRemover::Permanent => std::fs::remove_file(path),
+            FileRemover::Trash(batch) => batch.move_in(path),
+        }
+    }
+
+    pub fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        match self {
+            FileRemover::Permanent => std::fs::remove_dir_all(path),
+            FileRemover::Trash(batch) => batch.move_in(path),
+        }
+    }
+}
+
+/// 一次删除操作对应的回收站批次，批次目录在第一次移入文件时才创建
+pub struct TrashBatch {
+    source_root: PathBuf,
+    index_path: PathBuf,
+    // 回收站所在目录 -> 本批次的目录
+    dirs: Mutex<HashMap<PathBuf, PathBuf>>,
+}
+
+impl TrashBatch {
+    fn new(source_root: &Path) -> Self {
+        Self::with_index(source_root, trash_index_path())
+    }
+
+    fn with_index(source_root: &Path, index_path: PathBuf) -> Self {
+        Self {
+            source_root: source_root.to_path_buf(),
+            index_path,
+            dirs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn batch_dir(&self, root: &Path) -> io::Result<PathBuf> {
+        let mut dirs = self.dirs.lock().map_err(|_| io::Error::other("回收站状态异常"))?;
+        if let Some(dir) = dirs.get(root) {
+            return Ok(dir.clone());
+        }
+        let trash_dir = root.join(TRASH_DIR_NAME);
+        let name = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let mut dir = trash_dir.join(&name);
+        let mut suffix = 1;
+        while dir.exists() {
+            dir = trash_dir.join(format!("{}-{}", name, suffix));
+            suffix += 1;
+        }
+        std::fs::create_dir_all(&dir)?;
+        if let Err(e) = record_trash_root(&self.index_path, root) {
+            warn!("记录回收站位置失败: {:?} - {}", root, e);
+        }
+        dirs.insert(root.to_path_buf(), dir.clone());
+        Ok(dir)
+    }
+
+    fn move_in(&self, path: &Path) -> io::Result<()> {
+        // 不在视频源根目录下的文件放入其所在目录的回收站
+        let (root, relative) = match path.strip_prefix(&self.source_root) {
+            Ok(relative) if !relative.as_os_str().is_empty() => (self.source_root.as_path(), relative),
+            _ => {
+                let parent = path.parent().ok_or_else(|| io::Error::other("无法确定回收站位置"))?;
+                let file_name = path.file_name().ok_or_else(|| io::Error::other("无法确定文件名"))?;
+                (parent, Path::new(file_name))
+            }
+        };
+        let target = self.batch_dir(root)?.join(relative);
+        move_path(path, &target)?;
+        info!("已移动到回收站: {:?} -> {:?}", path, target);
+        Ok(())
+    }
+}
+
+/// 移动文件或文件夹，跨文件系统无法重命名时退化为复制后删除
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        std::fs::remove_dir_all(from)
+    } else {
+        std::fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
+fn trash_index_path() -> PathBuf {
+    CONFIG_DIR.join(TRASH_INDEX_FILE)
+}
+
+fn load_trash_roots(index_path: &Path) -> Vec<PathBuf> {
+    let Ok(content) = std::fs::read(index_path) else {
+        return Vec::new();
+    };
+    serde_json::from_slice(&content).unwrap_or_else(|e| {
+        warn!("解析回收站记录失败: {:?} - {}", index_path, e);
+        Vec::new()
+    })
+}
+
+fn save_trash_roots(index_path: &Path, roots: &[PathBuf]) -> io::Result<()> {
+    if let Some(parent) = index_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = index_path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec_pretty(roots)?)?;
+    std::fs::rename(&tmp_path, index_path)
+}
+
+fn record_trash_root(index_path: &Path, root: &Path) -> io::Result<()> {
+    let _guard = TRASH_INDEX_LOCK
+        .lock()
+        .map_err(|_| io::Error::other("回收站状态异常"))?;
+    let mut roots = load_trash_roots(index_path);
+    if roots.iter().any(|recorded| recorded == root) {
+        return Ok(());
+    }
+    roots.push(root.to_path_buf());
+    save_trash_roots(index_path, &roots)
+}
+
+/// 读取记录过的回收站所在目录，回收站已不存在的目录会从记录中移除
+fn recorded_trash_roots(index_path: &Path) -> Vec<PathBuf> {
+    let Ok(_guard) = TRASH_INDEX_LOCK.lock() else {
+        return Vec::new();
+    };
+    let mut roots = load_trash_roots(index_path);
+    let recorded = roots.len();
+    roots.retain(|root| root.join(TRASH_DIR_NAME).is_dir());
+    if roots.len() != recorded {
+        if let Err(e) = save_trash_roots(index_path, &roots) {
+            warn!("更新回收站记录失败: {:?} - {}", index_path, e);
+        }
+    }
+    roots
+}
+
+/// 回收站中的一个批次
+pub struct TrashEntry {
+    pub path: PathBuf,
+    pub source_root: PathBuf,
+    pub modified: Option<SystemTime>,
+    pub file_count: usize,
+}
+
+/// 所有视频源的根目录，回收站只会出现在这些目录下
+async fn source_roots(connection: &DatabaseConnection) -> Result<Vec<PathBuf>> {
+    let mut roots: Vec<String> = Vec::new();
+    roots.extend(
+        collection::Entity::find()
+            .select_only()
+            .column(collection::Column::Path)
+            .into_tuple::<String>()
+            .all(connection)
+            .await?,
+    );
+    roots.extend(
+        favorite::Entity::find()
+            .select_only()
+            .column(favorite::Column::Path)
+            .into_tuple::<String>()
+            .all(connection)
+            .await?,
+    );
+    roots.extend(
+        submission::Entity::find()
+            .select_only()
+            .column(submission::Column::Path)
+            .into_tuple::<String>()
+            .all(connection)
+            .await?,
+    );
+    roots.extend(
+        watch_later::Entity::find()
+            .select_only()
+            .column(watch_later::Column::Path)
+            .into_tuple::<String>()
+            .all(connection)
+            .await?,
+    );
+    roots.extend(
+        video_source::Entity::find()
+            .select_only()
+            .column(video_source::Column::Path)
+            .into_tuple::<String>()
+            .all(connection)
+            .await?,
+    );
+    let mut roots: Vec<PathBuf> = roots
+        .into_iter()
+        .filter(|root| !root.trim().is_empty())
+        .map(PathBuf::from)
+        .collect();
+    roots.sort();
+    roots.dedup();
+    Ok(roots)
+}
+
+/// 视频所属视频源的根目录，找不到时返回 None
+pub async fn video_source_root(connection: &DatabaseConnection, video: &video::Model) -> Option<PathBuf> {
+    let path = if let Some(id) = video.collection_id {
+        collection::Entity::find_by_id(id)
+            .one(connection)
+            .await
+            .ok()?
+            .map(|s| s.path)
+    } else if let Some(id) = video.favorite_id {
+        favorite::Entity::find_by_id(id)
+            .one(connection)
+            .await
+            .ok()?
+            .map(|s| s.path)
+    } else if let Some(id) = video.submission_id {
+        submission::Entity::find_by_id(id)
+            .one(connection)
+            .await
+            .ok()?
+            .map(|s| s.path)
+    } else if let Some(id) = video.watch_later_id {
+        watch_later::Entity::find_by_id(id)
+            .one(connection)
+            .await
+            .ok()?
+            .map(|s| s.path)
+    } else if let Some(id) = video.source_id {
+        video_source::Entity::find_by_id(id)
+            .one(connection)
+            .await
+            .ok()?
+            .map(|s| s.path)
+    } else {
+        None
+    };
+    path.filter(|path| !path.trim().is_empty()).map(PathBuf::from)
+}
+
+/// 单个视频删除时使用的删除方式，找不到视频源时以视频所在文件夹的上一级作为回收站位置
+pub async fn remover_for_video(connection: &DatabaseConnection, video: &video::Model) -> FileRemover {
+    let root = match video_source_root(connection, video).await {
+        Some(root) => root,
+        None => {
+            let video_path = Path::new(&video.path);
+            video_path.parent().unwrap_or(video_path).to_path_buf()
+        }
+    };
+    FileRemover::new(&root)
+}
+
+fn count_files(path: &Path) -> usize {
+    match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    count_files(&path)
+                } else {
+                    1
+                }
+            })
+            .sum(),
+        Err(_) => 0,
+    }
+}
+
+/// 列出所有回收站批次，按时间倒序
+///
+/// 除了现有视频源的根目录，还包括记录过的回收站目录，已删除视频源的回收站同样可以恢复和过期清理
+pub async fn list_trash(connection: &DatabaseConnection) -> Result<Vec<TrashEntry>> {
+    let mut roots = source_roots(connection).await?;
+    roots.extend(recorded_trash_roots(&trash_index_path()));
+    roots.sort();
+    roots.dedup();
+    Ok(collect_trash_entries(&roots))
+}
+
+fn collect_trash_entries(roots: &[PathBuf]) -> Vec<TrashEntry> {
+    let mut entries = Vec::new();
+    for root in roots {
+        let Ok(batches) = std::fs::read_dir(root.join(TRASH_DIR_NAME)) else {
+            continue;
+        };
+        for batch in batches.flatten() {
+            let path = batch.path();
+            if !path.is_dir() {
+                continue;
+            }
+            entries.push(TrashEntry {
+                modified: batch.metadata().and_then(|meta| meta.modified()).ok(),
+                file_count: count_files(&path),
+                source_root: root.clone(),
+                path,
+            });
+        }
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    entries
+}
+
+/// 将回收站批次中的文件移回原位置，原位置已存在的文件会被跳过并保留在回收站中
+///
+/// 返回 (恢复的文件数, 跳过的原路径)
+pub fn restore_batch(batch_dir: &Path) -> Result<(usize, Vec<String>)> {
+    let Some(trash_dir) = batch_dir.parent() else {
+        bail!("无效的回收站路径: {:?}", batch_dir);
+    };
+    if trash_dir.file_name().is_none_or(|name| name != TRASH_DIR_NAME) || !batch_dir.is_dir() {
+        bail!("不是有效的回收站批次: {:?}", batch_dir);
+    }
+    let source_root = trash_dir
+        .parent()
+        .with_context(|| format!("无法确定回收站所属目录: {:?}", batch_dir))?;
+
+    let mut restored = 0;
+    let mut skipped = Vec::new();
+    restore_dir(batch_dir, batch_dir, source_root, &mut restored, &mut skipped)?;
+    // 全部恢复后删除空的批次目录
+    if skipped.is_empty() {
+        if let Err(e) = std::fs::remove_dir_all(batch_dir) {
+            warn!("删除空的回收站批次失败: {:?} - {}", batch_dir, e);
+        }
+    }
+    Ok((restored, skipped))
+}
+
+fn restore_dir(
+    dir: &Path,
+    batch_dir: &Path,
+    source_root: &Path,
+    restored: &mut usize,
+    skipped: &mut Vec<String>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            restore_dir(&path, batch_dir, source_root, restored, skipped)?;
+            continue;
+        }
+        let relative = path.strip_prefix(batch_dir)?;
+        let target = source_root.join(relative);
+        if target.exists() {
+            skipped.push(target.to_string_lossy().to_string());
+            continue;
+        }
+        move_path(&path, &target).with_context(|| format!("恢复文件失败: {:?}", target))?;
+        debug!("已从回收站恢复: {:?}", target);
+        *restored += 1;
+    }
+    Ok(())
+}
+
+/// 清理超过保留天数的回收站批次，返回清理的批次数，保留天数为 0 时不清理
+pub async fn cleanup_expired_trash(connection: &DatabaseConnection) -> Result<usize> {
+    let retention_days = crate::config::with_config(|bundle| bundle.config.trash_retention_days);
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    let max_age = Duration::from_secs(retention_days as u64 * 24 * 60 * 60);
+    let mut removed = 0;
+    for entry in list_trash(connection).await? {
+        let expired = entry
+            .modified
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > max_age);
+        if !expired {
+            continue;
+        }
+        match std::fs::remove_dir_all(&entry.path) {
+            Ok(()) => {
+                removed += 1;
+                info!(
+                    "回收站批次超过保留天数 {} 天，已永久删除: {:?}",
+                    retention_days, entry.path
+                );
+            }
+            Err(e) => warn!("清理回收站批次失败: {:?} - {}", entry.path, e),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_and_restore() {
+        let root = std::env::temp_dir().join(format!("bili_sync_trash_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let video_dir = root.join("视频A");
+        std::fs::create_dir_all(&video_dir).unwrap();
+        std::fs::write(video_dir.join("视频A.mp4"), b"video").unwrap();
+        std::fs::write(video_dir.join("视频A.nfo"), b"nfo").unwrap();
+
+        let index_path = root.join(TRASH_INDEX_FILE);
+        let batch = TrashBatch::with_index(&root, index_path.clone());
+        batch.move_in(&video_dir.join("视频A.nfo")).unwrap();
+        batch.move_in(&video_dir).unwrap();
+        assert!(!video_dir.exists());
+
+        let batch_dir = batch.batch_dir(&root).unwrap();
+        assert_eq!(batch_dir.parent().unwrap(), root.join(TRASH_DIR_NAME));
+        assert!(batch_dir.join("视频A").join("视频A.mp4").exists());
+        assert_eq!(count_files(&batch_dir), 2);
+        // 回收站位置被记录下来，不依赖视频源是否仍然存在
+        let recorded = recorded_trash_roots(&index_path);
+        assert_eq!(recorded, vec![root.clone()]);
+        let entries = collect_trash_entries(&recorded);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, batch_dir);
+
+        // 原位置已存在的文件会被跳过
+        std::fs::create_dir_all(&video_dir).unwrap();
+        std::fs::write(video_dir.join("视频A.nfo"), b"new nfo").unwrap();
+        let (restored, skipped) = restore_batch(&batch_dir).unwrap();
+        assert_eq!(restored, 1);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(std::fs::read(video_dir.join("视频A.mp4")).unwrap(), b"video");
+        assert_eq!(std::fs::read(video_dir.join("视频A.nfo")).unwrap(), b"new nfo");
+
+        assert!(restore_batch(&video_dir).is_err());
+
+        // 回收站被清空后不再保留记录
+        std::fs::remove_dir_all(root.join(TRASH_DIR_NAME)).unwrap();
+        assert!(recorded_trash_roots(&index_path).is_empty());
+        assert!(load_trash_roots(&index_path).is_empty());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
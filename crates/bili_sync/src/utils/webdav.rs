@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, Method, StatusCode};
+use tokio_util::io::ReaderStream;
+
+use crate::config::WebDavConfig;
+
+/// 简单的 WebDAV 客户端，仅支持创建目录与上传文件
+pub struct WebDavClient {
+    client: Client,
+    base_url: reqwest::Url,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl WebDavClient {
+    /// 根据配置构建客户端，未配置 WebDAV 地址时返回 None
+    pub fn from_config(config: &WebDavConfig) -> Result<Option<Self>> {
+        if !config.is_configured() {
+            return Ok(None);
+        }
+        config.validate()?;
+        let url = config.url.as_deref().unwrap_or_default().trim();
+        let mut base_url = reqwest::Url::parse(url).context("WebDAV 地址无效")?;
+        let base_path = config.base_path.trim_matches('/');
+        if !base_path.is_empty() {
+            base_url
+                .path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("WebDAV 地址无法作为基础路径"))?
+                .pop_if_empty()
+                .extend(base_path.split('/').filter(|s| !s.is_empty()));
+        }
+        Ok(Some(Self {
+            client: Client::new(),
+            base_url,
+            username: config.username.clone().filter(|s| !s.is_empty()),
+            password: config.password.clone(),
+        }))
+    }
+
+    /// 将以 / 分隔的相对路径拼接到基础地址上
+    fn remote_url(&self, segments: &[&str]) -> Result<reqwest::Url> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("WebDAV 地址无法作为基础路径"))?
+            .pop_if_empty()
+            .extend(segments.iter().filter(|s| !s.is_empty()));
+        Ok(url)
+    }
+
+    fn request(&self, method: Method, url: reqwest::Url) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.username {
+            Some(username) => request.basic_auth(username, self.password.as_ref()),
+            None => request,
+        }
+    }
+
+    /// 逐级创建远端目录，目录已存在时忽略错误
+    pub async fn ensure_dir(&self, segments: &[&str]) -> Result<()> {
+        for i in 1..=segments.len() {
+            let url = self.remote_url(&segments[..i])?;
+            let response = self
+                .request(Method::from_bytes(b"MKCOL")?, url.clone())
+                .send()
+                .await
+                .with_context(|| format!("创建 WebDAV 目录 {} 失败", url))?;
+            let status = response.status();
+            // 405 表示目录已存在
+            if !(status.is_success() || status == StatusCode::METHOD_NOT_ALLOWED) {
+                bail!("创建 WebDAV 目录 {} 失败，状态码：{}", url, status);
+            }
+        }
+        Ok(())
+    }
+
+    /// 上传本地文件到远端路径，segments 的最后一项为文件名
+    pub async fn upload_file(&self, local: &Path, segments: &[&str]) -> Result<()> {
+        let url = self.remote_url(segments)?;
+        let file = tokio::fs::File::open(local)
+            .await
+            .with_context(|| format!("打开本地文件 {} 失败", local.display()))?;
+        let length = file.metadata().await?.len();
+        let response = self
+            .request(Method::PUT, url.clone())
+            .header(reqwest::header::CONTENT_LENGTH, length)
+            .body(reqwest::Body::wrap_stream(ReaderStream::new(file)))
+            .send()
+            .await
+            .with_context(|| format!("上传文件到 {} 失败", url))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("上传文件到 {} 失败，状态码：{}", url, status);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_url() {
+        let config = WebDavConfig {
+            url: Some("https://dav.example.com/remote.php/dav/".to_owned()),
+            base_path: "/bili/sync/".to_owned(),
+            ..Default::default()
+        };
+        let client = WebDavClient::from_config(&config).unwrap().unwrap();
+        let url = client.remote_url(&["收藏夹", "视频 1", "a#b.mp4"]).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://dav.example.com/remote.php/dav/bili/sync/%E6%94%B6%E8%97%8F%E5%A4%B9/%E8%A7%86%E9%A2%91%201/a%23b.mp4"
+        );
+        assert!(WebDavClient::from_config(&WebDavConfig::default()).unwrap().is_none());
+    }
+}
@@ -12,25 +12,35 @@ use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseBackend, Statement, TransactionTrait};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
-use tokio::sync::Semaphore;
+use tokio::sync::{OnceCell, Semaphore};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
+use crate::utils::season_title_cache::SEASON_TITLE_CACHE;
 use crate::utils::time_format::now_standard_string;
 
-// 全局番剧季度标题缓存
 lazy_static::lazy_static! {
-    pub static ref SEASON_TITLE_CACHE: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
+    /// 所有视频源共享的视频下载信号量及其容量，并行扫描多个视频源时下载总并发仍受 concurrent_limit.video 限制
+    static ref VIDEO_DOWNLOAD_SEMAPHORE: Mutex<(usize, Arc<Semaphore>)> = Mutex::new((0, Arc::new(Semaphore::new(0))));
+    /// 各分页连续因下载中断而保留续传进度的次数
+    static ref PAGE_RESUME_ATTEMPTS: Mutex<HashMap<i32, u32>> = Mutex::new(HashMap::new());
 }
 
-use crate::adapter::{video_source_from, Args, VideoSource, VideoSourceEnum};
+/// 分页最多连续保留续传进度的次数，超过后丢弃进度并按普通失败计入重试次数，避免损坏的临时文件导致无限重试
+const MAX_PAGE_RESUME_ATTEMPTS: u32 = 3;
+
+use crate::adapter::{is_newer_than_latest_row, video_source_from, Args, VideoSource, VideoSourceEnum};
+use crate::bilibili::bangumi::EpisodeSkip;
 use crate::bilibili::{
-    BestStream, BiliClient, BiliError, Dimension, FlvSegment, PageInfo, Stream as VideoStream, Video, VideoInfo,
+    to_chapters_xml, view_points_from_player_info, BestStream, BiliClient, BiliError, Dimension, FlvSegment,
+    PageAnalyzer, PageInfo, Stream as VideoStream, Video, VideoInfo, VideoQuality,
+};
+use crate::config::{DanmakuEmbedMode, NFOFlavor, NFOTimeType, ARGS};
+use crate::error::{DownloadAbortError, ExecutionStatus, IntegrityError, ProcessPageError};
+use crate::unified_downloader::{
+    discard_resumable_download, has_resumable_download, move_to_destination, staging_path, AudioTags, ExpectedFile,
+    UnifiedDownloader,
 };
-use crate::config::ARGS;
-use crate::error::{DownloadAbortError, ExecutionStatus, ProcessPageError};
-use crate::task::{DeleteVideoTask, VIDEO_DELETE_TASK_QUEUE};
-use crate::unified_downloader::UnifiedDownloader;
 use crate::utils::format_arg::{page_format_args, video_format_args};
 use crate::utils::model::{
     create_pages, create_videos, filter_unfilled_videos, filter_unhandled_video_pages,
@@ -38,7 +48,10 @@ use crate::utils::model::{
 };
 use crate::utils::nfo::NFO;
 use crate::utils::notification::NewVideoInfo;
-use crate::utils::status::{PageStatus, VideoStatus, STATUS_OK};
+use crate::utils::page_files;
+use crate::utils::status::{PageStatus, UploadStatus, VideoStatus, STATUS_NOT_APPLICABLE, STATUS_OK};
+use crate::utils::task_notifier::SCAN_PROGRESS_NOTIFIER;
+use crate::utils::webdav::WebDavClient;
 
 // 新增：番剧季信息结构体
 #[derive(Debug, Clone)]
@@ -79,7 +92,7 @@ pub struct EpisodeInfo {
 
 /// 创建一个配置了 truncate 辅助函数的 handlebars 实例
 ///
-/// 完整地处理某个视频来源，返回新增的视频数量和视频信息
+/// 完整地处理某个视频来源，返回新增的视频数量和视频信息，以及被过滤、跳过和无权观看的视频数量
 pub async fn process_video_source(
     args: &Args,
     bili_client: &BiliClient,
@@ -87,7 +100,7 @@ pub async fn process_video_source(
     connection: &DatabaseConnection,
     downloader: &UnifiedDownloader,
     token: CancellationToken,
-) -> Result<(usize, Vec<NewVideoInfo>)> {
+) -> Result<(usize, Vec<NewVideoInfo>, usize, usize, usize)> {
     // 记录当前处理的参数和路径
     if let Args::Bangumi {
         season_id,
@@ -146,7 +159,7 @@ pub async fn process_video_source(
         };
 
     // 从视频流中获取新视频的简要信息，写入数据库，并获取新增视频数量和信息
-    let (new_video_count, new_videos) =
+    let (new_video_count, new_videos, skipped_video_count) =
         match refresh_video_source(&video_source, video_streams, connection, token.clone(), bili_client).await {
             Ok(result) => result,
             Err(e) => {
@@ -165,7 +178,37 @@ pub async fn process_video_source(
     // Guard: skip further steps if paused/cancelled or no new videos in this round
     if crate::task::TASK_CONTROLLER.is_paused() || token.is_cancelled() {
         info!("任务已暂停/取消，跳过详情与下载阶段");
-        return Ok((new_video_count, new_videos));
+        return Ok((new_video_count, new_videos, 0, skipped_video_count, 0));
+    }
+    // 启用封面刷新时同步合集在B站的最新封面
+    if let VideoSourceEnum::Collection(collection_source) = &video_source {
+        if collection_source.r#type != i32::from(crate::bilibili::CollectionType::Manual)
+            && crate::config::with_config(|bundle| bundle.config.refresh_covers)
+        {
+            if let Err(e) = refresh_collection_cover(bili_client, collection_source, connection).await {
+                warn!("刷新合集「{}」封面失败: {:#}", collection_source.name, e);
+            }
+        }
+    }
+
+    // 配置了头像刷新间隔时检查UP主头像是否变化，番剧没有UP主信息
+    if !matches!(video_source, VideoSourceEnum::BangumiSource(_))
+        && crate::config::with_config(|bundle| bundle.config.refresh_upper_avatar_days) > 0
+    {
+        if let Err(e) = refresh_upper_avatars(bili_client, &video_source, connection, token.clone()).await {
+            warn!("检查UP主头像时出错: {:#}", e);
+        }
+    }
+
+    // 启用画质升级时检查已下载分页是否有更高画质可用，需要升级的分页会在本轮下载阶段重新下载
+    if video_source.upgrade_quality() && !video_source.strm_mode() && !video_source.audio_only() && !ARGS.scan_only {
+        if let Err(e) = check_quality_upgrades(bili_client, &video_source, connection, token.clone()).await {
+            if e.downcast_ref::<DownloadAbortError>().is_some() {
+                error!("检查画质升级时触发风控，已终止当前视频源的处理");
+                return Err(e);
+            }
+            warn!("检查画质升级时出错: {:#}", e);
+        }
     }
     if new_video_count == 0 {
         let has_unfilled = !filter_unfilled_videos(video_source.filter_expr(), connection)
@@ -179,29 +222,46 @@ pub async fn process_video_source(
             .is_empty();
         if !(has_unfilled || has_unhandled || has_failed) {
             info!("本轮未发现新视频，且无待处理任务，跳过详情与下载阶段");
-            return Ok((new_video_count, new_videos));
+            return Ok((new_video_count, new_videos, 0, skipped_video_count, 0));
         } else {
             info!("本轮未发现新视频，但存在待处理任务（重置/未完成/可重试），继续执行下载阶段");
         }
     }
 
     // 单独请求视频详情接口，获取视频的详情信息与所有的分页，写入数据库
-    if let Err(e) = fetch_video_details(bili_client, &video_source, connection, token.clone()).await {
-        // 新增：检查是否为风控导致的下载中止
-        if e.downcast_ref::<DownloadAbortError>().is_some() {
-            error!("获取视频详情时触发风控，已终止当前视频源的处理，停止所有后续扫描");
-            // 风控时应该返回错误，中断整个扫描循环，而不是继续处理下一个视频源
-            return Err(e);
-        }
+    let skipped_video_ids = match fetch_video_details(bili_client, &video_source, connection, token.clone()).await {
+        Ok(skipped_video_ids) => skipped_video_ids,
+        Err(e) => {
+            // 新增：检查是否为风控导致的下载中止
+            if e.downcast_ref::<DownloadAbortError>().is_some() {
+                error!("获取视频详情时触发风控，已终止当前视频源的处理，停止所有后续扫描");
+                // 风控时应该返回错误，中断整个扫描循环，而不是继续处理下一个视频源
+                return Err(e);
+            }
 
-        let error_msg = format!("{:#}", e);
-        if retry_with_refresh(error_msg).await.is_ok() {
-            // 刷新成功，重试
-            fetch_video_details(bili_client, &video_source, connection, token.clone()).await?;
-        } else {
-            return Err(e);
+            let error_msg = format!("{:#}", e);
+            if retry_with_refresh(error_msg).await.is_ok() {
+                // 刷新成功，重试
+                fetch_video_details(bili_client, &video_source, connection, token.clone()).await?
+            } else {
+                return Err(e);
+            }
         }
-    }
+    };
+
+    // 因时长被过滤或无权观看的视频不会下载，也不作为新视频推送
+    let (filtered_videos, new_videos): (Vec<_>, Vec<_>) = new_videos.into_iter().partition(|video| {
+        video
+            .video_id
+            .is_some_and(|video_id| skipped_video_ids.duration_filtered.contains(&video_id))
+    });
+    let filtered_video_count = filtered_videos.len();
+    let (no_permission_videos, new_videos): (Vec<_>, Vec<_>) = new_videos.into_iter().partition(|video| {
+        video
+            .video_id
+            .is_some_and(|video_id| skipped_video_ids.no_permission.contains(&video_id))
+    });
+    let no_permission_video_count = no_permission_videos.len();
 
     if ARGS.scan_only {
         warn!("已开启仅扫描模式，跳过视频下载..");
@@ -228,7 +288,13 @@ pub async fn process_video_source(
             // 重试失败不中断主流程，继续执行
         }
     }
-    Ok((new_video_count, new_videos))
+    Ok((
+        new_video_count,
+        new_videos,
+        filtered_video_count,
+        skipped_video_count,
+        no_permission_video_count,
+    ))
 }
 
 /// 更新番剧缓存
@@ -375,10 +441,22 @@ pub async fn refresh_video_source<'a>(
     connection: &DatabaseConnection,
     token: CancellationToken,
     bili_client: &BiliClient,
-) -> Result<(usize, Vec<NewVideoInfo>)> {
+) -> Result<(usize, Vec<NewVideoInfo>, usize)> {
     video_source.log_refresh_video_start();
     let latest_row_at_string = video_source.get_latest_row_at();
-    let latest_row_at = crate::utils::time_format::parse_time_string(&latest_row_at_string)
+    let parsed_latest_row_at = crate::utils::time_format::parse_time_string(&latest_row_at_string);
+    // 严格增量模式：只拉取比 latest_row_at 更新的视频，游标丢失时跳过本次扫描而不是回退到全量扫描
+    let strict_incremental = video_source.strict_incremental();
+    if strict_incremental && parsed_latest_row_at.is_none() {
+        warn!(
+            "{} {} 已启用严格增量模式，但增量游标 latest_row_at「{}」无效，跳过本次扫描",
+            video_source.source_type_display(),
+            video_source.source_name_display(),
+            latest_row_at_string
+        );
+        return Ok((0, Vec::new(), 0));
+    }
+    let latest_row_at = parsed_latest_row_at
         .unwrap_or_else(|| chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc())
         .and_utc();
     let mut max_datetime = latest_row_at;
@@ -394,21 +472,30 @@ pub async fn refresh_video_source<'a>(
                     futures::future::ready(false)
                 }
                 Ok(v) => {
-                    // 虽然 video_streams 是从新到旧的，但由于此处是分页请求，极端情况下可能发生访问完第一页时插入了两整页视频的情况
-                    // 此时获取到的第二页视频比第一页的还要新，因此为了确保正确，理应对每一页的第一个视频进行时间比较
-                    // 但在 streams 的抽象下，无法判断具体是在哪里分页的，所以暂且对每个视频都进行比较，应该不会有太大性能损失
                     let release_datetime = v.release_datetime();
-                    if release_datetime > &max_datetime {
-                        max_datetime = *release_datetime;
-                    }
-                    futures::future::ready(video_source.should_take(release_datetime, latest_row_at_string.as_str()))
+                    let should_take = if strict_incremental {
+                        is_newer_than_latest_row(release_datetime, latest_row_at_string.as_str())
+                    } else {
+                        video_source.should_take(release_datetime, latest_row_at_string.as_str())
+                    };
+                    futures::future::ready(should_take)
                 }
             }
         })
         .filter_map(|res| futures::future::ready(res.ok()))
         .chunks(10);
     let mut count = 0;
+    let mut skipped_count = 0;
     let mut new_videos = Vec::new();
+    let (pubtime_after, pubtime_before) = video_source.pubtime_window();
+    let title_filter = video_source.title_filter();
+    let episode_range = match video_source {
+        VideoSourceEnum::BangumiSource(bangumi_source) => bangumi_source.episode_range.as_ref(),
+        _ => None,
+    };
+    // 只处理最新的 N 个视频，视频流从新到旧排列，达到数量后不再拉取更早的视频
+    let limit_newest = video_source.limit_newest();
+    let mut taken_count = 0;
 
     while let Some(videos_info) = video_streams.next().await {
         // 在处理每批视频前检查取消状态
@@ -416,6 +503,58 @@ pub async fn refresh_video_source<'a>(
             warn!("视频源处理过程中检测到取消/暂停信号，停止处理");
             break;
         }
+
+        // 游标推进到本次检查过的所有视频，被时间窗口、关键词、集数范围或数量限制跳过的视频同样推进游标，避免每次扫描重复拉取
+        // 虽然 video_streams 是从新到旧的，但由于此处是分页请求，极端情况下可能发生访问完第一页时插入了两整页视频的情况
+        // 此时获取到的第二页视频比第一页的还要新，因此为了确保正确，对每个视频都进行时间比较
+        for video_info in &videos_info {
+            let release_datetime = video_info.release_datetime();
+            if release_datetime > &max_datetime {
+                max_datetime = *release_datetime;
+            }
+        }
+
+        // 发布时间不在视频源设置的时间窗口内、标题未通过关键词过滤、或番剧集数不在设置范围内的视频直接跳过，不写入数据库
+        let batch_len = videos_info.len();
+        let mut videos_info: Vec<VideoInfo> = videos_info
+            .into_iter()
+            .filter(|v| crate::utils::time_format::pubtime_in_window(v.pubtime(), pubtime_after, pubtime_before))
+            .filter(
+                |v| match title_filter.as_ref().and_then(|filter| filter.skip_reason(v.title())) {
+                    Some(reason) => {
+                        debug!("跳过视频「{}」: {}", v.title(), reason);
+                        false
+                    }
+                    None => true,
+                },
+            )
+            .filter(|v| match (episode_range, v) {
+                (Some(range), VideoInfo::Bangumi { episode_number, .. }) => {
+                    let in_range = episode_number.is_some_and(|number| range.contains(number));
+                    if !in_range {
+                        debug!(
+                            "跳过番剧「{}」: 第 {:?} 集不在设置的集数范围内",
+                            v.title(),
+                            episode_number
+                        );
+                    }
+                    in_range
+                }
+                _ => true,
+            })
+            .collect();
+        if let Some(limit) = limit_newest {
+            videos_info.truncate(limit.saturating_sub(taken_count));
+            taken_count += videos_info.len();
+        }
+        skipped_count += batch_len - videos_info.len();
+        let limit_reached = limit_newest.is_some_and(|limit| taken_count >= limit);
+        if videos_info.is_empty() {
+            if limit_reached {
+                break;
+            }
+            continue;
+        }
         // 获取插入前的视频数量
         let before_count = get_video_count_for_source(video_source, connection).await?;
 
@@ -548,9 +687,27 @@ pub async fn refresh_video_source<'a>(
 
             debug!("实际收集到 {} 个新视频信息用于推送", new_videos.len());
         }
+
+        if limit_reached {
+            info!(
+                "{} {} 已处理最新的 {} 个视频，不再获取更早的视频",
+                video_source.source_type_display(),
+                video_source.source_name_display(),
+                taken_count
+            );
+            break;
+        }
     }
     // 如果获取视频分页过程中发生了错误，直接在此处返回，不更新 latest_row_at
     error?;
+    if skipped_count > 0 {
+        info!(
+            "{} {} 有 {} 个视频不满足视频源的过滤条件（发布时间/标题/集数/最新数量限制），已跳过",
+            video_source.source_type_display(),
+            video_source.source_name_display(),
+            skipped_count
+        );
+    }
     if max_datetime != latest_row_at {
         // 转换为北京时间的标准字符串格式
         let beijing_datetime = max_datetime.with_timezone(&crate::utils::time_format::beijing_timezone());
@@ -588,20 +745,37 @@ pub async fn refresh_video_source<'a>(
 
     video_source.log_refresh_video_end(count);
     debug!("workflow返回: count={}, new_videos.len()={}", count, new_videos.len());
-    Ok((count, new_videos))
+    Ok((count, new_videos, skipped_count))
+}
+
+/// 获取详情后不会下载的视频
+#[derive(Default)]
+pub struct SkippedVideoIds {
+    /// 时长不在设置范围内的视频ID
+    pub duration_filtered: HashSet<i32>,
+    /// 充电专享且当前账号无权观看的视频ID
+    pub no_permission: HashSet<i32>,
+}
+
+/// 单个视频获取详情后不下载的原因
+enum DetailSkip {
+    DurationFiltered(i32),
+    NoPermission(i32),
 }
 
 /// 筛选出所有未获取到全部信息的视频，尝试补充其详细信息
+/// 返回获取详情后被跳过下载的视频ID
 pub async fn fetch_video_details(
     bili_client: &BiliClient,
     video_source: &VideoSourceEnum,
     connection: &DatabaseConnection,
     token: CancellationToken,
-) -> Result<()> {
+) -> Result<SkippedVideoIds> {
+    let mut skipped = SkippedVideoIds::default();
     // Early exit when paused/cancelled
     if crate::task::TASK_CONTROLLER.is_paused() || token.is_cancelled() {
         info!("任务已暂停/取消，跳过视频详情阶段");
-        return Ok(());
+        return Ok(skipped);
     }
     video_source.log_fetch_video_start();
     let videos_model = filter_unfilled_videos(video_source.filter_expr(), connection).await?;
@@ -803,25 +977,15 @@ pub async fn fetch_video_details(
                                 ));
                             };
 
-                            // 革命性充电视频检测：基于API返回的upower字段进行精确判断
-                            if let (Some(true), Some(false)) = (is_upower_exclusive, is_upower_play) {
-                                info!("「{}」检测到充电专享视频（未充电），将自动删除", &video_model.name);
-                                // 创建自动删除任务
-                                let delete_task = DeleteVideoTask {
-                                    video_id: video_model.id,
-                                    task_id: format!("auto_delete_upower_{}", video_model.id),
-                                };
-
-                                if let Err(delete_err) =
-                                    VIDEO_DELETE_TASK_QUEUE.enqueue_task(delete_task, connection).await
-                                {
-                                    error!("创建充电视频删除任务失败「{}」: {:#}", &video_model.name, delete_err);
-                                } else {
-                                    debug!("充电视频删除任务已加入队列「{}」", &video_model.name);
-                                }
-
-                                // 跳过后续处理，返回成功完成这个视频的处理
-                                return Ok(());
+                            // 充电专享且当前账号无权观看的视频下载必然失败，标记为无权限后不再尝试
+                            let no_permission =
+                                matches!((is_upower_exclusive, is_upower_play), (Some(true), Some(false)))
+                                    && crate::config::with_config(|bundle| bundle.config.skip_upower_exclusive);
+                            if no_permission {
+                                info!(
+                                    "「{}」为充电专享视频且当前账号无权观看，标记为无权限并跳过下载",
+                                    &video_model.name
+                                );
                             }
 
                             // 日志记录upower字段状态（仅debug级别）
@@ -835,6 +999,18 @@ pub async fn fetch_video_details(
                             let pages = std::mem::take(pages);
                             let pages_len = pages.len();
 
+                            // 时长不在设置范围内的视频只记录信息，不自动下载
+                            let total_duration: u32 = pages.iter().map(|page| page.duration).sum();
+                            let duration_filtered = !crate::config::with_config(|bundle| {
+                                bundle.config.filter_option.duration_in_range(total_duration)
+                            });
+                            if duration_filtered {
+                                info!(
+                                    "「{}」时长 {} 秒不在设置范围内，跳过下载",
+                                    &video_model.name, total_duration
+                                );
+                            }
+
                             // 提取第一个page的cid用于更新video表
                             let first_page_cid = pages.first().map(|p| p.cid);
 
@@ -993,11 +1169,34 @@ pub async fn fetch_video_details(
                                 debug!("非合作视频或未发生更新，保持API返回的upper信息");
                             }
 
+                            if duration_filtered {
+                                video_active_model.auto_download = Set(false);
+                            }
+
+                            if no_permission {
+                                let mut video_status = VideoStatus::default();
+                                video_status.mark_no_permission();
+                                video_active_model.download_status = Set(video_status.into());
+                                let mut page_status = PageStatus::default();
+                                page_status.mark_no_permission();
+                                page::Entity::update_many()
+                                    .col_expr(page::Column::DownloadStatus, Expr::value(u32::from(page_status)))
+                                    .filter(page::Column::VideoId.eq(video_model.id))
+                                    .exec(&txn)
+                                    .await?;
+                            }
+
                             video_active_model.save(&txn).await?;
                             txn.commit().await?;
+                            if no_permission {
+                                return Ok(Some(DetailSkip::NoPermission(video_model.id)));
+                            }
+                            if duration_filtered {
+                                return Ok(Some(DetailSkip::DurationFiltered(video_model.id)));
+                            }
                         }
                     };
-                    Ok::<_, anyhow::Error>(())
+                    Ok::<_, anyhow::Error>(None)
                 }
             })
             .collect::<FuturesUnordered<_>>();
@@ -1005,6 +1204,13 @@ pub async fn fetch_video_details(
         // 并发执行所有任务
         let mut stream = tasks;
         while let Some(res) = stream.next().await {
+            if let Ok(Some(skip)) = res {
+                match skip {
+                    DetailSkip::DurationFiltered(video_id) => skipped.duration_filtered.insert(video_id),
+                    DetailSkip::NoPermission(video_id) => skipped.no_permission.insert(video_id),
+                };
+                continue;
+            }
             if let Err(e) = res {
                 // 使用错误分类器进行统一处理
                 #[allow(clippy::needless_borrow)]
@@ -1030,9 +1236,349 @@ pub async fn fetch_video_details(
         info!("完成普通视频详情处理");
     }
     video_source.log_fetch_video_end();
+    Ok(skipped)
+}
+
+/// 从B站获取合集最新封面，与数据库保存的不同时更新封面地址，并重置合集第一个视频的封面任务
+/// 合集封面由第一个视频下载，重新下载时写入同一路径覆盖旧封面。返回封面是否发生变化
+pub async fn refresh_collection_cover(
+    bili_client: &BiliClient,
+    collection_model: &collection::Model,
+    connection: &DatabaseConnection,
+) -> Result<bool> {
+    use sea_orm::QueryOrder;
+
+    let cover =
+        crate::api::handler::get_collection_cover_from_api(collection_model.m_id, collection_model.s_id, bili_client)
+            .await?;
+    // 以数据库中的最新值比较，避免使用扫描开始时的旧模型
+    let current_cover = collection::Entity::find_by_id(collection_model.id)
+        .one(connection)
+        .await?
+        .context("collection not found")?
+        .cover;
+    if current_cover.as_deref() == Some(cover.as_str()) {
+        return Ok(false);
+    }
+    info!(
+        "合集「{}」封面已变化: {} → {}",
+        collection_model.name,
+        current_cover.as_deref().unwrap_or("无"),
+        cover
+    );
+
+    let txn = connection.begin().await?;
+    collection::ActiveModel {
+        id: sea_orm::ActiveValue::Unchanged(collection_model.id),
+        cover: Set(Some(cover)),
+        ..Default::default()
+    }
+    .update(&txn)
+    .await?;
+    let first_video = video::Entity::find()
+        .filter(video::Column::CollectionId.eq(collection_model.id))
+        .order_by_asc(video::Column::Pubtime)
+        .one(&txn)
+        .await?;
+    if let Some(first_video) = first_video.filter(|video| video.deleted == 0) {
+        let mut video_status = VideoStatus::from(first_video.download_status);
+        video_status.set(0, 0);
+        video::ActiveModel {
+            id: sea_orm::ActiveValue::Unchanged(first_video.id),
+            download_status: Set(video_status.into()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// 每轮扫描最多请求检查头像的UP主数量，避免关注的UP主较多时频繁请求用户信息
+const UPPER_AVATAR_CHECK_LIMIT: usize = 10;
+
+/// 与 UP 主头像 folder.jpg 放在一起的头像地址哈希文件，修改时间记录上次检查头像的时间
+const UPPER_FACE_HASH_FILE: &str = ".face_hash";
+
+/// UP 主头像地址的哈希，用于判断头像是否变化
+fn face_url_hash(url: &str) -> String {
+    format!("{:x}", md5::compute(url))
+}
+
+/// UP 主头像与 person.nfo 所在的目录
+fn upper_base_path(upper_path: &Path, upper_id: i64) -> Result<PathBuf> {
+    let upper_id = upper_id.to_string();
+    Ok(upper_path
+        .join(upper_id.chars().next().context("upper_id is empty")?.to_string())
+        .join(upper_id))
+}
+
+/// 检查视频源中 UP 主的头像是否变化，超过配置天数未检查的 UP 主会重新获取用户信息
+/// 头像地址变化时更新视频记录中的头像地址，并重置该 UP 主最新视频的头像与 nfo 任务，由随后的下载阶段覆盖旧头像
+async fn refresh_upper_avatars(
+    bili_client: &BiliClient,
+    video_source: &VideoSourceEnum,
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+) -> Result<()> {
+    use sea_orm::{QueryOrder, QuerySelect};
+
+    let (refresh_days, upper_path) = crate::config::with_config(|bundle| {
+        (
+            bundle.config.refresh_upper_avatar_days,
+            bundle.config.upper_path.clone(),
+        )
+    });
+    let refresh_interval = std::time::Duration::from_secs(refresh_days as u64 * 24 * 60 * 60);
+    let videos = video::Entity::find()
+        .select_only()
+        .columns([video::Column::Id, video::Column::UpperId, video::Column::UpperFace])
+        .filter(
+            video::Column::Deleted
+                .eq(0)
+                .and(video::Column::UpperId.gt(0))
+                .and(video::Column::SeasonId.is_null())
+                .and(video_source.filter_expr()),
+        )
+        .order_by_desc(video::Column::Pubtime)
+        .into_tuple::<(i32, i64, String)>()
+        .all(connection)
+        .await
+        .context("query upper avatars failed")?;
+
+    let mut seen_uppers = HashSet::new();
+    let mut checked = 0;
+    let mut refreshed = 0;
+    // 按发布时间倒序，每个 UP 主只保留最新的视频
+    for (video_id, upper_id, upper_face) in videos {
+        if token.is_cancelled() || checked >= UPPER_AVATAR_CHECK_LIMIT {
+            break;
+        }
+        if !seen_uppers.insert(upper_id) {
+            continue;
+        }
+        let base_path = upper_base_path(&upper_path, upper_id)?;
+        // 头像尚未下载的 UP 主由正常的下载流程处理
+        if !base_path.join("folder.jpg").exists() {
+            continue;
+        }
+        let hash_path = base_path.join(UPPER_FACE_HASH_FILE);
+        let last_checked = fs::metadata(&hash_path).await.and_then(|meta| meta.modified()).ok();
+        if last_checked.is_some_and(|time| time.elapsed().is_ok_and(|elapsed| elapsed < refresh_interval)) {
+            continue;
+        }
+        checked += 1;
+        let upper = match crate::bilibili::Submission::new(bili_client, upper_id.to_string())
+            .get_info()
+            .await
+        {
+            Ok(upper) => upper,
+            Err(e) => {
+                if let Some(BiliError::RiskControlOccurred) = e.downcast_ref::<BiliError>() {
+                    warn!("检查UP主头像时触发风控，停止本轮检查");
+                    break;
+                }
+                warn!("获取UP主 {} 的信息失败: {:#}", upper_id, e);
+                continue;
+            }
+        };
+        if upper.face.is_empty() || !upper.face.starts_with("http") {
+            continue;
+        }
+        // 没有哈希文件时以数据库中记录的头像地址作为已下载的头像
+        let stored_hash = match fs::read_to_string(&hash_path).await {
+            Ok(hash) => hash.trim().to_string(),
+            Err(_) => face_url_hash(&upper_face),
+        };
+        let new_hash = face_url_hash(&upper.face);
+        if new_hash == stored_hash {
+            // 重新写入以更新检查时间
+            if let Err(e) = fs::write(&hash_path, &new_hash).await {
+                warn!("写入UP主头像哈希文件 {} 失败: {:#}", hash_path.display(), e);
+            }
+            continue;
+        }
+        info!("UP主「{}」头像已变化，将重新下载", upper.name);
+
+        let video_model = video::Entity::find_by_id(video_id)
+            .one(connection)
+            .await?
+            .context("video not found")?;
+        let mut video_status = VideoStatus::from(video_model.download_status);
+        video_status.set(2, 0);
+        video_status.set(3, 0);
+        let txn = connection.begin().await?;
+        video::Entity::update_many()
+            .col_expr(video::Column::UpperFace, Expr::value(upper.face.clone()))
+            .filter(video::Column::UpperId.eq(upper_id))
+            .exec(&txn)
+            .await?;
+        video::ActiveModel {
+            id: sea_orm::ActiveValue::Unchanged(video_id),
+            download_status: Set(video_status.into()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        txn.commit().await?;
+        refreshed += 1;
+    }
+    if refreshed > 0 {
+        info!("{} 位UP主的头像已变化，已标记重新下载", refreshed);
+    }
+    Ok(())
+}
+
+/// 每轮扫描最多检查画质升级的分页数量，避免已下载分页较多时频繁请求播放地址
+const QUALITY_UPGRADE_CHECK_LIMIT: u64 = 20;
+/// 同一分页两次画质升级检查之间的最短间隔
+const QUALITY_UPGRADE_CHECK_COOLDOWN_DAYS: i64 = 7;
+
+/// 检查最近下载的分页是否有更高画质可用，有则重置分页的下载子任务，由随后的下载阶段重新下载并替换
+/// 只升级不降级，且新画质受筛选配置的上下限约束
+async fn check_quality_upgrades(
+    bili_client: &BiliClient,
+    video_source: &VideoSourceEnum,
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+) -> Result<()> {
+    use sea_orm::{QueryOrder, QuerySelect};
+
+    let config = crate::config::reload_config();
+    let filter_option = &config.filter_option;
+    // 按上次检查时间轮换，从未检查过的分页优先，冷却期内检查过的分页跳过
+    let checked_before = crate::utils::time_format::to_standard_string(
+        crate::utils::time_format::beijing_now() - chrono::Duration::days(QUALITY_UPGRADE_CHECK_COOLDOWN_DAYS),
+    );
+    let candidates = page::Entity::find()
+        .find_also_related(video::Entity)
+        .filter(
+            video::Column::Valid
+                .eq(true)
+                .and(video::Column::Deleted.eq(0))
+                .and(page::Column::DownloadedQuality.lt(filter_option.video_max_quality as u32))
+                .and(
+                    page::Column::QualityCheckedAt
+                        .is_null()
+                        .or(page::Column::QualityCheckedAt.lt(checked_before)),
+                )
+                .and(video_source.filter_expr()),
+        )
+        .order_by_asc(page::Column::QualityCheckedAt)
+        .order_by_desc(page::Column::Id)
+        .limit(QUALITY_UPGRADE_CHECK_LIMIT)
+        .all(connection)
+        .await
+        .context("query quality upgrade candidates failed")?;
+
+    let mut upgraded = 0;
+    let mut checked_ids = Vec::new();
+    for (page_model, video_model) in candidates {
+        if token.is_cancelled() {
+            break;
+        }
+        checked_ids.push(page_model.id);
+        let (Some(video_model), Some(current_quality)) = (video_model, page_model.downloaded_quality) else {
+            continue;
+        };
+        // 只检查下载子任务已经成功的分页，其余分页本就会在下载阶段处理
+        if PageStatus::from(page_model.download_status).get(1) != STATUS_OK {
+            continue;
+        }
+        let page_info = PageInfo {
+            cid: page_model.cid,
+            duration: page_model.duration,
+            ..Default::default()
+        };
+        let mut streams = match get_page_analyzer(bili_client, &video_model, &page_info, token.clone()).await {
+            Ok(streams) => streams,
+            Err(e) => {
+                if let Some(BiliError::RiskControlOccurred) = e.downcast_ref::<BiliError>() {
+                    // 触发风控的分页没有完成检查，下一轮优先重试
+                    checked_ids.pop();
+                    mark_quality_checked(connection, checked_ids).await?;
+                    bail!(DownloadAbortError());
+                }
+                warn!(
+                    "检查视频「{}」第 {} 页的可用画质失败: {:#}",
+                    video_model.name, page_model.pid, e
+                );
+                continue;
+            }
+        };
+        let available_quality = match streams.best_stream(filter_option) {
+            Ok(BestStream::VideoAudio {
+                video: VideoStream::DashVideo { quality, .. },
+                ..
+            }) => quality,
+            _ => continue,
+        };
+        if available_quality as u32 <= current_quality {
+            continue;
+        }
+        info!(
+            "视频「{}」第 {} 页画质升级: {} → {}，将重新下载",
+            video_model.name,
+            page_model.pid,
+            VideoQuality::from_repr(current_quality as _).map_or(current_quality.to_string(), |q| q.to_string()),
+            available_quality
+        );
+        let mut page_status = PageStatus::from(page_model.download_status);
+        page_status.set(1, 0);
+        let mut video_status = VideoStatus::from(video_model.download_status);
+        video_status.set(4, 0);
+        let txn = connection.begin().await?;
+        page::ActiveModel {
+            id: sea_orm::ActiveValue::Unchanged(page_model.id),
+            download_status: Set(page_status.into()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        video::ActiveModel {
+            id: sea_orm::ActiveValue::Unchanged(video_model.id),
+            download_status: Set(video_status.into()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        txn.commit().await?;
+        upgraded += 1;
+    }
+    mark_quality_checked(connection, checked_ids).await?;
+    if upgraded > 0 {
+        info!("{} 个分页有更高画质可用，已标记重新下载", upgraded);
+    }
     Ok(())
 }
 
+/// 记录分页的画质升级检查时间，冷却期内不再重复检查
+async fn mark_quality_checked(connection: &DatabaseConnection, page_ids: Vec<i32>) -> Result<()> {
+    if page_ids.is_empty() {
+        return Ok(());
+    }
+    page::Entity::update_many()
+        .col_expr(
+            page::Column::QualityCheckedAt,
+            sea_orm::sea_query::Expr::value(now_standard_string()),
+        )
+        .filter(page::Column::Id.is_in(page_ids))
+        .exec(connection)
+        .await
+        .context("update quality checked time failed")?;
+    Ok(())
+}
+
+/// 获取视频下载信号量，配置的并发数变化时重新创建，已取得的许可不受影响
+fn video_download_semaphore(limit: usize) -> Arc<Semaphore> {
+    let mut guard = VIDEO_DOWNLOAD_SEMAPHORE.lock().unwrap();
+    if guard.0 != limit {
+        *guard = (limit, Arc::new(Semaphore::new(limit)));
+    }
+    guard.1.clone()
+}
+
 /// 下载所有未处理成功的视频
 pub async fn download_unprocessed_videos(
     bili_client: &BiliClient,
@@ -1047,17 +1593,24 @@ pub async fn download_unprocessed_videos(
         return Ok(());
     }
     video_source.log_download_video_start();
+    if video_source.strm_mode() && video_source.strm_refresh() {
+        if let Err(e) = refresh_strm_files(bili_client, video_source, connection, token.clone()).await {
+            warn!("刷新 .strm 文件失败: {:#}", e);
+        }
+    }
     let current_config = crate::config::reload_config();
-    let semaphore = Semaphore::new(current_config.concurrent_limit.video);
+    let semaphore = video_download_semaphore(current_config.concurrent_limit.video);
     let unhandled_videos_pages = filter_unhandled_video_pages(video_source.filter_expr(), connection).await?;
 
     // 只有当有未处理视频时才显示日志
     if !unhandled_videos_pages.is_empty() {
         info!("找到 {} 个未处理完成的视频", unhandled_videos_pages.len());
     }
+    SCAN_PROGRESS_NOTIFIER.set_videos_total(unhandled_videos_pages.len());
 
     let mut assigned_upper = HashSet::new();
     let mut assigned_bangumi_seasons = HashSet::new();
+    let season_skips = SeasonSkipCache::default();
     let tasks = unhandled_videos_pages
         .into_iter()
         .map(|(video_model, pages_model)| {
@@ -1087,6 +1640,7 @@ pub async fn download_unprocessed_videos(
                 &semaphore,
                 downloader,
                 should_download_upper,
+                &season_skips,
                 token.clone(),
             )
         })
@@ -1095,6 +1649,7 @@ pub async fn download_unprocessed_videos(
     let mut stream = tasks;
     // 使用循环和select来处理任务，以便在检测到取消信号时立即停止
     while let Some(res) = stream.next().await {
+        SCAN_PROGRESS_NOTIFIER.video_processed();
         match res {
             Ok(model) => {
                 if download_aborted {
@@ -1160,6 +1715,11 @@ pub async fn download_unprocessed_videos(
         // 风控时返回错误，中断整个扫描循环
         bail!(DownloadAbortError());
     }
+    if video_source.webdav_upload() {
+        if let Err(e) = upload_pages_to_webdav(video_source, connection, token.clone()).await {
+            warn!("上传分页到 WebDAV 失败: {:#}", e);
+        }
+    }
     video_source.log_download_video_end();
     Ok(())
 }
@@ -1187,9 +1747,10 @@ pub async fn retry_failed_videos_once(
     info!("开始重试当前循环中的 {} 个失败视频", failed_videos_pages.len());
 
     let current_config = crate::config::reload_config();
-    let semaphore = Semaphore::new(current_config.concurrent_limit.video);
+    let semaphore = video_download_semaphore(current_config.concurrent_limit.video);
     let mut assigned_upper = HashSet::new();
     let mut assigned_bangumi_seasons = HashSet::new();
+    let season_skips = SeasonSkipCache::default();
 
     let tasks = failed_videos_pages
         .into_iter()
@@ -1220,6 +1781,7 @@ pub async fn retry_failed_videos_once(
                 &semaphore,
                 downloader,
                 should_download_upper,
+                &season_skips,
                 token.clone(),
             )
         })
@@ -1289,6 +1851,7 @@ pub struct DownloadPageArgs<'a> {
     pub connection: &'a DatabaseConnection,
     pub downloader: &'a UnifiedDownloader,
     pub base_path: &'a Path,
+    pub season_skips: &'a SeasonSkipCache,
     #[allow(dead_code)]
     pub token: CancellationToken,
 }
@@ -1303,6 +1866,7 @@ pub async fn download_video_pages(
     semaphore: &Semaphore,
     downloader: &UnifiedDownloader,
     should_download_upper: bool,
+    season_skips: &SeasonSkipCache,
     token: CancellationToken,
 ) -> Result<video::ActiveModel> {
     let _permit = tokio::select! {
@@ -1310,7 +1874,12 @@ pub async fn download_video_pages(
         _ = token.cancelled() => return Err(anyhow!("Download cancelled")),
         permit = semaphore.acquire() => permit.context("acquire semaphore failed")?,
     };
+    // 磁盘空间不足时暂停任务，避免写出截断的文件或合并失败
+    if !crate::task::video_downloader::ensure_disk_space(video_source.path()).await {
+        return Err(anyhow!("磁盘空间不足，任务已暂停"));
+    }
     let mut status = VideoStatus::from(video_model.download_status);
+    status.apply_applicable(video_source.subtask_toggles().video_applicable());
     let separate_status = status.should_run();
 
     // 检查是否为番剧
@@ -1475,6 +2044,10 @@ pub async fn download_video_pages(
             path: None,
             image: None,
             download_status: 0,
+            webdav_upload_status: 0,
+            canonical_path: None,
+            downloaded_quality: None,
+            quality_checked_at: None,
             created_at: now_standard_string(),
         };
 
@@ -1674,12 +2247,8 @@ pub async fn download_video_pages(
 
     // 延迟创建季度文件夹，只在实际需要写入文件时创建
 
-    let upper_id = final_video_model.upper_id.to_string();
     let current_config = crate::config::reload_config();
-    let base_upper_path = &current_config
-        .upper_path
-        .join(upper_id.chars().next().context("upper_id is empty")?.to_string())
-        .join(upper_id);
+    let base_upper_path = &upper_base_path(&current_config.upper_path, final_video_model.upper_id)?;
     let is_single_page = final_video_model.single_page.context("single_page is null")?;
 
     // 为多P视频生成基于视频名称的文件名
@@ -1806,6 +2375,9 @@ pub async fn download_video_pages(
         true // 番剧不在此处检查
     };
 
+    // 视频源单独设置的 NFO 时间类型，未设置时使用全局配置
+    let nfo_time_type = video_source.nfo_time_type();
+
     // 先处理NFO生成（独立执行，避免tokio::join!类型问题）
     let nfo_result = if is_bangumi && season_info.is_some() {
         // 番剧且有API数据：使用API驱动的NFO生成
@@ -1813,6 +2385,7 @@ pub async fn download_video_pages(
         generate_bangumi_video_nfo(
             separate_status[2] && bangumi_folder_path.is_some() && should_download_bangumi_nfo,
             &video_model,
+            nfo_time_type.as_ref(),
             season_info.as_ref().unwrap(),
             bangumi_folder_path.as_ref().unwrap().join("tvshow.nfo"),
         )
@@ -1860,6 +2433,7 @@ pub async fn download_video_pages(
                 generate_collection_video_nfo(
                     true,
                     &video_model,
+                    nfo_time_type.as_ref(),
                     Some(&collection_source.name),
                     collection_cover.as_deref(),
                     if let Some(ref bangumi_path) = bangumi_folder_path {
@@ -1902,6 +2476,7 @@ pub async fn download_video_pages(
             generate_video_nfo(
                 should_generate_nfo,
                 &video_model,
+                nfo_time_type.as_ref(),
                 if let Some(ref bangumi_path) = bangumi_folder_path {
                     if is_bangumi {
                         // 番剧tvshow.nfo放在番剧文件夹根目录，使用固定文件名
@@ -2011,11 +2586,37 @@ pub async fn download_video_pages(
         generate_bangumi_season_nfo(
             should_generate_season_nfo,
             &video_model,
+            nfo_time_type.as_ref(),
             season_info.as_ref().unwrap(),
             base_path.clone(),
             season_number,
         )
         .await
+    } else if !is_bangumi && season_folder.is_some() {
+        // 启用Season结构的合集或多P视频：合集使用合集名称和封面，多P视频使用视频本身的信息
+        // 统一模式下同一合集的视频共享季度文件夹，season.nfo 内容只取决于合集，重复写入结果一致
+        let collection = match video_source {
+            VideoSourceEnum::Collection(collection_source) => {
+                let cover = match collection::Entity::find_by_id(collection_source.id)
+                    .one(connection)
+                    .await
+                {
+                    Ok(Some(fresh_collection)) => fresh_collection.cover,
+                    _ => None,
+                };
+                Some((collection_source.name.as_str(), cover))
+            }
+            _ => None,
+        };
+        generate_video_season_nfo(
+            separate_status[2],
+            &video_model,
+            nfo_time_type.as_ref(),
+            collection.as_ref().map(|(name, _)| *name),
+            collection.as_ref().and_then(|(_, cover)| cover.as_deref()),
+            base_path.clone(),
+        )
+        .await
     } else {
         Ok(ExecutionStatus::Skipped)
     };
@@ -2126,6 +2727,7 @@ pub async fn download_video_pages(
         Some(ExecutionStatus::Skipped)
     };
 
+    let nfo_flavor = crate::config::reload_config().nfo_flavor;
     let (res_1, res_2, res_3, res_4, res_5) = tokio::join!(
         // 下载视频封面（番剧和普通视频采用不同策略）
         fetch_video_poster(
@@ -2153,7 +2755,7 @@ pub async fn download_video_pages(
                     bangumi_folder_path
                         .as_ref()
                         .unwrap()
-                        .join(format!("{}-thumb.jpg", bangumi_base_name))
+                        .join(nfo_flavor.bangumi_thumb_file_name(&bangumi_base_name))
                 }
             } else {
                 // 多P视频或合集使用Season结构时，封面放在视频根目录
@@ -2164,11 +2766,11 @@ pub async fn download_video_pages(
                     // 需要从base_path（Season文件夹）回到父目录（视频根目录）
                     base_path
                         .parent()
-                        .map(|parent| parent.join(format!("{}-thumb.jpg", video_base_name)))
-                        .unwrap_or_else(|| base_path.join(format!("{}-thumb.jpg", video_base_name)))
+                        .map(|parent| parent.join(nfo_flavor.show_poster_file_name(&video_base_name)))
+                        .unwrap_or_else(|| base_path.join(nfo_flavor.show_poster_file_name(&video_base_name)))
                 } else {
                     // 普通视频封面放在视频文件夹
-                    base_path.join(format!("{}-thumb.jpg", video_base_name))
+                    base_path.join(nfo_flavor.show_poster_file_name(&video_base_name))
                 }
             },
             if is_bangumi && bangumi_folder_path.is_some() {
@@ -2183,7 +2785,7 @@ pub async fn download_video_pages(
                     bangumi_folder_path
                         .as_ref()
                         .unwrap()
-                        .join(format!("{}-fanart.jpg", bangumi_base_name))
+                        .join(nfo_flavor.show_fanart_file_name(&bangumi_base_name))
                 }
             } else {
                 // 多P视频或合集使用Season结构时，fanart放在视频根目录
@@ -2194,11 +2796,11 @@ pub async fn download_video_pages(
                     // 需要从base_path（Season文件夹）回到父目录（视频根目录）
                     base_path
                         .parent()
-                        .map(|parent| parent.join(format!("{}-fanart.jpg", video_base_name)))
-                        .unwrap_or_else(|| base_path.join(format!("{}-fanart.jpg", video_base_name)))
+                        .map(|parent| parent.join(nfo_flavor.show_fanart_file_name(&video_base_name)))
+                        .unwrap_or_else(|| base_path.join(nfo_flavor.show_fanart_file_name(&video_base_name)))
                 } else {
                     // 普通视频fanart放在视频文件夹
-                    base_path.join(format!("{}-fanart.jpg", video_base_name))
+                    base_path.join(nfo_flavor.show_fanart_file_name(&video_base_name))
                 }
             },
             token.clone(),
@@ -2292,6 +2894,7 @@ pub async fn download_video_pages(
                 connection,
                 downloader,
                 base_path: &base_path,
+                season_skips,
                 token: token.clone(),
             },
             token.clone()
@@ -2488,6 +3091,7 @@ pub async fn dispatch_download_page(args: DownloadPageArgs<'_>, token: Cancellat
             let connection = args.connection;
             let downloader = args.downloader;
             let base_path = args.base_path;
+            let season_skips = args.season_skips;
             async move {
                 let result = download_page(
                     bili_client,
@@ -2498,6 +3102,7 @@ pub async fn dispatch_download_page(args: DownloadPageArgs<'_>, token: Cancellat
                     semaphore_clone.as_ref(),
                     downloader,
                     base_path,
+                    season_skips,
                     token_clone,
                 )
                 .await;
@@ -2521,9 +3126,19 @@ pub async fn dispatch_download_page(args: DownloadPageArgs<'_>, token: Cancellat
                 // 新版本实现会将此处取值为所有子任务状态的最小值，这样只有所有分页的子任务全部成功时才会认为视频层的分 P 下载状态是 Succeeded
                 let page_download_status = model.download_status.try_as_ref().expect("download_status must be set");
                 let separate_status: [u32; 5] = PageStatus::from(*page_download_status).into();
-                for status in separate_status {
+                // 被视频源关闭的子任务不参与判断
+                for status in separate_status
+                    .into_iter()
+                    .filter(|status| *status != STATUS_NOT_APPLICABLE)
+                {
                     target_status = target_status.min(status);
                 }
+                if separate_status
+                    .iter()
+                    .all(|status| *status == STATUS_OK || *status == STATUS_NOT_APPLICABLE)
+                {
+                    SCAN_PROGRESS_NOTIFIER.page_downloaded();
+                }
                 update_pages_model(vec![model], args.connection).await?;
             }
             Err(e) => {
@@ -2626,6 +3241,7 @@ pub async fn download_page(
     semaphore: &Semaphore,
     downloader: &UnifiedDownloader,
     base_path: &Path,
+    season_skips: &SeasonSkipCache,
     token: CancellationToken,
 ) -> Result<page::ActiveModel> {
     let _permit = tokio::select! {
@@ -2634,6 +3250,7 @@ pub async fn download_page(
         permit = semaphore.acquire() => permit.context("acquire semaphore failed")?,
     };
     let mut status = PageStatus::from(page_model.download_status);
+    status.apply_applicable(video_source.subtask_toggles().page_applicable());
     let separate_status = status.should_run();
     let is_single_page = video_model.single_page.context("single_page is null")?;
 
@@ -2731,34 +3348,48 @@ pub async fn download_page(
             .map_err(|e| anyhow::anyhow!("模板渲染失败: {}", e))?
     };
 
+    // 过长的文件名会导致写入失败（如 Windows 的 260 字符路径限制），按配置截断并为扩展名预留空间
+    let base_name = crate::utils::filenamify::fit_file_stem(base_path, base_name, page_files::MAX_SUFFIX_BYTES);
+
+    let nfo_flavor = crate::config::reload_config().nfo_flavor;
+    // .strm 模式下视频文件替换为记录播放地址的 .strm 文件，仅音频模式下替换为 .m4a 音频文件
+    let strm_mode = video_source.strm_mode();
+    let audio_only = video_source.audio_only();
+    let video_extension = if strm_mode {
+        "strm"
+    } else if audio_only {
+        "m4a"
+    } else {
+        "mp4"
+    };
     let (poster_path, video_path, nfo_path, danmaku_path, fanart_path, subtitle_path) = if is_single_page {
         (
-            base_path.join(format!("{}-thumb.jpg", &base_name)),
-            base_path.join(format!("{}.mp4", &base_name)),
-            base_path.join(format!("{}.nfo", &base_name)),
-            base_path.join(format!("{}.zh-CN.default.ass", &base_name)),
-            Some(base_path.join(format!("{}-fanart.jpg", &base_name))),
+            base_path.join(nfo_flavor.episode_thumb_file_name(&base_name)),
+            base_path.join(format!("{}.{}", &base_name, video_extension)),
+            base_path.join(page_files::nfo_file_name(&base_name)),
+            base_path.join(page_files::danmaku_file_name(&base_name)),
+            Some(base_path.join(nfo_flavor.episode_fanart_file_name(&base_name))),
             base_path.join(format!("{}.srt", &base_name)),
         )
     } else if is_bangumi {
         // 番剧直接使用基础路径，不创建子文件夹结构
         (
-            base_path.join(format!("{}-thumb.jpg", &base_name)),
-            base_path.join(format!("{}.mp4", &base_name)),
-            base_path.join(format!("{}.nfo", &base_name)),
-            base_path.join(format!("{}.zh-CN.default.ass", &base_name)),
+            base_path.join(nfo_flavor.episode_thumb_file_name(&base_name)),
+            base_path.join(format!("{}.{}", &base_name, video_extension)),
+            base_path.join(page_files::nfo_file_name(&base_name)),
+            base_path.join(page_files::danmaku_file_name(&base_name)),
             None,
             base_path.join(format!("{}.srt", &base_name)),
         )
     } else {
         // 非番剧的多P视频直接使用基础路径，不创建子文件夹
         (
-            base_path.join(format!("{}-thumb.jpg", &base_name)),
-            base_path.join(format!("{}.mp4", &base_name)),
-            base_path.join(format!("{}.nfo", &base_name)),
-            base_path.join(format!("{}.zh-CN.default.ass", &base_name)),
+            base_path.join(nfo_flavor.episode_thumb_file_name(&base_name)),
+            base_path.join(format!("{}.{}", &base_name, video_extension)),
+            base_path.join(page_files::nfo_file_name(&base_name)),
+            base_path.join(page_files::danmaku_file_name(&base_name)),
             // 多P视频的每个分页都应该有自己的fanart
-            Some(base_path.join(format!("{}-fanart.jpg", &base_name))),
+            Some(base_path.join(nfo_flavor.episode_fanart_file_name(&base_name))),
             base_path.join(format!("{}.srt", &base_name)),
         )
     };
@@ -2776,6 +3407,31 @@ pub async fn download_page(
         dimension,
         ..Default::default()
     };
+    // 信任已有文件时，从未处理过的分页若目标文件已存在且大小合理则直接视为下载完成
+    // 重置或等待画质升级的分页已记录过路径，不受影响
+    let trusted_existing = separate_status[1]
+        && !strm_mode
+        && page_model.path.is_none()
+        && crate::config::with_config(|bundle| bundle.config.trust_existing_files)
+        && has_plausible_existing_file(&video_path, page_model.duration).await;
+    if trusted_existing {
+        info!(
+            "视频「{}」第 {} 页的文件已存在，跳过下载: {}",
+            video_model.name,
+            page_model.pid,
+            video_path.display()
+        );
+    }
+    // 其他视频源已下载过相同分页时直接创建链接，无需重复下载
+    let canonical_path = if separate_status[1] && !strm_mode && !audio_only && !trusted_existing {
+        crate::utils::dedup::link_duplicate_page(connection, &page_model, &video_path).await
+    } else {
+        None
+    };
+    // 记录实际下载的视频流画质，供之后的画质升级检查比较
+    let mut downloaded_quality = None;
+    // 字幕与看点章节来自同一个播放器接口，由先执行的一方请求，另一方复用
+    let player_info = OnceCell::new();
     // 使用 tokio::join! 替代装箱的 Future，零分配并行执行
     let (res_1, res_2, res_3, res_4, res_5) = tokio::join!(
         fetch_page_poster(
@@ -2785,36 +3441,162 @@ pub async fn download_page(
             downloader,
             poster_path,
             fanart_path,
+            (!strm_mode && !audio_only).then_some(video_path.as_path()),
             token.clone(),
         ),
-        fetch_page_video(
-            separate_status[1],
-            bili_client,
-            video_model,
-            downloader,
-            &page_info,
-            &video_path,
-            token.clone(),
-        ),
-        generate_page_nfo(separate_status[2], video_model, &page_model, nfo_path, connection),
-        fetch_page_danmaku(
-            separate_status[3],
-            bili_client,
-            video_model,
-            &page_info,
-            danmaku_path,
-            token.clone(),
-        ),
-        fetch_page_subtitle(
-            separate_status[4],
-            bili_client,
-            video_model,
+        async {
+            if strm_mode {
+                return fetch_page_strm(
+                    separate_status[1],
+                    bili_client,
+                    video_model,
+                    &page_info,
+                    &video_path,
+                    token.clone(),
+                )
+                .await;
+            }
+            if canonical_path.is_some() || trusted_existing {
+                return Ok(ExecutionStatus::Succeeded);
+            }
+            if audio_only {
+                return fetch_page_audio(
+                    separate_status[1],
+                    bili_client,
+                    video_model,
+                    &page_model,
+                    downloader,
+                    &page_info,
+                    &video_path,
+                    token.clone(),
+                )
+                .await;
+            }
+            fetch_page_video(
+                separate_status[1],
+                bili_client,
+                video_model,
+                downloader,
+                &page_info,
+                &video_path,
+                &mut downloaded_quality,
+                token.clone(),
+            )
+            .await
+            .inspect_err(|e| {
+                if let Some(integrity_error) = e.downcast_ref::<IntegrityError>() {
+                    warn!(
+                        "视频 {} 「{}」 第{}P (cid: {}) 下载不完整，将作为失败重新下载: {}",
+                        video_model.bvid, video_model.name, page_model.pid, page_model.cid, integrity_error
+                    );
+                }
+            })
+        },
+        async {
+            let res = generate_page_nfo(
+                separate_status[2],
+                video_model,
+                video_source.nfo_time_type().as_ref(),
+                &page_model,
+                nfo_path,
+                connection,
+            )
+            .await;
+            // 演员头像随详情一同下载，失败不影响分页状态
+            fetch_actor_images(separate_status[2], video_model, downloader, base_path, token.clone()).await;
+            // 片头/片尾跳过标记随详情一同生成，失败不影响分页状态
+            if is_bangumi {
+                write_bangumi_skip_markers(
+                    separate_status[2],
+                    bili_client,
+                    season_skips,
+                    video_model,
+                    &video_path,
+                    token.clone(),
+                )
+                .await;
+            }
+            // 看点章节随详情一同生成，失败不影响分页状态
+            write_page_chapters(
+                separate_status[2],
+                bili_client,
+                video_model,
+                &page_info,
+                &player_info,
+                &video_path,
+                token.clone(),
+            )
+            .await;
+            res
+        },
+        fetch_page_danmaku(
+            separate_status[3],
+            bili_client,
+            video_model,
+            &page_info,
+            danmaku_path.clone(),
+            token.clone(),
+        ),
+        fetch_page_subtitle(
+            separate_status[4],
+            bili_client,
+            video_model,
             &page_info,
+            &player_info,
             &subtitle_path,
             token.clone(),
         )
     );
 
+    // 新下载的视频按视频源设置嵌入弹幕，链接到其他视频源的文件不修改，失败时保留外挂弹幕，不影响分页状态
+    let danmaku_embed_mode = video_source.danmaku_embed_mode();
+    if danmaku_embed_mode != DanmakuEmbedMode::None
+        && !strm_mode
+        && !audio_only
+        && canonical_path.is_none()
+        && !trusted_existing
+        && matches!(res_2, Ok(ExecutionStatus::Succeeded))
+        && danmaku_path.exists()
+    {
+        match crate::utils::ffmpeg::embed_danmaku(&video_path, &danmaku_path, danmaku_embed_mode).await {
+            Ok(_) => debug!(
+                "视频「{}」第 {} 页已嵌入弹幕（{}）",
+                video_model.name,
+                page_model.pid,
+                danmaku_embed_mode.as_str()
+            ),
+            Err(e) => warn!(
+                "视频「{}」第 {} 页嵌入弹幕失败，保留外挂弹幕文件: {:#}",
+                video_model.name, page_model.pid, e
+            ),
+        }
+    }
+
+    // 下载中断但留有可续传的临时文件时，视频子任务保持未完成且不计入失败次数，下次扫描从断点继续
+    let mut resumable = !strm_mode
+        && matches!(res_2, Ok(ExecutionStatus::Failed(_)) | Err(_))
+        && page_download_resumable(&video_path).await;
+    if resumable && !record_page_resume_attempt(page_model.id) {
+        warn!(
+            "视频「{}」第 {} 页已连续 {} 次从断点续传失败，丢弃续传进度并计为失败",
+            video_model.name, page_model.pid, MAX_PAGE_RESUME_ATTEMPTS
+        );
+        discard_page_download(&video_path).await;
+        resumable = false;
+    } else if !resumable {
+        PAGE_RESUME_ATTEMPTS.lock().unwrap().remove(&page_model.id);
+    }
+    let res_2 = match res_2 {
+        Ok(ExecutionStatus::Failed(e)) | Err(e) if resumable => {
+            info!(
+                "视频「{}」第 {} 页下载中断，已保留断点续传进度，下次扫描继续下载",
+                video_model.name, page_model.pid
+            );
+            Ok(ExecutionStatus::Ignored(e))
+        }
+        other => other,
+    };
+
     let results = [res_1, res_2, res_3, res_4, res_5]
         .into_iter()
         .map(Into::into)
@@ -2948,9 +3730,35 @@ pub async fn download_page(
     let mut page_active_model: page::ActiveModel = page_model.into();
     page_active_model.download_status = Set(status.into());
     page_active_model.path = Set(Some(video_path.to_string_lossy().to_string()));
+    if separate_status[1] {
+        page_active_model.canonical_path = Set(canonical_path);
+        if status.get(1) == STATUS_OK {
+            page_active_model.downloaded_quality = Set(downloaded_quality);
+        }
+    }
     Ok(page_active_model)
 }
 
+/// 信任已有文件时要求的最低平均码率（字节/秒），低于该值的文件视为不完整
+const MIN_TRUSTED_BYTES_PER_SECOND: u64 = 4 * 1024;
+
+/// 按分页时长判断已有文件的大小是否合理
+fn is_plausible_file_size(size: u64, duration: u32) -> bool {
+    size > 0 && size >= duration as u64 * MIN_TRUSTED_BYTES_PER_SECOND
+}
+
+/// 判断目标路径是否已存在大小合理的文件
+async fn has_plausible_existing_file(path: &Path, duration: u32) -> bool {
+    match fs::metadata(path).await {
+        Ok(metadata) => metadata.is_file() && is_plausible_file_size(metadata.len(), duration),
+        Err(_) => false,
+    }
+}
+
+/// 从视频中截取封面的位置，取视频时长的 10% 处以避开片头黑屏
+const THUMBNAIL_POSITION: f64 = 0.1;
+
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_page_poster(
     should_run: bool,
     video_model: &video::Model,
@@ -2958,6 +3766,7 @@ pub async fn fetch_page_poster(
     downloader: &UnifiedDownloader,
     poster_path: PathBuf,
     fanart_path: Option<PathBuf>,
+    video_path: Option<&Path>,
     token: CancellationToken,
 ) -> Result<ExecutionStatus> {
     if !should_run {
@@ -2974,12 +3783,34 @@ pub async fn fetch_page_poster(
             None => video_model.cover.as_str(),
         }
     };
-    let urls = vec![url];
-    tokio::select! {
-        biased;
-        _ = token.cancelled() => return Ok(ExecutionStatus::Skipped),
-        res = downloader.fetch_with_fallback(&urls, &poster_path) => res,
-    }?;
+    let url = crate::utils::cover::sized_cover_url(url);
+    let poster_path = crate::utils::cover::cover_file_path(poster_path);
+    let fanart_path = fanart_path.map(crate::utils::cover::cover_file_path);
+    let res = if url.is_empty() {
+        Err(anyhow!("封面地址为空"))
+    } else {
+        let urls = vec![url.as_ref()];
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => return Ok(ExecutionStatus::Skipped),
+            res = downloader.fetch_with_fallback(&urls, &poster_path) => res,
+        }
+    };
+    if let Err(e) = res {
+        // 封面缺失或下载失败时，从已下载的视频中截取一帧作为封面
+        let generate_thumbnail = crate::config::with_config(|bundle| bundle.config.generate_thumbnail_fallback);
+        let Some(video_path) = video_path.filter(|path| generate_thumbnail && path.exists()) else {
+            return Err(e);
+        };
+        warn!(
+            "视频「{}」第 {} 页封面获取失败，改为从视频中截取: {:#}",
+            video_model.name, page_model.pid, e
+        );
+        let at_seconds = page_model.duration as f64 * THUMBNAIL_POSITION;
+        crate::utils::ffmpeg::extract_frame(video_path, at_seconds, &poster_path)
+            .await
+            .context("从视频中截取封面失败")?;
+    }
     if let Some(fanart_path) = fanart_path {
         ensure_parent_dir_for_file(&fanart_path).await?;
         fs::copy(&poster_path, &fanart_path).await?;
@@ -2988,10 +3819,17 @@ pub async fn fetch_page_poster(
 }
 
 /// 下载单个流文件并返回文件大小（使用UnifiedDownloader智能选择下载方式）
-async fn download_stream(downloader: &UnifiedDownloader, urls: &[&str], path: &Path) -> Result<u64> {
+/// label 标明文件所属的视频与分页，用于重试日志
+async fn download_stream(
+    downloader: &UnifiedDownloader,
+    urls: &[&str],
+    path: &Path,
+    expected: &ExpectedFile,
+    label: &str,
+) -> Result<u64> {
     // 直接使用UnifiedDownloader，它会智能选择aria2或原生下载器
     // aria2本身就支持多线程，原生下载器作为备选方案使用单线程
-    let download_result = downloader.fetch_with_fallback(urls, path).await;
+    let download_result = downloader.fetch_with_retry(urls, path, expected, label).await;
 
     match download_result {
         Ok(_) => {
@@ -3028,9 +3866,23 @@ async fn download_stream(downloader: &UnifiedDownloader, urls: &[&str], path: &P
 }
 
 async fn download_flv_stream(
+    downloader: &UnifiedDownloader,
+    segments: Vec<FlvSegment>,
+    path: &Path,
+    label: &str,
+) -> Result<u64> {
+    // 分段在暂存目录中下载并拼接，完成后再移动到目标位置
+    let staged_path = staging_path(path);
+    let size = download_flv_segments(downloader, segments, &staged_path, label).await?;
+    move_to_destination(&staged_path, path).await?;
+    Ok(size)
+}
+
+async fn download_flv_segments(
     downloader: &UnifiedDownloader,
     mut segments: Vec<FlvSegment>,
     path: &Path,
+    label: &str,
 ) -> Result<u64> {
     if segments.is_empty() {
         bail!("FLV流分段为空");
@@ -3039,7 +3891,7 @@ async fn download_flv_stream(
     if segments.len() == 1 {
         let segment = segments.pop().unwrap();
         let urls: Vec<&str> = segment.urls.iter().map(|u| u.as_str()).collect();
-        return download_stream(downloader, &urls, path).await;
+        return download_stream(downloader, &urls, path, &segment.expected_file(), label).await;
     }
 
     segments.sort_by_key(|segment| segment.order);
@@ -3063,7 +3915,8 @@ async fn download_flv_stream(
             urls.len()
         );
 
-        match download_stream(downloader, &urls, &part_path).await {
+        let segment_label = format!("{} 分段 {}/{}", label, idx + 1, segment_count);
+        match download_stream(downloader, &urls, &part_path, &segment.expected_file(), &segment_label).await {
             Ok(size) => {
                 total_downloaded += size;
                 part_paths.push(part_path);
@@ -3132,25 +3985,17 @@ async fn download_flv_stream(
     Ok(final_size)
 }
 
-pub async fn fetch_page_video(
-    should_run: bool,
+/// 获取分页的视频流分析器，番剧优先使用番剧 API，普通视频在必要时降级到番剧 API
+async fn get_page_analyzer(
     bili_client: &BiliClient,
     video_model: &video::Model,
-    downloader: &UnifiedDownloader,
     page_info: &PageInfo,
-    page_path: &Path,
     token: CancellationToken,
-) -> Result<ExecutionStatus> {
-    if !should_run {
-        return Ok(ExecutionStatus::Skipped);
-    }
-
+) -> Result<PageAnalyzer> {
     let bili_video = Video::new(bili_client, video_model.bvid.clone());
-
-    // 获取视频流信息 - 使用带API降级机制的调用
-    let mut streams = tokio::select! {
+    tokio::select! {
         biased;
-        _ = token.cancelled() => return Err(anyhow!("Download cancelled")),
+        _ = token.cancelled() => Err(anyhow!("Download cancelled")),
         res = async {
             // 检查是否为番剧视频
             if video_model.source_type == Some(1) && video_model.ep_id.is_some() {
@@ -3171,7 +4016,329 @@ pub async fn fetch_page_video(
                 bili_video.get_page_analyzer_with_api_fallback(page_info, ep_id).await
             }
         } => res
-    }?;
+    }
+}
+
+/// 以 .strm 模式处理分页：解析最佳视频流并将播放地址写入 .strm 文件，不下载视频流本身
+pub async fn fetch_page_strm(
+    should_run: bool,
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_info: &PageInfo,
+    strm_path: &Path,
+    token: CancellationToken,
+) -> Result<ExecutionStatus> {
+    if !should_run {
+        return Ok(ExecutionStatus::Skipped);
+    }
+    write_page_strm(bili_client, video_model, page_info, strm_path, token).await?;
+    Ok(ExecutionStatus::Succeeded)
+}
+
+/// 解析分页当前的播放地址并写入 .strm 文件
+async fn write_page_strm(
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_info: &PageInfo,
+    strm_path: &Path,
+    token: CancellationToken,
+) -> Result<()> {
+    let mut streams = get_page_analyzer(bili_client, video_model, page_info, token).await?;
+    let config = crate::config::reload_config();
+    let url = match streams.best_stream(&config.filter_option)? {
+        BestStream::Mixed(stream) => stream.urls().first().map(|url| (*url).to_owned()),
+        // .strm 只能记录一个地址，而 DASH 视频流不含音频，优先改用音视频合一的 MP4 流
+        BestStream::VideoAudio { video, audio: Some(_) } => {
+            match mixed_stream_url(bili_client, video_model, page_info).await {
+                Ok(url) => Some(url),
+                Err(e) => {
+                    warn!(
+                        "视频「{}」(cid: {}) 没有可用的音视频合一流，.strm 只能写入不含音频的 DASH 视频流地址，播放时将没有声音: {:#}",
+                        video_model.name, page_info.cid, e
+                    );
+                    video.urls().first().map(|url| (*url).to_owned())
+                }
+            }
+        }
+        BestStream::VideoAudio { video, audio: None } => video.urls().first().map(|url| (*url).to_owned()),
+    }
+    .context("未找到可用的播放地址")?;
+    ensure_parent_dir_for_file(strm_path).await?;
+    fs::write(strm_path, format!("{}\n", url)).await?;
+    Ok(())
+}
+
+/// 获取分页音视频合一流的播放地址
+async fn mixed_stream_url(
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_info: &PageInfo,
+) -> Result<String> {
+    let mut streams = Video::new(bili_client, video_model.bvid.clone())
+        .get_page_mixed_analyzer(page_info)
+        .await?;
+    let config = crate::config::reload_config();
+    match streams.best_stream(&config.filter_option)? {
+        BestStream::Mixed(stream) => stream
+            .urls()
+            .first()
+            .map(|url| (*url).to_owned())
+            .context("未找到可用的播放地址"),
+        BestStream::VideoAudio { .. } => bail!("接口返回的不是音视频合一流"),
+    }
+}
+
+/// 播放地址在距离过期不足该时长时才刷新 .strm 文件
+const STRM_REFRESH_MARGIN_SECS: i64 = 10 * 60;
+
+/// 每轮扫描最多刷新的 .strm 文件数量，避免单次扫描集中请求大量播放地址触发风控，其余留到下一轮扫描
+const MAX_STRM_REFRESH_PER_SCAN: usize = 100;
+
+/// 解析播放地址的过期时间（deadline 参数，Unix 时间戳）
+fn strm_url_deadline(url: &str) -> Option<i64> {
+    let url = reqwest::Url::parse(url.trim()).ok()?;
+    url.query_pairs()
+        .find(|(key, _)| key == "deadline")
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// .strm 文件中的播放地址是否需要刷新：无法解析过期时间，或会在 `refresh_before` 之前过期
+async fn strm_needs_refresh(strm_path: &Path, refresh_before: i64) -> bool {
+    match fs::read_to_string(strm_path).await {
+        Ok(content) => strm_url_deadline(&content).is_none_or(|deadline| deadline <= refresh_before),
+        Err(_) => true,
+    }
+}
+
+/// 将已下载完成的分页文件（视频、NFO、封面、弹幕、字幕）上传到 WebDAV，保持相对视频源的目录结构
+/// 上传失败只记录在 webdav_upload_status 中，本地文件不受影响
+async fn upload_pages_to_webdav(
+    video_source: &VideoSourceEnum,
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+) -> Result<()> {
+    let config = crate::config::reload_config();
+    let Some(client) = WebDavClient::from_config(&config.webdav)? else {
+        return Ok(());
+    };
+    let videos_pages = video::Entity::find()
+        .filter(
+            video::Column::Valid
+                .eq(true)
+                .and(video::Column::Deleted.eq(0))
+                .and(video_source.filter_expr()),
+        )
+        .find_with_related(page::Entity)
+        .all(connection)
+        .await?;
+    let (mut uploaded, mut failed) = (0, 0);
+    for (video_model, pages) in videos_pages {
+        for page_model in pages {
+            if token.is_cancelled() || crate::task::TASK_CONTROLLER.is_paused() {
+                return Ok(());
+            }
+            let mut upload_status = UploadStatus::from(page_model.webdav_upload_status);
+            if !upload_status.should_run()[0] || PageStatus::from(page_model.download_status).get(1) != STATUS_OK {
+                continue;
+            }
+            let Some(page_path) = page_model.path.as_deref().map(Path::new).filter(|path| path.exists()) else {
+                continue;
+            };
+            let result = upload_page_files(&client, video_source.path(), page_path).await;
+            let status = match result {
+                Ok(files) => {
+                    uploaded += 1;
+                    if config.webdav.delete_local {
+                        for file in files {
+                            if let Err(e) = fs::remove_file(&file).await {
+                                warn!("删除已上传的本地文件 {} 失败: {:#}", file.display(), e);
+                            }
+                        }
+                    }
+                    ExecutionStatus::Succeeded
+                }
+                Err(e) => {
+                    failed += 1;
+                    warn!(
+                        "上传视频「{}」第{}P 到 WebDAV 失败: {:#}",
+                        video_model.name, page_model.pid, e
+                    );
+                    ExecutionStatus::Failed(e)
+                }
+            };
+            upload_status.update_status(&[status]);
+            let mut page_active_model: page::ActiveModel = page_model.into();
+            page_active_model.webdav_upload_status = Set(upload_status.into());
+            page_active_model.update(connection).await?;
+        }
+    }
+    if uploaded > 0 || failed > 0 {
+        info!("WebDAV 上传完成：成功 {} 个分页，失败 {} 个分页", uploaded, failed);
+    }
+    Ok(())
+}
+
+/// 上传分页目录中与分页文件同名前缀的所有文件，返回已上传的本地文件列表
+async fn upload_page_files(client: &WebDavClient, source_path: &Path, page_path: &Path) -> Result<Vec<PathBuf>> {
+    let (Some(dir), Some(stem)) = (page_path.parent(), page_path.file_stem().and_then(|s| s.to_str())) else {
+        bail!("分页路径 {} 无效", page_path.display());
+    };
+    // 远端目录为 视频源目录名/相对视频源的路径
+    let relative_dir = dir.strip_prefix(source_path).unwrap_or(dir);
+    let mut dir_segments: Vec<String> = source_path
+        .file_name()
+        .into_iter()
+        .chain(relative_dir.iter())
+        .map(|s| s.to_string_lossy().into_owned())
+        .filter(|s| !s.is_empty() && s != "/")
+        .collect();
+    client
+        .ensure_dir(&dir_segments.iter().map(String::as_str).collect::<Vec<_>>())
+        .await?;
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !file_name.starts_with(stem) || !entry.file_type().await?.is_file() {
+            continue;
+        }
+        dir_segments.push(file_name);
+        client
+            .upload_file(
+                &entry.path(),
+                &dir_segments.iter().map(String::as_str).collect::<Vec<_>>(),
+            )
+            .await?;
+        dir_segments.pop();
+        files.push(entry.path());
+    }
+    Ok(files)
+}
+
+/// 刷新视频源下已生成的 .strm 文件，播放地址有时效，只重新解析即将过期或已经过期的地址
+async fn refresh_strm_files(
+    bili_client: &BiliClient,
+    video_source: &VideoSourceEnum,
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+) -> Result<()> {
+    let videos_pages = video::Entity::find()
+        .filter(
+            video::Column::Valid
+                .eq(true)
+                .and(video::Column::Deleted.eq(0))
+                .and(video_source.filter_expr()),
+        )
+        .find_with_related(page::Entity)
+        .all(connection)
+        .await?;
+    let refresh_before = chrono::Utc::now().timestamp() + STRM_REFRESH_MARGIN_SECS;
+    let mut attempted = 0;
+    let mut refreshed = 0;
+    'videos: for (video_model, pages) in videos_pages {
+        for page_model in pages {
+            if token.is_cancelled() || crate::task::TASK_CONTROLLER.is_paused() {
+                return Ok(());
+            }
+            let Some(strm_path) = page_model.path.as_deref().filter(|path| path.ends_with(".strm")) else {
+                continue;
+            };
+            let strm_path = Path::new(strm_path);
+            if !strm_path.exists() || !strm_needs_refresh(strm_path, refresh_before).await {
+                continue;
+            }
+            if attempted >= MAX_STRM_REFRESH_PER_SCAN {
+                info!(
+                    "本轮扫描已刷新 {} 个 .strm 文件，达到单轮上限，其余文件留到下一轮扫描刷新",
+                    attempted
+                );
+                break 'videos;
+            }
+            attempted += 1;
+            let page_info = PageInfo {
+                cid: page_model.cid,
+                duration: page_model.duration,
+                ..Default::default()
+            };
+            match write_page_strm(bili_client, &video_model, &page_info, strm_path, token.clone()).await {
+                Ok(()) => refreshed += 1,
+                Err(e) => warn!(
+                    "刷新视频「{}」第{}P 的 .strm 文件失败: {:#}",
+                    video_model.name, page_model.pid, e
+                ),
+            }
+        }
+    }
+    if refreshed > 0 {
+        info!("已刷新 {} 个 .strm 文件的播放地址", refreshed);
+    }
+    Ok(())
+}
+
+/// 下载日志中标明分页所属的视频，形如 视频「标题」(BV号) 第 1 页
+fn download_label(video_model: &video::Model, page_info: &PageInfo) -> String {
+    format!(
+        "视频「{}」({}) 第 {} 页",
+        video_model.name, video_model.bvid, page_info.page
+    )
+}
+
+/// 分页视频下载可能留下续传进度的文件，包括直接下载的文件与合并前的音视频流
+fn page_download_files(page_path: &Path) -> [PathBuf; 3] {
+    let staged_page_path = staging_path(page_path);
+    [
+        page_path.to_path_buf(),
+        staged_page_path.with_extension("tmp_video"),
+        staged_page_path.with_extension("tmp_audio"),
+    ]
+}
+
+/// 分页视频是否有中断后可以续传的下载
+async fn page_download_resumable(page_path: &Path) -> bool {
+    for path in page_download_files(page_path) {
+        if has_resumable_download(&path).await {
+            return true;
+        }
+    }
+    false
+}
+
+/// 丢弃分页视频下载的续传进度，下次下载从头开始
+async fn discard_page_download(page_path: &Path) {
+    for path in page_download_files(page_path) {
+        discard_resumable_download(&path).await;
+    }
+}
+
+/// 记录分页又一次保留了续传进度，返回是否仍未超过连续续传的次数上限，超过时清除记录
+fn record_page_resume_attempt(page_id: i32) -> bool {
+    let mut attempts = PAGE_RESUME_ATTEMPTS.lock().unwrap();
+    let count = attempts.entry(page_id).or_default();
+    *count += 1;
+    if *count > MAX_PAGE_RESUME_ATTEMPTS {
+        attempts.remove(&page_id);
+        return false;
+    }
+    true
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_page_video(
+    should_run: bool,
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    downloader: &UnifiedDownloader,
+    page_info: &PageInfo,
+    page_path: &Path,
+    downloaded_quality: &mut Option<u32>,
+    token: CancellationToken,
+) -> Result<ExecutionStatus> {
+    if !should_run {
+        return Ok(ExecutionStatus::Skipped);
+    }
+
+    // 获取视频流信息 - 使用带API降级机制的调用
+    let mut streams = get_page_analyzer(bili_client, video_model, page_info, token.clone()).await?;
 
     // 按需创建保存目录（只在实际下载时创建）
     ensure_parent_dir_for_file(page_path).await?;
@@ -3279,13 +4446,21 @@ pub async fn fetch_page_video(
         }
     }
     debug!("=== 流选择结束 ===");
+    if let BestStream::VideoAudio {
+        video: VideoStream::DashVideo { quality, .. },
+        ..
+    } = &best_stream_result
+    {
+        *downloaded_quality = Some(*quality as u32);
+    }
 
+    let label = download_label(video_model, page_info);
     let total_bytes = match best_stream_result {
         BestStream::Mixed(mix_stream) => match mix_stream {
-            VideoStream::Flv { segments } => download_flv_stream(downloader, segments, page_path).await?,
+            VideoStream::Flv { segments } => download_flv_stream(downloader, segments, page_path, &label).await?,
             other => {
                 let urls = other.urls();
-                download_stream(downloader, &urls, page_path).await?
+                download_stream(downloader, &urls, page_path, &ExpectedFile::default(), &label).await?
             }
         },
         BestStream::VideoAudio {
@@ -3293,58 +4468,76 @@ pub async fn fetch_page_video(
             audio: None,
         } => {
             let urls = video_stream.urls();
-            download_stream(downloader, &urls, page_path).await?
+            download_stream(downloader, &urls, page_path, &ExpectedFile::default(), &label).await?
         }
         BestStream::VideoAudio {
             video: video_stream,
             audio: Some(audio_stream),
         } => {
+            // 音视频流及合并结果都先写入暂存目录，合并完成后再移动到目标位置
+            let staged_page_path = staging_path(page_path);
             let (tmp_video_path, tmp_audio_path) = (
-                page_path.with_extension("tmp_video"),
-                page_path.with_extension("tmp_audio"),
+                staged_page_path.with_extension("tmp_video"),
+                staged_page_path.with_extension("tmp_audio"),
             );
 
             let video_urls = video_stream.urls();
-            let video_size = download_stream(downloader, &video_urls, &tmp_video_path)
-                .await
-                .map_err(|e| {
-                    // 使用错误分类器进行统一处理
-                    let classified_error = crate::error::ErrorClassifier::classify_error(&e);
-                    match classified_error.error_type {
-                        crate::error::ErrorType::UserCancelled => {
-                            info!("视频流下载因用户暂停而终止");
-                        }
-                        _ => {
-                            error!("视频流下载失败: {:#}", e);
-                        }
+            let video_label = format!("{} 视频流", label);
+            let video_size = download_stream(
+                downloader,
+                &video_urls,
+                &tmp_video_path,
+                &ExpectedFile::default(),
+                &video_label,
+            )
+            .await
+            .map_err(|e| {
+                // 使用错误分类器进行统一处理
+                let classified_error = crate::error::ErrorClassifier::classify_error(&e);
+                match classified_error.error_type {
+                    crate::error::ErrorType::UserCancelled => {
+                        info!("视频流下载因用户暂停而终止");
+                    }
+                    _ => {
+                        error!("视频流下载失败: {:#}", e);
                     }
-                    e
-                })?;
+                }
+                e
+            })?;
 
             let audio_urls = audio_stream.urls();
-            let audio_size = download_stream(downloader, &audio_urls, &tmp_audio_path)
-                .await
-                .map_err(|e| {
-                    // 使用错误分类器进行统一处理
-                    let classified_error = crate::error::ErrorClassifier::classify_error(&e);
-                    match classified_error.error_type {
-                        crate::error::ErrorType::UserCancelled => {
-                            info!("音频流下载因用户暂停而终止");
-                        }
-                        _ => {
-                            error!("音频流下载失败: {:#}", e);
-                        }
+            let audio_label = format!("{} 音频流", label);
+            let audio_size = download_stream(
+                downloader,
+                &audio_urls,
+                &tmp_audio_path,
+                &ExpectedFile::default(),
+                &audio_label,
+            )
+            .await
+            .map_err(|e| {
+                // 使用错误分类器进行统一处理
+                let classified_error = crate::error::ErrorClassifier::classify_error(&e);
+                match classified_error.error_type {
+                    crate::error::ErrorType::UserCancelled => {
+                        info!("音频流下载因用户暂停而终止");
                     }
-                    // 异步删除临时视频文件
-                    let video_path_clone = tmp_video_path.clone();
-                    tokio::spawn(async move {
-                        let _ = fs::remove_file(&video_path_clone).await;
-                    });
-                    e
-                })?;
+                    _ => {
+                        error!("音频流下载失败: {:#}", e);
+                        // 异步删除临时视频文件，用户暂停时保留以便下次继续
+                        let video_path_clone = tmp_video_path.clone();
+                        tokio::spawn(async move {
+                            let _ = fs::remove_file(&video_path_clone).await;
+                        });
+                    }
+                }
+                e
+            })?;
 
             // 增强的音视频合并，带损坏文件检测和重试机制
-            let res = downloader.merge(&tmp_video_path, &tmp_audio_path, page_path).await;
+            let res = downloader
+                .merge(&tmp_video_path, &tmp_audio_path, &staged_page_path)
+                .await;
 
             // 合并失败时的智能处理
             if let Err(e) = res {
@@ -3378,6 +4571,7 @@ pub async fn fetch_page_video(
             // 合并成功，清理临时文件
             let _ = fs::remove_file(tmp_video_path).await;
             let _ = fs::remove_file(tmp_audio_path).await;
+            move_to_destination(&staged_page_path, page_path).await?;
 
             // 获取合并后文件大小，如果失败则使用视频和音频大小之和
             tokio::fs::metadata(page_path)
@@ -3413,6 +4607,87 @@ pub async fn fetch_page_video(
     Ok(ExecutionStatus::Succeeded)
 }
 
+/// 仅音频模式下载分页：只下载音频流，由 FFmpeg 提取音轨并写入标签和封面
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_page_audio(
+    should_run: bool,
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_model: &page::Model,
+    downloader: &UnifiedDownloader,
+    page_info: &PageInfo,
+    page_path: &Path,
+    token: CancellationToken,
+) -> Result<ExecutionStatus> {
+    if !should_run {
+        return Ok(ExecutionStatus::Skipped);
+    }
+
+    let mut streams = get_page_analyzer(bili_client, video_model, page_info, token.clone()).await?;
+    ensure_parent_dir_for_file(page_path).await?;
+    let config = crate::config::reload_config();
+    let audio_stream = streams.best_audio_stream(&config.filter_option)?;
+    debug!("仅音频模式选择音频流: {:?}", audio_stream);
+
+    let staged_page_path = staging_path(page_path);
+    let (tmp_audio_path, tmp_cover_path) = (
+        staged_page_path.with_extension("tmp_audio"),
+        staged_page_path.with_extension("tmp_cover"),
+    );
+    let label = download_label(video_model, page_info);
+    match audio_stream {
+        VideoStream::Flv { segments } => download_flv_stream(downloader, segments, &tmp_audio_path, &label).await?,
+        other => {
+            download_stream(
+                downloader,
+                &other.urls(),
+                &tmp_audio_path,
+                &ExpectedFile::default(),
+                &label,
+            )
+            .await?
+        }
+    };
+
+    // 封面下载失败时仍输出不带封面的音频文件
+    let cover_url = match (video_model.single_page, &page_model.image) {
+        (Some(false), Some(image)) => image.as_str(),
+        _ => video_model.cover.as_str(),
+    };
+    let cover_path = match downloader.fetch_with_fallback(&[cover_url], &tmp_cover_path).await {
+        Ok(_) => Some(tmp_cover_path.as_path()),
+        Err(e) => {
+            warn!("下载音频封面失败，将不嵌入封面: {:#}", e);
+            None
+        }
+    };
+
+    let is_single_page = video_model.single_page.unwrap_or(true);
+    let tags = AudioTags {
+        title: if is_single_page {
+            video_model.name.clone()
+        } else {
+            page_model.name.clone()
+        },
+        artist: video_model.upper_name.clone(),
+        album: (!is_single_page).then(|| video_model.name.clone()),
+    };
+    let res = downloader
+        .extract_audio(&tmp_audio_path, cover_path, &tags, &staged_page_path)
+        .await;
+    let _ = fs::remove_file(&tmp_audio_path).await;
+    let _ = fs::remove_file(&tmp_cover_path).await;
+    if let Err(e) = res {
+        error!("提取音轨失败: {:#}", e);
+        let _ = fs::remove_file(&staged_page_path).await;
+        return Err(e);
+    }
+    move_to_destination(&staged_page_path, page_path).await?;
+    info!("音频下载完成: {}", page_path.display());
+
+    Ok(ExecutionStatus::Succeeded)
+}
+
 pub async fn fetch_page_danmaku(
     should_run: bool,
     bili_client: &BiliClient,
@@ -3476,22 +4751,32 @@ pub async fn fetch_page_subtitle(
     bili_client: &BiliClient,
     video_model: &video::Model,
     page_info: &PageInfo,
+    player_info: &OnceCell<serde_json::Value>,
     subtitle_path: &Path,
     token: CancellationToken,
 ) -> Result<ExecutionStatus> {
-    if !should_run {
+    if !should_run || !crate::config::with_config(|bundle| bundle.config.download_subtitles) {
         return Ok(ExecutionStatus::Skipped);
     }
     let bili_video = Video::new(bili_client, video_model.bvid.clone());
     let subtitles = tokio::select! {
         biased;
         _ = token.cancelled() => return Err(anyhow!("Download cancelled")),
-        res = bili_video.get_subtitles(page_info) => res?,
+        res = async {
+            let player_info = player_info.get_or_try_init(|| bili_video.get_player_info(page_info)).await?;
+            bili_video.get_subtitles_from(player_info).await
+        } => res?,
     };
+    if subtitles.is_empty() {
+        // 没有字幕的视频很常见，直接跳过而不是视为失败
+        debug!("视频 {} 的分页 {} 没有可用字幕", video_model.bvid, page_info.cid);
+        return Ok(ExecutionStatus::Skipped);
+    }
     let tasks = subtitles
         .into_iter()
         .map(|subtitle| async move {
-            let path = subtitle_path.with_extension(format!("{}.srt", subtitle.lan));
+            let stem = subtitle_path.file_stem().unwrap_or_default().to_string_lossy();
+            let path = subtitle_path.with_file_name(page_files::subtitle_file_name(&stem, &subtitle.lan));
             ensure_parent_dir_for_file(&path).await.map_err(std::io::Error::other)?;
             tokio::fs::write(path, subtitle.body.to_string()).await
         })
@@ -3503,6 +4788,7 @@ pub async fn fetch_page_subtitle(
 pub async fn generate_page_nfo(
     should_run: bool,
     video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
     page_model: &page::Model,
     nfo_path: PathBuf,
     _connection: &DatabaseConnection,
@@ -3560,10 +4846,185 @@ pub async fn generate_page_nfo(
             NFO::Episode(episode)
         }
     };
-    generate_nfo(nfo, nfo_path).await?;
+    generate_nfo(nfo.with_time_type(video_model, time_type), nfo_path).await?;
     Ok(ExecutionStatus::Succeeded)
 }
 
+/// 将创作团队头像下载到 NFO 同级的 .actors 目录，文件名与 NFO 中的 <thumb> 一致，已存在的头像不会重复下载
+async fn fetch_actor_images(
+    should_run: bool,
+    video_model: &video::Model,
+    downloader: &UnifiedDownloader,
+    base_path: &Path,
+    token: CancellationToken,
+) {
+    if !should_run || !crate::config::with_config(|bundle| bundle.config.nfo_download_actor_images) {
+        return;
+    }
+    for member in crate::utils::nfo::staff_from_video(video_model) {
+        if !member.face.starts_with("http") {
+            continue;
+        }
+        let image_path = base_path.join(crate::utils::nfo::actor_image_path(&member.name));
+        if image_path.exists() {
+            continue;
+        }
+        let urls = vec![member.face.as_str()];
+        let res = async {
+            ensure_parent_dir_for_file(&image_path).await?;
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => Ok(()),
+                res = downloader.fetch_with_fallback(&urls, &image_path) => res,
+            }
+        }
+        .await;
+        if let Err(e) = res {
+            warn!("下载演员「{}」的头像失败: {:#}", member.name, e);
+        }
+    }
+}
+
+/// 季度片头/片尾跳过信息的缓存单元，获取失败时记录为 None，不再重复请求
+type SeasonSkipCell = Arc<OnceCell<Option<HashMap<String, EpisodeSkip>>>>;
+
+/// 本轮下载中各番剧季度的片头/片尾跳过信息，同一季的分集共用一次详情请求
+///
+/// 键为 season_id（缺失时为 ep_id），每个季度使用单独的缓存单元，不同季度的请求互不阻塞
+#[derive(Default)]
+pub struct SeasonSkipCache(Mutex<HashMap<String, SeasonSkipCell>>);
+
+impl SeasonSkipCache {
+    /// 获取分集的跳过信息，所在季度首次用到时请求详情接口，获取失败时返回 None
+    async fn episode_skip(
+        &self,
+        bili_client: &BiliClient,
+        video_model: &video::Model,
+        ep_id: &str,
+    ) -> Option<EpisodeSkip> {
+        let season_id = video_model.season_id.as_deref().filter(|id| !id.is_empty());
+        let key = season_id.map_or_else(|| format!("ep_{}", ep_id), str::to_string);
+        // 只在取出缓存单元时持有锁，请求在锁外进行，同一季的其他分集等待该单元的结果而不是重复请求
+        let cell = self.0.lock().unwrap().entry(key).or_default().clone();
+        let skips = cell
+            .get_or_init(|| async {
+                let bangumi = crate::bilibili::bangumi::Bangumi::new(
+                    bili_client,
+                    None,
+                    season_id.map(str::to_string),
+                    Some(ep_id.to_string()),
+                );
+                match bangumi.get_season_skips().await {
+                    Ok(skips) => Some(skips),
+                    Err(e) => {
+                        warn!(
+                            "获取番剧「{}」(EP{}) 的片头/片尾信息失败: {:#}",
+                            video_model.name, ep_id, e
+                        );
+                        None
+                    }
+                }
+            })
+            .await
+            .as_ref()?;
+        Some(skips.get(ep_id).cloned().unwrap_or_default())
+    }
+}
+
+/// 为番剧分集写入片头/片尾跳过标记（与视频同名的 .edl 文件），没有跳过信息的分集不生成文件
+async fn write_bangumi_skip_markers(
+    should_run: bool,
+    bili_client: &BiliClient,
+    season_skips: &SeasonSkipCache,
+    video_model: &video::Model,
+    video_path: &Path,
+    token: CancellationToken,
+) {
+    if !should_run || !crate::config::with_config(|bundle| bundle.config.bangumi_write_skip_markers) {
+        return;
+    }
+    let Some(ep_id) = video_model.ep_id.as_deref() else {
+        return;
+    };
+    let skip = tokio::select! {
+        biased;
+        _ = token.cancelled() => return,
+        skip = season_skips.episode_skip(bili_client, video_model, ep_id) => skip,
+    };
+    let Some(skip) = skip else {
+        return;
+    };
+    if skip.is_empty() {
+        debug!(
+            "番剧「{}」(EP{}) 没有片头/片尾信息，跳过生成标记文件",
+            video_model.name, ep_id
+        );
+        return;
+    }
+    let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+    let edl_path = video_path.with_file_name(page_files::edl_file_name(&stem));
+    let res = async {
+        ensure_parent_dir_for_file(&edl_path).await?;
+        fs::write(&edl_path, skip.to_edl()).await?;
+        Result::<()>::Ok(())
+    }
+    .await;
+    match res {
+        Ok(()) => debug!("已写入片头/片尾跳过标记: {}", edl_path.display()),
+        Err(e) => warn!("写入片头/片尾跳过标记 {} 失败: {:#}", edl_path.display(), e),
+    }
+}
+
+/// 将视频看点写入与视频同名的章节文件（.chapters.xml），没有看点的视频不生成文件
+async fn write_page_chapters(
+    should_run: bool,
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_info: &PageInfo,
+    player_info: &OnceCell<serde_json::Value>,
+    video_path: &Path,
+    token: CancellationToken,
+) {
+    if !should_run || !crate::config::with_config(|bundle| bundle.config.nfo_include_chapters) {
+        return;
+    }
+    let bili_video = Video::new(bili_client, video_model.bvid.clone());
+    let player_info = tokio::select! {
+        biased;
+        _ = token.cancelled() => return,
+        res = player_info.get_or_try_init(|| bili_video.get_player_info(page_info)) => res,
+    };
+    let points = match player_info {
+        Ok(player_info) => view_points_from_player_info(player_info),
+        Err(e) => {
+            warn!(
+                "获取视频「{}」(cid: {}) 的看点失败: {:#}",
+                video_model.name, page_info.cid, e
+            );
+            return;
+        }
+    };
+    if points.is_empty() {
+        debug!(
+            "视频 {} 的分页 {} 没有看点，跳过生成章节文件",
+            video_model.bvid, page_info.cid
+        );
+        return;
+    }
+    let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+    let chapters_path = video_path.with_file_name(page_files::chapters_file_name(&stem));
+    let res = async {
+        ensure_parent_dir_for_file(&chapters_path).await?;
+        fs::write(&chapters_path, to_chapters_xml(&points)).await?;
+        Result::<()>::Ok(())
+    }
+    .await;
+    match res {
+        Ok(()) => debug!("已写入 {} 个看点章节: {}", points.len(), chapters_path.display()),
+        Err(e) => warn!("写入章节文件 {} 失败: {:#}", chapters_path.display(), e),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn fetch_video_poster(
     should_run: bool,
@@ -3579,6 +5040,8 @@ pub async fn fetch_video_poster(
         return Ok(ExecutionStatus::Skipped);
     }
 
+    let poster_path = crate::utils::cover::cover_file_path(poster_path);
+    let fanart_path = crate::utils::cover::cover_file_path(fanart_path);
     debug!("开始处理视频「{}」的封面和背景图", video_model.name);
     debug!("  thumb路径: {:?}", poster_path);
     debug!("  fanart路径: {:?}", fanart_path);
@@ -3586,8 +5049,8 @@ pub async fn fetch_video_poster(
     debug!("  custom_fanart_url: {:?}", custom_fanart_url);
 
     // 下载thumb封面（依赖should_run参数，重置状态后会强制重新下载）
-    let thumb_url = custom_cover_url.unwrap_or(video_model.cover.as_str());
-    let urls = vec![thumb_url];
+    let thumb_url = crate::utils::cover::sized_cover_url(custom_cover_url.unwrap_or(video_model.cover.as_str()));
+    let urls = vec![thumb_url.as_ref()];
     tokio::select! {
         biased;
         _ = token.cancelled() => return Ok(ExecutionStatus::Skipped),
@@ -3596,9 +5059,9 @@ pub async fn fetch_video_poster(
 
     // 下载fanart背景图
     ensure_parent_dir_for_file(&fanart_path).await?;
-    if let Some(fanart_url) = custom_fanart_url {
+    if let Some(fanart_url) = custom_fanart_url.map(crate::utils::cover::sized_cover_url) {
         // 如果有专门的fanart URL，独立下载
-        let fanart_urls = vec![fanart_url];
+        let fanart_urls = vec![fanart_url.as_ref()];
         tokio::select! {
             biased;
             _ = token.cancelled() => return Ok(ExecutionStatus::Skipped),
@@ -3651,12 +5114,27 @@ pub async fn fetch_upper_face(
         return Ok(ExecutionStatus::Ignored(anyhow::anyhow!("无效的作者头像URL")));
     }
 
+    // 同一UP主的头像地址未变化时不重复下载
+    let face_hash = face_url_hash(upper_face_url);
+    let hash_path = upper_face_path.with_file_name(UPPER_FACE_HASH_FILE);
+    if upper_face_path.exists()
+        && fs::read_to_string(&hash_path)
+            .await
+            .is_ok_and(|hash| hash.trim() == face_hash)
+    {
+        debug!("作者头像未变化，跳过下载: {}", upper_face_path.display());
+        return Ok(ExecutionStatus::Succeeded);
+    }
+
     let urls = vec![upper_face_url.as_str()];
     tokio::select! {
         biased;
         _ = token.cancelled() => return Ok(ExecutionStatus::Skipped),
         res = downloader.fetch_with_fallback(&urls, &upper_face_path) => res,
     }?;
+    if let Err(e) = fs::write(&hash_path, &face_hash).await {
+        warn!("写入作者头像哈希文件 {} 失败: {:#}", hash_path.display(), e);
+    }
     Ok(ExecutionStatus::Succeeded)
 }
 
@@ -3684,6 +5162,7 @@ pub async fn fetch_bangumi_poster(
         return Ok(ExecutionStatus::Skipped);
     }
 
+    let poster_path = crate::utils::cover::cover_file_path(poster_path);
     debug!("开始处理番剧「{}」的主封面 poster.jpg", video_model.name);
     debug!("  poster路径: {:?}", poster_path);
     debug!("  custom_poster_url: {:?}", custom_poster_url);
@@ -3691,8 +5170,8 @@ pub async fn fetch_bangumi_poster(
     // 下载 poster.jpg 文件（依赖should_run参数，重置状态后会强制重新下载）
     ensure_parent_dir_for_file(&poster_path).await?;
 
-    let poster_url = custom_poster_url.unwrap_or(video_model.cover.as_str());
-    let urls = vec![poster_url];
+    let poster_url = crate::utils::cover::sized_cover_url(custom_poster_url.unwrap_or(video_model.cover.as_str()));
+    let urls = vec![poster_url.as_ref()];
 
     tokio::select! {
         biased;
@@ -3707,12 +5186,17 @@ pub async fn fetch_bangumi_poster(
 pub async fn generate_video_nfo(
     should_run: bool,
     video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
     nfo_path: PathBuf,
 ) -> Result<ExecutionStatus> {
     if !should_run {
         return Ok(ExecutionStatus::Skipped);
     }
-    generate_nfo(NFO::TVShow(video_model.into()), nfo_path).await?;
+    generate_nfo(
+        NFO::TVShow(video_model.into()).with_time_type(video_model, time_type),
+        nfo_path,
+    )
+    .await?;
     Ok(ExecutionStatus::Succeeded)
 }
 
@@ -3720,6 +5204,7 @@ pub async fn generate_video_nfo(
 pub async fn generate_collection_video_nfo(
     should_run: bool,
     video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
     collection_name: Option<&str>,
     collection_cover: Option<&str>,
     nfo_path: PathBuf,
@@ -3729,7 +5214,7 @@ pub async fn generate_collection_video_nfo(
     }
     use crate::utils::nfo::TVShow;
     let tvshow = TVShow::from_video_with_collection(video_model, collection_name, collection_cover);
-    generate_nfo(NFO::TVShow(tvshow), nfo_path).await?;
+    generate_nfo(NFO::TVShow(tvshow).with_time_type(video_model, time_type), nfo_path).await?;
     Ok(ExecutionStatus::Succeeded)
 }
 
@@ -3737,6 +5222,7 @@ pub async fn generate_collection_video_nfo(
 pub async fn generate_bangumi_video_nfo(
     should_run: bool,
     video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
     season_info: &SeasonInfo,
     nfo_path: PathBuf,
 ) -> Result<ExecutionStatus> {
@@ -3745,7 +5231,7 @@ pub async fn generate_bangumi_video_nfo(
     }
     use crate::utils::nfo::TVShow;
     let tvshow = TVShow::from_season_info(video_model, season_info);
-    generate_nfo(NFO::TVShow(tvshow), nfo_path).await?;
+    generate_nfo(NFO::TVShow(tvshow).with_time_type(video_model, time_type), nfo_path).await?;
     Ok(ExecutionStatus::Succeeded)
 }
 
@@ -3753,6 +5239,7 @@ pub async fn generate_bangumi_video_nfo(
 pub async fn generate_bangumi_season_nfo(
     should_run: bool,
     video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
     season_info: &SeasonInfo,
     season_path: PathBuf,
     _season_number: u32,
@@ -3772,11 +5259,39 @@ pub async fn generate_bangumi_season_nfo(
     let mut season = Season::from_season_info(video_model, season_info);
     season.season_number = _season_number as i32; // 设置正确的季度编号
 
-    generate_nfo(NFO::Season(season), nfo_path.clone()).await?;
+    generate_nfo(
+        NFO::Season(season).with_time_type(video_model, time_type),
+        nfo_path.clone(),
+    )
+    .await?;
     info!("成功生成season.nfo: {:?} (季度{})", nfo_path, _season_number);
     Ok(ExecutionStatus::Succeeded)
 }
 
+/// 为启用Season结构的合集或多P视频生成season.nfo文件
+pub async fn generate_video_season_nfo(
+    should_run: bool,
+    video_model: &video::Model,
+    time_type: Option<&NFOTimeType>,
+    collection_name: Option<&str>,
+    collection_cover: Option<&str>,
+    season_path: PathBuf,
+) -> Result<ExecutionStatus> {
+    if !should_run {
+        return Ok(ExecutionStatus::Skipped);
+    }
+    use crate::utils::nfo::{Season, SEASON_NFO_FILE};
+    let season = Season::from_video_with_collection(video_model, collection_name, collection_cover);
+    let nfo_path = season_path.join(SEASON_NFO_FILE);
+    generate_nfo(
+        NFO::Season(season).with_time_type(video_model, time_type),
+        nfo_path.clone(),
+    )
+    .await?;
+    debug!("成功生成season.nfo: {:?}", nfo_path);
+    Ok(ExecutionStatus::Succeeded)
+}
+
 /// 按需创建目录的辅助函数，只在实际需要写入文件时创建
 async fn ensure_parent_dir_for_file(file_path: &std::path::Path) -> Result<()> {
     if let Some(parent) = file_path.parent() {
@@ -3791,6 +5306,15 @@ async fn ensure_parent_dir_for_file(file_path: &std::path::Path) -> Result<()> {
 async fn generate_nfo(nfo: NFO<'_>, nfo_path: PathBuf) -> Result<()> {
     // 只在实际写入NFO文件时才创建父目录
     ensure_parent_dir_for_file(&nfo_path).await?;
+    // Plex 风格下，剧集目录额外写入 .plexmatch 帮助匹配
+    let plexmatch = if crate::config::reload_config().nfo_flavor == NFOFlavor::Plex {
+        nfo.plexmatch()
+    } else {
+        None
+    };
+    if let (Some(plexmatch), Some(parent)) = (plexmatch, nfo_path.parent()) {
+        fs::write(parent.join(".plexmatch"), plexmatch.as_bytes()).await?;
+    }
     fs::write(nfo_path, nfo.generate_nfo().await?.as_bytes()).await?;
     Ok(())
 }
@@ -3802,13 +5326,11 @@ async fn get_cached_season_title(
     token: CancellationToken,
 ) -> Option<String> {
     // 先检查缓存
-    if let Ok(cache) = SEASON_TITLE_CACHE.lock() {
-        if let Some(title) = cache.get(season_id) {
-            return Some(title.clone());
-        }
+    if let Some(title) = SEASON_TITLE_CACHE.get(season_id) {
+        return Some(title);
     }
 
-    // 缓存未命中，从API获取
+    // 缓存未命中或已过期，从API获取
     get_season_title_from_api(bili_client, season_id, token).await
 }
 
@@ -3852,9 +5374,7 @@ async fn get_season_title_from_api(
                                     debug!("获取到季度标题: {} (尝试次数: {})", title, retry_count + 1);
 
                                     // 缓存番剧标题
-                                    if let Ok(mut cache) = SEASON_TITLE_CACHE.lock() {
-                                        cache.insert(season_id.to_string(), title.to_string());
-                                    }
+                                    SEASON_TITLE_CACHE.insert(season_id, title);
 
                                     return Some(title.to_string());
                                 }
@@ -4172,9 +5692,7 @@ async fn get_season_info_from_api(
         .to_string();
 
     // 缓存番剧标题
-    if let Ok(mut cache) = SEASON_TITLE_CACHE.lock() {
-        cache.insert(season_id.to_string(), title.clone());
-    }
+    SEASON_TITLE_CACHE.insert(season_id, &title);
 
     // 提取API中的丰富元数据
     let alias = result["alias"].as_str().map(|s| s.to_string());
@@ -5568,5 +7086,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_plausible_file_size() {
+        use super::is_plausible_file_size;
+
+        assert!(!is_plausible_file_size(0, 0));
+        assert!(is_plausible_file_size(1, 0));
+        // 10 分钟的分页至少需要 2.4MB
+        assert!(!is_plausible_file_size(1024 * 1024, 600));
+        assert!(is_plausible_file_size(50 * 1024 * 1024, 600));
+    }
+
+    #[test]
+    fn test_upper_base_path() {
+        use std::path::{Path, PathBuf};
+
+        use super::upper_base_path;
+
+        assert_eq!(
+            upper_base_path(Path::new("/config/upper_face"), 12345).unwrap(),
+            PathBuf::from("/config/upper_face/1/12345")
+        );
+    }
+
+    #[test]
+    fn test_record_page_resume_attempt() {
+        use super::{record_page_resume_attempt, MAX_PAGE_RESUME_ATTEMPTS};
+
+        // 使用不会出现在数据库中的分页 ID，避免与其他测试共享记录
+        let page_id = -1;
+        for _ in 0..MAX_PAGE_RESUME_ATTEMPTS {
+            assert!(record_page_resume_attempt(page_id));
+        }
+        assert!(!record_page_resume_attempt(page_id));
+        // 超过上限后记录被清除，重新从头计数
+        assert!(record_page_resume_attempt(page_id));
+    }
+
+    #[test]
+    fn test_strm_url_deadline() {
+        use super::strm_url_deadline;
+
+        assert_eq!(
+            strm_url_deadline(
+                "https://upos-sz-mirrorcos.bilivideo.com/a.mp4?e=ig8e&deadline=1760000000&gen=playurlv2\n"
+            ),
+            Some(1760000000)
+        );
+        assert_eq!(strm_url_deadline("https://example.com/a.mp4"), None);
+        assert_eq!(strm_url_deadline("not a url"), None);
+    }
+
+    #[tokio::test]
+    async fn test_strm_needs_refresh() {
+        use super::{strm_needs_refresh, STRM_REFRESH_MARGIN_SECS};
+
+        let dir = std::env::temp_dir().join(format!("bili-sync-strm-refresh-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let strm_path = dir.join("a.strm");
+        let now = chrono::Utc::now().timestamp();
+        let refresh_before = now + STRM_REFRESH_MARGIN_SECS;
+
+        // 还有较长有效期的地址不需要刷新，即将过期的地址需要刷新
+        let url = |deadline: i64| format!("https://upos-sz-mirrorcos.bilivideo.com/a.mp4?deadline={}", deadline);
+        tokio::fs::write(&strm_path, url(now + 90 * 60)).await.unwrap();
+        assert!(!strm_needs_refresh(&strm_path, refresh_before).await);
+        tokio::fs::write(&strm_path, url(now + 60)).await.unwrap();
+        assert!(strm_needs_refresh(&strm_path, refresh_before).await);
+        // 无法解析过期时间或文件不存在时需要刷新
+        tokio::fs::write(&strm_path, "https://example.com/a.mp4").await.unwrap();
+        assert!(strm_needs_refresh(&strm_path, refresh_before).await);
+        assert!(strm_needs_refresh(&dir.join("missing.strm"), refresh_before).await);
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
     // 旧的87007/87008错误检测测试已清理，现在使用革命性的upower字段检测
 }
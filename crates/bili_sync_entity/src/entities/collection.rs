@@ -16,6 +16,30 @@ pub struct Model {
     pub latest_row_at: String,
     pub enabled: bool,
     pub scan_deleted_videos: bool,
+    pub scan_interval: Option<i64>,
+    pub pubtime_after: Option<String>,
+    pub pubtime_before: Option<String>,
+    pub title_include: Option<String>,
+    pub title_exclude: Option<String>,
+    pub title_regex: bool,
+    pub strm_mode: bool,
+    pub strm_refresh: bool,
+    pub strict_incremental: bool,
+    pub webdav_upload: bool,
+    pub retention_days: Option<i32>,
+    pub retention_max_count: Option<i32>,
+    pub audio_only: bool,
+    pub upgrade_quality: bool,
+    pub danmaku_embed_mode: String,
+    pub download_cover: bool,
+    pub download_nfo: bool,
+    pub download_danmaku: bool,
+    pub download_subtitle: bool,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+    pub limit_newest: Option<i32>,
+    pub nfo_time_type: Option<String>,
+    pub paused: bool,
     pub cover: Option<String>,
 }
 
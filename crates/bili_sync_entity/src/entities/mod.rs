@@ -6,6 +6,7 @@ pub mod collection;
 pub mod config_item;
 pub mod favorite;
 pub mod page;
+pub mod scan_history;
 pub mod submission;
 pub mod task_queue;
 pub mod video;
@@ -17,6 +17,10 @@ pub struct Model {
     pub path: Option<String>,
     pub image: Option<String>,
     pub download_status: u32,
+    pub webdav_upload_status: u32,
+    pub canonical_path: Option<String>,
+    pub downloaded_quality: Option<u32>,
+    pub quality_checked_at: Option<String>,
     pub created_at: String,
 }
 
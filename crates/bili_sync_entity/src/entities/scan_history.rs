@@ -0,0 +1,36 @@
+//! 扫描历史数据库实体
+
+use sea_orm::entity::prelude::*;
+
+/// 每轮扫描中单个视频源的统计记录
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "scan_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// 视频源类型（collection、favorite、submission、watch_later、bangumi）
+    pub source_type: String,
+    /// 视频源ID
+    pub source_id: i32,
+    /// 开始扫描的时间
+    pub started_at: String,
+    /// 扫描耗时（毫秒）
+    pub duration_ms: i64,
+    /// 新增视频数量
+    pub new_videos: i32,
+    /// 新增视频的分页数量
+    pub new_pages: i32,
+    /// 新增视频中下载失败的数量
+    pub failed_videos: i32,
+    /// 因时长不在范围内而被过滤的视频数量
+    pub filtered_videos: i32,
+    /// 因发布时间或标题不满足过滤条件而被跳过的视频数量
+    pub skipped_videos: i32,
+    /// 扫描失败时的错误信息
+    pub error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
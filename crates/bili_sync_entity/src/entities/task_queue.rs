@@ -32,6 +32,11 @@ pub enum TaskStatus {
     Completed,
     #[sea_orm(string_value = "failed")]
     Failed,
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
+    /// 超过最大重试次数，不再自动恢复
+    #[sea_orm(string_value = "dead_letter")]
+    DeadLetter,
 }
 
 /// 任务队列数据库实体
@@ -48,6 +53,8 @@ pub struct Model {
     pub status: TaskStatus,
     /// 重试次数
     pub retry_count: i32,
+    /// 优先级，数值越大越先执行
+    pub priority: i32,
     /// 创建时间
     pub created_at: String,
     /// 更新时间
@@ -42,6 +42,7 @@ pub struct Model {
     pub actors: Option<String>,
     pub auto_download: bool,
     pub cid: Option<i64>,
+    pub user_tags: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +32,27 @@ mod m20250726_000001_unify_time_format;
 mod m20250807_000001_add_video_cid;
 mod m20250914_000001_fix_video_unique_index_for_bangumi;
 mod m20250921_000001_add_collection_cover;
+mod m20261016_000001_add_scan_interval;
+mod m20261016_000002_add_pubtime_window;
+mod m20261016_000003_add_title_filter;
+mod m20261016_000004_add_episode_range;
+mod m20261017_000001_add_strm_mode;
+mod m20261017_000002_add_strict_incremental;
+mod m20261017_000003_add_task_priority;
+mod m20261017_000004_add_webdav_upload;
+mod m20261017_000005_add_retention_policy;
+mod m20261017_000006_add_page_canonical_path;
+mod m20261017_000007_add_audio_only;
+mod m20261017_000008_add_user_tags;
+mod m20261017_000009_add_upgrade_quality;
+mod m20261017_000010_add_danmaku_embed_mode;
+mod m20261017_000011_create_scan_history;
+mod m20261017_000012_add_subtask_toggles;
+mod m20261017_000013_add_source_last_error;
+mod m20261017_000014_add_limit_newest;
+mod m20261017_000015_add_nfo_time_type;
+mod m20261017_000016_add_page_quality_checked_at;
+mod m20261017_000017_add_source_paused;
 
 pub struct Migrator;
 
@@ -71,6 +92,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20250807_000001_add_video_cid::Migration),
             Box::new(m20250914_000001_fix_video_unique_index_for_bangumi::Migration),
             Box::new(m20250921_000001_add_collection_cover::Migration),
+            Box::new(m20261016_000001_add_scan_interval::Migration),
+            Box::new(m20261016_000002_add_pubtime_window::Migration),
+            Box::new(m20261016_000003_add_title_filter::Migration),
+            Box::new(m20261016_000004_add_episode_range::Migration),
+            Box::new(m20261017_000001_add_strm_mode::Migration),
+            Box::new(m20261017_000002_add_strict_incremental::Migration),
+            Box::new(m20261017_000003_add_task_priority::Migration),
+            Box::new(m20261017_000004_add_webdav_upload::Migration),
+            Box::new(m20261017_000005_add_retention_policy::Migration),
+            Box::new(m20261017_000006_add_page_canonical_path::Migration),
+            Box::new(m20261017_000007_add_audio_only::Migration),
+            Box::new(m20261017_000008_add_user_tags::Migration),
+            Box::new(m20261017_000009_add_upgrade_quality::Migration),
+            Box::new(m20261017_000010_add_danmaku_embed_mode::Migration),
+            Box::new(m20261017_000011_create_scan_history::Migration),
+            Box::new(m20261017_000012_add_subtask_toggles::Migration),
+            Box::new(m20261017_000013_add_source_last_error::Migration),
+            Box::new(m20261017_000014_add_limit_newest::Migration),
+            Box::new(m20261017_000015_add_nfo_time_type::Migration),
+            Box::new(m20261017_000016_add_page_quality_checked_at::Migration),
+            Box::new(m20261017_000017_add_source_paused::Migration),
         ]
     }
 }
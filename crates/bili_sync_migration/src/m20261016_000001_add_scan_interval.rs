@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为各种视频源表添加 scan_interval 字段，为空表示使用全局扫描间隔
+
+        // 合集表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .add_column(ColumnDef::new(Collection::ScanInterval).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 收藏夹表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .add_column(ColumnDef::new(Favorite::ScanInterval).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 投稿表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .add_column(ColumnDef::new(Submission::ScanInterval).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 稍后观看表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .add_column(ColumnDef::new(WatchLater::ScanInterval).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 视频源表（番剧）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .add_column(ColumnDef::new(VideoSource::ScanInterval).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .drop_column(Collection::ScanInterval)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .drop_column(Favorite::ScanInterval)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .drop_column(Submission::ScanInterval)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .drop_column(WatchLater::ScanInterval)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .drop_column(VideoSource::ScanInterval)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    ScanInterval,
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    ScanInterval,
+}
+
+#[derive(DeriveIden)]
+enum Submission {
+    Table,
+    ScanInterval,
+}
+
+#[derive(DeriveIden)]
+enum WatchLater {
+    Table,
+    ScanInterval,
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    ScanInterval,
+}
@@ -0,0 +1,228 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为各种视频源表添加发布时间窗口字段（YYYY-MM-DD），为空表示不限制
+
+        // 合集表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .add_column(ColumnDef::new(Collection::PubtimeAfter).string().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .add_column(ColumnDef::new(Collection::PubtimeBefore).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 收藏夹表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .add_column(ColumnDef::new(Favorite::PubtimeAfter).string().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .add_column(ColumnDef::new(Favorite::PubtimeBefore).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 投稿表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .add_column(ColumnDef::new(Submission::PubtimeAfter).string().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .add_column(ColumnDef::new(Submission::PubtimeBefore).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 稍后观看表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .add_column(ColumnDef::new(WatchLater::PubtimeAfter).string().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .add_column(ColumnDef::new(WatchLater::PubtimeBefore).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 视频源表（番剧）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .add_column(ColumnDef::new(VideoSource::PubtimeAfter).string().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .add_column(ColumnDef::new(VideoSource::PubtimeBefore).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .drop_column(Collection::PubtimeAfter)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .drop_column(Collection::PubtimeBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .drop_column(Favorite::PubtimeAfter)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .drop_column(Favorite::PubtimeBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .drop_column(Submission::PubtimeAfter)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .drop_column(Submission::PubtimeBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .drop_column(WatchLater::PubtimeAfter)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .drop_column(WatchLater::PubtimeBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .drop_column(VideoSource::PubtimeAfter)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .drop_column(VideoSource::PubtimeBefore)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    PubtimeAfter,
+    PubtimeBefore,
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    PubtimeAfter,
+    PubtimeBefore,
+}
+
+#[derive(DeriveIden)]
+enum Submission {
+    Table,
+    PubtimeAfter,
+    PubtimeBefore,
+}
+
+#[derive(DeriveIden)]
+enum WatchLater {
+    Table,
+    PubtimeAfter,
+    PubtimeBefore,
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    PubtimeAfter,
+    PubtimeBefore,
+}
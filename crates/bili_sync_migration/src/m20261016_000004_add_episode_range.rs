@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为番剧源添加集数范围字段，如 "1-12,25"，为空表示下载全部集数
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .add_column(ColumnDef::new(VideoSource::EpisodeRange).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .drop_column(VideoSource::EpisodeRange)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    EpisodeRange,
+}
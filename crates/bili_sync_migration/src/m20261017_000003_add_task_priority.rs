@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为任务队列表添加 priority 字段，数值越大越先执行
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskQueue::Table)
+                    .add_column(ColumnDef::new(TaskQueue::Priority).integer().not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已存在的重载配置任务使用最高优先级
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(TaskQueue::Table)
+                    .value(TaskQueue::Priority, i32::MAX)
+                    .and_where(Expr::col(TaskQueue::TaskType).eq("reload_config"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskQueue::Table)
+                    .drop_column(TaskQueue::Priority)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskQueue {
+    Table,
+    TaskType,
+    Priority,
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 分页通过链接复用其他视频源已下载的文件时，记录原始文件路径
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Page::Table)
+                    .add_column(ColumnDef::new(Page::CanonicalPath).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 按 cid 查找已下载的相同分页
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_page_cid")
+                    .table(Page::Table)
+                    .col(Page::Cid)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_page_cid")
+                    .table(Page::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Page::Table)
+                    .drop_column(Page::CanonicalPath)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Page {
+    Table,
+    Cid,
+    CanonicalPath,
+}
@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为各种视频源表添加 danmaku_embed_mode 字段，控制是否将弹幕以字幕轨道（soft）或烧录（hard）的方式嵌入视频
+
+        // 合集表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .add_column(
+                        ColumnDef::new(Collection::DanmakuEmbedMode)
+                            .string()
+                            .not_null()
+                            .default("none"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 收藏夹表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .add_column(
+                        ColumnDef::new(Favorite::DanmakuEmbedMode)
+                            .string()
+                            .not_null()
+                            .default("none"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 投稿表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .add_column(
+                        ColumnDef::new(Submission::DanmakuEmbedMode)
+                            .string()
+                            .not_null()
+                            .default("none"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 稍后观看表
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .add_column(
+                        ColumnDef::new(WatchLater::DanmakuEmbedMode)
+                            .string()
+                            .not_null()
+                            .default("none"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 视频源表（番剧）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .add_column(
+                        ColumnDef::new(VideoSource::DanmakuEmbedMode)
+                            .string()
+                            .not_null()
+                            .default("none"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Collection::Table)
+                    .drop_column(Collection::DanmakuEmbedMode)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Favorite::Table)
+                    .drop_column(Favorite::DanmakuEmbedMode)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Submission::Table)
+                    .drop_column(Submission::DanmakuEmbedMode)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WatchLater::Table)
+                    .drop_column(WatchLater::DanmakuEmbedMode)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VideoSource::Table)
+                    .drop_column(VideoSource::DanmakuEmbedMode)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    DanmakuEmbedMode,
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    DanmakuEmbedMode,
+}
+
+#[derive(DeriveIden)]
+enum Submission {
+    Table,
+    DanmakuEmbedMode,
+}
+
+#[derive(DeriveIden)]
+enum WatchLater {
+    Table,
+    DanmakuEmbedMode,
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    DanmakuEmbedMode,
+}
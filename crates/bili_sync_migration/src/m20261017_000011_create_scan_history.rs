@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建扫描历史表，记录每轮扫描中每个视频源的新增、失败、跳过数量与耗时
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScanHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ScanHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ScanHistory::SourceType).string_len(20).not_null())
+                    .col(ColumnDef::new(ScanHistory::SourceId).integer().not_null())
+                    .col(ColumnDef::new(ScanHistory::StartedAt).string().not_null())
+                    .col(
+                        ColumnDef::new(ScanHistory::DurationMs)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ScanHistory::NewVideos).integer().not_null().default(0))
+                    .col(ColumnDef::new(ScanHistory::NewPages).integer().not_null().default(0))
+                    .col(
+                        ColumnDef::new(ScanHistory::FailedVideos)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ScanHistory::FilteredVideos)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ScanHistory::SkippedVideos)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ScanHistory::Error).text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 按视频源查询最近的扫描记录
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_scan_history_source")
+                    .table(ScanHistory::Table)
+                    .col(ScanHistory::SourceType)
+                    .col(ScanHistory::SourceId)
+                    .col(ScanHistory::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_scan_history_source")
+                    .table(ScanHistory::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ScanHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScanHistory {
+    Table,
+    Id,
+    SourceType,
+    SourceId,
+    StartedAt,
+    DurationMs,
+    NewVideos,
+    NewPages,
+    FailedVideos,
+    FilteredVideos,
+    SkippedVideos,
+    Error,
+}
@@ -0,0 +1,204 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为各种视频源表添加封面、NFO、弹幕、字幕子任务的开关，默认全部开启
+        // SQLite 的 ALTER TABLE 每次只能添加一列
+
+        // 合集表
+        for column in [
+            Collection::DownloadCover,
+            Collection::DownloadNfo,
+            Collection::DownloadDanmaku,
+            Collection::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Collection::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 收藏夹表
+        for column in [
+            Favorite::DownloadCover,
+            Favorite::DownloadNfo,
+            Favorite::DownloadDanmaku,
+            Favorite::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Favorite::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 投稿表
+        for column in [
+            Submission::DownloadCover,
+            Submission::DownloadNfo,
+            Submission::DownloadDanmaku,
+            Submission::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Submission::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 稍后观看表
+        for column in [
+            WatchLater::DownloadCover,
+            WatchLater::DownloadNfo,
+            WatchLater::DownloadDanmaku,
+            WatchLater::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(WatchLater::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 视频源表（番剧）
+        for column in [
+            VideoSource::DownloadCover,
+            VideoSource::DownloadNfo,
+            VideoSource::DownloadDanmaku,
+            VideoSource::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(VideoSource::Table)
+                        .add_column(ColumnDef::new(column).boolean().not_null().default(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        for column in [
+            Collection::DownloadCover,
+            Collection::DownloadNfo,
+            Collection::DownloadDanmaku,
+            Collection::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(Table::alter().table(Collection::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [
+            Favorite::DownloadCover,
+            Favorite::DownloadNfo,
+            Favorite::DownloadDanmaku,
+            Favorite::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(Table::alter().table(Favorite::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [
+            Submission::DownloadCover,
+            Submission::DownloadNfo,
+            Submission::DownloadDanmaku,
+            Submission::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(Table::alter().table(Submission::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [
+            WatchLater::DownloadCover,
+            WatchLater::DownloadNfo,
+            WatchLater::DownloadDanmaku,
+            WatchLater::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(Table::alter().table(WatchLater::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [
+            VideoSource::DownloadCover,
+            VideoSource::DownloadNfo,
+            VideoSource::DownloadDanmaku,
+            VideoSource::DownloadSubtitle,
+        ] {
+            manager
+                .alter_table(Table::alter().table(VideoSource::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    DownloadCover,
+    DownloadNfo,
+    DownloadDanmaku,
+    DownloadSubtitle,
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    DownloadCover,
+    DownloadNfo,
+    DownloadDanmaku,
+    DownloadSubtitle,
+}
+
+#[derive(DeriveIden)]
+enum Submission {
+    Table,
+    DownloadCover,
+    DownloadNfo,
+    DownloadDanmaku,
+    DownloadSubtitle,
+}
+
+#[derive(DeriveIden)]
+enum WatchLater {
+    Table,
+    DownloadCover,
+    DownloadNfo,
+    DownloadDanmaku,
+    DownloadSubtitle,
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    DownloadCover,
+    DownloadNfo,
+    DownloadDanmaku,
+    DownloadSubtitle,
+}
@@ -0,0 +1,144 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 为各种视频源表添加最近一次扫描失败的原因与时间，扫描成功后清空
+        // SQLite 的 ALTER TABLE 每次只能添加一列
+
+        // 合集表
+        for column in [Collection::LastError, Collection::LastErrorAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Collection::Table)
+                        .add_column(ColumnDef::new(column).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 收藏夹表
+        for column in [Favorite::LastError, Favorite::LastErrorAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Favorite::Table)
+                        .add_column(ColumnDef::new(column).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 投稿表
+        for column in [Submission::LastError, Submission::LastErrorAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Submission::Table)
+                        .add_column(ColumnDef::new(column).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 稍后观看表
+        for column in [WatchLater::LastError, WatchLater::LastErrorAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(WatchLater::Table)
+                        .add_column(ColumnDef::new(column).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 视频源表（番剧）
+        for column in [VideoSource::LastError, VideoSource::LastErrorAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(VideoSource::Table)
+                        .add_column(ColumnDef::new(column).text().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 回滚时删除字段
+        for column in [Collection::LastError, Collection::LastErrorAt] {
+            manager
+                .alter_table(Table::alter().table(Collection::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [Favorite::LastError, Favorite::LastErrorAt] {
+            manager
+                .alter_table(Table::alter().table(Favorite::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [Submission::LastError, Submission::LastErrorAt] {
+            manager
+                .alter_table(Table::alter().table(Submission::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [WatchLater::LastError, WatchLater::LastErrorAt] {
+            manager
+                .alter_table(Table::alter().table(WatchLater::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [VideoSource::LastError, VideoSource::LastErrorAt] {
+            manager
+                .alter_table(Table::alter().table(VideoSource::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Collection {
+    Table,
+    LastError,
+    LastErrorAt,
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    LastError,
+    LastErrorAt,
+}
+
+#[derive(DeriveIden)]
+enum Submission {
+    Table,
+    LastError,
+    LastErrorAt,
+}
+
+#[derive(DeriveIden)]
+enum WatchLater {
+    Table,
+    LastError,
+    LastErrorAt,
+}
+
+#[derive(DeriveIden)]
+enum VideoSource {
+    Table,
+    LastError,
+    LastErrorAt,
+}